@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use node_data::bls::{PublicKey, PublicKeyBytes};
 use node_data::ledger::{
-    Block, Fault, Header, Slash, SpentTransaction, Transaction,
+    Block, Fault, Header, Seed, Slash, SpentTransaction, Transaction,
 };
 use node_data::StepName;
 
@@ -27,6 +27,8 @@ pub struct CallParams {
     pub voters_pubkey: Vec<Voter>,
     pub max_txs_bytes: usize,
     pub prev_state_root: StateRoot,
+    pub prev_seed: Seed,
+    pub timestamp: u64,
 }
 
 #[derive(Default)]
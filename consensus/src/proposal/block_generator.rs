@@ -111,6 +111,13 @@ impl<T: Operations> Generator<T> {
         let max_txs_bytes = MAX_BLOCK_SIZE - header_size - faults_size;
         let voters = ru.att_voters();
 
+        // The timestamp is set before executing the transactions, since it is
+        // made available to the contracts.
+        blk_header.timestamp = max(
+            ru.timestamp() + *MINIMUM_BLOCK_TIME,
+            get_current_timestamp(),
+        );
+
         let call_params = CallParams {
             round: ru.round,
             generator_pubkey: ru.pubkey_bls.clone(),
@@ -118,6 +125,8 @@ impl<T: Operations> Generator<T> {
             voters_pubkey: voters.to_owned(),
             max_txs_bytes,
             prev_state_root: ru.state_root(),
+            prev_seed: ru.seed(),
+            timestamp: blk_header.timestamp,
         };
 
         let result =
@@ -131,11 +140,6 @@ impl<T: Operations> Generator<T> {
         let txs: Vec<_> = result.txs.into_iter().map(|t| t.inner).collect();
         blk_header.txroot = merkle_root(&tx_digests[..]);
 
-        blk_header.timestamp = max(
-            ru.timestamp() + *MINIMUM_BLOCK_TIME,
-            get_current_timestamp(),
-        );
-
         match Block::new(blk_header, txs, faults.to_vec()) {
             Ok(blk) => {
                 info!(
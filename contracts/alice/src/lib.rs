@@ -19,7 +19,9 @@ mod wasm {
     use super::*;
     use state::Alice;
 
-    static mut STATE: Alice = state::Alice;
+    static mut STATE: Alice = Alice::new();
+
+    dusk_core::query_trampoline!();
 
     #[no_mangle]
     unsafe fn ping(arg_len: u32) -> u32 {
@@ -45,4 +47,39 @@ mod wasm {
     unsafe fn contract_to_account(arg_len: u32) -> u32 {
         abi::wrap_call(arg_len, |arg| STATE.contract_to_account(arg))
     }
+
+    #[no_mangle]
+    unsafe fn approved_deposit(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |arg| STATE.approved_deposit(arg))
+    }
+
+    #[no_mangle]
+    unsafe fn subscribe_and_emit(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |topic| STATE.subscribe_and_emit(topic))
+    }
+
+    #[no_mangle]
+    unsafe fn on_event(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |delivery| STATE.on_event(delivery))
+    }
+
+    #[no_mangle]
+    unsafe fn deliveries(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |()| STATE.deliveries())
+    }
+
+    #[no_mangle]
+    unsafe fn query_echo(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |arg| STATE.query_echo(arg))
+    }
+
+    #[no_mangle]
+    unsafe fn query_reset(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |arg| STATE.query_reset(arg))
+    }
+
+    #[no_mangle]
+    unsafe fn metadata_of(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |contract| STATE.metadata_of(contract))
+    }
 }
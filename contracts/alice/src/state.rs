@@ -4,17 +4,34 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use dusk_core::abi;
+use alloc::string::String;
+
+use dusk_core::abi::{
+    self, ContractError, ContractFailure, ContractId, EventDelivery,
+};
 use dusk_core::transfer::{
-    withdraw::Withdraw, ContractToAccount, ContractToContract,
+    withdraw::Withdraw, ApprovedDeposit, ContractToAccount, ContractToContract,
     TRANSFER_CONTRACT,
 };
 
 /// Alice contract.
+/// The topic of the events whose delivery fails.
+const FAILING_TOPIC: &str = "fail";
+
 #[derive(Debug, Clone)]
-pub struct Alice;
+pub struct Alice {
+    deliveries: u64,
+    deliverer: Option<ContractId>,
+}
 
 impl Alice {
+    pub const fn new() -> Self {
+        Self {
+            deliveries: 0,
+            deliverer: None,
+        }
+    }
+
     pub fn ping(&mut self) {
         // no-op
     }
@@ -36,7 +53,63 @@ impl Alice {
     }
 
     pub fn contract_to_account(&mut self, transfer: ContractToAccount) {
-        abi::call::<_, ()>(TRANSFER_CONTRACT, "contract_to_account", &transfer)
-            .expect("Transferring to account should succeed");
+        forward_failure(
+            abi::call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "contract_to_account",
+                &transfer,
+            ),
+            "Transferring to account should succeed",
+        );
+    }
+
+    pub fn approved_deposit(&mut self, deposit: ApprovedDeposit) {
+        forward_failure(
+            abi::call::<_, ()>(TRANSFER_CONTRACT, "approved_deposit", &deposit),
+            "Approved deposit should succeed",
+        );
+    }
+
+    pub fn subscribe_and_emit(&mut self, topic: String) {
+        abi::on_event(abi::self_id(), topic.clone());
+        abi::emit(&topic, ());
+    }
+
+    pub fn on_event(&mut self, delivery: EventDelivery) {
+        self.deliveries += 1;
+        self.deliverer = abi::caller();
+        if delivery.topic == FAILING_TOPIC {
+            panic!("Failing delivery");
+        }
+    }
+
+    pub fn deliveries(&self) -> (u64, Option<ContractId>) {
+        (self.deliveries, self.deliverer)
     }
+
+    pub fn query_echo(&mut self, (contract, n): (ContractId, u64)) -> u64 {
+        abi::call_query(contract, "echo", &n)
+            .expect("Querying echo should succeed")
+    }
+
+    pub fn query_reset(&mut self, (contract, n): (ContractId, u8)) {
+        let _: () = abi::call_query(contract, "reset", &n)
+            .expect("Querying reset should succeed");
+    }
+
+    pub fn metadata_of(
+        &mut self,
+        contract: ContractId,
+    ) -> Option<abi::ContractMetadata> {
+        abi::contract_metadata(contract)
+    }
+}
+
+/// Unwraps the result of a call, failing with the same failure as the callee
+/// if it failed with `abi::fail`, so that the failure reaches the caller.
+fn forward_failure<T>(result: Result<T, ContractError>, msg: &str) -> T {
+    result.unwrap_or_else(|err| match ContractFailure::from_error(&err) {
+        Some(failure) => abi::fail(failure.code, &failure.message),
+        None => panic!("{msg}"),
+    })
 }
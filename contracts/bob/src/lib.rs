@@ -32,6 +32,11 @@ mod wasm {
         abi::wrap_call(arg_len, |n| STATE.reset(n))
     }
 
+    #[no_mangle]
+    unsafe fn __metadata(arg_len: u32) -> u32 {
+        abi::wrap_call(arg_len, |()| STATE.metadata())
+    }
+
     #[no_mangle]
     unsafe fn owner_reset(arg_len: u32) -> u32 {
         abi::wrap_call(arg_len, |(sig, msg)| STATE.owner_reset(sig, msg))
@@ -57,6 +57,13 @@ impl Bob {
         self.value = n;
     }
 
+    /// Returns the metadata of bob, resetting its value on the way, to check
+    /// that the metadata is queried without allowing changes.
+    pub fn metadata(&mut self) -> abi::ContractMetadata {
+        self.value = 0;
+        abi::metadata_entry("bob", "0.1.0", None)
+    }
+
     pub fn owner_reset(&mut self, sig: BlsSignature, msg: OwnerMessage) {
         let mut granted = false;
         let message_bytes = rkyv::to_bytes::<_, 4096>(&msg)
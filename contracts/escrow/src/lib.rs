@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use dusk_core::abi;
+
+mod state;
+use state::EscrowState;
+
+static mut STATE: EscrowState = EscrowState::new();
+
+// Transactions
+
+#[no_mangle]
+unsafe fn create(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.create(arg))
+}
+
+#[no_mangle]
+unsafe fn release(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |escrow_id| STATE.release(escrow_id))
+}
+
+#[no_mangle]
+unsafe fn refund(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |escrow_id| STATE.refund(escrow_id))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn escrow(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |escrow_id| STATE.escrow(escrow_id).cloned())
+}
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::escrow::{
+    CreateEscrow, CreateEvent, EscrowData, EscrowId, SettleEvent, CREATE_TOPIC,
+    REFUND_TOPIC, RELEASE_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+
+/// Contract holding funds in escrow between a payer and a payee.
+///
+/// Escrows are funded with the deposit of the transaction creating them, and
+/// removed once settled.
+#[derive(Debug, Default, Clone)]
+pub struct EscrowState {
+    next_id: EscrowId,
+    escrows: BTreeMap<EscrowId, EscrowData>,
+}
+
+impl EscrowState {
+    pub const fn new() -> Self {
+        Self {
+            next_id: 0,
+            escrows: BTreeMap::new(),
+        }
+    }
+
+    /// Create an escrow funded with the deposit of the calling transaction.
+    pub fn create(&mut self, escrow: CreateEscrow) -> EscrowId {
+        let payer = public_sender();
+        let value = escrow.value;
+
+        if value == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "An escrow should be funded with a deposit",
+            );
+        }
+
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing the escrowed funds should succeed");
+
+        let escrow_id = self.next_id;
+        self.next_id += 1;
+
+        self.escrows.insert(
+            escrow_id,
+            EscrowData {
+                payer,
+                payee: escrow.payee,
+                arbiter: escrow.arbiter,
+                value,
+                timeout: escrow.timeout,
+            },
+        );
+
+        abi::emit(
+            CREATE_TOPIC,
+            CreateEvent {
+                escrow_id,
+                payer,
+                payee: escrow.payee,
+                value,
+            },
+        );
+
+        escrow_id
+    }
+
+    /// Release the funds of an escrow to the payee.
+    ///
+    /// Only the payer or the arbiter can release the funds.
+    pub fn release(&mut self, escrow_id: EscrowId) {
+        let settler = public_sender();
+        let escrow = self.escrow_ref(escrow_id);
+
+        if settler != escrow.payer && Some(settler) != escrow.arbiter {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the payer or the arbiter can release the funds",
+            );
+        }
+
+        let receiver = escrow.payee;
+        self.settle(escrow_id, settler, receiver, RELEASE_TOPIC);
+    }
+
+    /// Refund the funds of an escrow to the payer.
+    ///
+    /// Only the payee or the arbiter can refund the funds, or the payer once
+    /// the timeout height is reached.
+    pub fn refund(&mut self, escrow_id: EscrowId) {
+        let settler = public_sender();
+        let escrow = self.escrow_ref(escrow_id);
+
+        let timed_out = abi::block_height() >= escrow.timeout;
+        if settler != escrow.payee
+            && Some(settler) != escrow.arbiter
+            && !(settler == escrow.payer && timed_out)
+        {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the payee or the arbiter can refund the funds before the timeout",
+            );
+        }
+
+        let receiver = escrow.payer;
+        self.settle(escrow_id, settler, receiver, REFUND_TOPIC);
+    }
+
+    /// Removes the escrow and pays its funds to the receiver.
+    fn settle(
+        &mut self,
+        escrow_id: EscrowId,
+        settler: AccountPublicKey,
+        receiver: AccountPublicKey,
+        topic: &'static str,
+    ) {
+        let escrow = self
+            .escrows
+            .remove(&escrow_id)
+            .expect("The escrow should exist");
+        let value = escrow.value;
+
+        let _: () = abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_account",
+            &ContractToAccount {
+                account: receiver,
+                value,
+            },
+        )
+        .expect("Paying out of the contract's balance should succeed");
+
+        abi::emit(
+            topic,
+            SettleEvent {
+                escrow_id,
+                settler,
+                receiver,
+                value,
+            },
+        );
+    }
+
+    fn escrow_ref(&self, escrow_id: EscrowId) -> &EscrowData {
+        match self.escrows.get(&escrow_id) {
+            Some(escrow) => escrow,
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The escrow doesn't exist",
+            ),
+        }
+    }
+
+    /// The data of the given escrow, if it is not settled.
+    pub fn escrow(&self, escrow_id: EscrowId) -> Option<&EscrowData> {
+        self.escrows.get(&escrow_id)
+    }
+}
+
+/// The public sender of the calling transaction.
+///
+/// # Panics
+/// When the contract is called by a shielded transaction.
+fn public_sender() -> AccountPublicKey {
+    match abi::public_sender() {
+        Some(pk) => pk,
+        None => abi::fail(
+            ContractFailure::UNAUTHORIZED,
+            "Escrows can only be managed by a public account",
+        ),
+    }
+}
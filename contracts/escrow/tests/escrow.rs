@@ -0,0 +1,261 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ContractId, ARGBUF_LEN};
+use dusk_core::escrow::{CreateEscrow, EscrowData, EscrowId};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const ESCROW_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x24;
+    ContractId::from_bytes(bytes)
+};
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+const CONFIG: ExecutionConfig = ExecutionConfig {
+    with_public_sender: true,
+    ..ExecutionConfig::DEFAULT
+};
+
+/// Deploy the transfer and escrow contracts, with the given moonlight
+/// accounts owning the `MOONLIGHT_GENESIS_VALUE`, returning the commit to
+/// start sessions from.
+fn instantiate(vm: &mut VM, moonlight_pks: &[AccountPublicKey]) -> [u8; 32] {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let escrow_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/escrow_contract.wasm"
+    );
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    session
+        .deploy(
+            escrow_bytecode,
+            ContractData::builder().owner(OWNER).contract_id(ESCROW_ID),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the escrow contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    session.commit().expect("Committing should succeed")
+}
+
+/// Commit the given session and start a new one at the given block height.
+fn advance(vm: &VM, session: Session, height: u64) -> Session {
+    let base = session.commit().expect("Committing should succeed");
+    vm.session(base, CHAIN_ID, height)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the escrow contract from the given moonlight account, depositing
+/// the given value, and returning whether the call succeeded.
+fn call_escrow(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    deposit: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(ESCROW_ID, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        deposit,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt =
+        execute(session, &tx, &CONFIG).expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn moonlight_balance(session: &mut Session, pk: &AccountPublicKey) -> u64 {
+    session
+        .call::<_, AccountData>(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
+        .expect("Querying the account should succeed")
+        .data
+        .balance
+}
+
+fn escrow_balance(session: &mut Session) -> u64 {
+    session
+        .call(TRANSFER_CONTRACT, "contract_balance", &ESCROW_ID, GAS_LIMIT)
+        .expect("Querying the contract balance should succeed")
+        .data
+}
+
+fn escrow(session: &mut Session, escrow_id: EscrowId) -> Option<EscrowData> {
+    session
+        .call(ESCROW_ID, "escrow", &escrow_id, GAS_LIMIT)
+        .expect("Querying the escrow should succeed")
+        .data
+}
+
+#[test]
+fn arbiter_release() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let payer_sk = AccountSecretKey::random(rng);
+    let payer_pk = AccountPublicKey::from(&payer_sk);
+    let payee_sk = AccountSecretKey::random(rng);
+    let payee_pk = AccountPublicKey::from(&payee_sk);
+    let arbiter_sk = AccountSecretKey::random(rng);
+    let arbiter_pk = AccountPublicKey::from(&arbiter_sk);
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+    let base = instantiate(vm, &[payer_pk, payee_pk, arbiter_pk]);
+    let session = &mut vm
+        .session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed");
+
+    const VALUE: u64 = dusk(100.0);
+    const ESCROW: EscrowId = 0;
+
+    let create = CreateEscrow {
+        payee: payee_pk,
+        arbiter: Some(arbiter_pk),
+        value: VALUE,
+        timeout: 1_000,
+    };
+
+    // the deposit must match the escrowed value
+    assert!(!call_escrow(
+        session,
+        &payer_sk,
+        1,
+        VALUE - 1,
+        "create",
+        &create
+    ));
+    assert!(call_escrow(session, &payer_sk, 2, VALUE, "create", &create));
+    assert_eq!(escrow_balance(session), VALUE);
+
+    // the payee can't release the funds to themselves
+    assert!(!call_escrow(session, &payee_sk, 1, 0, "release", &ESCROW));
+
+    let payee_balance = moonlight_balance(session, &payee_pk);
+    assert!(call_escrow(session, &arbiter_sk, 1, 0, "release", &ESCROW));
+    assert_eq!(moonlight_balance(session, &payee_pk), payee_balance + VALUE);
+    assert_eq!(escrow_balance(session), 0);
+    assert_eq!(escrow(session, ESCROW), None);
+
+    // a settled escrow can't be settled again
+    assert!(!call_escrow(session, &arbiter_sk, 2, 0, "refund", &ESCROW));
+}
+
+#[test]
+fn timeout_refund() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let payer_sk = AccountSecretKey::random(rng);
+    let payer_pk = AccountPublicKey::from(&payer_sk);
+    let payee_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+    let base = instantiate(vm, &[payer_pk]);
+    let mut session = vm
+        .session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed");
+
+    const VALUE: u64 = dusk(100.0);
+    const TIMEOUT: u64 = 100;
+    const ESCROW: EscrowId = 0;
+
+    let create = CreateEscrow {
+        payee: payee_pk,
+        arbiter: None,
+        value: VALUE,
+        timeout: TIMEOUT,
+    };
+    assert!(call_escrow(
+        &mut session,
+        &payer_sk,
+        1,
+        VALUE,
+        "create",
+        &create
+    ));
+
+    // the payer can't refund themselves before the timeout
+    assert!(!call_escrow(
+        &mut session,
+        &payer_sk,
+        2,
+        0,
+        "refund",
+        &ESCROW
+    ));
+    assert_eq!(escrow_balance(&mut session), VALUE);
+
+    let mut session = advance(vm, session, TIMEOUT);
+    assert!(call_escrow(
+        &mut session,
+        &payer_sk,
+        3,
+        0,
+        "refund",
+        &ESCROW
+    ));
+    assert_eq!(escrow_balance(&mut session), 0);
+    assert_eq!(escrow(&mut session, ESCROW), None);
+    assert_eq!(moonlight_balance(&mut session, &payee_pk), 0);
+}
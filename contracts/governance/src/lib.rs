@@ -0,0 +1,68 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use dusk_core::abi;
+
+mod state;
+use state::GovernanceState;
+
+static mut STATE: GovernanceState = GovernanceState::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn propose(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.propose(arg))
+}
+
+#[no_mangle]
+unsafe fn vote(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.vote(arg))
+}
+
+#[no_mangle]
+unsafe fn execute(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |proposal_id| STATE.execute(proposal_id))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn proposal(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |proposal_id| STATE.proposal(proposal_id).cloned())
+}
+
+#[no_mangle]
+unsafe fn parameter(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |key: String| STATE.parameter(&key).cloned())
+}
+
+#[no_mangle]
+unsafe fn voting_period(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.voting_period())
+}
+
+#[no_mangle]
+unsafe fn quorum(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.quorum())
+}
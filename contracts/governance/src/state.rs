@@ -0,0 +1,249 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::governance::{
+    ExecuteEvent, GovernanceInit, ProposalData, ProposalId, Propose,
+    ProposeEvent, Vote, VoteEvent, EXECUTE_TOPIC, PROPOSE_TOPIC, VOTE_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::stake::{StakeData, STAKE_CONTRACT};
+
+/// Contract holding parameters changed by stake-weighted votes.
+///
+/// The stake of a voter is read when the vote is cast. Each account can vote
+/// once per proposal.
+#[derive(Debug, Default, Clone)]
+pub struct GovernanceState {
+    voting_period: u64,
+    quorum: u64,
+    parameters: BTreeMap<String, Vec<u8>>,
+    next_id: ProposalId,
+    proposals: BTreeMap<ProposalId, ProposalData>,
+    votes: BTreeMap<(ProposalId, Vec<u8>), bool>,
+}
+
+impl GovernanceState {
+    pub const fn new() -> Self {
+        Self {
+            voting_period: 0,
+            quorum: 0,
+            parameters: BTreeMap::new(),
+            next_id: 0,
+            proposals: BTreeMap::new(),
+            votes: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: GovernanceInit) {
+        if self.voting_period != 0 {
+            panic!("The governance is already initialized");
+        }
+        if init.voting_period == 0 {
+            panic!("The voting period should not be empty");
+        }
+
+        self.voting_period = init.voting_period;
+        self.quorum = init.quorum;
+        for change in init.parameters {
+            self.parameters.insert(change.key, change.value);
+        }
+    }
+
+    /// Submit a proposal, open to votes for the voting period.
+    ///
+    /// Only accounts with an eligible stake can submit proposals.
+    pub fn propose(&mut self, propose: Propose) -> ProposalId {
+        let proposer = public_sender();
+        if stake_weight(&proposer) == 0 {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only stakers can submit proposals",
+            );
+        }
+
+        let proposal_id = self.next_id;
+        self.next_id += 1;
+
+        let end = abi::block_height() + self.voting_period;
+        self.proposals.insert(
+            proposal_id,
+            ProposalData {
+                proposer,
+                description: propose.description,
+                changes: propose.changes,
+                end,
+                yes: 0,
+                no: 0,
+                executed: false,
+            },
+        );
+
+        abi::emit(
+            PROPOSE_TOPIC,
+            ProposeEvent {
+                proposal_id,
+                proposer,
+                end,
+            },
+        );
+
+        proposal_id
+    }
+
+    /// Vote on a proposal during its voting period, with the eligible stake
+    /// of the sender.
+    pub fn vote(&mut self, vote: Vote) {
+        let voter = public_sender();
+        let proposal_id = vote.proposal_id;
+
+        let weight = stake_weight(&voter);
+        if weight == 0 {
+            abi::fail(ContractFailure::UNAUTHORIZED, "Only stakers can vote");
+        }
+
+        let height = abi::block_height();
+        let proposal = match self.proposals.get_mut(&proposal_id) {
+            Some(proposal) if height < proposal.end => proposal,
+            Some(_) => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The voting period is over",
+            ),
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal doesn't exist",
+            ),
+        };
+
+        let vote_key = (proposal_id, voter.to_raw_bytes().to_vec());
+        if self.votes.contains_key(&vote_key) {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The account already voted on the proposal",
+            );
+        }
+
+        if vote.approve {
+            proposal.yes = proposal.yes.saturating_add(weight);
+        } else {
+            proposal.no = proposal.no.saturating_add(weight);
+        }
+        self.votes.insert(vote_key, vote.approve);
+
+        abi::emit(
+            VOTE_TOPIC,
+            VoteEvent {
+                proposal_id,
+                voter,
+                approve: vote.approve,
+                weight,
+            },
+        );
+    }
+
+    /// Execute an approved proposal once its voting period is over, applying
+    /// its parameter changes.
+    ///
+    /// Anyone can execute an approved proposal.
+    pub fn execute(&mut self, proposal_id: ProposalId) {
+        let height = abi::block_height();
+        let quorum = self.quorum;
+
+        let proposal = match self.proposals.get_mut(&proposal_id) {
+            Some(proposal) => proposal,
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal doesn't exist",
+            ),
+        };
+        if height < proposal.end {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The voting period is not over",
+            );
+        }
+        if proposal.executed {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal was already executed",
+            );
+        }
+        if !proposal.is_approved(quorum) {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal was not approved",
+            );
+        }
+
+        proposal.executed = true;
+        let changes = proposal.changes.clone();
+
+        for change in &changes {
+            self.parameters
+                .insert(change.key.clone(), change.value.clone());
+        }
+
+        abi::emit(
+            EXECUTE_TOPIC,
+            ExecuteEvent {
+                proposal_id,
+                changes,
+            },
+        );
+    }
+
+    /// The given proposal, if it exists.
+    pub fn proposal(&self, proposal_id: ProposalId) -> Option<&ProposalData> {
+        self.proposals.get(&proposal_id)
+    }
+
+    /// The value of the given parameter, if it is set.
+    pub fn parameter(&self, key: &str) -> Option<&Vec<u8>> {
+        self.parameters.get(key)
+    }
+
+    /// The number of blocks a proposal can be voted on.
+    pub fn voting_period(&self) -> u64 {
+        self.voting_period
+    }
+
+    /// The minimum stake that must vote on a proposal for it to be approved.
+    pub fn quorum(&self) -> u64 {
+        self.quorum
+    }
+}
+
+/// The stake of the given account that is eligible at the current block
+/// height, as read from the stake contract.
+fn stake_weight(account: &AccountPublicKey) -> u64 {
+    let stake: Option<StakeData> =
+        abi::call(STAKE_CONTRACT, "get_stake", account)
+            .expect("Querying the stake should succeed");
+
+    stake
+        .and_then(|stake| stake.amount)
+        .filter(|amount| amount.eligibility <= abi::block_height())
+        .map(|amount| amount.value)
+        .unwrap_or_default()
+}
+
+/// The public sender of the calling transaction.
+///
+/// # Panics
+/// When the contract is called by a shielded transaction.
+fn public_sender() -> AccountPublicKey {
+    match abi::public_sender() {
+        Some(pk) => pk,
+        None => abi::fail(
+            ContractFailure::UNAUTHORIZED,
+            "Governance is only open to public accounts",
+        ),
+    }
+}
@@ -0,0 +1,243 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use contract_test_utils::{Scenario, ScenarioBuilder};
+use dusk_core::abi::ContractId;
+use dusk_core::dusk;
+use dusk_core::governance::{
+    GovernanceInit, ParameterChange, ProposalData, ProposalId, Propose,
+    ProposeEvent, Vote, EXECUTE_TOPIC, PROPOSE_TOPIC,
+};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+
+const GOVERNANCE_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x25;
+    ContractId::from_bytes(bytes)
+};
+
+const VOTING_PERIOD: u64 = 100;
+const QUORUM: u64 = dusk(1_000.0);
+
+const PARAMETER: &str = "block_gas_limit";
+
+/// Start a scenario with the stake and governance contracts deployed, and the
+/// given moonlight accounts owning the `MOONLIGHT_GENESIS_VALUE` and staking
+/// the given values.
+fn instantiate(stakers: &[(AccountPublicKey, u64)]) -> Scenario {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let stake_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/stake_contract.wasm"
+    );
+    let governance_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/governance_contract.wasm"
+    );
+
+    let init = GovernanceInit {
+        voting_period: VOTING_PERIOD,
+        quorum: QUORUM,
+        parameters: vec![ParameterChange {
+            key: String::from(PARAMETER),
+            value: 3_000_000_000u64.to_le_bytes().to_vec(),
+        }],
+    };
+
+    let mut builder = ScenarioBuilder::new(transfer_bytecode)
+        .with_stake(stake_bytecode)
+        .deploy(GOVERNANCE_ID, governance_bytecode, &init);
+
+    for (pk, stake) in stakers {
+        builder = builder.account(pk, MOONLIGHT_GENESIS_VALUE);
+        if *stake > 0 {
+            builder = builder.stake(pk, *stake);
+        }
+    }
+
+    builder.build()
+}
+
+fn proposal(scenario: &mut Scenario, proposal_id: ProposalId) -> ProposalData {
+    scenario
+        .query::<_, Option<ProposalData>>(
+            GOVERNANCE_ID,
+            "proposal",
+            &proposal_id,
+        )
+        .expect("The proposal should exist")
+}
+
+fn parameter(scenario: &mut Scenario) -> Option<u64> {
+    scenario
+        .query::<_, Option<Vec<u8>>>(
+            GOVERNANCE_ID,
+            "parameter",
+            &String::from(PARAMETER),
+        )
+        .map(|value| {
+            u64::from_le_bytes(value.try_into().expect("The value is a u64"))
+        })
+}
+
+fn propose_gas_limit(gas_limit: u64) -> Propose {
+    Propose {
+        description: String::from("Raise the block gas limit"),
+        changes: vec![ParameterChange {
+            key: String::from(PARAMETER),
+            value: gas_limit.to_le_bytes().to_vec(),
+        }],
+    }
+}
+
+#[test]
+fn approved_proposal() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let alice_sk = AccountSecretKey::random(rng);
+    let alice_pk = AccountPublicKey::from(&alice_sk);
+    let bob_sk = AccountSecretKey::random(rng);
+    let bob_pk = AccountPublicKey::from(&bob_sk);
+    let charlie_sk = AccountSecretKey::random(rng);
+    let charlie_pk = AccountPublicKey::from(&charlie_sk);
+
+    let scenario = &mut instantiate(&[
+        (alice_pk, dusk(1_000.0)),
+        (bob_pk, dusk(500.0)),
+        (charlie_pk, 0),
+    ]);
+
+    // accounts without stake can't submit proposals
+    let propose = propose_gas_limit(4_000_000_000);
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "propose", &propose)
+        .assert_failure();
+
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "propose", &propose)
+        .assert_success()
+        .assert_event_data(
+            GOVERNANCE_ID,
+            PROPOSE_TOPIC,
+            &ProposeEvent {
+                proposal_id: 0,
+                proposer: alice_pk,
+                end: 1 + VOTING_PERIOD,
+            },
+        );
+
+    // votes are weighted by stake, and cast once per account
+    let vote = Vote {
+        proposal_id: 0,
+        approve: true,
+    };
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_success();
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_failure();
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_failure();
+
+    let vote = Vote {
+        proposal_id: 0,
+        approve: false,
+    };
+    scenario
+        .call(&bob_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_success();
+
+    let data = proposal(scenario, 0);
+    assert_eq!(data.yes, dusk(1_000.0));
+    assert_eq!(data.no, dusk(500.0));
+
+    // the proposal can't be executed during the voting period
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "execute", &0u64)
+        .assert_failure();
+    assert_eq!(parameter(scenario), Some(3_000_000_000));
+
+    scenario.advance(VOTING_PERIOD);
+
+    // votes are closed after the voting period
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_failure();
+
+    // anyone can execute the approved proposal, only once
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "execute", &0u64)
+        .assert_success()
+        .assert_event(GOVERNANCE_ID, EXECUTE_TOPIC);
+    assert_eq!(parameter(scenario), Some(4_000_000_000));
+    assert!(proposal(scenario, 0).executed);
+    scenario
+        .call(&charlie_sk, GOVERNANCE_ID, "execute", &0u64)
+        .assert_failure();
+}
+
+#[test]
+fn rejected_proposals() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let alice_sk = AccountSecretKey::random(rng);
+    let alice_pk = AccountPublicKey::from(&alice_sk);
+    let bob_sk = AccountSecretKey::random(rng);
+    let bob_pk = AccountPublicKey::from(&bob_sk);
+
+    let scenario =
+        &mut instantiate(&[(alice_pk, dusk(500.0)), (bob_pk, dusk(1_000.0))]);
+
+    // the first proposal is outvoted, the second doesn't meet the quorum
+    let propose = propose_gas_limit(4_000_000_000);
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "propose", &propose)
+        .assert_success();
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "propose", &propose)
+        .assert_success();
+
+    let vote = Vote {
+        proposal_id: 0,
+        approve: true,
+    };
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_success();
+    let vote = Vote {
+        proposal_id: 0,
+        approve: false,
+    };
+    scenario
+        .call(&bob_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_success();
+
+    let vote = Vote {
+        proposal_id: 1,
+        approve: true,
+    };
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "vote", &vote)
+        .assert_success();
+
+    scenario.advance(VOTING_PERIOD);
+
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "execute", &0u64)
+        .assert_failure();
+    scenario
+        .call(&alice_sk, GOVERNANCE_ID, "execute", &1u64)
+        .assert_failure();
+    assert_eq!(parameter(scenario), Some(3_000_000_000));
+}
@@ -9,6 +9,7 @@
 #![deny(clippy::all)]
 
 extern crate alloc;
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use dusk_bytes::Serializable;
@@ -34,6 +35,19 @@ impl HostFnTest {
         abi::poseidon_hash(scalars)
     }
 
+    pub fn blake2b(
+        &self,
+        bytes: Vec<u8>,
+        hash_len: u8,
+        personal: [u8; 16],
+    ) -> Vec<u8> {
+        abi::blake2b(bytes, hash_len, personal)
+    }
+
+    pub fn sha512(&self, bytes: Vec<u8>) -> [u8; 64] {
+        abi::sha512(bytes)
+    }
+
     pub fn verify_plonk(
         &self,
         verifier_data: Vec<u8>,
@@ -87,10 +101,44 @@ impl HostFnTest {
         abi::block_height()
     }
 
+    pub fn block_seed(&self) -> [u8; abi::SEED_BYTES] {
+        abi::block_seed()
+    }
+
+    pub fn block_timestamp(&self) -> u64 {
+        abi::block_timestamp()
+    }
+
+    pub fn block_generator(&self) -> Option<BlsPublicKey> {
+        abi::block_generator()
+    }
+
+    pub fn streamed_sum(&self) -> (u64, u64) {
+        let arg = abi::take_streamed_arg();
+        let sum = arg.iter().map(|byte| *byte as u64).sum();
+        (arg.len() as u64, sum)
+    }
+
+    pub fn gas(&self) -> (u64, u64) {
+        (abi::gas_remaining(), abi::gas_spent_so_far())
+    }
+
+    pub fn fail(&self, code: u32, message: String) {
+        abi::fail(code, &message)
+    }
+
+    pub fn metadata(&self) -> abi::ContractMetadata {
+        self_metadata()
+    }
+
     pub fn owner(&self) -> BlsPublicKey {
         abi::self_owner()
     }
 
+    pub fn try_owner(&self) -> Option<BlsPublicKey> {
+        abi::try_self_owner()
+    }
+
     pub fn owner_raw(&self) -> [u8; BlsPublicKey::SIZE] {
         abi::self_owner_raw()
     }
@@ -106,6 +154,18 @@ unsafe fn poseidon_hash(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |scalars| STATE.poseidon_hash(scalars))
 }
 
+#[no_mangle]
+unsafe fn blake2b(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(bytes, hash_len, personal)| {
+        STATE.blake2b(bytes, hash_len, personal)
+    })
+}
+
+#[no_mangle]
+unsafe fn sha512(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |bytes| STATE.sha512(bytes))
+}
+
 #[no_mangle]
 unsafe fn verify_plonk(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |(verifier_data, proof, public_inputs)| {
@@ -147,11 +207,55 @@ unsafe fn block_height(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.block_height())
 }
 
+#[no_mangle]
+unsafe fn block_seed(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.block_seed())
+}
+
+#[no_mangle]
+unsafe fn block_timestamp(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.block_timestamp())
+}
+
+#[no_mangle]
+unsafe fn block_generator(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.block_generator())
+}
+
+dusk_core::stream_receiver!();
+
+#[no_mangle]
+unsafe fn streamed_sum(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.streamed_sum())
+}
+
+#[no_mangle]
+unsafe fn gas(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.gas())
+}
+
+#[no_mangle]
+unsafe fn fail(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(code, message)| STATE.fail(code, message))
+}
+
+dusk_core::contract_metadata!("host_fn", None);
+
+#[no_mangle]
+unsafe fn metadata(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.metadata())
+}
+
 #[no_mangle]
 unsafe fn contract_owner(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.owner())
 }
 
+#[no_mangle]
+unsafe fn contract_try_owner(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.try_owner())
+}
+
 #[no_mangle]
 unsafe fn contract_owner_raw(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.owner_raw())
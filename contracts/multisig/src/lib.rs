@@ -0,0 +1,61 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use dusk_core::abi;
+
+mod state;
+use state::MultisigState;
+
+static mut STATE: MultisigState = MultisigState::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn deposit(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |value| STATE.deposit(value))
+}
+
+#[no_mangle]
+unsafe fn propose(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |call| STATE.propose(call))
+}
+
+#[no_mangle]
+unsafe fn approve(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |approval| STATE.approve(approval))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn owners(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.owners().to_vec())
+}
+
+#[no_mangle]
+unsafe fn threshold(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.threshold())
+}
+
+#[no_mangle]
+unsafe fn proposal(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |id| STATE.proposal(id).cloned())
+}
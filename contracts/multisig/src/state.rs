@@ -0,0 +1,166 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::multisig::{
+    approval_message, Approval, ApproveEvent, ExecuteEvent, MultisigInit,
+    ProposalData, ProposalId, ProposeEvent, APPROVE_TOPIC, EXECUTE_TOPIC,
+    PROPOSE_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+
+/// Contract holding a balance controlled by a set of owners.
+///
+/// Proposed calls are executed by the contract once `threshold` owners
+/// approved them. Paying out of the contract's balance is done by proposing a
+/// call to the transfer contract.
+#[derive(Debug, Default, Clone)]
+pub struct MultisigState {
+    owners: Vec<AccountPublicKey>,
+    threshold: u32,
+    next_id: ProposalId,
+    proposals: BTreeMap<ProposalId, ProposalData>,
+}
+
+impl MultisigState {
+    pub const fn new() -> Self {
+        Self {
+            owners: Vec::new(),
+            threshold: 0,
+            next_id: 0,
+            proposals: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: MultisigInit) {
+        if self.threshold != 0 {
+            panic!("The multisig is already initialized");
+        }
+
+        let mut owners = init.owners;
+        owners.sort_by_key(AccountPublicKey::to_raw_bytes);
+        owners.dedup();
+
+        if init.threshold == 0 || init.threshold as usize > owners.len() {
+            panic!("The threshold should be between one and the owners count");
+        }
+
+        self.owners = owners;
+        self.threshold = init.threshold;
+    }
+
+    /// Deposit funds into the contract's balance, from the deposit of the
+    /// calling transaction.
+    pub fn deposit(&mut self, value: u64) {
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing funds into the multisig should succeed");
+    }
+
+    pub fn propose(&mut self, call: ContractCall) -> ProposalId {
+        let proposal_id = self.next_id;
+        self.next_id += 1;
+
+        self.proposals.insert(
+            proposal_id,
+            ProposalData {
+                call: call.clone(),
+                approvals: Vec::new(),
+            },
+        );
+
+        abi::emit(PROPOSE_TOPIC, ProposeEvent { proposal_id, call });
+
+        proposal_id
+    }
+
+    /// Approve a proposal, executing it when the threshold is met.
+    pub fn approve(&mut self, approval: Approval) {
+        let proposal_id = approval.proposal_id;
+        let owner = approval.owner;
+
+        if !self.owners.contains(&owner) {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the owners can approve proposals",
+            );
+        }
+
+        let Some(proposal) = self.proposals.get_mut(&proposal_id) else {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal doesn't exist",
+            );
+        };
+
+        if proposal.approvals.contains(&owner) {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The proposal is already approved by the owner",
+            );
+        }
+
+        let msg = approval_message(
+            abi::chain_id(),
+            abi::self_id(),
+            proposal_id,
+            &proposal.call,
+        );
+        if !abi::verify_bls(msg, owner, approval.signature) {
+            abi::fail(
+                ContractFailure::INVALID_SIGNATURE,
+                "Invalid approval signature",
+            );
+        }
+
+        proposal.approvals.push(owner);
+        let approvals = proposal.approvals.len();
+
+        abi::emit(APPROVE_TOPIC, ApproveEvent { proposal_id, owner });
+
+        if approvals >= self.threshold as usize {
+            self.execute(proposal_id);
+        }
+    }
+
+    /// Executes the given proposal, removing it from the pending ones.
+    ///
+    /// # Panics
+    /// When the proposed call fails, reverting the approval that met the
+    /// threshold.
+    fn execute(&mut self, proposal_id: ProposalId) {
+        let proposal = self
+            .proposals
+            .remove(&proposal_id)
+            .expect("The proposal should exist");
+        let call = proposal.call;
+
+        let data = abi::call_raw(call.contract, &call.fn_name, &call.fn_args)
+            .expect("The proposed call should succeed");
+
+        abi::emit(EXECUTE_TOPIC, ExecuteEvent { proposal_id, data });
+    }
+
+    /// The accounts owning the multisig.
+    pub fn owners(&self) -> &[AccountPublicKey] {
+        &self.owners
+    }
+
+    /// The number of owners that must approve a proposal for it to be
+    /// executed.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// The given proposal, if it is pending.
+    pub fn proposal(&self, proposal_id: ProposalId) -> Option<&ProposalData> {
+        self.proposals.get(&proposal_id)
+    }
+}
@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ContractId, ARGBUF_LEN};
+use dusk_core::multisig::{Approval, MultisigInit, ProposalData, ProposalId};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+const THRESHOLD: u32 = 2;
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const MULTISIG_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x22;
+    ContractId::from_bytes(bytes)
+};
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+/// Instantiate the virtual machine with the transfer and multisig contracts
+/// deployed, with the given moonlight accounts owning the
+/// `MOONLIGHT_GENESIS_VALUE` and the multisig.
+fn instantiate(moonlight_pks: &[AccountPublicKey]) -> Session {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let multisig_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/multisig_contract.wasm"
+    );
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    let init = MultisigInit {
+        owners: moonlight_pks.to_vec(),
+        threshold: THRESHOLD,
+    };
+    session
+        .deploy(
+            multisig_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(MULTISIG_ID)
+                .init_arg(&init),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the multisig contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    let base = session.commit().expect("Committing should succeed");
+
+    vm.session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the multisig contract from the given moonlight account, depositing
+/// the given value, and returning whether the call succeeded.
+fn call_multisig(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    deposit: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(MULTISIG_ID, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        deposit,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt = execute(session, &tx, &ExecutionConfig::DEFAULT)
+        .expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn proposal(
+    session: &mut Session,
+    proposal_id: ProposalId,
+) -> Option<ProposalData> {
+    session
+        .call(MULTISIG_ID, "proposal", &proposal_id, GAS_LIMIT)
+        .expect("Querying the proposal should succeed")
+        .data
+}
+
+fn moonlight_balance(session: &mut Session, pk: &AccountPublicKey) -> u64 {
+    session
+        .call::<_, AccountData>(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
+        .expect("Querying the account should succeed")
+        .data
+        .balance
+}
+
+fn multisig_balance(session: &mut Session) -> u64 {
+    session
+        .call(
+            TRANSFER_CONTRACT,
+            "contract_balance",
+            &MULTISIG_ID,
+            GAS_LIMIT,
+        )
+        .expect("Querying the contract balance should succeed")
+        .data
+}
+
+#[test]
+fn threshold_execution() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let owner_sks: Vec<_> =
+        (0..3).map(|_| AccountSecretKey::random(rng)).collect();
+    let owner_pks: Vec<_> =
+        owner_sks.iter().map(AccountPublicKey::from).collect();
+    let stranger_sk = AccountSecretKey::random(rng);
+    let receiver_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&owner_pks);
+
+    const DEPOSIT: u64 = dusk(100.0);
+    const PAYOUT: u64 = dusk(40.0);
+
+    assert!(call_multisig(
+        session,
+        &owner_sks[0],
+        1,
+        DEPOSIT,
+        "deposit",
+        &DEPOSIT
+    ));
+    assert_eq!(multisig_balance(session), DEPOSIT);
+
+    // propose to pay out of the multisig's balance
+    let payout = ContractCall::new(
+        TRANSFER_CONTRACT,
+        "contract_to_account",
+        &ContractToAccount {
+            account: receiver_pk,
+            value: PAYOUT,
+        },
+    )
+    .expect("Creating the contract call should succeed");
+    assert!(call_multisig(
+        session,
+        &owner_sks[0],
+        2,
+        0,
+        "propose",
+        &payout
+    ));
+
+    const PROPOSAL_ID: ProposalId = 0;
+    let approval = |sk: &AccountSecretKey| {
+        Approval::new(sk, CHAIN_ID, MULTISIG_ID, PROPOSAL_ID, &payout)
+    };
+
+    // approvals by accounts that don't own the multisig are refused
+    assert!(!call_multisig(
+        session,
+        &owner_sks[0],
+        3,
+        0,
+        "approve",
+        &approval(&stranger_sk)
+    ));
+
+    // the first approval doesn't meet the threshold
+    assert!(call_multisig(
+        session,
+        &owner_sks[0],
+        4,
+        0,
+        "approve",
+        &approval(&owner_sks[0])
+    ));
+    let pending =
+        proposal(session, PROPOSAL_ID).expect("The proposal should be pending");
+    assert_eq!(pending.approvals, vec![owner_pks[0]]);
+    assert_eq!(moonlight_balance(session, &receiver_pk), 0);
+
+    // approving twice is refused
+    assert!(!call_multisig(
+        session,
+        &owner_sks[0],
+        5,
+        0,
+        "approve",
+        &approval(&owner_sks[0])
+    ));
+
+    // the second approval, relayed by any account, executes the proposal
+    assert!(call_multisig(
+        session,
+        &owner_sks[0],
+        6,
+        0,
+        "approve",
+        &approval(&owner_sks[1])
+    ));
+    assert_eq!(proposal(session, PROPOSAL_ID), None);
+    assert_eq!(moonlight_balance(session, &receiver_pk), PAYOUT);
+    assert_eq!(multisig_balance(session), DEPOSIT - PAYOUT);
+}
@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use dusk_core::abi;
+
+mod state;
+use state::NameServiceState;
+
+static mut STATE: NameServiceState = NameServiceState::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn register(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.register(arg))
+}
+
+#[no_mangle]
+unsafe fn renew(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |name: String| STATE.renew(&name))
+}
+
+#[no_mangle]
+unsafe fn transfer(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn set_target(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.set_target(arg))
+}
+
+#[no_mangle]
+unsafe fn bid(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.bid(arg))
+}
+
+#[no_mangle]
+unsafe fn claim(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |name: String| STATE.claim(name))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn resolve(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |name: String| STATE.resolve(&name))
+}
+
+#[no_mangle]
+unsafe fn name_data(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |name: String| STATE.name_data(&name).cloned())
+}
+
+#[no_mangle]
+unsafe fn auction(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |name: String| STATE.auction(&name).cloned())
+}
@@ -0,0 +1,327 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::name_service::{
+    is_valid_name, AuctionData, Bid, BidEvent, NameData, NameEvent,
+    NameServiceInit, Register, SetTarget, Target, TargetEvent, TransferName,
+    AUCTION_DURATION, BID_TOPIC, REGISTER_TOPIC, REGISTRATION_FEE,
+    REGISTRATION_PERIOD, RENEW_TOPIC, SHORT_NAME_LEN, TARGET_TOPIC,
+    TRANSFER_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+
+/// Contract mapping names to the accounts, addresses and contracts they
+/// resolve to.
+///
+/// Registration fees and winning bids are paid to the treasury set when the
+/// contract is deployed, while the contract's balance holds the highest bid
+/// of each running auction.
+#[derive(Debug, Default, Clone)]
+pub struct NameServiceState {
+    treasury: Option<AccountPublicKey>,
+    names: BTreeMap<String, NameData>,
+    auctions: BTreeMap<String, AuctionData>,
+}
+
+impl NameServiceState {
+    pub const fn new() -> Self {
+        Self {
+            treasury: None,
+            names: BTreeMap::new(),
+            auctions: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: NameServiceInit) {
+        if self.treasury.is_some() {
+            panic!("The name service is already initialized");
+        }
+        self.treasury = Some(init.treasury);
+    }
+
+    /// Register a name that is available, paying the registration fee.
+    pub fn register(&mut self, register: Register) {
+        let owner = public_sender();
+        let name = register.name;
+
+        check_name(&name);
+        if name.len() < SHORT_NAME_LEN {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "Short names can only be allocated by auction",
+            );
+        }
+        self.check_available(&name);
+
+        self.collect(REGISTRATION_FEE);
+        self.insert_name(name, owner, register.target);
+    }
+
+    /// Renew a registered name, paying the registration fee.
+    ///
+    /// Anyone can renew a name, extending its expiry on behalf of its owner.
+    pub fn renew(&mut self, name: &str) {
+        let height = abi::block_height();
+        let data = self.active_name_mut(name, height);
+        data.expiry += REGISTRATION_PERIOD;
+
+        let event = NameEvent {
+            name: String::from(name),
+            owner: data.owner,
+            expiry: data.expiry,
+        };
+
+        self.collect(REGISTRATION_FEE);
+        abi::emit(RENEW_TOPIC, event);
+    }
+
+    pub fn transfer(&mut self, transfer: TransferName) {
+        let data = self.owned_name_mut(&transfer.name);
+        data.owner = transfer.owner;
+
+        abi::emit(
+            TRANSFER_TOPIC,
+            NameEvent {
+                name: transfer.name,
+                owner: data.owner,
+                expiry: data.expiry,
+            },
+        );
+    }
+
+    pub fn set_target(&mut self, set_target: SetTarget) {
+        let data = self.owned_name_mut(&set_target.name);
+        data.target = set_target.target.clone();
+
+        abi::emit(
+            TARGET_TOPIC,
+            TargetEvent {
+                name: set_target.name,
+                target: set_target.target,
+            },
+        );
+    }
+
+    /// Bid on a short name that is available, locking the deposit of the
+    /// calling transaction.
+    ///
+    /// The first bid starts the auction. Each bid must be higher than the
+    /// previous one, which is refunded to its bidder.
+    pub fn bid(&mut self, bid: Bid) {
+        let bidder = public_sender();
+        let height = abi::block_height();
+        let name = bid.name;
+        let value = bid.value;
+
+        check_name(&name);
+        if name.len() >= SHORT_NAME_LEN {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "Only short names are allocated by auction",
+            );
+        }
+        self.check_available(&name);
+
+        let (end, outbid) = match self.auctions.get(&name) {
+            Some(auction) if height >= auction.end => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The auction has ended",
+            ),
+            Some(auction) if value <= auction.value => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The bid should be higher than the highest bid",
+            ),
+            Some(auction) => {
+                (auction.end, Some((auction.bidder, auction.value)))
+            }
+            None if value < REGISTRATION_FEE => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The bid should cover the registration fee",
+            ),
+            None => (height + AUCTION_DURATION, None),
+        };
+
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing the bid should succeed");
+
+        if let Some((bidder, value)) = outbid {
+            pay(bidder, value);
+        }
+
+        self.auctions
+            .insert(name.clone(), AuctionData { bidder, value, end });
+
+        abi::emit(
+            BID_TOPIC,
+            BidEvent {
+                name,
+                bidder,
+                value,
+                end,
+            },
+        );
+    }
+
+    /// Register a short name to the highest bidder once its auction has
+    /// ended, paying the winning bid to the treasury.
+    ///
+    /// Anyone can claim the name on behalf of the highest bidder, to whose
+    /// Moonlight account it resolves until changed.
+    pub fn claim(&mut self, name: String) {
+        let height = abi::block_height();
+
+        let auction = match self.auctions.get(&name) {
+            Some(auction) if height >= auction.end => auction.clone(),
+            Some(_) => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The auction has not ended",
+            ),
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "There is no auction for the name",
+            ),
+        };
+        self.auctions.remove(&name);
+
+        pay(self.treasury(), auction.value);
+        self.insert_name(
+            name,
+            auction.bidder,
+            Target::Moonlight(auction.bidder),
+        );
+    }
+
+    fn insert_name(
+        &mut self,
+        name: String,
+        owner: AccountPublicKey,
+        target: Target,
+    ) {
+        let expiry = abi::block_height() + REGISTRATION_PERIOD;
+
+        self.names.insert(
+            name.clone(),
+            NameData {
+                owner,
+                target,
+                expiry,
+            },
+        );
+
+        abi::emit(
+            REGISTER_TOPIC,
+            NameEvent {
+                name,
+                owner,
+                expiry,
+            },
+        );
+    }
+
+    /// Checks that the name is neither registered, nor expired.
+    fn check_available(&self, name: &str) {
+        let height = abi::block_height();
+        if let Some(data) = self.names.get(name) {
+            if !data.is_expired(height) {
+                abi::fail(
+                    ContractFailure::INVALID_ARGUMENT,
+                    "The name is already registered",
+                );
+            }
+        }
+    }
+
+    fn active_name_mut(&mut self, name: &str, height: u64) -> &mut NameData {
+        match self.names.get_mut(name) {
+            Some(data) if !data.is_expired(height) => data,
+            _ => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The name is not registered",
+            ),
+        }
+    }
+
+    /// The data of a registered name, checking the sender owns it.
+    fn owned_name_mut(&mut self, name: &str) -> &mut NameData {
+        let sender = public_sender();
+        let height = abi::block_height();
+
+        let data = self.active_name_mut(name, height);
+        if data.owner != sender {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the owner can change the name",
+            );
+        }
+        data
+    }
+
+    /// Collects a fee from the deposit of the calling transaction, paying it
+    /// to the treasury.
+    fn collect(&self, fee: u64) {
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &fee)
+            .expect("Depositing the fee should succeed");
+        pay(self.treasury(), fee);
+    }
+
+    fn treasury(&self) -> AccountPublicKey {
+        self.treasury
+            .expect("The name service should be initialized")
+    }
+
+    /// What the given name resolves to, if it is registered.
+    pub fn resolve(&self, name: &str) -> Option<Target> {
+        self.name_data(name).map(|data| data.target.clone())
+    }
+
+    /// The data of the given name, if it is registered.
+    pub fn name_data(&self, name: &str) -> Option<&NameData> {
+        let height = abi::block_height();
+        self.names.get(name).filter(|data| !data.is_expired(height))
+    }
+
+    /// The running auction of the given name, if any.
+    pub fn auction(&self, name: &str) -> Option<&AuctionData> {
+        self.auctions.get(name)
+    }
+}
+
+/// Pays the given value out of the contract's balance to a public account.
+fn pay(account: AccountPublicKey, value: u64) {
+    if value > 0 {
+        let _: () = abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_account",
+            &ContractToAccount { account, value },
+        )
+        .expect("Paying out of the contract's balance should succeed");
+    }
+}
+
+fn check_name(name: &str) {
+    if !is_valid_name(name) {
+        abi::fail(ContractFailure::INVALID_ARGUMENT, "Invalid name");
+    }
+}
+
+/// The public sender of the calling transaction.
+///
+/// # Panics
+/// When the contract is called by a shielded transaction.
+fn public_sender() -> AccountPublicKey {
+    match abi::public_sender() {
+        Some(pk) => pk,
+        None => abi::fail(
+            ContractFailure::UNAUTHORIZED,
+            "Names can only be managed by a public account",
+        ),
+    }
+}
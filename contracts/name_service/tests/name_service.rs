@@ -0,0 +1,407 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ARGBUF_LEN};
+use dusk_core::name_service::{
+    AuctionData, Bid, NameServiceInit, Register, SetTarget, Target,
+    TransferName, AUCTION_DURATION, NAME_SERVICE_CONTRACT, REGISTRATION_FEE,
+    REGISTRATION_PERIOD,
+};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+const CONFIG: ExecutionConfig = ExecutionConfig {
+    with_public_sender: true,
+    ..ExecutionConfig::DEFAULT
+};
+
+/// Deploy the transfer and name service contracts, with the given moonlight
+/// accounts owning the `MOONLIGHT_GENESIS_VALUE` and the fees paid to the
+/// treasury, returning the commit to start sessions from.
+fn instantiate(
+    vm: &mut VM,
+    moonlight_pks: &[AccountPublicKey],
+    treasury: AccountPublicKey,
+) -> [u8; 32] {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let name_service_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/name_service_contract.wasm"
+    );
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    session
+        .deploy(
+            name_service_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(NAME_SERVICE_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the name service contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    session.commit().expect("Committing should succeed")
+}
+
+/// Commit the given session and start a new one at the given block height.
+fn advance(vm: &VM, session: Session, height: u64) -> Session {
+    let base = session.commit().expect("Committing should succeed");
+    vm.session(base, CHAIN_ID, height)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the name service contract from the given moonlight account, depositing
+/// the given value, and returning whether the call succeeded.
+fn call_name_service(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    deposit: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(NAME_SERVICE_CONTRACT, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        deposit,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt =
+        execute(session, &tx, &CONFIG).expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn moonlight_balance(session: &mut Session, pk: &AccountPublicKey) -> u64 {
+    session
+        .call::<_, AccountData>(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
+        .expect("Querying the account should succeed")
+        .data
+        .balance
+}
+
+fn name_service_balance(session: &mut Session) -> u64 {
+    session
+        .call(
+            TRANSFER_CONTRACT,
+            "contract_balance",
+            &NAME_SERVICE_CONTRACT,
+            GAS_LIMIT,
+        )
+        .expect("Querying the contract balance should succeed")
+        .data
+}
+
+fn resolve(session: &mut Session, name: &str) -> Option<Target> {
+    session
+        .call(
+            NAME_SERVICE_CONTRACT,
+            "resolve",
+            &String::from(name),
+            GAS_LIMIT,
+        )
+        .expect("Resolving the name should succeed")
+        .data
+}
+
+#[test]
+fn register_transfer_and_expire() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let alice_sk = AccountSecretKey::random(rng);
+    let alice_pk = AccountPublicKey::from(&alice_sk);
+    let bob_sk = AccountSecretKey::random(rng);
+    let bob_pk = AccountPublicKey::from(&bob_sk);
+    let treasury_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+    let base = instantiate(vm, &[alice_pk, bob_pk], treasury_pk);
+    let mut session = vm
+        .session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed");
+
+    let name = String::from("alice");
+    let register = Register {
+        name: name.clone(),
+        target: Target::Moonlight(alice_pk),
+    };
+
+    // the fee must be paid with the deposit
+    assert!(!call_name_service(
+        &mut session,
+        &alice_sk,
+        1,
+        0,
+        "register",
+        &register
+    ));
+    assert!(call_name_service(
+        &mut session,
+        &alice_sk,
+        2,
+        REGISTRATION_FEE,
+        "register",
+        &register
+    ));
+    assert_eq!(
+        moonlight_balance(&mut session, &treasury_pk),
+        REGISTRATION_FEE
+    );
+    assert_eq!(name_service_balance(&mut session), 0);
+    assert_eq!(
+        resolve(&mut session, &name),
+        Some(Target::Moonlight(alice_pk))
+    );
+
+    // a registered name can't be registered again
+    let register = Register {
+        name: name.clone(),
+        target: Target::Moonlight(bob_pk),
+    };
+    assert!(!call_name_service(
+        &mut session,
+        &bob_sk,
+        1,
+        REGISTRATION_FEE,
+        "register",
+        &register
+    ));
+
+    // only the owner can transfer the name, or change its target
+    let transfer = TransferName {
+        name: name.clone(),
+        owner: bob_pk,
+    };
+    assert!(!call_name_service(
+        &mut session,
+        &bob_sk,
+        2,
+        0,
+        "transfer",
+        &transfer
+    ));
+    assert!(call_name_service(
+        &mut session,
+        &alice_sk,
+        3,
+        0,
+        "transfer",
+        &transfer
+    ));
+    let set_target = SetTarget {
+        name: name.clone(),
+        target: Target::Moonlight(bob_pk),
+    };
+    assert!(!call_name_service(
+        &mut session,
+        &alice_sk,
+        4,
+        0,
+        "set_target",
+        &set_target
+    ));
+    assert!(call_name_service(
+        &mut session,
+        &bob_sk,
+        3,
+        0,
+        "set_target",
+        &set_target
+    ));
+    assert_eq!(
+        resolve(&mut session, &name),
+        Some(Target::Moonlight(bob_pk))
+    );
+
+    // the name stops resolving once expired, and can be registered again
+    let mut session = advance(vm, session, 1 + REGISTRATION_PERIOD);
+    assert_eq!(resolve(&mut session, &name), None);
+
+    let register = Register {
+        name: name.clone(),
+        target: Target::Moonlight(alice_pk),
+    };
+    assert!(call_name_service(
+        &mut session,
+        &alice_sk,
+        5,
+        REGISTRATION_FEE,
+        "register",
+        &register
+    ));
+    assert_eq!(
+        resolve(&mut session, &name),
+        Some(Target::Moonlight(alice_pk))
+    );
+}
+
+#[test]
+fn short_name_auction() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let alice_sk = AccountSecretKey::random(rng);
+    let alice_pk = AccountPublicKey::from(&alice_sk);
+    let bob_sk = AccountSecretKey::random(rng);
+    let bob_pk = AccountPublicKey::from(&bob_sk);
+    let treasury_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+    let base = instantiate(vm, &[alice_pk, bob_pk], treasury_pk);
+    let mut session = vm
+        .session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed");
+
+    let name = String::from("dsk");
+
+    // short names can't be registered directly
+    let register = Register {
+        name: name.clone(),
+        target: Target::Moonlight(alice_pk),
+    };
+    assert!(!call_name_service(
+        &mut session,
+        &alice_sk,
+        1,
+        REGISTRATION_FEE,
+        "register",
+        &register
+    ));
+
+    let alice_bid = Bid {
+        name: name.clone(),
+        value: REGISTRATION_FEE,
+    };
+    assert!(call_name_service(
+        &mut session,
+        &alice_sk,
+        2,
+        REGISTRATION_FEE,
+        "bid",
+        &alice_bid
+    ));
+
+    // a bid must be higher than the highest one
+    assert!(!call_name_service(
+        &mut session,
+        &bob_sk,
+        1,
+        REGISTRATION_FEE,
+        "bid",
+        &alice_bid
+    ));
+
+    // outbidding refunds the previous bidder
+    let alice_balance = moonlight_balance(&mut session, &alice_pk);
+    let bob_bid = Bid {
+        name: name.clone(),
+        value: 2 * REGISTRATION_FEE,
+    };
+    assert!(call_name_service(
+        &mut session,
+        &bob_sk,
+        2,
+        bob_bid.value,
+        "bid",
+        &bob_bid
+    ));
+    assert_eq!(
+        moonlight_balance(&mut session, &alice_pk),
+        alice_balance + REGISTRATION_FEE
+    );
+    assert_eq!(name_service_balance(&mut session), bob_bid.value);
+
+    let auction: Option<AuctionData> = session
+        .call(NAME_SERVICE_CONTRACT, "auction", &name, GAS_LIMIT)
+        .expect("Querying the auction should succeed")
+        .data;
+    let auction = auction.expect("The auction should be running");
+    assert_eq!(auction.bidder, bob_pk);
+    assert_eq!(auction.end, 1 + AUCTION_DURATION);
+
+    // the name can't be claimed before the end of the auction
+    assert!(!call_name_service(
+        &mut session,
+        &alice_sk,
+        3,
+        0,
+        "claim",
+        &name
+    ));
+
+    // anyone can claim the name for the highest bidder
+    let mut session = advance(vm, session, auction.end);
+    assert!(call_name_service(
+        &mut session,
+        &alice_sk,
+        4,
+        0,
+        "claim",
+        &name
+    ));
+    assert_eq!(
+        resolve(&mut session, &name),
+        Some(Target::Moonlight(bob_pk))
+    );
+    assert_eq!(moonlight_balance(&mut session, &treasury_pk), bob_bid.value);
+    assert_eq!(name_service_balance(&mut session), 0);
+}
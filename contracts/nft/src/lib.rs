@@ -0,0 +1,93 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use dusk_core::abi;
+
+mod state;
+use state::NftState;
+
+static mut STATE: NftState = NftState::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn mint(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.mint(arg))
+}
+
+#[no_mangle]
+unsafe fn burn(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_id| STATE.burn(token_id))
+}
+
+#[no_mangle]
+unsafe fn transfer(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn list(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.list(arg))
+}
+
+#[no_mangle]
+unsafe fn buy(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_id| STATE.buy(token_id))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn name(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.name().clone())
+}
+
+#[no_mangle]
+unsafe fn symbol(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.symbol().clone())
+}
+
+#[no_mangle]
+unsafe fn owner_of(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_id| STATE.owner_of(token_id))
+}
+
+#[no_mangle]
+unsafe fn token_uri(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_id| STATE.token_uri(token_id))
+}
+
+#[no_mangle]
+unsafe fn token_data(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |token_id| STATE.token_data(token_id).cloned())
+}
+
+#[no_mangle]
+unsafe fn royalty_info(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(token_id, price)| {
+        STATE.royalty_info(token_id, price)
+    })
+}
+
+#[no_mangle]
+unsafe fn balance_of(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |account| STATE.balance_of(&account))
+}
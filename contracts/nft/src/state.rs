@@ -0,0 +1,331 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::nft::{
+    Account, List, ListEvent, Mint, NftInit, SaleEvent, TokenData, TokenId,
+    Transfer, TransferEvent, BURN_TOPIC, LIST_TOPIC, MAX_ROYALTY, MINT_TOPIC,
+    SALE_TOPIC, TRANSFER_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+
+/// Contract keeping track of the ownership of a collection of NFTs.
+///
+/// NFTs are minted by the minter set when the contract is deployed, and can
+/// be listed for sale by their owner. A sale is paid in Dusk deposited with
+/// the buying transaction, out of which the contract pays the royalty to the
+/// creator of the NFT and the rest to its seller.
+///
+/// Accounts are keyed with [`Account::to_key`].
+#[derive(Debug, Default, Clone)]
+pub struct NftState {
+    info: Option<NftInit>,
+    next_id: TokenId,
+    tokens: BTreeMap<TokenId, TokenData>,
+    balances: BTreeMap<Vec<u8>, u64>,
+}
+
+impl NftState {
+    pub const fn new() -> Self {
+        Self {
+            info: None,
+            next_id: 0,
+            tokens: BTreeMap::new(),
+            balances: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: NftInit) {
+        if self.info.is_some() {
+            panic!("The collection is already initialized");
+        }
+        self.info = Some(init);
+    }
+
+    pub fn mint(&mut self, mint: Mint) -> TokenId {
+        if sender() != self.info().minter {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the minter can mint NFTs",
+            );
+        }
+        if mint.royalty > MAX_ROYALTY {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The royalty should not exceed 100%",
+            );
+        }
+
+        let token_id = self.next_id;
+        self.next_id += 1;
+
+        let owner = mint.owner;
+        self.tokens.insert(
+            token_id,
+            TokenData {
+                owner,
+                uri: mint.uri,
+                creator: mint.creator,
+                royalty: mint.royalty,
+                price: None,
+            },
+        );
+        *self.balances.entry(owner.to_key()).or_default() += 1;
+
+        abi::emit(
+            MINT_TOPIC,
+            TransferEvent {
+                token_id,
+                sender: None,
+                receiver: Some(owner),
+            },
+        );
+
+        token_id
+    }
+
+    pub fn burn(&mut self, token_id: TokenId) {
+        let owner = self.check_owner(token_id);
+
+        self.tokens.remove(&token_id);
+        self.decrement_balance(&owner);
+
+        abi::emit(
+            BURN_TOPIC,
+            TransferEvent {
+                token_id,
+                sender: Some(owner),
+                receiver: None,
+            },
+        );
+    }
+
+    pub fn transfer(&mut self, transfer: Transfer) {
+        let owner = self.check_owner(transfer.token_id);
+        self.move_token(transfer.token_id, owner, transfer.receiver);
+    }
+
+    pub fn list(&mut self, list: List) {
+        let owner = self.check_owner(list.token_id);
+        if !matches!(owner, Account::External(_)) {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only NFTs owned by a public account can be listed",
+            );
+        }
+
+        let price = (list.price > 0).then_some(list.price);
+        self.token_data_mut(list.token_id).price = price;
+
+        abi::emit(
+            LIST_TOPIC,
+            ListEvent {
+                token_id: list.token_id,
+                price,
+            },
+        );
+    }
+
+    /// Buy a listed NFT, paying its price with the deposit of the calling
+    /// transaction.
+    ///
+    /// The royalty is paid to the creator of the NFT and the rest of the price
+    /// to its seller.
+    pub fn buy(&mut self, token_id: TokenId) {
+        let buyer = sender();
+
+        let token = self.token_data_mut(token_id);
+        let price = match token.price {
+            Some(price) => price,
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The NFT is not listed for sale",
+            ),
+        };
+        let seller = match token.owner {
+            Account::External(pk) => pk,
+            Account::Contract(_) => {
+                unreachable!("Only NFTs owned by a public account are listed")
+            }
+        };
+        let creator = token.creator;
+        let royalty = token.royalty_amount(price);
+
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &price)
+            .expect("Depositing the price of the NFT should succeed");
+
+        pay(creator, royalty);
+        pay(seller, price - royalty);
+
+        abi::emit(
+            SALE_TOPIC,
+            SaleEvent {
+                token_id,
+                seller,
+                buyer,
+                price,
+                royalty,
+            },
+        );
+
+        self.move_token(token_id, Account::External(seller), buyer);
+    }
+
+    /// Moves an NFT from its `owner` to the `receiver`, removing any listing
+    /// of it.
+    fn move_token(
+        &mut self,
+        token_id: TokenId,
+        owner: Account,
+        receiver: Account,
+    ) {
+        let token = self.token_data_mut(token_id);
+        token.owner = receiver;
+        token.price = None;
+
+        self.decrement_balance(&owner);
+        *self.balances.entry(receiver.to_key()).or_default() += 1;
+
+        abi::emit(
+            TRANSFER_TOPIC,
+            TransferEvent {
+                token_id,
+                sender: Some(owner),
+                receiver: Some(receiver),
+            },
+        );
+    }
+
+    /// Checks that the sender owns the given NFT, returning the owner.
+    fn check_owner(&self, token_id: TokenId) -> Account {
+        let sender = sender();
+        match self.tokens.get(&token_id) {
+            Some(token) if token.owner == sender => sender,
+            Some(_) => abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the owner can move the NFT",
+            ),
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The NFT doesn't exist",
+            ),
+        }
+    }
+
+    fn token_data_mut(&mut self, token_id: TokenId) -> &mut TokenData {
+        match self.tokens.get_mut(&token_id) {
+            Some(token) => token,
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The NFT doesn't exist",
+            ),
+        }
+    }
+
+    fn decrement_balance(&mut self, account: &Account) {
+        let key = account.to_key();
+        match self.balances.get(&key).copied() {
+            Some(1) => {
+                self.balances.remove(&key);
+            }
+            Some(balance) => {
+                self.balances.insert(key, balance - 1);
+            }
+            None => unreachable!("The owner of an NFT has a balance"),
+        }
+    }
+
+    fn info(&self) -> &NftInit {
+        self.info
+            .as_ref()
+            .expect("The collection should be initialized")
+    }
+
+    /// The name of the collection.
+    pub fn name(&self) -> &String {
+        &self.info().name
+    }
+
+    /// The symbol of the collection.
+    pub fn symbol(&self) -> &String {
+        &self.info().symbol
+    }
+
+    /// The owner of the given NFT, if it exists.
+    pub fn owner_of(&self, token_id: TokenId) -> Option<Account> {
+        self.tokens.get(&token_id).map(|token| token.owner)
+    }
+
+    /// The metadata URI of the given NFT, if it exists.
+    pub fn token_uri(&self, token_id: TokenId) -> Option<String> {
+        self.tokens.get(&token_id).map(|token| token.uri.clone())
+    }
+
+    /// The data stored for the given NFT, if it exists.
+    pub fn token_data(&self, token_id: TokenId) -> Option<&TokenData> {
+        self.tokens.get(&token_id)
+    }
+
+    /// The creator of the given NFT, together with the royalty paid to them
+    /// out of a sale at the given price.
+    pub fn royalty_info(
+        &self,
+        token_id: TokenId,
+        price: u64,
+    ) -> Option<(AccountPublicKey, u64)> {
+        self.tokens
+            .get(&token_id)
+            .map(|token| (token.creator, token.royalty_amount(price)))
+    }
+
+    /// The number of NFTs owned by the given account.
+    pub fn balance_of(&self, account: &Account) -> u64 {
+        self.balances
+            .get(&account.to_key())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Pays the given value out of the contract's balance to a public account.
+fn pay(account: AccountPublicKey, value: u64) {
+    if value > 0 {
+        let _: () = abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_account",
+            &ContractToAccount { account, value },
+        )
+        .expect("Paying out of the contract's balance should succeed");
+    }
+}
+
+/// The account calling the NFT contract.
+///
+/// Contracts are identified as the caller of the NFT contract. Calls made
+/// through the transfer contract, meaning directly by a transaction, are
+/// identified as the public sender of the transaction.
+///
+/// # Panics
+/// When the NFT contract is called by a shielded transaction.
+fn sender() -> Account {
+    match abi::caller() {
+        Some(contract) if contract != TRANSFER_CONTRACT => {
+            Account::Contract(contract)
+        }
+        _ => match abi::public_sender() {
+            Some(pk) => Account::External(pk),
+            None => abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "NFTs can only be moved by a public account or a contract",
+            ),
+        },
+    }
+}
@@ -0,0 +1,281 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ContractId, ARGBUF_LEN};
+use dusk_core::nft::{Account, List, Mint, NftInit, TokenId, Transfer};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const NFT_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x21;
+    ContractId::from_bytes(bytes)
+};
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+const CONFIG: ExecutionConfig = ExecutionConfig {
+    with_public_sender: true,
+    ..ExecutionConfig::DEFAULT
+};
+
+/// Instantiate the virtual machine with the transfer and NFT contracts
+/// deployed, with the given moonlight accounts owning the
+/// `MOONLIGHT_GENESIS_VALUE`, and the first one being the minter of the
+/// collection.
+fn instantiate(moonlight_pks: &[AccountPublicKey]) -> Session {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let nft_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/nft_contract.wasm"
+    );
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    let init = NftInit {
+        name: String::from("Reference Collection"),
+        symbol: String::from("RNFT"),
+        minter: Account::from(moonlight_pks[0]),
+    };
+    session
+        .deploy(
+            nft_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(NFT_ID)
+                .init_arg(&init),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the NFT contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    let base = session.commit().expect("Committing should succeed");
+
+    vm.session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the NFT contract from the given moonlight account, depositing the
+/// given value, and returning whether the call succeeded.
+fn call_nft(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    deposit: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(NFT_ID, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        deposit,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt =
+        execute(session, &tx, &CONFIG).expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn owner_of(session: &mut Session, token_id: TokenId) -> Option<Account> {
+    session
+        .call(NFT_ID, "owner_of", &token_id, GAS_LIMIT)
+        .expect("Querying the owner should succeed")
+        .data
+}
+
+fn balance_of(session: &mut Session, account: impl Into<Account>) -> u64 {
+    session
+        .call(NFT_ID, "balance_of", &account.into(), GAS_LIMIT)
+        .expect("Querying the balance should succeed")
+        .data
+}
+
+fn moonlight_balance(session: &mut Session, pk: &AccountPublicKey) -> u64 {
+    session
+        .call::<_, AccountData>(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
+        .expect("Querying the account should succeed")
+        .data
+        .balance
+}
+
+#[test]
+fn mint_transfer_and_burn() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let minter_sk = AccountSecretKey::random(rng);
+    let minter_pk = AccountPublicKey::from(&minter_sk);
+    let owner_sk = AccountSecretKey::random(rng);
+    let owner_pk = AccountPublicKey::from(&owner_sk);
+    let receiver_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&[minter_pk, owner_pk]);
+
+    let mint = Mint {
+        owner: Account::from(owner_pk),
+        uri: String::from("ipfs://nft/0"),
+        creator: minter_pk,
+        royalty: 500,
+    };
+
+    // only the minter can mint
+    assert!(!call_nft(session, &owner_sk, 1, 0, "mint", &mint));
+    assert!(call_nft(session, &minter_sk, 1, 0, "mint", &mint));
+
+    const TOKEN_ID: TokenId = 0;
+    assert_eq!(owner_of(session, TOKEN_ID), Some(Account::from(owner_pk)));
+    assert_eq!(balance_of(session, owner_pk), 1);
+    let uri: Option<String> = session
+        .call(NFT_ID, "token_uri", &TOKEN_ID, GAS_LIMIT)
+        .expect("Querying the URI should succeed")
+        .data;
+    assert_eq!(uri.as_deref(), Some("ipfs://nft/0"));
+
+    // only the owner can transfer the NFT
+    let transfer = Transfer {
+        token_id: TOKEN_ID,
+        receiver: Account::from(receiver_pk),
+    };
+    assert!(!call_nft(session, &minter_sk, 2, 0, "transfer", &transfer));
+    let transfer = Transfer {
+        token_id: TOKEN_ID,
+        receiver: Account::from(minter_pk),
+    };
+    assert!(call_nft(session, &owner_sk, 2, 0, "transfer", &transfer));
+    assert_eq!(owner_of(session, TOKEN_ID), Some(Account::from(minter_pk)));
+    assert_eq!(balance_of(session, owner_pk), 0);
+    assert_eq!(balance_of(session, minter_pk), 1);
+
+    assert!(!call_nft(session, &owner_sk, 3, 0, "burn", &TOKEN_ID));
+    assert!(call_nft(session, &minter_sk, 3, 0, "burn", &TOKEN_ID));
+    assert_eq!(owner_of(session, TOKEN_ID), None);
+    assert_eq!(balance_of(session, minter_pk), 0);
+}
+
+#[test]
+fn sale_pays_royalty() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let minter_sk = AccountSecretKey::random(rng);
+    let minter_pk = AccountPublicKey::from(&minter_sk);
+    let seller_sk = AccountSecretKey::random(rng);
+    let seller_pk = AccountPublicKey::from(&seller_sk);
+    let buyer_sk = AccountSecretKey::random(rng);
+    let buyer_pk = AccountPublicKey::from(&buyer_sk);
+    let creator_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&[minter_pk, seller_pk, buyer_pk]);
+
+    // a royalty above 100% is refused
+    let mut mint = Mint {
+        owner: Account::from(seller_pk),
+        uri: String::from("ipfs://nft/0"),
+        creator: creator_pk,
+        royalty: 10_001,
+    };
+    assert!(!call_nft(session, &minter_sk, 1, 0, "mint", &mint));
+
+    // 2.5% royalty
+    mint.royalty = 250;
+    assert!(call_nft(session, &minter_sk, 2, 0, "mint", &mint));
+
+    const TOKEN_ID: TokenId = 0;
+    const PRICE: u64 = dusk(100.0);
+    const ROYALTY: u64 = dusk(2.5);
+
+    // an NFT that isn't listed can't be bought
+    assert!(!call_nft(session, &buyer_sk, 1, PRICE, "buy", &TOKEN_ID));
+
+    let list = List {
+        token_id: TOKEN_ID,
+        price: PRICE,
+    };
+    assert!(call_nft(session, &seller_sk, 1, 0, "list", &list));
+
+    let royalty_info: Option<(AccountPublicKey, u64)> = session
+        .call(NFT_ID, "royalty_info", &(TOKEN_ID, PRICE), GAS_LIMIT)
+        .expect("Querying the royalty should succeed")
+        .data;
+    assert_eq!(royalty_info, Some((creator_pk, ROYALTY)));
+
+    // paying less than the price fails
+    assert!(!call_nft(
+        session,
+        &buyer_sk,
+        2,
+        PRICE - 1,
+        "buy",
+        &TOKEN_ID
+    ));
+
+    let seller_balance = moonlight_balance(session, &seller_pk);
+    assert!(call_nft(session, &buyer_sk, 3, PRICE, "buy", &TOKEN_ID));
+
+    assert_eq!(owner_of(session, TOKEN_ID), Some(Account::from(buyer_pk)));
+    assert_eq!(moonlight_balance(session, &creator_pk), ROYALTY);
+    assert_eq!(
+        moonlight_balance(session, &seller_pk),
+        seller_balance + PRICE - ROYALTY
+    );
+
+    // the listing is removed after the sale
+    assert!(!call_nft(session, &buyer_sk, 4, PRICE, "buy", &TOKEN_ID));
+}
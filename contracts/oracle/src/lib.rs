@@ -0,0 +1,58 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use dusk_core::abi;
+
+mod state;
+use state::OracleState;
+
+static mut STATE: OracleState = OracleState::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn push_prices(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |updates| STATE.push_prices(updates))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn get_price(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pair: String| STATE.get_price(&pair).cloned())
+}
+
+#[no_mangle]
+unsafe fn feeders(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.feeders().to_vec())
+}
+
+#[no_mangle]
+unsafe fn threshold(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.threshold())
+}
+
+#[no_mangle]
+unsafe fn max_age(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.max_age())
+}
@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::oracle::{
+    update_message, OracleInit, PriceData, PriceEvent, PriceUpdate, PRICE_TOPIC,
+};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+
+/// The latest price pushed by a feeder for a pair.
+#[derive(Debug, Clone)]
+struct Submission {
+    price: u64,
+    timestamp: u64,
+    height: u64,
+}
+
+/// Contract aggregating the prices signed by a set of feeders.
+///
+/// The price of a pair is re-aggregated every time a feeder pushes a price for
+/// it, as the median of the submissions pushed in the last `max_age` blocks,
+/// provided there are at least `threshold` of them.
+#[derive(Debug, Default, Clone)]
+pub struct OracleState {
+    feeders: Vec<AccountPublicKey>,
+    threshold: u32,
+    max_age: u64,
+    submissions: BTreeMap<String, BTreeMap<Vec<u8>, Submission>>,
+    prices: BTreeMap<String, PriceData>,
+}
+
+impl OracleState {
+    pub const fn new() -> Self {
+        Self {
+            feeders: Vec::new(),
+            threshold: 0,
+            max_age: 0,
+            submissions: BTreeMap::new(),
+            prices: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: OracleInit) {
+        if self.threshold != 0 {
+            panic!("The oracle is already initialized");
+        }
+
+        let mut feeders = init.feeders;
+        feeders.sort_by_key(AccountPublicKey::to_raw_bytes);
+        feeders.dedup();
+
+        if init.threshold == 0 || init.threshold as usize > feeders.len() {
+            panic!("The threshold should be between one and the feeders count");
+        }
+
+        self.feeders = feeders;
+        self.threshold = init.threshold;
+        self.max_age = init.max_age;
+    }
+
+    /// Push price updates signed by the feeders, aggregating the price of
+    /// every pair updated.
+    ///
+    /// Anyone can push updates, since they are authenticated by the
+    /// signatures of the feeders.
+    pub fn push_prices(&mut self, updates: Vec<PriceUpdate>) {
+        let chain_id = abi::chain_id();
+        let oracle = abi::self_id();
+        let height = abi::block_height();
+
+        let mut pairs = Vec::new();
+
+        for update in updates {
+            let feeder = update.feeder;
+            if !self.feeders.contains(&feeder) {
+                abi::fail(
+                    ContractFailure::UNAUTHORIZED,
+                    "Only the feeders can update prices",
+                );
+            }
+
+            let submissions =
+                self.submissions.entry(update.pair.clone()).or_default();
+            let feeder_key = feeder.to_raw_bytes().to_vec();
+
+            if let Some(submission) = submissions.get(&feeder_key) {
+                if update.timestamp <= submission.timestamp {
+                    abi::fail(
+                        ContractFailure::INVALID_ARGUMENT,
+                        "The update is older than the feeder's latest",
+                    );
+                }
+            }
+
+            let msg = update_message(
+                chain_id,
+                oracle,
+                &update.pair,
+                update.price,
+                update.timestamp,
+            );
+            if !abi::verify_bls(msg, feeder, update.signature) {
+                abi::fail(
+                    ContractFailure::INVALID_SIGNATURE,
+                    "Invalid price update signature",
+                );
+            }
+
+            submissions.insert(
+                feeder_key,
+                Submission {
+                    price: update.price,
+                    timestamp: update.timestamp,
+                    height,
+                },
+            );
+
+            if !pairs.contains(&update.pair) {
+                pairs.push(update.pair);
+            }
+        }
+
+        for pair in pairs {
+            self.aggregate(pair, height);
+        }
+    }
+
+    /// Aggregate the price of the given pair from the recent submissions of
+    /// the feeders, if there are enough of them.
+    fn aggregate(&mut self, pair: String, height: u64) {
+        let Some(submissions) = self.submissions.get(&pair) else {
+            return;
+        };
+
+        let recent: Vec<&Submission> = submissions
+            .values()
+            .filter(|s| height.saturating_sub(s.height) <= self.max_age)
+            .collect();
+
+        if recent.len() < self.threshold as usize {
+            return;
+        }
+
+        let mut prices: Vec<u64> = recent.iter().map(|s| s.price).collect();
+        let timestamp = recent
+            .iter()
+            .map(|s| s.timestamp)
+            .min()
+            .expect("There should be at least one submission");
+
+        let price = PriceData {
+            price: median(&mut prices),
+            timestamp,
+            height,
+            sources: recent.len() as u32,
+        };
+
+        self.prices.insert(pair.clone(), price.clone());
+
+        abi::emit(PRICE_TOPIC, PriceEvent { pair, price });
+    }
+
+    /// The latest aggregated price of the given pair, if any.
+    pub fn get_price(&self, pair: &str) -> Option<&PriceData> {
+        self.prices.get(pair)
+    }
+
+    /// The accounts allowed to sign price updates.
+    pub fn feeders(&self) -> &[AccountPublicKey] {
+        &self.feeders
+    }
+
+    /// The number of recent submissions needed to aggregate a price.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// The number of blocks a submission is taken into account for.
+    pub fn max_age(&self) -> u64 {
+        self.max_age
+    }
+}
+
+/// The median of the given values, rounded down when there is an even number
+/// of them.
+fn median(values: &mut [u64]) -> u64 {
+    values.sort_unstable();
+
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        let (low, high) = (values[mid - 1], values[mid]);
+        low + (high - low) / 2
+    }
+}
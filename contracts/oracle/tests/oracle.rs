@@ -0,0 +1,238 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use contract_test_utils::{Scenario, ScenarioBuilder, Snapshot};
+use dusk_core::abi::ContractId;
+use dusk_core::dusk;
+use dusk_core::oracle::{
+    OracleInit, PriceData, PriceEvent, PriceUpdate, PRICE_TOPIC,
+};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+
+const ORACLE_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x26;
+    ContractId::from_bytes(bytes)
+};
+
+const THRESHOLD: u32 = 2;
+const MAX_AGE: u64 = 10;
+
+const PAIR: &str = "DUSK/USD";
+
+/// Start a scenario with the oracle contract deployed with the given feeders,
+/// and the relayer owning the `MOONLIGHT_GENESIS_VALUE`.
+fn instantiate(
+    relayer_pk: &AccountPublicKey,
+    feeders: Vec<AccountPublicKey>,
+) -> Scenario {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let oracle_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/oracle_contract.wasm"
+    );
+
+    let init = OracleInit {
+        feeders,
+        threshold: THRESHOLD,
+        max_age: MAX_AGE,
+    };
+
+    ScenarioBuilder::new(transfer_bytecode)
+        .deploy(ORACLE_ID, oracle_bytecode, &init)
+        .account(relayer_pk, MOONLIGHT_GENESIS_VALUE)
+        .build()
+}
+
+fn get_price(scenario: &mut Scenario) -> Option<PriceData> {
+    scenario.query(ORACLE_ID, "get_price", &String::from(PAIR))
+}
+
+fn update(
+    scenario: &Scenario,
+    sk: &AccountSecretKey,
+    price: u64,
+    timestamp: u64,
+) -> PriceUpdate {
+    PriceUpdate::new(
+        sk,
+        scenario.chain_id(),
+        ORACLE_ID,
+        String::from(PAIR),
+        price,
+        timestamp,
+    )
+}
+
+#[test]
+fn aggregate_median() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let feeder_sks: Vec<_> =
+        (0..3).map(|_| AccountSecretKey::random(rng)).collect();
+    let feeders = feeder_sks.iter().map(AccountPublicKey::from).collect();
+    let relayer_sk = AccountSecretKey::random(rng);
+    let relayer_pk = AccountPublicKey::from(&relayer_sk);
+
+    let scenario = &mut instantiate(&relayer_pk, feeders);
+
+    // a single price doesn't meet the threshold
+    let updates = vec![update(scenario, &feeder_sks[0], 100, 1_000)];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success();
+    assert_eq!(get_price(scenario), None);
+
+    // the median of an even number of prices is the mean of the middle ones
+    let price = PriceData {
+        price: 105,
+        timestamp: 1_000,
+        height: 1,
+        sources: 2,
+    };
+    let updates = vec![update(scenario, &feeder_sks[1], 110, 1_001)];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success()
+        .assert_event_data(
+            ORACLE_ID,
+            PRICE_TOPIC,
+            &PriceEvent {
+                pair: String::from(PAIR),
+                price: price.clone(),
+            },
+        );
+    assert_eq!(get_price(scenario), Some(price));
+
+    scenario.advance_to(5);
+
+    let updates = vec![
+        update(scenario, &feeder_sks[0], 104, 1_010),
+        update(scenario, &feeder_sks[2], 200, 1_011),
+    ];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success();
+    assert_eq!(
+        get_price(scenario),
+        Some(PriceData {
+            price: 110,
+            timestamp: 1_001,
+            height: 5,
+            sources: 3,
+        })
+    );
+}
+
+#[test]
+fn reject_updates() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let feeder_sks: Vec<_> =
+        (0..3).map(|_| AccountSecretKey::random(rng)).collect();
+    let feeders = feeder_sks.iter().map(AccountPublicKey::from).collect();
+    let relayer_sk = AccountSecretKey::random(rng);
+    let relayer_pk = AccountPublicKey::from(&relayer_sk);
+
+    let scenario = &mut instantiate(&relayer_pk, feeders);
+
+    // updates signed by other accounts are rejected
+    let updates = vec![update(scenario, &relayer_sk, 100, 1_000)];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_failure();
+
+    // tampered updates are rejected
+    let mut tampered = update(scenario, &feeder_sks[0], 100, 1_000);
+    tampered.price = 1;
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &vec![tampered])
+        .assert_failure();
+
+    let updates = vec![
+        update(scenario, &feeder_sks[0], 100, 1_000),
+        update(scenario, &feeder_sks[1], 100, 1_000),
+    ];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success();
+
+    // replayed updates are rejected
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_failure();
+}
+
+#[test]
+fn stale_submissions() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let feeder_sks: Vec<_> =
+        (0..3).map(|_| AccountSecretKey::random(rng)).collect();
+    let feeders = feeder_sks.iter().map(AccountPublicKey::from).collect();
+    let relayer_sk = AccountSecretKey::random(rng);
+    let relayer_pk = AccountPublicKey::from(&relayer_sk);
+
+    let scenario = &mut instantiate(&relayer_pk, feeders);
+
+    let updates = vec![
+        update(scenario, &feeder_sks[0], 100, 1_000),
+        update(scenario, &feeder_sks[1], 100, 1_000),
+    ];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success();
+
+    scenario.advance(MAX_AGE + 1);
+
+    // the previous submissions are too old to be aggregated with a new one,
+    // leaving the price stale
+    let updates = vec![update(scenario, &feeder_sks[2], 200, 1_100)];
+    scenario
+        .call(&relayer_sk, ORACLE_ID, "push_prices", &updates)
+        .assert_success();
+
+    let price = get_price(scenario).expect("The price should exist");
+    assert_eq!(price.price, 100);
+    assert_eq!(price.height, 1);
+    assert!(price.is_stale(scenario.height(), MAX_AGE));
+}
+
+#[test]
+fn price_snapshot() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let feeder_sks: Vec<_> =
+        (0..3).map(|_| AccountSecretKey::random(rng)).collect();
+    let feeders = feeder_sks.iter().map(AccountPublicKey::from).collect();
+    let relayer_sk = AccountSecretKey::random(rng);
+    let relayer_pk = AccountPublicKey::from(&relayer_sk);
+
+    let scenario = &mut instantiate(&relayer_pk, feeders);
+
+    let updates = vec![
+        update(scenario, &feeder_sks[0], 100, 1_000),
+        update(scenario, &feeder_sks[1], 110, 1_001),
+        update(scenario, &feeder_sks[2], 120, 1_002),
+    ];
+    let outcome =
+        scenario.call(&relayer_sk, ORACLE_ID, "push_prices", &updates);
+    outcome.assert_success();
+
+    let mut snapshot = Snapshot::new();
+    snapshot
+        .record_debug("price", &get_price(scenario))
+        .record_debug("relayer", &scenario.account(&relayer_pk))
+        .record_events("push_prices", outcome.events());
+    snapshot.assert_matches("price_snapshot");
+}
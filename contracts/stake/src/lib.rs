@@ -71,6 +71,46 @@ unsafe fn withdraw_from_contract(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |rewards| STATE.withdraw_from_contract(rewards))
 }
 
+#[no_mangle]
+unsafe fn delegate(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.delegate(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn undelegate(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.undelegate(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn withdraw_delegation(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.withdraw_delegation(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn set_commission(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.set_commission(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn set_reward_address(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| {
+        assert_transfer_caller();
+        STATE.set_reward_address(arg)
+    })
+}
+
 // Queries
 
 #[no_mangle]
@@ -98,6 +138,33 @@ unsafe fn get_config(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.config().clone())
 }
 
+#[no_mangle]
+unsafe fn get_delegation(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(provisioner, delegator)| {
+        STATE.get_delegation(&provisioner, &delegator).copied()
+    })
+}
+
+#[no_mangle]
+unsafe fn get_commission(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pk| STATE.get_commission(&pk))
+}
+
+#[no_mangle]
+unsafe fn get_nonce(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pk| STATE.get_nonce(&pk))
+}
+
+#[no_mangle]
+unsafe fn get_reward_address(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pk| STATE.get_reward_address(&pk))
+}
+
+#[no_mangle]
+unsafe fn rewards_at_epoch(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(pk, epoch)| STATE.rewards_at_epoch(&pk, epoch))
+}
+
 // "Feeder" queries
 
 #[no_mangle]
@@ -105,11 +172,21 @@ unsafe fn stakes(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.stakes())
 }
 
+#[no_mangle]
+unsafe fn weighted_stakes(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.weighted_stakes())
+}
+
 #[no_mangle]
 unsafe fn prev_state_changes(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.prev_state_changes())
 }
 
+#[no_mangle]
+unsafe fn delegations(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pk| STATE.delegations(&pk))
+}
+
 // "Management" transactions
 
 #[no_mangle]
@@ -12,9 +12,12 @@ use dusk_bytes::Serializable;
 use dusk_core::abi::{self, ContractId};
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::stake::{
-    next_epoch, Reward, SlashEvent, Stake, StakeAmount, StakeConfig, StakeData,
-    StakeEvent, StakeFundOwner, StakeKeys, Withdraw, WithdrawToContract, EPOCH,
-    STAKE_CONTRACT,
+    epoch_at, next_epoch, Commission, CommissionEvent, Delegate,
+    DelegatedStakeEvent, DelegationData, DelegationEvent, DelegationWithdraw,
+    EpochRewards, Reward, RewardAddress, RewardAddressEvent, SlashEvent, Stake,
+    StakeAmount, StakeConfig, StakeData, StakeEvent, StakeFundOwner, StakeKeys,
+    Withdraw, WithdrawToContract, EPOCH, MAX_COMMISSION, MAX_DELEGATORS,
+    MINIMUM_DELEGATION, REWARD_SNAPSHOT_EPOCHS, STAKE_CONTRACT,
 };
 use dusk_core::transfer::{
     ContractToContract, ReceiveFromContract, TRANSFER_CONTRACT,
@@ -28,6 +31,16 @@ use dusk_core::transfer::{
 ///
 /// Rewards may be received by a public key regardless of whether they have a
 /// valid stake.
+///
+/// Accounts can also delegate Dusk to a provisioner that opted in by setting
+/// a commission. Delegated value adds to the weight of the provisioner in the
+/// consensus for as long as it has a stake, and the delegators earn the part
+/// of the provisioner's rewards proportional to the weight they add to it,
+/// minus its commission. Delegations are slashed together with the
+/// provisioner's stake.
+///
+/// The rewards of each stake are snapshotted per epoch, keeping the history of
+/// their accrual and withdrawal over the last [`REWARD_SNAPSHOT_EPOCHS`].
 #[derive(Debug, Default, Clone)]
 pub struct StakeState {
     burnt_amount: u64,
@@ -35,8 +48,17 @@ pub struct StakeState {
     previous_block_state:
         BTreeMap<[u8; BlsPublicKey::SIZE], (Option<StakeData>, BlsPublicKey)>,
     stakes: BTreeMap<[u8; BlsPublicKey::SIZE], (StakeData, StakeKeys)>,
+    delegations: BTreeMap<[u8; BlsPublicKey::SIZE], Delegations>,
+    commissions: BTreeMap<[u8; BlsPublicKey::SIZE], u16>,
+    reward_addresses: BTreeMap<[u8; BlsPublicKey::SIZE], BlsPublicKey>,
+    reward_snapshots: BTreeMap<([u8; BlsPublicKey::SIZE], u64), EpochRewards>,
+    nonces: BTreeMap<[u8; BlsPublicKey::SIZE], u64>,
 }
 
+/// The delegations to a provisioner, by delegator.
+type Delegations =
+    BTreeMap<[u8; BlsPublicKey::SIZE], (DelegationData, BlsPublicKey)>;
+
 const STAKE_CONTRACT_VERSION: u64 = 8;
 
 impl StakeState {
@@ -46,6 +68,11 @@ impl StakeState {
             config: StakeConfig::new(),
             previous_block_state: BTreeMap::new(),
             stakes: BTreeMap::new(),
+            delegations: BTreeMap::new(),
+            commissions: BTreeMap::new(),
+            reward_addresses: BTreeMap::new(),
+            reward_snapshots: BTreeMap::new(),
+            nonces: BTreeMap::new(),
         }
     }
 
@@ -95,10 +122,12 @@ impl StakeState {
         }
 
         let account = stake.keys().account;
-        let prev_stake = self.get_stake(&stake.keys().account).copied();
+        let prev_stake = self.weighted_stake(&account);
+        let delegated = self.delegated(&account);
         let (loaded_stake, keys) = self.load_or_create_stake_mut(stake.keys());
+        let is_new = loaded_stake.amount.is_none();
 
-        if loaded_stake.amount.is_none() && value < minimum_stake {
+        if is_new && value < minimum_stake {
             panic!("The staked value is lower than the minimum amount!");
         }
 
@@ -121,12 +150,7 @@ impl StakeState {
         // update the state accordingly
         let stake_event = match &mut loaded_stake.amount {
             Some(amount) => {
-                let locked = if block_height >= amount.eligibility {
-                    value / 10
-                } else {
-                    // No penalties applied if the stake is not eligible yet
-                    0
-                };
+                let locked = amount.top_up_locked(value, block_height);
                 let value = value - locked;
                 amount.locked += locked;
                 amount.value += value;
@@ -138,6 +162,10 @@ impl StakeState {
             }
         };
         abi::emit("stake", stake_event);
+        // the delegations left from a previous stake weigh again
+        if is_new {
+            emit_delegated_stake("delegated_stake", account, delegated);
+        }
 
         let key = keys.account.to_bytes();
         self.previous_block_state
@@ -156,14 +184,16 @@ impl StakeState {
         }
 
         let account = stake.keys().account;
-        let prev_stake = self.get_stake(&stake.keys().account).copied();
+        let prev_stake = self.weighted_stake(&account);
+        let delegated = self.delegated(&account);
         let (loaded_stake, keys) = self.load_or_create_stake_mut(stake.keys());
+        let is_new = loaded_stake.amount.is_none();
 
         let contract = Self::unwrap_contract_owner(&keys.owner);
         assert!(contract == &recv.contract, "Invalid contract caller");
         assert!(value == recv.value, "Stake amount mismatch");
 
-        if loaded_stake.amount.is_none() {
+        if is_new {
             if value < minimum_stake {
                 panic!("The staked value is lower than the minimum amount!");
             }
@@ -180,12 +210,7 @@ impl StakeState {
         // update the state accordingly
         let stake_event = match &mut loaded_stake.amount {
             Some(amount) => {
-                let locked = if block_height >= amount.eligibility {
-                    value / 10
-                } else {
-                    // No penalties applied if the stake is not eligible yet
-                    0
-                };
+                let locked = amount.top_up_locked(value, block_height);
                 let value = value - locked;
                 amount.locked += locked;
                 amount.value += value;
@@ -197,6 +222,10 @@ impl StakeState {
             }
         };
         abi::emit("stake", stake_event);
+        // the delegations left from a previous stake weigh again
+        if is_new {
+            emit_delegated_stake("delegated_stake", account, delegated);
+        }
 
         let key = keys.account.to_bytes();
         self.previous_block_state
@@ -210,10 +239,11 @@ impl StakeState {
         let value = transfer_withdraw.value();
         let signature = *unstake.signature();
 
+        let prev_stake = self.weighted_stake(&account);
+        let delegated = self.delegated(&account);
         let (loaded_stake, keys) = self
             .get_stake_mut(&account)
             .expect("A stake should exist in the map to be unstaked!");
-        let prev_stake = Some(*loaded_stake);
 
         // ensure there is a value staked, and that the withdrawal is not
         // greater than the available funds
@@ -260,6 +290,8 @@ impl StakeState {
             if loaded_stake.reward == 0 {
                 self.stakes.remove(&unstake.account().to_bytes());
             }
+            // the delegations weigh no more without a stake
+            emit_delegated_stake("undelegated_stake", account, delegated);
         } else if stake.total_funds() < self.config.minimum_stake {
             panic!("Stake left is lower than minimum stake");
         }
@@ -275,10 +307,11 @@ impl StakeState {
         let value = unstake.value();
         let data = unstake.data().to_vec();
 
+        let prev_stake = self.weighted_stake(account);
+        let delegated = self.delegated(account);
         let (loaded_stake, keys) = self
             .get_stake_mut(account)
             .expect("A stake should exist in the map to be unstaked!");
-        let prev_stake = Some(*loaded_stake);
 
         // ensure there is a value staked, and that the withdrawal is not
         // greater than the available funds
@@ -325,6 +358,8 @@ impl StakeState {
             if loaded_stake.reward == 0 {
                 self.stakes.remove(&unstake.account().to_bytes());
             }
+            // the delegations weigh no more without a stake
+            emit_delegated_stake("undelegated_stake", *account, delegated);
         }
         // Note: We no longer enforce the minimum stake condition here to
         // avoid locked funds exploit for contracts.
@@ -345,6 +380,7 @@ impl StakeState {
         let account = withdraw.account();
         let value = transfer_withdraw.value();
         let signature = *withdraw.signature();
+        let reward_address = self.get_reward_address(account);
 
         let (loaded_stake, keys) = self
             .get_stake_mut(account)
@@ -372,18 +408,34 @@ impl StakeState {
             panic!("Invalid account signature!");
         }
 
-        // make call to the transfer contract to withdraw funds from this
-        // contract into the receiver specified by the withdrawal.
-        let _: () = abi::call(TRANSFER_CONTRACT, "mint", transfer_withdraw)
-            .expect("Withdrawing reward should succeed");
+        // make call to the transfer contract to mint the reward into the
+        // reward address of the stake if one is set, or into the receiver
+        // specified by the withdrawal otherwise.
+        match reward_address {
+            Some(receiver) => {
+                let _: () = abi::call(
+                    TRANSFER_CONTRACT,
+                    "mint_to_account",
+                    &(transfer_withdraw.clone(), receiver),
+                )
+                .expect("Withdrawing reward should succeed");
+            }
+            None => {
+                let _: () =
+                    abi::call(TRANSFER_CONTRACT, "mint", transfer_withdraw)
+                        .expect("Withdrawing reward should succeed");
+            }
+        }
 
         // update the state accordingly
         loaded_stake.reward -= value;
+        let claimable = loaded_stake.reward;
         abi::emit("withdraw", StakeEvent::new(*keys, value));
 
         if loaded_stake.reward == 0 && loaded_stake.amount.is_none() {
             self.stakes.remove(&account.to_bytes());
         }
+        self.snapshot_rewards(account, 0, value, claimable);
     }
 
     pub fn withdraw_from_contract(&mut self, withdraw: WithdrawToContract) {
@@ -424,11 +476,461 @@ impl StakeState {
 
         // update the state accordingly
         loaded_stake.reward -= value;
+        let claimable = loaded_stake.reward;
         abi::emit("withdraw", StakeEvent::new(*keys, value));
 
         if loaded_stake.reward == 0 && loaded_stake.amount.is_none() {
             self.stakes.remove(&account.to_bytes());
         }
+        self.snapshot_rewards(account, 0, value, claimable);
+    }
+
+    pub fn delegate(&mut self, delegate: Delegate) {
+        let value = delegate.value();
+        let delegator = *delegate.delegator();
+        let provisioner = *delegate.provisioner();
+
+        if delegate.chain_id() != self.chain_id() {
+            panic!("The delegation must target the correct chain");
+        }
+
+        if value == 0 {
+            panic!("Delegating 0 is not allowed");
+        }
+
+        let Some(amount) =
+            self.get_stake(&provisioner).and_then(|stake| stake.amount)
+        else {
+            panic!("The provisioner must have a stake to delegate to");
+        };
+
+        // provisioners opt in to delegations by setting their commission
+        if !self.commissions.contains_key(&provisioner.to_bytes()) {
+            panic!("The provisioner doesn't accept delegations");
+        }
+
+        let msg = delegate.signature_message();
+        if !abi::verify_bls(msg, delegator, *delegate.signature()) {
+            panic!("Invalid delegator signature!");
+        }
+
+        self.use_nonce(&delegator, delegate.nonce());
+
+        // make call to transfer contract to transfer balance from the user to
+        // this contract
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing funds into contract should succeed");
+
+        // like a top-up of the stake, delegating to an eligible provisioner
+        // locks part of the value
+        let locked = amount.top_up_locked(value, abi::block_height());
+        let weighted = value - locked;
+        let prev_stake = self.weighted_stake(&provisioner);
+
+        let delegations =
+            self.delegations.entry(provisioner.to_bytes()).or_default();
+        if !delegations.contains_key(&delegator.to_bytes())
+            && delegations.len() >= MAX_DELEGATORS
+        {
+            panic!("The provisioner has reached the maximum delegators");
+        }
+
+        let (delegation, _) = delegations
+            .entry(delegator.to_bytes())
+            .or_insert_with(|| (DelegationData::default(), delegator));
+        delegation.value += weighted;
+        delegation.locked += locked;
+        if delegation.total_funds() < MINIMUM_DELEGATION {
+            panic!("The delegated value is lower than the minimum amount!");
+        }
+
+        abi::emit(
+            "delegate",
+            DelegationEvent {
+                provisioner,
+                delegator,
+                value,
+            },
+        );
+        emit_delegated_stake("delegated_stake", provisioner, weighted);
+
+        self.previous_block_state
+            .entry(provisioner.to_bytes())
+            .or_insert((prev_stake, provisioner));
+    }
+
+    pub fn undelegate(&mut self, undelegate: DelegationWithdraw) {
+        let transfer_withdraw = undelegate.transfer_withdraw();
+        let value = transfer_withdraw.value();
+        let provisioner = *undelegate.provisioner();
+
+        let prev_stake = self.weighted_stake(&provisioner);
+        let delegation = self.verified_delegation_mut(&undelegate);
+
+        if value == 0 {
+            panic!("Undelegating 0 is not allowed");
+        }
+        if value > delegation.total_funds() {
+            panic!("Value to undelegate higher than the delegated amount");
+        }
+
+        // make call to the transfer contract to withdraw funds from this
+        // contract into the receiver specified by the withdrawal.
+        let _: () = abi::call(TRANSFER_CONTRACT, "withdraw", transfer_withdraw)
+            .expect("Withdrawing delegation should succeed");
+
+        // the value is withdrawn before the locked funds
+        let from_value = min(value, delegation.value);
+        delegation.value -= from_value;
+        delegation.locked -= value - from_value;
+
+        let left = delegation.total_funds();
+        if left > 0 && left < MINIMUM_DELEGATION {
+            panic!("Delegation left is lower than the minimum delegation");
+        }
+
+        abi::emit(
+            "undelegate",
+            DelegationEvent {
+                provisioner,
+                delegator: *undelegate.delegator(),
+                value,
+            },
+        );
+
+        self.remove_empty_delegation(&undelegate);
+
+        // the delegated value only weighs while the provisioner has a stake
+        if prev_stake.is_some_and(|stake| stake.amount.is_some()) {
+            emit_delegated_stake("undelegated_stake", provisioner, from_value);
+            self.previous_block_state
+                .entry(provisioner.to_bytes())
+                .or_insert((prev_stake, provisioner));
+        }
+    }
+
+    pub fn withdraw_delegation(&mut self, withdraw: DelegationWithdraw) {
+        let transfer_withdraw = withdraw.transfer_withdraw();
+        let value = transfer_withdraw.value();
+
+        let delegation = self.verified_delegation_mut(&withdraw);
+
+        if value == 0 {
+            panic!("Withdrawing 0 reward is not allowed");
+        }
+        if value > delegation.reward {
+            panic!("Value to withdraw is higher than available reward");
+        }
+
+        // make call to the transfer contract to mint the reward into the
+        // receiver specified by the withdrawal.
+        let _: () = abi::call(TRANSFER_CONTRACT, "mint", transfer_withdraw)
+            .expect("Withdrawing reward should succeed");
+
+        delegation.reward -= value;
+
+        abi::emit(
+            "withdraw_delegation",
+            DelegationEvent {
+                provisioner: *withdraw.provisioner(),
+                delegator: *withdraw.delegator(),
+                value,
+            },
+        );
+
+        self.remove_empty_delegation(&withdraw);
+    }
+
+    /// Gets a mutable reference to the delegation withdrawn from, after
+    /// checking the signature of the delegator.
+    fn verified_delegation_mut(
+        &mut self,
+        withdraw: &DelegationWithdraw,
+    ) -> &mut DelegationData {
+        let delegator = *withdraw.delegator();
+
+        let msg = withdraw.signature_message();
+        if !abi::verify_bls(msg, delegator, *withdraw.signature()) {
+            panic!("Invalid delegator signature!");
+        }
+
+        self.delegations
+            .get_mut(&withdraw.provisioner().to_bytes())
+            .and_then(|delegations| delegations.get_mut(&delegator.to_bytes()))
+            .map(|(delegation, _)| delegation)
+            .expect("A delegation should exist in the map to withdraw from!")
+    }
+
+    fn remove_empty_delegation(&mut self, withdraw: &DelegationWithdraw) {
+        let provisioner = withdraw.provisioner().to_bytes();
+
+        if let Some(delegations) = self.delegations.get_mut(&provisioner) {
+            delegations.retain(|_, (delegation, _)| !delegation.is_empty());
+            if delegations.is_empty() {
+                self.delegations.remove(&provisioner);
+            }
+        }
+    }
+
+    pub fn set_commission(&mut self, commission: Commission) {
+        let account = *commission.account();
+        let rate = commission.rate();
+        let signature = *commission.signature();
+
+        if commission.chain_id() != self.chain_id() {
+            panic!("The commission must target the correct chain");
+        }
+
+        if rate > MAX_COMMISSION {
+            panic!("The commission rate is higher than the maximum");
+        }
+
+        let keys = self
+            .get_stake_keys(&account)
+            .expect("A stake should exist in the map to set its commission!");
+        let owner = Self::unwrap_account_owner(&keys.owner);
+
+        let msg = commission.signature_message();
+        if !abi::verify_bls(msg.clone(), owner, signature.owner) {
+            panic!("Invalid owner signature!");
+        }
+        if !abi::verify_bls(msg, account, signature.account) {
+            panic!("Invalid account signature!");
+        }
+
+        self.use_nonce(&account, commission.nonce());
+
+        self.commissions.insert(account.to_bytes(), rate);
+
+        abi::emit("commission", CommissionEvent { account, rate });
+    }
+
+    pub fn set_reward_address(&mut self, reward_address: RewardAddress) {
+        let account = *reward_address.account();
+        let receiver = reward_address.receiver().copied();
+        let signature = *reward_address.signature();
+
+        if reward_address.chain_id() != self.chain_id() {
+            panic!("The reward address must target the correct chain");
+        }
+
+        let keys = self.get_stake_keys(&account).expect(
+            "A stake should exist in the map to set its reward address!",
+        );
+        let owner = Self::unwrap_account_owner(&keys.owner);
+
+        let msg = reward_address.signature_message();
+        if !abi::verify_bls(msg.clone(), owner, signature.owner) {
+            panic!("Invalid owner signature!");
+        }
+        if !abi::verify_bls(msg, account, signature.account) {
+            panic!("Invalid account signature!");
+        }
+
+        self.use_nonce(&account, reward_address.nonce());
+
+        match receiver {
+            Some(receiver) => {
+                self.reward_addresses.insert(account.to_bytes(), receiver);
+            }
+            None => {
+                self.reward_addresses.remove(&account.to_bytes());
+            }
+        }
+
+        abi::emit("reward_address", RewardAddressEvent { account, receiver });
+    }
+
+    /// Gets the nonce of a stake, incremented by each signed change of its
+    /// settings.
+    pub fn get_nonce(&self, account: &BlsPublicKey) -> u64 {
+        self.nonces
+            .get(&account.to_bytes())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Checks the nonce of a signed change of the settings of a stake is the
+    /// one following its current nonce, and increments it, so the change
+    /// can't be replayed.
+    fn use_nonce(&mut self, account: &BlsPublicKey, nonce: u64) {
+        let current = self.nonces.entry(account.to_bytes()).or_default();
+        if nonce != *current + 1 {
+            panic!("Invalid stake nonce");
+        }
+        *current = nonce;
+    }
+
+    /// Gets the account the rewards of a stake are minted to, if one is set.
+    pub fn get_reward_address(
+        &self,
+        account: &BlsPublicKey,
+    ) -> Option<BlsPublicKey> {
+        self.reward_addresses.get(&account.to_bytes()).copied()
+    }
+
+    /// Gets the delegation of a delegator to a provisioner.
+    pub fn get_delegation(
+        &self,
+        provisioner: &BlsPublicKey,
+        delegator: &BlsPublicKey,
+    ) -> Option<&DelegationData> {
+        self.delegations
+            .get(&provisioner.to_bytes())?
+            .get(&delegator.to_bytes())
+            .map(|(delegation, _)| delegation)
+    }
+
+    /// Gets the commission rate of a provisioner, in basis points.
+    pub fn get_commission(&self, provisioner: &BlsPublicKey) -> u16 {
+        self.commissions
+            .get(&provisioner.to_bytes())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Feeds the host with the delegations to a provisioner.
+    pub fn delegations(&self, provisioner: &BlsPublicKey) {
+        if let Some(delegations) = self.delegations.get(&provisioner.to_bytes())
+        {
+            for (delegation, delegator) in delegations.values() {
+                abi::feed((*delegator, *delegation));
+            }
+        }
+    }
+
+    /// Splits the reward of a provisioner with its delegators.
+    ///
+    /// The delegated value adds to the weight of the provisioner, so each
+    /// delegation earns the part of the reward proportional to the weight it
+    /// adds, of which the provisioner keeps its commission. A provisioner
+    /// without a stake keeps the whole reward, since its delegations add no
+    /// weight to it. Returns the part of the reward left to the provisioner.
+    fn share_reward(&mut self, provisioner: &BlsPublicKey, reward: u64) -> u64 {
+        let staked = match self.get_stake(provisioner).and_then(|s| s.amount) {
+            Some(amount) => amount.value,
+            None => return reward,
+        };
+        let rate = self.get_commission(provisioner);
+
+        let delegations =
+            match self.delegations.get_mut(&provisioner.to_bytes()) {
+                Some(delegations) => delegations,
+                None => return reward,
+            };
+
+        let delegated: u128 = delegations
+            .values()
+            .map(|(delegation, _)| delegation.value as u128)
+            .sum();
+        if delegated == 0 {
+            return reward;
+        }
+        let weight = staked as u128 + delegated;
+
+        let mut shared = 0;
+        for (delegation, delegator) in delegations.values_mut() {
+            let earned = reward as u128 * delegation.value as u128 / weight;
+            let commission = earned * rate as u128 / MAX_COMMISSION as u128;
+            let value = (earned - commission) as u64;
+            if value == 0 {
+                continue;
+            }
+
+            delegation.reward += value;
+            shared += value;
+
+            abi::emit(
+                "delegation_reward",
+                DelegationEvent {
+                    provisioner: *provisioner,
+                    delegator: *delegator,
+                    value,
+                },
+            );
+        }
+
+        // the commission and the rounding remainder stay with the provisioner
+        reward - shared
+    }
+
+    /// Records a change of the rewards of a stake in the snapshot of the
+    /// current epoch, pruning the snapshots that fell out of the retained
+    /// window.
+    fn snapshot_rewards(
+        &mut self,
+        account: &BlsPublicKey,
+        accrued: u64,
+        withdrawn: u64,
+        claimable: u64,
+    ) {
+        let epoch = epoch_at(abi::block_height());
+        let snapshot = self
+            .reward_snapshots
+            .entry((account.to_bytes(), epoch))
+            .or_default();
+
+        snapshot.accrued += accrued;
+        snapshot.withdrawn += withdrawn;
+        snapshot.claimable = claimable;
+
+        self.prune_snapshots(account, epoch);
+    }
+
+    /// Removes the snapshots of a stake older than the window of
+    /// [`REWARD_SNAPSHOT_EPOCHS`] ending at the given epoch.
+    ///
+    /// The last snapshot before the window is kept, since the epochs at the
+    /// start of the window carry over its claimable rewards.
+    fn prune_snapshots(&mut self, account: &BlsPublicKey, epoch: u64) {
+        let oldest = oldest_snapshot_epoch(epoch);
+        if oldest == 0 {
+            return;
+        }
+
+        let key = account.to_bytes();
+        let mut expired: Vec<_> = self
+            .reward_snapshots
+            .range((key, 0)..(key, oldest))
+            .map(|(key, _)| *key)
+            .collect();
+        expired.pop();
+
+        for key in expired {
+            self.reward_snapshots.remove(&key);
+        }
+    }
+
+    /// Gets the snapshot of the rewards of a stake at the given epoch.
+    ///
+    /// An epoch in which the rewards didn't change carries over the claimable
+    /// rewards of the last snapshot before it. Snapshots are only retained
+    /// for the last [`REWARD_SNAPSHOT_EPOCHS`], up to the current epoch.
+    /// Returns `None` if the stake had no rewards up to the given epoch, or
+    /// if the epoch is older than the retained window.
+    pub fn rewards_at_epoch(
+        &self,
+        account: &BlsPublicKey,
+        epoch: u64,
+    ) -> Option<EpochRewards> {
+        if epoch < oldest_snapshot_epoch(epoch_at(abi::block_height())) {
+            return None;
+        }
+
+        let key = account.to_bytes();
+        let (&(_, snapshot_epoch), snapshot) = self
+            .reward_snapshots
+            .range((key, 0)..=(key, epoch))
+            .next_back()?;
+
+        if snapshot_epoch == epoch {
+            Some(*snapshot)
+        } else {
+            Some(EpochRewards {
+                claimable: snapshot.claimable,
+                ..EpochRewards::default()
+            })
+        }
     }
 
     /// Gets a reference to a stake.
@@ -480,6 +982,8 @@ impl StakeState {
     /// If a stake does not exist in the map, it is skipped.
     pub fn reward(&mut self, rewards: Vec<Reward>) {
         for reward in &rewards {
+            let value = self.share_reward(&reward.account, reward.value);
+
             let stake =
                 if let Some((stake, _)) = self.get_stake_mut(&reward.account) {
                     // Reset faults counters
@@ -492,7 +996,9 @@ impl StakeState {
                     stake
                 };
 
-            stake.reward += reward.value;
+            stake.reward += value;
+            let claimable = stake.reward;
+            self.snapshot_rewards(&reward.account, value, 0, claimable);
         }
         if !rewards.is_empty() {
             abi::emit("reward", rewards);
@@ -516,10 +1022,10 @@ impl StakeState {
     /// next epoch as well
     pub fn slash(&mut self, account: &BlsPublicKey, to_slash: Option<u64>) {
         let stake_warnings = self.config.warnings;
+        let prev_stake = self.weighted_stake(account);
         let (stake, _) = self
             .get_stake_mut(account)
             .expect("The stake to slash should exist");
-        let prev_stake = Some(*stake);
 
         // Stake can have no amount if provisioner unstake in the same block
         if stake.amount.is_none() {
@@ -549,9 +1055,14 @@ impl StakeState {
             .unwrap_or(stake_amount.value / 100 * effective_faults * 10);
         let to_slash = min(to_slash, stake_amount.value);
 
+        let staked = stake_amount.value;
         if to_slash > 0 {
             stake_amount.lock_amount(to_slash);
         }
+        let next_eligibility = stake_amount.eligibility;
+
+        // the delegations are slashed in the same proportion as the stake
+        self.slash_delegations(account, staked, to_slash, false);
 
         if to_slash > 0 || effective_faults > 0 {
             abi::emit(
@@ -559,7 +1070,7 @@ impl StakeState {
                 SlashEvent {
                     account: *account,
                     value: to_slash,
-                    next_eligibility: stake_amount.eligibility,
+                    next_eligibility,
                 },
             );
         }
@@ -580,6 +1091,7 @@ impl StakeState {
         to_slash: Option<u64>,
         severity: Option<u8>,
     ) {
+        let prev_stake = self.weighted_stake(account);
         let (stake, _) = self
             .get_stake_mut(account)
             .expect("The stake to slash should exist");
@@ -589,8 +1101,6 @@ impl StakeState {
             return;
         }
 
-        let prev_stake = Some(*stake);
-
         let stake_amount = stake.amount.as_mut().expect("stake_to_exists");
 
         let severity = severity.unwrap_or(1);
@@ -608,6 +1118,7 @@ impl StakeState {
         let to_slash =
             to_slash.unwrap_or(stake_amount.value / 100 * hard_faults * 10);
         let to_slash = min(to_slash, stake_amount.value);
+        let staked = stake_amount.value;
 
         if to_slash > 0 {
             // Update the staked amount
@@ -618,6 +1129,9 @@ impl StakeState {
             self.burnt_amount += to_slash;
         }
 
+        // the delegations are slashed in the same proportion as the stake
+        self.slash_delegations(account, staked, to_slash, true);
+
         abi::emit(
             "hard_slash",
             SlashEvent {
@@ -633,6 +1147,63 @@ impl StakeState {
             .or_insert_with(|| (prev_stake, *account));
     }
 
+    /// Slashes the delegations to a provisioner in the same proportion as
+    /// `to_slash` is of the `staked` value of the provisioner.
+    ///
+    /// A soft slash locks the slashed value of each delegation, while a hard
+    /// slash burns it.
+    fn slash_delegations(
+        &mut self,
+        provisioner: &BlsPublicKey,
+        staked: u64,
+        to_slash: u64,
+        hard: bool,
+    ) {
+        if staked == 0 || to_slash == 0 {
+            return;
+        }
+
+        let delegations =
+            match self.delegations.get_mut(&provisioner.to_bytes()) {
+                Some(delegations) => delegations,
+                None => return,
+            };
+
+        let mut total = 0;
+        for (delegation, delegator) in delegations.values_mut() {
+            let slashed = (delegation.value as u128 * to_slash as u128
+                / staked as u128) as u64;
+            if slashed == 0 {
+                continue;
+            }
+
+            delegation.value -= slashed;
+            if !hard {
+                delegation.locked += slashed;
+            }
+            total += slashed;
+
+            abi::emit(
+                if hard {
+                    "hard_slash_delegation"
+                } else {
+                    "slash_delegation"
+                },
+                DelegationEvent {
+                    provisioner: *provisioner,
+                    delegator: *delegator,
+                    value: slashed,
+                },
+            );
+        }
+
+        if hard && total > 0 {
+            Self::deduct_contract_balance(total);
+            self.burnt_amount += total;
+        }
+        emit_delegated_stake("undelegated_stake", *provisioner, total);
+    }
+
     /// Sets the burnt amount
     pub fn set_burnt_amount(&mut self, burnt_amount: u64) {
         self.burnt_amount = burnt_amount;
@@ -645,6 +1216,46 @@ impl StakeState {
         }
     }
 
+    /// Feeds the host with the stakes as weighted in the consensus, with the
+    /// value delegated to each provisioner added to its staked value.
+    pub fn weighted_stakes(&self) {
+        for (stake_data, keys) in self.stakes.values() {
+            abi::feed((*keys, self.weighted(&keys.account, *stake_data)));
+        }
+    }
+
+    /// The value delegated to a provisioner.
+    fn delegated(&self, provisioner: &BlsPublicKey) -> u64 {
+        self.delegations
+            .get(&provisioner.to_bytes())
+            .map(|delegations| {
+                delegations
+                    .values()
+                    .map(|(delegation, _)| delegation.value)
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The stake of a provisioner as weighted in the consensus. The value
+    /// delegated to it adds to its staked value only while it has a stake.
+    fn weighted(
+        &self,
+        account: &BlsPublicKey,
+        mut stake: StakeData,
+    ) -> StakeData {
+        if let Some(amount) = &mut stake.amount {
+            amount.value += self.delegated(account);
+        }
+        stake
+    }
+
+    /// The weighted stake of an account, if it has one.
+    fn weighted_stake(&self, account: &BlsPublicKey) -> Option<StakeData> {
+        self.get_stake(account)
+            .map(|stake| self.weighted(account, *stake))
+    }
+
     fn chain_id(&self) -> u8 {
         abi::chain_id()
     }
@@ -667,3 +1278,22 @@ impl StakeState {
         }
     }
 }
+
+/// Notifies the change of the delegated value adding to the weight of a
+/// provisioner, with either the `delegated_stake` or `undelegated_stake`
+/// topic.
+fn emit_delegated_stake(
+    topic: &'static str,
+    account: BlsPublicKey,
+    value: u64,
+) {
+    if value > 0 {
+        abi::emit(topic, DelegatedStakeEvent { account, value });
+    }
+}
+
+/// The oldest epoch in the window of retained reward snapshots ending at the
+/// given epoch.
+const fn oldest_snapshot_epoch(epoch: u64) -> u64 {
+    epoch.saturating_sub(REWARD_SNAPSHOT_EPOCHS - 1)
+}
@@ -4,11 +4,17 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::sync::mpsc;
+
 use dusk_core::dusk;
 use dusk_core::signatures::bls::{
     PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
 };
-use dusk_core::stake::{Reward, RewardReason, EPOCH, STAKE_CONTRACT};
+use dusk_core::stake::{
+    epoch_at, DelegationData, EpochRewards, Reward, RewardReason, StakeData,
+    StakeKeys, EPOCH, MAX_COMMISSION, MINIMUM_DELEGATION,
+    REWARD_SNAPSHOT_EPOCHS, STAKE_CONTRACT,
+};
 use dusk_core::transfer::TRANSFER_CONTRACT;
 use dusk_vm::{
     execute, ContractData, Error as VMError, ExecutionConfig, Session, VM,
@@ -16,7 +22,9 @@ use dusk_vm::{
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use wallet_core::transaction::{
-    moonlight_stake, moonlight_stake_reward, moonlight_unstake,
+    moonlight_delegate, moonlight_delegation_reward, moonlight_set_commission,
+    moonlight_set_reward_address, moonlight_stake, moonlight_stake_reward,
+    moonlight_undelegate, moonlight_unstake,
 };
 
 pub mod common;
@@ -434,6 +442,824 @@ fn withdraw_reward() -> Result<(), VMError> {
     Ok(())
 }
 
+#[test]
+fn withdraw_reward_to_reward_address() -> Result<(), VMError> {
+    // ------
+    // instantiate the test
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sk = BlsSecretKey::random(rng);
+    let moonlight_pk = BlsPublicKey::from(&moonlight_sk);
+
+    let stake_sk = BlsSecretKey::random(rng);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+
+    let cold_pk = BlsPublicKey::from(&BlsSecretKey::random(rng));
+
+    let mut vm = &mut VM::ephemeral()?;
+    let mut session = instantiate(&mut vm, &moonlight_pk);
+
+    // initial stake
+    let mut nonce = GENESIS_NONCE + 1;
+    let tx = moonlight_stake(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        STAKE_VALUE,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    let mut moonlight_balance = GENESIS_VALUE - STAKE_VALUE - receipt.gas_spent;
+
+    // ------
+    // Redirect the rewards to the cold account
+
+    nonce += 1;
+    let tx = moonlight_set_reward_address(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        Some(cold_pk),
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    moonlight_balance -= receipt.gas_spent;
+    assert!(receipt
+        .events
+        .iter()
+        .any(|event| event.topic == "reward_address"));
+
+    // replaying a reward address with the same stake nonce fails
+    nonce += 1;
+    let tx = moonlight_set_reward_address(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        None,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(receipt.data.is_err(), "replaying a stake nonce should fail");
+    moonlight_balance -= receipt.gas_spent;
+
+    const REWARD_AMOUNT: u64 = dusk(3.0);
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    // ------
+    // Withdraw the reward, which is minted to the cold account
+
+    nonce += 1;
+    let tx = moonlight_stake_reward(
+        rng,
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        REWARD_AMOUNT,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+
+    assert_stake_event(
+        &receipt.events,
+        "withdraw",
+        &stake_pk,
+        REWARD_AMOUNT,
+        0,
+    );
+    assert_stake(&mut session, &stake_pk, STAKE_VALUE, 0, 0);
+    moonlight_balance -= receipt.gas_spent;
+    assert_moonlight(&mut session, &moonlight_pk, moonlight_balance, nonce);
+    assert_moonlight(&mut session, &cold_pk, REWARD_AMOUNT, 0);
+
+    Ok(())
+}
+
+#[test]
+fn rewards_at_epoch() -> Result<(), VMError> {
+    // ------
+    // instantiate the test
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sk = BlsSecretKey::random(rng);
+    let moonlight_pk = BlsPublicKey::from(&moonlight_sk);
+
+    let stake_sk = BlsSecretKey::random(rng);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+
+    let mut vm = &mut VM::ephemeral()?;
+    let mut session = instantiate(&mut vm, &moonlight_pk);
+
+    // initial stake
+    let mut nonce = GENESIS_NONCE + 1;
+    let tx = moonlight_stake(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        STAKE_VALUE,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    // there are no snapshots before the first reward
+    assert_eq!(get_rewards_at_epoch(&mut session, &stake_pk, 0)?, None);
+
+    // ------
+    // Reward and withdraw during the first epoch
+
+    const REWARD_AMOUNT: u64 = dusk(3.0);
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    nonce += 1;
+    let tx = moonlight_stake_reward(
+        rng,
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        dusk(1.0),
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    let first_epoch = EpochRewards {
+        accrued: REWARD_AMOUNT,
+        withdrawn: dusk(1.0),
+        claimable: dusk(2.0),
+    };
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, 0)?,
+        Some(first_epoch)
+    );
+
+    // ------
+    // Reward two epochs later
+
+    let base = session.commit()?;
+    let mut session = vm.session(base, CHAIN_ID, 2 * EPOCH)?;
+    let epoch = epoch_at(2 * EPOCH);
+
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    // past snapshots are kept, and epochs without changes carry over the
+    // claimable rewards
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, 0)?,
+        Some(first_epoch)
+    );
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, epoch - 1)?,
+        Some(EpochRewards {
+            accrued: 0,
+            withdrawn: 0,
+            claimable: dusk(2.0),
+        })
+    );
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, epoch)?,
+        Some(EpochRewards {
+            accrued: REWARD_AMOUNT,
+            withdrawn: 0,
+            claimable: dusk(5.0),
+        })
+    );
+
+    // ------
+    // Reward once the first epochs fell out of the retained window
+
+    let base = session.commit()?;
+    let last_epoch = epoch + REWARD_SNAPSHOT_EPOCHS + 1;
+    let mut session = vm.session(base, CHAIN_ID, last_epoch * EPOCH)?;
+
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    // snapshots older than the window are pruned, while the epochs at the
+    // start of the window still carry over the claimable rewards
+    let oldest_epoch = last_epoch - REWARD_SNAPSHOT_EPOCHS + 1;
+    assert_eq!(get_rewards_at_epoch(&mut session, &stake_pk, 0)?, None);
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, oldest_epoch - 1)?,
+        None
+    );
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, oldest_epoch)?,
+        Some(EpochRewards {
+            accrued: 0,
+            withdrawn: 0,
+            claimable: dusk(5.0),
+        })
+    );
+    assert_eq!(
+        get_rewards_at_epoch(&mut session, &stake_pk, last_epoch)?,
+        Some(EpochRewards {
+            accrued: REWARD_AMOUNT,
+            withdrawn: 0,
+            claimable: dusk(8.0),
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn delegate() -> Result<(), VMError> {
+    // ------
+    // instantiate the test
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sk = BlsSecretKey::random(rng);
+    let moonlight_pk = BlsPublicKey::from(&moonlight_sk);
+
+    let stake_sk = BlsSecretKey::random(rng);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+
+    let mut vm = &mut VM::ephemeral()?;
+    let mut session = instantiate(&mut vm, &moonlight_pk);
+
+    // initial stake, with a commission of 10% of the provisioner's rewards
+    let stake_value = STAKE_VALUE / 2;
+    let mut nonce = GENESIS_NONCE + 1;
+    let tx = moonlight_stake(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        stake_value,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    let mut moonlight_balance = GENESIS_VALUE - stake_value - receipt.gas_spent;
+
+    const COMMISSION: u16 = MAX_COMMISSION / 10;
+    nonce += 1;
+    let tx = moonlight_set_commission(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        COMMISSION,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    moonlight_balance -= receipt.gas_spent;
+
+    // replaying a commission with the same stake nonce fails
+    nonce += 1;
+    let tx = moonlight_set_commission(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        MAX_COMMISSION,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(receipt.data.is_err(), "replaying a stake nonce should fail");
+    moonlight_balance -= receipt.gas_spent;
+    let stake_nonce = session
+        .call::<_, u64>(STAKE_CONTRACT, "get_nonce", &stake_pk, GAS_LIMIT)?
+        .data;
+    assert_eq!(stake_nonce, 1);
+
+    // ------
+    // Delegate the same value as staked by the provisioner
+
+    let delegate_value = stake_value;
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegate_value,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+
+    let gas_spent = receipt.gas_spent;
+    println!("DELEGATE: {gas_spent} gas");
+    moonlight_balance -= delegate_value;
+    moonlight_balance -= gas_spent;
+    assert_moonlight(&mut session, &moonlight_pk, moonlight_balance, nonce);
+
+    // the delegated value adds to the weight of the provisioner
+    let weight = get_weight(&mut session, &stake_pk)?;
+    assert_eq!(weight, stake_value + delegate_value);
+
+    // replaying a delegation with the same stake nonce fails
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegate_value,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(receipt.data.is_err(), "replaying a stake nonce should fail");
+    moonlight_balance -= receipt.gas_spent;
+    assert_moonlight(&mut session, &moonlight_pk, moonlight_balance, nonce);
+    let delegation = get_delegation(&mut session, &stake_pk, &moonlight_pk)?;
+    assert_eq!(delegation.value, delegate_value);
+
+    // ------
+    // Reward the provisioner
+
+    const REWARD_AMOUNT: u64 = dusk(3.0);
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    // the delegation weighs as much as the stake, so it earns half of the
+    // reward, of which the provisioner keeps its commission
+    let earned = REWARD_AMOUNT / 2;
+    let commission = earned * COMMISSION as u64 / MAX_COMMISSION as u64;
+    let delegation_reward = earned - commission;
+    let provisioner_reward = REWARD_AMOUNT - delegation_reward;
+    assert_stake(&mut session, &stake_pk, stake_value, 0, provisioner_reward);
+    let delegation = get_delegation(&mut session, &stake_pk, &moonlight_pk)?;
+    assert_eq!(delegation.value, delegate_value);
+    assert_eq!(delegation.reward, delegation_reward);
+
+    // ------
+    // Withdraw the reward of the delegation
+
+    nonce += 1;
+    let tx = moonlight_delegation_reward(
+        rng,
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegation_reward,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+
+    let gas_spent = receipt.gas_spent;
+    println!("WITHDRAW DELEGATION: {gas_spent} gas");
+    moonlight_balance += delegation_reward;
+    moonlight_balance -= gas_spent;
+    assert_moonlight(&mut session, &moonlight_pk, moonlight_balance, nonce);
+
+    // ------
+    // Undelegate the whole value
+
+    nonce += 1;
+    let tx = moonlight_undelegate(
+        rng,
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegate_value,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+
+    let gas_spent = receipt.gas_spent;
+    println!("UNDELEGATE: {gas_spent} gas");
+    moonlight_balance += delegate_value;
+    moonlight_balance -= gas_spent;
+    assert_moonlight(&mut session, &moonlight_pk, moonlight_balance, nonce);
+
+    // the emptied delegation is removed from the state
+    let delegation = session
+        .call::<_, Option<DelegationData>>(
+            STAKE_CONTRACT,
+            "get_delegation",
+            &(stake_pk, moonlight_pk),
+            GAS_LIMIT,
+        )?
+        .data;
+    assert_eq!(delegation, None);
+    let weight = get_weight(&mut session, &stake_pk)?;
+    assert_eq!(weight, stake_value);
+
+    Ok(())
+}
+
+#[test]
+fn delegation_opt_in_and_slash() -> Result<(), VMError> {
+    // ------
+    // instantiate the test
+
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+
+    let moonlight_sk = BlsSecretKey::random(rng);
+    let moonlight_pk = BlsPublicKey::from(&moonlight_sk);
+
+    let stake_sk = BlsSecretKey::random(rng);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+
+    let mut vm = &mut VM::ephemeral()?;
+    let mut session = instantiate(&mut vm, &moonlight_pk);
+
+    let stake_value = STAKE_VALUE / 2;
+    let mut nonce = GENESIS_NONCE + 1;
+    let tx = moonlight_stake(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        stake_value,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    // ------
+    // Delegating fails until the provisioner opts in with a commission
+
+    let delegate_value = stake_value / 2;
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegate_value,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(
+        receipt.data.is_err(),
+        "delegating without opt-in should fail"
+    );
+
+    nonce += 1;
+    let tx = moonlight_set_commission(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        0,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    // delegations lower than the minimum are rejected
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        MINIMUM_DELEGATION - 1,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(
+        receipt.data.is_err(),
+        "delegating less than the minimum should fail"
+    );
+
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &moonlight_sk,
+        &stake_pk,
+        delegate_value,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(receipt.data.is_ok(), "delegating after opt-in should pass");
+
+    // ------
+    // A soft slash of 10% of the stake locks 10% of the delegation
+
+    let slash_amount = stake_value / 10;
+    session.call::<_, ()>(
+        STAKE_CONTRACT,
+        "slash",
+        &(stake_pk, Some(slash_amount)),
+        GAS_LIMIT,
+    )?;
+    let locked = delegate_value / 10;
+    let delegation = get_delegation(&mut session, &stake_pk, &moonlight_pk)?;
+    assert_eq!(delegation.value, delegate_value - locked);
+    assert_eq!(delegation.locked, locked);
+
+    // ------
+    // A hard slash of 10% of the stake burns 10% of the delegation
+
+    let staked = stake_value - slash_amount;
+    let delegated = delegate_value - locked;
+    let slash_amount = staked / 10;
+    session.call::<_, ()>(
+        STAKE_CONTRACT,
+        "hard_slash",
+        &(stake_pk, Some(slash_amount), None::<u8>),
+        GAS_LIMIT,
+    )?;
+    let burnt = delegated / 10;
+    let delegation = get_delegation(&mut session, &stake_pk, &moonlight_pk)?;
+    assert_eq!(delegation.value, delegated - burnt);
+    assert_eq!(delegation.locked, locked);
+
+    let burnt_amount = session
+        .call::<_, u64>(STAKE_CONTRACT, "burnt_amount", &(), GAS_LIMIT)?
+        .data;
+    assert_eq!(burnt_amount, slash_amount + burnt);
+
+    // the slashed delegation weighs less together with the stake
+    let weight = get_weight(&mut session, &stake_pk)?;
+    assert_eq!(weight, staked - slash_amount + delegated - burnt);
+
+    Ok(())
+}
+
+#[test]
+fn delegation_reward_split() -> Result<(), VMError> {
+    // ------
+    // instantiate the test
+
+    let rng = &mut StdRng::seed_from_u64(0xd1e9);
+
+    let moonlight_sk = BlsSecretKey::random(rng);
+    let moonlight_pk = BlsPublicKey::from(&moonlight_sk);
+
+    let stake_sk = BlsSecretKey::random(rng);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+
+    let delegator_sks: Vec<BlsSecretKey> =
+        (0..3).map(|_| BlsSecretKey::random(rng)).collect();
+    let delegator_pks: Vec<BlsPublicKey> =
+        delegator_sks.iter().map(BlsPublicKey::from).collect();
+
+    let mut vm = &mut VM::ephemeral()?;
+    let mut session = instantiate(&mut vm, &moonlight_pk);
+
+    let stake_value = STAKE_VALUE / 4;
+    let mut nonce = GENESIS_NONCE + 1;
+    let tx = moonlight_stake(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        stake_value,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    const COMMISSION: u16 = MAX_COMMISSION / 5;
+    nonce += 1;
+    let tx = moonlight_set_commission(
+        &moonlight_sk,
+        &stake_sk,
+        &stake_sk,
+        COMMISSION,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    execute(&mut session, &tx, &NO_CONFIG)?;
+
+    // the first two delegators delegate a quarter and three quarters of the
+    // stake, before it is eligible
+    let base_value = stake_value / 4;
+    for (delegator_sk, value) in
+        delegator_sks.iter().zip([base_value, 3 * base_value])
+    {
+        nonce += 1;
+        let tx = moonlight_delegate(
+            &moonlight_sk,
+            delegator_sk,
+            &stake_pk,
+            value,
+            1,
+            GAS_LIMIT,
+            GAS_PRICE,
+            nonce,
+            CHAIN_ID,
+        )
+        .expect("tx creation should pass");
+        let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+        assert!(receipt.data.is_ok(), "delegating should pass");
+    }
+
+    // ------
+    // The delegators earn the part of the reward proportional to the weight
+    // they add, minus the commission kept by the provisioner
+
+    const REWARD_AMOUNT: u64 = dusk(8.0);
+
+    let base = session.commit()?;
+    let mut session = vm.session(base, CHAIN_ID, 2 * EPOCH)?;
+
+    let weight = get_weight(&mut session, &stake_pk)?;
+    assert_eq!(weight, 2 * stake_value);
+
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    let first_share =
+        delegation_share(REWARD_AMOUNT, base_value, weight, COMMISSION);
+    let second_share =
+        delegation_share(REWARD_AMOUNT, 3 * base_value, weight, COMMISSION);
+    let mut provisioner_reward = REWARD_AMOUNT - first_share - second_share;
+    assert_stake(&mut session, &stake_pk, stake_value, 0, provisioner_reward);
+
+    let first = get_delegation(&mut session, &stake_pk, &delegator_pks[0])?;
+    let second = get_delegation(&mut session, &stake_pk, &delegator_pks[1])?;
+    assert_eq!(first.reward, first_share);
+    assert_eq!(second.reward, second_share);
+
+    // ------
+    // Delegating to an eligible provisioner locks part of the value, like a
+    // top-up of the stake, and only the rest adds to its weight
+
+    let third_value = 4 * base_value;
+    nonce += 1;
+    let tx = moonlight_delegate(
+        &moonlight_sk,
+        &delegator_sks[2],
+        &stake_pk,
+        third_value,
+        1,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+    )
+    .expect("tx creation should pass");
+    let receipt = execute(&mut session, &tx, &NO_CONFIG)?;
+    assert!(receipt.data.is_ok(), "delegating should pass");
+
+    let third = get_delegation(&mut session, &stake_pk, &delegator_pks[2])?;
+    let third_locked = third_value / 10;
+    assert_eq!(third.value, third_value - third_locked);
+    assert_eq!(third.locked, third_locked);
+
+    let base = session.commit()?;
+    let mut session = vm.session(base, CHAIN_ID, 4 * EPOCH)?;
+
+    let weight = get_weight(&mut session, &stake_pk)?;
+    assert_eq!(weight, 2 * stake_value + third.value);
+
+    add_reward(&mut session, &stake_pk, REWARD_AMOUNT)?;
+
+    let shares = [base_value, 3 * base_value, third.value].map(|value| {
+        delegation_share(REWARD_AMOUNT, value, weight, COMMISSION)
+    });
+    provisioner_reward += REWARD_AMOUNT - shares.iter().sum::<u64>();
+    assert_stake(&mut session, &stake_pk, stake_value, 0, provisioner_reward);
+
+    let first = get_delegation(&mut session, &stake_pk, &delegator_pks[0])?;
+    let second = get_delegation(&mut session, &stake_pk, &delegator_pks[1])?;
+    let third = get_delegation(&mut session, &stake_pk, &delegator_pks[2])?;
+    assert_eq!(first.reward, first_share + shares[0]);
+    assert_eq!(second.reward, second_share + shares[1]);
+    assert_eq!(third.reward, shares[2]);
+
+    Ok(())
+}
+
+/// The reward earned by a delegation of the given value to a provisioner of
+/// the given weight, once the provisioner kept its commission.
+fn delegation_share(reward: u64, value: u64, weight: u64, rate: u16) -> u64 {
+    let earned = (reward as u128 * value as u128 / weight as u128) as u64;
+    earned - earned * rate as u64 / MAX_COMMISSION as u64
+}
+
+/// The weight of a provisioner in the consensus, as fed by the stake contract.
+fn get_weight(
+    session: &mut Session,
+    provisioner: &BlsPublicKey,
+) -> Result<u64, VMError> {
+    let (feeder, receiver) = mpsc::channel();
+    session.feeder_call::<_, ()>(
+        STAKE_CONTRACT,
+        "weighted_stakes",
+        &(),
+        GAS_LIMIT,
+        feeder,
+    )?;
+
+    let weight = receiver
+        .iter()
+        .map(|bytes| {
+            rkyv::from_bytes::<(StakeKeys, StakeData)>(&bytes)
+                .expect("The contract should feed the weighted stakes")
+        })
+        .find(|(keys, _)| keys.account == *provisioner)
+        .and_then(|(_, stake)| stake.amount)
+        .map(|amount| amount.value)
+        .unwrap_or_default();
+
+    Ok(weight)
+}
+
+fn get_delegation(
+    session: &mut Session,
+    provisioner: &BlsPublicKey,
+    delegator: &BlsPublicKey,
+) -> Result<DelegationData, VMError> {
+    let delegation = session
+        .call::<_, Option<DelegationData>>(
+            STAKE_CONTRACT,
+            "get_delegation",
+            &(*provisioner, *delegator),
+            GAS_LIMIT,
+        )?
+        .data
+        .expect("The delegation should exist");
+
+    Ok(delegation)
+}
+
+fn get_rewards_at_epoch(
+    session: &mut Session,
+    stake_pk: &BlsPublicKey,
+    epoch: u64,
+) -> Result<Option<EpochRewards>, VMError> {
+    let rewards = session
+        .call::<_, Option<EpochRewards>>(
+            STAKE_CONTRACT,
+            "rewards_at_epoch",
+            &(*stake_pk, epoch),
+            GAS_LIMIT,
+        )?
+        .data;
+
+    Ok(rewards)
+}
+
 fn add_reward(
     session: &mut Session,
     stake_pk: &BlsPublicKey,
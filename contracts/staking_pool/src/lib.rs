@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use dusk_core::abi;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+
+mod state;
+use state::StakingPool;
+
+static mut STATE: StakingPool = StakingPool::new();
+
+// Initialization
+
+#[no_mangle]
+unsafe fn init(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.init(arg))
+}
+
+// Transactions
+
+#[no_mangle]
+unsafe fn open(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |stake| STATE.open(stake))
+}
+
+#[no_mangle]
+unsafe fn deposit(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |value| STATE.deposit(value))
+}
+
+#[no_mangle]
+unsafe fn collect_rewards(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.collect_rewards())
+}
+
+#[no_mangle]
+unsafe fn claim(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.claim())
+}
+
+#[no_mangle]
+unsafe fn request_withdrawal(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |shares| STATE.request_withdrawal(shares))
+}
+
+#[no_mangle]
+unsafe fn process_withdrawals(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.process_withdrawals())
+}
+
+// Callbacks of the stake contract, through the transfer contract
+
+#[no_mangle]
+unsafe fn receive_reward(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |receive| {
+        assert_transfer_caller();
+        STATE.receive_reward(receive)
+    })
+}
+
+#[no_mangle]
+unsafe fn receive_unstake(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |receive| {
+        assert_transfer_caller();
+        STATE.receive_unstake(receive)
+    })
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn info(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.info())
+}
+
+#[no_mangle]
+unsafe fn account(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |pk| STATE.account(&pk))
+}
+
+#[no_mangle]
+unsafe fn withdrawal_queue(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.withdrawal_queue().to_vec())
+}
+
+fn assert_transfer_caller() {
+    const PANIC_MSG: &str = "Can only be called from the transfer contract";
+    if abi::caller().expect(PANIC_MSG) != TRANSFER_CONTRACT {
+        panic!("{PANIC_MSG}");
+    }
+}
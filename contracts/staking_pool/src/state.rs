@@ -0,0 +1,437 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::stake::{
+    Stake, StakeData, StakeFundOwner, StakeKeys, WithdrawToContract,
+    STAKE_CONTRACT,
+};
+use dusk_core::staking_pool::{
+    withdrawal_ready_at, ClaimEvent, DepositEvent, PoolAccount, PoolInfo,
+    PoolInit, RequestEvent, RewardEvent, WithdrawEvent, WithdrawalRequest,
+    CLAIM_TOPIC, DEPOSIT_TOPIC, REQUEST_TOPIC, REWARD_TOPIC, WITHDRAW_TOPIC,
+};
+use dusk_core::transfer::{
+    ContractToAccount, ContractToContract, ReceiveFromContract,
+    TRANSFER_CONTRACT,
+};
+
+const SCRATCH_BUF_BYTES: usize = 256;
+
+/// The precision of the rewards accumulated per share.
+const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// The position of an account in the pool.
+#[derive(Debug, Default, Clone)]
+struct Position {
+    shares: u64,
+    rewards: u64,
+    reward_debt: u128,
+}
+
+/// Contract pooling the deposits of many accounts to stake to a single
+/// provisioner.
+///
+/// Rewards are distributed with an accumulator of the rewards per share, so
+/// that distributing them doesn't depend on the number of depositors: the
+/// rewards of an account are its shares times the accumulator, minus the
+/// value of the accumulator when its shares last changed.
+///
+/// Slashes of the provisioner are not accounted for, and would leave the last
+/// withdrawals unable to be processed.
+#[derive(Debug, Default, Clone)]
+pub struct StakingPool {
+    provisioner: Option<AccountPublicKey>,
+    open: bool,
+    staked: u64,
+    shares: u64,
+    reward_per_share: u128,
+    positions: BTreeMap<Vec<u8>, Position>,
+    queue: Vec<WithdrawalRequest>,
+    queued: u64,
+}
+
+impl StakingPool {
+    pub const fn new() -> Self {
+        Self {
+            provisioner: None,
+            open: false,
+            staked: 0,
+            shares: 0,
+            reward_per_share: 0,
+            positions: BTreeMap::new(),
+            queue: Vec::new(),
+            queued: 0,
+        }
+    }
+
+    pub fn init(&mut self, init: PoolInit) {
+        if self.provisioner.is_some() {
+            panic!("The pool is already initialized");
+        }
+        self.provisioner = Some(init.provisioner);
+    }
+
+    /// Open the pool with a stake signed by the provisioner, which is
+    /// required by the stake contract to create the stake of the pool.
+    ///
+    /// The value of the stake is deposited by the calling transaction, and
+    /// the sender receives the corresponding shares.
+    pub fn open(&mut self, stake: Stake) {
+        if self.open {
+            panic!("The pool is already open");
+        }
+
+        let provisioner = self.provisioner();
+        if *stake.account() != provisioner {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The stake should be to the provisioner of the pool",
+            );
+        }
+        if stake.keys().owner != StakeFundOwner::Contract(abi::self_id()) {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The stake should be owned by the pool",
+            );
+        }
+
+        self.open = true;
+        self.stake(stake);
+    }
+
+    /// Deposit funds into the pool, from the deposit of the calling
+    /// transaction, staking them to the provisioner.
+    pub fn deposit(&mut self, value: u64) {
+        if !self.open {
+            abi::fail(ContractFailure::UNAUTHORIZED, "The pool is not open");
+        }
+
+        let keys = StakeKeys::new(self.provisioner(), abi::self_id());
+        let stake = Stake::new_unsigned(keys, value, abi::chain_id());
+
+        self.stake(stake);
+    }
+
+    /// Deposit the value of the given stake, mint the shares of the sender,
+    /// and stake the value through the transfer contract.
+    fn stake(&mut self, stake: Stake) {
+        let account = public_sender();
+        let value = stake.value();
+
+        let shares = if self.shares == 0 {
+            value
+        } else {
+            mul_div(value, self.shares, self.staked)
+        };
+        if shares == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The deposit is too small to receive shares",
+            );
+        }
+
+        let _: () = abi::call(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing funds into the pool should succeed");
+
+        let data = rkyv::to_bytes::<_, SCRATCH_BUF_BYTES>(&stake)
+            .expect("Serializing the stake should succeed")
+            .to_vec();
+        let contract_to_contract = ContractToContract {
+            contract: STAKE_CONTRACT,
+            value,
+            data,
+            fn_name: "stake_from_contract".into(),
+        };
+        let _: () = abi::call(
+            TRANSFER_CONTRACT,
+            "contract_to_contract",
+            &contract_to_contract,
+        )
+        .expect("Staking the deposit should succeed");
+
+        let reward_per_share = self.reward_per_share;
+        let position = self.position_mut(&account);
+        position.shares += shares;
+        position.reward_debt = accumulated(position.shares, reward_per_share);
+
+        self.staked += value;
+        self.shares += shares;
+
+        abi::emit(
+            DEPOSIT_TOPIC,
+            DepositEvent {
+                account,
+                value,
+                shares,
+            },
+        );
+    }
+
+    /// Withdraw the rewards of the provisioner from the stake contract,
+    /// distributing them to the shares of the pool.
+    ///
+    /// Anyone can collect the rewards of the pool.
+    pub fn collect_rewards(&mut self) {
+        let provisioner = self.provisioner();
+
+        let stake: Option<StakeData> =
+            abi::call(STAKE_CONTRACT, "get_stake", &provisioner)
+                .expect("Querying the stake should succeed");
+        let reward = stake.map(|stake| stake.reward).unwrap_or_default();
+        if reward == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "There are no rewards to collect",
+            );
+        }
+
+        let withdraw =
+            WithdrawToContract::new(provisioner, reward, "receive_reward");
+        let _: () =
+            abi::call(STAKE_CONTRACT, "withdraw_from_contract", &withdraw)
+                .expect("Withdrawing the rewards should succeed");
+    }
+
+    /// Distribute the rewards withdrawn from the stake contract.
+    ///
+    /// Rewards received while the pool has no shares stay in its balance.
+    pub fn receive_reward(&mut self, receive: ReceiveFromContract) {
+        assert_stake_sender(&receive);
+
+        if self.shares > 0 {
+            self.reward_per_share += u128::from(receive.value)
+                * REWARD_PRECISION
+                / u128::from(self.shares);
+        }
+
+        abi::emit(
+            REWARD_TOPIC,
+            RewardEvent {
+                value: receive.value,
+                shares: self.shares,
+            },
+        );
+    }
+
+    /// Pay the rewards of the sender to their account.
+    pub fn claim(&mut self) {
+        let account = public_sender();
+
+        self.settle(&account);
+        let position = self.position_mut(&account);
+        let value = position.rewards;
+        if value == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "There are no rewards to claim",
+            );
+        }
+        position.rewards = 0;
+
+        pay(account, value);
+
+        abi::emit(CLAIM_TOPIC, ClaimEvent { account, value });
+    }
+
+    /// Burn the given shares of the sender, queuing the withdrawal of their
+    /// value until the start of the next epoch.
+    ///
+    /// The pool is closed when its last shares are burned.
+    pub fn request_withdrawal(&mut self, shares: u64) {
+        let account = public_sender();
+
+        self.settle(&account);
+        let position = self.position_mut(&account);
+        if shares == 0 || shares > position.shares {
+            abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "The account doesn't own enough shares",
+            );
+        }
+
+        let value = if shares == self.shares {
+            self.staked
+        } else {
+            mul_div(shares, self.staked, self.shares)
+        };
+
+        let reward_per_share = self.reward_per_share;
+        let position = self.position_mut(&account);
+        position.shares -= shares;
+        position.reward_debt = accumulated(position.shares, reward_per_share);
+
+        self.shares -= shares;
+        self.staked -= value;
+        self.queued += value;
+        if self.shares == 0 {
+            self.open = false;
+        }
+
+        let request = WithdrawalRequest {
+            account,
+            value,
+            ready_at: withdrawal_ready_at(abi::block_height()),
+        };
+        self.queue.push(request.clone());
+
+        abi::emit(REQUEST_TOPIC, RequestEvent { shares, request });
+    }
+
+    /// Unstake the value of the withdrawals that are ready, and pay them out.
+    ///
+    /// Anyone can process the withdrawal queue.
+    pub fn process_withdrawals(&mut self) {
+        let block_height = abi::block_height();
+
+        let ready = self
+            .queue
+            .iter()
+            .take_while(|request| request.ready_at <= block_height)
+            .count();
+        if ready == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "There are no withdrawals ready",
+            );
+        }
+
+        let requests: Vec<_> = self.queue.drain(..ready).collect();
+        let value = requests.iter().map(|request| request.value).sum();
+
+        let withdraw = WithdrawToContract::new(
+            self.provisioner(),
+            value,
+            "receive_unstake",
+        );
+        let _: () =
+            abi::call(STAKE_CONTRACT, "unstake_from_contract", &withdraw)
+                .expect("Unstaking the withdrawals should succeed");
+        self.queued -= value;
+
+        for request in requests {
+            pay(request.account, request.value);
+            abi::emit(
+                WITHDRAW_TOPIC,
+                WithdrawEvent {
+                    account: request.account,
+                    value: request.value,
+                },
+            );
+        }
+    }
+
+    /// Receive the value unstaked for the withdrawals being processed.
+    pub fn receive_unstake(&mut self, receive: ReceiveFromContract) {
+        assert_stake_sender(&receive);
+    }
+
+    /// The state of the pool.
+    pub fn info(&self) -> PoolInfo {
+        PoolInfo {
+            provisioner: self.provisioner(),
+            open: self.open,
+            staked: self.staked,
+            shares: self.shares,
+            queued: self.queued,
+        }
+    }
+
+    /// The position of the given account in the pool.
+    pub fn account(&self, account: &AccountPublicKey) -> PoolAccount {
+        let key = account.to_raw_bytes().to_vec();
+        match self.positions.get(&key) {
+            Some(position) => PoolAccount {
+                shares: position.shares,
+                rewards: position.rewards + self.pending(position),
+            },
+            None => PoolAccount {
+                shares: 0,
+                rewards: 0,
+            },
+        }
+    }
+
+    /// The withdrawals waiting in the queue, in the order they were
+    /// requested.
+    pub fn withdrawal_queue(&self) -> &[WithdrawalRequest] {
+        &self.queue
+    }
+
+    fn provisioner(&self) -> AccountPublicKey {
+        self.provisioner.expect("The pool should be initialized")
+    }
+
+    fn position_mut(&mut self, account: &AccountPublicKey) -> &mut Position {
+        let key = account.to_raw_bytes().to_vec();
+        self.positions.entry(key).or_default()
+    }
+
+    /// The rewards accumulated by a position since its shares last changed.
+    fn pending(&self, position: &Position) -> u64 {
+        let accumulated = accumulated(position.shares, self.reward_per_share);
+        (accumulated - position.reward_debt) as u64
+    }
+
+    /// Move the rewards accumulated by the position of the given account to
+    /// its claimable rewards.
+    fn settle(&mut self, account: &AccountPublicKey) {
+        let reward_per_share = self.reward_per_share;
+        let key = account.to_raw_bytes().to_vec();
+        let position = self.positions.entry(key).or_default();
+
+        let accumulated = accumulated(position.shares, reward_per_share);
+        position.rewards += (accumulated - position.reward_debt) as u64;
+        position.reward_debt = accumulated;
+    }
+}
+
+/// The rewards accumulated by the given shares, scaled by the
+/// `REWARD_PRECISION`.
+fn accumulated(shares: u64, reward_per_share: u128) -> u128 {
+    u128::from(shares) * reward_per_share / REWARD_PRECISION
+}
+
+/// Compute `value * numerator / denominator` without overflowing.
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> u64 {
+    (u128::from(value) * u128::from(numerator) / u128::from(denominator)) as u64
+}
+
+/// Assert the funds received through the transfer contract are sent by the
+/// stake contract.
+fn assert_stake_sender(receive: &ReceiveFromContract) {
+    if receive.contract != STAKE_CONTRACT {
+        panic!("Can only receive funds from the stake contract");
+    }
+}
+
+/// Pay the given value out of the pool's balance to the given account.
+fn pay(account: AccountPublicKey, value: u64) {
+    abi::call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "contract_to_account",
+        &ContractToAccount { account, value },
+    )
+    .expect("Paying out of the pool should succeed");
+}
+
+/// The public sender of the calling transaction.
+///
+/// # Panics
+/// When the contract is called by a shielded transaction.
+fn public_sender() -> AccountPublicKey {
+    match abi::public_sender() {
+        Some(pk) => pk,
+        None => abi::fail(
+            ContractFailure::UNAUTHORIZED,
+            "The pool is only open to public accounts",
+        ),
+    }
+}
@@ -0,0 +1,286 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use contract_test_utils::{Scenario, ScenarioBuilder, GAS_LIMIT, GAS_PRICE};
+use dusk_core::abi::ContractId;
+use dusk_core::dusk;
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::stake::{
+    Reward, RewardReason, Stake, StakeData, EPOCH, STAKE_CONTRACT,
+};
+use dusk_core::staking_pool::{
+    withdrawal_ready_at, ClaimEvent, PoolAccount, PoolInfo, PoolInit,
+    WithdrawalRequest, CLAIM_TOPIC, DEPOSIT_TOPIC, REWARD_TOPIC,
+    WITHDRAW_TOPIC,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(10_000.0);
+
+const POOL_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x27;
+    ContractId::from_bytes(bytes)
+};
+
+/// Start a scenario with the stake and staking-pool contracts deployed, the
+/// pool staking to the given provisioner, and the given moonlight accounts
+/// owning the `MOONLIGHT_GENESIS_VALUE`.
+fn instantiate(
+    provisioner: AccountPublicKey,
+    accounts: &[AccountPublicKey],
+) -> Scenario {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let stake_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/stake_contract.wasm"
+    );
+    let pool_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/staking_pool_contract.wasm"
+    );
+
+    let mut builder = ScenarioBuilder::new(transfer_bytecode)
+        .with_stake(stake_bytecode)
+        .deploy(POOL_ID, pool_bytecode, &PoolInit { provisioner });
+
+    for pk in accounts {
+        builder = builder.account(pk, MOONLIGHT_GENESIS_VALUE);
+    }
+
+    builder.build()
+}
+
+fn info(scenario: &mut Scenario) -> PoolInfo {
+    scenario.query(POOL_ID, "info", &())
+}
+
+fn pool_account(scenario: &mut Scenario, pk: &AccountPublicKey) -> PoolAccount {
+    scenario.query(POOL_ID, "account", pk)
+}
+
+fn pool_stake(scenario: &mut Scenario, provisioner: &AccountPublicKey) -> u64 {
+    scenario
+        .query::<_, Option<StakeData>>(STAKE_CONTRACT, "get_stake", provisioner)
+        .and_then(|stake| stake.amount)
+        .map(|amount| amount.total_funds())
+        .unwrap_or_default()
+}
+
+/// Reward the provisioner, as done by the node at the end of a block.
+fn reward(scenario: &mut Scenario, provisioner: &AccountPublicKey, value: u64) {
+    let rewards = vec![Reward {
+        account: *provisioner,
+        value,
+        reason: RewardReason::Other,
+    }];
+    scenario
+        .session()
+        .call::<_, ()>(STAKE_CONTRACT, "reward", &rewards, GAS_LIMIT)
+        .expect("Rewarding the provisioner should succeed");
+}
+
+#[test]
+fn deposit_reward_withdraw() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let provisioner_sk = AccountSecretKey::random(rng);
+    let provisioner_pk = AccountPublicKey::from(&provisioner_sk);
+    let alice_sk = AccountSecretKey::random(rng);
+    let alice_pk = AccountPublicKey::from(&alice_sk);
+
+    let scenario =
+        &mut instantiate(provisioner_pk, &[provisioner_pk, alice_pk]);
+    let chain_id = scenario.chain_id();
+
+    // deposits are refused until the provisioner opens the pool
+    scenario
+        .call_with_deposit(
+            &alice_sk,
+            POOL_ID,
+            "deposit",
+            &dusk(3_000.0),
+            dusk(3_000.0),
+        )
+        .assert_failure();
+
+    // the pool can only be opened with a stake signed by the provisioner
+    let stake =
+        Stake::new_from_contract(&alice_sk, POOL_ID, dusk(1_000.0), chain_id);
+    scenario
+        .call_with_deposit(&alice_sk, POOL_ID, "open", &stake, dusk(1_000.0))
+        .assert_failure();
+
+    let stake = Stake::new_from_contract(
+        &provisioner_sk,
+        POOL_ID,
+        dusk(1_000.0),
+        chain_id,
+    );
+    scenario
+        .call_with_deposit(
+            &provisioner_sk,
+            POOL_ID,
+            "open",
+            &stake,
+            dusk(1_000.0),
+        )
+        .assert_success()
+        .assert_event(POOL_ID, DEPOSIT_TOPIC);
+
+    scenario
+        .call_with_deposit(
+            &alice_sk,
+            POOL_ID,
+            "deposit",
+            &dusk(3_000.0),
+            dusk(3_000.0),
+        )
+        .assert_success()
+        .assert_event(POOL_ID, DEPOSIT_TOPIC);
+
+    let pool = info(scenario);
+    assert!(pool.open);
+    assert_eq!(pool.staked, dusk(4_000.0));
+    assert_eq!(pool.shares, dusk(4_000.0));
+    assert_eq!(pool_stake(scenario, &provisioner_pk), dusk(4_000.0));
+    assert_eq!(pool_account(scenario, &alice_pk).shares, dusk(3_000.0));
+
+    // rewards are distributed in proportion to the shares
+    reward(scenario, &provisioner_pk, dusk(400.0));
+    scenario
+        .call(&alice_sk, POOL_ID, "collect_rewards", &())
+        .assert_success()
+        .assert_event(POOL_ID, REWARD_TOPIC);
+    assert_eq!(scenario.contract_balance(POOL_ID), dusk(400.0));
+
+    assert_eq!(pool_account(scenario, &provisioner_pk).rewards, dusk(100.0));
+    assert_eq!(pool_account(scenario, &alice_pk).rewards, dusk(300.0));
+
+    let balance = scenario.balance(&alice_pk);
+    let outcome = scenario.call(&alice_sk, POOL_ID, "claim", &());
+    outcome.assert_success().assert_event_data(
+        POOL_ID,
+        CLAIM_TOPIC,
+        &ClaimEvent {
+            account: alice_pk,
+            value: dusk(300.0),
+        },
+    );
+    assert_eq!(
+        scenario.balance(&alice_pk),
+        balance + dusk(300.0) - outcome.gas_spent() * GAS_PRICE
+    );
+    assert_eq!(pool_account(scenario, &alice_pk).rewards, 0);
+    scenario
+        .call(&alice_sk, POOL_ID, "claim", &())
+        .assert_failure();
+
+    // withdrawals are queued until the end of the epoch
+    scenario
+        .call(&alice_sk, POOL_ID, "request_withdrawal", &dusk(4_000.0))
+        .assert_failure();
+    scenario
+        .call(&alice_sk, POOL_ID, "request_withdrawal", &dusk(3_000.0))
+        .assert_success();
+
+    let ready_at = withdrawal_ready_at(scenario.height());
+    assert_eq!(ready_at, EPOCH);
+    assert_eq!(
+        scenario.query::<_, Vec<WithdrawalRequest>>(
+            POOL_ID,
+            "withdrawal_queue",
+            &()
+        ),
+        vec![WithdrawalRequest {
+            account: alice_pk,
+            value: dusk(3_000.0),
+            ready_at,
+        }]
+    );
+
+    let pool = info(scenario);
+    assert_eq!(pool.staked, dusk(1_000.0));
+    assert_eq!(pool.shares, dusk(1_000.0));
+    assert_eq!(pool.queued, dusk(3_000.0));
+
+    scenario
+        .call(&provisioner_sk, POOL_ID, "process_withdrawals", &())
+        .assert_failure();
+
+    scenario.advance_to(ready_at);
+
+    let balance = scenario.balance(&alice_pk);
+    scenario
+        .call(&provisioner_sk, POOL_ID, "process_withdrawals", &())
+        .assert_success()
+        .assert_event(POOL_ID, WITHDRAW_TOPIC);
+    assert_eq!(scenario.balance(&alice_pk), balance + dusk(3_000.0));
+    assert_eq!(pool_stake(scenario, &provisioner_pk), dusk(1_000.0));
+    assert_eq!(info(scenario).queued, 0);
+
+    // the rewards of the provisioner are kept across the withdrawal
+    assert_eq!(pool_account(scenario, &provisioner_pk).rewards, dusk(100.0));
+}
+
+#[test]
+fn last_withdrawal_closes_pool() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let provisioner_sk = AccountSecretKey::random(rng);
+    let provisioner_pk = AccountPublicKey::from(&provisioner_sk);
+
+    let scenario = &mut instantiate(provisioner_pk, &[provisioner_pk]);
+    let chain_id = scenario.chain_id();
+
+    let stake = Stake::new_from_contract(
+        &provisioner_sk,
+        POOL_ID,
+        dusk(1_000.0),
+        chain_id,
+    );
+    scenario
+        .call_with_deposit(
+            &provisioner_sk,
+            POOL_ID,
+            "open",
+            &stake,
+            dusk(1_000.0),
+        )
+        .assert_success();
+
+    scenario
+        .call(
+            &provisioner_sk,
+            POOL_ID,
+            "request_withdrawal",
+            &dusk(1_000.0),
+        )
+        .assert_success();
+
+    let pool = info(scenario);
+    assert!(!pool.open);
+    assert_eq!(pool.shares, 0);
+
+    scenario
+        .call_with_deposit(
+            &provisioner_sk,
+            POOL_ID,
+            "deposit",
+            &dusk(1_000.0),
+            dusk(1_000.0),
+        )
+        .assert_failure();
+
+    scenario.advance_epochs(1);
+    scenario
+        .call(&provisioner_sk, POOL_ID, "process_withdrawals", &())
+        .assert_success();
+    assert_eq!(pool_stake(scenario, &provisioner_pk), 0);
+}
@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use crate::Outcome;
+
+/// The environment variable that, when set, makes gas reports write their
+/// baseline files instead of being compared against them.
+pub const UPDATE_GAS_BASELINE: &str = "UPDATE_GAS_BASELINE";
+
+/// The environment variable that, when set, turns gas regressions into
+/// warnings for every report.
+pub const WARN_GAS_REGRESSION: &str = "WARN_GAS_REGRESSION";
+
+/// The gas spent by named scenarios, compared against a baseline JSON file to
+/// catch changes making them more expensive.
+///
+/// A scenario regresses when it spends more gas than its baseline by more
+/// than the threshold of the report. Scenarios spending less gas are reported
+/// but never fail, while scenarios missing from the baseline always fail, so
+/// that they can't go untracked.
+///
+/// ```ignore
+/// let mut gas = GasReport::new().threshold(5);
+/// gas.record_outcome("transfer", &scenario.call(&alice_sk, ...));
+/// gas.record_outcome("stake", &scenario.call(&alice_sk, ...));
+/// gas.assert_baseline("token");
+/// ```
+#[derive(Debug, Default)]
+pub struct GasReport {
+    threshold: u64,
+    warn_only: bool,
+    entries: BTreeMap<String, u64>,
+}
+
+impl GasReport {
+    /// Create an empty report, failing on any increase of gas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the increase of gas tolerated, in percent of the baseline.
+    pub fn threshold(mut self, percent: u64) -> Self {
+        self.threshold = percent;
+        self
+    }
+
+    /// Warn about regressions instead of failing on them.
+    pub fn warn_only(mut self) -> Self {
+        self.warn_only = true;
+        self
+    }
+
+    /// Record the gas spent by the given scenario.
+    pub fn record(&mut self, name: &str, gas_spent: u64) -> &mut Self {
+        let previous = self.entries.insert(name.to_string(), gas_spent);
+        assert!(previous.is_none(), "\"{name}\" is already recorded");
+        self
+    }
+
+    /// Record the gas spent by the transaction of the given outcome.
+    pub fn record_outcome(
+        &mut self,
+        name: &str,
+        outcome: &Outcome,
+    ) -> &mut Self {
+        self.record(name, outcome.gas_spent())
+    }
+
+    /// The pretty-printed JSON of the report.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(&self.entries)
+            .expect("The gas report should serialize to JSON");
+        json.push('\n');
+        json
+    }
+
+    /// Assert the report doesn't regress from the baseline file of the given
+    /// name, in the `tests/gas` directory of the crate under test.
+    ///
+    /// A missing baseline file fails the assertion, so that a baseline that
+    /// was never committed can't pass silently. Set the
+    /// [`UPDATE_GAS_BASELINE`] environment variable to write it.
+    #[track_caller]
+    pub fn assert_baseline(&self, name: &str) {
+        let dir = env::var("CARGO_MANIFEST_DIR")
+            .expect("Gas baselines should be asserted from a cargo test");
+        let path = PathBuf::from(dir)
+            .join("tests")
+            .join("gas")
+            .join(format!("{name}.json"));
+        self.assert_baseline_file(path);
+    }
+
+    /// Assert the report doesn't regress from the given baseline file.
+    ///
+    /// A missing baseline file fails the assertion, so that a baseline that
+    /// was never committed can't pass silently. Set the
+    /// [`UPDATE_GAS_BASELINE`] environment variable to write it.
+    #[track_caller]
+    pub fn assert_baseline_file(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+
+        if env::var_os(UPDATE_GAS_BASELINE).is_some() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .expect("Creating the baselines directory should succeed");
+            }
+            fs::write(path, self.to_json())
+                .expect("Writing the baseline file should succeed");
+            return;
+        }
+        if !path.exists() {
+            panic!(
+                "The baseline file {} doesn't exist, set {UPDATE_GAS_BASELINE} \
+                 to create it",
+                path.display()
+            );
+        }
+
+        let baseline = fs::read_to_string(path)
+            .expect("Reading the baseline file should succeed");
+        let baseline: BTreeMap<String, u64> = serde_json::from_str(&baseline)
+            .expect("The baseline file should be a map of gas spent");
+
+        let mut untracked = String::new();
+        let mut regressions = String::new();
+        let mut changes = String::new();
+        for (name, &gas_spent) in &self.entries {
+            let Some(&base) = baseline.get(name) else {
+                untracked.push_str(&format!("  {name}: {gas_spent}\n"));
+                continue;
+            };
+            if gas_spent == base {
+                continue;
+            }
+
+            let line = format!("  {name}: {base} -> {gas_spent}\n");
+            // Compare `gas_spent / base > 1 + threshold / 100` in integers
+            let limit = base as u128 * (100 + self.threshold as u128);
+            if gas_spent as u128 * 100 > limit {
+                regressions.push_str(&line);
+            } else {
+                changes.push_str(&line);
+            }
+        }
+
+        if !untracked.is_empty() {
+            panic!(
+                "Gas isn't tracked by {}, set {UPDATE_GAS_BASELINE} to add \
+                 it:\n{untracked}",
+                path.display()
+            );
+        }
+        if !changes.is_empty() {
+            eprintln!(
+                "Gas changed from {}, set {UPDATE_GAS_BASELINE} to update \
+                 it:\n{changes}",
+                path.display()
+            );
+        }
+        if regressions.is_empty() {
+            return;
+        }
+
+        let message = format!(
+            "Gas regressed by more than {}% from {}, set \
+             {UPDATE_GAS_BASELINE} to accept it:\n{regressions}",
+            self.threshold,
+            path.display()
+        );
+        if self.warn_only || env::var_os(WARN_GAS_REGRESSION).is_some() {
+            eprintln!("{message}");
+        } else {
+            panic!("{message}");
+        }
+    }
+}
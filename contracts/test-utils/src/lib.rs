@@ -0,0 +1,505 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Utilities to write integration tests spanning the transfer, stake and
+//! custom contracts.
+//!
+//! A [`ScenarioBuilder`] sets up the genesis state of the chain - funded
+//! accounts, stakes and deployed contracts - and builds a [`Scenario`], which
+//! then drives it block by block: calling contracts as a given account,
+//! querying them, asserting on the events emitted and advancing the block
+//! height.
+//!
+//! ```ignore
+//! let mut scenario = ScenarioBuilder::new(TRANSFER_BYTECODE)
+//!     .account(&alice_pk, dusk(1_000.0))
+//!     .deploy(TOKEN_ID, TOKEN_BYTECODE, &init)
+//!     .build();
+//!
+//! scenario
+//!     .call(&alice_sk, TOKEN_ID, "transfer", &transfer)
+//!     .assert_success()
+//!     .assert_event(TOKEN_ID, TRANSFER_TOPIC);
+//!
+//! scenario.advance(10);
+//! ```
+//!
+//! A [`Snapshot`] captures the state and events of a scenario and compares
+//! them against a golden JSON file, instead of asserting on each of them.
+//! Golden files are written by running the tests with [`UPDATE_SNAPSHOTS`]
+//! set, and must be committed alongside them.
+//!
+//! A [`GasReport`] records the gas spent by named scenarios and fails when
+//! it grows past a baseline file, so that contract and VM changes can't
+//! silently make transactions more expensive. Baseline files are written by
+//! running the tests with [`UPDATE_GAS_BASELINE`] set, and must be committed
+//! alongside them.
+
+#![deny(missing_docs)]
+#![deny(clippy::all)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+use std::mem;
+
+use bytecheck::CheckBytes;
+use dusk_core::abi::{
+    ContractError, ContractFailure, ContractId, StandardBufSerializer,
+    ARGBUF_LEN,
+};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::stake::{epoch_at, StakeData, StakeKeys, EPOCH, STAKE_CONTRACT};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::LUX;
+use dusk_vm::{
+    execute, CallReceipt, ContractData, Event, ExecutionConfig, Session, VM,
+};
+use rkyv::de::deserializers::SharedDeserializeMap;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{Archive, Deserialize, Infallible, Serialize};
+
+mod gas;
+mod snapshot;
+pub use gas::{GasReport, UPDATE_GAS_BASELINE, WARN_GAS_REGRESSION};
+pub use snapshot::{Snapshot, UPDATE_SNAPSHOTS};
+
+/// The chain ID scenarios run on, unless set otherwise.
+pub const DEFAULT_CHAIN_ID: u8 = 0xFA;
+
+/// The gas limit of the transactions and calls made in a scenario.
+pub const GAS_LIMIT: u64 = 0x10000000;
+
+/// The gas price of the transactions made in a scenario.
+pub const GAS_PRICE: u64 = LUX;
+
+const OWNER: [u8; 32] = [0; 32];
+
+fn config() -> ExecutionConfig {
+    ExecutionConfig {
+        with_public_sender: true,
+        ..ExecutionConfig::DEFAULT
+    }
+}
+
+/// Builds the genesis state of a [`Scenario`].
+///
+/// The transfer contract is always deployed, while the stake contract is
+/// deployed with [`ScenarioBuilder::with_stake`]. Every other method applies
+/// its change to the genesis state right away, in the order they are called.
+pub struct ScenarioBuilder {
+    vm: VM,
+    session: Session,
+    chain_id: u8,
+}
+
+impl ScenarioBuilder {
+    /// Start building a scenario on the [`DEFAULT_CHAIN_ID`], deploying the
+    /// given transfer contract bytecode.
+    pub fn new(transfer_bytecode: &[u8]) -> Self {
+        Self::with_chain_id(transfer_bytecode, DEFAULT_CHAIN_ID)
+    }
+
+    /// Start building a scenario on the given chain, deploying the given
+    /// transfer contract bytecode.
+    pub fn with_chain_id(transfer_bytecode: &[u8], chain_id: u8) -> Self {
+        let vm = VM::ephemeral().expect("Creating ephemeral VM should work");
+        let mut session = vm.genesis_session(chain_id);
+
+        session
+            .deploy(
+                transfer_bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .contract_id(TRANSFER_CONTRACT),
+                GAS_LIMIT,
+            )
+            .expect("Deploying the transfer contract should succeed");
+
+        Self {
+            vm,
+            session,
+            chain_id,
+        }
+    }
+
+    /// Deploy the given stake contract bytecode.
+    pub fn with_stake(mut self, stake_bytecode: &[u8]) -> Self {
+        self.session
+            .deploy(
+                stake_bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .contract_id(STAKE_CONTRACT),
+                GAS_LIMIT,
+            )
+            .expect("Deploying the stake contract should succeed");
+        self
+    }
+
+    /// Fund the given moonlight account with the given balance.
+    pub fn account(mut self, pk: &AccountPublicKey, balance: u64) -> Self {
+        self.session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, balance),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+        self
+    }
+
+    /// Fund the given contract with the given balance.
+    pub fn contract_balance(
+        mut self,
+        contract: ContractId,
+        value: u64,
+    ) -> Self {
+        self.session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_contract_balance",
+                &(contract, value),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis contract balance should succeed");
+        self
+    }
+
+    /// Insert a stake of the given value for the given account, eligible from
+    /// genesis.
+    ///
+    /// The stake contract must have been deployed with
+    /// [`ScenarioBuilder::with_stake`].
+    pub fn stake(mut self, pk: &AccountPublicKey, value: u64) -> Self {
+        self.session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_contract_balance",
+                &(STAKE_CONTRACT, value),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis stake balance should succeed");
+        self.session
+            .call::<_, ()>(
+                STAKE_CONTRACT,
+                "insert_stake",
+                &(
+                    StakeKeys::single_key(*pk),
+                    StakeData::with_eligibility(value, 0, 0),
+                ),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis stake should succeed");
+        self
+    }
+
+    /// Deploy the given contract bytecode, without an `init` call.
+    pub fn contract(mut self, contract: ContractId, bytecode: &[u8]) -> Self {
+        self.session
+            .deploy(
+                bytecode,
+                ContractData::builder().owner(OWNER).contract_id(contract),
+                GAS_LIMIT,
+            )
+            .expect("Deploying the contract should succeed");
+        self
+    }
+
+    /// Deploy the given contract bytecode, calling its `init` function with
+    /// the given argument.
+    pub fn deploy<A>(
+        mut self,
+        contract: ContractId,
+        bytecode: &[u8],
+        init_arg: &A,
+    ) -> Self
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+    {
+        self.session
+            .deploy(
+                bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .contract_id(contract)
+                    .init_arg(init_arg),
+                GAS_LIMIT,
+            )
+            .expect("Deploying the contract should succeed");
+        self
+    }
+
+    /// Commit the genesis state, and start the scenario at block height 1.
+    pub fn build(self) -> Scenario {
+        let base = self.session.commit().expect("Committing should succeed");
+        let session = self
+            .vm
+            .session(base, self.chain_id, 1)
+            .expect("Instantiating new session should succeed");
+
+        Scenario {
+            vm: self.vm,
+            session,
+            chain_id: self.chain_id,
+            height: 1,
+        }
+    }
+}
+
+/// A running scenario, built by a [`ScenarioBuilder`].
+pub struct Scenario {
+    vm: VM,
+    session: Session,
+    chain_id: u8,
+    height: u64,
+}
+
+impl Scenario {
+    /// The chain ID the scenario runs on.
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    /// The current block height.
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// The epoch of the current block.
+    pub fn epoch(&self) -> u64 {
+        epoch_at(self.height)
+    }
+
+    /// The session of the current block, for the calls not covered by the
+    /// scenario.
+    pub fn session(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Call a contract from the given moonlight account, with a transaction
+    /// using the next nonce of the account.
+    pub fn call(
+        &mut self,
+        sender_sk: &AccountSecretKey,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+    ) -> Outcome {
+        self.call_with_deposit(sender_sk, contract, fn_name, fn_arg, 0)
+    }
+
+    /// Call a contract from the given moonlight account, depositing the given
+    /// value, with a transaction using the next nonce of the account.
+    pub fn call_with_deposit(
+        &mut self,
+        sender_sk: &AccountSecretKey,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+        deposit: u64,
+    ) -> Outcome {
+        let call = ContractCall::new(contract, fn_name, fn_arg)
+            .expect("Creating the contract call should succeed");
+
+        let sender_pk = AccountPublicKey::from(sender_sk);
+        let nonce = self.account(&sender_pk).nonce + 1;
+
+        let tx = wallet_core::transaction::moonlight(
+            sender_sk,
+            None,
+            0,
+            deposit,
+            GAS_LIMIT,
+            GAS_PRICE,
+            nonce,
+            self.chain_id,
+            Some(call),
+        )
+        .expect("Creating the transaction should succeed");
+
+        let receipt = execute(&mut self.session, &tx, &config())
+            .expect("Executing TX should succeed");
+
+        Outcome { receipt }
+    }
+
+    /// Query a contract, returning the data of the call.
+    pub fn query<A, R>(
+        &mut self,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> R
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .call(contract, fn_name, fn_arg, GAS_LIMIT)
+            .expect("Querying the contract should succeed")
+            .data
+    }
+
+    /// The data of the given moonlight account.
+    pub fn account(&mut self, pk: &AccountPublicKey) -> AccountData {
+        self.query(TRANSFER_CONTRACT, "account", pk)
+    }
+
+    /// The balance of the given moonlight account.
+    pub fn balance(&mut self, pk: &AccountPublicKey) -> u64 {
+        self.account(pk).balance
+    }
+
+    /// The balance of the given contract.
+    pub fn contract_balance(&mut self, contract: ContractId) -> u64 {
+        self.query(TRANSFER_CONTRACT, "contract_balance", &contract)
+    }
+
+    /// Commit the current block, and advance the block height by the given
+    /// number of blocks.
+    pub fn advance(&mut self, blocks: u64) {
+        self.advance_to(self.height + blocks);
+    }
+
+    /// Commit the current block, and advance to the first block of the epoch
+    /// the given number of epochs after the current one.
+    ///
+    /// As with [`Scenario::advance_to`], the blocks in between are skipped
+    /// rather than executed, so stake maturity and other epoch-bound logic
+    /// are reached in a single step.
+    pub fn advance_epochs(&mut self, epochs: u64) {
+        assert!(
+            epochs > 0,
+            "The scenario should advance by an epoch at least"
+        );
+        self.advance_to((self.epoch() + epochs) * EPOCH);
+    }
+
+    /// Commit the current block, and advance to the given block height.
+    ///
+    /// The blocks in between are skipped: the next session simply runs at the
+    /// given height.
+    pub fn advance_to(&mut self, height: u64) {
+        let session = mem::replace(
+            &mut self.session,
+            self.vm.genesis_session(self.chain_id),
+        );
+        let base = session.commit().expect("Committing should succeed");
+        self.session = self
+            .vm
+            .session(base, self.chain_id, height)
+            .expect("Instantiating new session should succeed");
+        self.height = height;
+    }
+}
+
+/// The outcome of a transaction executed in a [`Scenario`].
+#[derive(Debug)]
+pub struct Outcome {
+    /// The receipt of the transaction.
+    pub receipt: CallReceipt<Result<Vec<u8>, ContractError>>,
+}
+
+impl Outcome {
+    /// Whether the contract call succeeded.
+    pub fn succeeded(&self) -> bool {
+        self.receipt.data.is_ok()
+    }
+
+    /// The gas spent by the transaction.
+    pub fn gas_spent(&self) -> u64 {
+        self.receipt.gas_spent
+    }
+
+    /// The events emitted by the transaction.
+    pub fn events(&self) -> &[Event] {
+        &self.receipt.events
+    }
+
+    /// Assert the contract call succeeded.
+    #[track_caller]
+    pub fn assert_success(&self) -> &Self {
+        if let Err(err) = &self.receipt.data {
+            panic!("The call should succeed: {err:?}");
+        }
+        self
+    }
+
+    /// Assert the contract call failed with a panic.
+    #[track_caller]
+    pub fn assert_failure(&self) -> &Self {
+        match &self.receipt.data {
+            Ok(_) => panic!("The call should fail"),
+            Err(err) => {
+                assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            }
+        }
+        self
+    }
+
+    /// Assert the contract call failed with the given [`ContractFailure`]
+    /// code.
+    #[track_caller]
+    pub fn assert_failure_code(&self, code: u32) -> &Self {
+        match &self.receipt.data {
+            Ok(_) => panic!("The call should fail"),
+            Err(err) => {
+                let failure = ContractFailure::from_error(err)
+                    .expect("The call should fail with a failure code");
+                assert_eq!(failure.code, code, "{}", failure.message);
+            }
+        }
+        self
+    }
+
+    /// Assert the given contract emitted an event with the given topic.
+    #[track_caller]
+    pub fn assert_event(&self, source: ContractId, topic: &str) -> &Self {
+        self.find_event(source, topic);
+        self
+    }
+
+    /// Assert the given contract emitted an event with the given topic and
+    /// data.
+    #[track_caller]
+    pub fn assert_event_data<T>(
+        &self,
+        source: ContractId,
+        topic: &str,
+        expected: &T,
+    ) -> &Self
+    where
+        T: Archive + PartialEq + std::fmt::Debug,
+        T::Archived: Deserialize<T, SharedDeserializeMap>
+            + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        let event = self.find_event(source, topic);
+        let data = rkyv::from_bytes::<T>(&event.data)
+            .expect("The event data should deserialize correctly");
+        assert_eq!(&data, expected, "The event data should match");
+        self
+    }
+
+    #[track_caller]
+    fn find_event(&self, source: ContractId, topic: &str) -> &Event {
+        self.receipt
+            .events
+            .iter()
+            .find(|event| event.source == source && event.topic == topic)
+            .unwrap_or_else(|| {
+                panic!("No event emitted with the topic \"{topic}\"")
+            })
+    }
+}
@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use dusk_vm::Event;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// The environment variable that, when set, makes snapshots write their golden
+/// files instead of being compared against them.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// A structured capture of contract state and events, compared against a
+/// golden JSON file.
+///
+/// Entries are recorded under a name, and written sorted by name. Types
+/// implementing `serde::Serialize` are recorded as their JSON, and the others
+/// as their pretty-printed `Debug` output.
+///
+/// ```ignore
+/// let mut snapshot = Snapshot::new();
+/// snapshot
+///     .record_debug("alice", &scenario.account(&alice_pk))
+///     .record_events("transfer", outcome.events());
+/// snapshot.assert_matches("token_transfer");
+/// ```
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    entries: Map<String, Value>,
+}
+
+impl Snapshot {
+    /// Create an empty snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the JSON of the given value.
+    pub fn record<T: Serialize>(&mut self, name: &str, value: &T) -> &mut Self {
+        let value = serde_json::to_value(value)
+            .expect("The value should serialize to JSON");
+        self.insert(name, value)
+    }
+
+    /// Record the `Debug` output of the given value, one line per element so
+    /// that differences are shown line by line.
+    pub fn record_debug<T: Debug>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> &mut Self {
+        let lines = format!("{value:#?}").lines().map(Value::from).collect();
+        self.insert(name, Value::Array(lines))
+    }
+
+    /// Record the given events, with their data hex encoded.
+    pub fn record_events(&mut self, name: &str, events: &[Event]) -> &mut Self {
+        let events = events
+            .iter()
+            .map(|event| {
+                json!({
+                    "source": hex::encode(event.source.as_bytes()),
+                    "topic": event.topic,
+                    "data": hex::encode(&event.data),
+                })
+            })
+            .collect();
+        self.insert(name, Value::Array(events))
+    }
+
+    fn insert(&mut self, name: &str, value: Value) -> &mut Self {
+        let previous = self.entries.insert(name.to_string(), value);
+        assert!(previous.is_none(), "\"{name}\" is already recorded");
+        self
+    }
+
+    /// The pretty-printed JSON of the snapshot.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(&self.entries)
+            .expect("The snapshot should serialize to JSON");
+        json.push('\n');
+        json
+    }
+
+    /// Assert the snapshot matches the golden file of the given name, in the
+    /// `tests/snapshots` directory of the crate under test.
+    ///
+    /// A missing golden file fails the assertion, so that a snapshot that was
+    /// never committed can't pass silently. Set the [`UPDATE_SNAPSHOTS`]
+    /// environment variable to write it.
+    #[track_caller]
+    pub fn assert_matches(&self, name: &str) {
+        let dir = env::var("CARGO_MANIFEST_DIR")
+            .expect("Snapshots should be asserted from a cargo test");
+        let path = PathBuf::from(dir)
+            .join("tests")
+            .join("snapshots")
+            .join(format!("{name}.json"));
+        self.assert_matches_file(path);
+    }
+
+    /// Assert the snapshot matches the given golden file.
+    ///
+    /// A missing golden file fails the assertion, so that a snapshot that was
+    /// never committed can't pass silently. Set the [`UPDATE_SNAPSHOTS`]
+    /// environment variable to write it.
+    #[track_caller]
+    pub fn assert_matches_file(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.to_json();
+
+        if env::var_os(UPDATE_SNAPSHOTS).is_some() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .expect("Creating the snapshots directory should succeed");
+            }
+            fs::write(path, &actual)
+                .expect("Writing the golden file should succeed");
+            return;
+        }
+        if !path.exists() {
+            panic!(
+                "The golden file {} doesn't exist, set {UPDATE_SNAPSHOTS} to \
+                 create it",
+                path.display()
+            );
+        }
+
+        let expected = fs::read_to_string(path)
+            .expect("Reading the golden file should succeed");
+        if expected != actual {
+            panic!(
+                "The snapshot doesn't match {}, set {UPDATE_SNAPSHOTS} to \
+                 update it:\n{}",
+                path.display(),
+                diff(&expected, &actual)
+            );
+        }
+    }
+}
+
+/// A line by line diff of two texts, with the removed lines prefixed by `-`
+/// and the added ones by `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    // Length of the longest common subsequence of the suffixes
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            out.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < actual.len()
+            && (i == expected.len() || lcs[i][j + 1] >= lcs[i + 1][j])
+        {
+            out.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        }
+    }
+    out
+}
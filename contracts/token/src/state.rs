@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::token::{
+    Account, Approve, ApproveEvent, TokenInfo, TokenInit, TokenReceived,
+    Transfer, TransferEvent, TransferFrom, APPROVE_TOPIC, TOKEN_RECEIVED_FN,
+    TRANSFER_TOPIC,
+};
+use dusk_core::transfer::TRANSFER_CONTRACT;
+
+/// Contract keeping track of the balances and allowances of a fungible token.
+///
+/// The whole supply of the token is created when the contract is deployed,
+/// and distributed to the initial balances passed to `init`.
+///
+/// Accounts are keyed with [`Account::to_key`].
+#[derive(Debug, Default, Clone)]
+pub struct TokenState {
+    info: Option<TokenInfo>,
+    total_supply: u64,
+    balances: BTreeMap<Vec<u8>, u64>,
+    allowances: BTreeMap<(Vec<u8>, Vec<u8>), u64>,
+}
+
+impl TokenState {
+    pub const fn new() -> Self {
+        Self {
+            info: None,
+            total_supply: 0,
+            balances: BTreeMap::new(),
+            allowances: BTreeMap::new(),
+        }
+    }
+
+    pub fn init(&mut self, init: TokenInit) {
+        if self.info.is_some() {
+            panic!("The token is already initialized");
+        }
+
+        for (account, value) in init.balances {
+            self.total_supply = self
+                .total_supply
+                .checked_add(value)
+                .expect("The total supply should not overflow");
+            *self.balances.entry(account.to_key()).or_default() += value;
+        }
+
+        self.info = Some(init.info);
+    }
+
+    pub fn transfer(&mut self, transfer: Transfer) {
+        let sender = sender();
+        self.move_tokens(sender, transfer.receiver, transfer.value);
+    }
+
+    pub fn transfer_from(&mut self, transfer: TransferFrom) {
+        let spender = sender();
+        let value = transfer.value;
+
+        let allowance_key = (transfer.owner.to_key(), spender.to_key());
+        let allowance =
+            self.allowances.get(&allowance_key).copied().unwrap_or(0);
+        if value > allowance {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Value to transfer higher than the allowance",
+            );
+        }
+
+        if allowance == value {
+            self.allowances.remove(&allowance_key);
+        } else {
+            self.allowances.insert(allowance_key, allowance - value);
+        }
+
+        self.move_tokens(transfer.owner, transfer.receiver, value);
+    }
+
+    pub fn approve(&mut self, approve: Approve) {
+        let owner = sender();
+        let spender = approve.spender;
+        let value = approve.value;
+
+        let allowance_key = (owner.to_key(), spender.to_key());
+        if value == 0 {
+            self.allowances.remove(&allowance_key);
+        } else {
+            self.allowances.insert(allowance_key, value);
+        }
+
+        abi::emit(
+            APPROVE_TOPIC,
+            ApproveEvent {
+                owner,
+                spender,
+                value,
+            },
+        );
+    }
+
+    /// Moves tokens from the `sender` to the `receiver`, notifying the
+    /// receiver if it is a contract.
+    ///
+    /// The receiving contract is called with [`TokenReceived`] on its
+    /// [`TOKEN_RECEIVED_FN`] function, and may panic to refuse the tokens.
+    fn move_tokens(&mut self, sender: Account, receiver: Account, value: u64) {
+        let sender_key = sender.to_key();
+        let sender_balance = self.balances.get(&sender_key).copied();
+
+        match sender_balance {
+            Some(balance) if balance >= value => {
+                if balance == value {
+                    self.balances.remove(&sender_key);
+                } else {
+                    self.balances.insert(sender_key, balance - value);
+                }
+            }
+            _ => abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "Insufficient balance",
+            ),
+        }
+
+        if value > 0 {
+            *self.balances.entry(receiver.to_key()).or_default() += value;
+        }
+
+        abi::emit(
+            TRANSFER_TOPIC,
+            TransferEvent {
+                sender,
+                receiver,
+                value,
+            },
+        );
+
+        if let Account::Contract(contract) = receiver {
+            let _: () = abi::call(
+                contract,
+                TOKEN_RECEIVED_FN,
+                &TokenReceived { sender, value },
+            )
+            .expect("The receiving contract should accept the tokens");
+        }
+    }
+
+    /// The information describing the token.
+    pub fn info(&self) -> &TokenInfo {
+        self.info.as_ref().expect("The token should be initialized")
+    }
+
+    /// The total supply of the token.
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    /// The balance of the given account.
+    pub fn balance(&self, account: &Account) -> u64 {
+        self.balances
+            .get(&account.to_key())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The amount the `spender` is allowed to transfer on behalf of the
+    /// `owner`.
+    pub fn allowance(&self, owner: &Account, spender: &Account) -> u64 {
+        self.allowances
+            .get(&(owner.to_key(), spender.to_key()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The account calling the token contract.
+///
+/// Contracts are identified as the caller of the token contract. Calls made
+/// through the transfer contract, meaning directly by a transaction, are
+/// identified as the public sender of the transaction.
+///
+/// # Panics
+/// When the token contract is called by a shielded transaction.
+fn sender() -> Account {
+    match abi::caller() {
+        Some(contract) if contract != TRANSFER_CONTRACT => {
+            Account::Contract(contract)
+        }
+        _ => match abi::public_sender() {
+            Some(pk) => Account::External(pk),
+            None => abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Tokens can only be moved by a public account or a contract",
+            ),
+        },
+    }
+}
@@ -0,0 +1,259 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ContractId, ARGBUF_LEN};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::token::{
+    Account, Approve, TokenInfo, TokenInit, Transfer, TransferFrom,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(1_000.0);
+const TOKEN_SUPPLY: u64 = 1_000_000;
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const TOKEN_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x20;
+    ContractId::from_bytes(bytes)
+};
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+const CONFIG: ExecutionConfig = ExecutionConfig {
+    with_public_sender: true,
+    ..ExecutionConfig::DEFAULT
+};
+
+/// Instantiate the virtual machine with the transfer and token contracts
+/// deployed, with the given moonlight accounts owning the
+/// `MOONLIGHT_GENESIS_VALUE`, and the first one owning the whole token supply.
+fn instantiate(moonlight_pks: &[AccountPublicKey]) -> Session {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let token_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/token_contract.wasm"
+    );
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    let init = TokenInit {
+        info: TokenInfo {
+            name: String::from("Reference Token"),
+            symbol: String::from("REF"),
+            decimals: 6,
+        },
+        balances: vec![(Account::from(moonlight_pks[0]), TOKEN_SUPPLY)],
+    };
+    session
+        .deploy(
+            token_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TOKEN_ID)
+                .init_arg(&init),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the token contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    let base = session.commit().expect("Committing should succeed");
+
+    vm.session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the token contract from the given moonlight account, returning
+/// whether the call succeeded.
+fn call_token(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(TOKEN_ID, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt =
+        execute(session, &tx, &CONFIG).expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn balance(session: &mut Session, account: impl Into<Account>) -> u64 {
+    session
+        .call(TOKEN_ID, "balance", &account.into(), GAS_LIMIT)
+        .expect("Querying the balance should succeed")
+        .data
+}
+
+fn allowance(
+    session: &mut Session,
+    owner: impl Into<Account>,
+    spender: impl Into<Account>,
+) -> u64 {
+    session
+        .call(
+            TOKEN_ID,
+            "allowance",
+            &(owner.into(), spender.into()),
+            GAS_LIMIT,
+        )
+        .expect("Querying the allowance should succeed")
+        .data
+}
+
+#[test]
+fn transfer() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let sender_sk = AccountSecretKey::random(rng);
+    let sender_pk = AccountPublicKey::from(&sender_sk);
+    let receiver_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&[sender_pk]);
+
+    let total_supply: u64 = session
+        .call(TOKEN_ID, "total_supply", &(), GAS_LIMIT)
+        .expect("Querying the total supply should succeed")
+        .data;
+    assert_eq!(total_supply, TOKEN_SUPPLY);
+    assert_eq!(balance(session, sender_pk), TOKEN_SUPPLY);
+
+    const TRANSFER_VALUE: u64 = TOKEN_SUPPLY / 4;
+    let transfer = Transfer {
+        receiver: Account::from(receiver_pk),
+        value: TRANSFER_VALUE,
+    };
+    assert!(call_token(session, &sender_sk, 1, "transfer", &transfer));
+    assert_eq!(balance(session, sender_pk), TOKEN_SUPPLY - TRANSFER_VALUE);
+    assert_eq!(balance(session, receiver_pk), TRANSFER_VALUE);
+
+    // transferring more than the balance fails
+    let transfer = Transfer {
+        receiver: Account::from(receiver_pk),
+        value: TOKEN_SUPPLY,
+    };
+    assert!(!call_token(session, &sender_sk, 2, "transfer", &transfer));
+    assert_eq!(balance(session, sender_pk), TOKEN_SUPPLY - TRANSFER_VALUE);
+    assert_eq!(balance(session, receiver_pk), TRANSFER_VALUE);
+}
+
+#[test]
+fn approve_and_transfer_from() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let owner_sk = AccountSecretKey::random(rng);
+    let owner_pk = AccountPublicKey::from(&owner_sk);
+    let spender_sk = AccountSecretKey::random(rng);
+    let spender_pk = AccountPublicKey::from(&spender_sk);
+    let receiver_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&[owner_pk, spender_pk]);
+
+    const ALLOWANCE: u64 = TOKEN_SUPPLY / 2;
+    let approve = Approve {
+        spender: Account::from(spender_pk),
+        value: ALLOWANCE,
+    };
+    assert!(call_token(session, &owner_sk, 1, "approve", &approve));
+    assert_eq!(allowance(session, owner_pk, spender_pk), ALLOWANCE);
+
+    // spending within the allowance succeeds
+    const TRANSFER_VALUE: u64 = ALLOWANCE / 2;
+    let transfer = TransferFrom {
+        owner: Account::from(owner_pk),
+        receiver: Account::from(receiver_pk),
+        value: TRANSFER_VALUE,
+    };
+    assert!(call_token(
+        session,
+        &spender_sk,
+        1,
+        "transfer_from",
+        &transfer
+    ));
+    assert_eq!(balance(session, owner_pk), TOKEN_SUPPLY - TRANSFER_VALUE);
+    assert_eq!(balance(session, receiver_pk), TRANSFER_VALUE);
+    assert_eq!(
+        allowance(session, owner_pk, spender_pk),
+        ALLOWANCE - TRANSFER_VALUE
+    );
+
+    // spending more than the allowance left fails
+    let transfer = TransferFrom {
+        owner: Account::from(owner_pk),
+        receiver: Account::from(receiver_pk),
+        value: ALLOWANCE,
+    };
+    assert!(!call_token(
+        session,
+        &spender_sk,
+        2,
+        "transfer_from",
+        &transfer
+    ));
+    assert_eq!(balance(session, owner_pk), TOKEN_SUPPLY - TRANSFER_VALUE);
+    assert_eq!(
+        allowance(session, owner_pk, spender_pk),
+        ALLOWANCE - TRANSFER_VALUE
+    );
+}
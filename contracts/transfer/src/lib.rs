@@ -31,6 +31,13 @@ unsafe fn mint(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |arg| STATE.mint(arg))
 }
 
+#[no_mangle]
+unsafe fn mint_to_account(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(mint, account)| {
+        STATE.mint_to_account(mint, account)
+    })
+}
+
 #[no_mangle]
 unsafe fn mint_to_contract(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |arg| STATE.mint_to_contract(arg))
@@ -61,6 +68,16 @@ unsafe fn contract_to_account(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |arg| STATE.contract_to_account(arg))
 }
 
+#[no_mangle]
+unsafe fn approve(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.approve(arg))
+}
+
+#[no_mangle]
+unsafe fn approved_deposit(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.approved_deposit(arg))
+}
+
 // Queries
 
 #[no_mangle]
@@ -78,26 +95,72 @@ unsafe fn contract_balance(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |contract| STATE.contract_balance(&contract))
 }
 
+#[no_mangle]
+unsafe fn allowance(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(account, contract)| {
+        STATE.allowance(&account, &contract)
+    })
+}
+
 #[no_mangle]
 unsafe fn opening(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |pos| STATE.opening(pos))
 }
 
+#[no_mangle]
+unsafe fn note_tree_checkpoint(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |block_height| {
+        STATE.note_tree_checkpoint(block_height)
+    })
+}
+
 #[no_mangle]
 unsafe fn existing_nullifiers(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |nullifiers| STATE.existing_nullifiers(nullifiers))
 }
 
+#[no_mangle]
+unsafe fn existing_nullifiers_paged(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(nullifiers, offset)| {
+        STATE.existing_nullifiers_paged(nullifiers, offset)
+    })
+}
+
 #[no_mangle]
 unsafe fn num_notes(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.num_notes())
 }
 
+#[no_mangle]
+unsafe fn limits(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.limits().clone())
+}
+
+#[no_mangle]
+unsafe fn limits_nonce(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |_: ()| STATE.limits_nonce())
+}
+
 #[no_mangle]
 unsafe fn chain_id(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |_: ()| STATE.chain_id())
 }
 
+#[no_mangle]
+unsafe fn code_hash(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |contract| STATE.code_hash(&contract))
+}
+
+#[no_mangle]
+unsafe fn upgrade_nonce(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |contract| STATE.upgrade_nonce(&contract))
+}
+
+#[no_mangle]
+unsafe fn deploy_deposit(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |contract| STATE.deploy_deposit(&contract))
+}
+
 // "Feeder" queries
 
 #[no_mangle]
@@ -124,6 +187,13 @@ unsafe fn sync_nullifiers(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn existing_nullifiers_feed(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |nullifiers| {
+        STATE.existing_nullifiers_feed(nullifiers)
+    })
+}
+
 #[no_mangle]
 unsafe fn sync_contract_balances(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |(from, count_limint)| {
@@ -156,6 +226,14 @@ unsafe fn refund(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn deliver_event(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(subscriber, delivery)| {
+        assert_external_caller();
+        STATE.deliver_event(subscriber, delivery)
+    })
+}
+
 #[no_mangle]
 unsafe fn push_note(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |(block_height, note)| {
@@ -196,6 +274,35 @@ unsafe fn add_contract_balance(arg_len: u32) -> u32 {
     })
 }
 
+#[no_mangle]
+unsafe fn set_limits(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |set_limits| STATE.set_limits(set_limits))
+}
+
+#[no_mangle]
+unsafe fn set_code_hash(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(contract, hash)| {
+        assert_external_caller();
+        STATE.set_code_hash(contract, hash)
+    })
+}
+
+#[no_mangle]
+unsafe fn record_upgrade(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(contract, hash)| {
+        assert_external_caller();
+        STATE.record_upgrade(contract, hash)
+    })
+}
+
+#[no_mangle]
+unsafe fn escrow_deposit(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |(contract, gas)| {
+        assert_external_caller();
+        STATE.escrow_deposit(contract, gas)
+    })
+}
+
 #[no_mangle]
 unsafe fn sub_contract_balance(arg_len: u32) -> u32 {
     abi::wrap_call(arg_len, |(module, value)| {
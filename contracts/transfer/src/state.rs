@@ -16,28 +16,37 @@ use dusk_core::stake::EPOCH;
 use dusk_core::transfer::MINT_CONTRACT_TOPIC;
 use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
 
-use dusk_core::abi::{self, ContractError, ContractId};
+use dusk_core::abi::{
+    self, ContractError, ContractFailure, ContractId, EventDelivery,
+    ON_EVENT_FN,
+};
 use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
 use dusk_core::stake::STAKE_CONTRACT;
+use dusk_core::transfer::data::{
+    ContractDeployDeposit, MemoKind, DEPLOY_DEPOSIT_TOPIC,
+};
 use dusk_core::transfer::moonlight::{
     AccountData, Transaction as MoonlightTransaction,
 };
 use dusk_core::transfer::phoenix::{
-    Note, NoteLeaf, NoteOpening, Sender, Transaction as PhoenixTransaction,
+    Note, NoteLeaf, NoteOpening, NoteTreeCheckpoint, Sender,
+    Transaction as PhoenixTransaction,
 };
 use dusk_core::transfer::withdraw::{
     Withdraw, WithdrawReceiver, WithdrawReplayToken, WithdrawSignature,
 };
 use dusk_core::transfer::{
+    Approve, ApproveEvent, ApprovedDeposit, ApprovedDepositEvent,
     ContractToAccount, ContractToAccountEvent, ContractToContract,
-    ContractToContractEvent, ConvertEvent, DepositEvent,
+    ContractToContractEvent, ConvertEvent, DepositEvent, MemoEvent,
     MoonlightTransactionEvent, PhoenixTransactionEvent, ReceiveFromContract,
-    Transaction, WithdrawEvent, CONTRACT_TO_ACCOUNT_TOPIC,
-    CONTRACT_TO_CONTRACT_TOPIC, CONVERT_TOPIC, DEPOSIT_TOPIC, MINT_TOPIC,
-    MOONLIGHT_TOPIC, PANIC_NONCE_NOT_READY, PHOENIX_TOPIC, TRANSFER_CONTRACT,
-    WITHDRAW_TOPIC,
+    SetLimits, Transaction, TransferLimits, WithdrawEvent,
+    APPROVED_DEPOSIT_TOPIC, APPROVE_TOPIC, CONTRACT_TO_ACCOUNT_TOPIC,
+    CONTRACT_TO_CONTRACT_TOPIC, CONVERT_TOPIC, DEPOSIT_TOPIC, LIMITS_TOPIC,
+    MAX_NULLIFIERS_PAGE, MEMO_TOPIC, MINT_TOPIC, MOONLIGHT_TOPIC,
+    PANIC_NONCE_NOT_READY, PHOENIX_TOPIC, TRANSFER_CONTRACT, WITHDRAW_TOPIC,
 };
-use dusk_core::BlsScalar;
+use dusk_core::{Amount, BlsScalar};
 
 use crate::transitory::{self, Deposit};
 
@@ -64,6 +73,35 @@ fn contract_fn_sender(fn_name: &str, contract: ContractId) -> Sender {
     Sender::ContractInfo(bytes)
 }
 
+/// The value that flowed in and out of contract balances in a block, checked
+/// against the [`TransferLimits`].
+struct BlockFlows {
+    block_height: u64,
+    deposited: u64,
+    withdrawn: u64,
+    withdrawn_by: BTreeMap<ContractId, u64>,
+}
+
+impl BlockFlows {
+    const fn new(block_height: u64) -> Self {
+        Self {
+            block_height,
+            deposited: 0,
+            withdrawn: 0,
+            withdrawn_by: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the flows of the given block, resetting them if the block
+    /// changed since the last transfer.
+    fn at(&mut self, block_height: u64) -> &mut Self {
+        if self.block_height != block_height {
+            *self = Self::new(block_height);
+        }
+        self
+    }
+}
+
 pub struct TransferState {
     tree: Tree,
     nullifiers: BTreeSet<BlsScalar>,
@@ -73,6 +111,19 @@ pub struct TransferState {
     //       up to replay attacks.
     accounts: BTreeMap<[u8; 193], AccountData>,
     contract_balances: BTreeMap<ContractId, u64>,
+    allowances: BTreeMap<([u8; 193], ContractId), u64>,
+    limits: TransferLimits,
+    limits_nonce: u64,
+    block_flows: BlockFlows,
+    // The hash of the bytecode of the contracts deployed or upgraded through
+    // a transaction, recorded by the host.
+    code_hashes: BTreeMap<ContractId, [u8; 32]>,
+    // The number of upgrades of each upgraded contract, recorded by the host.
+    // Upgrades commit to it, so that they can't be replayed.
+    upgrade_nonces: BTreeMap<ContractId, u64>,
+    // The value escrowed as the deposit for the state of the contracts
+    // deployed or upgraded through a transaction, recorded by the host.
+    deploy_deposits: BTreeMap<ContractId, u64>,
 }
 
 impl TransferState {
@@ -83,7 +134,117 @@ impl TransferState {
             roots: ConstGenericRingBuffer::new(),
             accounts: BTreeMap::new(),
             contract_balances: BTreeMap::new(),
+            allowances: BTreeMap::new(),
+            limits: TransferLimits::new(),
+            limits_nonce: 0,
+            block_flows: BlockFlows::new(0),
+            code_hashes: BTreeMap::new(),
+            upgrade_nonces: BTreeMap::new(),
+            deploy_deposits: BTreeMap::new(),
+        }
+    }
+
+    pub fn limits(&self) -> &TransferLimits {
+        &self.limits
+    }
+
+    pub fn limits_nonce(&self) -> u64 {
+        self.limits_nonce
+    }
+
+    /// Sets the limits, if they are signed by the owner of the contract.
+    ///
+    /// # Panics
+    /// When the nonce doesn't follow the one of the limits last set, or the
+    /// limits aren't signed by the owner.
+    pub fn set_limits(&mut self, set_limits: SetLimits) {
+        if set_limits.chain_id != self.chain_id() {
+            abi::fail(
+                ContractFailure::WRONG_CHAIN_ID,
+                "The limits must target the correct chain",
+            );
+        }
+        if set_limits.nonce != self.limits_nonce + 1 {
+            abi::fail(
+                ContractFailure::INVALID_NONCE,
+                "The nonce of the limits is invalid",
+            );
         }
+
+        let owner = abi::try_self_owner().unwrap_or_else(|| {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "The contract isn't owned by an account",
+            )
+        });
+        if !abi::verify_bls(
+            set_limits.signature_message(),
+            owner,
+            set_limits.signature,
+        ) {
+            abi::fail(
+                ContractFailure::INVALID_SIGNATURE,
+                "The limits aren't signed by the owner",
+            );
+        }
+
+        self.limits_nonce = set_limits.nonce;
+        self.limits = set_limits.limits;
+        abi::emit(LIMITS_TOPIC, self.limits.clone());
+    }
+
+    /// Accounts for a deposit into a contract in the current block.
+    ///
+    /// # Panics
+    /// When the deposit exceeds the deposit limit of the block.
+    fn limit_deposit(&mut self, value: u64) {
+        let flows = self.block_flows.at(abi::block_height());
+
+        let deposited = flows.deposited.saturating_add(value);
+        if let Some(max) = self.limits.max_block_deposit {
+            if deposited > max {
+                abi::fail(
+                    ContractFailure::LIMIT_EXCEEDED,
+                    "The deposit limit of the block is exceeded",
+                );
+            }
+        }
+
+        flows.deposited = deposited;
+    }
+
+    /// Accounts for a withdrawal from the given contract in the current
+    /// block, be it to a phoenix note or to a moonlight account.
+    ///
+    /// # Panics
+    /// When the withdrawal exceeds either the withdrawal limit of the block,
+    /// or the one of the contract.
+    fn limit_withdrawal(&mut self, contract: ContractId, value: u64) {
+        let flows = self.block_flows.at(abi::block_height());
+
+        let withdrawn = flows.withdrawn.saturating_add(value);
+        if let Some(max) = self.limits.max_block_withdrawal {
+            if withdrawn > max {
+                abi::fail(
+                    ContractFailure::LIMIT_EXCEEDED,
+                    "The withdrawal limit of the block is exceeded",
+                );
+            }
+        }
+
+        let withdrawn_by = flows.withdrawn_by.entry(contract).or_insert(0);
+        let contract_withdrawn = withdrawn_by.saturating_add(value);
+        if let Some(cap) = self.limits.contract_cap(&contract) {
+            if contract_withdrawn > cap {
+                abi::fail(
+                    ContractFailure::LIMIT_EXCEEDED,
+                    "The withdrawal limit of the contract is exceeded",
+                );
+            }
+        }
+
+        *withdrawn_by = contract_withdrawn;
+        flows.withdrawn = withdrawn;
     }
 
     /// Checks the [`Withdraw`] is correct, and mints the amount of the
@@ -95,24 +256,7 @@ impl TransferState {
         let msg = withdraw.signature_message();
         let signature = withdraw.signature();
 
-        match withdraw.token() {
-            WithdrawReplayToken::Phoenix(nullifiers) => {
-                let phoenix_tx = transitory::phoenix_transaction();
-
-                for n in phoenix_tx.nullifiers() {
-                    if !nullifiers.contains(n) {
-                        panic!("Incorrect nullifiers signed");
-                    }
-                }
-            }
-            WithdrawReplayToken::Moonlight(nonce) => {
-                let moonlight_tx = transitory::moonlight_transaction();
-
-                if *nonce != moonlight_tx.nonce() {
-                    panic!("Incorrect nonce signed");
-                }
-            }
-        }
+        Self::check_replay_token(withdraw.token());
 
         match withdraw.receiver() {
             WithdrawReceiver::Phoenix(address) => {
@@ -127,7 +271,10 @@ impl TransferState {
                 let pk = address.note_pk();
 
                 if !abi::verify_schnorr(hash, *pk, *signature) {
-                    panic!("Invalid signature");
+                    abi::fail(
+                        ContractFailure::INVALID_SIGNATURE,
+                        "Invalid signature",
+                    );
                 }
 
                 let sender = contract_fn_sender(fn_name, *contract);
@@ -144,7 +291,10 @@ impl TransferState {
                 };
 
                 if !abi::verify_bls(msg, *account, *signature) {
-                    panic!("Invalid signature");
+                    abi::fail(
+                        ContractFailure::INVALID_SIGNATURE,
+                        "Invalid signature",
+                    );
                 }
 
                 let account_bytes = account.to_raw_bytes();
@@ -156,6 +306,32 @@ impl TransferState {
         }
     }
 
+    /// Checks the replay token of a withdrawal matches the transaction it is
+    /// executed in.
+    fn check_replay_token(token: &WithdrawReplayToken) {
+        match token {
+            WithdrawReplayToken::Phoenix(nullifiers) => {
+                let phoenix_tx = transitory::phoenix_transaction();
+
+                for n in phoenix_tx.nullifiers() {
+                    if !nullifiers.contains(n) {
+                        panic!("Incorrect nullifiers signed");
+                    }
+                }
+            }
+            WithdrawReplayToken::Moonlight(nonce) => {
+                let moonlight_tx = transitory::moonlight_transaction();
+
+                if *nonce != moonlight_tx.nonce() {
+                    abi::fail(
+                        ContractFailure::INVALID_NONCE,
+                        "Incorrect nonce signed",
+                    );
+                }
+            }
+        }
+    }
+
     /// Mint more Dusk.
     ///
     /// This can only be called by the stake contract, and will increase the
@@ -181,6 +357,47 @@ impl TransferState {
         abi::emit(MINT_TOPIC, WithdrawEvent::from(mint));
     }
 
+    /// Mint more Dusk to the given account, instead of the receiver of the
+    /// withdrawal.
+    ///
+    /// This can only be called by the stake contract, to mint the rewards of a
+    /// stake to the reward address registered for it. The withdrawal is
+    /// authorized by the stake contract, so only its replay token is checked.
+    ///
+    /// # Safety
+    /// We assume on trust that the value sent by the stake contract is
+    /// according to consensus rules.
+    pub fn mint_to_account(
+        &mut self,
+        mint: Withdraw,
+        account: AccountPublicKey,
+    ) {
+        const PANIC_MSG: &str = "Can only be called by the stake contract";
+        if abi::caller().expect(PANIC_MSG) != STAKE_CONTRACT {
+            panic!("{PANIC_MSG}")
+        }
+
+        if mint.contract() != &STAKE_CONTRACT {
+            panic!("Withdrawal should from the stake contract");
+        }
+
+        Self::check_replay_token(mint.token());
+
+        let account_bytes = account.to_raw_bytes();
+        let receiver =
+            self.accounts.entry(account_bytes).or_insert(EMPTY_ACCOUNT);
+        receiver.balance += mint.value();
+
+        abi::emit(
+            MINT_TOPIC,
+            WithdrawEvent {
+                sender: STAKE_CONTRACT,
+                receiver: WithdrawReceiver::Moonlight(account),
+                value: mint.value(),
+            },
+        );
+    }
+
     /// Mint more Dusk to be owned by a contract.
     ///
     /// This can only be called by the stake contract, and will increase the
@@ -228,7 +445,8 @@ impl TransferState {
     ///
     /// # Panics
     /// This can only be called by the contract specified, and only if said
-    /// contract has enough balance.
+    /// contract has enough balance and the withdrawal is within the
+    /// [`TransferLimits`].
     pub fn withdraw(&mut self, withdraw: Withdraw) {
         let contract = withdraw.contract();
 
@@ -244,6 +462,8 @@ impl TransferState {
             panic!("The contract doesn't have enough balance");
         }
 
+        self.limit_withdrawal(*contract, value);
+
         self.sub_contract_balance(contract, value)
             .expect("Subtracting balance from contract should succeed");
 
@@ -325,8 +545,9 @@ impl TransferState {
     /// added to the contract's balance.
     ///
     /// # Panics
-    /// This function will panic if there is no deposit on the state or the
-    /// caller-id doesn't match the contract-id stored for the deposit.
+    /// This function will panic if there is no deposit on the state, the
+    /// caller-id doesn't match the contract-id stored for the deposit, or the
+    /// deposit exceeds the [`TransferLimits`].
     pub fn deposit(&mut self, value: u64) {
         let caller = abi::caller()
             .expect("A deposit must happen in the context of a transaction");
@@ -354,6 +575,8 @@ impl TransferState {
                 // copy here because `set_taken` needs a mutable reference
                 let sender = *sender;
 
+                self.limit_deposit(deposit_value);
+
                 // add to the contract's balance and set the deposit as taken
                 self.add_contract_balance(deposit_contract, deposit_value);
                 deposit.set_taken();
@@ -374,6 +597,97 @@ impl TransferState {
         }
     }
 
+    /// Allow a contract to pull up to the given value from the Moonlight
+    /// account sending the transaction, replacing any previous allowance.
+    ///
+    /// An allowance of zero revokes the approval.
+    ///
+    /// # Panics
+    /// This can only be called by this contract - the transfer contract - as
+    /// the contract call of a Moonlight transaction, and will panic if this is
+    /// not the case.
+    pub fn approve(&mut self, approve: Approve) {
+        // since each transaction only has, at maximum, a single contract call,
+        // this check implies that the approval is the call of the transaction.
+        let caller = abi::caller()
+            .expect("An approval must happen in the context of a transaction");
+        if caller != TRANSFER_CONTRACT {
+            panic!("Only the first contract call can be an approval");
+        }
+        let account = *transitory::moonlight_transaction().sender();
+
+        let key = (account.to_raw_bytes(), approve.contract);
+        if approve.value == 0 {
+            self.allowances.remove(&key);
+        } else {
+            self.allowances.insert(key, approve.value);
+        }
+
+        abi::emit(
+            APPROVE_TOPIC,
+            ApproveEvent {
+                account,
+                contract: approve.contract,
+                value: approve.value,
+            },
+        );
+    }
+
+    /// Deposit funds from a Moonlight account into the calling contract,
+    /// within the allowance the account approved for it.
+    ///
+    /// # Panics
+    /// This function will panic if the deposit exceeds either the allowance of
+    /// the caller, the balance of the account, or the [`TransferLimits`].
+    pub fn approved_deposit(&mut self, deposit: ApprovedDeposit) {
+        let caller = abi::caller()
+            .expect("An approved deposit can only be made by a contract");
+
+        let account_bytes = deposit.account.to_raw_bytes();
+        let key = (account_bytes, caller);
+
+        let allowance = self.allowance(&deposit.account, &caller);
+        let allowance = match allowance.checked_sub(deposit.value) {
+            Some(allowance) => allowance,
+            None => abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "The deposit exceeds the allowance of the contract",
+            ),
+        };
+
+        let account = self
+            .accounts
+            .get_mut(&account_bytes)
+            .filter(|account| account.balance >= deposit.value);
+        let account = match account {
+            Some(account) => account,
+            None => abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "The account doesn't have enough balance",
+            ),
+        };
+        account.balance -= deposit.value;
+
+        self.limit_deposit(deposit.value);
+
+        if allowance == 0 {
+            self.allowances.remove(&key);
+        } else {
+            self.allowances.insert(key, allowance);
+        }
+        self.add_contract_balance(caller, deposit.value);
+
+        abi::emit(
+            APPROVED_DEPOSIT_TOPIC,
+            ApprovedDepositEvent {
+                sender: deposit.account,
+                receiver: caller,
+                value: deposit.value,
+                allowance,
+            },
+        );
+    }
+
     /// Transfer funds from one contract's balance to another.
     ///
     /// Contracts can call the function and expect that if it succeeds the funds
@@ -404,7 +718,10 @@ impl TransferState {
             .expect("Caller must have a balance");
 
         if *sender_balance < transfer.value {
-            panic!("Caller must have enough balance");
+            abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "Caller must have enough balance",
+            );
         }
 
         *sender_balance -= transfer.value;
@@ -440,8 +757,9 @@ impl TransferState {
     ///
     /// # Panics
     /// The function will panic if it is not being called by a contract, if it
-    /// is called by the transfer contract itself, or if the calling contract
-    /// doesn't have enough funds.
+    /// is called by the transfer contract itself, if the calling contract
+    /// doesn't have enough funds, or if the transfer exceeds the
+    /// [`TransferLimits`].
     pub fn contract_to_account(&mut self, transfer: ContractToAccount) {
         let sender_contract = abi::caller()
             .expect("A transfer to an account must happen in the context of a transaction");
@@ -450,13 +768,18 @@ impl TransferState {
             panic!("Cannot be called directly by the transfer contract");
         }
 
+        self.limit_withdrawal(sender_contract, transfer.value);
+
         let sender_balance = self
             .contract_balances
             .get_mut(&sender_contract)
             .expect("Caller must have a balance");
 
         if *sender_balance < transfer.value {
-            panic!("Caller must have enough balance");
+            abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "Caller must have enough balance",
+            );
         }
 
         let account = self
@@ -501,7 +824,7 @@ impl TransferState {
         tx: Transaction,
     ) -> Result<Vec<u8>, ContractError> {
         if tx.gas_price() == 0 {
-            panic!("Gas price too low!");
+            abi::fail(ContractFailure::GAS_PRICE_TOO_LOW, "Gas price too low!");
         }
 
         transitory::put_transaction(tx);
@@ -531,7 +854,10 @@ impl TransferState {
     /// change in state.
     fn spend_phoenix(&mut self, phoenix_tx: &PhoenixTransaction) {
         if phoenix_tx.chain_id() != self.chain_id() {
-            panic!("The tx must target the correct chain");
+            abi::fail(
+                ContractFailure::WRONG_CHAIN_ID,
+                "The tx must target the correct chain",
+            );
         }
 
         // panic if the root is invalid
@@ -549,7 +875,10 @@ impl TransferState {
 
         // verify the phoenix-circuit
         if !verify_tx_proof(phoenix_tx) {
-            panic!("Invalid transaction proof!");
+            abi::fail(
+                ContractFailure::INVALID_PROOF,
+                "Invalid transaction proof!",
+            );
         }
 
         // append the output notes to the phoenix-notes tree
@@ -574,7 +903,10 @@ impl TransferState {
     /// change in state.
     fn spend_moonlight(&mut self, moonlight_tx: &MoonlightTransaction) {
         if moonlight_tx.chain_id() != self.chain_id() {
-            panic!("The tx must target the correct chain");
+            abi::fail(
+                ContractFailure::WRONG_CHAIN_ID,
+                "The tx must target the correct chain",
+            );
         }
 
         // check the signature is valid and made by `sender`
@@ -583,7 +915,7 @@ impl TransferState {
             *moonlight_tx.sender(),
             *moonlight_tx.signature(),
         ) {
-            panic!("Invalid signature!");
+            abi::fail(ContractFailure::INVALID_SIGNATURE, "Invalid signature!");
         }
 
         // check `sender` has the funds necessary to suppress the total value
@@ -603,15 +935,27 @@ impl TransferState {
         let sender_bytes = moonlight_tx.sender().to_raw_bytes();
 
         // the total value carried by a transaction is the sum of the value, the
-        // deposit, and gas_limit * gas_price.
-        let total_value = moonlight_tx.value()
-            + moonlight_tx.deposit()
-            + moonlight_tx.gas_limit() * moonlight_tx.gas_price();
+        // deposit, and gas_limit * gas_price. A total that overflows could
+        // never be covered by any balance.
+        let total_value = Amount::from_lux(moonlight_tx.gas_price())
+            .checked_mul(moonlight_tx.gas_limit())
+            .and_then(|fee| fee.checked_add(moonlight_tx.value().into()))
+            .and_then(|cost| cost.checked_add(moonlight_tx.deposit().into()))
+            .unwrap_or_else(|_| {
+                abi::fail(
+                    ContractFailure::INSUFFICIENT_BALANCE,
+                    "Transaction value overflows",
+                )
+            })
+            .lux();
 
         match self.accounts.get_mut(&sender_bytes) {
             Some(account) => {
                 if total_value > account.balance {
-                    panic!("Account doesn't have enough funds");
+                    abi::fail(
+                        ContractFailure::INSUFFICIENT_BALANCE,
+                        "Account doesn't have enough funds",
+                    );
                 }
 
                 // NOTE: exhausting the nonce is nearly impossible, since it
@@ -620,7 +964,10 @@ impl TransferState {
                 //       skip overflow checks.
                 let incremented_nonce = account.nonce + 1;
                 if moonlight_tx.nonce() < incremented_nonce {
-                    panic!("Already used nonce");
+                    abi::fail(
+                        ContractFailure::INVALID_NONCE,
+                        "Already used nonce",
+                    );
                 }
                 if moonlight_tx.nonce() > incremented_nonce {
                     panic!("{PANIC_NONCE_NOT_READY}",);
@@ -629,7 +976,10 @@ impl TransferState {
                 account.balance -= total_value;
                 account.nonce = moonlight_tx.nonce();
             }
-            None => panic!("Account has no funds"),
+            None => abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "Account has no funds",
+            ),
         }
 
         // add the value to the receiver account
@@ -646,6 +996,21 @@ impl TransferState {
         }
     }
 
+    /// Delivers an event to the [`ON_EVENT_FN`] entrypoint of a contract that
+    /// subscribed to it during the ongoing transaction.
+    ///
+    /// The subscriber is called by the transfer contract, before the
+    /// transaction is refunded. If the callback fails its changes are
+    /// reverted and the error is returned, but the gas it spent is still
+    /// charged to the transaction.
+    pub fn deliver_event(
+        &mut self,
+        subscriber: ContractId,
+        delivery: EventDelivery,
+    ) -> Result<(), ContractError> {
+        abi::call(subscriber, ON_EVENT_FN, &delivery)
+    }
+
     /// Refund the previously performed transaction, taking into account the
     /// given gas spent and a potential deposit that hasn't been picked up by
     /// the contract. The note produced will be refunded to the address present
@@ -662,9 +1027,25 @@ impl TransferState {
             _ => None,
         };
 
+        // The gas escrowed as the deposit of a deployed or upgraded contract
+        // is withheld from the refund, on top of the gas spent.
+        let escrowed_gas = ongoing.escrowed_gas;
+
         let mut memo = Vec::new();
         if let Some(m) = ongoing.tx.memo() {
-            memo = m.to_vec();
+            memo = m.content().to_vec();
+
+            // the kind of a tagged memo is carried by its own event, leaving
+            // the layout of the transaction events unchanged
+            if m.kind() != MemoKind::Plain {
+                abi::emit(
+                    MEMO_TOPIC,
+                    MemoEvent {
+                        kind: m.kind(),
+                        content: memo.clone(),
+                    },
+                );
+            }
         }
 
         // the unspent gas is refunded to the refund-address specified in the
@@ -677,8 +1058,9 @@ impl TransferState {
             Transaction::Phoenix(tx) => {
                 let notes = ongoing.notes;
 
-                let remainder_note =
-                    tx.fee().gen_remainder_note(gas_spent, deposit);
+                let remainder_note = tx
+                    .fee()
+                    .gen_remainder_note(gas_spent + escrowed_gas, deposit);
 
                 // if the refund-value is 0, we don't push the note onto the
                 // tree and the refund-note will be None
@@ -696,7 +1078,7 @@ impl TransferState {
                 );
             }
             Transaction::Moonlight(tx) => {
-                let remaining_gas = tx.gas_limit() - gas_spent;
+                let remaining_gas = tx.gas_limit() - gas_spent - escrowed_gas;
                 let refund = remaining_gas * tx.gas_price()
                     + deposit.unwrap_or_default();
 
@@ -823,6 +1205,33 @@ impl TransferState {
         self.tree.opening(pos)
     }
 
+    /// Get a proof linking the current root of the tree of notes to its root at
+    /// the given block height.
+    ///
+    /// Returns `None` if the tree was empty at the given block height, or if
+    /// its root at that height is no longer kept in the root history.
+    pub fn note_tree_checkpoint(
+        &self,
+        block_height: u64,
+    ) -> Option<NoteTreeCheckpoint> {
+        let leaves = self.tree.leaves_len_at(block_height);
+        if leaves == 0 {
+            return None;
+        }
+
+        let checkpoint = NoteTreeCheckpoint {
+            block_height,
+            leaves,
+            opening: self.tree.opening(leaves - 1)?,
+        };
+
+        if !self.root_exists(&checkpoint.root()) {
+            return None;
+        }
+
+        Some(checkpoint)
+    }
+
     /// Takes some nullifiers and returns a vector containing the ones that
     /// already exists in the contract
     pub fn existing_nullifiers(
@@ -835,6 +1244,41 @@ impl TransferState {
             .collect()
     }
 
+    /// Returns the positions of the nullifiers in the page that already exist,
+    /// where `offset` is the position of the first nullifier of the page in
+    /// the caller's full list.
+    ///
+    /// # Panics
+    /// When the page holds more than [`MAX_NULLIFIERS_PAGE`] nullifiers.
+    pub fn existing_nullifiers_paged(
+        &self,
+        nullifiers: Vec<BlsScalar>,
+        offset: u64,
+    ) -> Vec<u64> {
+        if nullifiers.len() > MAX_NULLIFIERS_PAGE {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "Too many nullifiers in the page",
+            );
+        }
+
+        nullifiers
+            .iter()
+            .zip(offset..)
+            .filter(|(n, _)| self.nullifiers.contains(n))
+            .map(|(_, pos)| pos)
+            .collect()
+    }
+
+    /// Feeds the nullifiers that already exist, one at a time.
+    pub fn existing_nullifiers_feed(&self, nullifiers: Vec<BlsScalar>) {
+        for n in nullifiers {
+            if self.nullifiers.contains(&n) {
+                abi::feed(n);
+            }
+        }
+    }
+
     pub fn account(&self, key: &AccountPublicKey) -> AccountData {
         let key_bytes = key.to_raw_bytes();
         self.accounts
@@ -856,6 +1300,19 @@ impl TransferState {
         }
     }
 
+    /// Return the value the given contract can still pull from the given
+    /// account.
+    pub fn allowance(
+        &self,
+        account: &AccountPublicKey,
+        contract: &ContractId,
+    ) -> u64 {
+        self.allowances
+            .get(&(account.to_raw_bytes(), *contract))
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Return the balance of a given contract.
     pub fn contract_balance(&self, contract_id: &ContractId) -> u64 {
         self.contract_balances
@@ -915,6 +1372,61 @@ impl TransferState {
     pub fn chain_id(&self) -> u8 {
         abi::chain_id()
     }
+
+    /// Returns the hash of the bytecode of the given contract, if it was
+    /// recorded.
+    pub fn code_hash(&self, contract: &ContractId) -> Option<[u8; 32]> {
+        self.code_hashes.get(contract).copied()
+    }
+
+    /// Records the hash of the bytecode of a contract, after it is deployed
+    /// or upgraded.
+    pub fn set_code_hash(&mut self, contract: ContractId, hash: [u8; 32]) {
+        self.code_hashes.insert(contract, hash);
+    }
+
+    /// Returns the number of upgrades of the given contract.
+    pub fn upgrade_nonce(&self, contract: &ContractId) -> u64 {
+        self.upgrade_nonces
+            .get(contract)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Records an upgrade of a contract to the bytecode of the given hash,
+    /// incrementing its upgrade nonce.
+    pub fn record_upgrade(&mut self, contract: ContractId, hash: [u8; 32]) {
+        self.set_code_hash(contract, hash);
+        *self.upgrade_nonces.entry(contract).or_default() += 1;
+    }
+
+    /// Returns the value escrowed as the deposit of the given contract.
+    pub fn deploy_deposit(&self, contract: &ContractId) -> u64 {
+        self.deploy_deposits
+            .get(contract)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Escrows the value of the given gas as the deposit of a contract, after
+    /// it is deployed or upgraded by the ongoing transaction.
+    ///
+    /// The gas is withheld from the refund of the transaction, so its value
+    /// is kept by the transfer contract instead of being paid as fee.
+    pub fn escrow_deposit(&mut self, contract: ContractId, gas: u64) {
+        let value = gas * transitory::transaction().gas_price();
+        transitory::escrow_gas(gas);
+
+        *self.deploy_deposits.entry(contract).or_default() += value;
+
+        abi::emit(
+            DEPLOY_DEPOSIT_TOPIC,
+            ContractDeployDeposit {
+                contract,
+                deposit: value,
+            },
+        );
+    }
 }
 
 fn verify_tx_proof(tx: &PhoenixTransaction) -> bool {
@@ -963,4 +1475,25 @@ mod test_transfer {
         assert!(existing.contains(&two));
         assert!(existing.contains(&three));
     }
+
+    #[test]
+    fn find_existing_nullifiers_paged() {
+        let mut transfer = TransferState::new();
+
+        for i in 1..10 {
+            transfer.nullifiers.insert(BlsScalar::from(i));
+        }
+
+        let nullifiers: Vec<BlsScalar> = [0u64, 1, 2, 10, 11, 3]
+            .into_iter()
+            .map(BlsScalar::from)
+            .collect();
+
+        // the positions are relative to the offset of the page
+        let (first, second) = nullifiers.split_at(3);
+        let existing = transfer.existing_nullifiers_paged(first.to_vec(), 0);
+        assert_eq!(existing, vec![1, 2]);
+        let existing = transfer.existing_nullifiers_paged(second.to_vec(), 3);
+        assert_eq!(existing, vec![5]);
+    }
 }
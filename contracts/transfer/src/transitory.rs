@@ -76,6 +76,9 @@ pub struct OngoingTransaction {
     pub deposit: Deposit,
     /// The notes that have been inserted into the tree.
     pub notes: Vec<Note>,
+    /// The gas withheld from the refund, escrowed as the deposit of a
+    /// deployed or upgraded contract.
+    pub escrowed_gas: u64,
 }
 
 static mut CURRENT_TX: Option<OngoingTransaction> = None;
@@ -113,6 +116,7 @@ pub fn put_transaction(tx: impl Into<Transaction>) {
             tx,
             deposit,
             notes: Vec::new(),
+            escrowed_gas: 0,
         });
     }
 }
@@ -140,6 +144,17 @@ pub fn push_note(note: Note) {
     }
 }
 
+/// Withhold the given gas from the refund of the ongoing transaction.
+pub fn escrow_gas(gas: u64) {
+    unsafe {
+        let escrowed_gas = &mut CURRENT_TX
+            .as_mut()
+            .expect("There must be an ongoing transaction")
+            .escrowed_gas;
+        *escrowed_gas += gas;
+    }
+}
+
 /// Get a reference of the current ongoing transaction.
 pub fn transaction() -> &'static Transaction {
     unsafe {
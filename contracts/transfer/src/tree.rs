@@ -115,6 +115,16 @@ impl Tree {
         self.tree.opening(pos)
     }
 
+    /// Return the amount of leaves that were stored in the tree up to, and
+    /// including, the given `block_height`.
+    pub fn leaves_len_at(&self, block_height: u64) -> u64 {
+        // the leaves are strictly increasing in block-height, see
+        // `Tree::leaves`
+        self.leaves
+            .partition_point(|leaf| leaf.block_height <= block_height)
+            as u64
+    }
+
     /// Return the amount of leaves, i.e. notes, that are stored in the tree.
     pub fn leaves_len(&self) -> u64 {
         self.leaves.len() as u64
@@ -4,7 +4,8 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use dusk_core::abi::{ContractError, ContractId};
+use dusk_bytes::Serializable;
+use dusk_core::abi::{ContractError, ContractFailure, ContractId};
 use dusk_core::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
 };
@@ -18,10 +19,11 @@ use dusk_core::transfer::withdraw::{
     Withdraw, WithdrawReceiver, WithdrawReplayToken,
 };
 use dusk_core::transfer::{
-    ContractToAccount, ContractToContract, Transaction, TRANSFER_CONTRACT,
+    Approve, ApprovedDeposit, ContractToAccount, ContractToContract, SetLimits,
+    Transaction, TransferLimits, TRANSFER_CONTRACT,
 };
 use dusk_core::{dusk, JubJubScalar, LUX};
-use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use dusk_vm::{execute, ContractData, Error, ExecutionConfig, Session, VM};
 use ff::Field;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -50,10 +52,26 @@ const BOB_ID: ContractId = {
 };
 
 const OWNER: [u8; 32] = [0; 32];
+const TRANSFER_OWNER_SEED: u64 = 0x0123;
 const CHAIN_ID: u8 = 0xFA;
 
 const NO_CONFIG: ExecutionConfig = ExecutionConfig::DEFAULT;
 
+/// The secret key of the account owning the transfer contract, which signs
+/// its limits.
+fn transfer_owner_sk() -> AccountSecretKey {
+    AccountSecretKey::random(&mut StdRng::seed_from_u64(TRANSFER_OWNER_SEED))
+}
+
+/// Recover the failure a direct call to a contract panicked with.
+fn call_failure(err: Error) -> ContractFailure {
+    let Error::Panic(msg) = err else {
+        panic!("The call should panic, got {err:?}");
+    };
+    ContractFailure::from_panic(&msg)
+        .expect("The panic should carry a failure code")
+}
+
 /// Instantiate the virtual machine with the transfer contract deployed, with a
 /// moonlight account owning the `MOONLIGHT_GENESIS_VALUE` and alice and bob
 /// contracts deployed with alice contract owning `ALICE_GENESIS_VALUE`.
@@ -76,7 +94,7 @@ fn instantiate(moonlight_pk: &AccountPublicKey) -> Session {
         .deploy(
             transfer_bytecode,
             ContractData::builder()
-                .owner(OWNER)
+                .owner(AccountPublicKey::from(&transfer_owner_sk()).to_bytes())
                 .contract_id(TRANSFER_CONTRACT),
             GAS_LIMIT,
         )
@@ -338,6 +356,55 @@ fn transfer_gas_fails() {
     );
 }
 
+#[test]
+fn transfer_value_overflow_fails() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sender_sk = AccountSecretKey::random(rng);
+    let moonlight_sender_pk = AccountPublicKey::from(&moonlight_sender_sk);
+
+    let moonlight_receiver_pk =
+        AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let session = &mut instantiate(&moonlight_sender_pk);
+
+    // the value and the gas paid sum to more than `u64::MAX`, which would
+    // wrap around to a total the sender can cover
+    let transaction = Transaction::moonlight(
+        &moonlight_sender_sk,
+        Some(moonlight_receiver_pk),
+        u64::MAX,
+        0,
+        GAS_LIMIT,
+        LUX,
+        MOONLIGHT_GENESIS_NONCE + 1,
+        CHAIN_ID,
+        None::<TransactionData>,
+    )
+    .expect("Creating moonlight transaction should succeed");
+
+    let result = execute(session, &transaction, &NO_CONFIG);
+
+    assert!(
+        result.is_err(),
+        "Transaction should fail due to the value overflowing"
+    );
+
+    let sender_account = account(session, &moonlight_sender_pk)
+        .expect("Getting the sender account should succeed");
+    let receiver_account = account(session, &moonlight_receiver_pk)
+        .expect("Getting the receiver account should succeed");
+
+    assert_eq!(
+        sender_account.balance, MOONLIGHT_GENESIS_VALUE,
+        "The sender account should still have the genesis value"
+    );
+    assert_eq!(
+        receiver_account.balance, 0,
+        "The receiver account should still be empty"
+    );
+}
+
 /// Performs a simple contract-call.
 #[test]
 fn alice_ping() {
@@ -982,3 +1049,236 @@ fn contract_to_account_direct_call() {
         "Alice's balance should be unchanged"
     );
 }
+
+/// In this test we limit the value alice can withdraw in a block, and check
+/// that transfers from alice to an account beyond the limit fail.
+#[test]
+fn contract_to_account_limits() {
+    const TRANSFER_VALUE: u64 = ALICE_GENESIS_VALUE / 4;
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sk = AccountSecretKey::random(rng);
+    let moonlight_pk = AccountPublicKey::from(&moonlight_sk);
+
+    let session = &mut instantiate(&moonlight_pk);
+
+    let limits = TransferLimits {
+        max_block_withdrawal: Some(2 * TRANSFER_VALUE),
+        contract_caps: vec![(ALICE_ID, TRANSFER_VALUE)],
+        ..TransferLimits::new()
+    };
+    let owner_sk = transfer_owner_sk();
+
+    // limits signed by someone other than the owner are rejected
+    let forged = SetLimits::new(&moonlight_sk, limits.clone(), 1, CHAIN_ID);
+    let err = session
+        .call::<_, ()>(TRANSFER_CONTRACT, "set_limits", &forged, GAS_LIMIT)
+        .expect_err("Setting forged limits should fail");
+    assert_eq!(call_failure(err).code, ContractFailure::INVALID_SIGNATURE);
+
+    // limits signed for another chain are rejected, be it with the chain ID
+    // they were signed for or with this chain's ID
+    let mut other_chain =
+        SetLimits::new(&owner_sk, limits.clone(), 1, CHAIN_ID + 1);
+    let err = session
+        .call::<_, ()>(TRANSFER_CONTRACT, "set_limits", &other_chain, GAS_LIMIT)
+        .expect_err("Setting limits for another chain should fail");
+    assert_eq!(call_failure(err).code, ContractFailure::WRONG_CHAIN_ID);
+    other_chain.chain_id = CHAIN_ID;
+    let err = session
+        .call::<_, ()>(TRANSFER_CONTRACT, "set_limits", &other_chain, GAS_LIMIT)
+        .expect_err("Replaying limits from another chain should fail");
+    assert_eq!(call_failure(err).code, ContractFailure::INVALID_SIGNATURE);
+
+    let signed = SetLimits::new(&owner_sk, limits.clone(), 1, CHAIN_ID);
+    session
+        .call::<_, ()>(TRANSFER_CONTRACT, "set_limits", &signed, GAS_LIMIT)
+        .expect("Setting the limits should succeed");
+
+    // the same signed limits can't be replayed
+    let err = session
+        .call::<_, ()>(TRANSFER_CONTRACT, "set_limits", &signed, GAS_LIMIT)
+        .expect_err("Replaying the limits should fail");
+    assert_eq!(call_failure(err).code, ContractFailure::INVALID_NONCE);
+
+    let set_limits: TransferLimits = session
+        .call(TRANSFER_CONTRACT, "limits", &(), GAS_LIMIT)
+        .expect("Querying the limits should succeed")
+        .data;
+    assert_eq!(set_limits, limits, "The limits should be set");
+
+    // the first transfer is within alice's cap, while the second exceeds it
+    for (nonce, within_limits) in [(1, true), (2, false)] {
+        let contract_call = Some(ContractCall {
+            contract: ALICE_ID,
+            fn_name: String::from("contract_to_account"),
+            fn_args: rkyv::to_bytes::<_, 256>(&ContractToAccount {
+                account: moonlight_pk,
+                value: TRANSFER_VALUE,
+            })
+            .expect("Serializing should succeed")
+            .to_vec(),
+        });
+
+        let transaction = Transaction::moonlight(
+            &moonlight_sk,
+            None,
+            0,
+            0,
+            GAS_LIMIT,
+            LUX,
+            MOONLIGHT_GENESIS_NONCE + nonce,
+            CHAIN_ID,
+            contract_call,
+        )
+        .expect("Creating moonlight transaction should succeed");
+
+        let receipt = execute(session, &transaction, &NO_CONFIG)
+            .expect("Transaction should succeed");
+
+        match receipt.data {
+            Ok(_) => assert!(within_limits, "The transfer should fail"),
+            Err(err) => {
+                assert!(!within_limits, "The transfer should succeed");
+                let failure = ContractFailure::from_error(&err)
+                    .expect("The transfer should fail with a failure code");
+                assert_eq!(failure.code, ContractFailure::LIMIT_EXCEEDED);
+            }
+        }
+    }
+
+    let alice_balance = contract_balance(session, ALICE_ID)
+        .expect("Querying the contract balance should succeed");
+    assert_eq!(
+        alice_balance,
+        ALICE_GENESIS_VALUE - TRANSFER_VALUE,
+        "Alice's balance should have decreased by a single transfer"
+    );
+}
+
+#[test]
+fn approved_deposit() {
+    const ALLOWANCE: u64 = dusk(10.0);
+    const DEPOSIT_VALUE: u64 = dusk(6.0);
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let moonlight_sk = AccountSecretKey::random(rng);
+    let moonlight_pk = AccountPublicKey::from(&moonlight_sk);
+
+    let session = &mut instantiate(&moonlight_pk);
+
+    let contract_call = Some(ContractCall {
+        contract: TRANSFER_CONTRACT,
+        fn_name: String::from("approve"),
+        fn_args: rkyv::to_bytes::<_, 256>(&Approve {
+            contract: ALICE_ID,
+            value: ALLOWANCE,
+        })
+        .expect("Serializing should succeed")
+        .to_vec(),
+    });
+
+    let transaction = Transaction::moonlight(
+        &moonlight_sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        LUX,
+        MOONLIGHT_GENESIS_NONCE + 1,
+        CHAIN_ID,
+        contract_call,
+    )
+    .expect("Creating moonlight transaction should succeed");
+
+    let receipt = execute(session, &transaction, &NO_CONFIG)
+        .expect("Transaction should succeed");
+    receipt.data.expect("The approval should succeed");
+    let mut gas_spent = receipt.gas_spent;
+
+    let allowance: u64 = session
+        .call(
+            TRANSFER_CONTRACT,
+            "allowance",
+            &(moonlight_pk, ALICE_ID),
+            GAS_LIMIT,
+        )
+        .expect("Querying the allowance should succeed")
+        .data;
+    assert_eq!(allowance, ALLOWANCE, "The allowance should be set");
+
+    // the first deposit is within the allowance, while the second exceeds
+    // what is left of it
+    for (nonce, within_allowance) in [(2, true), (3, false)] {
+        let contract_call = Some(ContractCall {
+            contract: ALICE_ID,
+            fn_name: String::from("approved_deposit"),
+            fn_args: rkyv::to_bytes::<_, 256>(&ApprovedDeposit {
+                account: moonlight_pk,
+                value: DEPOSIT_VALUE,
+            })
+            .expect("Serializing should succeed")
+            .to_vec(),
+        });
+
+        let transaction = Transaction::moonlight(
+            &moonlight_sk,
+            None,
+            0,
+            0,
+            GAS_LIMIT,
+            LUX,
+            MOONLIGHT_GENESIS_NONCE + nonce,
+            CHAIN_ID,
+            contract_call,
+        )
+        .expect("Creating moonlight transaction should succeed");
+
+        let receipt = execute(session, &transaction, &NO_CONFIG)
+            .expect("Transaction should succeed");
+        gas_spent += receipt.gas_spent;
+
+        match receipt.data {
+            Ok(_) => assert!(within_allowance, "The deposit should fail"),
+            Err(err) => {
+                assert!(!within_allowance, "The deposit should succeed");
+                let failure = ContractFailure::from_error(&err)
+                    .expect("The deposit should fail with a failure code");
+                assert_eq!(failure.code, ContractFailure::UNAUTHORIZED);
+            }
+        }
+    }
+
+    let allowance: u64 = session
+        .call(
+            TRANSFER_CONTRACT,
+            "allowance",
+            &(moonlight_pk, ALICE_ID),
+            GAS_LIMIT,
+        )
+        .expect("Querying the allowance should succeed")
+        .data;
+    assert_eq!(
+        allowance,
+        ALLOWANCE - DEPOSIT_VALUE,
+        "The allowance should have decreased by a single deposit"
+    );
+
+    let moonlight_account = account(session, &moonlight_pk)
+        .expect("Getting the account should succeed");
+    let alice_balance = contract_balance(session, ALICE_ID)
+        .expect("Querying the contract balance should succeed");
+
+    assert_eq!(
+        moonlight_account.balance,
+        MOONLIGHT_GENESIS_VALUE - gas_spent - DEPOSIT_VALUE,
+        "The account's balance should have decreased by the spent gas and a single deposit"
+    );
+    assert_eq!(
+        alice_balance,
+        ALICE_GENESIS_VALUE + DEPOSIT_VALUE,
+        "Alice's balance should have increased by a single deposit"
+    );
+}
@@ -16,9 +16,9 @@ use dusk_core::signatures::bls::{
 };
 use dusk_core::transfer::data::{ContractCall, TransactionData};
 use dusk_core::transfer::phoenix::{
-    Note, NoteLeaf, NoteOpening, NoteTreeItem, PublicKey as PhoenixPublicKey,
-    SecretKey as PhoenixSecretKey, Transaction as PhoenixTransaction,
-    ViewKey as PhoenixViewKey,
+    Note, NoteLeaf, NoteOpening, NoteTreeCheckpoint, NoteTreeItem,
+    PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
+    Transaction as PhoenixTransaction, ViewKey as PhoenixViewKey,
 };
 use dusk_core::transfer::withdraw::{
     Withdraw, WithdrawReceiver, WithdrawReplayToken,
@@ -1514,6 +1514,61 @@ fn contract_to_account() {
     );
 }
 
+#[test]
+fn note_tree_checkpoint() {
+    const BLOCKS: u64 = 6;
+
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let phoenix_sk = PhoenixSecretKey::random(rng);
+    let phoenix_pk = PhoenixPublicKey::from(&phoenix_sk);
+
+    let session = &mut instantiate::<5>(rng, &phoenix_sk);
+
+    // keep the root of the tree at each block height, pushing a different
+    // number of notes in each block
+    let mut roots = vec![root(session).expect("Getting the root should work")];
+    for block_height in 1..=BLOCKS {
+        for _ in 0..block_height {
+            let sender_blinder = [
+                JubJubScalar::random(&mut *rng),
+                JubJubScalar::random(&mut *rng),
+            ];
+            let note = Note::transparent(
+                rng,
+                &phoenix_pk,
+                &phoenix_pk,
+                LUX,
+                sender_blinder,
+            );
+            session
+                .call::<_, Note>(
+                    TRANSFER_CONTRACT,
+                    "push_note",
+                    &(block_height, note),
+                    GAS_LIMIT,
+                )
+                .expect("Pushing note should succeed");
+        }
+        update_root(session).expect("Updating the root should succeed");
+        roots.push(root(session).expect("Getting the root should work"));
+    }
+
+    let current_root = roots[BLOCKS as usize];
+    for (block_height, root) in roots.iter().enumerate() {
+        let checkpoint = note_tree_checkpoint(session, block_height as u64)
+            .expect("Querying the checkpoint should succeed")
+            .expect("The checkpoint should exist");
+
+        assert_eq!(checkpoint.root(), *root);
+        assert!(checkpoint.verify(&current_root, root));
+
+        // the checkpoint doesn't prove other roots
+        let other_root = roots[(block_height + 1) % roots.len()];
+        assert!(!checkpoint.verify(&current_root, &other_root));
+    }
+}
+
 // ----------------
 // helper functions
 
@@ -1558,6 +1613,20 @@ fn opening(
         .map(|r| r.data)
 }
 
+fn note_tree_checkpoint(
+    session: &mut Session,
+    block_height: u64,
+) -> Result<Option<NoteTreeCheckpoint>, VMError> {
+    session
+        .call(
+            TRANSFER_CONTRACT,
+            "note_tree_checkpoint",
+            &block_height,
+            GAS_LIMIT,
+        )
+        .map(|r| r.data)
+}
+
 fn gen_nullifiers(
     session: &mut Session,
     notes_pos: impl AsRef<[u64]>,
@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg_attr(target_family = "wasm", no_std)]
+#![cfg(target_family = "wasm")]
+#![feature(arbitrary_self_types)]
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+
+extern crate alloc;
+
+use dusk_core::abi;
+
+mod state;
+use state::VestingState;
+
+static mut STATE: VestingState = VestingState::new();
+
+// Transactions
+
+#[no_mangle]
+unsafe fn create_grant(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |arg| STATE.create_grant(arg))
+}
+
+#[no_mangle]
+unsafe fn release(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |grant_id| STATE.release(grant_id))
+}
+
+#[no_mangle]
+unsafe fn revoke(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |grant_id| STATE.revoke(grant_id))
+}
+
+// Queries
+
+#[no_mangle]
+unsafe fn grant(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |grant_id| STATE.grant(grant_id).cloned())
+}
+
+#[no_mangle]
+unsafe fn releasable(arg_len: u32) -> u32 {
+    abi::wrap_call(arg_len, |grant_id| STATE.releasable(grant_id))
+}
@@ -0,0 +1,225 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::collections::BTreeMap;
+
+use dusk_core::abi::{self, ContractFailure};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+use dusk_core::vesting::{
+    CreateGrant, GrantData, GrantEvent, GrantId, ReleaseEvent, RevokeEvent,
+    GRANT_TOPIC, RELEASE_TOPIC, REVOKE_TOPIC,
+};
+
+/// Contract locking Dusk for beneficiaries, releasing it on a vesting
+/// schedule.
+///
+/// Grants are funded with the deposit of the transaction creating them, and
+/// the contract's balance always covers the part of the grants not released
+/// yet.
+#[derive(Debug, Default, Clone)]
+pub struct VestingState {
+    next_id: GrantId,
+    grants: BTreeMap<GrantId, GrantData>,
+}
+
+impl VestingState {
+    pub const fn new() -> Self {
+        Self {
+            next_id: 0,
+            grants: BTreeMap::new(),
+        }
+    }
+
+    /// Create a grant funded with the deposit of the calling transaction.
+    pub fn create_grant(&mut self, grant: CreateGrant) -> GrantId {
+        let grantor = public_sender();
+
+        let value = grant.value;
+        if value == 0 {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "A grant should be funded with a deposit",
+            );
+        }
+        if grant.schedule.cliff > grant.schedule.duration {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The cliff should not be longer than the vesting duration",
+            );
+        }
+
+        let _: () = abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value)
+            .expect("Depositing the grant should succeed");
+
+        let grant_id = self.next_id;
+        self.next_id += 1;
+
+        self.grants.insert(
+            grant_id,
+            GrantData {
+                grantor,
+                beneficiary: grant.beneficiary,
+                schedule: grant.schedule,
+                total: value,
+                released: 0,
+                revocable: grant.revocable,
+                revoked: false,
+            },
+        );
+
+        abi::emit(
+            GRANT_TOPIC,
+            GrantEvent {
+                grant_id,
+                grantor,
+                beneficiary: grant.beneficiary,
+                value,
+            },
+        );
+
+        grant_id
+    }
+
+    /// Release the funds vested so far to the beneficiary of the grant.
+    ///
+    /// Anyone can release the vested funds, since they are always paid to the
+    /// beneficiary.
+    pub fn release(&mut self, grant_id: GrantId) -> u64 {
+        let height = abi::block_height();
+        let grant = self.grant_mut(grant_id);
+
+        let value = grant.releasable(height);
+        if value == 0 {
+            abi::fail(
+                ContractFailure::INSUFFICIENT_BALANCE,
+                "No vested funds to release",
+            );
+        }
+        grant.released += value;
+        let beneficiary = grant.beneficiary;
+
+        // forget grants that were fully released
+        if grant.released == grant.total {
+            self.grants.remove(&grant_id);
+        }
+
+        pay(beneficiary, value);
+
+        abi::emit(
+            RELEASE_TOPIC,
+            ReleaseEvent {
+                grant_id,
+                beneficiary,
+                value,
+            },
+        );
+
+        value
+    }
+
+    /// Revoke a grant, returning the funds that aren't vested yet to the
+    /// grantor.
+    ///
+    /// The funds vested before the revocation can still be released to the
+    /// beneficiary.
+    pub fn revoke(&mut self, grant_id: GrantId) -> u64 {
+        let sender = public_sender();
+        let height = abi::block_height();
+        let grant = self.grant_mut(grant_id);
+
+        if grant.grantor != sender {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "Only the grantor can revoke a grant",
+            );
+        }
+        if !grant.revocable {
+            abi::fail(
+                ContractFailure::UNAUTHORIZED,
+                "The grant is not revocable",
+            );
+        }
+        if grant.revoked {
+            abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The grant is already revoked",
+            );
+        }
+
+        let vested = grant.vested(height);
+        let value = grant.total - vested;
+        grant.total = vested;
+        grant.revoked = true;
+        let grantor = grant.grantor;
+
+        if grant.released == grant.total {
+            self.grants.remove(&grant_id);
+        }
+
+        pay(grantor, value);
+
+        abi::emit(
+            REVOKE_TOPIC,
+            RevokeEvent {
+                grant_id,
+                grantor,
+                value,
+            },
+        );
+
+        value
+    }
+
+    fn grant_mut(&mut self, grant_id: GrantId) -> &mut GrantData {
+        match self.grants.get_mut(&grant_id) {
+            Some(grant) => grant,
+            None => abi::fail(
+                ContractFailure::INVALID_ARGUMENT,
+                "The grant doesn't exist",
+            ),
+        }
+    }
+
+    /// The data of the given grant, if it wasn't fully released.
+    pub fn grant(&self, grant_id: GrantId) -> Option<&GrantData> {
+        self.grants.get(&grant_id)
+    }
+
+    /// The value that can currently be released from the given grant.
+    pub fn releasable(&self, grant_id: GrantId) -> u64 {
+        self.grants
+            .get(&grant_id)
+            .map(|grant| grant.releasable(abi::block_height()))
+            .unwrap_or_default()
+    }
+}
+
+/// Pays the given value out of the contract's balance to a public account.
+fn pay(account: AccountPublicKey, value: u64) {
+    if value > 0 {
+        let _: () = abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_account",
+            &ContractToAccount { account, value },
+        )
+        .expect("Paying out of the contract's balance should succeed");
+    }
+}
+
+/// The public sender of the calling transaction.
+///
+/// # Panics
+/// When the contract is called by a shielded transaction.
+fn public_sender() -> AccountPublicKey {
+    match abi::public_sender() {
+        Some(pk) => pk,
+        None => abi::fail(
+            ContractFailure::UNAUTHORIZED,
+            "Grants can only be managed by a public account",
+        ),
+    }
+}
@@ -0,0 +1,288 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ContractError, ContractId, ARGBUF_LEN};
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_core::vesting::{CreateGrant, GrantData, GrantId, VestingSchedule};
+use dusk_core::{dusk, LUX};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+
+const MOONLIGHT_GENESIS_VALUE: u64 = dusk(10_000.0);
+
+const GAS_LIMIT: u64 = 0x10000000;
+const GAS_PRICE: u64 = LUX;
+
+const VESTING_ID: ContractId = {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 0x23;
+    ContractId::from_bytes(bytes)
+};
+
+const OWNER: [u8; 32] = [0; 32];
+const CHAIN_ID: u8 = 0xFA;
+
+const CONFIG: ExecutionConfig = ExecutionConfig {
+    with_public_sender: true,
+    ..ExecutionConfig::DEFAULT
+};
+
+/// Deploy the transfer and vesting contracts, with the given moonlight
+/// accounts owning the `MOONLIGHT_GENESIS_VALUE`, returning the commit to
+/// start sessions from.
+fn instantiate(vm: &mut VM, moonlight_pks: &[AccountPublicKey]) -> [u8; 32] {
+    let transfer_bytecode = include_bytes!(
+        "../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let vesting_bytecode = include_bytes!(
+        "../../../target/dusk/wasm32-unknown-unknown/release/vesting_contract.wasm"
+    );
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner(OWNER)
+                .contract_id(TRANSFER_CONTRACT),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+
+    session
+        .deploy(
+            vesting_bytecode,
+            ContractData::builder().owner(OWNER).contract_id(VESTING_ID),
+            GAS_LIMIT,
+        )
+        .expect("Deploying the vesting contract should succeed");
+
+    for pk in moonlight_pks {
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(*pk, MOONLIGHT_GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+    }
+
+    session.commit().expect("Committing should succeed")
+}
+
+/// Commit the given session and start a new one at the given block height.
+fn advance(vm: &VM, session: Session, height: u64) -> Session {
+    let base = session.commit().expect("Committing should succeed");
+    vm.session(base, CHAIN_ID, height)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Call the vesting contract from the given moonlight account, depositing
+/// the given value, and returning whether the call succeeded.
+fn call_vesting(
+    session: &mut Session,
+    sender_sk: &AccountSecretKey,
+    nonce: u64,
+    deposit: u64,
+    fn_name: &str,
+    fn_arg: &impl Serialize<AllocSerializer<ARGBUF_LEN>>,
+) -> bool {
+    let call = ContractCall::new(VESTING_ID, fn_name, fn_arg)
+        .expect("Creating the contract call should succeed");
+
+    let tx = wallet_core::transaction::moonlight(
+        sender_sk,
+        None,
+        0,
+        deposit,
+        GAS_LIMIT,
+        GAS_PRICE,
+        nonce,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let receipt =
+        execute(session, &tx, &CONFIG).expect("Executing TX should succeed");
+
+    match receipt.data {
+        Ok(_) => true,
+        Err(err) => {
+            assert!(matches!(err, ContractError::Panic(_)), "{err:?}");
+            false
+        }
+    }
+}
+
+fn moonlight_balance(session: &mut Session, pk: &AccountPublicKey) -> u64 {
+    session
+        .call::<_, AccountData>(TRANSFER_CONTRACT, "account", pk, GAS_LIMIT)
+        .expect("Querying the account should succeed")
+        .data
+        .balance
+}
+
+fn vesting_balance(session: &mut Session) -> u64 {
+    session
+        .call(
+            TRANSFER_CONTRACT,
+            "contract_balance",
+            &VESTING_ID,
+            GAS_LIMIT,
+        )
+        .expect("Querying the contract balance should succeed")
+        .data
+}
+
+#[test]
+fn cliff_linear_release_and_revoke() {
+    let rng = &mut StdRng::seed_from_u64(0xfeeb);
+
+    let grantor_sk = AccountSecretKey::random(rng);
+    let grantor_pk = AccountPublicKey::from(&grantor_sk);
+    let stranger_sk = AccountSecretKey::random(rng);
+    let stranger_pk = AccountPublicKey::from(&stranger_sk);
+    let beneficiary_pk = AccountPublicKey::from(&AccountSecretKey::random(rng));
+
+    let vm = &mut VM::ephemeral().expect("Creating ephemeral VM should work");
+    let base = instantiate(vm, &[grantor_pk, stranger_pk]);
+    let mut session = vm
+        .session(base, CHAIN_ID, 1)
+        .expect("Instantiating new session should succeed");
+
+    const GRANT_VALUE: u64 = dusk(1_000.0);
+    const GRANT_ID: GrantId = 0;
+
+    let mut grant = CreateGrant {
+        beneficiary: beneficiary_pk,
+        value: GRANT_VALUE,
+        schedule: VestingSchedule {
+            start: 100,
+            cliff: 100,
+            duration: 400,
+        },
+        revocable: true,
+    };
+
+    // the deposit must match the value of the grant
+    assert!(!call_vesting(
+        &mut session,
+        &grantor_sk,
+        1,
+        GRANT_VALUE - 1,
+        "create_grant",
+        &grant
+    ));
+
+    // a cliff longer than the vesting is refused
+    grant.schedule.cliff = 500;
+    assert!(!call_vesting(
+        &mut session,
+        &grantor_sk,
+        2,
+        GRANT_VALUE,
+        "create_grant",
+        &grant
+    ));
+
+    grant.schedule.cliff = 100;
+    assert!(call_vesting(
+        &mut session,
+        &grantor_sk,
+        3,
+        GRANT_VALUE,
+        "create_grant",
+        &grant
+    ));
+    assert_eq!(vesting_balance(&mut session), GRANT_VALUE);
+
+    // nothing is released before the end of the cliff
+    let mut session = advance(vm, session, 150);
+    assert!(!call_vesting(
+        &mut session,
+        &grantor_sk,
+        4,
+        0,
+        "release",
+        &GRANT_ID
+    ));
+
+    // half of the grant is vested halfway through the vesting
+    let mut session = advance(vm, session, 300);
+    assert!(call_vesting(
+        &mut session,
+        &grantor_sk,
+        5,
+        0,
+        "release",
+        &GRANT_ID
+    ));
+    assert_eq!(
+        moonlight_balance(&mut session, &beneficiary_pk),
+        dusk(500.0)
+    );
+
+    // only the grantor can revoke the grant
+    let mut session = advance(vm, session, 400);
+    assert!(!call_vesting(
+        &mut session,
+        &stranger_sk,
+        1,
+        0,
+        "revoke",
+        &GRANT_ID
+    ));
+    assert!(call_vesting(
+        &mut session,
+        &grantor_sk,
+        6,
+        0,
+        "revoke",
+        &GRANT_ID
+    ));
+
+    // the unvested quarter is returned to the grantor, and the quarter vested
+    // since the last release is left to the beneficiary
+    assert_eq!(vesting_balance(&mut session), dusk(250.0));
+    let data: Option<GrantData> = session
+        .call(VESTING_ID, "grant", &GRANT_ID, GAS_LIMIT)
+        .expect("Querying the grant should succeed")
+        .data;
+    let data = data.expect("The grant should still exist");
+    assert!(data.revoked);
+    assert_eq!(data.total, dusk(750.0));
+
+    let mut session = advance(vm, session, 1_000);
+    let releasable: u64 = session
+        .call(VESTING_ID, "releasable", &GRANT_ID, GAS_LIMIT)
+        .expect("Querying the releasable value should succeed")
+        .data;
+    assert_eq!(releasable, dusk(250.0));
+    assert!(call_vesting(
+        &mut session,
+        &grantor_sk,
+        7,
+        0,
+        "release",
+        &GRANT_ID
+    ));
+    assert_eq!(
+        moonlight_balance(&mut session, &beneficiary_pk),
+        dusk(750.0)
+    );
+    assert_eq!(vesting_balance(&mut session), 0);
+}
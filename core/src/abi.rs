@@ -11,9 +11,21 @@ pub use piecrust_uplink::{
     CONTRACT_ID_BYTES,
 };
 
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as BlsPublicKey;
+
 #[cfg(feature = "abi")]
 pub use self::host_queries::*;
 
+/// The size of the block seed, in bytes.
+pub const SEED_BYTES: usize = 48;
+
 /// Enum storing the metadata identifiers.
 pub enum Metadata {}
 
@@ -24,6 +36,247 @@ impl Metadata {
     pub const BLOCK_HEIGHT: &'static str = "block_height";
     /// The sender of the transaction, if the transaction is public.
     pub const PUBLIC_SENDER: &'static str = "public_sender";
+    /// The seed of the previous block, as agreed by consensus.
+    pub const SEED: &'static str = "seed";
+    /// The timestamp of the current block.
+    pub const BLOCK_TIMESTAMP: &'static str = "block_timestamp";
+    /// The generator of the current block.
+    pub const BLOCK_GENERATOR: &'static str = "block_generator";
+}
+
+/// The topic of the event a contract emits to subscribe to the events of
+/// another contract, see `abi::on_event`.
+pub const SUBSCRIBE_TOPIC: &str = "__subscribe";
+
+/// The topic of the event reported in the receipt of a transaction, with the
+/// transfer contract as its source, when one of its events couldn't be
+/// delivered to a subscribed contract, see [`FailedDelivery`].
+pub const DELIVERY_FAILED_TOPIC: &str = "__delivery_failed";
+
+/// The entrypoint exported by [`query_trampoline!`], used by `abi::call_query`
+/// to perform read-only calls.
+///
+/// [`query_trampoline!`]: crate::query_trampoline
+pub const QUERY_TRAMPOLINE_FN: &str = "__call_query";
+
+/// The prefix of the panic message carrying the result of a read-only call.
+const QUERY_RESULT_PREFIX: &str = "query result: ";
+
+/// The maximum size of the result of a read-only call made with
+/// `abi::call_query`.
+///
+/// The result reaches the caller base64 encoded in the argument buffer, after
+/// a short prefix.
+pub const QUERY_RESULT_LEN: usize =
+    (ARGBUF_LEN - QUERY_RESULT_PREFIX.len()) / 4 * 3;
+
+/// The entrypoint exported by [`stream_receiver!`], receiving the chunks of a
+/// streamed argument.
+///
+/// [`stream_receiver!`]: crate::stream_receiver
+pub const STREAM_CHUNK_FN: &str = "__stream_chunk";
+
+/// The maximum size of the data carried by a single [`ArgChunk`].
+///
+/// Leaves room in the argument buffer for the rest of the chunk.
+pub const STREAM_CHUNK_LEN: usize = ARGBUF_LEN - 256;
+
+/// The entrypoint called on a subscribed contract with an [`EventDelivery`]
+/// for each event it subscribed to.
+pub const ON_EVENT_FN: &str = "on_event";
+
+/// The entrypoint exported by [`contract_metadata!`], returning the
+/// [`ContractMetadata`] of the contract.
+///
+/// [`contract_metadata!`]: crate::contract_metadata
+pub const METADATA_FN: &str = "__metadata";
+
+/// The metadata a contract declares about itself with
+/// [`contract_metadata!`].
+///
+/// Since the metadata is declared in the bytecode of the contract, it is
+/// fixed when the contract is deployed, and only changes when the contract is
+/// upgraded.
+///
+/// [`contract_metadata!`]: crate::contract_metadata
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ContractMetadata {
+    /// The human readable name of the contract.
+    pub name: String,
+    /// The semantic version of the contract.
+    pub version: String,
+    /// The hash of the data-driver able to encode and decode the calls and
+    /// events of the contract, if there is one.
+    pub driver_hash: Option<[u8; 32]>,
+    /// The owner of the contract, as recorded by the host on deployment.
+    ///
+    /// `None` if the owner isn't a valid BLS public key.
+    pub owner: Option<BlsPublicKey>,
+}
+
+/// A subscription to the events emitted by a contract with a given topic,
+/// valid for the rest of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct EventSubscription {
+    /// The contract emitting the events.
+    pub contract: ContractId,
+    /// The topic of the events.
+    pub topic: String,
+}
+
+/// An event delivered to a subscribed contract.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct EventDelivery {
+    /// The contract that emitted the event.
+    pub source: ContractId,
+    /// The topic of the event.
+    pub topic: String,
+    /// The data of the event.
+    pub data: Vec<u8>,
+}
+
+/// An event that couldn't be delivered to a subscribed contract.
+///
+/// The failure doesn't affect the outcome of the transaction, and is only
+/// reported in its receipt with the [`DELIVERY_FAILED_TOPIC`].
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct FailedDelivery {
+    /// The contract the event was delivered to.
+    pub subscriber: ContractId,
+    /// The contract that emitted the event.
+    pub source: ContractId,
+    /// The topic of the event.
+    pub topic: String,
+    /// The error the delivery failed with.
+    pub error: ContractError,
+}
+
+/// The prefix of the panic message carrying a [`ContractFailure`].
+const FAILURE_PREFIX: &str = "contract failure #";
+
+/// A structured contract error, raised with `abi::fail`.
+///
+/// The failure is carried by the panic message of the failing call, so it
+/// reaches the callers as a [`ContractError::Panic`], from which it can be
+/// recovered with [`ContractFailure::from_error`]. This holds for contracts
+/// calling other contracts, as well as for the receipts of transactions and
+/// queries.
+///
+/// Codes below [`ContractFailure::FIRST_CUSTOM_CODE`] are reserved for the
+/// standard failures defined here, while contracts are free to define the
+/// meaning of the codes above it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractFailure {
+    /// The code identifying the failure.
+    pub code: u32,
+    /// A human readable description of the failure.
+    pub message: String,
+}
+
+impl ContractFailure {
+    /// An argument of the call is invalid.
+    pub const INVALID_ARGUMENT: u32 = 1;
+    /// The caller is not allowed to perform the call.
+    pub const UNAUTHORIZED: u32 = 2;
+    /// An account or contract doesn't have enough balance.
+    pub const INSUFFICIENT_BALANCE: u32 = 3;
+    /// A signature doesn't verify.
+    pub const INVALID_SIGNATURE: u32 = 4;
+    /// A nonce is not the expected one.
+    pub const INVALID_NONCE: u32 = 5;
+    /// A proof doesn't verify.
+    pub const INVALID_PROOF: u32 = 6;
+    /// The transaction targets another chain.
+    pub const WRONG_CHAIN_ID: u32 = 7;
+    /// The gas price of the transaction is too low.
+    pub const GAS_PRICE_TOO_LOW: u32 = 8;
+    /// A limit on the value transferred is exceeded.
+    pub const LIMIT_EXCEEDED: u32 = 9;
+    /// The first code available to contracts for their own failures.
+    pub const FIRST_CUSTOM_CODE: u32 = 0x1_0000;
+
+    /// Create a new failure with the given code and message.
+    #[must_use]
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Recover a failure from the panic message of a call.
+    ///
+    /// The failure may be wrapped in further context, such as the location of
+    /// the panic or the error of an inter-contract call.
+    #[must_use]
+    pub fn from_panic(msg: &str) -> Option<Self> {
+        let (_, failure) = msg.split_once(FAILURE_PREFIX)?;
+        let (code, message) = failure.split_once(": ")?;
+        let code = code.parse().ok()?;
+        Some(Self::new(code, message))
+    }
+
+    /// Recover a failure from the error of a call.
+    ///
+    /// Returns `None` if the call didn't fail with `abi::fail`.
+    #[must_use]
+    pub fn from_error(err: &ContractError) -> Option<Self> {
+        match err {
+            ContractError::Panic(msg) => Self::from_panic(msg),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ContractFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{FAILURE_PREFIX}{}: {}", self.code, self.message)
+    }
+}
+
+/// A chunk of an argument streamed to a contract, see `abi::call_streamed`.
+///
+/// An argument of `total_len` bytes is sent as a sequence of chunks, each
+/// carrying at most [`STREAM_CHUNK_LEN`] bytes starting at `offset`. A chunk
+/// with a zero offset starts a new argument.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ArgChunk {
+    /// The length of the whole argument.
+    pub total_len: u64,
+    /// The position of the chunk in the argument.
+    pub offset: u64,
+    /// The data of the chunk.
+    pub data: Vec<u8>,
+}
+
+impl ArgChunk {
+    /// Split an argument into the chunks to stream.
+    ///
+    /// An empty argument is sent as a single empty chunk.
+    #[must_use]
+    pub fn split(arg: &[u8]) -> Vec<Self> {
+        let total_len = arg.len() as u64;
+        if arg.is_empty() {
+            return alloc::vec![Self {
+                total_len,
+                offset: 0,
+                data: Vec::new(),
+            }];
+        }
+        arg.chunks(STREAM_CHUNK_LEN)
+            .enumerate()
+            .map(|(i, data)| Self {
+                total_len,
+                offset: (i * STREAM_CHUNK_LEN) as u64,
+                data: data.to_vec(),
+            })
+            .collect()
+    }
 }
 
 /// Enum storing the available host-queries.
@@ -34,6 +287,10 @@ impl Query {
     pub const HASH: &'static str = "hash";
     /// Host-function name to compute the poseidon-hash of some input-data.
     pub const POSEIDON_HASH: &'static str = "poseidon_hash";
+    /// Host-function name to compute the blake2b-hash of some input-data.
+    pub const BLAKE2B: &'static str = "blake2b";
+    /// Host-function name to compute the sha512-hash of some input-data.
+    pub const SHA512: &'static str = "sha512";
     /// Host-function name to verify a plonk-proof.
     pub const VERIFY_PLONK: &'static str = "verify_plonk";
     /// Host-function name to verify a groth16-bn254 proof.
@@ -57,12 +314,23 @@ pub(crate) mod host_queries {
                               * spend_and_execute */
     };
 
+    use alloc::collections::BTreeMap;
+    use alloc::string::String;
     use alloc::vec::Vec;
+    use core::fmt::{self, Write};
+    use core::ptr::addr_of_mut;
 
     use dusk_bytes::Serializable;
-    use piecrust_uplink::{host_query, meta_data};
+    use piecrust_uplink::{host_query, meta_data, ArgbufWriter};
+    use rkyv::ser::serializers::AllocSerializer;
+    use rkyv::{Archive, Deserialize, Infallible, Serialize};
 
-    use crate::abi::{ContractId, Metadata, Query};
+    use crate::abi::{
+        ArgChunk, ContractError, ContractFailure, ContractId, ContractMetadata,
+        EventSubscription, Metadata, Query, ARGBUF_LEN, METADATA_FN,
+        QUERY_RESULT_LEN, QUERY_RESULT_PREFIX, QUERY_TRAMPOLINE_FN, SEED_BYTES,
+        STREAM_CHUNK_FN, SUBSCRIBE_TOPIC,
+    };
     use crate::signatures::bls::{
         MultisigSignature, PublicKey as BlsPublicKey, Signature as BlsSignature,
     };
@@ -85,6 +353,26 @@ pub(crate) mod host_queries {
         host_query(Query::POSEIDON_HASH, scalars)
     }
 
+    /// Compute the blake2b hash of the given bytes, with an output of
+    /// `hash_len` bytes and the given personalization.
+    ///
+    /// A personalization of all zeroes is equivalent to no personalization.
+    /// If `hash_len` is not between 1 and 64, the result is empty.
+    #[must_use]
+    pub fn blake2b(
+        bytes: Vec<u8>,
+        hash_len: u8,
+        personal: [u8; 16],
+    ) -> Vec<u8> {
+        host_query(Query::BLAKE2B, (bytes, hash_len, personal))
+    }
+
+    /// Compute the sha512 hash of the given bytes.
+    #[must_use]
+    pub fn sha512(bytes: Vec<u8>) -> [u8; 64] {
+        host_query(Query::SHA512, bytes)
+    }
+
     /// Verify that a Groth16 proof in the BN254 pairing is valid for a given
     /// circuit and inputs.
     ///
@@ -158,6 +446,47 @@ pub(crate) mod host_queries {
         meta_data(Metadata::BLOCK_HEIGHT).unwrap()
     }
 
+    /// Get the seed of the previous block.
+    ///
+    /// The seed is agreed upon by consensus and can't be biased by the
+    /// transaction sender, making it suitable as a source of randomness.
+    /// Sessions not executing a block, e.g. queries, use a zeroed seed.
+    ///
+    /// # Panics
+    /// Panics if the chain doesn't store a `[u8; SEED_BYTES]` `SEED` in the
+    /// metadata.
+    #[must_use]
+    pub fn block_seed() -> [u8; SEED_BYTES] {
+        meta_data(Metadata::SEED).unwrap()
+    }
+
+    /// Get the timestamp of the current block, in seconds since the Unix
+    /// epoch.
+    ///
+    /// The timestamp is set by the block generator and is only loosely bound
+    /// to the actual time, so it should not be relied upon with a precision
+    /// finer than the block time. Sessions not executing a block, e.g.
+    /// queries, use a timestamp of zero.
+    ///
+    /// # Panics
+    /// Panics if the chain doesn't store a `u64` `BLOCK_TIMESTAMP` in the
+    /// metadata.
+    #[must_use]
+    pub fn block_timestamp() -> u64 {
+        meta_data(Metadata::BLOCK_TIMESTAMP).unwrap()
+    }
+
+    /// Get the generator of the current block. Returns `None` if the session
+    /// is not executing a block, e.g. for queries.
+    ///
+    /// # Panics
+    /// Panics if the chain doesn't store an `Option<BlsPublicKey>`
+    /// `BLOCK_GENERATOR` in the metadata.
+    #[must_use]
+    pub fn block_generator() -> Option<BlsPublicKey> {
+        meta_data(Metadata::BLOCK_GENERATOR).unwrap()
+    }
+
     /// Get the public sender of the ongoing tx. Returns `None` if the
     /// transaction is shielded.
     ///
@@ -170,17 +499,379 @@ pub(crate) mod host_queries {
             .expect("moonlight sender metadata to be set")
     }
 
-    /// Query owner of a given contract.
-    /// Returns none if contract is not found.
+    /// Get the gas remaining to the current call.
+    ///
+    /// This is the gas limit of the call minus the gas spent so far, and
+    /// allows contracts to bound optional work, failing gracefully instead of
+    /// running out of gas in the middle of a mutation. Note that the gas
+    /// needed to return from the call has yet to be spent.
+    #[must_use]
+    pub fn gas_remaining() -> u64 {
+        limit().saturating_sub(spent())
+    }
+
+    /// Get the gas spent so far by the current call, including the gas spent
+    /// by the calls it made.
+    #[must_use]
+    pub fn gas_spent_so_far() -> u64 {
+        spent()
+    }
+
+    /// Subscribe to the events emitted by `contract` with the given `topic`
+    /// for the rest of the transaction.
+    ///
+    /// After the transaction's call returns successfully, each event matching
+    /// the subscription is delivered, in emission order, to the [`ON_EVENT_FN`]
+    /// entrypoint of the calling contract, which receives an
+    /// [`EventDelivery`] from the transfer contract. Only events emitted after
+    /// the subscription are delivered, and a failing callback only has its own
+    /// changes reverted, with the failure reported as a [`FailedDelivery`]
+    /// event.
+    ///
+    /// Delivery is only performed on networks where event subscriptions are
+    /// enabled.
+    ///
+    /// [`ON_EVENT_FN`]: crate::abi::ON_EVENT_FN
+    /// [`EventDelivery`]: crate::abi::EventDelivery
+    /// [`FailedDelivery`]: crate::abi::FailedDelivery
+    pub fn on_event(contract: ContractId, topic: impl Into<String>) {
+        let subscription = EventSubscription {
+            contract,
+            topic: topic.into(),
+        };
+        emit(SUBSCRIBE_TOPIC, subscription);
+    }
+
+    /// Abort the execution of the contract with a [`ContractFailure`].
+    ///
+    /// The changes made by the call are reverted, and the caller receives a
+    /// [`ContractError::Panic`] from which the failure can be recovered with
+    /// [`ContractFailure::from_error`].
     ///
     /// # Panics
-    /// Panics if owner is not a valid public key (should never happen).
+    /// Always panics, with a message carrying the code and the message of the
+    /// failure.
+    ///
+    /// [`ContractFailure`]: crate::abi::ContractFailure
+    /// [`ContractFailure::from_error`]: crate::abi::ContractFailure::from_error
+    pub fn fail(code: u32, message: &str) -> ! {
+        abort(format_args!("{}", ContractFailure::new(code, message)))
+    }
+
+    extern "C" {
+        #[link_name = "panic"]
+        fn host_panic(arg_len: u32);
+    }
+
+    /// Abort the execution of the contract, handing the given message to the
+    /// host as the message of a panic.
+    ///
+    /// Contracts are built with `panic_immediate_abort`, which discards the
+    /// message of a `panic!`. Messages meant to reach the caller are therefore
+    /// written to the argument buffer directly.
+    fn abort(msg: fmt::Arguments) -> ! {
+        let mut w = ArgbufWriter::default();
+        if w.write_fmt(msg).is_err() {
+            w = ArgbufWriter::default();
+            let _ = w.write_str("PANIC INFO TOO LONG");
+        }
+        panic_with(&w)
+    }
+
+    /// Hand the message written to the argument buffer to the host as the
+    /// message of a panic.
+    fn panic_with(w: &ArgbufWriter) -> ! {
+        #[allow(clippy::cast_possible_truncation)]
+        // SAFETY: the message was written to the argument buffer, and the host
+        // never returns from a panic.
+        unsafe {
+            host_panic(w.ofs() as u32);
+        }
+        unreachable!()
+    }
+
+    /// Call a function of another contract without allowing it to change the
+    /// state.
+    ///
+    /// The call is made through the [`query_trampoline!`] entrypoint of the
+    /// calling contract, which always aborts after the callee returns,
+    /// carrying the result in the message of the panic. Since the VM reverts
+    /// all the changes made during a failed call, any change the callee made,
+    /// including the ones made by re-entering the caller, is discarded. This
+    /// makes it safe to consult untrusted contracts, such as oracles or
+    /// registries.
+    ///
+    /// The callee still runs in the session of the caller, and sees its
+    /// uncommitted changes. Its result can be at most [`QUERY_RESULT_LEN`]
+    /// bytes long. The gas spent by the callee is charged as for a normal
+    /// call.
+    ///
+    /// # Errors
+    /// Errors if the callee fails, if its result is too long, or if it cannot
+    /// be deserialized.
+    ///
+    /// [`query_trampoline!`]: crate::query_trampoline
+    /// [`QUERY_RESULT_LEN`]: crate::abi::QUERY_RESULT_LEN
+    pub fn call_query<A, R>(
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<R, ContractError>
+    where
+        A: Serialize<AllocSerializer<ARGBUF_LEN>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible>,
+    {
+        let fn_arg = rkyv::to_bytes::<_, ARGBUF_LEN>(fn_arg)
+            .map_err(|_| ContractError::Unknown)?
+            .to_vec();
+        let request = (contract, String::from(fn_name), fn_arg);
+
+        let msg = match call::<_, ()>(self_id(), QUERY_TRAMPOLINE_FN, &request)
+        {
+            Err(ContractError::Panic(msg)) => msg,
+            Err(err) => return Err(err),
+            Ok(()) => return Err(ContractError::Unknown),
+        };
+
+        let Some(encoded) = msg.strip_prefix(QUERY_RESULT_PREFIX) else {
+            return Err(ContractError::Panic(msg));
+        };
+        let bytes = decode_base64(encoded).ok_or(ContractError::Unknown)?;
+
+        // SAFETY: as with `call`, the callee is trusted to return a valid
+        // archive of the expected type.
+        let archived = unsafe { rkyv::archived_root::<R>(&bytes) };
+        archived
+            .deserialize(&mut Infallible)
+            .map_err(|_| ContractError::Unknown)
+    }
+
+    /// The body of the entrypoint generated by [`query_trampoline!`].
+    ///
+    /// # Panics
+    /// Always aborts, to revert the changes made by the callee. The message
+    /// carries either the result of the call or the callee's error.
+    ///
+    /// [`query_trampoline!`]: crate::query_trampoline
+    #[doc(hidden)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn query_trampoline(
+        (contract, fn_name, fn_arg): (ContractId, String, Vec<u8>),
+    ) {
+        if caller() != Some(self_id()) {
+            fail(
+                ContractFailure::UNAUTHORIZED,
+                "The query trampoline can only be called by its own contract",
+            );
+        }
+
+        let result = match call_raw(contract, &fn_name, &fn_arg) {
+            Ok(result) => result,
+            Err(err) => abort(format_args!("{err}")),
+        };
+        if result.len() > QUERY_RESULT_LEN {
+            abort(format_args!(
+                "The query result exceeds {QUERY_RESULT_LEN} bytes"
+            ));
+        }
+
+        // The length of the result was checked, so it fits in the buffer
+        let mut w = ArgbufWriter::default();
+        let _ = w.write_str(QUERY_RESULT_PREFIX);
+        for chunk in result.chunks(3) {
+            let encoded = encode_base64(chunk);
+            // The encoding only uses ASCII characters
+            let _ = w.write_str(core::str::from_utf8(&encoded).unwrap_or(""));
+        }
+        panic_with(&w)
+    }
+
+    /// The alphabet of the base64 encoding of query results.
+    const BASE64: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Encode up to three bytes in base64, padding the output.
+    fn encode_base64(chunk: &[u8]) -> [u8; 4] {
+        let mut n = 0u32;
+        for (i, byte) in chunk.iter().enumerate() {
+            n |= u32::from(*byte) << (16 - 8 * i);
+        }
+
+        let mut encoded = [b'='; 4];
+        for (i, c) in encoded.iter_mut().take(chunk.len() + 1).enumerate() {
+            *c = BASE64[(n >> (18 - 6 * i)) as usize & 0x3f];
+        }
+        encoded
+    }
+
+    /// Decode a padded base64 string.
+    #[allow(clippy::cast_possible_truncation)]
+    fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+        let encoded = encoded.as_bytes();
+        if encoded.len() % 4 != 0 {
+            return None;
+        }
+
+        let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+        for chunk in encoded.chunks(4) {
+            let padding =
+                chunk.iter().rev().take_while(|&&c| c == b'=').count();
+            if padding > 2 {
+                return None;
+            }
+
+            let mut n = 0u32;
+            for (i, c) in chunk[..4 - padding].iter().enumerate() {
+                let value = BASE64.iter().position(|b| b == c)?;
+                n |= (value as u32) << (18 - 6 * i);
+            }
+            bytes.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+        }
+        Some(bytes)
+    }
+
+    /// Call a function of another contract with an argument that may be larger
+    /// than [`ARGBUF_LEN`].
+    ///
+    /// The argument is sent in chunks of at most [`STREAM_CHUNK_LEN`] bytes to
+    /// the [`stream_receiver!`] entrypoint of the callee, after which
+    /// `fn_name` is called without arguments. The callee retrieves the whole
+    /// argument with [`take_streamed_arg`].
+    ///
+    /// Each chunk is a separate call, so the gas spent grows with the number
+    /// of chunks.
+    ///
+    /// # Errors
+    /// Errors if sending any of the chunks fails, or if the call fails.
+    ///
+    /// [`ARGBUF_LEN`]: crate::abi::ARGBUF_LEN
+    /// [`STREAM_CHUNK_LEN`]: crate::abi::STREAM_CHUNK_LEN
+    /// [`stream_receiver!`]: crate::stream_receiver
+    pub fn call_streamed(
+        contract: ContractId,
+        fn_name: &str,
+        arg: &[u8],
+    ) -> Result<Vec<u8>, ContractError> {
+        for chunk in ArgChunk::split(arg) {
+            call::<_, ()>(contract, STREAM_CHUNK_FN, &chunk)?;
+        }
+        call_raw(contract, fn_name, &[])
+    }
+
+    /// The arguments being streamed to this contract, by block height and
+    /// caller.
+    ///
+    /// The memory of a contract persists across blocks, so streams are keyed
+    /// by the height of the block they are sent in, and the ones left over by
+    /// earlier blocks are dropped when a new chunk is received.
+    static mut STREAMS: BTreeMap<(u64, Option<ContractId>), (u64, Vec<u8>)> =
+        BTreeMap::new();
+
+    /// The body of the entrypoint generated by [`stream_receiver!`].
+    ///
+    /// # Panics
+    /// Panics if the chunk doesn't follow the previous one sent by the same
+    /// caller, or if it exceeds the length of the argument.
+    ///
+    /// [`stream_receiver!`]: crate::stream_receiver
+    #[doc(hidden)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn receive_chunk(chunk: ArgChunk) {
+        // SAFETY: contracts are single threaded, and the reference doesn't
+        // outlive this function.
+        let streams = unsafe { &mut *addr_of_mut!(STREAMS) };
+
+        let height = block_height();
+        streams.retain(|(stream_height, _), _| *stream_height == height);
+        let (total_len, buf) = streams.entry((height, caller())).or_default();
+
+        if chunk.offset == 0 {
+            *total_len = chunk.total_len;
+            buf.clear();
+        }
+
+        assert!(
+            chunk.total_len == *total_len && chunk.offset == buf.len() as u64,
+            "Argument chunk out of order"
+        );
+        assert!(
+            buf.len() as u64 + chunk.data.len() as u64 <= *total_len,
+            "Argument chunk exceeds the argument length"
+        );
+
+        buf.extend(chunk.data);
+    }
+
+    /// Take the argument streamed by the caller with [`call_streamed`] in the
+    /// current block, ending the stream.
+    ///
+    /// # Panics
+    /// Panics if the caller didn't stream a complete argument.
+    #[must_use]
+    pub fn take_streamed_arg() -> Vec<u8> {
+        // SAFETY: contracts are single threaded, and the reference doesn't
+        // outlive this function.
+        let streams = unsafe { &mut *addr_of_mut!(STREAMS) };
+        let (total_len, buf) = streams
+            .remove(&(block_height(), caller()))
+            .expect("No argument was streamed by the caller");
+
+        assert!(
+            buf.len() as u64 == total_len,
+            "The streamed argument is incomplete"
+        );
+
+        buf
+    }
+
+    /// Query the metadata of a given contract.
+    ///
+    /// Returns `None` if the contract doesn't exist, or if it doesn't declare
+    /// its metadata with [`contract_metadata!`].
+    ///
+    /// The metadata is queried with [`call_query`], so any change the queried
+    /// contract makes while returning it is discarded. The calling contract
+    /// must export the [`query_trampoline!`] entrypoint.
+    ///
+    /// The owner is always taken from the host, so a contract can't report
+    /// an owner other than the one it was deployed with.
+    ///
+    /// [`contract_metadata!`]: crate::contract_metadata
+    /// [`query_trampoline!`]: crate::query_trampoline
+    #[must_use]
+    pub fn contract_metadata(contract: ContractId) -> Option<ContractMetadata> {
+        let mut metadata: ContractMetadata =
+            call_query(contract, METADATA_FN, &()).ok()?;
+        metadata.owner = owner(contract);
+        Some(metadata)
+    }
+
+    /// The body of the entrypoint generated by [`contract_metadata!`].
+    ///
+    /// [`contract_metadata!`]: crate::contract_metadata
+    #[doc(hidden)]
+    #[must_use]
+    pub fn metadata_entry(
+        name: &str,
+        version: &str,
+        driver_hash: Option<[u8; 32]>,
+    ) -> ContractMetadata {
+        ContractMetadata {
+            name: String::from(name),
+            version: String::from(version),
+            driver_hash,
+            owner: try_self_owner(),
+        }
+    }
+
+    /// Query owner of a given contract.
+    ///
+    /// Returns `None` if the contract is not found, or if its owner is not a
+    /// valid BLS public key.
     #[must_use]
     pub fn owner(contract: ContractId) -> Option<BlsPublicKey> {
-        owner_raw(contract).map(|buf| {
-            BlsPublicKey::from_bytes(&buf)
-                .expect("Owner should deserialize correctly")
-        })
+        owner_raw(contract).and_then(|buf| BlsPublicKey::from_bytes(&buf).ok())
     }
 
     /// Query self owner of a given contract.
@@ -189,9 +880,16 @@ pub(crate) mod host_queries {
     /// Panics if owner is not a valid public key (should never happen).
     #[must_use]
     pub fn self_owner() -> BlsPublicKey {
-        let buf = self_owner_raw();
-        BlsPublicKey::from_bytes(&buf)
-            .expect("Owner should deserialize correctly")
+        try_self_owner().expect("Owner should deserialize correctly")
+    }
+
+    /// Query self owner of a given contract, without panicking.
+    ///
+    /// Returns `None` if the owner is not a valid BLS public key, in which
+    /// case [`self_owner_raw`] still returns its raw bytes.
+    #[must_use]
+    pub fn try_self_owner() -> Option<BlsPublicKey> {
+        BlsPublicKey::from_bytes(&self_owner_raw()).ok()
     }
 
     /// Query raw `to_bytes` serialization of the owner of a given contract.
@@ -206,3 +904,73 @@ pub(crate) mod host_queries {
         piecrust_uplink::self_owner()
     }
 }
+
+/// Exports the entrypoint required to perform read-only calls with
+/// `abi::call_query`.
+///
+/// Contracts performing read-only calls must invoke this macro once.
+#[cfg(feature = "abi")]
+#[macro_export]
+macro_rules! query_trampoline {
+    () => {
+        #[no_mangle]
+        unsafe fn __call_query(arg_len: u32) -> u32 {
+            $crate::abi::wrap_call(arg_len, $crate::abi::query_trampoline)
+        }
+    };
+}
+
+/// Exports the entrypoint required to receive arguments streamed with
+/// `abi::call_streamed`.
+///
+/// Contracts receiving streamed arguments must invoke this macro once.
+#[cfg(feature = "abi")]
+#[macro_export]
+macro_rules! stream_receiver {
+    () => {
+        #[no_mangle]
+        unsafe fn __stream_chunk(arg_len: u32) -> u32 {
+            $crate::abi::wrap_call(arg_len, $crate::abi::receive_chunk)
+        }
+    };
+}
+
+/// Exports the entrypoint returning the [`ContractMetadata`] of the contract,
+/// queried with `abi::contract_metadata`.
+///
+/// Takes the name of the contract, optionally its version, and the hash of
+/// its data-driver, if any. The version defaults to the version of the crate
+/// invoking the macro.
+///
+/// Also defines a `self_metadata` function in the invoking module, returning
+/// the metadata of the contract without calling into itself.
+///
+/// ```ignore
+/// dusk_core::contract_metadata!("my-token", None);
+/// ```
+///
+/// [`ContractMetadata`]: crate::abi::ContractMetadata
+#[cfg(feature = "abi")]
+#[macro_export]
+macro_rules! contract_metadata {
+    ($name:expr, $driver_hash:expr) => {
+        $crate::contract_metadata!(
+            $name,
+            env!("CARGO_PKG_VERSION"),
+            $driver_hash
+        );
+    };
+    ($name:expr, $version:expr, $driver_hash:expr) => {
+        /// The metadata of this contract, as declared with
+        /// `contract_metadata!`.
+        #[allow(dead_code)]
+        fn self_metadata() -> $crate::abi::ContractMetadata {
+            $crate::abi::metadata_entry($name, $version, $driver_hash)
+        }
+
+        #[no_mangle]
+        unsafe fn __metadata(arg_len: u32) -> u32 {
+            $crate::abi::wrap_call(arg_len, |_: ()| self_metadata())
+        }
+    };
+}
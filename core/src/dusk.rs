@@ -6,8 +6,23 @@
 
 //! Dusk denomination.
 
+use alloc::string::ToString;
+use core::fmt;
+use core::str::FromStr;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Error;
+
 const DUSK_UNIT: f64 = 1_000_000_000.0;
 
+/// The number of [`LUX`] in one Dusk.
+const LUX_PER_DUSK: u64 = 1_000_000_000;
+
+/// The number of decimals of a Dusk amount.
+const DECIMALS: usize = 9;
+
 /// The minimum increment of Dusk.
 pub const LUX: Dusk = dusk(1.0 / DUSK_UNIT);
 
@@ -34,8 +49,198 @@ pub const fn from_dusk(dusk: Dusk) -> f64 {
     dusk as f64 / DUSK_UNIT
 }
 
+/// An amount of Dusk, counted in [`LUX`], whose arithmetic is checked.
+///
+/// Amounts are parsed from and formatted to their decimal representation in
+/// Dusk, e.g. `"1.5 DUSK"`, and never pass through floating point. Amounts in
+/// Lux are parsed with the `LUX` unit, e.g. `"1500000000 LUX"`, and formatted
+/// with the alternate flag, `{:#}`.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Archive,
+    Serialize,
+    Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Amount(Dusk);
+
+impl Amount {
+    /// The zero amount.
+    pub const ZERO: Self = Self(0);
+
+    /// One Dusk.
+    pub const DUSK: Self = Self(LUX_PER_DUSK);
+
+    /// The largest amount that can be represented.
+    pub const MAX: Self = Self(Dusk::MAX);
+
+    /// Creates an amount of the given Lux.
+    #[must_use]
+    pub const fn from_lux(lux: Dusk) -> Self {
+        Self(lux)
+    }
+
+    /// Returns the amount in Lux.
+    #[must_use]
+    pub const fn lux(self) -> Dusk {
+        self.0
+    }
+
+    /// Adds two amounts.
+    ///
+    /// # Errors
+    /// Errors with [`Error::AmountOverflow`] if the sum doesn't fit an amount.
+    pub const fn checked_add(self, other: Self) -> Result<Self, Error> {
+        match self.0.checked_add(other.0) {
+            Some(lux) => Ok(Self(lux)),
+            None => Err(Error::AmountOverflow),
+        }
+    }
+
+    /// Subtracts an amount from another.
+    ///
+    /// # Errors
+    /// Errors with [`Error::InsufficientBalance`] if `other` is larger than
+    /// the amount.
+    pub const fn checked_sub(self, other: Self) -> Result<Self, Error> {
+        match self.0.checked_sub(other.0) {
+            Some(lux) => Ok(Self(lux)),
+            None => Err(Error::InsufficientBalance),
+        }
+    }
+
+    /// Multiplies an amount by a scalar, such as a gas price by a gas limit.
+    ///
+    /// # Errors
+    /// Errors with [`Error::AmountOverflow`] if the product doesn't fit an
+    /// amount.
+    pub const fn checked_mul(self, factor: u64) -> Result<Self, Error> {
+        match self.0.checked_mul(factor) {
+            Some(lux) => Ok(Self(lux)),
+            None => Err(Error::AmountOverflow),
+        }
+    }
+
+    /// Sums the given amounts.
+    ///
+    /// # Errors
+    /// Errors with [`Error::AmountOverflow`] if the sum doesn't fit an amount.
+    pub fn checked_sum<I>(amounts: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        amounts.into_iter().try_fold(Self::ZERO, Self::checked_add)
+    }
+}
+
+impl From<Dusk> for Amount {
+    fn from(lux: Dusk) -> Self {
+        Self(lux)
+    }
+}
+
+impl From<Amount> for Dusk {
+    fn from(amount: Amount) -> Self {
+        amount.0
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{} LUX", self.0);
+        }
+
+        let whole = self.0 / LUX_PER_DUSK;
+        let fraction = self.0 % LUX_PER_DUSK;
+        if fraction == 0 {
+            return write!(f, "{whole} DUSK");
+        }
+
+        // leave out the trailing zeros of the decimals
+        let mut fraction = fraction;
+        let mut digits = DECIMALS;
+        while fraction % 10 == 0 {
+            fraction /= 10;
+            digits -= 1;
+        }
+        write!(f, "{whole}.{fraction:0digits$} DUSK")
+    }
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    /// Parses an amount in Dusk, with at most 9 decimals, or in Lux when
+    /// followed by the `LUX` unit. The `DUSK` unit is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidAmount(s.to_string());
+
+        let trimmed = s.trim();
+        let upper = trimmed.to_ascii_uppercase();
+
+        if let Some(lux) = upper.strip_suffix("LUX") {
+            let lux = lux.trim_end();
+            if lux.is_empty() || !lux.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            return lux.parse().map(Self).map_err(|_| Error::AmountOverflow);
+        }
+
+        let value = upper
+            .strip_suffix("DUSK")
+            .unwrap_or(upper.as_str())
+            .trim_end();
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty())
+            || !is_digits(whole)
+            || !is_digits(fraction)
+            || fraction.len() > DECIMALS
+            || value.ends_with('.')
+        {
+            return Err(invalid());
+        }
+
+        let whole: Dusk = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| Error::AmountOverflow)?
+        };
+        let fraction: Dusk = if fraction.is_empty() {
+            0
+        } else {
+            let digits = fraction.len();
+            // `digits` is at most `DECIMALS`, so the power of ten can't
+            // overflow
+            #[allow(clippy::cast_possible_truncation)]
+            let scale = 10u64.pow((DECIMALS - digits) as u32);
+            fraction.parse::<Dusk>().map_err(|_| invalid())? * scale
+        };
+
+        Self::DUSK.checked_mul(whole)?.checked_add(Self(fraction))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -50,4 +255,50 @@ mod tests {
     fn lux_is_one() {
         assert_eq!(LUX, 1);
     }
+
+    #[test]
+    fn amount_checked_ops() {
+        let one = Amount::DUSK;
+        let two = one.checked_add(one).unwrap();
+
+        assert_eq!(two.lux(), dusk(2.0));
+        assert_eq!(two.checked_sub(one), Ok(one));
+        assert_eq!(one.checked_mul(3).unwrap().lux(), dusk(3.0));
+        assert_eq!(one.checked_sub(two), Err(Error::InsufficientBalance));
+        assert_eq!(Amount::MAX.checked_add(one), Err(Error::AmountOverflow));
+        assert_eq!(Amount::MAX.checked_mul(2), Err(Error::AmountOverflow));
+        assert_eq!(
+            Amount::checked_sum([one, Amount::MAX]),
+            Err(Error::AmountOverflow)
+        );
+    }
+
+    #[test]
+    fn amount_parse() {
+        let parse = |s: &str| s.parse::<Amount>().map(Amount::lux);
+
+        assert_eq!(parse("1"), Ok(1_000_000_000));
+        assert_eq!(parse("1.5 DUSK"), Ok(1_500_000_000));
+        assert_eq!(parse("0.29"), Ok(290_000_000));
+        assert_eq!(parse(".000000001dusk"), Ok(1));
+        assert_eq!(parse("42 LUX"), Ok(42));
+
+        assert!(parse("").is_err());
+        assert!(parse("1.").is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse("1.0000000001").is_err());
+        assert!(parse("1.5 LUX").is_err());
+        assert_eq!(parse("18446744074 DUSK"), Err(Error::AmountOverflow));
+    }
+
+    #[test]
+    fn amount_display() {
+        let amount = Amount::from_lux(1_500_000_000);
+
+        assert_eq!(amount.to_string(), "1.5 DUSK");
+        assert_eq!(format!("{amount:#}"), "1500000000 LUX");
+        assert_eq!(Amount::ZERO.to_string(), "0 DUSK");
+        assert_eq!(Amount::from_lux(1).to_string(), "0.000000001 DUSK");
+        assert_eq!(amount.to_string().parse(), Ok(amount));
+    }
 }
@@ -35,6 +35,12 @@ pub enum Error {
     ///
     /// [`MAX_MEMO_SIZE`]: crate::transfer::data::MAX_MEMO_SIZE
     MemoTooLarge(usize),
+    /// The result of an operation on amounts doesn't fit an [`Amount`].
+    ///
+    /// [`Amount`]: crate::Amount
+    AmountOverflow,
+    /// The given string isn't a valid amount of Dusk.
+    InvalidAmount(String),
 }
 
 impl fmt::Display for Error {
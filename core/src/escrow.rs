@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference escrow contract.
+//!
+//! A payer locks Dusk in the escrow contract for a payee. The funds are
+//! released to the payee by the payer or the arbiter, and refunded to the payer
+//! by the payee or the arbiter. Once the timeout height is reached, the payer
+//! can also refund the funds themselves.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+
+/// Topic of the event emitted when an escrow is created.
+pub const CREATE_TOPIC: &str = "create";
+
+/// Topic of the event emitted when the funds of an escrow are released to the
+/// payee.
+pub const RELEASE_TOPIC: &str = "release";
+
+/// Topic of the event emitted when the funds of an escrow are refunded to the
+/// payer.
+pub const REFUND_TOPIC: &str = "refund";
+
+/// The identifier of an escrow.
+pub type EscrowId = u64;
+
+/// Create an escrow for a payee, locking the deposit of the calling
+/// transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CreateEscrow {
+    /// The account the funds are released to.
+    pub payee: AccountPublicKey,
+    /// The account allowed to settle the escrow in either direction, if any.
+    pub arbiter: Option<AccountPublicKey>,
+    /// The value locked, in Lux, which should match the deposit of the
+    /// transaction.
+    pub value: u64,
+    /// The block height from which the payer can refund the funds.
+    pub timeout: u64,
+}
+
+/// Data stored for every escrow that is not settled.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct EscrowData {
+    /// The account that locked the funds.
+    pub payer: AccountPublicKey,
+    /// The account the funds are released to.
+    pub payee: AccountPublicKey,
+    /// The account allowed to settle the escrow in either direction, if any.
+    pub arbiter: Option<AccountPublicKey>,
+    /// The value locked, in Lux.
+    pub value: u64,
+    /// The block height from which the payer can refund the funds.
+    pub timeout: u64,
+}
+
+/// Event data emitted when an escrow is created.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CreateEvent {
+    /// The escrow created.
+    pub escrow_id: EscrowId,
+    /// The account that locked the funds.
+    pub payer: AccountPublicKey,
+    /// The account the funds are released to.
+    pub payee: AccountPublicKey,
+    /// The value locked, in Lux.
+    pub value: u64,
+}
+
+/// Event data emitted when an escrow is settled, either by releasing its funds
+/// to the payee or refunding them to the payer.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SettleEvent {
+    /// The escrow settled.
+    pub escrow_id: EscrowId,
+    /// The account that settled the escrow.
+    pub settler: AccountPublicKey,
+    /// The account receiving the funds.
+    pub receiver: AccountPublicKey,
+    /// The value paid, in Lux.
+    pub value: u64,
+}
@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the governance contract.
+//!
+//! Stakers submit proposals to change the parameters held by the governance
+//! contract, and vote on them during a voting period measured in blocks. Votes
+//! are weighted by the eligible stake of the voter, as read from the
+//! [stake contract](crate::stake::STAKE_CONTRACT) when the vote is cast. A
+//! proposal is approved if, at the end of the voting period, the stake in
+//! favor outweighs the stake against, and the stake that voted meets the
+//! quorum. Approved proposals can then be executed by anyone, applying their
+//! parameter changes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+
+/// Topic of the event emitted when a proposal is submitted.
+pub const PROPOSE_TOPIC: &str = "propose";
+
+/// Topic of the event emitted when a vote is cast.
+pub const VOTE_TOPIC: &str = "vote";
+
+/// Topic of the event emitted when an approved proposal is executed.
+pub const EXECUTE_TOPIC: &str = "execute";
+
+/// The identifier of a proposal.
+pub type ProposalId = u64;
+
+/// The argument of the `init` function of the governance contract, called
+/// when it is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct GovernanceInit {
+    /// The number of blocks a proposal can be voted on.
+    pub voting_period: u64,
+    /// The minimum stake, in Lux, that must vote on a proposal for it to be
+    /// approved.
+    pub quorum: u64,
+    /// The initial parameters.
+    pub parameters: Vec<ParameterChange>,
+}
+
+/// A change to a parameter held by the governance contract.
+///
+/// The value is opaque to the governance contract, and is interpreted by the
+/// contracts and nodes reading the parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ParameterChange {
+    /// The name of the parameter.
+    pub key: String,
+    /// The new value of the parameter.
+    pub value: Vec<u8>,
+}
+
+/// Submit a proposal to change some parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Propose {
+    /// A description of the proposal, or a link to it.
+    pub description: String,
+    /// The changes applied if the proposal is approved.
+    pub changes: Vec<ParameterChange>,
+}
+
+/// Vote on a proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Vote {
+    /// The proposal voted on.
+    pub proposal_id: ProposalId,
+    /// Whether the vote is in favor of the proposal.
+    pub approve: bool,
+}
+
+/// Data stored for every proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ProposalData {
+    /// The account that submitted the proposal.
+    pub proposer: AccountPublicKey,
+    /// A description of the proposal, or a link to it.
+    pub description: String,
+    /// The changes applied if the proposal is approved.
+    pub changes: Vec<ParameterChange>,
+    /// The block height after which the proposal can't be voted on.
+    pub end: u64,
+    /// The stake that voted in favor of the proposal, in Lux.
+    pub yes: u64,
+    /// The stake that voted against the proposal, in Lux.
+    pub no: u64,
+    /// Whether the proposal was executed.
+    pub executed: bool,
+}
+
+impl ProposalData {
+    /// Whether the proposal is approved with the given quorum, once its
+    /// voting period is over.
+    #[must_use]
+    pub fn is_approved(&self, quorum: u64) -> bool {
+        self.yes > self.no && self.yes.saturating_add(self.no) >= quorum
+    }
+}
+
+/// Event data emitted when a proposal is submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ProposeEvent {
+    /// The proposal submitted.
+    pub proposal_id: ProposalId,
+    /// The account that submitted the proposal.
+    pub proposer: AccountPublicKey,
+    /// The block height after which the proposal can't be voted on.
+    pub end: u64,
+}
+
+/// Event data emitted when a vote is cast.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct VoteEvent {
+    /// The proposal voted on.
+    pub proposal_id: ProposalId,
+    /// The account voting.
+    pub voter: AccountPublicKey,
+    /// Whether the vote is in favor of the proposal.
+    pub approve: bool,
+    /// The stake of the voter, in Lux.
+    pub weight: u64,
+}
+
+/// Event data emitted when an approved proposal is executed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ExecuteEvent {
+    /// The proposal executed.
+    pub proposal_id: ProposalId,
+    /// The changes applied.
+    pub changes: Vec<ParameterChange>,
+}
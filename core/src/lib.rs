@@ -19,14 +19,26 @@ extern crate alloc;
 
 pub mod abi;
 
+pub mod escrow;
+pub mod governance;
+pub mod multisig;
+pub mod name_service;
+pub mod nft;
+pub mod oracle;
 pub mod stake;
+pub mod staking_pool;
+pub mod token;
 pub mod transfer;
+pub mod vesting;
+
+#[cfg(feature = "proto")]
+pub mod proto;
 
 mod error;
 pub use error::Error;
 
 mod dusk;
-pub use dusk::{dusk, from_dusk, Dusk, LUX};
+pub use dusk::{dusk, from_dusk, Amount, Dusk, LUX};
 
 // elliptic curve types
 pub use dusk_bls12_381::BlsScalar;
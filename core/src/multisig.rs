@@ -0,0 +1,146 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference multisig wallet contract.
+//!
+//! The multisig contract holds a balance controlled by a set of owners. Any
+//! account can propose a contract call to be made by the multisig, which is
+//! executed as soon as a threshold of owners approved it. Approvals are BLS
+//! signatures collected on-chain, so that they can be relayed by any account.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use dusk_bytes::Serializable;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::abi::ContractId;
+use crate::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+    Signature as AccountSignature,
+};
+use crate::transfer::data::ContractCall;
+
+/// Topic of the event emitted when a call is proposed.
+pub const PROPOSE_TOPIC: &str = "propose";
+
+/// Topic of the event emitted when an owner approves a proposal.
+pub const APPROVE_TOPIC: &str = "approve";
+
+/// Topic of the event emitted when a proposal is executed.
+pub const EXECUTE_TOPIC: &str = "execute";
+
+const APPROVAL_DOMAIN: &[u8] = b"multisig-approval";
+
+/// The identifier of a proposal.
+pub type ProposalId = u64;
+
+/// The argument of the `init` function of the multisig contract, called when
+/// it is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct MultisigInit {
+    /// The accounts owning the multisig.
+    pub owners: Vec<AccountPublicKey>,
+    /// The number of owners that must approve a proposal for it to be
+    /// executed.
+    pub threshold: u32,
+}
+
+/// Data stored for every pending proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ProposalData {
+    /// The call made by the multisig once the proposal is approved.
+    pub call: ContractCall,
+    /// The owners who approved the proposal.
+    pub approvals: Vec<AccountPublicKey>,
+}
+
+/// The approval of a proposal by one of the owners of the multisig.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Approval {
+    /// The proposal approved.
+    pub proposal_id: ProposalId,
+    /// The owner approving the proposal.
+    pub owner: AccountPublicKey,
+    /// The signature of the owner over the [`approval_message`].
+    pub signature: AccountSignature,
+}
+
+impl Approval {
+    /// Create a new approval of the given proposal of a multisig, signed by
+    /// the given owner.
+    #[must_use]
+    pub fn new(
+        owner_sk: &AccountSecretKey,
+        chain_id: u8,
+        multisig: ContractId,
+        proposal_id: ProposalId,
+        call: &ContractCall,
+    ) -> Self {
+        let msg = approval_message(chain_id, multisig, proposal_id, call);
+
+        Self {
+            proposal_id,
+            owner: AccountPublicKey::from(owner_sk),
+            signature: owner_sk.sign(&msg),
+        }
+    }
+}
+
+/// Return the message signed by an owner to approve a proposal.
+///
+/// The message commits to the chain and the multisig contract, so that an
+/// approval can't be replayed on another chain or multisig, and to the
+/// proposed call, so that an approval can't be used for another call.
+#[must_use]
+pub fn approval_message(
+    chain_id: u8,
+    multisig: ContractId,
+    proposal_id: ProposalId,
+    call: &ContractCall,
+) -> Vec<u8> {
+    let mut bytes = Vec::from(APPROVAL_DOMAIN);
+
+    bytes.push(chain_id);
+    bytes.extend(multisig.as_bytes());
+    bytes.extend(proposal_id.to_bytes());
+    bytes.extend(call.to_var_bytes());
+
+    bytes
+}
+
+/// Event data emitted when a call is proposed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ProposeEvent {
+    /// The identifier of the proposal.
+    pub proposal_id: ProposalId,
+    /// The proposed call.
+    pub call: ContractCall,
+}
+
+/// Event data emitted when an owner approves a proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ApproveEvent {
+    /// The proposal approved.
+    pub proposal_id: ProposalId,
+    /// The owner approving the proposal.
+    pub owner: AccountPublicKey,
+}
+
+/// Event data emitted when a proposal is executed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ExecuteEvent {
+    /// The proposal executed.
+    pub proposal_id: ProposalId,
+    /// The data returned by the call.
+    pub data: Vec<u8>,
+}
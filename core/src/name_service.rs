@@ -0,0 +1,219 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the Dusk Name Service contract.
+//!
+//! The name service maps names, such as `alice.dusk`, to a [`Target`]: a
+//! Moonlight account, a Phoenix address or a contract. Names are registered
+//! for [`REGISTRATION_PERIOD`] blocks at a time and expire unless renewed.
+//!
+//! Names shorter than [`SHORT_NAME_LEN`] are scarce, and can't be registered
+//! directly. Instead, they are allocated by an auction lasting
+//! [`AUCTION_DURATION`] blocks from the first bid, after which the highest
+//! bidder can claim the name.
+
+use alloc::string::String;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::abi::ContractId;
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+use crate::transfer::phoenix::PublicKey as PhoenixPublicKey;
+use crate::{dusk, Dusk};
+
+/// ID of the name service contract.
+pub const NAME_SERVICE_CONTRACT: ContractId = crate::reserved(0x3);
+
+/// Suffix of the names resolved by the name service.
+pub const NAME_SUFFIX: &str = ".dusk";
+
+/// Maximum length of a name, without its suffix.
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Length from which names can be registered directly, without an auction.
+pub const SHORT_NAME_LEN: usize = 5;
+
+/// Number of blocks a name is registered for, or renewed by.
+///
+/// This is roughly one year, with a block every ten seconds.
+pub const REGISTRATION_PERIOD: u64 = 3_153_600;
+
+/// Fee for registering or renewing a name for a [`REGISTRATION_PERIOD`].
+pub const REGISTRATION_FEE: Dusk = dusk(10.0);
+
+/// Number of blocks an auction lasts from its first bid.
+///
+/// This is roughly three days, with a block every ten seconds.
+pub const AUCTION_DURATION: u64 = 25_920;
+
+/// Topic of the event emitted when a name is registered or claimed.
+pub const REGISTER_TOPIC: &str = "register";
+
+/// Topic of the event emitted when a name is renewed.
+pub const RENEW_TOPIC: &str = "renew";
+
+/// Topic of the event emitted when a name is transferred to a new owner.
+pub const TRANSFER_TOPIC: &str = "transfer";
+
+/// Topic of the event emitted when the target of a name is changed.
+pub const TARGET_TOPIC: &str = "target";
+
+/// Topic of the event emitted when a bid is placed on a name.
+pub const BID_TOPIC: &str = "bid";
+
+/// Checks that the given name, without its suffix, is made of lowercase ASCII
+/// letters, digits and inner hyphens, and is at most [`MAX_NAME_LEN`] long.
+#[must_use]
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Returns the name to resolve from a string ending with the
+/// [`NAME_SUFFIX`], if it is a valid name.
+#[must_use]
+pub fn parse_name(s: &str) -> Option<&str> {
+    s.strip_suffix(NAME_SUFFIX)
+        .filter(|name| is_valid_name(name))
+}
+
+/// What a name resolves to.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub enum Target {
+    /// A Moonlight account.
+    Moonlight(AccountPublicKey),
+    /// A Phoenix address.
+    Phoenix(PhoenixPublicKey),
+    /// A contract.
+    Contract(ContractId),
+}
+
+/// The argument of the `init` function of the name service contract, called
+/// when it is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct NameServiceInit {
+    /// The account receiving the registration fees and winning bids.
+    pub treasury: AccountPublicKey,
+}
+
+/// Register a name that isn't short, paying the [`REGISTRATION_FEE`] with
+/// the deposit of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Register {
+    /// The name registered, without its suffix.
+    pub name: String,
+    /// What the name resolves to.
+    pub target: Target,
+}
+
+/// Transfer a name owned by the sender to a new owner.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferName {
+    /// The name transferred, without its suffix.
+    pub name: String,
+    /// The new owner of the name.
+    pub owner: AccountPublicKey,
+}
+
+/// Change what a name owned by the sender resolves to.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SetTarget {
+    /// The name changed, without its suffix.
+    pub name: String,
+    /// What the name resolves to.
+    pub target: Target,
+}
+
+/// Bid on a short name, locking the deposit of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Bid {
+    /// The name bid on, without its suffix.
+    pub name: String,
+    /// The value of the bid, in Lux, which should match the deposit of the
+    /// transaction.
+    pub value: u64,
+}
+
+/// Data stored for every registered name.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct NameData {
+    /// The account owning the name.
+    pub owner: AccountPublicKey,
+    /// What the name resolves to.
+    pub target: Target,
+    /// The block height at which the name expires.
+    pub expiry: u64,
+}
+
+impl NameData {
+    /// Whether the name is expired at the given block height.
+    #[must_use]
+    pub fn is_expired(&self, height: u64) -> bool {
+        height >= self.expiry
+    }
+}
+
+/// Data stored for every running auction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct AuctionData {
+    /// The account with the highest bid.
+    pub bidder: AccountPublicKey,
+    /// The value of the highest bid, in Lux.
+    pub value: u64,
+    /// The block height at which the auction ends.
+    pub end: u64,
+}
+
+/// Event data emitted when a name is registered, claimed, renewed or
+/// transferred.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct NameEvent {
+    /// The name, without its suffix.
+    pub name: String,
+    /// The account owning the name.
+    pub owner: AccountPublicKey,
+    /// The block height at which the name expires.
+    pub expiry: u64,
+}
+
+/// Event data emitted when the target of a name is changed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TargetEvent {
+    /// The name, without its suffix.
+    pub name: String,
+    /// What the name resolves to.
+    pub target: Target,
+}
+
+/// Event data emitted when a bid is placed on a name.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct BidEvent {
+    /// The name bid on, without its suffix.
+    pub name: String,
+    /// The account placing the bid.
+    pub bidder: AccountPublicKey,
+    /// The value of the bid, in Lux.
+    pub value: u64,
+    /// The block height at which the auction ends.
+    pub end: u64,
+}
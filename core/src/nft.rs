@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference non-fungible token (NFT) contract.
+//!
+//! NFTs are owned by [`Account`]s, authenticated in the same way as in the
+//! [`token`](crate::token) contract. Each NFT carries a metadata URI and a
+//! royalty, paid to its creator out of every sale made through the contract.
+
+use alloc::string::String;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+pub use crate::token::Account;
+
+/// Topic of the event emitted when an NFT is minted.
+pub const MINT_TOPIC: &str = "mint";
+
+/// Topic of the event emitted when an NFT is burned.
+pub const BURN_TOPIC: &str = "burn";
+
+/// Topic of the event emitted when an NFT is transferred.
+pub const TRANSFER_TOPIC: &str = "transfer";
+
+/// Topic of the event emitted when an NFT is listed for sale, or its listing
+/// is removed.
+pub const LIST_TOPIC: &str = "list";
+
+/// Topic of the event emitted when an NFT is sold.
+pub const SALE_TOPIC: &str = "sale";
+
+/// The royalty, in basis points, that pays the creator the whole price of a
+/// sale.
+pub const MAX_ROYALTY: u16 = 10_000;
+
+/// The identifier of an NFT.
+pub type TokenId = u64;
+
+/// The argument of the `init` function of the NFT contract, called when it is
+/// deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct NftInit {
+    /// Name of the collection.
+    pub name: String,
+    /// Symbol of the collection.
+    pub symbol: String,
+    /// The account allowed to mint NFTs.
+    pub minter: Account,
+}
+
+/// Mint a new NFT.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Mint {
+    /// The account receiving the NFT.
+    pub owner: Account,
+    /// The URI of the metadata of the NFT.
+    pub uri: String,
+    /// The account receiving the royalties of the NFT.
+    pub creator: AccountPublicKey,
+    /// The royalty paid to the creator on every sale, in basis points.
+    pub royalty: u16,
+}
+
+/// Transfer an NFT owned by the sender to a receiver.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Transfer {
+    /// The NFT transferred.
+    pub token_id: TokenId,
+    /// The account receiving the NFT.
+    pub receiver: Account,
+}
+
+/// List an NFT owned by the sender for sale, at the given price in Lux.
+///
+/// A price of zero removes the listing.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct List {
+    /// The NFT listed.
+    pub token_id: TokenId,
+    /// The price of the NFT, in Lux.
+    pub price: u64,
+}
+
+/// Data stored for every NFT.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TokenData {
+    /// The account owning the NFT.
+    pub owner: Account,
+    /// The URI of the metadata of the NFT.
+    pub uri: String,
+    /// The account receiving the royalties of the NFT.
+    pub creator: AccountPublicKey,
+    /// The royalty paid to the creator on every sale, in basis points.
+    pub royalty: u16,
+    /// The price the NFT is listed for, in Lux, if any.
+    pub price: Option<u64>,
+}
+
+impl TokenData {
+    /// The royalty paid to the creator out of a sale at the given price.
+    #[must_use]
+    pub fn royalty_amount(&self, price: u64) -> u64 {
+        let royalty = u128::from(price) * u128::from(self.royalty)
+            / u128::from(MAX_ROYALTY);
+        u64::try_from(royalty).unwrap_or(price)
+    }
+}
+
+/// Event data emitted when an NFT is minted, burned or transferred.
+///
+/// The `sender` of a minted NFT and the `receiver` of a burned one are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferEvent {
+    /// The NFT transferred.
+    pub token_id: TokenId,
+    /// The previous owner of the NFT.
+    pub sender: Option<Account>,
+    /// The new owner of the NFT.
+    pub receiver: Option<Account>,
+}
+
+/// Event data emitted when an NFT is listed for sale, or its listing is
+/// removed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ListEvent {
+    /// The NFT listed.
+    pub token_id: TokenId,
+    /// The price of the NFT, in Lux, or `None` if the listing was removed.
+    pub price: Option<u64>,
+}
+
+/// Event data emitted when an NFT is sold.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SaleEvent {
+    /// The NFT sold.
+    pub token_id: TokenId,
+    /// The account selling the NFT.
+    pub seller: AccountPublicKey,
+    /// The account buying the NFT.
+    pub buyer: Account,
+    /// The price paid for the NFT, in Lux.
+    pub price: u64,
+    /// The part of the price paid to the creator of the NFT, in Lux.
+    pub royalty: u64,
+}
@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the oracle price-feed contract.
+//!
+//! A configured set of feeders sign price updates for asset pairs, which can
+//! be pushed to the oracle by any account. The price of a pair is the median
+//! of the latest prices of the feeders, aggregated once enough feeders pushed
+//! a recent price. Each aggregated price carries the block height and the
+//! timestamp it was aggregated from, so that contracts reading it can decide
+//! whether it is too stale to be used.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use dusk_bytes::Serializable;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::abi::ContractId;
+use crate::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+    Signature as AccountSignature,
+};
+
+/// Topic of the event emitted when the price of a pair is aggregated.
+pub const PRICE_TOPIC: &str = "price";
+
+const UPDATE_DOMAIN: &[u8] = b"oracle-price-update";
+
+/// The argument of the `init` function of the oracle contract, called when it
+/// is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct OracleInit {
+    /// The accounts allowed to sign price updates.
+    pub feeders: Vec<AccountPublicKey>,
+    /// The number of feeders that must have pushed a recent price for the
+    /// price of a pair to be aggregated.
+    pub threshold: u32,
+    /// The number of blocks after which the price pushed by a feeder is no
+    /// longer taken into account.
+    pub max_age: u64,
+}
+
+/// A price update signed by one of the feeders of the oracle.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PriceUpdate {
+    /// The asset pair priced, e.g. `DUSK/USD`.
+    pub pair: String,
+    /// The price of the pair, in the fixed-point units agreed on for it.
+    pub price: u64,
+    /// The time the price was observed by the feeder, in seconds since the
+    /// Unix epoch. It must increase with every update of a feeder.
+    pub timestamp: u64,
+    /// The feeder signing the update.
+    pub feeder: AccountPublicKey,
+    /// The signature of the feeder over the [`update_message`].
+    pub signature: AccountSignature,
+}
+
+impl PriceUpdate {
+    /// Create a new price update for the given oracle, signed by the given
+    /// feeder.
+    #[must_use]
+    pub fn new(
+        feeder_sk: &AccountSecretKey,
+        chain_id: u8,
+        oracle: ContractId,
+        pair: String,
+        price: u64,
+        timestamp: u64,
+    ) -> Self {
+        let msg = update_message(chain_id, oracle, &pair, price, timestamp);
+
+        Self {
+            pair,
+            price,
+            timestamp,
+            feeder: AccountPublicKey::from(feeder_sk),
+            signature: feeder_sk.sign(&msg),
+        }
+    }
+}
+
+/// Return the message signed by a feeder to update the price of a pair.
+///
+/// The message commits to the chain and the oracle contract, so that an update
+/// can't be replayed on another chain or oracle.
+#[must_use]
+pub fn update_message(
+    chain_id: u8,
+    oracle: ContractId,
+    pair: &str,
+    price: u64,
+    timestamp: u64,
+) -> Vec<u8> {
+    let mut bytes = Vec::from(UPDATE_DOMAIN);
+
+    bytes.push(chain_id);
+    bytes.extend(oracle.as_bytes());
+    bytes.extend((pair.len() as u64).to_bytes());
+    bytes.extend(pair.as_bytes());
+    bytes.extend(price.to_bytes());
+    bytes.extend(timestamp.to_bytes());
+
+    bytes
+}
+
+/// The aggregated price of a pair.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PriceData {
+    /// The median of the latest prices pushed by the feeders.
+    pub price: u64,
+    /// The timestamp of the oldest price the median was aggregated from.
+    pub timestamp: u64,
+    /// The block height at which the price was aggregated.
+    pub height: u64,
+    /// The number of feeders the median was aggregated from.
+    pub sources: u32,
+}
+
+impl PriceData {
+    /// Whether the price was aggregated more than `max_age` blocks before the
+    /// given block height.
+    #[must_use]
+    pub fn is_stale(&self, block_height: u64, max_age: u64) -> bool {
+        block_height.saturating_sub(self.height) > max_age
+    }
+}
+
+/// Event data emitted when the price of a pair is aggregated.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PriceEvent {
+    /// The asset pair priced.
+    pub pair: String,
+    /// The aggregated price.
+    pub price: PriceData,
+}
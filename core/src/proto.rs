@@ -0,0 +1,479 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Protobuf encoding of transactions and transfer events, for services
+//! written in other languages.
+//!
+//! The messages mirror the schema in `proto/dusk.proto` of this crate, which
+//! other languages generate their types from. They are converted from the
+//! types of this crate with [`From`], and encoded with the [`Message`] trait:
+//!
+//! ```ignore
+//! use dusk_core::proto::{self, Message};
+//!
+//! let bytes = proto::Transaction::from(&tx).encode_to_vec();
+//! ```
+//!
+//! Since the messages flatten the transaction, it is decoded back from the
+//! canonical encoding carried in [`Transaction::raw`].
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_bytes::Serializable;
+pub use prost::Message;
+
+use crate::abi::Event;
+use crate::transfer::data::MemoKind;
+use crate::transfer::{self, withdraw};
+
+/// The kind of a transaction.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum TransactionKind {
+    /// A Phoenix transaction.
+    Phoenix = 0,
+    /// A Moonlight transaction.
+    Moonlight = 1,
+}
+
+/// A contract call.
+#[derive(Clone, PartialEq, Message)]
+pub struct ContractCall {
+    /// The called contract.
+    #[prost(bytes = "vec", tag = "1")]
+    pub contract: Vec<u8>,
+    /// The called function.
+    #[prost(string, tag = "2")]
+    pub fn_name: String,
+    /// The rkyv serialized arguments of the call.
+    #[prost(bytes = "vec", tag = "3")]
+    pub fn_args: Vec<u8>,
+}
+
+/// A contract deployment.
+#[derive(Clone, PartialEq, Message)]
+pub struct ContractDeploy {
+    /// The blake3 hash of the bytecode.
+    #[prost(bytes = "vec", tag = "1")]
+    pub bytecode_hash: Vec<u8>,
+    /// The bytecode of the contract.
+    #[prost(bytes = "vec", tag = "2")]
+    pub bytecode: Vec<u8>,
+    /// The owner of the contract.
+    #[prost(bytes = "vec", tag = "3")]
+    pub owner: Vec<u8>,
+    /// The arguments of the `init` function, if any.
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub init_args: Option<Vec<u8>>,
+    /// The nonce of the deployment.
+    #[prost(uint64, tag = "5")]
+    pub nonce: u64,
+}
+
+/// A transaction.
+#[derive(Clone, PartialEq, Message)]
+pub struct Transaction {
+    /// The hash of the transaction, identifying it.
+    #[prost(bytes = "vec", tag = "1")]
+    pub id: Vec<u8>,
+    /// The kind of the transaction.
+    #[prost(enumeration = "TransactionKind", tag = "2")]
+    pub kind: i32,
+    /// The chain the transaction is meant for.
+    #[prost(uint32, tag = "3")]
+    pub chain_id: u32,
+    /// The gas limit of the transaction.
+    #[prost(uint64, tag = "4")]
+    pub gas_limit: u64,
+    /// The gas price of the transaction.
+    #[prost(uint64, tag = "5")]
+    pub gas_price: u64,
+    /// The value deposited to the called contract.
+    #[prost(uint64, tag = "6")]
+    pub deposit: u64,
+    /// The sender of a Moonlight transaction.
+    #[prost(bytes = "vec", optional, tag = "7")]
+    pub sender: Option<Vec<u8>>,
+    /// The receiver of a Moonlight transaction, if any.
+    #[prost(bytes = "vec", optional, tag = "8")]
+    pub receiver: Option<Vec<u8>>,
+    /// The value transferred by a Moonlight transaction.
+    #[prost(uint64, optional, tag = "9")]
+    pub value: Option<u64>,
+    /// The nonce of a Moonlight transaction.
+    #[prost(uint64, optional, tag = "10")]
+    pub nonce: Option<u64>,
+    /// The nullifiers of the notes spent by a Phoenix transaction.
+    #[prost(bytes = "vec", repeated, tag = "11")]
+    pub nullifiers: Vec<Vec<u8>>,
+    /// The notes created by a Phoenix transaction.
+    #[prost(bytes = "vec", repeated, tag = "12")]
+    pub notes: Vec<Vec<u8>>,
+    /// The content of the memo of the transaction, if any.
+    #[prost(bytes = "vec", optional, tag = "13")]
+    pub memo: Option<Vec<u8>>,
+    /// The contract call of the transaction, if any.
+    #[prost(message, optional, tag = "14")]
+    pub call: Option<ContractCall>,
+    /// The contract deployment of the transaction, if any.
+    #[prost(message, optional, tag = "15")]
+    pub deploy: Option<ContractDeploy>,
+    /// The canonical encoding of the transaction.
+    #[prost(bytes = "vec", tag = "16")]
+    pub raw: Vec<u8>,
+    /// The byte of the kind of the memo, if the memo is tagged.
+    #[prost(uint32, optional, tag = "17")]
+    pub memo_kind: Option<u32>,
+}
+
+/// A transaction included in a block.
+#[derive(Clone, PartialEq, Message)]
+pub struct SpentTransaction {
+    /// The transaction.
+    #[prost(message, optional, tag = "1")]
+    pub transaction: Option<Transaction>,
+    /// The height of the block including the transaction.
+    #[prost(uint64, tag = "2")]
+    pub block_height: u64,
+    /// The gas spent by the transaction.
+    #[prost(uint64, tag = "3")]
+    pub gas_spent: u64,
+    /// The error of the transaction, if it failed.
+    #[prost(string, optional, tag = "4")]
+    pub error: Option<String>,
+}
+
+/// An event emitted by a contract, with its data still rkyv serialized.
+#[derive(Clone, PartialEq, Message)]
+pub struct ContractEvent {
+    /// The contract emitting the event.
+    #[prost(bytes = "vec", tag = "1")]
+    pub source: Vec<u8>,
+    /// The topic of the event.
+    #[prost(string, tag = "2")]
+    pub topic: String,
+    /// The data of the event.
+    #[prost(bytes = "vec", tag = "3")]
+    pub data: Vec<u8>,
+}
+
+/// The receiver of a withdrawal.
+#[derive(Clone, PartialEq, Message)]
+pub struct WithdrawReceiver {
+    /// The stealth address, when withdrawing into a Phoenix note.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub phoenix: Option<Vec<u8>>,
+    /// The account, when withdrawing to Moonlight.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub moonlight: Option<Vec<u8>>,
+}
+
+/// The event of a completed Moonlight transaction.
+#[derive(Clone, PartialEq, Message)]
+pub struct MoonlightTransactionEvent {
+    /// The sender of the transaction.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sender: Vec<u8>,
+    /// The receiver of the transaction, if any.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub receiver: Option<Vec<u8>>,
+    /// The value transferred.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+    /// The memo of the transaction.
+    #[prost(bytes = "vec", tag = "4")]
+    pub memo: Vec<u8>,
+    /// The gas spent by the transaction.
+    #[prost(uint64, tag = "5")]
+    pub gas_spent: u64,
+    /// The account refunded, if different from the sender.
+    #[prost(bytes = "vec", optional, tag = "6")]
+    pub refund_account: Option<Vec<u8>>,
+    /// The value refunded, if the account refunded is not the sender.
+    #[prost(uint64, optional, tag = "7")]
+    pub refund_value: Option<u64>,
+}
+
+/// The event of a completed Phoenix transaction.
+#[derive(Clone, PartialEq, Message)]
+pub struct PhoenixTransactionEvent {
+    /// The nullifiers of the notes spent.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub nullifiers: Vec<Vec<u8>>,
+    /// The notes created.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub notes: Vec<Vec<u8>>,
+    /// The memo of the transaction.
+    #[prost(bytes = "vec", tag = "3")]
+    pub memo: Vec<u8>,
+    /// The gas spent by the transaction.
+    #[prost(uint64, tag = "4")]
+    pub gas_spent: u64,
+    /// The note refunding the unspent gas, if any.
+    #[prost(bytes = "vec", optional, tag = "5")]
+    pub refund_note: Option<Vec<u8>>,
+}
+
+/// The event of a deposit to a contract.
+#[derive(Clone, PartialEq, Message)]
+pub struct DepositEvent {
+    /// The account depositing, if known.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub sender: Option<Vec<u8>>,
+    /// The contract receiving the deposit.
+    #[prost(bytes = "vec", tag = "2")]
+    pub receiver: Vec<u8>,
+    /// The value deposited.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+}
+
+/// The event of a withdrawal from a contract.
+#[derive(Clone, PartialEq, Message)]
+pub struct WithdrawEvent {
+    /// The contract withdrawn from.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sender: Vec<u8>,
+    /// The receiver of the value.
+    #[prost(message, optional, tag = "2")]
+    pub receiver: Option<WithdrawReceiver>,
+    /// The value withdrawn.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+}
+
+/// The event of a conversion between Phoenix and Moonlight.
+#[derive(Clone, PartialEq, Message)]
+pub struct ConvertEvent {
+    /// The account converting, if known.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub sender: Option<Vec<u8>>,
+    /// The receiver of the value.
+    #[prost(message, optional, tag = "2")]
+    pub receiver: Option<WithdrawReceiver>,
+    /// The value converted.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+}
+
+/// The event of a transfer from a contract to a contract.
+#[derive(Clone, PartialEq, Message)]
+pub struct ContractToContractEvent {
+    /// The contract sending the value.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sender: Vec<u8>,
+    /// The contract receiving the value.
+    #[prost(bytes = "vec", tag = "2")]
+    pub receiver: Vec<u8>,
+    /// The value transferred.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+}
+
+/// The event of a transfer from a contract to a Moonlight account.
+#[derive(Clone, PartialEq, Message)]
+pub struct ContractToAccountEvent {
+    /// The contract sending the value.
+    #[prost(bytes = "vec", tag = "1")]
+    pub sender: Vec<u8>,
+    /// The account receiving the value.
+    #[prost(bytes = "vec", tag = "2")]
+    pub receiver: Vec<u8>,
+    /// The value transferred.
+    #[prost(uint64, tag = "3")]
+    pub value: u64,
+}
+
+/// The event of a tagged memo.
+#[derive(Clone, PartialEq, Message)]
+pub struct MemoEvent {
+    /// The byte of the kind of the memo.
+    #[prost(uint32, tag = "1")]
+    pub kind: u32,
+    /// The content of the memo.
+    #[prost(bytes = "vec", tag = "2")]
+    pub content: Vec<u8>,
+}
+
+impl From<&transfer::Transaction> for Transaction {
+    fn from(tx: &transfer::Transaction) -> Self {
+        let (kind, chain_id, nonce) = match tx {
+            transfer::Transaction::Phoenix(tx) => {
+                (TransactionKind::Phoenix, tx.chain_id(), None)
+            }
+            transfer::Transaction::Moonlight(tx) => {
+                (TransactionKind::Moonlight, tx.chain_id(), Some(tx.nonce()))
+            }
+        };
+
+        Self {
+            id: tx.hash().to_bytes().to_vec(),
+            kind: kind as i32,
+            chain_id: chain_id.into(),
+            gas_limit: tx.gas_limit(),
+            gas_price: tx.gas_price(),
+            deposit: tx.deposit(),
+            sender: tx.moonlight_sender().map(|pk| pk.to_bytes().to_vec()),
+            receiver: tx.moonlight_receiver().map(|pk| pk.to_bytes().to_vec()),
+            value: tx.value(),
+            nonce,
+            nullifiers: tx
+                .nullifiers()
+                .iter()
+                .map(|n| n.to_bytes().to_vec())
+                .collect(),
+            notes: tx.outputs().iter().map(|n| n.to_bytes().to_vec()).collect(),
+            memo: tx.memo().map(|memo| memo.content().to_vec()),
+            call: tx.call().map(|call| ContractCall {
+                contract: call.contract.as_bytes().to_vec(),
+                fn_name: call.fn_name.clone(),
+                fn_args: call.fn_args.clone(),
+            }),
+            deploy: tx.deploy().map(|deploy| ContractDeploy {
+                bytecode_hash: deploy.bytecode.hash.to_vec(),
+                bytecode: deploy.bytecode.bytes.clone(),
+                owner: deploy.owner.clone(),
+                init_args: deploy.init_args.clone(),
+                nonce: deploy.nonce,
+            }),
+            raw: tx.to_var_bytes(),
+            memo_kind: tx
+                .memo()
+                .filter(|memo| memo.kind() != MemoKind::Plain)
+                .map(|memo| memo.kind().to_byte().into()),
+        }
+    }
+}
+
+impl TryFrom<&Transaction> for transfer::Transaction {
+    type Error = dusk_bytes::Error;
+
+    fn try_from(tx: &Transaction) -> Result<Self, Self::Error> {
+        transfer::Transaction::from_slice(&tx.raw)
+    }
+}
+
+impl From<&Event> for ContractEvent {
+    fn from(event: &Event) -> Self {
+        Self {
+            source: event.source.as_bytes().to_vec(),
+            topic: event.topic.clone(),
+            data: event.data.clone(),
+        }
+    }
+}
+
+impl From<&withdraw::WithdrawReceiver> for WithdrawReceiver {
+    fn from(receiver: &withdraw::WithdrawReceiver) -> Self {
+        match receiver {
+            withdraw::WithdrawReceiver::Phoenix(address) => Self {
+                phoenix: Some(address.to_bytes().to_vec()),
+                moonlight: None,
+            },
+            withdraw::WithdrawReceiver::Moonlight(account) => Self {
+                phoenix: None,
+                moonlight: Some(account.to_bytes().to_vec()),
+            },
+        }
+    }
+}
+
+impl From<&transfer::MoonlightTransactionEvent> for MoonlightTransactionEvent {
+    fn from(event: &transfer::MoonlightTransactionEvent) -> Self {
+        Self {
+            sender: event.sender.to_bytes().to_vec(),
+            receiver: event.receiver.map(|pk| pk.to_bytes().to_vec()),
+            value: event.value,
+            memo: event.memo.clone(),
+            gas_spent: event.gas_spent,
+            refund_account: event
+                .refund_info
+                .map(|(pk, _)| pk.to_bytes().to_vec()),
+            refund_value: event.refund_info.map(|(_, value)| value),
+        }
+    }
+}
+
+impl From<&transfer::PhoenixTransactionEvent> for PhoenixTransactionEvent {
+    fn from(event: &transfer::PhoenixTransactionEvent) -> Self {
+        Self {
+            nullifiers: event
+                .nullifiers
+                .iter()
+                .map(|n| n.to_bytes().to_vec())
+                .collect(),
+            notes: event.notes.iter().map(|n| n.to_bytes().to_vec()).collect(),
+            memo: event.memo.clone(),
+            gas_spent: event.gas_spent,
+            refund_note: event
+                .refund_note
+                .as_ref()
+                .map(|n| n.to_bytes().to_vec()),
+        }
+    }
+}
+
+impl From<&transfer::DepositEvent> for DepositEvent {
+    fn from(event: &transfer::DepositEvent) -> Self {
+        Self {
+            sender: event.sender.map(|pk| pk.to_bytes().to_vec()),
+            receiver: event.receiver.as_bytes().to_vec(),
+            value: event.value,
+        }
+    }
+}
+
+impl From<&transfer::WithdrawEvent> for WithdrawEvent {
+    fn from(event: &transfer::WithdrawEvent) -> Self {
+        Self {
+            sender: event.sender.as_bytes().to_vec(),
+            receiver: Some((&event.receiver).into()),
+            value: event.value,
+        }
+    }
+}
+
+impl From<&transfer::ConvertEvent> for ConvertEvent {
+    fn from(event: &transfer::ConvertEvent) -> Self {
+        Self {
+            sender: event.sender.map(|pk| pk.to_bytes().to_vec()),
+            receiver: Some((&event.receiver).into()),
+            value: event.value,
+        }
+    }
+}
+
+impl From<&transfer::ContractToContractEvent> for ContractToContractEvent {
+    fn from(event: &transfer::ContractToContractEvent) -> Self {
+        Self {
+            sender: event.sender.as_bytes().to_vec(),
+            receiver: event.receiver.as_bytes().to_vec(),
+            value: event.value,
+        }
+    }
+}
+
+impl From<&transfer::ContractToAccountEvent> for ContractToAccountEvent {
+    fn from(event: &transfer::ContractToAccountEvent) -> Self {
+        Self {
+            sender: event.sender.as_bytes().to_vec(),
+            receiver: event.receiver.to_bytes().to_vec(),
+            value: event.value,
+        }
+    }
+}
+
+impl From<&transfer::MemoEvent> for MemoEvent {
+    fn from(event: &transfer::MemoEvent) -> Self {
+        Self {
+            kind: event.kind.to_byte().into(),
+            content: event.content.clone(),
+        }
+    }
+}
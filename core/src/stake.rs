@@ -75,6 +75,12 @@ pub const fn next_epoch(block_height: u64) -> u64 {
     block_height + to_next_epoch
 }
 
+/// Calculate the epoch the given block height belongs to.
+#[must_use]
+pub const fn epoch_at(block_height: u64) -> u64 {
+    block_height / EPOCH
+}
+
 /// Stake a value on the stake contract.
 #[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -102,12 +108,7 @@ impl Stake {
 
         let keys = StakeKeys::new(account, owner);
 
-        let mut stake = Stake {
-            chain_id,
-            keys,
-            value,
-            signature: DoubleSignature::default(),
-        };
+        let mut stake = Self::new_unsigned(keys, value, chain_id);
 
         let msg = stake.signature_message();
 
@@ -119,6 +120,25 @@ impl Stake {
         stake
     }
 
+    /// Create a new stake without signing it.
+    ///
+    /// This allows the [`signature_message`] to be signed by keys that are not
+    /// available in memory, such as the ones of a hardware wallet. The
+    /// resulting signatures need to be set with [`set_signature`] for the
+    /// stake to be accepted by the stake contract.
+    ///
+    /// [`signature_message`]: Self::signature_message
+    /// [`set_signature`]: Self::set_signature
+    #[must_use]
+    pub fn new_unsigned(keys: StakeKeys, value: u64, chain_id: u8) -> Self {
+        Stake {
+            chain_id,
+            keys,
+            value,
+            signature: DoubleSignature::default(),
+        }
+    }
+
     /// Create a new stake from a contract.
     #[must_use]
     pub fn new_from_contract(
@@ -178,6 +198,12 @@ impl Stake {
         &self.signature
     }
 
+    /// Set the signature of the stake, created over the
+    /// [`signature_message`](Self::signature_message).
+    pub fn set_signature(&mut self, signature: DoubleSignature) {
+        self.signature = signature;
+    }
+
     /// Return the message that is used as the input to the signature.
     #[must_use]
     pub fn signature_message(&self) -> [u8; Self::MESSAGE_SIZE] {
@@ -302,11 +328,7 @@ impl Withdraw {
     ) -> Self {
         let account = BlsPublicKey::from(account_sk);
 
-        let mut stake_withdraw = Withdraw {
-            account,
-            withdraw,
-            signature: DoubleSignature::default(),
-        };
+        let mut stake_withdraw = Self::new_unsigned(account, withdraw);
 
         let msg = stake_withdraw.signature_message();
 
@@ -318,6 +340,26 @@ impl Withdraw {
         stake_withdraw
     }
 
+    /// Create a new withdraw call without signing it.
+    ///
+    /// The [`signature_message`] needs to be signed by the account and owner
+    /// keys, and the signatures set with [`set_signature`], for the withdrawal
+    /// to be accepted by the stake contract.
+    ///
+    /// [`signature_message`]: Self::signature_message
+    /// [`set_signature`]: Self::set_signature
+    #[must_use]
+    pub fn new_unsigned(
+        account: BlsPublicKey,
+        withdraw: TransferWithdraw,
+    ) -> Self {
+        Withdraw {
+            account,
+            withdraw,
+            signature: DoubleSignature::default(),
+        }
+    }
+
     /// Create a new withdraw call using the same account as the owner.
     #[must_use]
     pub fn with_single_key(
@@ -345,6 +387,12 @@ impl Withdraw {
         &self.signature
     }
 
+    /// Set the signature of the withdrawal, created over the
+    /// [`signature_message`](Self::signature_message).
+    pub fn set_signature(&mut self, signature: DoubleSignature) {
+        self.signature = signature;
+    }
+
     /// Signature message used for [`Withdraw`].
     #[must_use]
     pub fn signature_message(&self) -> Vec<u8> {
@@ -357,6 +405,509 @@ impl Withdraw {
     }
 }
 
+/// The commission rate, in basis points, with which a provisioner keeps the
+/// whole of its rewards, leaving nothing to its delegators.
+pub const MAX_COMMISSION: u16 = 10_000;
+
+/// The minimum value a delegation can hold.
+pub const MINIMUM_DELEGATION: Dusk = dusk(100.0);
+
+/// The maximum number of delegators a provisioner can have, bounding the work
+/// done when rewarding or slashing it.
+pub const MAX_DELEGATORS: usize = 64;
+
+const DELEGATE_DOMAIN: &[u8] = b"dusk stake delegate";
+const DELEGATION_WITHDRAW_DOMAIN: &[u8] = b"dusk stake delegation withdraw";
+const COMMISSION_DOMAIN: &[u8] = b"dusk stake commission";
+const REWARD_ADDRESS_DOMAIN: &[u8] = b"dusk stake reward address";
+
+/// Delegate a value to a provisioner on the stake contract.
+///
+/// The delegated value is accounted separately from the provisioner's stake,
+/// but adds to its weight in the consensus for as long as the provisioner has
+/// a stake. Like a top-up, delegating to an eligible provisioner locks 10% of
+/// the value. The delegation earns the share of the provisioner's rewards
+/// proportional to its weight, minus the commission of the provisioner, and is
+/// slashed together with the stake of the provisioner.
+///
+/// Only provisioners that opted in to delegations, by setting their
+/// [`Commission`], accept delegations.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Delegate {
+    chain_id: u8,
+    delegator: BlsPublicKey,
+    provisioner: BlsPublicKey,
+    value: u64,
+    nonce: u64,
+    signature: BlsSignature,
+}
+
+impl Delegate {
+    /// Create a new delegation of `value` to the given provisioner.
+    ///
+    /// The `nonce` must be the one following the current stake nonce of the
+    /// delegator, to prevent the delegation from being replayed.
+    #[must_use]
+    pub fn new(
+        delegator_sk: &BlsSecretKey,
+        provisioner: &BlsPublicKey,
+        value: u64,
+        nonce: u64,
+        chain_id: u8,
+    ) -> Self {
+        let delegator = BlsPublicKey::from(delegator_sk);
+
+        let mut delegate =
+            Self::new_unsigned(delegator, *provisioner, value, nonce, chain_id);
+        delegate.signature = delegator_sk.sign(&delegate.signature_message());
+
+        delegate
+    }
+
+    /// Create a new delegation without signing it.
+    ///
+    /// The [`signature_message`] needs to be signed by the delegator key, and
+    /// the signature set with [`set_signature`], for the delegation to be
+    /// accepted by the stake contract.
+    ///
+    /// [`signature_message`]: Self::signature_message
+    /// [`set_signature`]: Self::set_signature
+    #[must_use]
+    pub fn new_unsigned(
+        delegator: BlsPublicKey,
+        provisioner: BlsPublicKey,
+        value: u64,
+        nonce: u64,
+        chain_id: u8,
+    ) -> Self {
+        Self {
+            chain_id,
+            delegator,
+            provisioner,
+            value,
+            nonce,
+            signature: BlsSignature::default(),
+        }
+    }
+
+    /// The account delegating the value.
+    #[must_use]
+    pub fn delegator(&self) -> &BlsPublicKey {
+        &self.delegator
+    }
+
+    /// The provisioner the value is delegated to.
+    #[must_use]
+    pub fn provisioner(&self) -> &BlsPublicKey {
+        &self.provisioner
+    }
+
+    /// Value to delegate.
+    #[must_use]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The stake nonce of the delegator the delegation is made with.
+    #[must_use]
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Chain ID of the delegation.
+    #[must_use]
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    /// Signature of the delegation.
+    #[must_use]
+    pub fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+
+    /// Set the signature of the delegation, created over the
+    /// [`signature_message`](Self::signature_message).
+    pub fn set_signature(&mut self, signature: BlsSignature) {
+        self.signature = signature;
+    }
+
+    /// Return the message that is used as the input to the signature.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(DELEGATE_DOMAIN);
+
+        bytes.push(self.chain_id);
+        bytes.extend(self.delegator.to_bytes());
+        bytes.extend(self.provisioner.to_bytes());
+        bytes.extend(self.value.to_bytes());
+        bytes.extend(self.nonce.to_bytes());
+
+        bytes
+    }
+}
+
+/// Withdraw some value from a delegation on the stake contract.
+///
+/// This is used in both `undelegate`, to withdraw the delegated value, and
+/// `withdraw_delegation`, to withdraw the rewards of the delegation.
+#[derive(Debug, Clone, PartialEq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DelegationWithdraw {
+    delegator: BlsPublicKey,
+    provisioner: BlsPublicKey,
+    withdraw: TransferWithdraw,
+    signature: BlsSignature,
+}
+
+impl DelegationWithdraw {
+    /// Create a new withdrawal from the delegation to the given provisioner.
+    #[must_use]
+    pub fn new(
+        delegator_sk: &BlsSecretKey,
+        provisioner: &BlsPublicKey,
+        withdraw: TransferWithdraw,
+    ) -> Self {
+        let delegator = BlsPublicKey::from(delegator_sk);
+
+        let mut delegation_withdraw =
+            Self::new_unsigned(delegator, *provisioner, withdraw);
+        delegation_withdraw.signature =
+            delegator_sk.sign(&delegation_withdraw.signature_message());
+
+        delegation_withdraw
+    }
+
+    /// Create a new withdrawal from a delegation without signing it.
+    ///
+    /// The [`signature_message`] needs to be signed by the delegator key, and
+    /// the signature set with [`set_signature`], for the withdrawal to be
+    /// accepted by the stake contract.
+    ///
+    /// [`signature_message`]: Self::signature_message
+    /// [`set_signature`]: Self::set_signature
+    #[must_use]
+    pub fn new_unsigned(
+        delegator: BlsPublicKey,
+        provisioner: BlsPublicKey,
+        withdraw: TransferWithdraw,
+    ) -> Self {
+        Self {
+            delegator,
+            provisioner,
+            withdraw,
+            signature: BlsSignature::default(),
+        }
+    }
+
+    /// The account that delegated the value.
+    #[must_use]
+    pub fn delegator(&self) -> &BlsPublicKey {
+        &self.delegator
+    }
+
+    /// The provisioner the value was delegated to.
+    #[must_use]
+    pub fn provisioner(&self) -> &BlsPublicKey {
+        &self.provisioner
+    }
+
+    /// The inner withdrawal to pass to the transfer contract.
+    #[must_use]
+    pub fn transfer_withdraw(&self) -> &TransferWithdraw {
+        &self.withdraw
+    }
+
+    /// Signature of the withdrawal.
+    #[must_use]
+    pub fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+
+    /// Set the signature of the withdrawal, created over the
+    /// [`signature_message`](Self::signature_message).
+    pub fn set_signature(&mut self, signature: BlsSignature) {
+        self.signature = signature;
+    }
+
+    /// Signature message used for [`DelegationWithdraw`].
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(DELEGATION_WITHDRAW_DOMAIN);
+
+        bytes.extend(self.delegator.to_bytes());
+        bytes.extend(self.provisioner.to_bytes());
+        bytes.extend(self.withdraw.wrapped_signature_message());
+
+        bytes
+    }
+}
+
+/// Set the commission rate of a provisioner, the part of the rewards earned by
+/// the value delegated to it that it keeps. The rest is split between its
+/// delegators.
+///
+/// Setting a commission, signed by both the account and owner keys of the
+/// stake, is how a provisioner opts in to receive delegations.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Commission {
+    chain_id: u8,
+    account: BlsPublicKey,
+    rate: u16,
+    nonce: u64,
+    signature: DoubleSignature,
+}
+
+impl Commission {
+    /// Create a new commission rate, in basis points, for the stake of the
+    /// given account, signed by both the account and owner keys of the stake.
+    ///
+    /// The `nonce` must be the one following the current nonce of the stake,
+    /// to prevent the commission from being replayed.
+    #[must_use]
+    pub fn new(
+        account_sk: &BlsSecretKey,
+        owner_sk: &BlsSecretKey,
+        rate: u16,
+        nonce: u64,
+        chain_id: u8,
+    ) -> Self {
+        let account = BlsPublicKey::from(account_sk);
+
+        let mut commission = Self {
+            chain_id,
+            account,
+            rate,
+            nonce,
+            signature: DoubleSignature::default(),
+        };
+
+        let msg = commission.signature_message();
+
+        commission.signature = DoubleSignature {
+            account: account_sk.sign(&msg),
+            owner: owner_sk.sign(&msg),
+        };
+
+        commission
+    }
+
+    /// The account of the provisioner.
+    #[must_use]
+    pub fn account(&self) -> &BlsPublicKey {
+        &self.account
+    }
+
+    /// The commission rate, in basis points.
+    #[must_use]
+    pub fn rate(&self) -> u16 {
+        self.rate
+    }
+
+    /// The nonce of the stake the commission is set with.
+    #[must_use]
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Chain ID of the commission.
+    #[must_use]
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    /// Signature of the commission.
+    #[must_use]
+    pub fn signature(&self) -> &DoubleSignature {
+        &self.signature
+    }
+
+    /// Return the message that is used as the input to the signature.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(COMMISSION_DOMAIN);
+
+        bytes.push(self.chain_id);
+        bytes.extend(self.account.to_bytes());
+        bytes.extend(self.rate.to_le_bytes());
+        bytes.extend(self.nonce.to_bytes());
+
+        bytes
+    }
+}
+
+/// The value delegated by an account to a provisioner, and the rewards it
+/// earned.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Deserialize, Serialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DelegationData {
+    /// The value delegated.
+    pub value: u64,
+    /// The value that has been locked, due to a soft slash of the
+    /// provisioner.
+    pub locked: u64,
+    /// The rewards earned by the delegation.
+    pub reward: u64,
+}
+
+impl DelegationData {
+    /// Returns true if there is no value or reward left to withdraw.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.total_funds() + self.reward == 0
+    }
+
+    /// Get the total funds delegated (value + locked).
+    #[must_use]
+    pub fn total_funds(&self) -> u64 {
+        self.value + self.locked
+    }
+}
+
+/// Event emitted after a delegation operation is performed.
+#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DelegationEvent {
+    /// The provisioner of the delegation.
+    pub provisioner: BlsPublicKey,
+    /// The account that delegated the value.
+    pub delegator: BlsPublicKey,
+    /// Effective value of the relevant operation, be it `delegate`,
+    /// `undelegate`, `withdraw_delegation` or `delegation_reward`.
+    pub value: u64,
+}
+
+/// Event emitted when the delegated value adding to the weight of a
+/// provisioner changes, be it with `delegated_stake` or `undelegated_stake`.
+#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DelegatedStakeEvent {
+    /// The account of the provisioner.
+    pub account: BlsPublicKey,
+    /// The delegated value added to, or removed from, the weight of the
+    /// provisioner.
+    pub value: u64,
+}
+
+/// Event emitted after a provisioner sets its commission rate.
+#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CommissionEvent {
+    /// The account of the provisioner.
+    pub account: BlsPublicKey,
+    /// The commission rate, in basis points.
+    pub rate: u16,
+}
+
+/// Set the Moonlight account the rewards of a stake are minted to on
+/// withdrawal, instead of the receiver of the withdrawal.
+///
+/// Setting no receiver restores minting the rewards to the receiver of the
+/// withdrawal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RewardAddress {
+    chain_id: u8,
+    account: BlsPublicKey,
+    receiver: Option<BlsPublicKey>,
+    nonce: u64,
+    signature: DoubleSignature,
+}
+
+impl RewardAddress {
+    /// Create a new reward address for the stake of the given account, signed
+    /// by both the account and owner keys of the stake.
+    ///
+    /// The `nonce` must be the one following the current nonce of the stake,
+    /// to prevent the reward address from being replayed.
+    #[must_use]
+    pub fn new(
+        account_sk: &BlsSecretKey,
+        owner_sk: &BlsSecretKey,
+        receiver: Option<BlsPublicKey>,
+        nonce: u64,
+        chain_id: u8,
+    ) -> Self {
+        let account = BlsPublicKey::from(account_sk);
+
+        let mut reward_address = Self {
+            chain_id,
+            account,
+            receiver,
+            nonce,
+            signature: DoubleSignature::default(),
+        };
+
+        let msg = reward_address.signature_message();
+
+        reward_address.signature = DoubleSignature {
+            account: account_sk.sign(&msg),
+            owner: owner_sk.sign(&msg),
+        };
+
+        reward_address
+    }
+
+    /// The account of the stake.
+    #[must_use]
+    pub fn account(&self) -> &BlsPublicKey {
+        &self.account
+    }
+
+    /// The account the rewards are minted to, if any.
+    #[must_use]
+    pub fn receiver(&self) -> Option<&BlsPublicKey> {
+        self.receiver.as_ref()
+    }
+
+    /// The nonce of the stake the reward address is set with.
+    #[must_use]
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Chain ID of the reward address.
+    #[must_use]
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    /// Signature of the reward address.
+    #[must_use]
+    pub fn signature(&self) -> &DoubleSignature {
+        &self.signature
+    }
+
+    /// Return the message that is used as the input to the signature.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(REWARD_ADDRESS_DOMAIN);
+
+        bytes.push(self.chain_id);
+        bytes.extend(self.account.to_bytes());
+        bytes.extend(self.nonce.to_bytes());
+        if let Some(receiver) = &self.receiver {
+            bytes.extend(receiver.to_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// Event emitted after the reward address of a stake is set.
+#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RewardAddressEvent {
+    /// The account of the stake.
+    pub account: BlsPublicKey,
+    /// The account the rewards are minted to, if any.
+    pub receiver: Option<BlsPublicKey>,
+}
+
 /// Event emitted after a stake contract operation is performed.
 #[derive(Debug, Clone, Archive, Deserialize, Serialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -438,6 +989,29 @@ pub struct StakeData {
     pub hard_faults: u8,
 }
 
+/// The number of epochs, up to the current one, for which the stake contract
+/// keeps the reward snapshots of a stake.
+pub const REWARD_SNAPSHOT_EPOCHS: u64 = 120;
+
+/// Snapshot of the rewards of a stake during an epoch.
+///
+/// The stake contract updates the snapshot of the current epoch whenever the
+/// rewards of a stake change, so the snapshot of a past epoch reflects the
+/// rewards at its end.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Deserialize, Serialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct EpochRewards {
+    /// The rewards accrued during the epoch.
+    pub accrued: u64,
+    /// The rewards withdrawn during the epoch.
+    pub withdrawn: u64,
+    /// The rewards left to claim at the end of the epoch, or at the current
+    /// block height for the ongoing epoch.
+    pub claimable: u64,
+}
+
 /// Keys that identify a stake
 #[derive(
     Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Deserialize, Serialize,
@@ -651,6 +1225,20 @@ impl StakeAmount {
         self.locked += amount;
     }
 
+    /// The part of a top-up of `value` that gets locked when added to this
+    /// stake at the given block height.
+    ///
+    /// Topping up an eligible stake locks 10% of the added value, while a stake
+    /// that isn't eligible yet is topped up without penalty.
+    #[must_use]
+    pub const fn top_up_locked(&self, value: u64, block_height: u64) -> u64 {
+        if block_height >= self.eligibility {
+            value / 10
+        } else {
+            0
+        }
+    }
+
     /// Get the total funds belonging to the stake (value + locked)
     #[must_use]
     pub fn total_funds(&self) -> u64 {
@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference staking-pool contract.
+//!
+//! The pool stakes the deposits of many accounts to a single provisioner,
+//! through the contract staking functions of the
+//! [stake contract](crate::stake::STAKE_CONTRACT). Depositors receive shares
+//! of the pool, and the rewards of the provisioner are distributed to them in
+//! proportion to their shares whenever they are withdrawn from the stake
+//! contract. Withdrawing a deposit is done by burning shares, which queues the
+//! withdrawal until the end of the current epoch.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+use crate::stake::EPOCH;
+
+/// Topic of the event emitted when funds are deposited into the pool.
+pub const DEPOSIT_TOPIC: &str = "deposit";
+
+/// Topic of the event emitted when rewards are distributed to the pool.
+pub const REWARD_TOPIC: &str = "reward";
+
+/// Topic of the event emitted when an account claims its rewards.
+pub const CLAIM_TOPIC: &str = "claim";
+
+/// Topic of the event emitted when an account requests a withdrawal.
+pub const REQUEST_TOPIC: &str = "request";
+
+/// Topic of the event emitted when a queued withdrawal is paid out.
+pub const WITHDRAW_TOPIC: &str = "withdraw";
+
+/// The argument of the `init` function of the staking-pool contract, called
+/// when it is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PoolInit {
+    /// The provisioner the pool stakes to.
+    pub provisioner: AccountPublicKey,
+}
+
+/// The state of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PoolInfo {
+    /// The provisioner the pool stakes to.
+    pub provisioner: AccountPublicKey,
+    /// Whether the pool has an active stake, and accepts deposits.
+    pub open: bool,
+    /// The value staked by the pool, in Lux.
+    pub staked: u64,
+    /// The total shares of the pool.
+    pub shares: u64,
+    /// The value of the queued withdrawals, in Lux.
+    pub queued: u64,
+}
+
+/// The position of an account in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct PoolAccount {
+    /// The shares owned by the account.
+    pub shares: u64,
+    /// The rewards the account can claim, in Lux.
+    pub rewards: u64,
+}
+
+/// A withdrawal waiting in the queue of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct WithdrawalRequest {
+    /// The account receiving the withdrawal.
+    pub account: AccountPublicKey,
+    /// The value withdrawn, in Lux.
+    pub value: u64,
+    /// The block height from which the withdrawal can be paid out.
+    pub ready_at: u64,
+}
+
+/// The block height from which a withdrawal requested at the given height can
+/// be paid out, i.e. the start of the next epoch.
+#[must_use]
+pub const fn withdrawal_ready_at(block_height: u64) -> u64 {
+    (block_height / EPOCH + 1) * EPOCH
+}
+
+/// Event data emitted when funds are deposited into the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct DepositEvent {
+    /// The account depositing.
+    pub account: AccountPublicKey,
+    /// The value deposited, in Lux.
+    pub value: u64,
+    /// The shares received for the deposit.
+    pub shares: u64,
+}
+
+/// Event data emitted when rewards are distributed to the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RewardEvent {
+    /// The value of the rewards, in Lux.
+    pub value: u64,
+    /// The total shares the rewards are distributed to.
+    pub shares: u64,
+}
+
+/// Event data emitted when an account claims its rewards.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ClaimEvent {
+    /// The account claiming.
+    pub account: AccountPublicKey,
+    /// The value claimed, in Lux.
+    pub value: u64,
+}
+
+/// Event data emitted when an account requests a withdrawal.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RequestEvent {
+    /// The shares burned.
+    pub shares: u64,
+    /// The withdrawal queued.
+    pub request: WithdrawalRequest,
+}
+
+/// Event data emitted when a queued withdrawal is paid out.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct WithdrawEvent {
+    /// The account receiving the withdrawal.
+    pub account: AccountPublicKey,
+    /// The value withdrawn, in Lux.
+    pub value: u64,
+}
@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference fungible token contract (DRC-20).
+//!
+//! Token balances are held by [`Account`]s, which are either Moonlight
+//! accounts or contracts. A Moonlight account is authenticated as the public
+//! sender of the transaction calling the token contract, while a contract is
+//! authenticated as the caller of the token contract.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::abi::ContractId;
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+
+/// Topic of the event emitted when tokens are transferred.
+pub const TRANSFER_TOPIC: &str = "transfer";
+
+/// Topic of the event emitted when an allowance is approved.
+pub const APPROVE_TOPIC: &str = "approve";
+
+/// Name of the function called on a contract receiving tokens.
+pub const TOKEN_RECEIVED_FN: &str = "token_received";
+
+/// An account holding tokens.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub enum Account {
+    /// A Moonlight account.
+    External(AccountPublicKey),
+    /// A contract.
+    Contract(ContractId),
+}
+
+impl Account {
+    /// A unique byte representation of the account, to be used as a key.
+    ///
+    /// Moonlight accounts and contracts are represented with byte arrays of
+    /// different lengths, so that their keys never collide.
+    #[must_use]
+    pub fn to_key(&self) -> Vec<u8> {
+        match self {
+            Self::External(pk) => pk.to_raw_bytes().to_vec(),
+            Self::Contract(contract) => contract.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<AccountPublicKey> for Account {
+    fn from(pk: AccountPublicKey) -> Self {
+        Self::External(pk)
+    }
+}
+
+impl From<ContractId> for Account {
+    fn from(contract: ContractId) -> Self {
+        Self::Contract(contract)
+    }
+}
+
+/// The information describing a token.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TokenInfo {
+    /// Name of the token.
+    pub name: String,
+    /// Symbol of the token.
+    pub symbol: String,
+    /// Number of decimals used to display amounts of the token.
+    pub decimals: u8,
+}
+
+/// The argument of the `init` function of the token contract, called when it
+/// is deployed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TokenInit {
+    /// The information describing the token.
+    pub info: TokenInfo,
+    /// The initial balances of the token, which make up its total supply.
+    pub balances: Vec<(Account, u64)>,
+}
+
+/// Transfer tokens owned by the sender to a receiver.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Transfer {
+    /// The account receiving the tokens.
+    pub receiver: Account,
+    /// The amount of tokens transferred.
+    pub value: u64,
+}
+
+/// Transfer tokens on behalf of their owner, within the allowance the owner
+/// approved for the sender.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferFrom {
+    /// The account owning the tokens.
+    pub owner: Account,
+    /// The account receiving the tokens.
+    pub receiver: Account,
+    /// The amount of tokens transferred.
+    pub value: u64,
+}
+
+/// Approve a spender to transfer up to the given amount of the sender's
+/// tokens, replacing any previous allowance.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Approve {
+    /// The account allowed to spend the tokens.
+    pub spender: Account,
+    /// The amount of tokens allowed to be spent.
+    pub value: u64,
+}
+
+/// The payload sent by the token contract to a contract receiving tokens,
+/// when calling its [`TOKEN_RECEIVED_FN`] function.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TokenReceived {
+    /// The account the tokens were transferred from.
+    pub sender: Account,
+    /// The amount of tokens received.
+    pub value: u64,
+}
+
+/// Event data emitted when tokens are transferred.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferEvent {
+    /// The account the tokens were transferred from.
+    pub sender: Account,
+    /// The account receiving the tokens.
+    pub receiver: Account,
+    /// The amount of tokens transferred.
+    pub value: u64,
+}
+
+/// Event data emitted when an allowance is approved.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ApproveEvent {
+    /// The account owning the tokens.
+    pub owner: Account,
+    /// The account allowed to spend the tokens.
+    pub spender: Account,
+    /// The amount of tokens allowed to be spent.
+    pub value: u64,
+}
@@ -21,10 +21,14 @@ use rkyv::{Archive, Deserialize, Serialize};
 use crate::abi::ContractId;
 use crate::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+    Signature as AccountSignature,
 };
 use crate::{BlsScalar, Error};
 
-use self::data::{ContractCall, ContractDeploy, TransactionData};
+use self::data::{
+    ContractCall, ContractDeploy, ContractUpgrade, Memo, MemoKind,
+    TransactionData,
+};
 use self::moonlight::Transaction as MoonlightTransaction;
 use self::phoenix::{
     Note, Prove, PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
@@ -41,6 +45,11 @@ pub mod withdraw;
 /// ID of the genesis transfer contract
 pub const TRANSFER_CONTRACT: ContractId = crate::reserved(0x1);
 
+/// The maximum number of nullifiers checked by a single
+/// `existing_nullifiers_paged` query, so that a page always fits in the
+/// argument buffer.
+pub const MAX_NULLIFIERS_PAGE: usize = 1024;
+
 /// Panic of "Nonce not ready to be used yet"
 pub const PANIC_NONCE_NOT_READY: &str = "Nonce not ready to be used yet";
 
@@ -62,6 +71,14 @@ pub const CONVERT_TOPIC: &str = "convert";
 pub const MINT_TOPIC: &str = "mint";
 /// Topic for the mint to contract event.
 pub const MINT_CONTRACT_TOPIC: &str = "mint_c";
+/// Topic for the approve event.
+pub const APPROVE_TOPIC: &str = "approve";
+/// Topic for the approved deposit event.
+pub const APPROVED_DEPOSIT_TOPIC: &str = "approved_deposit";
+/// Topic for the event emitted when the [`TransferLimits`] change.
+pub const LIMITS_TOPIC: &str = "limits";
+/// Topic for the event of a tagged memo.
+pub const MEMO_TOPIC: &str = "memo";
 
 /// The transaction used by the transfer contract.
 #[derive(Debug, Clone, Archive, PartialEq, Eq, Serialize, Deserialize)]
@@ -265,16 +282,27 @@ impl Transaction {
 
     /// Returns the memo used with the transaction, if any.
     #[must_use]
-    pub fn memo(&self) -> Option<&[u8]> {
+    pub fn memo(&self) -> Option<&Memo> {
         match self {
             Self::Phoenix(tx) => tx.memo(),
             Self::Moonlight(tx) => tx.memo(),
         }
     }
 
+    /// Return the contract upgrade data, if there is any.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<&ContractUpgrade> {
+        match self {
+            Self::Phoenix(tx) => tx.upgrade(),
+            Self::Moonlight(tx) => tx.upgrade(),
+        }
+    }
+
     /// Creates a modified clone of this transaction if it contains data for
-    /// deployment, clones all fields except for the bytecode' 'bytes' part.
-    /// Returns none if the transaction is not a deployment transaction.
+    /// a deployment or an upgrade, clones all fields except for the bytecode
+    /// 'bytes' part.
+    /// Returns none if the transaction is not a deployment or an upgrade
+    /// transaction.
     #[must_use]
     pub fn strip_off_bytecode(&self) -> Option<Self> {
         Some(match self {
@@ -341,22 +369,24 @@ impl Transaction {
         }
     }
 
-    /// Returns the charge for a contract deployment. The deployment of a
-    /// contract will cost at least `min_deploy_points`.
-    /// If the transaction is not a deploy-transaction, the deploy-charge will
-    /// be 0.
+    /// Returns the charge for a contract deployment or upgrade. The
+    /// deployment of a contract, as well as its upgrade, will cost at least
+    /// `min_deploy_points`.
+    /// If the transaction is neither a deploy nor an upgrade transaction, the
+    /// deploy-charge will be 0.
     #[must_use]
     pub fn deploy_charge(
         &self,
         gas_per_deploy_byte: u64,
         min_deploy_points: u64,
     ) -> u64 {
-        if let Some(deploy) = self.deploy() {
-            let bytecode_len = deploy.bytecode.bytes.len() as u64;
-            max(bytecode_len * gas_per_deploy_byte, min_deploy_points)
-        } else {
-            0
-        }
+        let bytecode = match (self.deploy(), self.upgrade()) {
+            (Some(deploy), _) => &deploy.bytecode,
+            (_, Some(upgrade)) => &upgrade.bytecode,
+            _ => return 0,
+        };
+        let bytecode_len = bytecode.bytes.len() as u64;
+        max(bytecode_len * gas_per_deploy_byte, min_deploy_points)
     }
 }
 
@@ -420,6 +450,145 @@ pub struct ContractToAccount {
     pub value: u64,
 }
 
+/// The payload of the `approve` call of the transfer contract, allowing a
+/// contract to pull up to the given value from the Moonlight account sending
+/// the transaction, replacing any previous allowance.
+///
+/// The contract pulls funds with an `approved_deposit` call, without the need
+/// for the account to sign a transaction for each deposit.
+#[derive(Debug, Clone, Archive, PartialEq, Eq, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Approve {
+    /// The contract allowed to pull the funds.
+    pub contract: ContractId,
+    /// The maximum value the contract can pull.
+    pub value: u64,
+}
+
+/// The payload sent by a contract to the transfer contract to pull some funds
+/// from an account that approved it.
+#[derive(Debug, Clone, Archive, PartialEq, Eq, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ApprovedDeposit {
+    /// Account to pull the funds from.
+    pub account: AccountPublicKey,
+    /// Amount to deposit to the calling contract.
+    pub value: u64,
+}
+
+/// Limits on the value flowing in and out of contract balances in a single
+/// block, acting as a circuit-breaker against draining a contract.
+///
+/// The limits are set with the `set_limits` call of the transfer contract,
+/// which only accepts limits signed by the owner of the contract (see
+/// [`SetLimits`]). Withdrawals and transfers from contracts to accounts both
+/// count towards the withdrawal limits.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct TransferLimits {
+    /// The maximum total value deposited into contracts in a block, if any.
+    pub max_block_deposit: Option<u64>,
+    /// The maximum total value withdrawn from contracts in a block, if any.
+    pub max_block_withdrawal: Option<u64>,
+    /// The maximum value each of the given contracts can withdraw in a block,
+    /// in addition to the total limit.
+    pub contract_caps: Vec<(ContractId, u64)>,
+}
+
+impl TransferLimits {
+    /// Create new limits, leaving all transfers unlimited.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_block_deposit: None,
+            max_block_withdrawal: None,
+            contract_caps: Vec::new(),
+        }
+    }
+
+    /// The maximum value the given contract can withdraw in a block, if any.
+    #[must_use]
+    pub fn contract_cap(&self, contract: &ContractId) -> Option<u64> {
+        self.contract_caps
+            .iter()
+            .find(|(id, _)| id == contract)
+            .map(|(_, cap)| *cap)
+    }
+
+    /// Provides contribution bytes for a signature over the limits.
+    #[must_use]
+    pub fn to_hash_input_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for max in [self.max_block_deposit, self.max_block_withdrawal] {
+            match max {
+                Some(max) => {
+                    bytes.push(1);
+                    bytes.extend(max.to_le_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        for (contract, cap) in &self.contract_caps {
+            bytes.extend(contract.as_bytes());
+            bytes.extend(cap.to_le_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// New [`TransferLimits`], signed by the owner of the transfer contract.
+///
+/// The nonce must follow the one of the limits last set, so that a signature
+/// can't be replayed to restore older limits, and the chain ID must match the
+/// one of the chain, so that it can't be replayed on another chain.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct SetLimits {
+    /// The new limits.
+    pub limits: TransferLimits,
+    /// The nonce of the change.
+    pub nonce: u64,
+    /// The ID of the chain the limits are set on.
+    pub chain_id: u8,
+    /// Signature of the owner over [`Self::signature_message`].
+    pub signature: AccountSignature,
+}
+
+impl SetLimits {
+    /// Create new limits, signed by the owner of the transfer contract.
+    #[must_use]
+    pub fn new(
+        owner_sk: &AccountSecretKey,
+        limits: TransferLimits,
+        nonce: u64,
+        chain_id: u8,
+    ) -> Self {
+        let mut set_limits = Self {
+            limits,
+            nonce,
+            chain_id,
+            signature: AccountSignature::default(),
+        };
+        set_limits.signature = owner_sk.sign(&set_limits.signature_message());
+        set_limits
+    }
+
+    /// Returns the message the owner of the transfer contract is expected to
+    /// sign.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(TRANSFER_CONTRACT.as_bytes());
+        bytes.push(self.chain_id);
+        bytes.extend(self.nonce.to_le_bytes());
+        bytes.extend(self.limits.to_hash_input_bytes());
+        bytes
+    }
+}
+
 /// Event data emitted on a withdrawal from a contract.
 #[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -486,6 +655,33 @@ pub struct DepositEvent {
     pub value: u64,
 }
 
+/// Event data emitted when an account approves a contract to pull its funds.
+#[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ApproveEvent {
+    /// The account approving the contract.
+    pub account: AccountPublicKey,
+    /// The contract allowed to pull the funds.
+    pub contract: ContractId,
+    /// The maximum value the contract can pull.
+    pub value: u64,
+}
+
+/// Event data emitted on a deposit pulled by a contract from an account that
+/// approved it.
+#[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ApprovedDepositEvent {
+    /// The account the funds were pulled from.
+    pub sender: AccountPublicKey,
+    /// The receiver of the value.
+    pub receiver: ContractId,
+    /// The value deposited.
+    pub value: u64,
+    /// The value the contract can still pull from the account.
+    pub allowance: u64,
+}
+
 /// Event data emitted on a transfer from a contract to a contract.
 #[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -518,7 +714,7 @@ pub struct PhoenixTransactionEvent {
     pub nullifiers: Vec<BlsScalar>,
     /// Notes produced during the transaction.
     pub notes: Vec<Note>,
-    /// The memo included in the transaction.
+    /// The content of the memo included in the transaction.
     pub memo: Vec<u8>,
     /// Gas spent by the transaction.
     pub gas_spent: u64,
@@ -526,6 +722,20 @@ pub struct PhoenixTransactionEvent {
     pub refund_note: Option<Note>,
 }
 
+/// Event data emitted, before the transaction event, on the completion of a
+/// transaction carrying a tagged memo.
+///
+/// The transaction events only carry the content of the memo. Plain memos
+/// emit no such event, so a memo without one is plain.
+#[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct MemoEvent {
+    /// The kind of content of the memo.
+    pub kind: MemoKind,
+    /// The content of the memo, without its tag.
+    pub content: Vec<u8>,
+}
+
 /// Event data emitted on a moonlight transaction's completion.
 #[derive(Debug, Clone, Archive, PartialEq, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -536,7 +746,7 @@ pub struct MoonlightTransactionEvent {
     pub receiver: Option<AccountPublicKey>,
     /// Transfer amount
     pub value: u64,
-    /// The memo included in the transaction.
+    /// The content of the memo included in the transaction.
     pub memo: Vec<u8>,
     /// Gas spent by the transaction.
     pub gas_spent: u64,
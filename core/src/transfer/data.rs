@@ -17,11 +17,23 @@ use rkyv::ser::serializers::AllocSerializer;
 use rkyv::{Archive, Deserialize, Serialize};
 
 use crate::abi::{ContractId, ARGBUF_LEN};
+use crate::signatures::bls::Signature as BlsSignature;
 use crate::Error;
 
 /// The maximum size of a memo.
 pub const MAX_MEMO_SIZE: usize = 512;
 
+/// Topic of the event emitted when a contract is upgraded.
+pub const UPGRADE_TOPIC: &str = "upgrade";
+
+/// Topic of the event emitted by the transfer contract when it escrows the
+/// deposit of a deployed or upgraded contract.
+pub const DEPLOY_DEPOSIT_TOPIC: &str = "deploy_deposit";
+
+/// Name of the optional entrypoint called on the new bytecode of an upgraded
+/// contract.
+pub const MIGRATE_FN: &str = "migrate";
+
 /// Data for either contract call or contract deployment.
 #[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
@@ -33,7 +45,9 @@ pub enum TransactionData {
     Deploy(ContractDeploy),
     /// Additional data added to a transaction, that is not a deployment or a
     /// call.
-    Memo(Vec<u8>),
+    Memo(Memo),
+    /// Data for an upgrade of the bytecode of a deployed contract.
+    Upgrade(ContractUpgrade),
 }
 
 impl From<ContractCall> for TransactionData {
@@ -48,15 +62,242 @@ impl From<ContractDeploy> for TransactionData {
     }
 }
 
+impl From<ContractUpgrade> for TransactionData {
+    fn from(u: ContractUpgrade) -> Self {
+        TransactionData::Upgrade(u)
+    }
+}
+
+impl From<Memo> for TransactionData {
+    fn from(m: Memo) -> Self {
+        TransactionData::Memo(m)
+    }
+}
+
 impl From<Vec<u8>> for TransactionData {
     fn from(d: Vec<u8>) -> Self {
-        TransactionData::Memo(d)
+        TransactionData::Memo(Memo {
+            kind: MemoKind::Plain,
+            content: d,
+        })
     }
 }
 
 impl From<String> for TransactionData {
     fn from(d: String) -> Self {
-        TransactionData::Memo(d.as_bytes().to_vec())
+        TransactionData::Memo(Memo {
+            kind: MemoKind::Plain,
+            content: d.into_bytes(),
+        })
+    }
+}
+
+impl TransactionData {
+    /// Creates the data of a transaction carrying a plain memo with the
+    /// given bytes.
+    ///
+    /// Unlike the conversions from `Vec<u8>` and `String`, which only fail
+    /// once the transaction is created, this checks the size of the memo
+    /// up front.
+    ///
+    /// # Errors
+    /// Errors with [`Error::MemoTooLarge`] if the memo is larger than
+    /// [`MAX_MEMO_SIZE`].
+    pub fn new_memo(bytes: impl Into<Vec<u8>>) -> Result<Self, Error> {
+        Memo::new(bytes).map(TransactionData::Memo)
+    }
+}
+
+/// The number of bytes a tagged [`Memo`] is larger than its content: the byte
+/// of its [`MemoKind`].
+pub const MEMO_TAG_SIZE: usize = 1;
+
+/// The kind of content carried by a [`Memo`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Archive,
+    Serialize,
+    Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum MemoKind {
+    /// Plain bytes, such as a message or a payment reference.
+    #[default]
+    Plain,
+    /// Content encrypted for the receiver of the transaction.
+    Encrypted,
+    /// The hash of data kept off-chain, committing to it.
+    HashCommitment,
+}
+
+impl MemoKind {
+    /// Returns the byte encoding the kind of a tagged memo.
+    #[must_use]
+    pub const fn to_byte(self) -> u8 {
+        match self {
+            Self::Plain => 0,
+            Self::Encrypted => 1,
+            Self::HashCommitment => 2,
+        }
+    }
+
+    /// Returns the kind of a tagged memo encoded by the given byte, if any.
+    #[must_use]
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Plain),
+            1 => Some(Self::Encrypted),
+            2 => Some(Self::HashCommitment),
+            _ => None,
+        }
+    }
+}
+
+/// Additional data of a transaction, of at most [`MAX_MEMO_SIZE`] bytes.
+///
+/// Plain memos are encoded in transactions as their bytes, the same as the
+/// memos of transactions predating the [`MemoKind`], so any bytes read as a
+/// plain memo. The other kinds are tagged: they are encoded in transactions
+/// under their own data discriminant, followed by the byte of their kind, and
+/// never collide with a plain memo.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Hash, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Memo {
+    kind: MemoKind,
+    content: Vec<u8>,
+}
+
+impl Memo {
+    /// Creates a plain memo with the given bytes.
+    ///
+    /// # Errors
+    /// Errors with [`Error::MemoTooLarge`] if the memo is larger than
+    /// [`MAX_MEMO_SIZE`].
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Result<Self, Error> {
+        Self::checked(MemoKind::Plain, bytes.into())
+    }
+
+    /// Creates a memo with the given kind of content.
+    ///
+    /// # Errors
+    /// Errors with [`Error::MemoTooLarge`] if the memo, including its tag, is
+    /// larger than [`MAX_MEMO_SIZE`].
+    pub fn with_kind(kind: MemoKind, content: &[u8]) -> Result<Self, Error> {
+        Self::checked(kind, content.to_vec())
+    }
+
+    fn checked(kind: MemoKind, content: Vec<u8>) -> Result<Self, Error> {
+        let memo = Self { kind, content };
+        if memo.len() > MAX_MEMO_SIZE {
+            return Err(Error::MemoTooLarge(memo.len()));
+        }
+        Ok(memo)
+    }
+
+    /// Returns the kind of content of the memo.
+    #[must_use]
+    pub fn kind(&self) -> MemoKind {
+        self.kind
+    }
+
+    /// Returns the content of the memo, without its tag.
+    #[must_use]
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Returns the size of the memo, including its tag.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self.kind {
+            MemoKind::Plain => self.content.len(),
+            _ => self.content.len() + MEMO_TAG_SIZE,
+        }
+    }
+
+    /// Returns if the memo is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes of the memo to be included in the hash of a
+    /// transaction: the byte of its kind, if tagged, followed by its
+    /// content.
+    #[must_use]
+    pub fn to_hash_input_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len());
+        if self.kind != MemoKind::Plain {
+            bytes.push(self.kind.to_byte());
+        }
+        bytes.extend(&self.content);
+        bytes
+    }
+}
+
+impl TryFrom<Vec<u8>> for Memo {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+impl TryFrom<&str> for Memo {
+    type Error = Error;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        Self::new(text.as_bytes())
+    }
+}
+
+/// Memos are serialized with their kind and their hex encoded content.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Memo {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Memo", 2)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("content", &hex::encode(&self.content))?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Memo {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+
+        #[derive(serde::Deserialize)]
+        struct Encoded {
+            kind: MemoKind,
+            content: String,
+        }
+
+        let encoded =
+            <Encoded as serde::Deserialize>::deserialize(deserializer)?;
+        let content = hex::decode(encoded.content).map_err(D::Error::custom)?;
+        Self::checked(encoded.kind, content)
+            .map_err(|_| D::Error::custom("memo too large"))
     }
 }
 
@@ -234,3 +475,155 @@ impl ContractBytecode {
         Ok(Self { hash, bytes })
     }
 }
+
+/// An upgrade of the bytecode of a deployed contract, signed by its owner.
+///
+/// The upgrade replaces the bytecode of `contract` with `bytecode`, keeping
+/// the contract ID. If `migrate_args` is set, the [`MIGRATE_FN`] entrypoint of
+/// the new bytecode is called with them in the same session, before the new
+/// bytecode takes the place of the old one. This gives the new bytecode the
+/// chance to query the old contract and carry its state over.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ContractUpgrade {
+    /// The ID of the contract to upgrade.
+    pub contract: ContractId,
+    /// The chain ID of the network the upgrade is meant for.
+    pub chain_id: u8,
+    /// The number of upgrades of the contract including this one, as recorded
+    /// by the transfer contract.
+    pub nonce: u64,
+    /// Blake3 hash of the bytecode being replaced, as known to the owner.
+    pub prev_hash: [u8; 32],
+    /// The new bytecode of the contract.
+    pub bytecode: ContractBytecode,
+    /// Arguments of the migrate entrypoint, if it should be called.
+    pub migrate_args: Option<Vec<u8>>,
+    /// Signature of the contract owner over [`Self::signature_message`].
+    pub signature: BlsSignature,
+}
+
+impl ContractUpgrade {
+    /// Returns the message the contract owner is expected to sign.
+    ///
+    /// The message commits to the contract, to the bytecode being replaced and
+    /// to the new bytecode, so that a signature cannot be used to upgrade a
+    /// different contract. It also commits to the chain ID and the nonce of
+    /// the upgrade, so that it can neither be replayed on another network nor
+    /// after the contract returns to the replaced bytecode.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend(self.contract.as_bytes());
+        bytes.push(self.chain_id);
+        bytes.extend(self.nonce.to_bytes());
+        bytes.extend(self.prev_hash);
+        bytes.extend(self.bytecode.to_hash_input_bytes());
+
+        match &self.migrate_args {
+            Some(migrate_args) => {
+                bytes.push(1);
+                bytes.extend(migrate_args);
+            }
+            None => bytes.push(0),
+        }
+
+        bytes
+    }
+
+    /// Serialize a `ContractUpgrade` into a variable length byte buffer.
+    #[must_use]
+    pub fn to_var_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend(self.contract.as_bytes());
+        bytes.push(self.chain_id);
+        bytes.extend(self.nonce.to_bytes());
+        bytes.extend(self.prev_hash);
+        bytes.extend(&self.bytecode.to_var_bytes());
+
+        match &self.migrate_args {
+            Some(migrate_args) => {
+                bytes.push(1);
+                bytes.extend((migrate_args.len() as u64).to_bytes());
+                bytes.extend(migrate_args);
+            }
+            None => bytes.push(0),
+        }
+
+        bytes.extend(self.signature.to_bytes());
+
+        bytes
+    }
+
+    /// Deserialize a `ContractUpgrade` from a byte buffer.
+    ///
+    /// # Errors
+    /// Errors when the bytes are not canonical.
+    pub fn from_slice(buf: &[u8]) -> Result<Self, BytesError> {
+        let mut buf = buf;
+
+        let contract = crate::read_arr::<32>(&mut buf)?;
+        let chain_id = u8::from_reader(&mut buf)?;
+        let nonce = u64::from_reader(&mut buf)?;
+        let prev_hash = crate::read_arr::<32>(&mut buf)?;
+        let bytecode = ContractBytecode::from_buf(&mut buf)?;
+
+        let migrate_args = match u8::from_reader(&mut buf)? {
+            0 => None,
+            1 => Some(crate::read_vec(&mut buf)?),
+            _ => return Err(BytesError::InvalidData),
+        };
+
+        let signature = BlsSignature::from_bytes(&crate::read_arr(&mut buf)?)
+            .map_err(|_| BytesError::InvalidData)?;
+
+        Ok(Self {
+            contract: contract.into(),
+            chain_id,
+            nonce,
+            prev_hash,
+            bytecode,
+            migrate_args,
+            signature,
+        })
+    }
+
+    /// Provides contribution bytes for the hash of a transaction.
+    #[must_use]
+    pub fn to_hash_input_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.signature_message();
+        bytes.extend(self.signature.to_bytes());
+        bytes
+    }
+}
+
+/// Event emitted when a contract is upgraded.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ContractUpgradeEvent {
+    /// The ID of the upgraded contract.
+    pub contract: ContractId,
+    /// Blake3 hash of the replaced bytecode.
+    pub old_hash: [u8; 32],
+    /// Blake3 hash of the new bytecode.
+    pub new_hash: [u8; 32],
+}
+
+/// Event emitted by the transfer contract when a contract is deployed or
+/// upgraded, recording the value escrowed as a deposit for the state it
+/// occupies.
+///
+/// The deposit scales with the size of the bytecode and the initial memory of
+/// the contract. It is withheld from the refund of the transaction instead of
+/// being paid as fee, and is meant to be returned once the contract is
+/// removed.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ContractDeployDeposit {
+    /// The ID of the deployed or upgraded contract.
+    pub contract: ContractId,
+    /// The value, in LUX, escrowed as a deposit.
+    pub deposit: u64,
+}
@@ -18,8 +18,8 @@ use crate::signatures::bls::{
     Signature as AccountSignature,
 };
 use crate::transfer::data::{
-    ContractBytecode, ContractCall, ContractDeploy, TransactionData,
-    MAX_MEMO_SIZE,
+    ContractBytecode, ContractCall, ContractDeploy, ContractUpgrade, Memo,
+    MemoKind, TransactionData, MAX_MEMO_SIZE,
 };
 use crate::{BlsScalar, Error};
 
@@ -231,13 +231,22 @@ impl Transaction {
 
     /// Returns the memo used with the transaction, if any.
     #[must_use]
-    pub fn memo(&self) -> Option<&[u8]> {
+    pub fn memo(&self) -> Option<&Memo> {
         match self.data()? {
             TransactionData::Memo(memo) => Some(memo),
             _ => None,
         }
     }
 
+    /// Return the contract upgrade data, if there is any.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<&ContractUpgrade> {
+        match self.data()? {
+            TransactionData::Upgrade(upgrade) => Some(upgrade),
+            _ => None,
+        }
+    }
+
     /// Returns the transaction data, if it exists.
     #[must_use]
     fn data(&self) -> Option<&TransactionData> {
@@ -245,24 +254,43 @@ impl Transaction {
     }
 
     /// Creates a modified clone of this transaction if it contains data for
-    /// deployment, clones all fields except for the bytecode 'bytes' part.
-    /// Returns none if the transaction is not a deployment transaction.
+    /// a deployment or an upgrade, clones all fields except for the bytecode
+    /// 'bytes' part.
+    /// Returns none if the transaction is not a deployment or an upgrade
+    /// transaction.
     #[must_use]
     pub fn strip_off_bytecode(&self) -> Option<Self> {
-        let deploy = self.deploy()?;
-
-        let stripped_deploy = TransactionData::Deploy(ContractDeploy {
-            owner: deploy.owner.clone(),
-            init_args: deploy.init_args.clone(),
-            bytecode: ContractBytecode {
-                hash: deploy.bytecode.hash,
-                bytes: Vec::new(),
-            },
-            nonce: deploy.nonce,
-        });
+        let stripped_data = match self.data()? {
+            TransactionData::Deploy(deploy) => {
+                TransactionData::Deploy(ContractDeploy {
+                    owner: deploy.owner.clone(),
+                    init_args: deploy.init_args.clone(),
+                    bytecode: ContractBytecode {
+                        hash: deploy.bytecode.hash,
+                        bytes: Vec::new(),
+                    },
+                    nonce: deploy.nonce,
+                })
+            }
+            TransactionData::Upgrade(upgrade) => {
+                TransactionData::Upgrade(ContractUpgrade {
+                    contract: upgrade.contract,
+                    chain_id: upgrade.chain_id,
+                    nonce: upgrade.nonce,
+                    prev_hash: upgrade.prev_hash,
+                    bytecode: ContractBytecode {
+                        hash: upgrade.bytecode.hash,
+                        bytes: Vec::new(),
+                    },
+                    migrate_args: upgrade.migrate_args.clone(),
+                    signature: upgrade.signature,
+                })
+            }
+            _ => return None,
+        };
 
         let mut stripped_transaction = self.clone();
-        stripped_transaction.payload.data = Some(stripped_deploy);
+        stripped_transaction.payload.data = Some(stripped_data);
 
         Some(stripped_transaction)
     }
@@ -391,7 +419,7 @@ impl Payload {
 
         bytes.extend(self.nonce.to_bytes());
 
-        // serialize the contract call, deployment or memo, if present.
+        // serialize the contract call, deployment, memo or upgrade, if present.
         match &self.data {
             Some(TransactionData::Call(call)) => {
                 bytes.push(1);
@@ -401,12 +429,24 @@ impl Payload {
                 bytes.push(2);
                 bytes.extend(deploy.to_var_bytes());
             }
-            Some(TransactionData::Memo(memo)) => {
+            Some(TransactionData::Memo(memo))
+                if memo.kind() == MemoKind::Plain =>
+            {
                 bytes.push(3);
-                bytes.extend((memo.len() as u64).to_bytes());
-                bytes.extend(memo);
+                bytes.extend((memo.content().len() as u64).to_bytes());
+                bytes.extend(memo.content());
+            }
+            Some(TransactionData::Memo(memo)) => {
+                bytes.push(5);
+                bytes.push(memo.kind().to_byte());
+                bytes.extend((memo.content().len() as u64).to_bytes());
+                bytes.extend(memo.content());
             }
-            _ => bytes.push(0),
+            Some(TransactionData::Upgrade(upgrade)) => {
+                bytes.push(4);
+                bytes.extend(upgrade.to_var_bytes());
+            }
+            None => bytes.push(0),
         }
 
         bytes
@@ -449,7 +489,7 @@ impl Payload {
 
         let nonce = u64::from_reader(&mut buf)?;
 
-        // deserialize contract call, deploy data, or memo, if present
+        // deserialize contract call, deploy data, memo or upgrade, if present
         let data = match u8::from_reader(&mut buf)? {
             0 => None,
             1 => Some(TransactionData::Call(ContractCall::from_slice(buf)?)),
@@ -465,7 +505,29 @@ impl Payload {
                     return Err(BytesError::InvalidData);
                 }
 
-                let memo = buf[..size].to_vec();
+                let memo = Memo::new(&buf[..size])
+                    .map_err(|_| BytesError::InvalidData)?;
+                Some(TransactionData::Memo(memo))
+            }
+            4 => Some(TransactionData::Upgrade(ContractUpgrade::from_slice(
+                buf,
+            )?)),
+            5 => {
+                // plain memos are only ever encoded untagged
+                let kind = MemoKind::from_byte(u8::from_reader(&mut buf)?)
+                    .filter(|kind| *kind != MemoKind::Plain)
+                    .ok_or(BytesError::InvalidData)?;
+
+                // we only build for 64-bit so this truncation is impossible
+                #[allow(clippy::cast_possible_truncation)]
+                let size = u64::from_reader(&mut buf)? as usize;
+
+                if buf.len() != size {
+                    return Err(BytesError::InvalidData);
+                }
+
+                let memo = Memo::with_kind(kind, buf)
+                    .map_err(|_| BytesError::InvalidData)?;
                 Some(TransactionData::Memo(memo))
             }
             _ => {
@@ -520,7 +582,10 @@ impl Payload {
                 bytes.extend(&c.fn_args);
             }
             Some(TransactionData::Memo(m)) => {
-                bytes.extend(m);
+                bytes.extend(m.to_hash_input_bytes());
+            }
+            Some(TransactionData::Upgrade(u)) => {
+                bytes.extend(u.to_hash_input_bytes());
             }
             None => {}
         }
@@ -12,6 +12,7 @@ use core::fmt::Debug;
 
 use bytecheck::CheckBytes;
 use dusk_bytes::{DeserializableSlice, Error as BytesError, Serializable};
+use dusk_merkle::Aggregate;
 use dusk_poseidon::{Domain, Hash};
 use ff::Field;
 use rand::{CryptoRng, RngCore};
@@ -21,8 +22,8 @@ use crate::signatures::schnorr::{
     SecretKey as SchnorrSecretKey, Signature as SchnorrSignature,
 };
 use crate::transfer::data::{
-    ContractBytecode, ContractCall, ContractDeploy, TransactionData,
-    MAX_MEMO_SIZE,
+    ContractBytecode, ContractCall, ContractDeploy, ContractUpgrade, Memo,
+    MemoKind, TransactionData, MAX_MEMO_SIZE,
 };
 use crate::{BlsScalar, Error, JubJubAffine, JubJubScalar};
 
@@ -73,6 +74,80 @@ impl cmp::PartialOrd for NoteLeaf {
     }
 }
 
+/// Proof that the merkle tree of notes at a past block height is a prefix of
+/// the current tree.
+///
+/// Since the tree is append-only, the tree at the checkpoint's block height
+/// only differs from the current one on the right of its last note. The
+/// opening of that note in the current tree is therefore enough to recompute
+/// the root of the tree at the checkpoint, by replacing the subtrees on the
+/// right of its path with empty ones.
+#[derive(Debug, Clone, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct NoteTreeCheckpoint {
+    /// The block height of the checkpoint.
+    pub block_height: u64,
+    /// The number of notes in the tree at the checkpoint's block height.
+    pub leaves: u64,
+    /// The opening of the last note of the checkpoint in the current tree.
+    pub opening: NoteOpening,
+}
+
+impl NoteTreeCheckpoint {
+    /// The root of the current tree of notes.
+    #[must_use]
+    pub fn current_root(&self) -> BlsScalar {
+        self.opening.root().hash
+    }
+
+    /// The root of the tree of notes at the checkpoint's block height.
+    #[must_use]
+    pub fn root(&self) -> BlsScalar {
+        let branch = self.opening.branch();
+        let mut item =
+            branch[NOTES_TREE_DEPTH - 1][self.position(NOTES_TREE_DEPTH - 1)];
+
+        for height in (0..NOTES_TREE_DEPTH).rev() {
+            let position = self.position(height);
+            let level: [&NoteTreeItem; NOTES_TREE_ARITY] =
+                core::array::from_fn(|i| match i.cmp(&position) {
+                    cmp::Ordering::Less => &branch[height][i],
+                    cmp::Ordering::Equal => &item,
+                    cmp::Ordering::Greater => &NoteTreeItem::EMPTY_SUBTREE,
+                });
+            let parent = NoteTreeItem::aggregate(level);
+            item = parent;
+        }
+
+        item.hash
+    }
+
+    /// Verify the checkpoint links the given current root of the tree of
+    /// notes to the given root at the checkpoint's block height.
+    #[must_use]
+    pub fn verify(&self, current_root: &BlsScalar, root: &BlsScalar) -> bool {
+        if self.leaves == 0 {
+            return false;
+        }
+
+        let leaf = self.opening.branch()[NOTES_TREE_DEPTH - 1]
+            [self.position(NOTES_TREE_DEPTH - 1)];
+
+        self.opening.verify(leaf)
+            && self.current_root() == *current_root
+            && self.root() == *root
+    }
+
+    /// The position in the given level of the branch of the path to the last
+    /// note of the checkpoint, with level `0` being right below the root.
+    #[allow(clippy::cast_possible_truncation)]
+    fn position(&self, height: usize) -> usize {
+        let depth = (NOTES_TREE_DEPTH - 1 - height) as u32;
+        let arity = NOTES_TREE_ARITY as u64;
+        ((self.leaves - 1) / arity.pow(depth) % arity) as usize
+    }
+}
+
 /// Label used for the ZK transcript initialization. Must be the same for prover
 /// and verifier.
 pub const TRANSCRIPT_LABEL: &[u8] = b"dusk-network";
@@ -442,13 +517,22 @@ impl Transaction {
 
     /// Returns the memo used with the transaction, if any.
     #[must_use]
-    pub fn memo(&self) -> Option<&[u8]> {
+    pub fn memo(&self) -> Option<&Memo> {
         match self.data()? {
             TransactionData::Memo(memo) => Some(memo),
             _ => None,
         }
     }
 
+    /// Return the contract upgrade data, if there is any.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<&ContractUpgrade> {
+        match self.data()? {
+            TransactionData::Upgrade(upgrade) => Some(upgrade),
+            _ => None,
+        }
+    }
+
     /// Returns the transaction data, if it exists.
     #[must_use]
     fn data(&self) -> Option<&TransactionData> {
@@ -456,24 +540,43 @@ impl Transaction {
     }
 
     /// Creates a modified clone of this transaction if it contains data for
-    /// deployment, clones all fields except for the bytecode' 'bytes' part.
-    /// Returns none if the transaction is not a deployment transaction.
+    /// a deployment or an upgrade, clones all fields except for the bytecode
+    /// 'bytes' part.
+    /// Returns none if the transaction is not a deployment or an upgrade
+    /// transaction.
     #[must_use]
     pub fn strip_off_bytecode(&self) -> Option<Self> {
-        let deploy = self.deploy()?;
-
-        let stripped_deploy = TransactionData::Deploy(ContractDeploy {
-            owner: deploy.owner.clone(),
-            init_args: deploy.init_args.clone(),
-            bytecode: ContractBytecode {
-                hash: deploy.bytecode.hash,
-                bytes: Vec::new(),
-            },
-            nonce: deploy.nonce,
-        });
+        let stripped_data = match self.data()? {
+            TransactionData::Deploy(deploy) => {
+                TransactionData::Deploy(ContractDeploy {
+                    owner: deploy.owner.clone(),
+                    init_args: deploy.init_args.clone(),
+                    bytecode: ContractBytecode {
+                        hash: deploy.bytecode.hash,
+                        bytes: Vec::new(),
+                    },
+                    nonce: deploy.nonce,
+                })
+            }
+            TransactionData::Upgrade(upgrade) => {
+                TransactionData::Upgrade(ContractUpgrade {
+                    contract: upgrade.contract,
+                    chain_id: upgrade.chain_id,
+                    nonce: upgrade.nonce,
+                    prev_hash: upgrade.prev_hash,
+                    bytecode: ContractBytecode {
+                        hash: upgrade.bytecode.hash,
+                        bytes: Vec::new(),
+                    },
+                    migrate_args: upgrade.migrate_args.clone(),
+                    signature: upgrade.signature,
+                })
+            }
+            _ => return None,
+        };
 
         let mut stripped_transaction = self.clone();
-        stripped_transaction.payload.data = Some(stripped_deploy);
+        stripped_transaction.payload.data = Some(stripped_data);
 
         Some(stripped_transaction)
     }
@@ -649,7 +752,7 @@ impl Payload {
         // serialize the fee
         bytes.extend(self.fee.to_bytes());
 
-        // serialize the contract call, deployment or memo, if present.
+        // serialize the contract call, deployment, memo or upgrade, if present.
         match &self.data {
             Some(TransactionData::Call(call)) => {
                 bytes.push(1);
@@ -659,12 +762,24 @@ impl Payload {
                 bytes.push(2);
                 bytes.extend(deploy.to_var_bytes());
             }
-            Some(TransactionData::Memo(memo)) => {
+            Some(TransactionData::Memo(memo))
+                if memo.kind() == MemoKind::Plain =>
+            {
                 bytes.push(3);
-                bytes.extend((memo.len() as u64).to_bytes());
-                bytes.extend(memo);
+                bytes.extend((memo.content().len() as u64).to_bytes());
+                bytes.extend(memo.content());
+            }
+            Some(TransactionData::Memo(memo)) => {
+                bytes.push(5);
+                bytes.push(memo.kind().to_byte());
+                bytes.extend((memo.content().len() as u64).to_bytes());
+                bytes.extend(memo.content());
             }
-            _ => bytes.push(0),
+            Some(TransactionData::Upgrade(upgrade)) => {
+                bytes.push(4);
+                bytes.extend(upgrade.to_var_bytes());
+            }
+            None => bytes.push(0),
         }
 
         bytes
@@ -689,7 +804,7 @@ impl Payload {
         // deserialize fee
         let fee = Fee::from_reader(&mut buf)?;
 
-        // deserialize contract call, deploy data, or memo, if present
+        // deserialize contract call, deploy data, memo or upgrade, if present
         let data = match u8::from_reader(&mut buf)? {
             0 => None,
             1 => Some(TransactionData::Call(ContractCall::from_slice(buf)?)),
@@ -705,7 +820,29 @@ impl Payload {
                     return Err(BytesError::InvalidData);
                 }
 
-                let memo = buf[..size].to_vec();
+                let memo = Memo::new(&buf[..size])
+                    .map_err(|_| BytesError::InvalidData)?;
+                Some(TransactionData::Memo(memo))
+            }
+            4 => Some(TransactionData::Upgrade(ContractUpgrade::from_slice(
+                buf,
+            )?)),
+            5 => {
+                // plain memos are only ever encoded untagged
+                let kind = MemoKind::from_byte(u8::from_reader(&mut buf)?)
+                    .filter(|kind| *kind != MemoKind::Plain)
+                    .ok_or(BytesError::InvalidData)?;
+
+                // we only build for 64-bit so this truncation is impossible
+                #[allow(clippy::cast_possible_truncation)]
+                let size = u64::from_reader(&mut buf)? as usize;
+
+                if buf.len() != size {
+                    return Err(BytesError::InvalidData);
+                }
+
+                let memo = Memo::with_kind(kind, buf)
+                    .map_err(|_| BytesError::InvalidData)?;
                 Some(TransactionData::Memo(memo))
             }
             _ => {
@@ -745,7 +882,10 @@ impl Payload {
                 bytes.extend(&c.fn_args);
             }
             Some(TransactionData::Memo(m)) => {
-                bytes.extend(m);
+                bytes.extend(m.to_hash_input_bytes());
+            }
+            Some(TransactionData::Upgrade(u)) => {
+                bytes.extend(u.to_hash_input_bytes());
             }
             None => {}
         }
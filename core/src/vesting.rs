@@ -0,0 +1,157 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Types used by the reference vesting contract.
+//!
+//! A grantor locks Dusk in the vesting contract for a beneficiary, to be
+//! released on a [`VestingSchedule`] of block heights. A revocable grant can be
+//! revoked by its grantor, which returns the part that isn't vested yet.
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::signatures::bls::PublicKey as AccountPublicKey;
+
+/// Topic of the event emitted when a grant is created.
+pub const GRANT_TOPIC: &str = "grant";
+
+/// Topic of the event emitted when vested funds are released.
+pub const RELEASE_TOPIC: &str = "release";
+
+/// Topic of the event emitted when a grant is revoked.
+pub const REVOKE_TOPIC: &str = "revoke";
+
+/// The identifier of a grant.
+pub type GrantId = u64;
+
+/// A schedule releasing funds linearly from the `start` height over
+/// `duration` blocks, with nothing released before the end of the cliff.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize,
+)]
+#[archive_attr(derive(CheckBytes))]
+pub struct VestingSchedule {
+    /// The block height at which vesting starts.
+    pub start: u64,
+    /// The number of blocks after the start during which nothing is vested.
+    pub cliff: u64,
+    /// The number of blocks after the start at which everything is vested.
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    /// The part of the given total that is vested at the given block height.
+    #[must_use]
+    pub fn vested(&self, total: u64, height: u64) -> u64 {
+        let elapsed = height.saturating_sub(self.start);
+
+        if height < self.start || elapsed < self.cliff {
+            return 0;
+        }
+        if elapsed >= self.duration {
+            return total;
+        }
+
+        let vested =
+            u128::from(total) * u128::from(elapsed) / u128::from(self.duration);
+        u64::try_from(vested).unwrap_or(total)
+    }
+}
+
+/// Create a grant for a beneficiary, locking the deposit of the calling
+/// transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct CreateGrant {
+    /// The account receiving the vested funds.
+    pub beneficiary: AccountPublicKey,
+    /// The total value of the grant, in Lux, which should match the deposit
+    /// of the transaction.
+    pub value: u64,
+    /// The schedule on which the funds are vested.
+    pub schedule: VestingSchedule,
+    /// Whether the grantor can revoke the grant.
+    pub revocable: bool,
+}
+
+/// Data stored for every grant.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct GrantData {
+    /// The account that created the grant.
+    pub grantor: AccountPublicKey,
+    /// The account receiving the vested funds.
+    pub beneficiary: AccountPublicKey,
+    /// The schedule on which the funds are vested.
+    pub schedule: VestingSchedule,
+    /// The total value of the grant, in Lux.
+    ///
+    /// When the grant is revoked, this is reduced to the value vested at the
+    /// time of revocation.
+    pub total: u64,
+    /// The value already released to the beneficiary, in Lux.
+    pub released: u64,
+    /// Whether the grantor can revoke the grant.
+    pub revocable: bool,
+    /// Whether the grant was revoked.
+    pub revoked: bool,
+}
+
+impl GrantData {
+    /// The value vested at the given block height, in Lux.
+    #[must_use]
+    pub fn vested(&self, height: u64) -> u64 {
+        if self.revoked {
+            return self.total;
+        }
+        self.schedule.vested(self.total, height)
+    }
+
+    /// The value that can be released to the beneficiary at the given block
+    /// height, in Lux.
+    #[must_use]
+    pub fn releasable(&self, height: u64) -> u64 {
+        self.vested(height) - self.released
+    }
+}
+
+/// Event data emitted when a grant is created.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct GrantEvent {
+    /// The grant created.
+    pub grant_id: GrantId,
+    /// The account that created the grant.
+    pub grantor: AccountPublicKey,
+    /// The account receiving the vested funds.
+    pub beneficiary: AccountPublicKey,
+    /// The total value of the grant, in Lux.
+    pub value: u64,
+}
+
+/// Event data emitted when vested funds are released to the beneficiary.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct ReleaseEvent {
+    /// The grant the funds are released from.
+    pub grant_id: GrantId,
+    /// The account receiving the funds.
+    pub beneficiary: AccountPublicKey,
+    /// The value released, in Lux.
+    pub value: u64,
+}
+
+/// Event data emitted when a grant is revoked.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct RevokeEvent {
+    /// The grant revoked.
+    pub grant_id: GrantId,
+    /// The account that created the grant.
+    pub grantor: AccountPublicKey,
+    /// The value that wasn't vested, returned to the grantor, in Lux.
+    pub value: u64,
+}
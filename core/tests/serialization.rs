@@ -8,7 +8,8 @@ use dusk_core::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
 };
 use dusk_core::transfer::data::{
-    ContractBytecode, ContractCall, ContractDeploy, TransactionData,
+    ContractBytecode, ContractCall, ContractDeploy, ContractUpgrade, Memo,
+    MemoKind, TransactionData, MEMO_TAG_SIZE,
 };
 use dusk_core::transfer::phoenix::{
     Note, NoteTreeItem, NotesTree, Prove, PublicKey as PhoenixPublicKey,
@@ -251,7 +252,7 @@ fn phoenix_with_memo() -> Result<(), Error> {
     let memo = vec![1u8; 512];
 
     let transaction =
-        new_phoenix_tx(&mut rng, Some(TransactionData::Memo(memo)));
+        new_phoenix_tx(&mut rng, Some(TransactionData::Memo(Memo::new(memo)?)));
 
     let transaction_bytes = transaction.to_var_bytes();
     let deserialized = Transaction::from_slice(&transaction_bytes)?;
@@ -339,6 +340,72 @@ fn moonlight_with_deploy() -> Result<(), Error> {
     Ok(())
 }
 
+fn new_upgrade<R: RngCore + CryptoRng>(rng: &mut R) -> ContractUpgrade {
+    let mut hash = [0; 32];
+    rng.fill_bytes(&mut hash);
+    let mut bytes = vec![0; 100];
+    rng.fill_bytes(&mut bytes);
+    let bytecode = ContractBytecode { hash, bytes };
+
+    let mut prev_hash = [0; 32];
+    rng.fill_bytes(&mut prev_hash);
+
+    let mut migrate_args = vec![0; 20];
+    rng.fill_bytes(&mut migrate_args);
+
+    let owner_sk = AccountSecretKey::random(rng);
+    let mut upgrade = ContractUpgrade {
+        contract: [42; 32].into(),
+        chain_id: 0xFA,
+        nonce: rng.next_u64(),
+        prev_hash,
+        bytecode,
+        migrate_args: Some(migrate_args),
+        signature: Default::default(),
+    };
+    upgrade.signature = owner_sk.sign(&upgrade.signature_message());
+
+    upgrade
+}
+
+#[test]
+fn phoenix_with_upgrade() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let upgrade = new_upgrade(&mut rng);
+    let transaction =
+        new_phoenix_tx(&mut rng, Some(TransactionData::Upgrade(upgrade)));
+
+    let transaction_bytes = transaction.to_var_bytes();
+    let deserialized = Transaction::from_slice(&transaction_bytes)?;
+
+    assert_eq!(transaction, deserialized);
+
+    let stripped = transaction.strip_off_bytecode().unwrap();
+    assert!(stripped.upgrade().unwrap().bytecode.bytes.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn moonlight_with_upgrade() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let upgrade = new_upgrade(&mut rng);
+    let transaction =
+        new_moonlight_tx(&mut rng, Some(TransactionData::Upgrade(upgrade)));
+
+    let transaction_bytes = transaction.to_var_bytes();
+    let deserialized = Transaction::from_slice(&transaction_bytes)?;
+
+    assert_eq!(transaction, deserialized);
+
+    let stripped = transaction.strip_off_bytecode().unwrap();
+    assert!(stripped.upgrade().unwrap().bytecode.bytes.is_empty());
+
+    Ok(())
+}
+
 #[test]
 fn moonlight_with_memo() -> Result<(), Error> {
     let mut rng = StdRng::seed_from_u64(42);
@@ -356,6 +423,27 @@ fn moonlight_with_memo() -> Result<(), Error> {
 
     let memo = vec![1u8; 512];
 
+    let transaction = new_moonlight_tx(
+        &mut rng,
+        Some(TransactionData::Memo(Memo::new(memo)?)),
+    );
+
+    let transaction_bytes = transaction.to_var_bytes();
+    let deserialized = Transaction::from_slice(&transaction_bytes)?;
+
+    assert_eq!(transaction, deserialized);
+
+    Ok(())
+}
+
+#[test]
+fn moonlight_with_tagged_memo() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut hash = [0; 32];
+    rng.fill_bytes(&mut hash);
+    let memo = Memo::with_kind(MemoKind::HashCommitment, &hash)?;
+
     let transaction =
         new_moonlight_tx(&mut rng, Some(TransactionData::Memo(memo)));
 
@@ -364,6 +452,31 @@ fn moonlight_with_memo() -> Result<(), Error> {
 
     assert_eq!(transaction, deserialized);
 
+    let memo = deserialized.memo().expect("the memo should be included");
+    assert_eq!(memo.kind(), MemoKind::HashCommitment);
+    assert_eq!(memo.content(), hash);
+    assert_eq!(memo.len(), hash.len() + MEMO_TAG_SIZE);
+
+    Ok(())
+}
+
+#[test]
+fn moonlight_with_memo_resembling_tag() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // a plain memo starting like the tagged memos of an in-band encoding
+    let bytes = [0xff, MemoKind::Encrypted.to_byte(), 1, 2, 3];
+    let transaction = new_moonlight_tx(&mut rng, Some(bytes.to_vec().into()));
+
+    let transaction_bytes = transaction.to_var_bytes();
+    let deserialized = Transaction::from_slice(&transaction_bytes)?;
+
+    assert_eq!(transaction, deserialized);
+
+    let memo = deserialized.memo().expect("the memo should be included");
+    assert_eq!(memo.kind(), MemoKind::Plain);
+    assert_eq!(memo.content(), bytes);
+
     Ok(())
 }
 
@@ -378,3 +491,39 @@ fn nonsense_bytes_fails() -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[cfg(feature = "proto")]
+#[test]
+fn moonlight_proto() -> Result<(), Error> {
+    use dusk_core::proto::{self, Message};
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let call = ContractCall {
+        contract: [1; 32].into(),
+        fn_name: String::from("deposit"),
+        fn_args: vec![2; 100],
+    };
+    let transaction =
+        new_moonlight_tx(&mut rng, Some(TransactionData::Call(call.clone())));
+
+    let bytes = proto::Transaction::from(&transaction).encode_to_vec();
+    let message = proto::Transaction::decode(bytes.as_slice())
+        .expect("decoding the message should succeed");
+
+    assert_eq!(message.id, transaction.hash().to_bytes().to_vec());
+    assert_eq!(message.kind, proto::TransactionKind::Moonlight as i32);
+    assert_eq!(message.gas_limit, transaction.gas_limit());
+    assert_eq!(message.value, transaction.value());
+    assert!(message.nullifiers.is_empty());
+
+    let message_call = message.call.clone().expect("the call should be set");
+    assert_eq!(message_call.contract, call.contract.as_bytes().to_vec());
+    assert_eq!(message_call.fn_name, call.fn_name);
+    assert_eq!(message_call.fn_args, call.fn_args);
+
+    let decoded = Transaction::try_from(&message)?;
+    assert_eq!(transaction, decoded);
+
+    Ok(())
+}
@@ -7,7 +7,9 @@
 use dusk_core::signatures::bls::{
     PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
 };
-use dusk_core::transfer::data::{ContractCall, TransactionData, MAX_MEMO_SIZE};
+use dusk_core::transfer::data::{
+    ContractCall, Memo, MemoKind, TransactionData, MAX_MEMO_SIZE, MEMO_TAG_SIZE,
+};
 use dusk_core::transfer::phoenix::{
     Note, NoteOpening, NoteTreeItem, NotesTree, Prove,
     PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey, TxCircuitVec,
@@ -289,6 +291,18 @@ fn phoenix_memo_too_large() {
     );
 }
 
+// Checked memo data fails up front when memo is too large.
+#[test]
+fn memo_data_too_large() {
+    const MEMO_SIZE: usize = MAX_MEMO_SIZE + 1;
+
+    assert!(TransactionData::new_memo(vec![1; MAX_MEMO_SIZE]).is_ok());
+    assert_eq!(
+        TransactionData::new_memo(vec![1; MEMO_SIZE]).unwrap_err(),
+        Error::MemoTooLarge(MEMO_SIZE)
+    );
+}
+
 fn new_moonlight_tx(
     data: Option<impl Into<TransactionData>>,
 ) -> Result<Transaction, Error> {
@@ -336,3 +350,25 @@ fn moonlight_memo_too_large() {
         Error::MemoTooLarge(MEMO_SIZE)
     );
 }
+
+// A tagged memo counts its tag towards the maximum size.
+#[test]
+fn tagged_memo_too_large() {
+    let content = vec![1; MAX_MEMO_SIZE];
+
+    assert_eq!(
+        Memo::with_kind(MemoKind::Encrypted, &content).unwrap_err(),
+        Error::MemoTooLarge(MAX_MEMO_SIZE + MEMO_TAG_SIZE)
+    );
+}
+
+// Plain memos are never tagged, whatever bytes they start with.
+#[test]
+fn plain_memo_untagged() {
+    let content = [0xff, MemoKind::Encrypted.to_byte(), 2];
+    let memo = Memo::with_kind(MemoKind::Plain, &content).unwrap();
+    assert_eq!(memo, Memo::new(content).unwrap());
+    assert_eq!(memo.kind(), MemoKind::Plain);
+    assert_eq!(memo.content(), content);
+    assert_eq!(memo.len(), content.len());
+}
@@ -13,14 +13,14 @@ use aes::Aes256;
 use base64::engine::general_purpose::STANDARD as BASE64_ENGINE;
 use base64::Engine;
 use block_modes::block_padding::Pkcs7;
-use block_modes::{BlockMode, BlockModeError, Cbc};
+use block_modes::{BlockMode, BlockModeError, Cbc, InvalidKeyIvLength};
 use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_core::signatures::bls::{
     PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
 };
 use rand::rngs::StdRng;
-use rand::SeedableRng;
-use serde::Serialize;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tracing::warn;
 pub const PUBLIC_BLS_SIZE: usize = BlsPublicKey::SIZE;
@@ -105,10 +105,34 @@ impl std::fmt::Debug for PublicKey {
         f.debug_struct("PublicKey").field("bs58", &bs).finish()
     }
 }
+impl Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.as_bytes.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let bytes = PublicKeyBytes::deserialize(deserializer)?;
+        Self::try_from(bytes.0).map_err(|e| {
+            serde::de::Error::custom(format!("invalid public key: {e:?}"))
+        })
+    }
+}
+
 /// A wrapper of 96-sized array
-#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct PublicKeyBytes(
-    #[serde(serialize_with = "crate::serialize_b58")] pub [u8; PUBLIC_BLS_SIZE],
+    #[serde(
+        serialize_with = "crate::serialize_b58",
+        deserialize_with = "crate::deserialize_b58"
+    )]
+    pub [u8; PUBLIC_BLS_SIZE],
 );
 
 impl Default for PublicKeyBytes {
@@ -152,20 +176,44 @@ pub fn load_keys(
     Ok((sk, PublicKey::new(pk)))
 }
 
+/// Saves consensus keys to a file encrypted with the given password, in the
+/// format read by [`load_keys`].
+pub fn save_keys<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    path: PathBuf,
+    sk: &BlsSecretKey,
+    pk: &BlsPublicKey,
+    pwd: &str,
+) -> anyhow::Result<()> {
+    let keys = BlsKeyPair {
+        secret_key_bls: BASE64_ENGINE.encode(sk.to_bytes()),
+        public_key_bls: BASE64_ENGINE.encode(pk.to_bytes()),
+    };
+    let bytes = serde_json::to_vec(&keys)?;
+
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut iv);
+    let ciphertext = encrypt(&bytes, &hash_pwd(pwd), &iv)
+        .map_err(|e| anyhow::anyhow!("Cannot encrypt consensus keys {e}"))?;
+
+    fs::write(&path, ciphertext)
+        .map_err(|e| anyhow::anyhow!("Cannot write {} {e}", path.display()))?;
+
+    Ok(())
+}
+
+/// Bls key pair helper structure
+#[derive(Serialize, Deserialize)]
+struct BlsKeyPair {
+    secret_key_bls: String,
+    public_key_bls: String,
+}
+
 /// Fetches BLS public and secret keys from an encrypted consensus keys file.
 fn read_from_file(
     path: PathBuf,
     pwd: &str,
 ) -> anyhow::Result<(BlsPublicKey, BlsSecretKey)> {
-    use serde::Deserialize;
-
-    /// Bls key pair helper structure
-    #[derive(Deserialize)]
-    struct BlsKeyPair {
-        secret_key_bls: String,
-        public_key_bls: String,
-    }
-
     // attempt to load and decode wallet
     let ciphertext = fs::read(&path).map_err(|e| {
         anyhow::anyhow!(
@@ -174,9 +222,7 @@ fn read_from_file(
         )
     })?;
 
-    let mut hasher = Sha256::new();
-    hasher.update(pwd.as_bytes());
-    let hashed_pwd = hasher.finalize().to_vec();
+    let hashed_pwd = hash_pwd(pwd);
 
     let bytes = match decrypt(&ciphertext[..], &hashed_pwd) {
         Ok(bytes) => bytes,
@@ -210,8 +256,25 @@ fn read_from_file(
     Ok((pk, sk))
 }
 
+type Aes256Cbc = Cbc<Aes256, Pkcs7>;
+
+fn hash_pwd(pwd: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(pwd.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+fn encrypt(
+    data: &[u8],
+    pwd: &[u8],
+    iv: &[u8; 16],
+) -> Result<Vec<u8>, InvalidKeyIvLength> {
+    let cipher = Aes256Cbc::new_from_slices(pwd, iv)?;
+    let enc = cipher.encrypt_vec(data);
+    Ok(iv.iter().copied().chain(enc).collect())
+}
+
 fn decrypt(data: &[u8], pwd: &[u8]) -> Result<Vec<u8>, BlockModeError> {
-    type Aes256Cbc = Cbc<Aes256, Pkcs7>;
     let iv = &data[..16];
     let enc = &data[16..];
 
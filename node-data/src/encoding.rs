@@ -64,6 +64,30 @@ impl Serializable for Block {
 
         Block::new(header, txs, faults)
     }
+
+    fn read_slice(buf: &mut &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let header = Header::read(buf)?;
+
+        // Read transactions count, bounding the capacity by the smallest
+        // transaction encoding that could fit in the slice
+        let tx_len = Self::read_u32_le(buf)? as usize;
+        let mut txs = Vec::with_capacity(tx_len.min(buf.len() / 12));
+        for _ in 0..tx_len {
+            txs.push(Transaction::read_slice(buf)?);
+        }
+
+        // Read faults count
+        let faults_len = Self::read_u32_le(buf)?;
+
+        let faults = (0..faults_len)
+            .map(|_| Fault::read(buf))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Block::new(header, txs, faults)
+    }
 }
 
 impl Serializable for Transaction {
@@ -90,15 +114,35 @@ impl Serializable for Transaction {
         let tx_type = Self::read_u32_le(r)?;
 
         let protocol_tx = Self::read_var_le_bytes32(r)?;
-        let tx_size = protocol_tx.len();
-        let inner = ProtocolTransaction::from_slice(&protocol_tx[..])
+        Self::from_protocol_bytes(version, tx_type, &protocol_tx)
+    }
+
+    fn read_slice(buf: &mut &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let version = Self::read_u32_le(buf)?;
+        let tx_type = Self::read_u32_le(buf)?;
+
+        let protocol_tx = Self::read_var_le_slice32(buf)?;
+        Self::from_protocol_bytes(version, tx_type, protocol_tx)
+    }
+}
+
+impl Transaction {
+    fn from_protocol_bytes(
+        version: u32,
+        tx_type: u32,
+        protocol_tx: &[u8],
+    ) -> io::Result<Self> {
+        let inner = ProtocolTransaction::from_slice(protocol_tx)
             .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
 
         Ok(Self {
             inner,
             version,
             r#type: tx_type,
-            size: Some(tx_size),
+            size: Some(protocol_tx.len()),
         })
     }
 }
@@ -116,7 +160,7 @@ impl Serializable for SpentTransaction {
                 w.write_all(b)?;
             }
             None => {
-                w.write_all(&0_u64.to_le_bytes())?;
+                w.write_all(&0_u32.to_le_bytes())?;
             }
         }
 
@@ -137,7 +181,10 @@ impl Serializable for SpentTransaction {
             let mut buf = vec![0u8; error_len as usize];
             r.read_exact(&mut buf[..])?;
 
-            Some(String::from_utf8(buf).expect("Cannot from_utf8"))
+            let err = String::from_utf8(buf).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "Invalid error")
+            })?;
+            Some(err)
         } else {
             None
         };
@@ -462,6 +509,11 @@ mod tests {
         obj.write(&mut buf).expect("should be writable");
 
         assert!(obj.eq(&S::read(&mut &buf[..]).expect("should be readable")));
+
+        let mut slice = &buf[..];
+        let dup = S::read_slice(&mut slice).expect("should be readable");
+        assert!(obj.eq(&dup));
+        assert!(slice.is_empty(), "the whole slice should be read");
     }
 
     #[test]
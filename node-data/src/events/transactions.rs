@@ -33,7 +33,9 @@ use crate::ledger::{Hash, SpentTransaction, Transaction};
 ///     (including logic that triggers panics)
 ///
 ///     - A "successful" transaction: executed and the `err` field is `None`.
-///     - A "failed" transaction: executed and the `err` field is `Some`.
+///     - A "failed" transaction: executed and the `err` field is `Some`. If the
+///       transaction failed with `abi::fail`, the `err_code` field carries the
+///       code of the failure.
 #[derive(Clone, Debug)]
 pub enum TransactionEvent<'t> {
     Removed(Hash),
@@ -54,8 +56,8 @@ impl EventSource for TransactionEvent<'_> {
     fn data(&self) -> Option<serde_json::Value> {
         match self {
             Self::Removed(_) => None,
-            Self::Executed(t) => serde_json::to_value(t).ok(),
-            Self::Included(t) => serde_json::to_value(t).ok(),
+            Self::Executed(t) => serde_json::to_value(Spent(t)).ok(),
+            Self::Included(t) => serde_json::to_value(Described(t)).ok(),
         }
     }
     fn entity(&self) -> String {
@@ -71,94 +73,212 @@ use base64::engine::general_purpose::STANDARD as BASE64_ENGINE;
 use base64::Engine;
 use dusk_bytes::Serializable;
 use dusk_core::transfer::Transaction as ProtocolTransaction;
+use serde::de::{Deserialize, Deserializer, Error as _};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+/// The descriptive layout of a transaction, without its `raw` encoding.
+///
+/// Used by the transaction events, so that subscribers don't receive the full
+/// encoding of every transaction, which is only part of the canonical JSON of
+/// a [`Transaction`].
+struct Described<'a>(&'a Transaction);
+
+/// A [`SpentTransaction`] with the descriptive layout of its transaction.
+struct Spent<'a>(&'a SpentTransaction);
+
 impl Serialize for Transaction {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         let mut state = serializer.serialize_struct("Transaction", 1)?;
-        match &self.inner {
-            ProtocolTransaction::Phoenix(p) => {
-                state.serialize_field("type", "phoenix")?;
-
-                let root = p.root().to_bytes();
-                state.serialize_field("root", &hex::encode(root))?;
-
-                let nullifiers: Vec<_> = p
-                    .nullifiers()
-                    .iter()
-                    .map(|n| hex::encode(n.to_bytes()))
-                    .collect();
-                state.serialize_field("nullifiers", &nullifiers)?;
-            }
-            ProtocolTransaction::Moonlight(m) => {
-                state.serialize_field("type", "moonlight")?;
+        serialize_described(self, &mut state)?;
+        state
+            .serialize_field("raw", &hex::encode(self.inner.to_var_bytes()))?;
+        state.end()
+    }
+}
 
-                let sender = m.sender();
-                let sender = bs58::encode(sender.to_bytes()).into_string();
-                state.serialize_field("sender", &sender)?;
+impl Serialize for Described<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Transaction", 1)?;
+        serialize_described(self.0, &mut state)?;
+        state.end()
+    }
+}
 
-                let receiver = m.receiver().map(|receiver| {
-                    bs58::encode(receiver.to_bytes()).into_string()
-                });
-                state.serialize_field("receiver", &receiver)?;
+/// Serializes the fields of the descriptive layout of a transaction.
+fn serialize_described<S>(
+    tx: &Transaction,
+    state: &mut S,
+) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+{
+    match &tx.inner {
+        ProtocolTransaction::Phoenix(p) => {
+            state.serialize_field("type", "phoenix")?;
 
-                state.serialize_field("value", &m.value())?;
+            let root = p.root().to_bytes();
+            state.serialize_field("root", &hex::encode(root))?;
 
-                state.serialize_field("nonce", &m.nonce())?;
-            }
+            let nullifiers: Vec<_> = p
+                .nullifiers()
+                .iter()
+                .map(|n| hex::encode(n.to_bytes()))
+                .collect();
+            state.serialize_field("nullifiers", &nullifiers)?;
         }
+        ProtocolTransaction::Moonlight(m) => {
+            state.serialize_field("type", "moonlight")?;
 
-        let tx = &self.inner;
+            let sender = m.sender();
+            let sender = bs58::encode(sender.to_bytes()).into_string();
+            state.serialize_field("sender", &sender)?;
 
-        state.serialize_field("deposit", &tx.deposit())?;
+            let receiver = m.receiver().map(|receiver| {
+                bs58::encode(receiver.to_bytes()).into_string()
+            });
+            state.serialize_field("receiver", &receiver)?;
 
-        let notes: Vec<Note> = tx.outputs().iter().map(|n| n.into()).collect();
+            state.serialize_field("value", &m.value())?;
 
-        if !notes.is_empty() {
-            state.serialize_field("outputs", &notes)?;
+            state.serialize_field("nonce", &m.nonce())?;
         }
+    }
 
-        let fee = {
-            let mut fee = HashMap::new();
-            fee.insert("gas_limit", tx.gas_limit().to_string());
-            fee.insert("gas_price", tx.gas_price().to_string());
-
-            let encoded_address = match tx.refund_address() {
-                RefundAddress::Phoenix(address) => {
-                    bs58::encode(address.to_bytes()).into_string()
-                }
-                RefundAddress::Moonlight(address) => {
-                    bs58::encode(address.to_bytes()).into_string()
-                }
-            };
-            fee.insert("refund_address", encoded_address);
-            if let ProtocolTransaction::Phoenix(tx) = tx {
-                fee.insert(
-                    "phoenix sender",
-                    hex::encode(tx.sender().to_bytes()),
-                );
-            }
+    let tx = &tx.inner;
+
+    state.serialize_field("deposit", &tx.deposit())?;
+
+    let notes: Vec<Note> = tx.outputs().iter().map(|n| n.into()).collect();
+
+    if !notes.is_empty() {
+        state.serialize_field("outputs", &notes)?;
+    }
 
-            fee
+    let fee = {
+        let mut fee = HashMap::new();
+        fee.insert("gas_limit", tx.gas_limit().to_string());
+        fee.insert("gas_price", tx.gas_price().to_string());
+
+        let encoded_address = match tx.refund_address() {
+            RefundAddress::Phoenix(address) => {
+                bs58::encode(address.to_bytes()).into_string()
+            }
+            RefundAddress::Moonlight(address) => {
+                bs58::encode(address.to_bytes()).into_string()
+            }
         };
+        fee.insert("refund_address", encoded_address);
+        if let ProtocolTransaction::Phoenix(tx) = tx {
+            fee.insert("phoenix sender", hex::encode(tx.sender().to_bytes()));
+        }
 
-        state.serialize_field("fee", &fee)?;
+        fee
+    };
 
-        let call = tx.call().map(|c| {
-            let mut call = HashMap::new();
-            call.insert("contract", hex::encode(c.contract));
-            call.insert("fn_name", c.fn_name.to_string());
-            call.insert("fn_args", BASE64_ENGINE.encode(&c.fn_args));
-            call
-        });
-        state.serialize_field("call", &call)?;
+    state.serialize_field("fee", &fee)?;
 
-        state.serialize_field("is_deploy", &tx.deploy().is_some())?;
-        state.serialize_field("memo", &tx.memo().map(hex::encode))?;
-        state.end()
+    let call = tx.call().map(|c| {
+        let mut call = HashMap::new();
+        call.insert("contract", hex::encode(c.contract));
+        call.insert("fn_name", c.fn_name.to_string());
+        call.insert("fn_args", BASE64_ENGINE.encode(&c.fn_args));
+        call
+    });
+    state.serialize_field("call", &call)?;
+
+    state.serialize_field("is_deploy", &tx.deploy().is_some())?;
+    state.serialize_field(
+        "memo",
+        &tx.memo().map(|memo| hex::encode(memo.content())),
+    )?;
+    Ok(())
+}
+
+/// Only the `raw` field is read, the other ones being derived from it.
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Raw {
+            raw: String,
+        }
+
+        let Raw { raw } = Raw::deserialize(deserializer)?;
+        let raw = hex::decode(raw).map_err(D::Error::custom)?;
+        let inner = ProtocolTransaction::from_slice(&raw).map_err(|e| {
+            D::Error::custom(format!("invalid transaction: {e:?}"))
+        })?;
+        Ok(inner.into())
+    }
+}
+
+impl Serialize for SpentTransaction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_spent(self, &self.inner, serializer)
+    }
+}
+
+impl Serialize for Spent<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_spent(self.0, &Described(&self.0.inner), serializer)
+    }
+}
+
+/// Serializes a spent transaction, with the given layout of its transaction.
+fn serialize_spent<S, T>(
+    spent: &SpentTransaction,
+    inner: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut state = serializer.serialize_struct("SpentTransaction", 5)?;
+    state.serialize_field("inner", inner)?;
+    state.serialize_field("block_height", &spent.block_height)?;
+    state.serialize_field("gas_spent", &spent.gas_spent)?;
+    state.serialize_field("err", &spent.err)?;
+
+    let err_code = spent.failure().map(|failure| failure.code);
+    state.serialize_field("err_code", &err_code)?;
+    state.end()
+}
+
+impl<'de> Deserialize<'de> for SpentTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Spent {
+            inner: Transaction,
+            block_height: u64,
+            gas_spent: u64,
+            err: Option<String>,
+        }
+
+        let spent = Spent::deserialize(deserializer)?;
+        Ok(Self {
+            inner: spent.inner,
+            block_height: spent.block_height,
+            gas_spent: spent.gas_spent,
+            err: spent.err,
+        })
     }
 }
 
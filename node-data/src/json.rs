@@ -0,0 +1,112 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Canonical JSON layout of the ledger types and consensus messages.
+//!
+//! The blocks, transactions, attestations and consensus messages implement
+//! `serde::Serialize` and `serde::Deserialize`, so that their JSON is the same
+//! wherever it is produced or consumed. The layout follows these conventions:
+//!
+//! - Hashes, roots, blooms and BLS signatures are lowercase hex strings.
+//! - BLS public keys are base58 strings.
+//! - Integers are JSON numbers and structs are objects keyed by field name.
+//! - Enums are externally tagged, e.g. `{"Valid": "<hex>"}` for a vote or
+//!   `{"DoubleCandidate": [..]}` for a fault, and unit variants are strings.
+//! - A ratification result is its vote, since only a valid vote succeeds.
+//! - The iterations info of a header is a list with a `null` for each iteration
+//!   without a failed attestation.
+//! - A transaction is the descriptive layout of its protocol transaction, along
+//!   with the hex of its full encoding in the `raw` field. Only `raw` is read
+//!   back, the other fields being derived from it. The transaction events carry
+//!   the descriptive layout alone.
+
+#[cfg(test)]
+mod tests {
+    use fake::{Dummy, Fake, Faker};
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+
+    use crate::ledger::{
+        Attestation, Block, Fault, Header, SpentTransaction, Transaction,
+    };
+    use crate::message::payload::{
+        Candidate, Ratification, Validation, ValidationQuorum,
+    };
+    use crate::message::ConsensusHeader;
+
+    /// Asserts if a type survives a JSON round trip.
+    fn assert_json<S>()
+    where
+        S: Dummy<Faker> + Eq + Serialize + DeserializeOwned + std::fmt::Debug,
+    {
+        let obj: S = Faker.fake();
+        let json = serde_json::to_string(&obj).expect("should serialize");
+        let dup: S = serde_json::from_str(&json).expect("should deserialize");
+        assert_eq!(obj, dup, "{json}");
+    }
+
+    #[test]
+    fn test_json_ledger() {
+        assert_json::<Attestation>();
+        assert_json::<Header>();
+        assert_json::<Fault>();
+        assert_json::<Transaction>();
+        assert_json::<SpentTransaction>();
+    }
+
+    #[test]
+    fn test_json_messages() {
+        assert_json::<ConsensusHeader>();
+        assert_json::<Candidate>();
+        assert_json::<Validation>();
+        assert_json::<Ratification>();
+        assert_json::<ValidationQuorum>();
+    }
+
+    #[test]
+    fn test_json_transaction_event() {
+        use crate::events::{Event, TransactionEvent};
+
+        let tx: Transaction = Faker.fake();
+        let json = serde_json::to_value(&tx).expect("should serialize");
+        assert!(json.get("raw").is_some(), "the canonical JSON is raw");
+
+        let event = Event::from(TransactionEvent::Included(&tx));
+        let data = event.data.expect("the event should have data");
+        assert!(data.get("raw").is_none(), "events shouldn't be raw");
+        assert_eq!(data["type"], json["type"]);
+
+        let spent: SpentTransaction = Faker.fake();
+        let event = Event::from(TransactionEvent::Executed(&spent));
+        let data = event.data.expect("the event should have data");
+        assert!(
+            data["inner"].get("raw").is_none(),
+            "events shouldn't be raw"
+        );
+    }
+
+    #[test]
+    fn test_json_block() {
+        let block: Block = Faker.fake();
+        let json = serde_json::to_value(&block).expect("should serialize");
+        assert_eq!(
+            json["header"]["hash"],
+            hex::encode(block.header().hash),
+            "hashes should be hex encoded"
+        );
+        assert_eq!(
+            json["header"]["generator_bls_pubkey"],
+            block.header().generator_bls_pubkey.to_base58(),
+            "public keys should be base58 encoded"
+        );
+
+        let dup: Block =
+            serde_json::from_value(json).expect("should deserialize");
+        assert_eq!(block.header(), dup.header());
+        assert_eq!(block.txs(), dup.txs());
+        assert_eq!(block.faults(), dup.faults());
+    }
+}
@@ -4,12 +4,14 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 use crate::message::payload::RatificationResult;
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+#[derive(
+    Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize,
+)]
 #[cfg_attr(any(feature = "faker", test), derive(Dummy))]
 pub struct Attestation {
     pub result: RatificationResult,
@@ -17,7 +19,9 @@ pub struct Attestation {
     pub ratification: StepVotes,
 }
 
-#[derive(Debug, Default, Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[derive(
+    Debug, Default, Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize,
+)]
 #[cfg_attr(any(feature = "faker", test), derive(Dummy))]
 pub struct StepVotes {
     pub bitset: u64,
@@ -53,9 +57,13 @@ impl StepVotes {
 }
 
 /// A wrapper of 48-sized array to facilitate Signature
-#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Signature(
-    #[serde(serialize_with = "crate::serialize_hex")] [u8; 48],
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
+    [u8; 48],
 );
 
 impl Signature {
@@ -94,7 +102,7 @@ impl Default for Signature {
 pub type IterationInfo = (Attestation, PublicKeyBytes);
 
 /// Defines a set of attestations of former iterations
-#[derive(Default, Eq, PartialEq, Clone, Serialize)]
+#[derive(Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct IterationsInfo {
     /// Represents a list of attestations where position is the iteration
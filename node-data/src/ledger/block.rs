@@ -4,12 +4,14 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use serde::{Deserialize, Serialize};
+
 use super::*;
 
 pub type Hash = [u8; 32];
 pub type Bloom = [u8; 256];
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     header: Header,
     txs: Vec<Transaction>,
@@ -10,6 +10,7 @@ use dusk_core::signatures::bls::{
     MultisigSignature as BlsMultisigSignature,
 };
 use dusk_core::stake::EPOCH;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 use tracing::error;
 
@@ -20,7 +21,7 @@ use crate::message::payload::{
 };
 use crate::message::{ConsensusHeader, SignInfo, SignedStepMessage};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy, Eq, PartialEq))]
 pub enum Fault {
     DoubleCandidate(FaultData<Hash>, FaultData<Hash>),
@@ -260,6 +261,78 @@ pub struct FaultData<V> {
     data: V,
 }
 
+/// The JSON layout of [`FaultData`], generic over the representation of its
+/// data.
+#[derive(Serialize, Deserialize)]
+struct FaultDataJson<H, S, D> {
+    header: H,
+    sig: S,
+    data: D,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HexHash(
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
+    Hash,
+);
+
+impl Serialize for FaultData<Hash> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        FaultDataJson {
+            header: &self.header,
+            sig: &self.sig,
+            data: HexHash(self.data),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FaultData<Hash> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let json = FaultDataJson::<_, _, HexHash>::deserialize(deserializer)?;
+        Ok(Self {
+            header: json.header,
+            sig: json.sig,
+            data: json.data.0,
+        })
+    }
+}
+
+impl Serialize for FaultData<Vote> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        FaultDataJson {
+            header: &self.header,
+            sig: &self.sig,
+            data: &self.data,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FaultData<Vote> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let json = FaultDataJson::<_, _, Vote>::deserialize(deserializer)?;
+        Ok(Self {
+            header: json.header,
+            sig: json.sig,
+            data: json.data,
+        })
+    }
+}
+
 impl<V: Serializable> Serializable for FaultData<V> {
     fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
         self.header.write(w)?;
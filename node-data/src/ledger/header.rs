@@ -4,30 +4,45 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::*;
 use crate::message::ConsensusHeader;
 
 pub type Seed = Signature;
-#[derive(Eq, PartialEq, Clone, Serialize)]
+#[derive(Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[cfg_attr(any(feature = "faker", test), derive(Dummy))]
 pub struct Header {
     // Hashable fields
     pub version: u8,
     pub height: u64,
     pub timestamp: u64,
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub prev_block_hash: Hash,
     pub seed: Seed,
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub state_hash: Hash,
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub event_bloom: Bloom,
     pub generator_bls_pubkey: PublicKeyBytes,
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub txroot: Hash,
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub faultroot: Hash,
     pub gas_limit: u64,
     #[cfg_attr(any(feature = "faker", test), dummy(faker = "0..50"))]
@@ -36,13 +51,15 @@ pub struct Header {
     pub failed_iterations: IterationsInfo,
 
     // Block hash
-    #[serde(serialize_with = "crate::serialize_hex")]
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub hash: Hash,
 
     pub signature: Signature,
 
     // Non-hashable fields
-    #[serde(skip_serializing)]
     pub att: Attestation,
 }
 
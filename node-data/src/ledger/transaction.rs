@@ -7,9 +7,9 @@
 use std::io;
 
 use dusk_bytes::Serializable as DuskSerializable;
+use dusk_core::abi::ContractFailure;
 use dusk_core::signatures::bls;
 use dusk_core::transfer::Transaction as ProtocolTransaction;
-use serde::Serialize;
 use sha3::Digest;
 
 use crate::Serializable;
@@ -46,7 +46,7 @@ impl From<ProtocolTransaction> for Transaction {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct SpentTransaction {
     pub inner: Transaction,
     pub block_height: u64,
@@ -119,6 +119,26 @@ impl PartialEq<Self> for Transaction {
 
 impl Eq for Transaction {}
 
+impl SpentTransaction {
+    /// Returns the structured failure of the transaction, if its execution
+    /// was aborted with `abi::fail`.
+    pub fn failure(&self) -> Option<ContractFailure> {
+        self.err.as_deref().and_then(ContractFailure::from_panic)
+    }
+}
+
+#[cfg(feature = "proto")]
+impl From<&SpentTransaction> for dusk_core::proto::SpentTransaction {
+    fn from(tx: &SpentTransaction) -> Self {
+        Self {
+            transaction: Some((&tx.inner.inner).into()),
+            block_height: tx.block_height,
+            gas_spent: tx.gas_spent,
+            error: tx.err.clone(),
+        }
+    }
+}
+
 impl PartialEq<Self> for SpentTransaction {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner && self.gas_spent == other.gas_spent
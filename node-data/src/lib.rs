@@ -11,8 +11,11 @@ pub mod archive;
 pub mod bls;
 pub mod encoding;
 pub mod events;
+pub mod json;
 pub mod ledger;
 pub mod message;
+#[cfg(test)]
+mod roundtrip;
 
 use std::io::{self, Read, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -36,6 +39,17 @@ pub trait Serializable {
     where
         Self: Sized;
 
+    /// Reads from the start of a slice, advancing it past the bytes read.
+    ///
+    /// Implementations decoding large length-prefixed fields override it to
+    /// borrow them from the slice instead of copying them.
+    fn read_slice(buf: &mut &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::read(buf)
+    }
+
     fn read_bytes<R: Read, const N: usize>(r: &mut R) -> io::Result<[u8; N]> {
         let mut buffer = [0u8; N];
         r.read_exact(&mut buffer)?;
@@ -79,6 +93,18 @@ pub trait Serializable {
 
         Ok(buf)
     }
+
+    /// Reads length-prefixed fields, borrowing them from the slice
+    fn read_var_le_slice32<'a>(buf: &mut &'a [u8]) -> io::Result<&'a [u8]> {
+        let len = Self::read_u32_le(buf)? as usize;
+        if buf.len() < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let (field, rest) = buf.split_at(len);
+        *buf = rest;
+        Ok(field)
+    }
 }
 
 impl<const N: usize> Serializable for [u8; N] {
@@ -116,6 +142,40 @@ where
     serializer.serialize_str(&hex)
 }
 
+pub fn deserialize_hex<'de, const N: usize, D>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    let hex: String = serde::Deserialize::deserialize(deserializer)?;
+    let bytes = hex::decode(hex).map_err(Error::custom)?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        Error::custom(format!("expected {N} bytes, found {}", bytes.len()))
+    })
+}
+
+pub fn deserialize_b58<'de, const N: usize, D>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    let b58: String = serde::Deserialize::deserialize(deserializer)?;
+    let mut bytes = [0u8; N];
+    let len = bs58::decode(b58)
+        .into(&mut bytes[..])
+        .map_err(Error::custom)?;
+    if len != N {
+        return Err(Error::custom(format!("expected {N} bytes, found {len}")));
+    }
+    Ok(bytes)
+}
+
 pub fn get_current_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -8,6 +8,7 @@ use core::fmt;
 use std::cmp::Ordering;
 use std::io::{self, Read, Write};
 use std::net::SocketAddr;
+use std::str::FromStr;
 
 use async_channel::TrySendError;
 use dusk_bytes::Serializable as DuskSerializable;
@@ -17,6 +18,7 @@ use dusk_core::signatures::bls::{
     SecretKey as BlsSecretKey,
 };
 use payload::{Nonce, ValidationQuorum};
+use serde::{Deserialize, Serialize};
 use tracing::{error, warn};
 
 use self::payload::{Candidate, Ratification, Validation};
@@ -26,7 +28,18 @@ use crate::{bls, ledger, Serializable, StepName};
 
 /// Topic field position in the message binary representation
 pub const TOPIC_FIELD_POS: usize = 1 + 2 + 2;
-pub const PROTOCOL_VERSION: Version = Version(1, 0, 0);
+
+/// The latest version of the wire protocol, used to encode the messages from
+/// its activation height
+pub const PROTOCOL_VERSION: Version = Version(1, 1, 0);
+
+/// The oldest version of the wire protocol whose messages can be decoded, used
+/// to encode the messages until the activation of [`PROTOCOL_VERSION`]
+pub const MIN_PROTOCOL_VERSION: Version = Version(1, 0, 0);
+
+/// The version from which the payload of a message is length-prefixed, so
+/// that the fields appended to it by later minor versions can be skipped
+const LENGTH_PREFIX_VERSION: Version = Version(1, 1, 0);
 
 /// Block version
 pub const BLOCK_HEADER_VERSION: u8 = 1;
@@ -34,13 +47,29 @@ pub const BLOCK_HEADER_VERSION: u8 = 1;
 /// Max value for failed iterations.
 pub const MESSAGE_MAX_FAILED_ITERATIONS: u8 = 8;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represent version (major, minor, patch)
 pub struct Version(pub u8, pub u16, pub u16);
 
+impl Version {
+    /// Returns if messages of this version can be decoded, that is if it has
+    /// the same major version as [`PROTOCOL_VERSION`] and is not older than
+    /// [`MIN_PROTOCOL_VERSION`].
+    ///
+    /// Messages of newer minor versions are supported, ignoring the fields
+    /// they append to the payload.
+    pub fn is_supported(&self) -> bool {
+        self.0 == PROTOCOL_VERSION.0 && self >= &MIN_PROTOCOL_VERSION
+    }
+
+    fn is_length_prefixed(&self) -> bool {
+        self >= &LENGTH_PREFIX_VERSION
+    }
+}
+
 impl Default for Version {
     fn default() -> Self {
-        PROTOCOL_VERSION
+        MIN_PROTOCOL_VERSION
     }
 }
 
@@ -51,6 +80,25 @@ impl fmt::Display for Version {
     }
 }
 
+impl FromStr for Version {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid version {s}"),
+            )
+        };
+        let mut parts = s.splitn(3, '.');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let maj = next()?.parse().map_err(|_| invalid())?;
+        let min = next()?.parse().map_err(|_| invalid())?;
+        let patch = next()?.parse().map_err(|_| invalid())?;
+        Ok(Self(maj, min, patch))
+    }
+}
+
 impl crate::Serializable for Version {
     fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
         let Version(maj, min, patch) = self;
@@ -173,25 +221,29 @@ impl Message {
         self
     }
 
-    pub fn is_local(&self) -> bool {
-        self.metadata.is_none()
-    }
-}
-
-/// Defines a transport-related properties that determines how the message
-/// will be broadcast.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Metadata {
-    pub height: u8,
-    pub src_addr: SocketAddr,
-    pub ray_id: String,
-}
-
-impl Serializable for Message {
-    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
-        self.version.write(w)?;
+    /// Encode the message in the envelope of the given version of the wire
+    /// protocol, regardless of the version of the message.
+    ///
+    /// Up to version 1.0 the payload follows the topic. From version 1.1 it
+    /// is prefixed by its length.
+    pub fn write_versioned<W: Write>(
+        &self,
+        version: &Version,
+        w: &mut W,
+    ) -> io::Result<()> {
+        version.write(w)?;
         w.write_all(&[self.topic as u8])?;
 
+        if version.is_length_prefixed() {
+            let mut payload = vec![];
+            self.write_payload(&mut payload)?;
+            Self::write_var_le_bytes32(w, &payload)
+        } else {
+            self.write_payload(w)
+        }
+    }
+
+    fn write_payload<W: Write>(&self, w: &mut W) -> io::Result<()> {
         match &self.payload {
             Payload::Candidate(p) => p.write(w),
             Payload::Validation(p) => p.write(w),
@@ -210,15 +262,35 @@ impl Serializable for Message {
         }
     }
 
-    fn read<R: Read>(r: &mut R) -> io::Result<Self>
-    where
-        Self: Sized,
-    {
+    /// Read the version and the topic of a message, checking the version is
+    /// supported.
+    fn read_envelope<R: Read>(r: &mut R) -> io::Result<(Version, Topics)> {
         let version = Version::read(r)?;
+        if !version.is_supported() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported protocol version {version}"),
+            ));
+        }
 
-        // Read topic
         let topic = Topics::from(Self::read_u8(r)?);
-        let message: Message = match topic {
+        Ok((version, topic))
+    }
+
+    /// Read the payload of the given topic from a slice, borrowing the large
+    /// fields of blocks and transactions from it.
+    fn read_payload_slice(topic: Topics, buf: &mut &[u8]) -> io::Result<Self> {
+        let message = match topic {
+            Topics::Candidate => payload::Candidate::read_slice(buf)?.into(),
+            Topics::Block => ledger::Block::read_slice(buf)?.into(),
+            Topics::Tx => ledger::Transaction::read_slice(buf)?.into(),
+            _ => Self::read_payload(topic, buf)?,
+        };
+        Ok(message)
+    }
+
+    fn read_payload<R: Read>(topic: Topics, r: &mut R) -> io::Result<Self> {
+        let message = match topic {
             Topics::Candidate => payload::Candidate::read(r)?.into(),
             Topics::Validation => payload::Validation::read(r)?.into(),
             Topics::Ratification => payload::Ratification::read(r)?.into(),
@@ -241,6 +313,66 @@ impl Serializable for Message {
                 ));
             }
         };
+        Ok(message)
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.metadata.is_none()
+    }
+}
+
+/// Defines a transport-related properties that determines how the message
+/// will be broadcast.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub height: u8,
+    pub src_addr: SocketAddr,
+    pub ray_id: String,
+}
+
+impl Serializable for Message {
+    fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_versioned(&self.version, w)
+    }
+
+    fn read<R: Read>(r: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let (version, topic) = Self::read_envelope(r)?;
+
+        let message = if version.is_length_prefixed() {
+            let len = Self::read_u32_le(r)? as u64;
+            let mut payload = vec![];
+            r.take(len).read_to_end(&mut payload)?;
+            if payload.len() as u64 != len {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Truncated payload",
+                ));
+            }
+            // Any trailing field appended by a newer minor version is ignored
+            Self::read_payload_slice(topic, &mut &payload[..])?
+        } else {
+            Self::read_payload(topic, r)?
+        };
+
+        Ok(message.with_version(version))
+    }
+
+    fn read_slice(buf: &mut &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let (version, topic) = Self::read_envelope(buf)?;
+
+        let message = if version.is_length_prefixed() {
+            let mut payload = Self::read_var_le_slice32(buf)?;
+            // Any trailing field appended by a newer minor version is ignored
+            Self::read_payload_slice(topic, &mut payload)?
+        } else {
+            Self::read_payload_slice(topic, buf)?
+        };
 
         Ok(message.with_version(version))
     }
@@ -335,9 +467,13 @@ impl Message {
     }
 }
 
-#[derive(Default, Clone, PartialEq, Eq, Copy)]
+#[derive(Default, Clone, PartialEq, Eq, Copy, Serialize, Deserialize)]
 #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy))]
 pub struct ConsensusHeader {
+    #[serde(
+        serialize_with = "crate::serialize_hex",
+        deserialize_with = "crate::deserialize_hex"
+    )]
     pub prev_block_hash: Hash,
     pub round: u64,
     #[cfg_attr(any(feature = "faker", test), dummy(faker = "0..50"))]
@@ -505,13 +641,13 @@ pub mod payload {
         IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
     };
 
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
 
     use super::{ConsensusHeader, SignInfo};
     use crate::ledger::{self, to_str, Attestation, Block, Hash, StepVotes};
     use crate::{get_current_timestamp, Serializable};
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     #[cfg_attr(
         any(feature = "faker", test),
         derive(fake::Dummy, Eq, PartialEq)
@@ -524,7 +660,7 @@ pub mod payload {
         pub sign_info: SignInfo,
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     #[cfg_attr(
         any(feature = "faker", test),
         derive(fake::Dummy, Eq, PartialEq)
@@ -536,14 +672,35 @@ pub mod payload {
     }
 
     #[derive(
-        Clone, Copy, Hash, Eq, PartialEq, Default, PartialOrd, Ord, Serialize,
+        Clone,
+        Copy,
+        Hash,
+        Eq,
+        PartialEq,
+        Default,
+        PartialOrd,
+        Ord,
+        Serialize,
+        Deserialize,
     )]
     #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy))]
     #[repr(u8)]
     pub enum Vote {
         NoCandidate = 0,
-        Valid(#[serde(serialize_with = "crate::serialize_hex")] Hash) = 1,
-        Invalid(#[serde(serialize_with = "crate::serialize_hex")] Hash) = 2,
+        Valid(
+            #[serde(
+                serialize_with = "crate::serialize_hex",
+                deserialize_with = "crate::deserialize_hex"
+            )]
+            Hash,
+        ) = 1,
+        Invalid(
+            #[serde(
+                serialize_with = "crate::serialize_hex",
+                deserialize_with = "crate::deserialize_hex"
+            )]
+            Hash,
+        ) = 2,
 
         #[default]
         NoQuorum = 3,
@@ -638,7 +795,7 @@ pub mod payload {
         }
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, Serialize, Deserialize)]
     #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy))]
     pub struct Candidate {
         pub candidate: Block,
@@ -681,8 +838,19 @@ pub mod payload {
 
             Ok(Candidate { candidate })
         }
+
+        fn read_slice(buf: &mut &[u8]) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let candidate = Block::read_slice(buf)?;
+
+            Ok(Candidate { candidate })
+        }
     }
-    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[derive(
+        Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+    )]
     #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy))]
     pub enum QuorumType {
         /// Supermajority of Valid votes
@@ -707,7 +875,7 @@ pub mod payload {
         }
     }
 
-    #[derive(Debug, Clone, Default)]
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
     #[cfg_attr(
         any(feature = "faker", test),
         derive(fake::Dummy, Eq, PartialEq)
@@ -717,7 +885,7 @@ pub mod payload {
         pub result: ValidationResult,
     }
 
-    #[derive(Debug, Clone, Default)]
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
     #[cfg_attr(
         any(feature = "faker", test),
         derive(fake::Dummy, Eq, PartialEq)
@@ -746,14 +914,24 @@ pub mod payload {
         }
     }
 
-    #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
-    #[serde(untagged)]
-    #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy))]
+    /// Serialized as its vote, since only a valid vote succeeds.
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+    #[serde(untagged, from = "Vote")]
     pub enum RatificationResult {
         Fail(Vote),
         Success(Vote),
     }
 
+    #[cfg(any(feature = "faker", test))]
+    impl fake::Dummy<fake::Faker> for RatificationResult {
+        fn dummy_with_rng<R: rand::Rng + ?Sized>(
+            config: &fake::Faker,
+            rng: &mut R,
+        ) -> Self {
+            <Vote as fake::Dummy<_>>::dummy_with_rng(config, rng).into()
+        }
+    }
+
     impl Default for RatificationResult {
         fn default() -> Self {
             Self::Fail(Vote::NoQuorum)
@@ -787,7 +965,7 @@ pub mod payload {
         }
     }
 
-    #[derive(Debug, Clone, Eq, PartialEq)]
+    #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
     pub struct Quorum {
         pub header: ConsensusHeader,
         pub att: Attestation,
@@ -1578,7 +1756,7 @@ impl StepMessage for ValidationQuorum {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(any(feature = "faker", test), derive(fake::Dummy, Eq, PartialEq))]
 pub struct SignInfo {
     pub signer: bls::PublicKey,
@@ -1707,6 +1885,62 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_versioned_envelope() {
+        let msg = Message::from(payload::GetBlocks::new([3; 32]));
+        let decode = |buf: &[u8]| {
+            let dup = Message::read(&mut &buf[..]);
+            let borrowed = Message::read_slice(&mut &buf[..]);
+            assert_eq!(dup.is_ok(), borrowed.is_ok());
+            dup
+        };
+
+        // Until its activation, messages use the legacy envelope
+        assert_eq!(msg.version, MIN_PROTOCOL_VERSION);
+        let mut legacy = vec![];
+        msg.write(&mut legacy).expect("message to be encoded");
+        let mut current = vec![];
+        msg.write_versioned(&PROTOCOL_VERSION, &mut current)
+            .expect("message to be encoded");
+        assert_eq!(current.len(), legacy.len() + 4, "payload length prefix");
+
+        for (buf, version) in [
+            (&legacy, MIN_PROTOCOL_VERSION),
+            (&current, PROTOCOL_VERSION),
+        ] {
+            let dup = decode(buf).expect("message to be decoded");
+            assert_eq!(dup.version, version);
+            assert_eq!(dup.topic(), Topics::GetBlocks);
+            match dup.payload {
+                Payload::GetBlocks(p) => assert_eq!(p.locator, [3; 32]),
+                p => panic!("unexpected payload {p:?}"),
+            }
+        }
+
+        // A newer minor version appending fields to the payload
+        let mut newer = vec![];
+        Version(1, 2, 0).write(&mut newer).unwrap();
+        newer.push(Topics::GetBlocks as u8);
+        let payload = &current[TOPIC_FIELD_POS + 1 + 4..];
+        let extended = [payload, &[0xff; 8]].concat();
+        Message::write_var_le_bytes32(&mut newer, &extended).unwrap();
+        let dup = decode(&newer).expect("newer minor to be decoded");
+        assert_eq!(dup.version, Version(1, 2, 0));
+
+        // A newer major version, and a version older than the minimum one
+        for version in [Version(2, 0, 0), Version(0, 9, 0)] {
+            let mut buf = vec![];
+            msg.write_versioned(&version, &mut buf).unwrap();
+            assert!(decode(&buf).is_err(), "{version} should be rejected");
+        }
+
+        let truncated = &current[..current.len() - 1];
+        assert!(decode(truncated).is_err());
+
+        assert_eq!("1.1.0".parse::<Version>().unwrap(), PROTOCOL_VERSION);
+        assert!("1.1".parse::<Version>().is_err());
+    }
+
     fn assert_serialize<S: Serializable + PartialEq + core::fmt::Debug>(v: S) {
         let mut buf = vec![];
         assert!(v.write(&mut buf).is_ok());
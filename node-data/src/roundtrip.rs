@@ -0,0 +1,260 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Property tests of the binary encoding of every [`Serializable`] type.
+//!
+//! Random instances of each type are encoded and decoded back, checking that:
+//! - decoding consumes the whole encoding;
+//! - the decoded value encodes to the same bytes;
+//! - decoding from a reader and from a slice agree;
+//! - any truncation of the encoding is rejected.
+//!
+//! Setting `NODE_DATA_FUZZ_CORPUS` to a directory writes the encodings there,
+//! in the format of the `decode` fuzz target, to seed its corpus.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+
+use fake::{Dummy, Fake, Faker};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+
+use crate::ledger::{
+    Attestation, Block, Fault, Header, IterationsInfo, Label, SpentTransaction,
+    StepVotes, Transaction,
+};
+use crate::message::payload::{
+    Candidate, GetBlocks, GetCandidate, GetCandidateResp, GetMempool,
+    GetResource, Inv, Quorum, QuorumType, Ratification, RatificationResult,
+    Validation, ValidationQuorum, ValidationResult, Vote,
+};
+use crate::message::{
+    ConsensusHeader, Message, SignInfo, Version, MIN_PROTOCOL_VERSION,
+    PROTOCOL_VERSION,
+};
+use crate::Serializable;
+
+/// The number of random instances checked for each type.
+const CASES: usize = 16;
+
+/// The encodings up to this size are checked for every truncation, the
+/// larger ones for a sample of them.
+const EXHAUSTIVE_TRUNCATION_SIZE: usize = 1024;
+
+/// The selector of each type in the inputs of the `decode` fuzz target.
+mod selector {
+    pub const MESSAGE: u8 = 0;
+    pub const BLOCK: u8 = 1;
+    pub const HEADER: u8 = 2;
+    pub const TRANSACTION: u8 = 3;
+    pub const SPENT_TRANSACTION: u8 = 4;
+    pub const FAULT: u8 = 5;
+    pub const LABEL: u8 = 6;
+    pub const OTHER: u8 = u8::MAX;
+}
+
+fn encode<S: Serializable>(obj: &S) -> Vec<u8> {
+    let mut buf = vec![];
+    obj.write(&mut buf).expect("should be writable");
+    buf
+}
+
+/// Asserts the properties of the encoding of a value.
+fn assert_roundtrip<S: Serializable>(selector: u8, obj: &S) {
+    let name = std::any::type_name::<S>();
+    let buf = encode(obj);
+    dump_corpus(selector, &buf);
+
+    let mut reader = &buf[..];
+    let dup = S::read(&mut reader)
+        .unwrap_or_else(|e| panic!("{name} should be readable: {e}"));
+    assert!(reader.is_empty(), "{name} should be read entirely");
+    assert_eq!(encode(&dup), buf, "{name} should encode the same");
+
+    let mut slice = &buf[..];
+    let dup = S::read_slice(&mut slice)
+        .unwrap_or_else(|e| panic!("{name} should be readable: {e}"));
+    assert!(slice.is_empty(), "{name} should be read entirely");
+    assert_eq!(encode(&dup), buf, "{name} should encode the same");
+
+    let mut rng = StdRng::seed_from_u64(buf.len() as u64);
+    let cuts: Vec<usize> = if buf.len() <= EXHAUSTIVE_TRUNCATION_SIZE {
+        (0..buf.len()).collect()
+    } else {
+        (0..64).map(|_| rng.gen_range(0..buf.len())).collect()
+    };
+    for cut in cuts {
+        let truncated = &buf[..cut];
+        assert!(
+            S::read(&mut &truncated[..]).is_err(),
+            "{name} truncated to {cut} bytes should be rejected"
+        );
+        assert!(
+            S::read_slice(&mut &truncated[..]).is_err(),
+            "{name} truncated to {cut} bytes should be rejected"
+        );
+    }
+}
+
+/// Asserts the properties of the encoding of random values of a type.
+fn assert_fake_roundtrip<S: Serializable + Dummy<Faker>>(selector: u8) {
+    let mut rng = StdRng::seed_from_u64(0xdead_beef);
+    for _ in 0..CASES {
+        let obj: S = Faker.fake_with_rng(&mut rng);
+        assert_roundtrip(selector, &obj);
+    }
+}
+
+fn dump_corpus(selector: u8, buf: &[u8]) {
+    let Some(dir) = std::env::var_os("NODE_DATA_FUZZ_CORPUS") else {
+        return;
+    };
+    if selector == selector::OTHER {
+        return;
+    }
+
+    let input = [&[selector], buf].concat();
+    let name = hex::encode(Sha256::digest(&input));
+    let path = PathBuf::from(dir).join(name);
+    std::fs::write(path, input).expect("corpus should be writable");
+}
+
+fn random_inv(rng: &mut StdRng) -> Inv {
+    let mut inv = Inv::new(rng.gen());
+    for _ in 0..rng.gen_range(0..8) {
+        match rng.gen_range(0..6) {
+            0 => inv.add_tx_id(rng.gen()),
+            1 => inv.add_block_from_hash(rng.gen()),
+            2 => inv.add_block_from_height(rng.gen()),
+            3 => inv.add_candidate_from_hash(rng.gen()),
+            4 => inv.add_candidate_from_iteration(Faker.fake_with_rng(rng)),
+            _ => inv.add_validation_result(Faker.fake_with_rng(rng)),
+        }
+    }
+    inv
+}
+
+fn random_addr(rng: &mut StdRng) -> SocketAddr {
+    if rng.gen() {
+        (Ipv4Addr::from(rng.gen::<[u8; 4]>()), rng.gen()).into()
+    } else {
+        (Ipv6Addr::from(rng.gen::<[u8; 16]>()), rng.gen()).into()
+    }
+}
+
+fn random_label(rng: &mut StdRng) -> Label {
+    match rng.gen_range(0..4) {
+        0 => Label::Accepted(rng.gen()),
+        1 => Label::Attested(rng.gen()),
+        2 => Label::Confirmed(rng.gen()),
+        _ => Label::Final(rng.gen()),
+    }
+}
+
+/// The messages of every topic, with random payloads.
+fn random_messages(rng: &mut StdRng) -> Vec<Message> {
+    let mut get_blocks = GetBlocks::new(rng.gen());
+    get_blocks.set_nonce(rng.gen::<u64>());
+    let mut get_mempool = GetMempool::default();
+    get_mempool.set_nonce(rng.gen::<u64>());
+    let get_resource = GetResource::new(
+        random_inv(rng),
+        Some(random_addr(rng)),
+        rng.gen(),
+        rng.gen(),
+    );
+    let transaction: Transaction = Faker.fake_with_rng(rng);
+    let block: Block = Faker.fake_with_rng(rng);
+
+    vec![
+        Faker.fake_with_rng::<Candidate, _>(rng).into(),
+        Faker.fake_with_rng::<Validation, _>(rng).into(),
+        Faker.fake_with_rng::<Ratification, _>(rng).into(),
+        Faker.fake_with_rng::<ValidationQuorum, _>(rng).into(),
+        Quorum {
+            header: Faker.fake_with_rng(rng),
+            att: Faker.fake_with_rng(rng),
+        }
+        .into(),
+        block.into(),
+        transaction.into(),
+        get_resource.into(),
+        get_blocks.into(),
+        get_mempool.into(),
+        random_inv(rng).into(),
+    ]
+}
+
+#[test]
+fn ledger_roundtrip() {
+    assert_fake_roundtrip::<Block>(selector::BLOCK);
+    assert_fake_roundtrip::<Header>(selector::HEADER);
+    assert_fake_roundtrip::<Transaction>(selector::TRANSACTION);
+    assert_fake_roundtrip::<SpentTransaction>(selector::SPENT_TRANSACTION);
+    assert_fake_roundtrip::<Fault>(selector::FAULT);
+    assert_fake_roundtrip::<Attestation>(selector::OTHER);
+    assert_fake_roundtrip::<StepVotes>(selector::OTHER);
+    assert_fake_roundtrip::<IterationsInfo>(selector::OTHER);
+
+    let mut rng = StdRng::seed_from_u64(0xdead_beef);
+    for _ in 0..CASES {
+        assert_roundtrip(selector::LABEL, &random_label(&mut rng));
+    }
+
+    // Transactions executed successfully have no error
+    let mut spent: SpentTransaction = Faker.fake_with_rng(&mut rng);
+    spent.err = None;
+    assert_roundtrip(selector::SPENT_TRANSACTION, &spent);
+}
+
+#[test]
+fn consensus_roundtrip() {
+    assert_fake_roundtrip::<ConsensusHeader>(selector::OTHER);
+    assert_fake_roundtrip::<SignInfo>(selector::OTHER);
+    assert_fake_roundtrip::<Vote>(selector::OTHER);
+    assert_fake_roundtrip::<RatificationResult>(selector::OTHER);
+    assert_fake_roundtrip::<QuorumType>(selector::OTHER);
+    assert_fake_roundtrip::<ValidationResult>(selector::OTHER);
+    assert_fake_roundtrip::<Candidate>(selector::OTHER);
+    assert_fake_roundtrip::<Validation>(selector::OTHER);
+    assert_fake_roundtrip::<Ratification>(selector::OTHER);
+    assert_fake_roundtrip::<ValidationQuorum>(selector::OTHER);
+
+    let mut rng = StdRng::seed_from_u64(0xdead_beef);
+    for _ in 0..CASES {
+        let quorum = Quorum {
+            header: Faker.fake_with_rng(&mut rng),
+            att: Faker.fake_with_rng(&mut rng),
+        };
+        assert_roundtrip(selector::OTHER, &quorum);
+    }
+}
+
+#[test]
+fn message_roundtrip() {
+    let mut rng = StdRng::seed_from_u64(0xdead_beef);
+    for _ in 0..CASES {
+        for msg in random_messages(&mut rng) {
+            for version in [MIN_PROTOCOL_VERSION, PROTOCOL_VERSION] {
+                let msg = msg.clone().with_version(version);
+                assert_roundtrip(selector::MESSAGE, &msg);
+            }
+        }
+
+        let candidate = GetCandidate { hash: rng.gen() };
+        assert_roundtrip(selector::OTHER, &candidate);
+        let resp = GetCandidateResp {
+            candidate: Faker.fake_with_rng(&mut rng),
+        };
+        assert_roundtrip(selector::OTHER, &resp);
+        assert_roundtrip(selector::OTHER, &random_addr(&mut rng));
+        assert_roundtrip(selector::OTHER, &random_inv(&mut rng));
+
+        let version = Version(rng.gen(), rng.gen(), rng.gen());
+        assert_roundtrip(selector::OTHER, &version);
+    }
+}
@@ -171,6 +171,36 @@ impl Archive {
         Ok(contract_tx_events)
     }
 
+    /// Fetch the finalized vm events from the given block height onwards, in
+    /// the order they were archived.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_height` - The block height to start from.
+    /// * `after_id` - The id of the last event already fetched, or 0 to fetch
+    ///   from the first one.
+    /// * `limit` - The maximum number of events to fetch.
+    pub async fn fetch_finalized_events_after(
+        &self,
+        from_height: i64,
+        after_id: i64,
+        limit: i64,
+    ) -> Result<Vec<data::SequencedEvent>> {
+        let mut conn = self.sqlite_archive.acquire().await?;
+
+        let events = sqlx::query_as!(
+            data::SequencedEvent,
+            r#"SELECT id, block_height, block_hash, origin, topic, source, data FROM finalized_events WHERE block_height >= ? AND id > ? ORDER BY id LIMIT ?"#,
+            from_height,
+            after_id,
+            limit
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(events)
+    }
+
     /// Fetch the last finalized block height and block hash
     pub async fn fetch_last_finalized_block(&self) -> Result<(u64, String)> {
         let mut conn = self.sqlite_archive.acquire().await?;
@@ -448,6 +478,42 @@ mod data {
         pub data: Vec<u8>,
     }
 
+    /// Archived finalized ContractTxEvent, along with its position in the
+    /// archive.
+    ///
+    /// # Fields
+    /// - `id`: The id of the event, increasing in the order events are
+    ///   archived.
+    /// - `block_height`: The height of the block the event was emitted in.
+    /// - `block_hash`: The hex encoded hash of the block.
+    /// - The remaining fields are the same as in [`ArchivedEvent`].
+    #[serde_with::serde_as]
+    #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+    pub struct SequencedEvent {
+        pub id: i64,
+        pub block_height: i64,
+        pub block_hash: String,
+        pub origin: String,
+        pub topic: String,
+        pub source: String,
+        #[serde_as(as = "serde_with::hex::Hex")]
+        pub data: Vec<u8>,
+    }
+
+    impl TryFrom<SequencedEvent> for ContractTxEvent {
+        type Error = anyhow::Error;
+
+        fn try_from(value: SequencedEvent) -> Result<Self, Self::Error> {
+            ArchivedEvent {
+                origin: value.origin,
+                topic: value.topic,
+                source: value.source,
+                data: value.data,
+            }
+            .try_into()
+        }
+    }
+
     impl TryFrom<ArchivedEvent> for ContractTxEvent {
         type Error = anyhow::Error;
 
@@ -5,7 +5,9 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod acceptor;
+pub mod block_builder;
 mod consensus;
+pub mod failover;
 mod fallback;
 mod fsm;
 mod genesis;
@@ -33,6 +35,8 @@ use tokio::time::{sleep_until, Instant};
 use tracing::{debug, error, info, warn};
 
 use self::acceptor::Acceptor;
+use self::block_builder::{BlockBuilderPolicy, ByGasPrice};
+use self::failover::Failover;
 use self::fsm::SimpleFSM;
 use crate::database::rocksdb::MD_HASH_KEY;
 use crate::database::{Ledger, Metadata};
@@ -59,6 +63,10 @@ pub struct ChainSrv<N: Network, DB: database::DB, VM: vm::VMExecution> {
     event_sender: Sender<Event>,
     genesis_timestamp: u64,
     dusk_key: BlsPublicKey,
+    /// Active/standby coordination with a node sharing the consensus keys
+    failover: Option<failover::Params>,
+    /// Selects the mempool transactions of the generated candidates
+    block_builder: Arc<dyn BlockBuilderPolicy>,
 }
 
 #[async_trait]
@@ -95,6 +103,8 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         )
         .await?;
 
+        acc.set_block_builder(self.block_builder.clone()).await;
+
         self.acceptor = Some(Arc::new(RwLock::new(acc)));
 
         Ok(())
@@ -126,6 +136,19 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
         let mut heartbeat = Instant::now().checked_add(HEARTBEAT_SEC).unwrap();
 
+        let consensus_pubkey = acc.read().await.get_consensus_pubkey().await;
+        let failover = self
+            .failover
+            .clone()
+            .map(|params| Failover::new(params, consensus_pubkey))
+            .transpose()?
+            .map(Arc::new);
+        let mut failover_heartbeat = tokio::time::interval(
+            failover
+                .as_ref()
+                .map_or(HEARTBEAT_SEC, |f| f.heartbeat_interval()),
+        );
+
         // Message loop for Chain context
         loop {
             tokio::select! {
@@ -208,6 +231,20 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                 recv = outbound_chan.recv() => {
                     let msg = recv?;
 
+                    // Signed messages are held back unless the node is the
+                    // active one and their slot was never signed
+                    if let Some(failover) = &failover {
+                        if !failover.may_broadcast(&msg).await {
+                            debug!(
+                                event = "failover: message held back",
+                                topic = ?msg.topic(),
+                                round = msg.header.round,
+                                step = msg.get_step(),
+                            );
+                            continue;
+                        }
+                    }
+
                     // Handle quorum messages from Consensus layer.
                     // If the associated candidate block already exists,
                     // the winner block will be compiled and redirected to the Acceptor.
@@ -234,6 +271,14 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
 
                     heartbeat = Instant::now().checked_add(HEARTBEAT_SEC).unwrap();
                 },
+                // Handles failover heartbeat
+                _ = failover_heartbeat.tick(), if failover.is_some() => {
+                    if let Some(failover) = &failover {
+                        if let Err(err) = failover.heartbeat().await {
+                            error!(event = "failover heartbeat failed", ?err);
+                        }
+                    }
+                },
             }
         }
     }
@@ -265,9 +310,28 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
             event_sender,
             genesis_timestamp,
             dusk_key,
+            failover: None,
+            block_builder: Arc::new(ByGasPrice),
         }
     }
 
+    /// Sets the policy selecting the mempool transactions of the candidate
+    /// blocks generated by the node.
+    pub fn with_block_builder(
+        mut self,
+        policy: Arc<dyn BlockBuilderPolicy>,
+    ) -> Self {
+        self.block_builder = policy;
+        self
+    }
+
+    /// Enables the active/standby coordination with another node sharing the
+    /// same consensus keys.
+    pub fn with_failover(mut self, failover: Option<failover::Params>) -> Self {
+        self.failover = failover;
+        self
+    }
+
     /// Load both the chain tip and last finalized block from persisted ledger.
     ///
     /// Panics
@@ -21,7 +21,9 @@ use dusk_consensus::operations::Voter;
 use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
 use dusk_consensus::user::stake::Stake;
 use dusk_core::signatures::bls;
-use dusk_core::stake::{SlashEvent, StakeAmount, StakeEvent};
+use dusk_core::stake::{
+    DelegatedStakeEvent, SlashEvent, StakeAmount, StakeEvent,
+};
 use metrics::{counter, gauge, histogram};
 use node_data::bls::PublicKey;
 use node_data::events::contract::ContractEvent;
@@ -38,6 +40,7 @@ use tokio::sync::{RwLock, RwLockReadGuard};
 use tracing::{debug, error, info, trace, warn};
 
 use super::consensus::Task;
+use crate::chain::block_builder::BlockBuilderPolicy;
 use crate::chain::header_validation::{verify_att, verify_faults, Validator};
 use crate::chain::metrics::AverageElapsedTime;
 use crate::database::rocksdb::{
@@ -100,6 +103,8 @@ enum ProvisionerChange {
     Unstake(StakeEvent),
     Slash(SlashEvent),
     HardSlash(SlashEvent),
+    DelegatedStake(DelegatedStakeEvent),
+    UndelegatedStake(DelegatedStakeEvent),
 }
 
 fn stake_event(data: &[u8]) -> StakeEvent {
@@ -111,6 +116,14 @@ fn stake_event(data: &[u8]) -> StakeEvent {
     staking_event_data
 }
 
+fn delegated_stake_event(data: &[u8]) -> DelegatedStakeEvent {
+    let event_data = check_archived_root::<DelegatedStakeEvent>(data)
+        .expect("Delegated stake event data should deserialize correctly");
+    let event_data: DelegatedStakeEvent =
+        event_data.deserialize(&mut Infallible).expect("Infallible");
+    event_data
+}
+
 fn slash_event(data: &[u8]) -> SlashEvent {
     let staking_event_data = check_archived_root::<SlashEvent>(data)
         .expect("Stake event data should deserialize correctly");
@@ -129,6 +142,12 @@ impl ProvisionerChange {
             "hard_slash" => {
                 ProvisionerChange::HardSlash(slash_event(&event.data))
             }
+            "delegated_stake" => ProvisionerChange::DelegatedStake(
+                delegated_stake_event(&event.data),
+            ),
+            "undelegated_stake" => ProvisionerChange::UndelegatedStake(
+                delegated_stake_event(&event.data),
+            ),
             _ => return None,
         };
         Some(event)
@@ -140,6 +159,8 @@ impl ProvisionerChange {
             ProvisionerChange::Unstake(_) => "unstake",
             ProvisionerChange::Slash(_) => "slash",
             ProvisionerChange::HardSlash(_) => "hard_slash",
+            ProvisionerChange::DelegatedStake(_) => "delegated_stake",
+            ProvisionerChange::UndelegatedStake(_) => "undelegated_stake",
         }
     }
 
@@ -149,6 +170,8 @@ impl ProvisionerChange {
             ProvisionerChange::Unstake(e) => &e.keys.account,
             ProvisionerChange::Slash(e) => &e.account,
             ProvisionerChange::HardSlash(e) => &e.account,
+            ProvisionerChange::DelegatedStake(e) => &e.account,
+            ProvisionerChange::UndelegatedStake(e) => &e.account,
         }
     }
 
@@ -162,6 +185,8 @@ impl ProvisionerChange {
             ProvisionerChange::Unstake(e) => e.value,
             ProvisionerChange::Slash(e) => e.value,
             ProvisionerChange::HardSlash(e) => e.value,
+            ProvisionerChange::DelegatedStake(e) => e.value,
+            ProvisionerChange::UndelegatedStake(e) => e.value,
         }
     }
 }
@@ -196,6 +221,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             provisioners_list.apply_changes(changed_provisioners);
         }
 
+        network.read().await.set_tip_height(tip_height);
+
         let acc = Self {
             tip: RwLock::new(tip),
             provisioners_list: RwLock::new(provisioners_list),
@@ -577,6 +604,21 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                         to_slash
                             .change_eligibility(slash_event.next_eligibility);
                     }
+                    ProvisionerChange::DelegatedStake(event) => {
+                        new_prov
+                            .get_member_mut(&account)
+                            .ok_or(anyhow::anyhow!(
+                                "Delegating to a not existing stake"
+                            ))?
+                            .add(event.value);
+                    }
+                    ProvisionerChange::UndelegatedStake(event) => {
+                        new_prov.sub_stake(&account, event.value).ok_or(
+                            anyhow::anyhow!(
+                                "Undelegating a not existing stake"
+                            ),
+                        )?;
+                    }
                 }
             }
             // Update new prov
@@ -629,6 +671,10 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         provisioners_list.apply_changes(changed_provisioners);
 
         *tip = BlockWithLabel::new_with_label(blk.clone(), label);
+        self.network
+            .read()
+            .await
+            .set_tip_height(blk.header().height);
 
         Ok(())
     }
@@ -687,6 +733,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
 
         let start = std::time::Instant::now();
         let mut est_elapsed_time = Duration::default();
+        let mut execution_metrics = vm::ExecutionMetrics::default();
         let mut block_size_on_disk = 0;
         let mut slashed_count: usize = 0;
         // Persist block in consistency with the VM state update
@@ -697,15 +744,18 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             let vm = self.vm.write().await;
 
             let (stakes, finality) = self.db.read().await.update(|db| {
-                let (txs, verification_output, stake_events) = vm.accept(
-                    prev_header.state_hash,
-                    blk,
-                    &prev_block_voters[..],
-                )?;
+                let (txs, verification_output, stake_events, metrics) = vm
+                    .accept(
+                        prev_header.state_hash,
+                        prev_header.seed,
+                        blk,
+                        &prev_block_voters[..],
+                    )?;
                 for spent_tx in txs.iter() {
                     events.push(TransactionEvent::Executed(spent_tx).into());
                 }
                 est_elapsed_time = start.elapsed();
+                execution_metrics = metrics;
 
                 assert_eq!(header.state_hash, verification_output.state_root);
                 assert_eq!(header.event_bloom, verification_output.event_bloom);
@@ -754,6 +804,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             let (label, final_results) = finality;
             // Update tip
             *tip = BlockWithLabel::new_with_label(blk.clone(), label);
+            self.network.read().await.set_tip_height(header.height);
 
             let finalized = final_results.is_some();
 
@@ -781,6 +832,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             block_time,
             block_size_on_disk,
             slashed_count,
+            &execution_metrics,
         );
 
         // Clean up the database
@@ -1222,6 +1274,19 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         self.task.read().await.outbound.clone()
     }
 
+    /// Returns the public key the consensus messages of the node are signed
+    /// with.
+    pub(crate) async fn get_consensus_pubkey(&self) -> PublicKey {
+        self.task.read().await.keys.1.clone()
+    }
+
+    pub(crate) async fn set_block_builder(
+        &self,
+        policy: Arc<dyn BlockBuilderPolicy>,
+    ) {
+        self.task.write().await.block_builder = policy;
+    }
+
     async fn adjust_round_base_timeouts(&self) -> TimeoutSet {
         let mut base_timeout_set = TimeoutSet::new();
 
@@ -1294,6 +1359,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         block_time: u64,
         block_size_on_disk: usize,
         slashed_count: usize,
+        execution_metrics: &vm::ExecutionMetrics,
     ) {
         // The Cumulative number of all executed transactions
         counter!("dusk_txn_count").increment(blk.txs().len() as u64);
@@ -1315,6 +1381,23 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         histogram!("dusk_slashed_count").record(slashed_count as f64);
 
         histogram!("dusk_block_disk_size").record(block_size_on_disk as f64);
+
+        // The gas spent by the transactions of the block, and the largest
+        // number of contract calls made by one of them
+        histogram!("dusk_block_gas_spent")
+            .record(execution_metrics.gas_spent as f64);
+        histogram!("dusk_block_max_calls")
+            .record(execution_metrics.max_calls as f64);
+
+        // The cumulative gas spent by, and number of calls to, the contracts
+        // consuming the most gas in each block
+        for consumer in &execution_metrics.top_consumers {
+            let contract = hex::encode(consumer.contract.as_bytes());
+            counter!("dusk_contract_gas_spent", "contract" => contract.clone())
+                .increment(consumer.gas_spent);
+            counter!("dusk_contract_calls", "contract" => contract)
+                .increment(consumer.calls);
+        }
     }
 
     /// Verifies if a block with header `local` can be replaced with a block
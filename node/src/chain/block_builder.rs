@@ -0,0 +1,197 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Selection of the mempool transactions a candidate block is built from.
+//!
+//! The block generator hands the mempool transactions, highest gas price
+//! first, to a [`BlockBuilderPolicy`], and executes them in the order the
+//! policy returns them until the block is full. The built-in policies are
+//! selected through the [`Params`] of the node.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use dusk_bytes::Serializable;
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use node_data::ledger::Transaction;
+use serde::{Deserialize, Serialize};
+
+/// The mempool transactions a block is built from.
+pub type Candidates<'a> = Box<dyn Iterator<Item = Transaction> + 'a>;
+
+/// Orders and filters the mempool transactions a candidate block is built
+/// from.
+pub trait BlockBuilderPolicy: Send + Sync {
+    /// Returns the transactions to execute in the block, in order, given the
+    /// mempool transactions sorted by gas price, the highest first.
+    ///
+    /// Transactions left out stay in the mempool.
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a>;
+}
+
+/// The default policy, selecting the transactions by gas price.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ByGasPrice;
+
+impl BlockBuilderPolicy for ByGasPrice {
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a> {
+        txs
+    }
+}
+
+/// Leaves out the transactions paying less than a minimum gas price.
+#[derive(Debug, Clone, Copy)]
+pub struct MinGasPrice(pub u64);
+
+impl BlockBuilderPolicy for MinGasPrice {
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a> {
+        Box::new(txs.filter(|tx| tx.gas_price() >= self.0))
+    }
+}
+
+/// Selects the transactions of the given Moonlight accounts before any
+/// other, keeping the order by gas price within each group.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityAccounts(pub HashSet<[u8; BlsPublicKey::SIZE]>);
+
+impl PriorityAccounts {
+    fn is_priority(&self, tx: &Transaction) -> bool {
+        tx.inner
+            .moonlight_sender()
+            .is_some_and(|pk| self.0.contains(&pk.to_bytes()))
+    }
+}
+
+impl BlockBuilderPolicy for PriorityAccounts {
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a> {
+        let (priority, others): (Vec<_>, Vec<_>) =
+            txs.partition(|tx| self.is_priority(tx));
+        Box::new(priority.into_iter().chain(others))
+    }
+}
+
+/// Leaves out the deployments past a maximum number per block.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxDeploys(pub usize);
+
+impl BlockBuilderPolicy for MaxDeploys {
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a> {
+        let mut deploys = 0;
+        Box::new(txs.filter(move |tx| {
+            if tx.inner.deploy().is_none() {
+                return true;
+            }
+            deploys += 1;
+            deploys <= self.0
+        }))
+    }
+}
+
+/// Applies policies one after the other, each one selecting from the
+/// transactions selected by the previous one.
+#[derive(Default, Clone)]
+pub struct Chained(pub Vec<Arc<dyn BlockBuilderPolicy>>);
+
+impl BlockBuilderPolicy for Chained {
+    fn select<'a>(&'a self, txs: Candidates<'a>) -> Candidates<'a> {
+        self.0.iter().fold(txs, |txs, policy| policy.select(txs))
+    }
+}
+
+/// Block builder configuration parameters, each one enabling a built-in
+/// policy.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Params {
+    /// Minimum gas price of the transactions included in a block
+    pub min_gas_price: Option<u64>,
+
+    /// Moonlight accounts, base58 encoded, whose transactions are included
+    /// before any other
+    #[serde(default)]
+    pub priority_accounts: Vec<String>,
+
+    /// Maximum number of deployments included in a block
+    pub max_deploys: Option<usize>,
+}
+
+impl Params {
+    /// Returns the policy configured, filtering by gas price first, then
+    /// prioritizing accounts and finally capping the deployments.
+    pub fn policy(&self) -> anyhow::Result<Arc<dyn BlockBuilderPolicy>> {
+        let mut policies: Vec<Arc<dyn BlockBuilderPolicy>> = vec![];
+
+        if let Some(min) = self.min_gas_price {
+            policies.push(Arc::new(MinGasPrice(min)));
+        }
+
+        if !self.priority_accounts.is_empty() {
+            let accounts = self
+                .priority_accounts
+                .iter()
+                .map(|account| {
+                    let bytes = bs58::decode(account).into_vec()?;
+                    let pk = BlsPublicKey::from_slice(&bytes).map_err(|e| {
+                        anyhow::anyhow!("invalid account {account}: {e:?}")
+                    })?;
+                    Ok(pk.to_bytes())
+                })
+                .collect::<anyhow::Result<_>>()?;
+            policies.push(Arc::new(PriorityAccounts(accounts)));
+        }
+
+        if let Some(max) = self.max_deploys {
+            policies.push(Arc::new(MaxDeploys(max)));
+        }
+
+        Ok(match policies.len() {
+            0 => Arc::new(ByGasPrice),
+            1 => policies.remove(0),
+            _ => Arc::new(Chained(policies)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use node_data::ledger::faker::gen_dummy_tx;
+
+    use super::*;
+
+    fn select(policy: &dyn BlockBuilderPolicy, gas_prices: &[u64]) -> Vec<u64> {
+        let txs: Vec<_> = gas_prices.iter().map(|p| gen_dummy_tx(*p)).collect();
+        policy
+            .select(Box::new(txs.into_iter()))
+            .map(|tx| tx.gas_price())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_policy() -> anyhow::Result<()> {
+        let policy = Params::default().policy()?;
+        assert_eq!(select(policy.as_ref(), &[5, 3, 1]), vec![5, 3, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_gas_price() -> anyhow::Result<()> {
+        let params = Params {
+            min_gas_price: Some(3),
+            ..Default::default()
+        };
+        let policy = params.policy()?;
+        assert_eq!(select(policy.as_ref(), &[5, 3, 1]), vec![5, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_priority_account() {
+        let params = Params {
+            priority_accounts: vec!["not an account".into()],
+            ..Default::default()
+        };
+        assert!(params.policy().is_err());
+    }
+}
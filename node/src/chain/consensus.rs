@@ -27,6 +27,7 @@ use tokio::sync::{oneshot, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, info, trace, warn};
 
+use crate::chain::block_builder::{BlockBuilderPolicy, ByGasPrice};
 use crate::chain::header_validation::Validator;
 use crate::chain::metrics::AverageElapsedTime;
 use crate::database::rocksdb::{
@@ -59,6 +60,9 @@ pub(crate) struct Task {
         dusk_core::signatures::bls::SecretKey,
         node_data::bls::PublicKey,
     ),
+
+    /// Selects the mempool transactions of the generated candidates
+    pub(crate) block_builder: Arc<dyn BlockBuilderPolicy>,
 }
 
 impl Task {
@@ -92,6 +96,7 @@ impl Task {
             running_task: None,
             task_id: 0,
             keys,
+            block_builder: Arc::new(ByGasPrice),
         })
     }
 
@@ -114,6 +119,7 @@ impl Task {
                 vm,
                 tip.header().clone(),
                 provisioners_list, // TODO: Avoid cloning
+                self.block_builder.clone(),
             )),
             Arc::new(Mutex::new(CandidateDB::new(db.clone()))),
         );
@@ -266,6 +272,7 @@ pub struct Executor<DB: database::DB, VM: vm::VMExecution> {
     vm: Arc<RwLock<VM>>,
     tip_header: ledger::Header,
     provisioners: ContextProvisioners,
+    block_builder: Arc<dyn BlockBuilderPolicy>,
 }
 
 impl<DB: database::DB, VM: vm::VMExecution> Executor<DB, VM> {
@@ -274,12 +281,14 @@ impl<DB: database::DB, VM: vm::VMExecution> Executor<DB, VM> {
         vm: &Arc<RwLock<VM>>,
         tip_header: ledger::Header,
         provisioners: ContextProvisioners,
+        block_builder: Arc<dyn BlockBuilderPolicy>,
     ) -> Self {
         Executor {
             db: db.clone(),
             vm: vm.clone(),
             tip_header,
             provisioners,
+            block_builder,
         }
     }
 }
@@ -325,7 +334,7 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
 
         let vm = self.vm.read().await;
 
-        vm.verify_state_transition(prev_root, blk, voters)
+        vm.verify_state_transition(prev_root, self.tip_header.seed, blk, voters)
     }
 
     async fn execute_state_transition(
@@ -341,6 +350,7 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
                 let txs = view.mempool_txs_sorted_by_fee().map_err(|err| {
                     anyhow::anyhow!("failed to get mempool txs: {}", err)
                 })?;
+                let txs = self.block_builder.select(txs);
                 let ret = vm.execute_state_transition(&params, txs).map_err(
                     |err| anyhow::anyhow!("failed to call EST {}", err),
                 )?;
@@ -0,0 +1,579 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Active/standby coordination of two nodes sharing the same provisioner key.
+//!
+//! Both nodes run consensus, but only the active one broadcasts the messages
+//! it signs. The nodes coordinate through a directory they share:
+//! - the active node writes a heartbeat to it, and the standby takes over when
+//!   the heartbeat stops for longer than the configured timeout;
+//! - every message signed by the node claims its (round, step) slot in a
+//!   signing lock before being broadcast, and a slot can only be claimed if it
+//!   is higher than the last claimed one. The messages of other provisioners
+//!   relayed by consensus are broadcast as they are.
+//!
+//! The signing lock makes it impossible for the two nodes to broadcast
+//! different messages for the same slot, even while both believe to be
+//! active.
+//!
+//! The shared directory is accessed with blocking IO, which is run on the
+//! blocking thread pool of the runtime.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use node_data::bls::PublicKey;
+use node_data::message::{Message, Payload};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const HEARTBEAT_FILE: &str = "heartbeat";
+const SIGNING_SLOT_FILE: &str = "signing_slot";
+const SIGNING_LOCK_FILE: &str = "signing_slot.lock";
+
+/// The role a node starts with.
+#[derive(
+    Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Signs from startup, unless another node is already active.
+    #[default]
+    Primary,
+    /// Signs only once the active node stops sending heartbeats.
+    Standby,
+}
+
+/// Failover configuration parameters
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Params {
+    /// The role of the node at startup
+    #[serde(default)]
+    pub role: Role,
+
+    /// The directory shared by the primary and the standby, holding the
+    /// heartbeat and the signing lock
+    pub shared_dir: PathBuf,
+
+    /// Interval between the heartbeats of the active node
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub heartbeat_interval: Option<Duration>,
+
+    /// Time without heartbeats after which the active node is considered
+    /// dead
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub heartbeat_timeout: Option<Duration>,
+}
+
+impl Params {
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.heartbeat_interval
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL)
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        self.heartbeat_timeout.unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT)
+    }
+}
+
+/// The heartbeat of the active node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Heartbeat {
+    /// Identifies the node instance, since both nodes share the same key
+    instance: u64,
+    /// Milliseconds since the Unix epoch
+    timestamp: u64,
+}
+
+impl Heartbeat {
+    fn parse(content: &str) -> Option<Self> {
+        let (instance, timestamp) = content.trim().split_once(' ')?;
+        Some(Self {
+            instance: instance.parse().ok()?,
+            timestamp: timestamp.parse().ok()?,
+        })
+    }
+}
+
+/// A slot a provisioner signs at most one message for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Slot {
+    round: u64,
+    step: u8,
+}
+
+impl Slot {
+    /// Returns the slot of a message signed by the node with the given key,
+    /// if any.
+    ///
+    /// Only the messages created by the node are considered, since consensus
+    /// also relays the messages received from the network, including those of
+    /// the other node sharing the key.
+    fn of(msg: &Message, signer: &PublicKey) -> Option<Self> {
+        match msg.payload {
+            Payload::Candidate(_)
+            | Payload::Validation(_)
+            | Payload::Ratification(_)
+                if msg.is_local()
+                    && msg.get_signer().as_ref() == Some(signer) =>
+            {
+                Some(Self {
+                    round: msg.header.round,
+                    step: msg.get_step(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let (round, step) = content.trim().split_once(' ')?;
+        Some(Self {
+            round: round.parse().ok()?,
+            step: step.parse().ok()?,
+        })
+    }
+}
+
+/// The highest slot signed by either node, stored in the shared directory.
+struct SigningLock {
+    slot_path: PathBuf,
+    lock_path: PathBuf,
+}
+
+impl SigningLock {
+    fn new(dir: &Path) -> Self {
+        Self {
+            slot_path: dir.join(SIGNING_SLOT_FILE),
+            lock_path: dir.join(SIGNING_LOCK_FILE),
+        }
+    }
+
+    /// Claims the given slot, returning `false` if it is not higher than the
+    /// last claimed one.
+    fn try_claim(&self, slot: Slot) -> io::Result<bool> {
+        let _guard = self.acquire()?;
+
+        let last = match fs::read_to_string(&self.slot_path) {
+            Ok(content) => Some(Slot::parse(&content).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "bad signing slot")
+            })?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        if last.is_some_and(|last| slot <= last) {
+            return Ok(false);
+        }
+
+        let content = format!("{} {}", slot.round, slot.step);
+        write_atomic(&self.slot_path, content.as_bytes())?;
+        Ok(true)
+    }
+
+    /// Acquires exclusive access to the signing slot, across nodes, blocking
+    /// until the other node releases it.
+    ///
+    /// The lock is an advisory lock of the OS, released when the returned
+    /// guard is dropped or the process holding it dies.
+    fn acquire(&self) -> io::Result<LockGuard> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.lock_path)?;
+        file.lock_exclusive()?;
+        Ok(LockGuard { file })
+    }
+}
+
+struct LockGuard {
+    file: File,
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Writes a file so that readers see either its previous or its new content.
+fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    // Both nodes write to the same directory
+    let tmp = path.with_extension(format!("{}.tmp", new_instance()));
+    let mut file = File::create(&tmp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    fs::rename(tmp, path)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Returns a new identifier of a node instance, never `0`.
+fn new_instance() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(1);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    let id = nanos ^ ((std::process::id() as u64) << 32);
+    id.wrapping_add(COUNTER.fetch_add(1, Ordering::SeqCst))
+        .max(1)
+}
+
+/// The failover state of a node.
+pub(crate) struct Failover {
+    params: Params,
+    /// The key the consensus messages of the node are signed with
+    signer: PublicKey,
+    instance: u64,
+    active: AtomicBool,
+    heartbeat_path: PathBuf,
+    lock: SigningLock,
+}
+
+impl Failover {
+    pub(crate) fn new(params: Params, signer: PublicKey) -> io::Result<Self> {
+        fs::create_dir_all(&params.shared_dir)?;
+
+        let heartbeat_path = params.shared_dir.join(HEARTBEAT_FILE);
+        let lock = SigningLock::new(&params.shared_dir);
+        let instance = new_instance();
+
+        info!(
+            event = "failover enabled",
+            role = ?params.role,
+            shared_dir = %params.shared_dir.display(),
+            instance,
+        );
+
+        Ok(Self {
+            params,
+            signer,
+            instance,
+            active: AtomicBool::new(false),
+            heartbeat_path,
+            lock,
+        })
+    }
+
+    pub(crate) fn heartbeat_interval(&self) -> Duration {
+        self.params.heartbeat_interval()
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Reads the heartbeat of the active node, if any.
+    fn read_heartbeat(&self) -> io::Result<Option<Heartbeat>> {
+        match fs::read_to_string(&self.heartbeat_path) {
+            Ok(content) => Ok(Heartbeat::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn write_heartbeat(&self) -> io::Result<()> {
+        let content = format!("{} {}", self.instance, now_millis());
+        write_atomic(&self.heartbeat_path, content.as_bytes())
+    }
+
+    /// Whether another node is active, according to the heartbeat.
+    fn other_is_alive(&self) -> io::Result<bool> {
+        let timeout = self.params.heartbeat_timeout().as_millis() as u64;
+        Ok(self.read_heartbeat()?.is_some_and(|hb| {
+            hb.instance != self.instance
+                && now_millis().saturating_sub(hb.timestamp) <= timeout
+        }))
+    }
+
+    /// Updates the role of the node, to be called every heartbeat interval.
+    ///
+    /// The active node sends its heartbeat, or steps down if another node has
+    /// taken over in the meantime. The standby takes over once the active
+    /// node has been silent for the heartbeat timeout. At startup, the
+    /// primary takes over immediately unless another node is active.
+    pub(crate) fn on_heartbeat(&self) -> io::Result<()> {
+        let other_is_alive = self.other_is_alive()?;
+
+        if self.is_active() {
+            if other_is_alive {
+                warn!(event = "failover: another node took over, standing by");
+                self.active.store(false, Ordering::SeqCst);
+                return Ok(());
+            }
+            return self.write_heartbeat();
+        }
+
+        if other_is_alive {
+            return Ok(());
+        }
+
+        let takeover = match self.params.role {
+            Role::Primary => true,
+            // Without any heartbeat, wait for a whole timeout in case the
+            // primary is starting up
+            Role::Standby => match self.read_heartbeat()? {
+                Some(_) => true,
+                None => {
+                    self.write_heartbeat_placeholder()?;
+                    false
+                }
+            },
+        };
+
+        if takeover {
+            info!(event = "failover: becoming active", role = ?self.params.role);
+            self.write_heartbeat()?;
+            self.active.store(true, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Writes a heartbeat of an unknown instance, so that the standby waits
+    /// for a whole timeout before taking over a node that never started.
+    fn write_heartbeat_placeholder(&self) -> io::Result<()> {
+        let content = format!("0 {}", now_millis());
+        write_atomic(&self.heartbeat_path, content.as_bytes())
+    }
+
+    /// Calls [`Self::on_heartbeat`] on the blocking thread pool.
+    pub(crate) async fn heartbeat(self: &Arc<Self>) -> io::Result<()> {
+        let failover = self.clone();
+        tokio::task::spawn_blocking(move || failover.on_heartbeat())
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Returns whether the given outbound message can be broadcast.
+    ///
+    /// Messages not signed by the node always are. Signed ones are only if
+    /// the node is active and their slot can be claimed in the signing lock.
+    pub(crate) async fn may_broadcast(self: &Arc<Self>, msg: &Message) -> bool {
+        let Some(slot) = Slot::of(msg, &self.signer) else {
+            return true;
+        };
+        if !self.is_active() {
+            return false;
+        }
+
+        let failover = self.clone();
+        let claimed =
+            tokio::task::spawn_blocking(move || failover.lock.try_claim(slot))
+                .await
+                .map_err(io::Error::other)
+                .and_then(|claimed| claimed);
+
+        match claimed {
+            Ok(claimed) => {
+                if !claimed {
+                    warn!(
+                        event = "failover: slot already signed",
+                        round = slot.round,
+                        step = slot.step,
+                    );
+                }
+                claimed
+            }
+            Err(err) => {
+                warn!(event = "failover: cannot claim slot", ?err);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use node_data::message::payload::{Validation, Vote};
+    use node_data::message::{ConsensusHeader, Metadata, SignInfo};
+
+    use super::*;
+
+    fn params(dir: &Path, role: Role) -> Params {
+        Params {
+            role,
+            shared_dir: dir.to_path_buf(),
+            heartbeat_interval: None,
+            heartbeat_timeout: Some(Duration::from_millis(200)),
+        }
+    }
+
+    fn signer(seed: u64) -> PublicKey {
+        PublicKey::from_sk_seed_u64(seed)
+    }
+
+    fn validation(signer: PublicKey, round: u64) -> Message {
+        Validation {
+            header: ConsensusHeader {
+                round,
+                ..Default::default()
+            },
+            vote: Vote::NoCandidate,
+            sign_info: SignInfo {
+                signer,
+                ..Default::default()
+            },
+        }
+        .into()
+    }
+
+    fn relayed(mut msg: Message) -> Message {
+        msg.metadata = Some(Metadata {
+            height: 0,
+            src_addr: "127.0.0.1:9000".parse().expect("valid address"),
+            ray_id: String::new(),
+        });
+        msg
+    }
+
+    #[tokio::test]
+    async fn only_own_messages_claim_slots() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let failover = Arc::new(Failover::new(
+            params(dir.path(), Role::Primary),
+            signer(0),
+        )?);
+        failover.on_heartbeat()?;
+        assert!(failover.is_active());
+
+        // The messages of other provisioners relayed by consensus, even for
+        // future rounds, don't claim any slot
+        assert!(
+            failover
+                .may_broadcast(&relayed(validation(signer(1), 1)))
+                .await
+        );
+        assert!(
+            failover
+                .may_broadcast(&relayed(validation(signer(1), 5)))
+                .await
+        );
+
+        // Nor do the messages of the other node sharing the key
+        assert!(
+            failover
+                .may_broadcast(&relayed(validation(signer(0), 1)))
+                .await
+        );
+
+        // So that the node can still sign its own message for the same slot,
+        // but only once
+        assert!(failover.may_broadcast(&validation(signer(0), 1)).await);
+        assert!(!failover.may_broadcast(&validation(signer(0), 1)).await);
+
+        // The standby doesn't broadcast its own messages, but relays the
+        // others
+        let standby = Arc::new(Failover::new(
+            params(dir.path(), Role::Standby),
+            signer(0),
+        )?);
+        standby.on_heartbeat()?;
+        assert!(!standby.is_active());
+        assert!(
+            standby
+                .may_broadcast(&relayed(validation(signer(1), 2)))
+                .await
+        );
+        assert!(!standby.may_broadcast(&validation(signer(0), 2)).await);
+        Ok(())
+    }
+
+    #[test]
+    fn signing_slots_are_monotonic() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let lock = SigningLock::new(dir.path());
+
+        assert!(lock.try_claim(Slot { round: 1, step: 0 })?);
+        assert!(lock.try_claim(Slot { round: 1, step: 1 })?);
+        assert!(!lock.try_claim(Slot { round: 1, step: 1 })?);
+        assert!(!lock.try_claim(Slot { round: 1, step: 0 })?);
+        assert!(lock.try_claim(Slot { round: 2, step: 0 })?);
+
+        // A second node sharing the directory sees the same slots
+        let other = SigningLock::new(dir.path());
+        assert!(!other.try_claim(Slot { round: 2, step: 0 })?);
+        assert!(other.try_claim(Slot { round: 2, step: 1 })?);
+        Ok(())
+    }
+
+    #[test]
+    fn signing_slots_are_claimed_once_concurrently() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let slot = Slot { round: 1, step: 0 };
+
+        let claims = (0..8)
+            .map(|_| {
+                let lock = SigningLock::new(dir.path());
+                std::thread::spawn(move || lock.try_claim(slot))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|h| h.join().expect("thread not to panic"))
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(claims.iter().filter(|claimed| **claimed).count(), 1);
+
+        // The lock is released once a claim is over
+        let lock = SigningLock::new(dir.path());
+        drop(lock.acquire()?);
+        assert!(lock.try_claim(Slot { round: 1, step: 1 })?);
+        Ok(())
+    }
+
+    #[test]
+    fn standby_takes_over_dead_primary() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let primary =
+            Failover::new(params(dir.path(), Role::Primary), signer(0))?;
+        let standby =
+            Failover::new(params(dir.path(), Role::Standby), signer(0))?;
+
+        primary.on_heartbeat()?;
+        standby.on_heartbeat()?;
+        assert!(primary.is_active());
+        assert!(!standby.is_active());
+
+        // The primary stops sending heartbeats
+        std::thread::sleep(Duration::from_millis(300));
+        standby.on_heartbeat()?;
+        assert!(standby.is_active());
+
+        // The primary comes back and stands by
+        primary.on_heartbeat()?;
+        assert!(!primary.is_active());
+        Ok(())
+    }
+
+    #[test]
+    fn standby_waits_for_timeout_at_startup() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let standby =
+            Failover::new(params(dir.path(), Role::Standby), signer(0))?;
+
+        standby.on_heartbeat()?;
+        assert!(!standby.is_active());
+
+        std::thread::sleep(Duration::from_millis(300));
+        standby.on_heartbeat()?;
+        assert!(standby.is_active());
+        Ok(())
+    }
+}
@@ -858,7 +858,7 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
         match data {
             // None has a meaning key not found
             None => Ok(None),
-            Some(blob) => Ok(Some(Transaction::read(&mut &blob.to_vec()[..])?)),
+            Some(blob) => Ok(Some(Transaction::read_slice(&mut &blob[..])?)),
         }
     }
 
@@ -92,6 +92,10 @@ pub trait Network: Send + Sync + 'static {
     /// Retrieves number of alive nodes
     async fn alive_nodes_count(&self) -> usize;
 
+    /// Notifies the height of the chain tip, e.g. to switch the version of
+    /// the wire protocol at its activation height.
+    fn set_tip_height(&self, _height: u64) {}
+
     async fn wait_for_alive_nodes(&self, amount: usize, timeout: Duration) {
         let start = Instant::now();
         while self.alive_nodes_count().await < amount {
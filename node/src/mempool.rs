@@ -6,12 +6,14 @@
 
 pub mod conf;
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use conf::{
     DEFAULT_DOWNLOAD_REDUNDANCY, DEFAULT_EXPIRY_TIME, DEFAULT_IDLE_INTERVAL,
+    DEFAULT_REBROADCAST_INTERVAL,
 };
 use node_data::events::{Event, TransactionEvent};
 use node_data::get_current_timestamp;
@@ -60,6 +62,8 @@ pub struct MempoolSrv {
     conf: Params,
     /// Sender channel for sending out RUES events
     event_sender: Sender<Event>,
+    /// Transactions submitted to this node, re-broadcast until included
+    local_txs: HashSet<[u8; 32]>,
 }
 
 impl MempoolSrv {
@@ -72,6 +76,7 @@ impl MempoolSrv {
             ),
             conf,
             event_sender,
+            local_txs: HashSet::new(),
         }
     }
 }
@@ -106,8 +111,15 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
             .unwrap_or(DEFAULT_EXPIRY_TIME)
             .as_secs();
 
+        let rebroadcast_interval = self
+            .conf
+            .rebroadcast_interval
+            .unwrap_or(DEFAULT_REBROADCAST_INTERVAL);
+
         // Mempool service loop
         let mut on_idle_event = tokio::time::interval(idle_interval);
+        let mut on_rebroadcast_event =
+            tokio::time::interval(rebroadcast_interval);
         loop {
             tokio::select! {
                 biased;
@@ -141,6 +153,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                         Ok(())
                     })?;
 
+                    self.remove_stale_txs(&db, &vm).await?;
+                },
+                _ = on_rebroadcast_event.tick() => {
+                    self.rebroadcast_local_txs(&db, &network).await?;
                 },
                 msg = self.inbound.recv() => {
                     if let Ok(msg) = msg {
@@ -152,6 +168,10 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                                     continue;
                                 }
 
+                                if msg.is_local() {
+                                    self.local_txs.insert(tx.id());
+                                }
+
                                 let network = network.read().await;
                                 if let Err(e) = network.broadcast(&msg).await {
                                     warn!("Unable to broadcast accepted tx: {e}")
@@ -339,6 +359,85 @@ impl MempoolSrv {
         Ok(events)
     }
 
+    /// Removes from the mempool the transactions that can no longer be
+    /// included in a block, along with the ones depending on them.
+    async fn remove_stale_txs<DB: database::DB, VM: vm::VMExecution>(
+        &mut self,
+        db: &Arc<RwLock<DB>>,
+        vm: &Arc<RwLock<VM>>,
+    ) -> anyhow::Result<()> {
+        let vm = vm.read().await;
+        let db = db.read().await;
+
+        let stale_txs = db.view(|view| {
+            let stale_txs = view
+                .mempool_txs_sorted_by_fee()?
+                .filter(|tx| {
+                    vm.is_stale(tx).unwrap_or_else(|e| {
+                        warn!("cannot check if tx is stale: {e}");
+                        false
+                    })
+                })
+                .map(|tx| tx.id())
+                .collect::<Vec<_>>();
+            anyhow::Ok(stale_txs)
+        })?;
+
+        db.update(|db| {
+            for tx_id in stale_txs {
+                info!(event = "stale_tx", hash = hex::encode(tx_id));
+                let deleted_txs =
+                    db.delete_mempool_tx(tx_id, true).unwrap_or_else(|e| {
+                        error!("cannot delete stale tx: {e}");
+                        vec![]
+                    });
+                for deleted_tx_id in deleted_txs {
+                    let event = TransactionEvent::Removed(deleted_tx_id);
+                    info!(
+                        event = "mempool_deleted",
+                        hash = hex::encode(deleted_tx_id)
+                    );
+                    if let Err(e) = self.event_sender.try_send(event.into()) {
+                        warn!("cannot notify mempool removed transaction {e}")
+                    };
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Re-broadcasts the locally submitted transactions still in the
+    /// mempool, forgetting the ones that left it.
+    async fn rebroadcast_local_txs<N: Network, DB: database::DB>(
+        &mut self,
+        db: &Arc<RwLock<DB>>,
+        network: &Arc<RwLock<N>>,
+    ) -> anyhow::Result<()> {
+        if self.local_txs.is_empty() {
+            return Ok(());
+        }
+
+        let txs = db.read().await.view(|view| {
+            let mut txs = vec![];
+            for tx_id in &self.local_txs {
+                if let Some(tx) = view.mempool_tx(*tx_id)? {
+                    txs.push(tx);
+                }
+            }
+            anyhow::Ok(txs)
+        })?;
+        self.local_txs = txs.iter().map(|tx| tx.id()).collect();
+
+        info!(event = "mempool_rebroadcast", count = txs.len());
+        let network = network.read().await;
+        for tx in txs {
+            if let Err(e) = network.broadcast(&Message::from(tx)).await {
+                warn!("Unable to re-broadcast tx: {e}")
+            };
+        }
+        Ok(())
+    }
+
     /// Requests full mempool data from N alive peers
     ///
     /// Message flow:
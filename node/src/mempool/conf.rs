@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 pub const DEFAULT_EXPIRY_TIME: Duration = Duration::from_secs(3 * 60 * 60 * 24); /* 3 days */
 pub const DEFAULT_IDLE_INTERVAL: Duration = Duration::from_secs(60 * 60); /* 1 hour */
 pub const DEFAULT_DOWNLOAD_REDUNDANCY: usize = 5;
+pub const DEFAULT_REBROADCAST_INTERVAL: Duration = Duration::from_secs(10 * 60); /* 10 minutes */
 
 #[derive(Serialize, Deserialize, Copy, Clone)]
 pub struct Params {
@@ -32,6 +33,12 @@ pub struct Params {
 
     /// max number of peers to request mempool from
     pub mempool_download_redundancy: Option<usize>,
+
+    /// Interval to re-broadcast the locally submitted transactions not yet
+    /// included in a block
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub rebroadcast_interval: Option<Duration>,
 }
 
 impl Default for Params {
@@ -42,6 +49,7 @@ impl Default for Params {
             idle_interval: Some(DEFAULT_IDLE_INTERVAL),
             mempool_expiry: Some(DEFAULT_EXPIRY_TIME),
             mempool_download_redundancy: Some(DEFAULT_DOWNLOAD_REDUNDANCY),
+            rebroadcast_interval: Some(DEFAULT_REBROADCAST_INTERVAL),
         }
     }
 }
@@ -51,12 +59,14 @@ impl std::fmt::Display for Params {
         write!(
             f,
             "max_queue_size: {}, max_mempool_txn_count: {},
-         idle_interval: {:?}, mempool_expiry: {:?}, mempool_download_redundancy: {:?}",
+         idle_interval: {:?}, mempool_expiry: {:?}, mempool_download_redundancy: {:?},
+         rebroadcast_interval: {:?}",
             self.max_queue_size,
             self.max_mempool_txn_count,
             self.idle_interval,
             self.mempool_expiry,
-            self.mempool_download_redundancy
+            self.mempool_download_redundancy,
+            self.rebroadcast_interval
         )
     }
 }
@@ -4,6 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::io;
 use std::net::{AddrParseError, SocketAddr};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -13,7 +14,9 @@ use kadcast::config::Config;
 use kadcast::{MessageInfo, Peer};
 use metrics::counter;
 use node_data::message::payload::{GetResource, Inv, Nonce};
-use node_data::message::{AsyncQueue, Metadata, PROTOCOL_VERSION};
+use node_data::message::{
+    AsyncQueue, Metadata, Version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION,
+};
 use node_data::{get_current_timestamp, Serializable};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, trace, warn};
@@ -58,7 +61,7 @@ impl<const N: usize> Listener<N> {
 impl<const N: usize> kadcast::NetworkListen for Listener<N> {
     fn on_message(&self, blob: Vec<u8>, md: MessageInfo) {
         let msg_size = blob.len();
-        match Message::read(&mut &blob.to_vec()[..]) {
+        match Message::read_slice(&mut &blob[..]) {
             Ok(mut msg) => {
                 counter!("dusk_bytes_recv").increment(msg_size as u64);
                 counter!(format!("dusk_inbound_{:?}_size", msg.topic()))
@@ -117,6 +120,13 @@ pub struct Kadcast<const N: usize> {
     public_addr: SocketAddr,
 
     counter: AtomicU64,
+
+    /// The height from which the messages are encoded with
+    /// [`PROTOCOL_VERSION`] instead of [`MIN_PROTOCOL_VERSION`], if any
+    activation_height: Option<u64>,
+
+    /// The height of the chain tip, as last notified by the chain
+    tip_height: AtomicU64,
 }
 
 impl<const N: usize> Kadcast<N> {
@@ -136,7 +146,8 @@ impl<const N: usize> Kadcast<N> {
             filters: filters.clone(),
         };
         conf.version = format!("{PROTOCOL_VERSION}");
-        conf.version_match = format!("{PROTOCOL_VERSION}");
+        // Peers are accepted as long as their messages can be decoded
+        conf.version_match = format!("{MIN_PROTOCOL_VERSION}");
         let peer = Peer::new(conf.clone(), listener)?;
         let public_addr = conf
             .public_address
@@ -152,9 +163,40 @@ impl<const N: usize> Kadcast<N> {
             conf,
             public_addr,
             counter: AtomicU64::new(nonce.into()),
+            activation_height: None,
+            tip_height: AtomicU64::new(0),
         })
     }
 
+    /// Encode the messages with [`PROTOCOL_VERSION`] once the chain tip
+    /// reaches the given height.
+    ///
+    /// Until then, and if no height is set, the messages are encoded with
+    /// [`MIN_PROTOCOL_VERSION`] to be understood by the nodes not upgraded
+    /// yet.
+    pub fn with_activation_height(mut self, height: u64) -> Self {
+        self.activation_height = Some(height);
+        self
+    }
+
+    /// Returns the protocol version the messages are encoded with at the
+    /// current chain tip
+    pub fn wire_version(&self) -> Version {
+        let tip_height = self.tip_height.load(Ordering::Relaxed);
+        match self.activation_height {
+            Some(height) if tip_height >= height => PROTOCOL_VERSION,
+            _ => MIN_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Encode a message with the current wire version, regardless of the
+    /// version it was received with when relayed.
+    fn encode(&self, msg: &Message) -> io::Result<Vec<u8>> {
+        let mut encoded = vec![];
+        msg.write_versioned(&self.wire_version(), &mut encoded)?;
+        Ok(encoded)
+    }
+
     pub fn route_internal(&self, msg: Message) {
         let topic = msg.topic() as usize;
         let routes = self.routes.clone();
@@ -215,8 +257,7 @@ impl<const N: usize> crate::Network for Kadcast<N> {
             None => None,
         };
 
-        let mut encoded = vec![];
-        msg.write(&mut encoded).map_err(|err| {
+        let encoded = self.encode(msg).map_err(|err| {
             error!("could not encode message {msg:?}: {err}");
             anyhow::anyhow!("failed to broadcast: {err}")
         })?;
@@ -273,8 +314,8 @@ impl<const N: usize> crate::Network for Kadcast<N> {
 
         msg.payload.set_nonce(rnd_count);
 
-        let mut encoded = vec![];
-        msg.write(&mut encoded)
+        let encoded = self
+            .encode(&msg)
             .map_err(|err| anyhow::anyhow!("failed to send_to_peer: {err}"))?;
         let topic = msg.topic();
 
@@ -301,8 +342,8 @@ impl<const N: usize> crate::Network for Kadcast<N> {
 
         msg.payload.set_nonce(rnd_count);
 
-        let mut encoded = vec![];
-        msg.write(&mut encoded)
+        let encoded = self
+            .encode(&msg)
             .map_err(|err| anyhow::anyhow!("failed to encode: {err}"))?;
         let topic = msg.topic();
 
@@ -385,4 +426,8 @@ impl<const N: usize> crate::Network for Kadcast<N> {
         // TODO: This call should be replaced with no-copy Kadcast API
         self.peer.alive_nodes(u16::MAX as usize).await.len()
     }
+
+    fn set_tip_height(&self, height: u64) {
+        self.tip_height.store(height, Ordering::Relaxed);
+    }
 }
@@ -8,10 +8,11 @@ use dusk_consensus::errors::VstError;
 use dusk_consensus::operations::{CallParams, VerificationOutput, Voter};
 use dusk_consensus::user::provisioners::Provisioners;
 use dusk_consensus::user::stake::Stake;
+use dusk_core::abi::ContractId;
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::transfer::moonlight::AccountData;
 use node_data::events::contract::ContractEvent;
-use node_data::ledger::{Block, SpentTransaction, Transaction};
+use node_data::ledger::{Block, Seed, SpentTransaction, Transaction};
 
 #[derive(Default)]
 pub struct Config {}
@@ -30,6 +31,7 @@ pub trait VMExecution: Send + Sync + 'static {
     fn verify_state_transition(
         &self,
         prev_root: [u8; 32],
+        prev_seed: Seed,
         blk: &Block,
         voters: &[Voter],
     ) -> Result<VerificationOutput, VstError>;
@@ -37,12 +39,14 @@ pub trait VMExecution: Send + Sync + 'static {
     fn accept(
         &self,
         prev_root: [u8; 32],
+        prev_seed: Seed,
         blk: &Block,
         voters: &[Voter],
     ) -> anyhow::Result<(
         Vec<SpentTransaction>,
         VerificationOutput,
         Vec<ContractEvent>,
+        ExecutionMetrics,
     )>;
 
     fn finalize_state(
@@ -56,6 +60,11 @@ pub trait VMExecution: Send + Sync + 'static {
         tx: &Transaction,
     ) -> anyhow::Result<PreverificationResult>;
 
+    /// Returns whether the transaction can no longer be included in a block,
+    /// since it spends nullifiers already spent or uses a nonce already used
+    /// in the current state.
+    fn is_stale(&self, tx: &Transaction) -> anyhow::Result<bool>;
+
     fn get_provisioners(
         &self,
         base_commit: [u8; 32],
@@ -90,6 +99,30 @@ pub trait VMExecution: Send + Sync + 'static {
     fn min_deploy_points(&self) -> u64;
 }
 
+/// Execution statistics of the transactions of an accepted block.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecutionMetrics {
+    /// The total gas spent by the transactions.
+    pub gas_spent: u64,
+    /// The number of transactions executed.
+    pub transactions: u64,
+    /// The largest number of contract calls made by a single transaction.
+    pub max_calls: u64,
+    /// The contracts that spent the most gas, in descending order.
+    pub top_consumers: Vec<ContractMetrics>,
+}
+
+/// Execution statistics of a single contract over a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractMetrics {
+    /// The contract called.
+    pub contract: ContractId,
+    /// The gas spent in calls to the contract.
+    pub gas_spent: u64,
+    /// The number of times the contract was called.
+    pub calls: u64,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum PreverificationResult {
     Valid,
@@ -4,8 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use std::fs::{self, read, File};
-use std::io::prelude::*;
+use std::fs::{self, read};
 use std::io::{self, ErrorKind};
 use std::path::PathBuf;
 
@@ -14,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 use version_check::Version;
 
+use crate::lock::{write_atomic, DirLock};
 use crate::{
     extension, file_name, file_stem, get_rusk_circuits_dir, get_rusk_keys_dir,
     Theme,
@@ -55,6 +55,7 @@ impl Circuit {
     /// Attempt to create a new [`Circuit`] from local storage
     pub fn from_stored(id: [u8; 32]) -> io::Result<Self> {
         let mut file = get_rusk_circuits_dir()?;
+        let _lock = DirLock::shared(&file)?;
         let id_str = hex::encode(id);
         file.push(&id_str);
         file.set_extension("cd");
@@ -88,7 +89,10 @@ impl Circuit {
     /// Attempts to create a new [`Circuit`] from local storage by searching
     /// for the circuit name in the local toml files
     pub fn from_name(name: impl AsRef<str>) -> io::Result<Self> {
-        let id = search_id(name.as_ref())?;
+        let id = {
+            let _lock = DirLock::shared(&get_rusk_circuits_dir()?)?;
+            search_id(name.as_ref())?
+        };
         Circuit::from_stored(id)
     }
 
@@ -111,14 +115,16 @@ impl Circuit {
     /// metadata) or updates it if it exists but is different from the
     /// description in the struct
     pub fn store(&self) -> io::Result<()> {
+        let mut file = get_rusk_circuits_dir()?;
+        let _lock = DirLock::exclusive(&file)?;
+
         // store matadata
         self.metadata.update_or_store(&self.id)?;
 
         // store circuit
-        let mut file = get_rusk_circuits_dir()?;
         file.push(self.id_str());
         let cd_file = file.with_extension("cd");
-        File::create(&cd_file)?.write_all(&self.circuit)?;
+        write_atomic(&cd_file, &self.circuit)?;
         info!(
             "{}   {}",
             Theme::default().info("Cached"),
@@ -163,6 +169,7 @@ impl Circuit {
     /// Fetches the prover key if stored in the keys directory
     pub fn get_prover(&self) -> io::Result<Vec<u8>> {
         let mut file = get_rusk_keys_dir()?;
+        let _lock = DirLock::shared(&file)?;
         file.push(self.id_str());
         file.set_extension("pk");
 
@@ -179,6 +186,7 @@ impl Circuit {
     /// Fetches the verifier data if stored in the keys directory
     pub fn get_verifier(&self) -> io::Result<Vec<u8>> {
         let mut file = get_rusk_keys_dir()?;
+        let _lock = DirLock::shared(&file)?;
         file.push(self.id_str());
         file.set_extension("vd");
 
@@ -201,13 +209,14 @@ impl Circuit {
     /// Stores the given prover key and verifier data
     pub fn add_keys(&self, pk: Vec<u8>, vd: Vec<u8>) -> io::Result<()> {
         let mut file = get_rusk_keys_dir()?;
+        let _lock = DirLock::exclusive(&file)?;
         file.push(self.id_str());
 
         let pk_file = file.with_extension("pk");
         let vd_file = file.with_extension("vd");
 
-        File::create(pk_file)?.write_all(&pk)?;
-        File::create(vd_file)?.write_all(&vd)?;
+        write_atomic(&pk_file, &pk)?;
+        write_atomic(&vd_file, &vd)?;
 
         Ok(())
     }
@@ -216,12 +225,13 @@ impl Circuit {
     pub fn clean(&self) -> io::Result<()> {
         // collect all files with the circuit id as the file stem in circuits
         // directory
-        let circuit_files: Vec<PathBuf> =
-            fs::read_dir(get_rusk_circuits_dir()?)?
-                .flatten()
-                .map(|entry| entry.path())
-                .filter(|file| file_stem(file) == Some(self.id_str()))
-                .collect();
+        let circuits_dir = get_rusk_circuits_dir()?;
+        let lock = DirLock::exclusive(&circuits_dir)?;
+        let circuit_files: Vec<PathBuf> = fs::read_dir(&circuits_dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|file| file_stem(file) == Some(self.id_str()))
+            .collect();
 
         for file in circuit_files {
             info!(
@@ -231,10 +241,13 @@ impl Circuit {
             );
             fs::remove_file(file)?;
         }
+        drop(lock);
 
         // collect all files with the circuit id as the file stem in keys
         // directory
-        let keys_files: Vec<PathBuf> = fs::read_dir(get_rusk_keys_dir()?)?
+        let keys_dir = get_rusk_keys_dir()?;
+        let _lock = DirLock::exclusive(&keys_dir)?;
+        let keys_files: Vec<PathBuf> = fs::read_dir(&keys_dir)?
             .flatten()
             .map(|entry| entry.path())
             .filter(|file| file_stem(file) == Some(self.id_str()))
@@ -329,7 +342,7 @@ impl Metadata {
                 format!("Couldn't create string from metadata: {e}"),
             )
         })?;
-        File::create(&file)?.write_all(toml.as_bytes())?;
+        write_atomic(&file, toml.as_bytes())?;
 
         Ok(())
     }
@@ -0,0 +1,251 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Download of missing artifacts from remote mirrors.
+//!
+//! A mirror serves the artifacts next to a `SHA256SUMS` manifest, listing
+//! the SHA-256 of each artifact in the format of `sha256sum`, and a
+//! `SHA256SUMS.sig` file with the ed25519 signature of the manifest by the
+//! release key. An artifact is only installed if the manifest is signed by
+//! the release key and the artifact matches the hash it lists.
+
+use std::collections::HashMap;
+use std::io::{self, ErrorKind};
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use http_req::request;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::{
+    get_common_reference_string, set_common_reference_string,
+    verify_common_reference_string, Circuit, Theme, CRS_FNAME,
+};
+
+const MANIFEST_FNAME: &str = "SHA256SUMS";
+const SIGNATURE_FNAME: &str = "SHA256SUMS.sig";
+
+const MAX_REDIRECT: usize = 3;
+
+/// Downloads the artifacts missing from the profile from a list of mirrors,
+/// tried in order.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    mirrors: Vec<String>,
+    release_key: VerifyingKey,
+}
+
+impl Fetcher {
+    /// Create a new [`Fetcher`] from the URLs of the mirrors and the public
+    /// key the releases are signed with.
+    ///
+    /// Only HTTPS mirrors are accepted.
+    pub fn new(
+        mirrors: Vec<String>,
+        release_key: [u8; 32],
+    ) -> io::Result<Self> {
+        if let Some(mirror) = mirrors
+            .iter()
+            .find(|mirror| !mirror.starts_with("https://"))
+        {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Mirror {mirror} is not served over HTTPS"),
+            ));
+        }
+        let release_key = VerifyingKey::from_bytes(&release_key)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+
+        Ok(Self {
+            mirrors,
+            release_key,
+        })
+    }
+
+    /// Download the CRS, unless a valid one is already in the profile.
+    pub fn fetch_crs(&self) -> io::Result<()> {
+        if let Ok(crs) = get_common_reference_string() {
+            if verify_common_reference_string(&crs) {
+                return Ok(());
+            }
+        }
+
+        let mut files = self.fetch(&[CRS_FNAME])?;
+        set_common_reference_string(files.remove(0))
+    }
+
+    /// Download the prover key and verifier data of the given circuit,
+    /// unless they are already in the profile.
+    pub fn fetch_keys(&self, circuit: &Circuit) -> io::Result<()> {
+        if circuit.get_keys().is_ok() {
+            return Ok(());
+        }
+
+        let pk = format!("{}.pk", circuit.id_str());
+        let vd = format!("{}.vd", circuit.id_str());
+        let mut files = self.fetch(&[&pk, &vd])?;
+        let vd = files.remove(1);
+        let pk = files.remove(0);
+
+        circuit.add_keys(pk, vd)?;
+        info!("{}   {}", Theme::default().info("Fetched"), circuit.name());
+
+        Ok(())
+    }
+
+    /// Download the given files from the first mirror serving all of them
+    /// verified.
+    fn fetch(&self, names: &[&str]) -> io::Result<Vec<Vec<u8>>> {
+        for mirror in &self.mirrors {
+            info!(
+                "{} {names:?} from {mirror}",
+                Theme::default().action("Fetching")
+            );
+            match self.fetch_from(mirror, names) {
+                Ok(files) => return Ok(files),
+                Err(e) => {
+                    warn!("{} {mirror}: {e}", Theme::default().warn("Skipping"))
+                }
+            }
+        }
+
+        Err(io::Error::new(
+            ErrorKind::NotFound,
+            format!("No mirror serves {names:?}"),
+        ))
+    }
+
+    fn fetch_from(
+        &self,
+        mirror: &str,
+        names: &[&str],
+    ) -> io::Result<Vec<Vec<u8>>> {
+        let mirror = mirror.trim_end_matches('/');
+
+        let manifest = download(format!("{mirror}/{MANIFEST_FNAME}"))?;
+        let signature = download(format!("{mirror}/{SIGNATURE_FNAME}"))?;
+        let hashes = self.verify_manifest(&manifest, &signature)?;
+
+        names
+            .iter()
+            .map(|name| {
+                let expected = hashes.get(*name).ok_or_else(|| {
+                    io::Error::new(
+                        ErrorKind::NotFound,
+                        format!("{name} is not in the manifest"),
+                    )
+                })?;
+
+                let file = download(format!("{mirror}/{name}"))?;
+                if &hex::encode(Sha256::digest(&file)) != expected {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{name} doesn't match the manifest"),
+                    ));
+                }
+                Ok(file)
+            })
+            .collect()
+    }
+
+    /// Verify the signature of the manifest, returning the hashes it lists
+    /// by file name.
+    fn verify_manifest(
+        &self,
+        manifest: &[u8],
+        signature: &[u8],
+    ) -> io::Result<HashMap<String, String>> {
+        let signature = Signature::from_slice(signature)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        self.release_key
+            .verify(manifest, &signature)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+
+        let manifest = std::str::from_utf8(manifest)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        let hashes = manifest
+            .lines()
+            .filter_map(|line| {
+                let (hash, name) = line.split_once(char::is_whitespace)?;
+                // `sha256sum` marks files hashed in binary mode with a `*`
+                let name = name.trim_start().trim_start_matches('*');
+                Some((name.to_string(), hash.to_lowercase()))
+            })
+            .collect();
+
+        Ok(hashes)
+    }
+}
+
+fn download(uri: String) -> io::Result<Vec<u8>> {
+    download_with_redirect(uri, MAX_REDIRECT)
+}
+
+fn download_with_redirect(
+    uri: String,
+    redirect_left: usize,
+) -> io::Result<Vec<u8>> {
+    let mut buffer = vec![];
+
+    let response = request::get(&uri, &mut buffer)
+        .map_err(|e| io::Error::new(ErrorKind::Other, e.to_string()))?;
+    let sc = response.status_code();
+    if sc.is_success() {
+        return Ok(buffer);
+    }
+    if sc.is_redirect() && redirect_left > 1 {
+        if let Some(location) = response.headers().get("location") {
+            if location.starts_with("https://") {
+                return download_with_redirect(
+                    location.to_string(),
+                    redirect_left - 1,
+                );
+            }
+        }
+    }
+
+    Err(io::Error::new(
+        ErrorKind::Other,
+        format!("Download of {uri} failed: {}", response.reason()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    #[test]
+    fn manifest_verification() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let fetcher = Fetcher::new(
+            vec!["https://example.com".into()],
+            signing_key.verifying_key().to_bytes(),
+        )
+        .expect("fetcher to be valid");
+
+        let hash = hex::encode(Sha256::digest(b"keys"));
+        let manifest = format!("{hash}  a.pk\n{hash} *a.vd\n");
+        let signature = signing_key.sign(manifest.as_bytes()).to_bytes();
+
+        let hashes = fetcher
+            .verify_manifest(manifest.as_bytes(), &signature)
+            .expect("manifest to be verified");
+        assert_eq!(hashes.get("a.pk"), Some(&hash));
+        assert_eq!(hashes.get("a.vd"), Some(&hash));
+
+        let tampered = manifest.replace("a.pk", "b.pk");
+        assert!(fetcher
+            .verify_manifest(tampered.as_bytes(), &signature)
+            .is_err());
+
+        assert!(
+            Fetcher::new(vec!["http://example.com".into()], [0; 32]).is_err(),
+            "plain HTTP mirrors should be rejected"
+        );
+    }
+}
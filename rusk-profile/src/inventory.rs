@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashSet;
+use std::fs::{self, read, remove_file};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::lock::DirLock;
+use crate::{
+    extension, file_name, file_stem, get_rusk_circuits_dir, get_rusk_keys_dir,
+    get_rusk_profile_dir, is_lock, Circuit, Theme, CRS_17_HASH, CRS_FNAME,
+};
+
+/// The kind of a file cached in the profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    Crs,
+    /// A compressed circuit description.
+    Circuit,
+    /// The metadata of a circuit.
+    Metadata,
+    ProverKey,
+    VerifierData,
+    /// A file left over by an interrupted write.
+    Temporary,
+    Unknown,
+}
+
+impl ArtifactKind {
+    fn of(path: &Path) -> Self {
+        if file_name(path) == Some(CRS_FNAME) {
+            return Self::Crs;
+        }
+        match extension(path) {
+            Some("cd") => Self::Circuit,
+            Some("toml") => Self::Metadata,
+            Some("pk") => Self::ProverKey,
+            Some("vd") => Self::VerifierData,
+            Some("tmp") => Self::Temporary,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A file cached in the profile.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+    /// The size of the file, in bytes.
+    pub size: u64,
+    /// The hex-encoded SHA-256 of the file.
+    pub hash: String,
+    pub modified: SystemTime,
+}
+
+impl Artifact {
+    fn read(path: PathBuf) -> io::Result<Self> {
+        let contents = read(&path)?;
+        let modified = fs::metadata(&path)?.modified()?;
+        Ok(Self {
+            kind: ArtifactKind::of(&path),
+            size: contents.len() as u64,
+            hash: hex::encode(Sha256::digest(&contents)),
+            modified,
+            path,
+        })
+    }
+
+    /// The id of the circuit the artifact belongs to, if any.
+    fn circuit_id(&self) -> Option<&str> {
+        match self.kind {
+            ArtifactKind::Circuit
+            | ArtifactKind::Metadata
+            | ArtifactKind::ProverKey
+            | ArtifactKind::VerifierData => file_stem(&self.path),
+            _ => None,
+        }
+    }
+}
+
+/// An artifact found corrupted.
+#[derive(Debug, Clone)]
+pub struct Corruption {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// The files in the given directory, except for its lock.
+fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    Ok(fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && !is_lock(path))
+        .collect())
+}
+
+/// List the CRS, circuits and keys cached in the profile.
+pub fn inventory() -> io::Result<Vec<Artifact>> {
+    let mut artifacts = vec![];
+
+    let profile_dir = get_rusk_profile_dir()?;
+    {
+        let _lock = DirLock::shared(&profile_dir)?;
+        let crs = profile_dir.join(CRS_FNAME);
+        if crs.exists() {
+            artifacts.push(Artifact::read(crs)?);
+        }
+    }
+
+    for dir in [get_rusk_circuits_dir()?, get_rusk_keys_dir()?] {
+        let _lock = DirLock::shared(&dir)?;
+        for path in files(&dir)? {
+            artifacts.push(Artifact::read(path)?);
+        }
+    }
+
+    Ok(artifacts)
+}
+
+/// Check the integrity of the cached artifacts, returning the corrupted ones.
+///
+/// The CRS is checked against its known hash and the circuits against their
+/// ids. Since keys cannot be checked without compiling the circuits, they
+/// are only reported if empty or missing their other half.
+pub fn verify_all() -> io::Result<Vec<Corruption>> {
+    let artifacts = inventory()?;
+    let keys: HashSet<_> = artifacts
+        .iter()
+        .filter(|artifact| {
+            matches!(
+                artifact.kind,
+                ArtifactKind::ProverKey | ArtifactKind::VerifierData
+            )
+        })
+        .map(|artifact| (artifact.kind, artifact.circuit_id()))
+        .collect();
+
+    let mut corrupted = vec![];
+    for artifact in &artifacts {
+        let reason = match artifact.kind {
+            ArtifactKind::Crs => (artifact.hash != CRS_17_HASH)
+                .then(|| "The CRS doesn't match its hash".to_string()),
+            ArtifactKind::Circuit => verify_circuit(artifact).err(),
+            ArtifactKind::ProverKey | ArtifactKind::VerifierData => {
+                let other = match artifact.kind {
+                    ArtifactKind::ProverKey => ArtifactKind::VerifierData,
+                    _ => ArtifactKind::ProverKey,
+                };
+                if artifact.size == 0 {
+                    Some("The key is empty".to_string())
+                } else if !keys.contains(&(other, artifact.circuit_id())) {
+                    Some(format!("The {other:?} is missing"))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(reason) = reason {
+            corrupted.push(Corruption {
+                path: artifact.path.clone(),
+                reason,
+            });
+        }
+    }
+
+    Ok(corrupted)
+}
+
+fn verify_circuit(artifact: &Artifact) -> Result<(), String> {
+    let id = artifact
+        .circuit_id()
+        .and_then(|id| hex::decode(id).ok())
+        .and_then(|id| <[u8; 32]>::try_from(id).ok())
+        .ok_or("The circuit id is invalid")?;
+    Circuit::from_stored(id)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Remove the artifacts not referenced by the circuits cached, if they are
+/// older than `max_age`, returning the paths of the files removed.
+///
+/// The artifacts removed are the metadata and keys of circuits whose
+/// description is not cached, the files left over by interrupted writes and
+/// any unknown file. The CRS is never removed.
+pub fn gc(max_age: Duration) -> io::Result<Vec<PathBuf>> {
+    let circuits_dir = get_rusk_circuits_dir()?;
+    let circuits: HashSet<String> = {
+        let _lock = DirLock::shared(&circuits_dir)?;
+        files(&circuits_dir)?
+            .iter()
+            .filter(|path| ArtifactKind::of(path) == ArtifactKind::Circuit)
+            .filter_map(|path| file_stem(path).map(String::from))
+            .collect()
+    };
+
+    let mut removed = vec![];
+    for dir in [circuits_dir, get_rusk_keys_dir()?] {
+        let _lock = DirLock::exclusive(&dir)?;
+        for path in files(&dir)? {
+            let is_referenced = match ArtifactKind::of(&path) {
+                ArtifactKind::Crs | ArtifactKind::Circuit => true,
+                ArtifactKind::Temporary | ArtifactKind::Unknown => false,
+                _ => file_stem(&path).is_some_and(|id| circuits.contains(id)),
+            };
+            if is_referenced {
+                continue;
+            }
+
+            let age = fs::metadata(&path)?
+                .modified()?
+                .elapsed()
+                .unwrap_or_default();
+            if age < max_age {
+                continue;
+            }
+
+            warn!(
+                "{}   {}",
+                Theme::default().warn("Removing"),
+                file_name(&path).expect("file should be valid")
+            );
+            remove_file(&path)?;
+            removed.push(path);
+        }
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_kinds() {
+        let kind = |name: &str| ArtifactKind::of(Path::new(name));
+
+        assert_eq!(kind(CRS_FNAME), ArtifactKind::Crs);
+        assert_eq!(kind("00ff.cd"), ArtifactKind::Circuit);
+        assert_eq!(kind("00ff.toml"), ArtifactKind::Metadata);
+        assert_eq!(kind("00ff.pk"), ArtifactKind::ProverKey);
+        assert_eq!(kind("00ff.vd"), ArtifactKind::VerifierData);
+        assert_eq!(kind("00ff.pk.42.tmp"), ArtifactKind::Temporary);
+        assert_eq!(kind("notes.txt"), ArtifactKind::Unknown);
+    }
+}
@@ -7,7 +7,7 @@
 #![deny(unused_crate_dependencies)]
 #![deny(unused_extern_crates)]
 
-use std::fs::{self, read, remove_file, write};
+use std::fs::{self, read, remove_file};
 use std::path::{Path, PathBuf};
 use std::{env, io};
 
@@ -21,6 +21,22 @@ pub use theme::Theme;
 mod circuit;
 pub use circuit::Circuit;
 
+mod inventory;
+pub use inventory::{
+    gc, inventory, verify_all, Artifact, ArtifactKind, Corruption,
+};
+
+mod lock;
+use lock::{write_atomic, DirLock, LOCK_FNAME};
+
+mod network;
+pub use network::{get_rusk_network, to_rusk_network_dir};
+
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "fetch")]
+pub use fetch::Fetcher;
+
 /// HEX representation of the SHA-256 hash of the CRS uncompressed bytes.
 /// This is the hash of the contribution number 15 of the Dusk Trusted Setup.
 pub static CRS_17_HASH: &str =
@@ -40,18 +56,27 @@ fn file_name(p: &Path) -> Option<&str> {
     p.file_name()?.to_str()
 }
 
+/// Returns if the file is the one locked to access its directory, which must
+/// never be removed.
+fn is_lock(p: &Path) -> bool {
+    file_name(p) == Some(LOCK_FNAME)
+}
+
 /// Return Rusk profile directory, ensuring that all parents directory are
 /// created
 ///
 /// Default to [`home_dir`]/.dusk/rusk
 ///
-/// `RUSK_PROFILE_PATH` env can be used to override
+/// `RUSK_PROFILE_PATH` env can be used to override, and `RUSK_NETWORK` env
+/// to namespace it by network (see [`get_rusk_network`])
 ///
 /// E.g:
-/// RUSK_PROFILE_PATH | result
-/// -- | --
-/// None | $HOME/.dusk/rusk
-/// Set | $RUSK_PROFILE_PATH
+/// RUSK_PROFILE_PATH | RUSK_NETWORK | result
+/// -- | -- | --
+/// None | None | $HOME/.dusk/rusk
+/// Set | None | $RUSK_PROFILE_PATH
+/// None | Set | $HOME/.dusk/rusk/networks/$RUSK_NETWORK
+/// Set | Set | $RUSK_PROFILE_PATH/networks/$RUSK_NETWORK
 pub fn get_rusk_profile_dir() -> io::Result<PathBuf> {
     env::var("RUSK_PROFILE_PATH")
         .map_or_else(
@@ -62,7 +87,7 @@ pub fn get_rusk_profile_dir() -> io::Result<PathBuf> {
             },
             |profile_path| Ok(PathBuf::from(profile_path)),
         )
-        .and_then(|p| fs::create_dir_all(&p).map(|_| p))
+        .and_then(to_rusk_network_dir)
         .map_err(|e| {
             warn!("rusk-profile dir not found and impossible to create: {e}");
             e
@@ -151,8 +176,9 @@ pub fn to_rusk_state_id_path<P: AsRef<Path>>(dir: P) -> PathBuf {
 }
 
 pub fn get_common_reference_string() -> io::Result<Vec<u8>> {
-    let crs = get_rusk_profile_dir()?.join(CRS_FNAME);
-    read(crs)
+    let dir = get_rusk_profile_dir()?;
+    let _lock = DirLock::shared(&dir)?;
+    read(dir.join(CRS_FNAME))
 }
 
 pub fn set_common_reference_string(buffer: Vec<u8>) -> io::Result<()> {
@@ -162,16 +188,18 @@ pub fn set_common_reference_string(buffer: Vec<u8>) -> io::Result<()> {
             "CRS Mismatch",
         ));
     }
-    let crs = get_rusk_profile_dir()?.join(CRS_FNAME);
-    write(crs, buffer)?;
+    let dir = get_rusk_profile_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+    write_atomic(&dir.join(CRS_FNAME), &buffer)?;
     info!("{} CRS to cache", Theme::default().success("Added"),);
 
     Ok(())
 }
 
 pub fn delete_common_reference_string() -> io::Result<()> {
-    let crs = get_rusk_profile_dir()?.join(CRS_FNAME);
-    remove_file(crs)?;
+    let dir = get_rusk_profile_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+    remove_file(dir.join(CRS_FNAME))?;
     warn!("{}   CRS", Theme::default().warn("Removed"),);
 
     Ok(())
@@ -195,11 +223,14 @@ pub fn clean_outdated(circuits: &[Circuit]) -> io::Result<()> {
 }
 
 fn clean_outdated_circuits(ids: &[&str]) -> io::Result<()> {
+    let dir = get_rusk_circuits_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+
     // removing all untracked files in circuits directory
-    fs::read_dir(get_rusk_circuits_dir()?)?
+    fs::read_dir(dir)?
         .map(|res| res.map(|e| e.path()))
         .filter_map(|res| res.ok())
-        .filter(|e| e.is_file())
+        .filter(|e| e.is_file() && !is_lock(e))
         .filter(|p| match extension(p) {
             Some("cd" | "toml") => {
                 file_stem(p).filter(|id| ids.contains(id)).is_none()
@@ -218,11 +249,14 @@ fn clean_outdated_circuits(ids: &[&str]) -> io::Result<()> {
 }
 
 fn clean_outdated_keys(ids: &[&str]) -> io::Result<()> {
+    let dir = get_rusk_keys_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+
     // removing all untracked files in keys directory
-    fs::read_dir(get_rusk_keys_dir()?)?
+    fs::read_dir(dir)?
         .map(|res| res.map(|e| e.path()))
         .filter_map(|res| res.ok())
-        .filter(|e| e.is_file())
+        .filter(|e| e.is_file() && !is_lock(e))
         .filter(|p| match extension(p) {
             Some("pk" | "vd") => {
                 file_stem(p).filter(|id| ids.contains(id)).is_none()
@@ -250,10 +284,13 @@ fn clear_all_keys() -> io::Result<()> {
         Theme::default().warn("Clearing")
     );
 
-    fs::read_dir(get_rusk_keys_dir()?)?
+    let dir = get_rusk_keys_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+
+    fs::read_dir(dir)?
         .map(|res| res.map(|e| e.path()))
         .filter_map(|res| res.ok())
-        .filter(|e| e.is_file())
+        .filter(|e| e.is_file() && !is_lock(e))
         .try_for_each(remove_file)
 }
 
@@ -263,9 +300,12 @@ fn clear_all_circuits() -> io::Result<()> {
         Theme::default().warn("Clearing")
     );
 
-    fs::read_dir(get_rusk_circuits_dir()?)?
+    let dir = get_rusk_circuits_dir()?;
+    let _lock = DirLock::exclusive(&dir)?;
+
+    fs::read_dir(dir)?
         .map(|res| res.map(|e| e.path()))
         .filter_map(|res| res.ok())
-        .filter(|e| e.is_file())
+        .filter(|e| e.is_file() && !is_lock(e))
         .try_for_each(remove_file)
 }
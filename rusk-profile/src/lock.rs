@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Concurrent-safe access to the directories of the profile.
+//!
+//! The processes sharing a profile directory (node, wallet, prover, tests)
+//! serialize their access to it with an advisory lock on a file in the
+//! directory. Files are written to a temporary file first and renamed in
+//! place, so they are never observed partially written.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use fs2::FileExt;
+
+/// The name of the file locked to access a directory.
+pub(crate) const LOCK_FNAME: &str = ".lock";
+
+/// An advisory lock on a directory, released when dropped.
+///
+/// The lock is held on an open file, so a process must not lock the same
+/// directory twice.
+pub(crate) struct DirLock {
+    file: File,
+}
+
+impl DirLock {
+    /// Lock the directory for reading, waiting for any writer to be done.
+    pub fn shared(dir: &Path) -> io::Result<Self> {
+        let file = Self::open(dir)?;
+        file.lock_shared()?;
+        Ok(Self { file })
+    }
+
+    /// Lock the directory for writing, waiting for any other reader or
+    /// writer to be done.
+    pub fn exclusive(dir: &Path) -> io::Result<Self> {
+        let file = Self::open(dir)?;
+        file.lock_exclusive()?;
+        Ok(Self { file })
+    }
+
+    fn open(dir: &Path) -> io::Result<File> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(dir.join(LOCK_FNAME))
+    }
+}
+
+impl Drop for DirLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Write the contents to the given file atomically, writing them to a
+/// temporary file in the same directory and renaming it in place.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = Path::new(&tmp);
+
+    let result = File::create(tmp)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(tmp);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn atomic_writes() -> io::Result<()> {
+        let dir = std::env::temp_dir()
+            .join(format!("rusk-profile-lock-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("file.pk");
+
+        let writers = 4;
+        let barrier = Arc::new(Barrier::new(writers));
+        let handles: Vec<_> = (0..writers)
+            .map(|i| {
+                let (dir, path) = (dir.clone(), path.clone());
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    let contents = vec![i as u8; 1 << 16];
+                    barrier.wait();
+                    let _lock = DirLock::exclusive(&dir)?;
+                    write_atomic(&path, &contents)
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("writer not to panic")?;
+        }
+
+        let contents = {
+            let _lock = DirLock::shared(&dir)?;
+            fs::read(&path)?
+        };
+        assert_eq!(contents.len(), 1 << 16);
+        assert!(
+            contents.iter().all(|b| *b == contents[0]),
+            "the contents should come from a single writer"
+        );
+
+        let files: Vec<_> = fs::read_dir(&dir)?.flatten().collect();
+        assert_eq!(files.len(), 2, "only the file and the lock should remain");
+
+        fs::remove_dir_all(dir)
+    }
+}
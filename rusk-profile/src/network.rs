@@ -0,0 +1,163 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Namespacing of the profile by network.
+//!
+//! When a network is selected, the CRS, circuits, keys and state live in
+//! `networks/<network>` under the profile directory, so that the ones of
+//! different networks never collide. The same applies to the chain database
+//! and the archive, which by default live in the profile directory too.
+
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use tracing::info;
+
+use crate::lock::DirLock;
+use crate::{Theme, CRS_FNAME};
+
+/// The directory, under the profile one, holding the network namespaces.
+const NETWORKS_DIR: &str = "networks";
+
+/// The entries of a profile directory not namespaced by network.
+const LEGACY_ENTRIES: [&str; 6] = [
+    CRS_FNAME, "circuits", "keys", "state", "chain.db", "archive",
+];
+
+/// Return the network the profile is namespaced by, if any.
+///
+/// `RUSK_NETWORK` env is used to select it, e.g. `mainnet`, `testnet` or
+/// `devnet`.
+pub fn get_rusk_network() -> io::Result<Option<String>> {
+    match env::var("RUSK_NETWORK") {
+        Ok(network) if !network.is_empty() => {
+            validate(&network)?;
+            Ok(Some(network))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Return the given directory namespaced by the network selected, if any (see
+/// [`get_rusk_network`]), ensuring that it is created.
+///
+/// The entries of the directory not yet namespaced are migrated to the first
+/// network selected.
+pub fn to_rusk_network_dir<P: AsRef<Path>>(dir: P) -> io::Result<PathBuf> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    match get_rusk_network()? {
+        Some(network) => network_dir(dir, &network),
+        None => Ok(dir.to_path_buf()),
+    }
+}
+
+/// Network names are used as directory names, so only ASCII alphanumerics,
+/// `-` and `_` are accepted.
+fn validate(network: &str) -> io::Result<()> {
+    let is_valid = network
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_valid {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid network name {network:?}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Return the directory of the network in the given profile directory,
+/// migrating the entries of a profile not yet namespaced into it if it doesn't
+/// exist.
+///
+/// The first network selected in a profile directory takes over its existing
+/// CRS, circuits, keys, state, chain database and archive.
+pub(crate) fn network_dir(
+    profile_dir: &Path,
+    network: &str,
+) -> io::Result<PathBuf> {
+    let dir = profile_dir.join(NETWORKS_DIR).join(network);
+    if dir.exists() {
+        return Ok(dir);
+    }
+
+    let _lock = DirLock::exclusive(profile_dir)?;
+    // Another process may have created it while waiting for the lock
+    if !dir.exists() {
+        let networks_dir = profile_dir.join(NETWORKS_DIR);
+        let has_networks = networks_dir.exists();
+        fs::create_dir_all(&dir)?;
+        if !has_networks {
+            migrate(profile_dir, &dir)?;
+        }
+    }
+
+    Ok(dir)
+}
+
+/// Move the legacy entries of the profile directory into the given network
+/// directory.
+fn migrate(profile_dir: &Path, network_dir: &Path) -> io::Result<()> {
+    for entry in LEGACY_ENTRIES {
+        let from = profile_dir.join(entry);
+        if from.exists() {
+            info!(
+                "{} {} to {}",
+                Theme::default().action("Migrating"),
+                from.display(),
+                network_dir.display()
+            );
+            fs::rename(from, network_dir.join(entry))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_names() {
+        for network in ["mainnet", "testnet", "devnet", "local-1", "a_b"] {
+            assert!(validate(network).is_ok(), "{network} should be valid");
+        }
+        for network in ["../mainnet", "main/net", "main net", "."] {
+            assert!(validate(network).is_err(), "{network} should be invalid");
+        }
+    }
+
+    #[test]
+    fn legacy_migration() -> io::Result<()> {
+        let profile_dir = env::temp_dir()
+            .join(format!("rusk-profile-network-{}", std::process::id()));
+        fs::create_dir_all(profile_dir.join("keys"))?;
+        fs::write(profile_dir.join("keys").join("00ff.pk"), b"pk")?;
+        fs::write(profile_dir.join(CRS_FNAME), b"crs")?;
+        fs::create_dir_all(profile_dir.join("chain.db"))?;
+        fs::create_dir_all(profile_dir.join("archive"))?;
+
+        let mainnet = network_dir(&profile_dir, "mainnet")?;
+        assert!(mainnet.join("keys").join("00ff.pk").exists());
+        assert!(mainnet.join(CRS_FNAME).exists());
+        assert!(mainnet.join("chain.db").exists());
+        assert!(mainnet.join("archive").exists());
+        assert!(!profile_dir.join("keys").exists());
+        assert!(!profile_dir.join(CRS_FNAME).exists());
+        assert!(!profile_dir.join("chain.db").exists());
+        assert!(!profile_dir.join("archive").exists());
+
+        // Only the first network takes over the legacy entries
+        fs::create_dir_all(profile_dir.join("keys"))?;
+        let testnet = network_dir(&profile_dir, "testnet")?;
+        assert!(!testnet.join("keys").exists());
+        assert!(profile_dir.join("keys").exists());
+
+        fs::remove_dir_all(profile_dir)
+    }
+}
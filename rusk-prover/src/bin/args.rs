@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::net::SocketAddr;
+
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(
+    author = "Dusk Network B.V. All Rights Reserved.",
+    version,
+    about = "Rusk proving service"
+)]
+pub struct Args {
+    /// Address to listen on
+    #[clap(long, env = "RUSK_PROVER_LISTEN", default_value = "127.0.0.1:8090")]
+    pub listen: SocketAddr,
+
+    /// API keys accepted in the `Authorization: Bearer <key>` header
+    #[clap(
+        long = "api-key",
+        env = "RUSK_PROVER_API_KEYS",
+        value_delimiter = ','
+    )]
+    pub api_keys: Vec<String>,
+
+    /// Accept requests without an API key
+    #[clap(long)]
+    pub no_auth: bool,
+
+    /// Maximum number of proofs computed at the same time
+    #[clap(long, env = "RUSK_PROVER_CONCURRENCY", default_value_t = 2)]
+    pub concurrency: usize,
+
+    /// Maximum number of requests waiting for, or being, proven
+    #[clap(long, env = "RUSK_PROVER_QUEUE_SIZE", default_value_t = 32)]
+    pub queue_size: usize,
+
+    /// Seconds after which a request waiting for its proof is aborted
+    #[clap(long, env = "RUSK_PROVER_TIMEOUT", default_value_t = 60)]
+    pub timeout: u64,
+
+    /// Output log level
+    #[clap(long, default_value_t = tracing::Level::INFO)]
+    pub log_level: tracing::Level,
+}
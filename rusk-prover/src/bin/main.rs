@@ -0,0 +1,77 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+mod args;
+mod service;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::Parser;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use crate::args::Args;
+use crate::service::ProverService;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    tracing_subscriber::fmt()
+        .with_max_level(args.log_level)
+        .init();
+
+    let api_keys = match (args.api_keys.is_empty(), args.no_auth) {
+        (false, _) => Some(args.api_keys),
+        (true, true) => {
+            warn!("Serving requests without authentication");
+            None
+        }
+        (true, false) => anyhow::bail!(
+            "At least one API key is required, unless --no-auth is set"
+        ),
+    };
+    anyhow::ensure!(args.concurrency > 0, "Concurrency must be positive");
+    anyhow::ensure!(
+        args.queue_size >= args.concurrency,
+        "The queue size must be at least the concurrency"
+    );
+
+    let service = Arc::new(ProverService::new(
+        api_keys,
+        args.concurrency,
+        args.queue_size,
+        Duration::from_secs(args.timeout),
+    ));
+
+    let listener = TcpListener::bind(args.listen).await?;
+    info!("Listening on {}", args.listen);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!("Failed accepting connection: {err}");
+                continue;
+            }
+        };
+
+        let service = service.clone();
+        tokio::spawn(async move {
+            let handler = service_fn(move |req| service.clone().handle(req));
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), handler)
+                .await
+            {
+                warn!("Failed serving connection from {peer}: {err}");
+            }
+        });
+    }
+}
@@ -0,0 +1,192 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use dusk_core::transfer::phoenix::Prove;
+use dusk_core::Error;
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE};
+use hyper::{Method, Request, Response, StatusCode};
+use rusk_prover::LocalProver;
+use tokio::sync::Semaphore;
+use tokio::task;
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+/// The path of the Phoenix proving endpoint.
+const PROVE_PHOENIX_PATH: &str = "/prove/phoenix";
+
+/// The maximum size of a request body. Serialized transaction circuits are
+/// well below this.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// Serves proving requests.
+///
+/// At most `queue_size` requests are accepted at a time, the others being
+/// rejected with `503 Service Unavailable`. Of the accepted requests, at most
+/// `concurrency` are proven at the same time, while the others wait for their
+/// turn.
+///
+/// A request that doesn't get its proof within the timeout is answered with
+/// `504 Gateway Timeout`. Since proving can't be interrupted, a proof that
+/// already started keeps its slot until it completes, so the concurrency limit
+/// holds regardless of timeouts.
+pub struct ProverService {
+    api_keys: Option<Vec<String>>,
+    queue: Arc<Semaphore>,
+    workers: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl ProverService {
+    /// Creates a new service. If `api_keys` is `None` requests are not
+    /// authenticated.
+    pub fn new(
+        api_keys: Option<Vec<String>>,
+        concurrency: usize,
+        queue_size: usize,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            api_keys,
+            queue: Arc::new(Semaphore::new(queue_size)),
+            workers: Arc::new(Semaphore::new(concurrency)),
+            timeout,
+        }
+    }
+
+    pub async fn handle(
+        self: Arc<Self>,
+        request: Request<Incoming>,
+    ) -> Result<Response<Full<Bytes>>, Infallible> {
+        if request.uri().path() != PROVE_PHOENIX_PATH {
+            return Ok(response(StatusCode::NOT_FOUND, "Not found"));
+        }
+        if request.method() != Method::POST {
+            return Ok(response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "Method not allowed",
+            ));
+        }
+        if !self.is_authorized(&request) {
+            return Ok(response(StatusCode::UNAUTHORIZED, "Unauthorized"));
+        }
+
+        let Ok(_queued) = self.queue.clone().try_acquire_owned() else {
+            warn!("Rejecting request, the queue is full");
+            return Ok(response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "The queue is full",
+            ));
+        };
+
+        let body = match Limited::new(request.into_body(), MAX_BODY_SIZE)
+            .collect()
+            .await
+        {
+            Ok(body) => body.to_bytes(),
+            Err(_) => {
+                return Ok(response(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "Invalid request body",
+                ))
+            }
+        };
+
+        let workers = self.workers.clone();
+        let proving = async move {
+            let worker = workers
+                .acquire_owned()
+                .await
+                .expect("The semaphore should never be closed");
+            task::spawn_blocking(move || {
+                let _worker = worker;
+                LocalProver.prove(&body)
+            })
+            .await
+        };
+
+        let response = match timeout(self.timeout, proving).await {
+            Ok(Ok(Ok(proof))) => {
+                info!("Proof computed");
+                Response::builder()
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .body(Full::new(Bytes::from(proof)))
+                    .expect("The response should be valid")
+            }
+            Ok(Ok(Err(Error::PhoenixProver(err)))) => {
+                warn!("Proving failed: {err}");
+                response(StatusCode::INTERNAL_SERVER_ERROR, "Proving failed")
+            }
+            Ok(Ok(Err(err))) => {
+                response(StatusCode::BAD_REQUEST, &format!("{err}"))
+            }
+            Ok(Err(err)) => {
+                warn!("Proving task failed: {err}");
+                response(StatusCode::INTERNAL_SERVER_ERROR, "Proving failed")
+            }
+            Err(_) => {
+                warn!("Request timed out");
+                response(StatusCode::GATEWAY_TIMEOUT, "Request timed out")
+            }
+        };
+
+        Ok(response)
+    }
+
+    fn is_authorized(&self, request: &Request<Incoming>) -> bool {
+        let Some(api_keys) = &self.api_keys else {
+            return true;
+        };
+
+        let key = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        match key {
+            Some(key) => api_keys.iter().any(|k| constant_time_eq(k, key)),
+            None => false,
+        }
+    }
+}
+
+fn response(status: StatusCode, msg: &str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::from(msg.to_owned())))
+        .expect("The response should be valid")
+}
+
+/// Compares two strings in time independent of the position of the first
+/// difference, so the keys can't be guessed byte by byte.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_keys() {
+        assert!(constant_time_eq("secret", "secret"));
+        assert!(!constant_time_eq("secret", "secreT"));
+        assert!(!constant_time_eq("secret", "secrets"));
+        assert!(!constant_time_eq("", "secret"));
+    }
+}
@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_core::{BlsScalar, Error};
+use dusk_plonk::prelude::Proof;
+
+/// A proof together with what it proves, as produced by
+/// [`LocalProver::prove_audited`].
+///
+/// The envelope has a canonical serialization, made of:
+/// - the version of the envelope, as one byte
+/// - the ID of the circuit, as stored in the rusk profile
+/// - the number of public inputs, as 4 little-endian bytes
+/// - the public inputs, in the order of the circuit
+/// - the proof, of [`Proof::SIZE`] bytes
+///
+/// [`LocalProver::prove_audited`]: crate::LocalProver::prove_audited
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofEnvelope {
+    /// The ID of the circuit the proof is for.
+    pub circuit_id: [u8; 32],
+    /// The public inputs of the proof.
+    pub public_inputs: Vec<BlsScalar>,
+    /// The serialized proof.
+    pub proof: Vec<u8>,
+}
+
+impl ProofEnvelope {
+    /// The version of the serialization.
+    pub const VERSION: u8 = 1;
+
+    /// Serializes the envelope into its canonical form.
+    pub fn to_var_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            1 + 32
+                + 4
+                + self.public_inputs.len() * BlsScalar::SIZE
+                + self.proof.len(),
+        );
+
+        bytes.push(Self::VERSION);
+        bytes.extend(self.circuit_id);
+        bytes.extend((self.public_inputs.len() as u32).to_le_bytes());
+        for public_input in &self.public_inputs {
+            bytes.extend(public_input.to_bytes());
+        }
+        bytes.extend(&self.proof);
+
+        bytes
+    }
+
+    /// Deserializes an envelope from its canonical form.
+    ///
+    /// Fails if the number of public inputs doesn't match the length of the
+    /// bytes, or if they aren't followed by exactly one proof.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let (&version, bytes) =
+            bytes.split_first().ok_or(Error::InvalidData)?;
+        if version != Self::VERSION {
+            return Err(Error::InvalidData);
+        }
+
+        if bytes.len() < 32 {
+            return Err(Error::BadLength(bytes.len(), 32));
+        }
+        let (circuit_id, mut buf) = bytes.split_at(32);
+        let circuit_id = circuit_id.try_into().expect("The slice is 32 bytes");
+        let len = u32::from_reader(&mut buf)? as usize;
+
+        // checked before allocating, so that the number of public inputs
+        // can't be used to allocate more than the bytes given
+        let expected = len
+            .checked_mul(BlsScalar::SIZE)
+            .and_then(|len| len.checked_add(Proof::SIZE))
+            .ok_or(Error::InvalidData)?;
+        if buf.len() != expected {
+            return Err(Error::BadLength(buf.len(), expected));
+        }
+
+        let mut public_inputs = Vec::with_capacity(len);
+        for _ in 0..len {
+            public_inputs.push(BlsScalar::from_reader(&mut buf)?);
+        }
+
+        Ok(Self {
+            circuit_id,
+            public_inputs,
+            proof: buf.to_vec(),
+        })
+    }
+}
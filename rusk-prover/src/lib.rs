@@ -5,7 +5,8 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 #![cfg_attr(not(feature = "std"), no_std)]
-#![deny(unused_crate_dependencies)]
+// The dependencies of the `service` feature are only used by the binary.
+#![cfg_attr(not(feature = "service"), deny(unused_crate_dependencies))]
 #![deny(unused_extern_crates)]
 
 extern crate alloc;
@@ -14,41 +15,97 @@ extern crate std;
 
 use alloc::format;
 use alloc::vec::Vec;
+#[cfg(feature = "no_random")]
+use core::sync::atomic::{AtomicU64, Ordering};
 
 use dusk_bytes::Serializable;
 use dusk_core::transfer::phoenix::{
     Prove, TxCircuit, TxCircuitVec, NOTES_TREE_DEPTH,
 };
-use dusk_core::Error;
+use dusk_core::{BlsScalar, Error};
 use dusk_plonk::prelude::Prover as PlonkProver;
 use once_cell::sync::Lazy;
+#[cfg(feature = "no_random")]
+use rand::{rngs::StdRng, SeedableRng};
+use rand::{CryptoRng, RngCore};
 
-static TX_CIRCUIT_1_2_PROVER: Lazy<PlonkProver> =
+mod envelope;
+
+pub use envelope::ProofEnvelope;
+
+static TX_CIRCUIT_1_2_PROVER: Lazy<CircuitProver> =
     Lazy::new(|| fetch_prover("TxCircuitOneTwo"));
 
-static TX_CIRCUIT_2_2_PROVER: Lazy<PlonkProver> =
+static TX_CIRCUIT_2_2_PROVER: Lazy<CircuitProver> =
     Lazy::new(|| fetch_prover("TxCircuitTwoTwo"));
 
-static TX_CIRCUIT_3_2_PROVER: Lazy<PlonkProver> =
+static TX_CIRCUIT_3_2_PROVER: Lazy<CircuitProver> =
     Lazy::new(|| fetch_prover("TxCircuitThreeTwo"));
 
-static TX_CIRCUIT_4_2_PROVER: Lazy<PlonkProver> =
+static TX_CIRCUIT_4_2_PROVER: Lazy<CircuitProver> =
     Lazy::new(|| fetch_prover("TxCircuitFourTwo"));
 
+struct CircuitProver {
+    prover: PlonkProver,
+    circuit_id: [u8; 32],
+}
+
 #[derive(Debug, Default)]
 pub struct LocalProver;
 
-impl Prove for LocalProver {
-    fn prove(&self, tx_circuit_vec_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+#[cfg(feature = "parallel")]
+impl LocalProver {
+    /// Proves a batch of independent transaction circuits, spreading them over
+    /// the threads of the global rayon pool.
+    ///
+    /// The proofs are returned in the order of the circuits. The prover keys
+    /// are loaded once and shared by all the threads.
+    pub fn prove_batch(
+        &self,
+        tx_circuit_vecs: &[TxCircuitVec],
+    ) -> Vec<Result<Vec<u8>, Error>> {
+        use rayon::prelude::*;
+
+        tx_circuit_vecs
+            .par_iter()
+            .map(|tx_circuit_vec| {
+                let (proof, _, _) =
+                    prove_circuit(&mut rng(), tx_circuit_vec.clone())?;
+                Ok(proof)
+            })
+            .collect()
+    }
+}
+
+impl LocalProver {
+    /// Proves a transaction circuit, returning the proof in a
+    /// [`ProofEnvelope`] together with the public inputs and the ID of the
+    /// circuit, so that third parties can re-verify and archive exactly what
+    /// was proven.
+    ///
+    /// The proof is blinded with the same randomness as [`Prove::prove`], so
+    /// proving the same circuit twice results in different proofs of the same
+    /// public inputs.
+    pub fn prove_audited(
+        &self,
+        tx_circuit_vec_bytes: &[u8],
+    ) -> Result<ProofEnvelope, Error> {
         let tx_circuit_vec = TxCircuitVec::from_slice(tx_circuit_vec_bytes)?;
 
-        #[cfg(not(feature = "no_random"))]
-        let rng = &mut rand::rngs::OsRng;
+        let (proof, public_inputs, circuit_id) =
+            prove_circuit(&mut rng(), tx_circuit_vec)?;
 
-        #[cfg(feature = "no_random")]
-        use rand::{rngs::StdRng, SeedableRng};
-        #[cfg(feature = "no_random")]
-        let rng = &mut StdRng::seed_from_u64(0xbeef);
+        Ok(ProofEnvelope {
+            circuit_id,
+            public_inputs,
+            proof,
+        })
+    }
+}
+
+impl Prove for LocalProver {
+    fn prove(&self, tx_circuit_vec_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        let tx_circuit_vec = TxCircuitVec::from_slice(tx_circuit_vec_bytes)?;
 
         #[cfg(feature = "debug")]
         tracing::info!(
@@ -56,27 +113,82 @@ impl Prove for LocalProver {
             hex::encode(tx_circuit_vec_bytes)
         );
 
-        let (proof, _pi) = match tx_circuit_vec.input_notes_info.len() {
-            1 => TX_CIRCUIT_1_2_PROVER
-                .prove(rng, &create_circuit::<1>(tx_circuit_vec)?)
-                .map_err(|e| Error::PhoenixProver(format!("{e:?}")))?,
-            2 => TX_CIRCUIT_2_2_PROVER
-                .prove(rng, &create_circuit::<2>(tx_circuit_vec)?)
-                .map_err(|e| Error::PhoenixProver(format!("{e:?}")))?,
-            3 => TX_CIRCUIT_3_2_PROVER
-                .prove(rng, &create_circuit::<3>(tx_circuit_vec)?)
-                .map_err(|e| Error::PhoenixProver(format!("{e:?}")))?,
-            4 => TX_CIRCUIT_4_2_PROVER
-                .prove(rng, &create_circuit::<4>(tx_circuit_vec)?)
-                .map_err(|e| Error::PhoenixProver(format!("{e:?}")))?,
-            _ => return Err(Error::InvalidData),
-        };
-
-        Ok(proof.to_bytes().to_vec())
+        let (proof, _, _) = prove_circuit(&mut rng(), tx_circuit_vec)?;
+        Ok(proof)
     }
 }
 
-fn fetch_prover(circuit_name: &str) -> PlonkProver {
+#[cfg(not(feature = "no_random"))]
+fn rng() -> rand::rngs::OsRng {
+    rand::rngs::OsRng
+}
+
+/// The seed of the prover randomness when the `no_random` feature is enabled.
+#[cfg(feature = "no_random")]
+static SEED: AtomicU64 = AtomicU64::new(0xbeef);
+
+/// Sets the seed of the prover randomness, so that tests proving the same
+/// circuits with the same seed get the same proofs.
+///
+/// Only available with the `no_random` feature, which is never to be enabled
+/// outside of tests.
+#[cfg(feature = "no_random")]
+pub fn set_rng_seed(seed: u64) {
+    SEED.store(seed, Ordering::Relaxed);
+}
+
+#[cfg(feature = "no_random")]
+fn rng() -> StdRng {
+    StdRng::seed_from_u64(SEED.load(Ordering::Relaxed))
+}
+
+/// A proof, its public inputs and the ID of the circuit it proves.
+type CircuitProof = (Vec<u8>, Vec<BlsScalar>, [u8; 32]);
+
+/// Proves the circuit, returning the proof, its public inputs and the ID of
+/// the circuit.
+fn prove_circuit<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    tx_circuit_vec: TxCircuitVec,
+) -> Result<CircuitProof, Error> {
+    let (circuit_prover, result) = match tx_circuit_vec.input_notes_info.len() {
+        1 => (
+            &*TX_CIRCUIT_1_2_PROVER,
+            TX_CIRCUIT_1_2_PROVER
+                .prover
+                .prove(rng, &create_circuit::<1>(tx_circuit_vec)?),
+        ),
+        2 => (
+            &*TX_CIRCUIT_2_2_PROVER,
+            TX_CIRCUIT_2_2_PROVER
+                .prover
+                .prove(rng, &create_circuit::<2>(tx_circuit_vec)?),
+        ),
+        3 => (
+            &*TX_CIRCUIT_3_2_PROVER,
+            TX_CIRCUIT_3_2_PROVER
+                .prover
+                .prove(rng, &create_circuit::<3>(tx_circuit_vec)?),
+        ),
+        4 => (
+            &*TX_CIRCUIT_4_2_PROVER,
+            TX_CIRCUIT_4_2_PROVER
+                .prover
+                .prove(rng, &create_circuit::<4>(tx_circuit_vec)?),
+        ),
+        _ => return Err(Error::InvalidData),
+    };
+    let (proof, public_inputs) =
+        result.map_err(|e| Error::PhoenixProver(format!("{e:?}")))?;
+
+    Ok((
+        proof.to_bytes().to_vec(),
+        public_inputs,
+        circuit_prover.circuit_id,
+    ))
+}
+
+fn fetch_prover(circuit_name: &str) -> CircuitProver {
     let circuit_profile = rusk_profile::Circuit::from_name(circuit_name)
         .unwrap_or_else(|_| {
             panic!(
@@ -88,7 +200,13 @@ fn fetch_prover(circuit_name: &str) -> PlonkProver {
         panic!("there should be a prover key stored for {}", circuit_name)
     });
 
-    PlonkProver::try_from_bytes(pk).expect("Prover key is expected to by valid")
+    let prover = PlonkProver::try_from_bytes(pk)
+        .expect("Prover key is expected to by valid");
+
+    CircuitProver {
+        prover,
+        circuit_id: *circuit_profile.id(),
+    }
 }
 
 fn create_circuit<const I: usize>(
@@ -119,4 +237,50 @@ mod tests {
             hex::decode(include_str!("../tests/tx_circuit_vec.hex")).unwrap();
         let _proof = LocalProver.prove(&tx_circuit_vec_bytes).unwrap();
     }
+
+    #[test]
+    fn test_prove_audited() {
+        let tx_circuit_vec_bytes =
+            hex::decode(include_str!("../tests/tx_circuit_vec.hex")).unwrap();
+
+        let envelope =
+            LocalProver.prove_audited(&tx_circuit_vec_bytes).unwrap();
+        let again = LocalProver.prove_audited(&tx_circuit_vec_bytes).unwrap();
+        assert_eq!(envelope.circuit_id, again.circuit_id);
+        assert_eq!(envelope.public_inputs, again.public_inputs);
+
+        let bytes = envelope.to_var_bytes();
+        assert_eq!(ProofEnvelope::from_slice(&bytes).unwrap(), envelope);
+
+        // the number of public inputs must match the bytes that follow
+        let mut truncated = bytes.clone();
+        truncated.pop();
+        assert!(ProofEnvelope::from_slice(&truncated).is_err());
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(ProofEnvelope::from_slice(&extended).is_err());
+        let mut huge = bytes;
+        huge[33..37].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ProofEnvelope::from_slice(&huge).is_err());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_prove_batch() {
+        let tx_circuit_vec_bytes =
+            hex::decode(include_str!("../tests/tx_circuit_vec.hex")).unwrap();
+        let tx_circuit_vec =
+            TxCircuitVec::from_slice(&tx_circuit_vec_bytes).unwrap();
+
+        let proofs = LocalProver.prove_batch(&[
+            tx_circuit_vec.clone(),
+            tx_circuit_vec.clone(),
+            tx_circuit_vec,
+        ]);
+
+        assert_eq!(proofs.len(), 3);
+        for proof in proofs {
+            proof.unwrap();
+        }
+    }
 }
@@ -32,6 +32,9 @@ pub use snapshot::{GenesisStake, PhoenixBalance, Snapshot};
 
 pub mod tar;
 
+mod verify;
+pub use verify::{verify, ContractDivergence, StateVerification};
+
 pub const DEFAULT_SNAPSHOT: &str =
     include_str!("../config/testnet_remote.toml");
 
@@ -323,7 +326,7 @@ fn load_state<P: AsRef<Path>>(
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 
     use std::error::Error;
 
@@ -337,7 +340,7 @@ mod tests {
         Ok(snapshot)
     }
 
-    fn dusk_mainnet_key() -> AccountPublicKey {
+    pub(crate) fn dusk_mainnet_key() -> AccountPublicKey {
         let bytes = include_bytes!("../../rusk/src/assets/dusk.cpk");
         AccountPublicKey::from_slice(&bytes[..])
             .expect("faucet should have a valid key")
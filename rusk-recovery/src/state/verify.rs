@@ -0,0 +1,201 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::error::Error;
+use std::path::Path;
+use std::sync::mpsc;
+
+use dusk_core::abi::ContractId;
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::stake::STAKE_CONTRACT;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_vm::VM;
+
+use super::{
+    deploy, restore_state, Snapshot, GENESIS_BLOCK_HEIGHT, GENESIS_CHAIN_ID,
+};
+
+/// A genesis contract whose stored state diverges from the regenerated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDivergence {
+    pub contract: ContractId,
+    /// The digest of the state of the contract regenerated from the snapshot.
+    pub expected: [u8; 32],
+    /// The digest of the stored state of the contract.
+    pub found: [u8; 32],
+}
+
+/// The outcome of the comparison of a stored state with the one regenerated
+/// from its snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateVerification {
+    /// The root of the state regenerated from the snapshot.
+    pub expected_root: [u8; 32],
+    /// The root of the stored state.
+    pub found_root: [u8; 32],
+    /// The genesis contracts whose state diverges.
+    pub diverging: Vec<ContractDivergence>,
+}
+
+impl StateVerification {
+    /// Returns if the stored state matches the regenerated one.
+    pub fn is_valid(&self) -> bool {
+        self.expected_root == self.found_root
+    }
+}
+
+/// Regenerate the state of a snapshot and compare it with a commit of the
+/// state stored in the given directory.
+///
+/// The commit compared is the given one, or the one of the persisted state id
+/// if none is given. When the roots differ, the data of the genesis contracts
+/// is compared to find the ones diverging. Contracts deployed after genesis
+/// are not compared.
+///
+/// Note that the state is regenerated deterministically only if every phoenix
+/// balance of the snapshot has a seed.
+pub fn verify<P: AsRef<Path>>(
+    state_dir: P,
+    commit: Option<[u8; 32]>,
+    snapshot: &Snapshot,
+    dusk_key: AccountPublicKey,
+) -> Result<StateVerification, Box<dyn Error>> {
+    let tmp_dir = tempfile::TempDir::with_prefix("verify-state")?;
+    let (expected_vm, expected_root) =
+        deploy(tmp_dir.path(), snapshot, dusk_key, |_| {})?;
+
+    let (found_vm, stored_root) = restore_state(state_dir)?;
+    let found_root = commit.unwrap_or(stored_root);
+    if !found_vm.commits().contains(&found_root) {
+        return Err(
+            format!("Missing commit {}", hex::encode(found_root)).into()
+        );
+    }
+
+    let mut diverging = vec![];
+    if expected_root != found_root {
+        let expected = contract_digests(&expected_vm, expected_root)?;
+        let found = contract_digests(&found_vm, found_root)?;
+        for ((contract, expected), (_, found)) in
+            expected.into_iter().zip(found)
+        {
+            if expected != found {
+                diverging.push(ContractDivergence {
+                    contract,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    Ok(StateVerification {
+        expected_root,
+        found_root,
+        diverging,
+    })
+}
+
+/// Digest the data of each genesis contract at the given commit, as fed by
+/// their feeder queries.
+fn contract_digests(
+    vm: &VM,
+    commit: [u8; 32],
+) -> Result<Vec<(ContractId, [u8; 32])>, Box<dyn Error>> {
+    let mut session =
+        vm.session(commit, GENESIS_CHAIN_ID, GENESIS_BLOCK_HEIGHT)?;
+
+    let (sender, receiver) = mpsc::channel();
+    session.feeder_call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "leaves_from_height",
+        &0u64,
+        u64::MAX,
+        sender.clone(),
+    )?;
+    session.feeder_call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "sync_accounts",
+        &(0u64, 0u64),
+        u64::MAX,
+        sender.clone(),
+    )?;
+    session.feeder_call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "sync_contract_balances",
+        &(0u64, 0u64),
+        u64::MAX,
+        sender,
+    )?;
+    let transfer = digest(receiver);
+
+    let (sender, receiver) = mpsc::channel();
+    session.feeder_call::<_, ()>(
+        STAKE_CONTRACT,
+        "stakes",
+        &(),
+        u64::MAX,
+        sender,
+    )?;
+    let stake = digest(receiver);
+
+    Ok(vec![(TRANSFER_CONTRACT, transfer), (STAKE_CONTRACT, stake)])
+}
+
+fn digest(receiver: mpsc::Receiver<Vec<u8>>) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    for item in receiver {
+        hasher.update(&(item.len() as u64).to_le_bytes());
+        hasher.update(&item);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::stake::{StakeData, StakeKeys};
+
+    use super::*;
+    use crate::state::tests::{dusk_mainnet_key, mainnet_from_file};
+
+    #[test]
+    fn diverging_contract() -> Result<(), Box<dyn Error>> {
+        let mainnet = mainnet_from_file()?;
+        let dusk_key = dusk_mainnet_key();
+
+        let stored = tempfile::TempDir::with_prefix("stored")?;
+        deploy(stored.path(), &mainnet, dusk_key, |_| {})?;
+        let verification = verify(stored.path(), None, &mainnet, dusk_key)?;
+        assert!(verification.is_valid());
+        assert!(verification.diverging.is_empty());
+
+        let tampered = tempfile::TempDir::with_prefix("tampered")?;
+        deploy(tampered.path(), &mainnet, dusk_key, |session| {
+            let stake = StakeData {
+                reward: 1,
+                ..Default::default()
+            };
+            session
+                .call::<_, ()>(
+                    STAKE_CONTRACT,
+                    "insert_stake",
+                    &(StakeKeys::single_key(dusk_key), stake),
+                    u64::MAX,
+                )
+                .expect("stake to be inserted into the state");
+        })?;
+        let verification = verify(tampered.path(), None, &mainnet, dusk_key)?;
+        assert!(!verification.is_valid());
+        let diverging: Vec<_> = verification
+            .diverging
+            .iter()
+            .map(|divergence| divergence.contract)
+            .collect();
+        assert_eq!(diverging, [STAKE_CONTRACT]);
+
+        Ok(())
+    }
+}
@@ -10,9 +10,11 @@ pub use history::TransactionHistory;
 
 use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use clap::Subcommand;
-use dusk_core::abi::CONTRACT_ID_BYTES;
+use dusk_core::abi::{ContractId, CONTRACT_ID_BYTES};
+use dusk_core::name_service::NAME_SUFFIX;
 use dusk_core::stake::StakeData;
 use dusk_core::transfer::data::ContractCall;
 use dusk_core::BlsScalar;
@@ -22,12 +24,13 @@ use rusk_wallet::gas::{
     DEFAULT_PRICE, MIN_PRICE_DEPLOYMENT,
 };
 use rusk_wallet::{
-    Address, Error, Profile, Wallet, EPOCH, MAX_CONTRACT_INIT_ARG_SIZE,
-    MAX_PROFILES,
+    epoch_boundary_height, Address, CacheInfo, Error, GraphQL, Profile, Wallet,
+    EPOCH, MAX_CONTRACT_INIT_ARG_SIZE, MAX_PROFILES,
 };
 use wallet_core::BalanceInfo;
 
 use crate::io::prompt::{self, create_password};
+use crate::io::status;
 use crate::settings::Settings;
 use crate::{WalletFile, WalletPath};
 
@@ -83,9 +86,10 @@ pub(crate) enum Command {
         #[arg(long)]
         sender: Option<Address>,
 
-        /// Receiver address
-        #[arg(short, long)]
-        rcvr: Address,
+        /// Receiver address, or name registered with the Dusk Name Service
+        /// (e.g. `alice.dusk`)
+        #[arg(short, long, alias = "to")]
+        rcvr: Receiver,
 
         /// Amount of DUSK to send
         #[arg(short, long)]
@@ -175,6 +179,10 @@ pub(crate) enum Command {
         /// Price you're going to pay for each gas unit (in LUX)
         #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
         gas_price: Lux,
+
+        /// Wait for the end of the current epoch to submit the transaction
+        #[arg(long)]
+        at_epoch_boundary: bool,
     },
 
     /// Unstake DUSK
@@ -191,6 +199,10 @@ pub(crate) enum Command {
         /// Price you're going to pay for each gas unit (in LUX)
         #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
         gas_price: Lux,
+
+        /// Wait for the end of the current epoch to submit the transaction
+        #[arg(long)]
+        at_epoch_boundary: bool,
     },
 
     /// Withdraw accumulated rewards for a stake key
@@ -209,6 +221,156 @@ pub(crate) enum Command {
         gas_price: Lux,
     },
 
+    /// Check the balance of a token held by your public account
+    TokenBalance {
+        /// Profile index for the public account holding the token
+        /// [default: 0]
+        #[arg(long)]
+        profile_idx: Option<u8>,
+
+        /// Contract id of the token, hex-encoded
+        #[arg(short, long)]
+        token: String,
+    },
+
+    /// Send tokens from your public account
+    TokenTransfer {
+        /// Address from which to send the tokens [default: first address]
+        #[arg(long)]
+        sender: Option<Address>,
+
+        /// Contract id of the token, hex-encoded
+        #[arg(short, long)]
+        token: String,
+
+        /// Receiver public address
+        #[arg(short, long)]
+        rcvr: Address,
+
+        /// Amount of tokens to send, in the token's smallest unit
+        #[arg(short, long)]
+        amt: u64,
+
+        /// Max amount of gas for this transaction
+        #[arg(short = 'l', long, default_value_t = DEFAULT_LIMIT_CALL)]
+        gas_limit: u64,
+
+        /// Price you're going to pay for each gas unit (in LUX)
+        #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
+        gas_price: Lux,
+    },
+
+    /// Allow a public account to spend tokens from your public account
+    TokenApprove {
+        /// Address from which the tokens can be spent [default: first
+        /// address]
+        #[arg(long)]
+        sender: Option<Address>,
+
+        /// Contract id of the token, hex-encoded
+        #[arg(short, long)]
+        token: String,
+
+        /// Public address allowed to spend the tokens
+        #[arg(short, long)]
+        spender: Address,
+
+        /// Amount of tokens allowed to be spent, in the token's smallest unit
+        #[arg(short, long)]
+        amt: u64,
+
+        /// Max amount of gas for this transaction
+        #[arg(short = 'l', long, default_value_t = DEFAULT_LIMIT_CALL)]
+        gas_limit: u64,
+
+        /// Price you're going to pay for each gas unit (in LUX)
+        #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
+        gas_price: Lux,
+    },
+
+    /// Lock Dusk from your public account in an escrow for a payee
+    EscrowCreate {
+        /// Address from which to lock the Dusk [default: first address]
+        #[arg(long)]
+        sender: Option<Address>,
+
+        /// Contract id of the escrow contract, hex-encoded
+        #[arg(short, long)]
+        escrow: String,
+
+        /// Public address of the payee
+        #[arg(long)]
+        payee: Address,
+
+        /// Public address of the arbiter, allowed to release or refund the
+        /// escrow
+        #[arg(long)]
+        arbiter: Option<Address>,
+
+        /// Amount of DUSK to lock
+        #[arg(short, long)]
+        amt: Dusk,
+
+        /// Block height from which you can refund the escrow yourself
+        #[arg(long)]
+        timeout: u64,
+
+        /// Max amount of gas for this transaction
+        #[arg(short = 'l', long, default_value_t = DEFAULT_LIMIT_CALL)]
+        gas_limit: u64,
+
+        /// Price you're going to pay for each gas unit (in LUX)
+        #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
+        gas_price: Lux,
+    },
+
+    /// Release the funds of an escrow to its payee
+    EscrowRelease {
+        /// Address of the payer or the arbiter [default: first address]
+        #[arg(long)]
+        sender: Option<Address>,
+
+        /// Contract id of the escrow contract, hex-encoded
+        #[arg(short, long)]
+        escrow: String,
+
+        /// Identifier of the escrow
+        #[arg(short, long)]
+        id: u64,
+
+        /// Max amount of gas for this transaction
+        #[arg(short = 'l', long, default_value_t = DEFAULT_LIMIT_CALL)]
+        gas_limit: u64,
+
+        /// Price you're going to pay for each gas unit (in LUX)
+        #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
+        gas_price: Lux,
+    },
+
+    /// Refund the funds of an escrow to its payer
+    EscrowRefund {
+        /// Address of the payee or the arbiter, or of the payer after the
+        /// timeout [default: first address]
+        #[arg(long)]
+        sender: Option<Address>,
+
+        /// Contract id of the escrow contract, hex-encoded
+        #[arg(short, long)]
+        escrow: String,
+
+        /// Identifier of the escrow
+        #[arg(short, long)]
+        id: u64,
+
+        /// Max amount of gas for this transaction
+        #[arg(short = 'l', long, default_value_t = DEFAULT_LIMIT_CALL)]
+        gas_limit: u64,
+
+        /// Price you're going to pay for each gas unit (in LUX)
+        #[arg(short = 'p', long, default_value_t = DEFAULT_PRICE)]
+        gas_price: Lux,
+    },
+
     /// Call a contract
     ContractCall {
         /// Address that pays the gas for the contract call [default: first]
@@ -299,10 +461,23 @@ pub(crate) enum Command {
         export_pwd: Option<String>,
     },
 
+    /// Manage the local cache of the wallet
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
     /// Show current settings
     Settings,
 }
 
+/// Commands operating on the local cache of the wallet
+#[derive(PartialEq, Eq, Hash, Clone, Subcommand, Debug)]
+pub(crate) enum CacheCommand {
+    /// Show a summary of the notes and history stored in the cache
+    Inspect,
+}
+
 impl Command {
     /// Runs the command with the provided wallet
     pub async fn run<'a>(
@@ -367,6 +542,11 @@ impl Command {
                 gas_price,
                 memo,
             } => {
+                let rcvr = match rcvr {
+                    Receiver::Address(addr) => addr,
+                    Receiver::Name(name) => wallet.resolve_name(&name).await?,
+                };
+
                 let sender_idx = match sender {
                     Some(addr) => {
                         addr.same_transaction_model(&rcvr)?;
@@ -406,12 +586,17 @@ impl Command {
                 amt,
                 gas_limit,
                 gas_price,
+                at_epoch_boundary,
             } => {
                 let address = address.unwrap_or(wallet.default_address());
                 let addr_idx = wallet.find_index(&address)?;
                 let owner_idx =
                     owner.map(|owner| wallet.find_index(&owner)).transpose()?;
 
+                if at_epoch_boundary {
+                    wait_for_epoch_boundary(settings).await?;
+                }
+
                 let gas = Gas::new(gas_limit).with_price(gas_price);
                 let tx = match address {
                     Address::Shielded(_) => {
@@ -433,10 +618,15 @@ impl Command {
                 address,
                 gas_limit,
                 gas_price,
+                at_epoch_boundary,
             } => {
                 let address = address.unwrap_or(wallet.default_address());
                 let addr_idx = wallet.find_index(&address)?;
 
+                if at_epoch_boundary {
+                    wait_for_epoch_boundary(settings).await?;
+                }
+
                 let gas = Gas::new(gas_limit).with_price(gas_price);
                 let tx = match address {
                     Address::Shielded(_) => {
@@ -518,7 +708,8 @@ impl Command {
                 let address = wallet.public_address(profile_idx)?;
 
                 let mut phoenix_history =
-                    history::transaction_from_notes(settings, notes).await?;
+                    history::transaction_from_notes(wallet, settings, notes)
+                        .await?;
 
                 if let Ok(mut moonlight_history) =
                     history::moonlight_history(settings, address).await
@@ -560,6 +751,117 @@ impl Command {
                     wallet.moonlight_to_phoenix(profile_idx, amt, gas).await?;
                 Ok(RunResult::Tx(tx.hash()))
             }
+            Command::TokenBalance { profile_idx, token } => {
+                let profile_idx = profile_idx.unwrap_or_default();
+                let token = parse_contract_id(&token)?;
+
+                let balance = wallet.token_balance(profile_idx, token).await?;
+
+                Ok(RunResult::TokenBalance(balance))
+            }
+            Command::TokenTransfer {
+                sender,
+                token,
+                rcvr,
+                amt,
+                gas_limit,
+                gas_price,
+            } => {
+                let sender = sender.unwrap_or(wallet.default_public_address());
+                let sender_idx = wallet.find_index(&sender)?;
+                let token = parse_contract_id(&token)?;
+                let rcvr_pk = rcvr.public_key()?;
+
+                let gas = Gas::new(gas_limit).with_price(gas_price);
+                let tx = wallet
+                    .token_transfer(sender_idx, token, rcvr_pk, amt, gas)
+                    .await?;
+
+                Ok(RunResult::Tx(tx.hash()))
+            }
+            Command::TokenApprove {
+                sender,
+                token,
+                spender,
+                amt,
+                gas_limit,
+                gas_price,
+            } => {
+                let sender = sender.unwrap_or(wallet.default_public_address());
+                let sender_idx = wallet.find_index(&sender)?;
+                let token = parse_contract_id(&token)?;
+                let spender_pk = spender.public_key()?;
+
+                let gas = Gas::new(gas_limit).with_price(gas_price);
+                let tx = wallet
+                    .token_approve(sender_idx, token, spender_pk, amt, gas)
+                    .await?;
+
+                Ok(RunResult::Tx(tx.hash()))
+            }
+            Command::EscrowCreate {
+                sender,
+                escrow,
+                payee,
+                arbiter,
+                amt,
+                timeout,
+                gas_limit,
+                gas_price,
+            } => {
+                let sender = sender.unwrap_or(wallet.default_public_address());
+                let sender_idx = wallet.find_index(&sender)?;
+                let escrow = parse_contract_id(&escrow)?;
+                let payee_pk = payee.public_key()?;
+                let arbiter_pk = match &arbiter {
+                    Some(arbiter) => Some(arbiter.public_key()?),
+                    None => None,
+                };
+
+                let gas = Gas::new(gas_limit).with_price(gas_price);
+                let tx = wallet
+                    .escrow_create(
+                        sender_idx, escrow, payee_pk, arbiter_pk, amt, timeout,
+                        gas,
+                    )
+                    .await?;
+
+                Ok(RunResult::Tx(tx.hash()))
+            }
+            Command::EscrowRelease {
+                sender,
+                escrow,
+                id,
+                gas_limit,
+                gas_price,
+            } => {
+                let sender = sender.unwrap_or(wallet.default_public_address());
+                let sender_idx = wallet.find_index(&sender)?;
+                let escrow = parse_contract_id(&escrow)?;
+
+                let gas = Gas::new(gas_limit).with_price(gas_price);
+                let tx =
+                    wallet.escrow_release(sender_idx, escrow, id, gas).await?;
+
+                Ok(RunResult::Tx(tx.hash()))
+            }
+            Command::EscrowRefund {
+                sender,
+                escrow,
+                id,
+                gas_limit,
+                gas_price,
+            } => {
+                let sender = sender.unwrap_or(wallet.default_public_address());
+                let sender_idx = wallet.find_index(&sender)?;
+                let escrow = parse_contract_id(&escrow)?;
+
+                let gas = Gas::new(gas_limit).with_price(gas_price);
+                let tx =
+                    wallet.escrow_refund(sender_idx, escrow, id, gas).await?;
+
+                Ok(RunResult::Tx(tx.hash()))
+            }
             Command::ContractCall {
                 address,
                 contract_id,
@@ -680,6 +982,9 @@ impl Command {
 
                 Ok(RunResult::ContractId(contract_id))
             }
+            Command::Cache {
+                command: CacheCommand::Inspect,
+            } => Ok(RunResult::CacheInfo(wallet.cache_info()?)),
             Command::Create { .. } => Ok(RunResult::Create()),
             Command::Restore { .. } => Ok(RunResult::Restore()),
             Command::Settings => Ok(RunResult::Settings()),
@@ -687,11 +992,67 @@ impl Command {
     }
 }
 
+/// Wait for the block at which a staking transaction scheduled for the epoch
+/// boundary is submitted.
+async fn wait_for_epoch_boundary(settings: &Settings) -> anyhow::Result<()> {
+    let gql = GraphQL::new(settings.state.to_string(), status::headless)?;
+
+    let height = gql.block_height().await?;
+    let target = epoch_boundary_height(height);
+    if target > height {
+        println!(
+            "Waiting for block {target}, at the end of epoch {}, to submit \
+             the transaction...",
+            target / EPOCH
+        );
+        gql.wait_for_height(target).await?;
+    }
+
+    Ok(())
+}
+
+/// Parse a hex-encoded contract id.
+fn parse_contract_id(hex: &str) -> Result<ContractId, Error> {
+    let bytes: [u8; CONTRACT_ID_BYTES] = hex::decode(hex)
+        .map_err(|_| Error::InvalidContractId)?
+        .try_into()
+        .map_err(|_| Error::InvalidContractId)?;
+
+    Ok(ContractId::from_bytes(bytes))
+}
+
+/// The receiver of a transfer, given either as an address or as a name
+/// registered with the Dusk Name Service.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub(crate) enum Receiver {
+    Address(Address),
+    Name(String),
+}
+
+impl FromStr for Receiver {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.ends_with(NAME_SUFFIX) {
+            Ok(Self::Name(s.to_string()))
+        } else {
+            Ok(Self::Address(s.parse()?))
+        }
+    }
+}
+
+impl From<Address> for Receiver {
+    fn from(addr: Address) -> Self {
+        Self::Address(addr)
+    }
+}
+
 /// Possible results of running a command in interactive mode
 pub enum RunResult<'a> {
     Tx(BlsScalar),
     PhoenixBalance(BalanceInfo, bool),
     MoonlightBalance(Dusk),
+    TokenBalance(u64),
     StakeInfo(StakeData, bool),
     Profile((u8, &'a Profile)),
     Profiles(&'a Vec<Profile>),
@@ -701,6 +1062,7 @@ pub enum RunResult<'a> {
     Restore(),
     Settings(),
     History(Vec<TransactionHistory>),
+    CacheInfo(CacheInfo),
 }
 
 impl fmt::Display for RunResult<'_> {
@@ -719,6 +1081,9 @@ impl fmt::Display for RunResult<'_> {
             MoonlightBalance(balance) => {
                 write!(f, "> Total public balance: {balance} DUSK")
             }
+            TokenBalance(balance) => {
+                write!(f, "> Token balance: {balance}")
+            }
             Profile((profile_idx, profile)) => {
                 write!(
                     f,
@@ -789,6 +1154,31 @@ impl fmt::Display for RunResult<'_> {
                 }
                 Ok(())
             }
+            CacheInfo(info) => {
+                writeln!(f, "> Cache database: {}", info.path.display())?;
+                writeln!(f, "> Schema version: {}", info.schema_version)?;
+                match info.last_pos {
+                    Some(pos) => writeln!(f, "> Last synced note: #{pos}")?,
+                    None => writeln!(f, "> No note synced yet")?,
+                }
+                if let Some(secs) = info.last_sync {
+                    writeln!(f, "> Last sync at: {secs} (Unix time)")?;
+                }
+                for (profile_idx, (unspent, spent)) in
+                    info.notes.iter().enumerate()
+                {
+                    writeln!(
+                        f,
+                        "> {}: {unspent} unspent notes, {spent} spent notes",
+                        crate::Profile::index_string(profile_idx as u8),
+                    )?;
+                }
+                write!(
+                    f,
+                    "> Cached history: {} transactions in {} blocks",
+                    info.transactions, info.blocks,
+                )
+            }
             Create() | Restore() | Settings() => unreachable!(),
         }
     }
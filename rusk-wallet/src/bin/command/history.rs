@@ -10,10 +10,11 @@ use std::fmt::{self, Display};
 
 use dusk_core::transfer::Transaction;
 use dusk_core::{dusk, from_dusk};
-use rusk_wallet::{BlockTransaction, DecodedNote, GraphQL};
+use rusk_wallet::{BlockTransaction, DecodedNote, GraphQL, Wallet};
 
 use crate::io::{self};
 use crate::settings::Settings;
+use crate::WalletFile;
 
 pub struct TransactionHistory {
     direction: TransactionDirection,
@@ -66,6 +67,7 @@ impl Display for TransactionHistory {
 }
 
 pub(crate) async fn transaction_from_notes(
+    wallet: &Wallet<WalletFile>,
     settings: &Settings,
     mut notes: Vec<DecodedNote>,
 ) -> anyhow::Result<Vec<TransactionHistory>> {
@@ -93,7 +95,9 @@ pub(crate) async fn transaction_from_notes(
         let txs = match block_txs.entry(decoded_note.block_height) {
             Entry::Occupied(o) => o.into_mut(),
             Entry::Vacant(v) => {
-                let txs = gql.txs_for_block(decoded_note.block_height).await?;
+                let txs = wallet
+                    .block_transactions(&gql, decoded_note.block_height)
+                    .await?;
                 v.insert(txs)
             }
         };
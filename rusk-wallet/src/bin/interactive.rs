@@ -12,11 +12,12 @@ use bip39::{Language, Mnemonic, MnemonicType};
 use inquire::{InquireError, Select};
 use rusk_wallet::currency::Dusk;
 use rusk_wallet::dat::{DatFileVersion, LATEST_VERSION};
+use rusk_wallet::gas::Gas;
 use rusk_wallet::{Address, Error, Profile, Wallet, WalletPath, MAX_PROFILES};
 
 use crate::io::{self, prompt};
 use crate::settings::Settings;
-use crate::{Command, GraphQL, RunResult, WalletFile};
+use crate::{Command, GraphQL, Receiver, RunResult, WalletFile};
 
 /// Run the interactive UX loop with a loaded wallet
 pub(crate) async fn run_loop(
@@ -357,10 +358,16 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             memo,
         } => {
             let sender = sender.as_ref().ok_or(Error::BadAddress)?;
-            sender.same_transaction_model(rcvr)?;
-            let max_fee = gas_limit * gas_price;
+            let max_fee =
+                Gas::new(*gas_limit).with_price(*gas_price).max_fee()?;
             println!("   > Pay with {}", sender.preview());
-            println!("   > Recipient = {}", rcvr.preview());
+            match rcvr {
+                Receiver::Address(rcvr) => {
+                    sender.same_transaction_model(rcvr)?;
+                    println!("   > Recipient = {}", rcvr.preview());
+                }
+                Receiver::Name(name) => println!("   > Recipient = {name}"),
+            }
             println!("   > Amount to transfer = {} DUSK", amt);
             if let Some(memo) = memo {
                 println!("   > Memo = {memo}");
@@ -377,9 +384,11 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             amt,
             gas_limit,
             gas_price,
+            at_epoch_boundary,
         } => {
             let sender = address.as_ref().ok_or(Error::BadAddress)?;
-            let max_fee = gas_limit * gas_price;
+            let max_fee =
+                Gas::new(*gas_limit).with_price(*gas_price).max_fee()?;
             let stake_to = wallet.public_address(wallet.find_index(sender)?)?;
             let owner = owner.as_ref().unwrap_or(&stake_to);
             println!("   > Pay with {}", sender.preview());
@@ -387,6 +396,9 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             println!("   > Stake owner {}", owner.preview());
             println!("   > Amount to stake = {} DUSK", amt);
             println!("   > Max fee = {} DUSK", Dusk::from(max_fee));
+            if *at_epoch_boundary {
+                println!("   > Submitted at the end of the current epoch");
+            }
             if let Address::Public(_) = sender {
                 println!("   > ALERT: THIS IS A PUBLIC TRANSACTION");
             }
@@ -396,16 +408,21 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             address,
             gas_limit,
             gas_price,
+            at_epoch_boundary,
         } => {
             let sender = address.as_ref().ok_or(Error::BadAddress)?;
             let unstake_from =
                 wallet.public_address(wallet.find_index(sender)?)?;
-            let max_fee = gas_limit * gas_price;
+            let max_fee =
+                Gas::new(*gas_limit).with_price(*gas_price).max_fee()?;
 
             println!("   > Pay with {}", sender.preview());
             println!("   > Unstake from {}", unstake_from.preview());
             println!("   > Receive stake at {}", sender.preview());
             println!("   > Max fee = {} DUSK", Dusk::from(max_fee));
+            if *at_epoch_boundary {
+                println!("   > Submitted at the end of the current epoch");
+            }
             if let Address::Public(_) = sender {
                 println!("   > ALERT: THIS IS A PUBLIC TRANSACTION");
             }
@@ -418,7 +435,8 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             gas_price,
         } => {
             let sender = address.as_ref().ok_or(Error::BadAddress)?;
-            let max_fee = gas_limit * gas_price;
+            let max_fee =
+                Gas::new(*gas_limit).with_price(*gas_price).max_fee()?;
             let withdraw_from =
                 wallet.public_address(wallet.find_index(sender)?)?;
 
@@ -442,7 +460,8 @@ fn confirm(cmd: &Command, wallet: &Wallet<WalletFile>) -> anyhow::Result<bool> {
             let sender = address.as_ref().ok_or(Error::BadAddress)?;
             let sender_index = wallet.find_index(sender)?;
             let code_len = code.metadata()?.len();
-            let max_fee = gas_limit * gas_price;
+            let max_fee =
+                Gas::new(*gas_limit).with_price(*gas_price).max_fee()?;
             let code_bytes = std::fs::read(code)?;
 
             let contract_id = wallet.get_contract_id(
@@ -10,7 +10,7 @@ use dusk_core::transfer::data::MAX_MEMO_SIZE;
 use inquire::{InquireError, Select};
 use rusk_wallet::currency::Dusk;
 use rusk_wallet::gas::{
-    self, DEFAULT_LIMIT_CALL, DEFAULT_LIMIT_STAKE, DEFAULT_LIMIT_TRANSFER,
+    self, Gas, DEFAULT_LIMIT_CALL, DEFAULT_LIMIT_STAKE, DEFAULT_LIMIT_TRANSFER,
     DEFAULT_PRICE, GAS_PER_DEPLOY_BYTE, MIN_PRICE_DEPLOYMENT,
 };
 use rusk_wallet::{
@@ -130,7 +130,7 @@ pub(crate) async fn online(
 
             ProfileOp::Run(Box::new(Command::Transfer {
                 sender: Some(sender),
-                rcvr,
+                rcvr: rcvr.into(),
                 amt,
                 gas_limit: prompt::request_gas_limit(
                     gas::DEFAULT_LIMIT_TRANSFER,
@@ -191,6 +191,7 @@ pub(crate) async fn online(
                     DEFAULT_PRICE,
                     mempool_gas_prices,
                 )?,
+                at_epoch_boundary: prompt::ask_epoch_boundary()?,
             }))
         }
         MenuItem::Unstake => {
@@ -220,6 +221,7 @@ pub(crate) async fn online(
                     DEFAULT_PRICE,
                     mempool_gas_prices,
                 )?,
+                at_epoch_boundary: prompt::ask_epoch_boundary()?,
             }))
         }
         MenuItem::Withdraw => {
@@ -275,7 +277,7 @@ pub(crate) async fn online(
 
             if check_min_gas_balance(
                 balance,
-                gas_limit * gas_price,
+                Gas::new(gas_limit).with_price(gas_price).max_fee()?,
                 "the deployment of the given contract",
             )
             .is_err()
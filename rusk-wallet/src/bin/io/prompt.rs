@@ -178,6 +178,18 @@ pub(crate) fn ask_confirm() -> anyhow::Result<bool> {
         .prompt()?)
 }
 
+/// Asks the user whether to wait for the end of the epoch before submitting a
+/// staking transaction
+pub(crate) fn ask_epoch_boundary() -> anyhow::Result<bool> {
+    Ok(Confirm::new("Wait for the end of the current epoch to submit?")
+        .with_default(false)
+        .with_help_message(
+            "A stake only becomes eligible from the second epoch after it is \
+             included, wherever it lands in the current one",
+        )
+        .prompt()?)
+}
+
 /// Asks the user for confirmation before deleting cache
 pub(crate) fn ask_confirm_erase_cache(msg: &str) -> anyhow::Result<bool> {
     Ok(Confirm::new(msg).prompt()?)
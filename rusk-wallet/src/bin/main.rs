@@ -10,7 +10,7 @@ mod interactive;
 mod io;
 mod settings;
 
-pub(crate) use command::{Command, RunResult};
+pub(crate) use command::{Command, Receiver, RunResult};
 
 use std::fs::{self, File};
 use std::io::Write;
@@ -83,17 +83,33 @@ where
 
     // check for connection errors
     match con {
-        Err(Error::RocksDB(e)) => {
+        Err(
+            e @ (Error::Sqlite(_)
+            | Error::RocksDB(_)
+            | Error::CacheDatabaseCorrupted
+            | Error::CacheOwnerMismatch),
+        ) => {
             wallet.close();
 
-            let msg = match e.kind() {
-                ErrorKind::InvalidArgument => {
+            let corrupted = match &e {
+                Error::CacheDatabaseCorrupted => true,
+                Error::RocksDB(e) => matches!(e.kind(), ErrorKind::Corruption),
+                Error::Sqlite(rusqlite::Error::SqliteFailure(e, _)) => matches!(
+                    e.code,
+                    rusqlite::ErrorCode::DatabaseCorrupt
+                        | rusqlite::ErrorCode::NotADatabase
+                ),
+                _ => false,
+            };
+
+            let msg = match e {
+                Error::CacheOwnerMismatch => {
                     format!("You seem to try access a wallet with a different mnemonic phrase\n\r\n\r{0: <1} delete the cache? (Alternatively specify the --wallet-dir flag to add a new wallet under the given path)", "[ALERT]")
                 },
-                ErrorKind::Corruption => {
+                _ if corrupted => {
                        format!("The database appears to be corrupted \n\r\n\r{0: <1} delete the cache?", "[ALERT]")
                 },
-                _ => {
+                e => {
                     format!("Unknown database error {:?} \n\r\n\r{1: <1} delete the cache?", e, "[ALERT]")
                 }
             };
@@ -334,6 +350,9 @@ async fn exec() -> anyhow::Result<()> {
                 RunResult::MoonlightBalance(balance) => {
                     println!("Total: {}", balance);
                 }
+                RunResult::TokenBalance(balance) => {
+                    println!("{balance}");
+                }
                 RunResult::Profile((profile_idx, profile)) => {
                     println!(
                         "> {}\n>   {}\n>   {}\n",
@@ -401,6 +420,9 @@ async fn exec() -> anyhow::Result<()> {
                 RunResult::ContractId(id) => {
                     println!("Contract ID: {:?}", id);
                 }
+                RunResult::CacheInfo(info) => {
+                    println!("{}", RunResult::CacheInfo(info));
+                }
                 RunResult::Settings() => {}
                 RunResult::Create() | RunResult::Restore() => {}
             }
@@ -4,103 +4,220 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod legacy;
+
 use std::collections::BTreeSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_core::transfer::phoenix::NoteLeaf;
-use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use dusk_core::transfer::Transaction;
+use rusqlite::{params, Connection, OptionalExtension};
+use wallet_core::CoinControl;
 
 use super::*;
 use crate::clients::TREE_LEAF;
 use crate::error::Error;
 
-type DB = DBWithThreadMode<MultiThreaded>;
+/// The name of the database file, inside the cache directory
+const DB_FILE: &str = "cache.sqlite";
+
+/// The migrations of the database schema, applied in order. The version of
+/// the schema, stored as the `user_version` of the database, is the number of
+/// migrations applied to it.
+const MIGRATIONS: &[&str] = &[
+    // 1: notes, sync checkpoints and transactions history
+    "CREATE TABLE metadata (
+        key TEXT PRIMARY KEY,
+        value BLOB NOT NULL
+    );
+    CREATE TABLE notes (
+        nullifier BLOB PRIMARY KEY,
+        owner TEXT NOT NULL,
+        block_height INTEGER NOT NULL,
+        pos INTEGER NOT NULL,
+        leaf BLOB NOT NULL,
+        spent INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX notes_owner ON notes (owner, spent, pos);
+    CREATE TABLE sync_checkpoints (
+        pos INTEGER PRIMARY KEY,
+        synced_at INTEGER NOT NULL
+    );
+    CREATE TABLE blocks (
+        block_height INTEGER PRIMARY KEY
+    );
+    CREATE TABLE transactions (
+        id TEXT PRIMARY KEY,
+        block_height INTEGER NOT NULL REFERENCES blocks (block_height),
+        raw BLOB NOT NULL,
+        gas_spent INTEGER NOT NULL
+    );
+    CREATE INDEX transactions_block ON transactions (block_height);",
+];
+
+/// A summary of the content of the wallet cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// The path of the cache database
+    pub path: PathBuf,
+    /// The version of the schema of the database
+    pub schema_version: usize,
+    /// The position of the last synced note, if any
+    pub last_pos: Option<u64>,
+    /// The time of the last sync, in seconds since the Unix epoch
+    pub last_sync: Option<u64>,
+    /// The number of unspent and spent notes of each profile, in order
+    pub notes: Vec<(usize, usize)>,
+    /// The number of blocks whose transactions are cached
+    pub blocks: usize,
+    /// The number of cached transactions
+    pub transactions: usize,
+}
 
-/// A cache of notes received from Rusk.
+/// A cache of notes received from Rusk, and of the transactions of the blocks
+/// they were received in.
 ///
-/// path is the path of the rocks db database
+/// The cache is an SQLite database stored in the cache directory.
 pub(crate) struct Cache {
-    db: DB,
+    conn: Mutex<Connection>,
+    path: PathBuf,
 }
 
 impl Cache {
     /// Returns a new cache instance.
+    ///
+    /// The cache belongs to the given profiles, identified by the bs58
+    /// encoding of their shielded keys. Opening a cache created for a
+    /// different wallet returns [`Error::CacheOwnerMismatch`].
     pub(crate) fn new<T: AsRef<Path>>(
-        path: T,
-        cfs: Vec<String>,
+        dir: T,
+        profiles: Vec<String>,
         status: fn(&str),
     ) -> Result<Self, Error> {
         status("Opening notes database");
 
-        let mut opts = Options::default();
-        opts.create_if_missing(true);
-        opts.create_missing_column_families(true);
-        // After 10 million bytes, sort the cache file and create new one
-        opts.set_write_buffer_size(10_000_000);
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let path = dir.join(DB_FILE);
+        let mut conn = Connection::open(&path)?;
+
+        let integrity: String =
+            conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        if integrity != "ok" {
+            return Err(Error::CacheDatabaseCorrupted);
+        }
+
+        migrate(&mut conn)?;
+
+        let cache = Self {
+            conn: Mutex::new(conn),
+            path,
+        };
+
+        // the first profile identifies the wallet the cache belongs to
+        if let Some(owner) = profiles.first() {
+            cache.check_owner(owner)?;
+        }
+
+        // import the notes of a cache created by previous versions, which is
+        // removed only once imported
+        if legacy::exists(dir) {
+            status("Migrating notes database");
+            legacy::import(dir, &profiles, &cache)?;
+        }
+
+        Ok(cache)
+    }
+
+    fn conn(&self) -> MutexGuard<Connection> {
+        // We can get an error if the thread holding the lock panicked while
+        // holding the lock. In this case, we can recover the guard from the
+        // poison error, since every write happens in a transaction.
+        match self.conn.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn check_owner(&self, owner: &str) -> Result<(), Error> {
+        let conn = self.conn();
+
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'owner'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match stored {
+            Some(stored) if stored != owner => Err(Error::CacheOwnerMismatch),
+            Some(_) => Ok(()),
+            None => {
+                conn.execute(
+                    "INSERT INTO metadata (key, value) VALUES ('owner', ?1)",
+                    params![owner],
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    // We store the nullifier of the note as key and the bytes representation
+    // of the leaf, together with its owner and position.
+    fn insert_leaf(
+        &self,
+        owner: &str,
+        nullifier: &[u8],
+        leaf: &NoteLeaf,
+        spent: bool,
+    ) -> Result<(), Error> {
+        let data = rkyv::to_bytes::<NoteLeaf, TREE_LEAF>(leaf)
+            .map_err(|_| Error::Rkyv)?;
 
-        // create all CF(s) on startup if we don't have them
-        let db = DB::open_cf(&opts, path, cfs)?;
+        self.conn().execute(
+            "INSERT OR REPLACE INTO notes
+                (nullifier, owner, block_height, pos, leaf, spent)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                nullifier,
+                owner,
+                leaf.block_height,
+                *leaf.note.pos(),
+                data.as_slice(),
+                spent
+            ],
+        )?;
 
-        Ok(Self { db })
+        Ok(())
     }
 
-    // We store a column family named by hex representation of the pk.
-    // We store the nullifier of the note as key and the value is the bytes
-    // representation of the tuple (NoteHeight, Note)
     pub(crate) fn insert(
         &self,
         pk_bs58: &str,
         block_height: u64,
         note_data: (Note, BlsScalar),
     ) -> Result<(), Error> {
-        let cf_name = pk_bs58;
-
-        let cf = self
-            .db
-            .cf_handle(cf_name)
-            .ok_or(Error::CacheDatabaseCorrupted)?;
-
         let (note, nullifier) = note_data;
-
         let leaf = NoteLeaf { block_height, note };
 
-        let data = rkyv::to_bytes::<NoteLeaf, TREE_LEAF>(&leaf)
-            .map_err(|_| Error::Rkyv)?;
-
-        let key = nullifier.to_bytes();
-
-        self.db.put_cf(&cf, key, data)?;
-
-        Ok(())
+        self.insert_leaf(pk_bs58, &nullifier.to_bytes(), &leaf, false)
     }
 
-    // We store a column family named by hex representation of the pk.
-    // We store the nullifier of the note as key and the value is the bytes
-    // representation of the tuple (NoteHeight, Note)
     pub(crate) fn insert_spent(
         &self,
         pk_bs58: &str,
         block_height: u64,
         note_data: (Note, BlsScalar),
     ) -> Result<(), Error> {
-        let cf_name = format!("spent_{pk_bs58}");
-
-        let cf = self
-            .db
-            .cf_handle(&cf_name)
-            .ok_or(Error::CacheDatabaseCorrupted)?;
-
         let (note, nullifier) = note_data;
-
         let leaf = NoteLeaf { block_height, note };
-        let data = rkyv::to_bytes::<NoteLeaf, TREE_LEAF>(&leaf)
-            .map_err(|_| Error::Rkyv)?;
-        let key = nullifier.to_bytes();
-
-        self.db.put_cf(&cf, key, data)?;
 
-        Ok(())
+        self.insert_leaf(pk_bs58, &nullifier.to_bytes(), &leaf, true)
     }
 
     pub(crate) fn spend_notes(
@@ -114,33 +231,44 @@ impl Cache {
 
         let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
 
-        let spent_cf_name = format!("spent_{pk_bs58}");
-        let cf_name = pk_bs58;
-
-        let cf = self
-            .db
-            .cf_handle(&cf_name)
-            .ok_or(Error::CacheDatabaseCorrupted)?;
-        let spent_cf = self
-            .db
-            .cf_handle(&spent_cf_name)
-            .ok_or(Error::CacheDatabaseCorrupted)?;
-
-        for n in nullifiers {
-            let key = n.to_bytes();
-            let to_move = self
-                .db
-                .get_cf(&cf, key)?
-                .ok_or(Error::CacheDatabaseCorrupted)?;
-            self.db.put_cf(&spent_cf, key, to_move)?;
-            self.db.delete_cf(&cf, n.to_bytes())?;
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "UPDATE notes SET spent = 1
+                    WHERE nullifier = ?1 AND owner = ?2 AND spent = 0",
+            )?;
+            for n in nullifiers {
+                if stmt.execute(params![&n.to_bytes()[..], pk_bs58])? == 0 {
+                    return Err(Error::CacheDatabaseCorrupted);
+                }
+            }
+        }
+        tx.commit()?;
+        drop(conn);
+
+        // spent notes can't be frozen anymore
+        let mut coin_control = self.coin_control()?;
+        if nullifiers.iter().any(|n| coin_control.is_frozen(n)) {
+            coin_control.remove_spent(nullifiers);
+            self.insert_coin_control(&coin_control)?;
         }
 
         Ok(())
     }
 
+    /// Records a sync checkpoint at the given position.
     pub(crate) fn insert_last_pos(&self, last_pos: u64) -> Result<(), Error> {
-        self.db.put(b"last_pos", last_pos.to_be_bytes())?;
+        let synced_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        self.conn().execute(
+            "INSERT OR REPLACE INTO sync_checkpoints (pos, synced_at)
+                VALUES (?1, ?2)",
+            params![last_pos, synced_at],
+        )?;
 
         Ok(())
     }
@@ -148,98 +276,298 @@ impl Cache {
     /// Returns the last position of inserted notes. If no note has ever been
     /// inserted it returns None.
     pub(crate) fn last_pos(&self) -> Result<Option<u64>, Error> {
-        let last_pos = self.db.get(b"last_pos")?;
+        let last_pos = self.conn().query_row(
+            "SELECT MAX(pos) FROM sync_checkpoints",
+            [],
+            |row| row.get(0),
+        )?;
 
-        match last_pos {
-            Some(x) => {
-                let buff =
-                    x.try_into().map_err(|_| Error::CacheDatabaseCorrupted)?;
+        Ok(last_pos)
+    }
 
-                Ok(Some(u64::from_be_bytes(buff)))
-            }
-            None => Ok(None),
+    pub(crate) fn insert_coin_control(
+        &self,
+        coin_control: &CoinControl,
+    ) -> Result<(), Error> {
+        let data = rkyv::to_bytes::<CoinControl, 1024>(coin_control)
+            .map_err(|_| Error::Rkyv)?;
+
+        self.conn().execute(
+            "INSERT OR REPLACE INTO metadata (key, value)
+                VALUES ('coin_control', ?1)",
+            params![data.as_slice()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the coin-control metadata, holding the notes that are frozen.
+    pub(crate) fn coin_control(&self) -> Result<CoinControl, Error> {
+        let data: Option<Vec<u8>> = self
+            .conn()
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'coin_control'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match data {
+            Some(data) => rkyv::from_bytes(&data)
+                .map_err(|_| Error::CacheDatabaseCorrupted),
+            None => Ok(CoinControl::default()),
         }
     }
 
-    /// Returns an iterator over all unspent notes nullifier for the given pk.
+    /// Returns all unspent notes nullifier for the given pk.
     pub(crate) fn unspent_notes_id(
         &self,
         pk: &PhoenixPublicKey,
     ) -> Result<Vec<BlsScalar>, Error> {
-        let cf_name = bs58::encode(pk.to_bytes()).into_string();
-        let mut notes = vec![];
-
-        if let Some(cf) = self.db.cf_handle(&cf_name) {
-            let iterator =
-                self.db.iterator_cf(&cf, rocksdb::IteratorMode::Start);
+        let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
 
-            for i in iterator {
-                let (id, _) = i?;
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT nullifier FROM notes WHERE owner = ?1 AND spent = 0",
+        )?;
+        let rows =
+            stmt.query_map(params![pk_bs58], |row| row.get::<_, Vec<u8>>(0))?;
 
-                let id = BlsScalar::from_slice(&id)?;
-                notes.push(id);
-            }
-        };
+        let mut notes = vec![];
+        for id in rows {
+            notes.push(BlsScalar::from_slice(&id?)?);
+        }
 
         Ok(notes)
     }
 
-    /// Returns an iterator over all unspent notes inserted for the given pk,
-    /// in order of note position.
+    /// Returns all unspent notes inserted for the given pk, in order of note
+    /// position.
     pub(crate) fn notes(
         &self,
         pk: &PhoenixPublicKey,
     ) -> Result<BTreeSet<NoteLeaf>, Error> {
-        let cf_name = bs58::encode(pk.to_bytes()).into_string();
-        let mut notes = BTreeSet::<NoteLeaf>::new();
-
-        if let Some(cf) = self.db.cf_handle(&cf_name) {
-            let iterator =
-                self.db.iterator_cf(&cf, rocksdb::IteratorMode::Start);
+        let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
 
-            for i in iterator {
-                let (_, note_data) = i?;
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT leaf FROM notes WHERE owner = ?1 AND spent = 0")?;
+        let rows =
+            stmt.query_map(params![pk_bs58], |row| row.get::<_, Vec<u8>>(0))?;
 
-                let note = rkyv::from_bytes(&note_data)
-                    .map_err(|_| Error::CacheDatabaseCorrupted)?;
+        let mut notes = BTreeSet::<NoteLeaf>::new();
+        for note_data in rows {
+            let note = rkyv::from_bytes(&note_data?)
+                .map_err(|_| Error::CacheDatabaseCorrupted)?;
 
-                notes.insert(note);
-            }
-        };
+            notes.insert(note);
+        }
 
         Ok(notes)
     }
 
-    /// Returns an iterator over all notes inserted for the given pk, in order
-    /// of block height.
+    /// Returns all spent notes inserted for the given pk, in order of block
+    /// height.
     pub(crate) fn spent_notes(
         &self,
         pk: &PhoenixPublicKey,
     ) -> Result<Vec<(BlsScalar, NoteLeaf)>, Error> {
         let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
-        let cf_name = format!("spent_{pk_bs58}");
+
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT nullifier, leaf FROM notes
+                WHERE owner = ?1 AND spent = 1 ORDER BY block_height, pos",
+        )?;
+        let rows = stmt.query_map(params![pk_bs58], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+
         let mut notes = vec![];
+        for row in rows {
+            let (key, note_data) = row?;
 
-        if let Some(cf) = self.db.cf_handle(&cf_name) {
-            let iterator =
-                self.db.iterator_cf(&cf, rocksdb::IteratorMode::Start);
+            let note = rkyv::from_bytes(&note_data)
+                .map_err(|_| Error::CacheDatabaseCorrupted)?;
 
-            for i in iterator {
-                let (key, note_data) = i?;
+            let key = BlsScalar::from_slice(&key)?;
 
-                let note = rkyv::from_bytes(&note_data)
-                    .map_err(|_| Error::CacheDatabaseCorrupted)?;
+            notes.push((key, note));
+        }
 
-                let key = BlsScalar::from_slice(&key)?;
+        Ok(notes)
+    }
+
+    /// Returns the transactions of the block at the given height, if they
+    /// were cached.
+    pub(crate) fn block_transactions(
+        &self,
+        block_height: u64,
+    ) -> Result<Option<Vec<BlockTransaction>>, Error> {
+        let conn = self.conn();
+
+        let cached: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM blocks WHERE block_height = ?1)",
+            params![block_height],
+            |row| row.get(0),
+        )?;
+        if !cached {
+            return Ok(None);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT id, raw, gas_spent FROM transactions
+                WHERE block_height = ?1",
+        )?;
+        let rows = stmt.query_map(params![block_height], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Vec<u8>>(1)?,
+                row.get::<_, u64>(2)?,
+            ))
+        })?;
+
+        let mut txs = vec![];
+        for row in rows {
+            let (id, raw, gas_spent) = row?;
+
+            let tx = Transaction::from_slice(&raw)
+                .map_err(|_| Error::CacheDatabaseCorrupted)?;
+
+            txs.push(BlockTransaction { tx, id, gas_spent });
+        }
+
+        Ok(Some(txs))
+    }
 
-                notes.push((key, note));
+    /// Stores the transactions of the block at the given height.
+    pub(crate) fn insert_block_transactions(
+        &self,
+        block_height: u64,
+        txs: &[BlockTransaction],
+    ) -> Result<(), Error> {
+        let mut conn = self.conn();
+        let db_tx = conn.transaction()?;
+
+        db_tx.execute(
+            "INSERT OR REPLACE INTO blocks (block_height) VALUES (?1)",
+            params![block_height],
+        )?;
+        {
+            let mut stmt = db_tx.prepare(
+                "INSERT OR REPLACE INTO transactions
+                    (id, block_height, raw, gas_spent)
+                    VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for tx in txs {
+                stmt.execute(params![
+                    tx.id,
+                    block_height,
+                    tx.tx.to_var_bytes(),
+                    tx.gas_spent
+                ])?;
             }
-        };
+        }
+        db_tx.commit()?;
 
-        Ok(notes)
+        Ok(())
+    }
+
+    /// Returns a summary of the content of the cache, with the notes counted
+    /// for each of the given profiles.
+    pub(crate) fn info(
+        &self,
+        profiles: &[PhoenixPublicKey],
+    ) -> Result<CacheInfo, Error> {
+        let conn = self.conn();
+
+        let schema_version =
+            conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+        let (last_pos, last_sync) = conn
+            .query_row(
+                "SELECT pos, synced_at FROM sync_checkpoints
+                    ORDER BY pos DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .unzip();
+
+        let mut stmt = conn.prepare(
+            "SELECT COUNT(*) FROM notes WHERE owner = ?1 AND spent = ?2",
+        )?;
+        let mut notes = vec![];
+        for pk in profiles {
+            let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
+            let mut count = |spent: bool| {
+                stmt.query_row(params![pk_bs58, spent], |row| {
+                    row.get::<_, usize>(0)
+                })
+            };
+
+            notes.push((count(false)?, count(true)?));
+        }
+
+        let blocks =
+            conn.query_row("SELECT COUNT(*) FROM blocks", [], |row| {
+                row.get(0)
+            })?;
+        let transactions =
+            conn.query_row("SELECT COUNT(*) FROM transactions", [], |row| {
+                row.get(0)
+            })?;
+
+        Ok(CacheInfo {
+            path: self.path.clone(),
+            schema_version,
+            last_pos,
+            last_sync,
+            notes,
+            blocks,
+            transactions,
+        })
     }
 
     pub fn close(&self) {
-        self.db.cancel_all_background_work(false);
+        // keep the query planner statistics up to date, as advised by SQLite
+        // before closing long-lived connections
+        let _ = self.conn().execute_batch("PRAGMA optimize");
+    }
+}
+
+/// Applies the pending migrations to the database, returning the version of
+/// its schema before migrating.
+fn migrate(conn: &mut Connection) -> Result<usize, Error> {
+    let version: usize =
+        conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    if version > MIGRATIONS.len() {
+        // the database was created by a newer version of the wallet
+        return Err(Error::CacheDatabaseCorrupted);
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrations_are_idempotent() -> Result<(), Error> {
+        let mut conn = Connection::open_in_memory()?;
+
+        assert_eq!(migrate(&mut conn)?, 0);
+        assert_eq!(migrate(&mut conn)?, MIGRATIONS.len());
+
+        Ok(())
     }
 }
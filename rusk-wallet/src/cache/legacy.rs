@@ -0,0 +1,94 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Import of the RocksDB cache created by previous versions of the wallet.
+
+use std::fs;
+use std::path::Path;
+
+use dusk_core::transfer::phoenix::NoteLeaf;
+use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use wallet_core::CoinControl;
+
+use super::{Cache, DB_FILE};
+use crate::error::Error;
+
+type DB = DBWithThreadMode<MultiThreaded>;
+
+/// Returns true if the directory holds a RocksDB cache.
+pub(super) fn exists(dir: &Path) -> bool {
+    dir.join("CURRENT").is_file()
+}
+
+/// Imports the notes, the last synced position and the coin-control metadata
+/// of the RocksDB cache in the directory, removing it afterwards.
+///
+/// The legacy cache stores a column family for the unspent notes of each
+/// profile, named by the bs58 representation of its key, and one prefixed
+/// with `spent_` for its spent notes.
+pub(super) fn import(
+    dir: &Path,
+    profiles: &[String],
+    cache: &Cache,
+) -> Result<(), Error> {
+    {
+        let cfs = DB::list_cf(&Options::default(), dir)?;
+        let db =
+            DB::open_cf_for_read_only(&Options::default(), dir, &cfs, false)?;
+
+        for owner in profiles {
+            for (cf_name, spent) in
+                [(owner.clone(), false), (format!("spent_{owner}"), true)]
+            {
+                let Some(cf) = db.cf_handle(&cf_name) else {
+                    continue;
+                };
+
+                for entry in db.iterator_cf(&cf, rocksdb::IteratorMode::Start) {
+                    let (nullifier, note_data) = entry?;
+
+                    let leaf: NoteLeaf = rkyv::from_bytes(&note_data)
+                        .map_err(|_| Error::CacheDatabaseCorrupted)?;
+
+                    cache.insert_leaf(owner, &nullifier, &leaf, spent)?;
+                }
+            }
+        }
+
+        if let Some(last_pos) = db.get(b"last_pos")? {
+            let buff = last_pos
+                .try_into()
+                .map_err(|_| Error::CacheDatabaseCorrupted)?;
+
+            cache.insert_last_pos(u64::from_be_bytes(buff))?;
+        }
+
+        if let Some(data) = db.get(b"coin_control")? {
+            let coin_control: CoinControl = rkyv::from_bytes(&data)
+                .map_err(|_| Error::CacheDatabaseCorrupted)?;
+
+            cache.insert_coin_control(&coin_control)?;
+        }
+    }
+
+    // the legacy database is closed, remove all its files
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+
+        if entry.file_name().to_string_lossy().starts_with(DB_FILE) {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
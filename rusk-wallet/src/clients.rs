@@ -10,8 +10,11 @@ use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use dusk_bytes::Serializable;
+use dusk_core::abi::ContractId;
+use dusk_core::name_service::{Target, NAME_SERVICE_CONTRACT};
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::stake::{StakeFundOwner, StakeKeys};
+use dusk_core::token::Account as TokenAccount;
 use dusk_core::transfer::moonlight::AccountData;
 use dusk_core::transfer::phoenix::{Note, NoteLeaf, Prove};
 use dusk_core::transfer::Transaction;
@@ -23,7 +26,8 @@ use tokio::time::{sleep, Duration};
 use wallet_core::keys::{
     derive_phoenix_pk, derive_phoenix_sk, derive_phoenix_vk,
 };
-use wallet_core::pick_notes;
+use wallet_core::transaction::{plan, Consolidation, SpendPlan};
+use wallet_core::{CoinSelection, CoinSelector};
 use zeroize::Zeroize;
 
 use self::sync::sync_db;
@@ -65,6 +69,7 @@ pub struct State {
     client: RuesHttpClient,
     prover: RuesHttpClient,
     store: LocalStore,
+    coin_selection: CoinSelection,
     pub sync_rx: Option<Receiver<String>>,
     sync_join_handle: Option<JoinHandle<()>>,
 }
@@ -77,19 +82,19 @@ impl State {
         client: RuesHttpClient,
         prover: RuesHttpClient,
         store: LocalStore,
+        coin_selection: CoinSelection,
     ) -> Result<Self, Error> {
-        let cfs = (0..MAX_PROFILES)
-            .flat_map(|i| {
+        let profiles = (0..MAX_PROFILES)
+            .map(|i| {
                 let pk: PhoenixPublicKey =
                     derive_phoenix_pk(store.get_seed(), i as u8);
 
-                let pk = bs58::encode(pk.to_bytes()).into_string();
-
-                [pk.clone(), format!("spent_{pk}")]
+                bs58::encode(pk.to_bytes()).into_string()
             })
             .collect();
 
-        let cache = Mutex::new(Arc::new(Cache::new(data_dir, cfs, status)?));
+        let cache =
+            Mutex::new(Arc::new(Cache::new(data_dir, profiles, status)?));
 
         Ok(Self {
             cache,
@@ -98,10 +103,16 @@ impl State {
             prover,
             status,
             client,
+            coin_selection,
             sync_join_handle: None,
         })
     }
 
+    /// Sets the strategy used to pick the input-notes of transactions
+    pub(crate) fn set_coin_selection(&mut self, coin_selection: CoinSelection) {
+        self.coin_selection = coin_selection;
+    }
+
     /// Returns the reference to the client
     pub fn client(&self) -> &RuesHttpClient {
         &self.client
@@ -207,8 +218,32 @@ impl State {
         Ok(tx)
     }
 
-    /// Selects up to MAX_INPUT_NOTES unspent input notes from the cache. The
-    /// value of the input notes need to cover the cost of the transaction.
+    /// Freezes or thaws the note with the given nullifier, so that it isn't
+    /// picked as input-note of transactions while frozen. Returns `false` if
+    /// the note was already in the requested state.
+    pub(crate) fn set_frozen(
+        &self,
+        nullifier: BlsScalar,
+        frozen: bool,
+    ) -> Result<bool, Error> {
+        let cache = self.cache();
+        let mut coin_control = cache.coin_control()?;
+
+        let changed = if frozen {
+            coin_control.freeze(nullifier)
+        } else {
+            coin_control.thaw(&nullifier)
+        };
+        if changed {
+            cache.insert_coin_control(&coin_control)?;
+        }
+
+        Ok(changed)
+    }
+
+    /// Selects up to MAX_INPUT_NOTES unspent input notes from the cache, using
+    /// the configured coin-selection strategy. The value of the input notes
+    /// need to cover the cost of the transaction.
     pub(crate) async fn tx_input_notes(
         &self,
         index: u8,
@@ -229,8 +264,14 @@ impl State {
             })
             .collect();
 
-        // pick up to MAX_INPUT_NOTES input-notes that cover the tx-cost
-        let tx_input_notes = pick_notes(&vk, cached_notes.into(), tx_cost);
+        // pick up to MAX_INPUT_NOTES input-notes that cover the tx-cost,
+        // leaving out the frozen ones
+        let tx_input_notes = self.cache().coin_control()?.select(
+            &self.coin_selection,
+            &vk,
+            cached_notes.into(),
+            tx_cost,
+        );
         if tx_input_notes.is_empty() {
             return Err(Error::NotEnoughBalance);
         }
@@ -249,6 +290,80 @@ impl State {
         Ok(tx_input)
     }
 
+    /// Plans the consolidations needed for the unspent notes in the cache to
+    /// cover the cost of a transaction, when more than MAX_INPUT_NOTES notes
+    /// are needed, leaving out the frozen ones. Each consolidation pays the
+    /// given fee.
+    pub(crate) fn spend_plan(
+        &self,
+        index: u8,
+        tx_cost: u64,
+        consolidation_fee: u64,
+    ) -> Result<SpendPlan, Error> {
+        let vk = derive_phoenix_vk(self.store().get_seed(), index);
+        let mut sk = derive_phoenix_sk(self.store().get_seed(), index);
+        let pk = derive_phoenix_pk(self.store().get_seed(), index);
+
+        let cached_notes: Vec<_> = self
+            .cache()
+            .notes(&pk)?
+            .into_iter()
+            .map(|note_leaf| {
+                let nullifier = note_leaf.note.gen_nullifier(&sk);
+                (nullifier, note_leaf)
+            })
+            .collect();
+        sk.zeroize();
+
+        let notes = self.cache().coin_control()?.spendable(cached_notes.into());
+
+        plan(&vk, &notes, tx_cost, consolidation_fee)
+            .ok_or(Error::NotEnoughBalance)
+    }
+
+    /// The input notes of the given consolidation, with their openings and
+    /// nullifiers.
+    pub(crate) async fn consolidation_input_notes(
+        &self,
+        consolidation: &Consolidation,
+    ) -> Result<Vec<(Note, NoteOpening, BlsScalar)>, Error> {
+        let mut tx_input = Vec::with_capacity(consolidation.inputs.len());
+        for input in &consolidation.inputs {
+            // only the first consolidation of a plan is executed, which never
+            // spends the change-note of another
+            let (nullifier, note_leaf) =
+                input.note().ok_or(Error::NoteCombinationProblem)?;
+            let opening = self.fetch_opening(&note_leaf.note).await?;
+
+            tx_input.push((note_leaf.note.clone(), opening, *nullifier));
+        }
+
+        Ok(tx_input)
+    }
+
+    /// Waits for the notes with the given nullifiers to be spent, and syncs
+    /// the cache once they are.
+    pub(crate) async fn wait_for_spent(
+        &self,
+        nullifiers: &[BlsScalar],
+    ) -> Result<(), Error> {
+        let status = self.status;
+        loop {
+            let spent = sync::fetch_existing_nullifiers_remote(
+                &self.client,
+                nullifiers,
+            )
+            .await?;
+            if spent.len() == nullifiers.len() {
+                break;
+            }
+            status("Waiting for tx to be included into a block...");
+            sleep(Duration::from_millis(1000)).await;
+        }
+
+        self.sync().await
+    }
+
     pub(crate) async fn fetch_account(
         &self,
         pk: &BlsPublicKey,
@@ -428,6 +543,66 @@ impl State {
         Ok(note_count)
     }
 
+    /// Queries a token contract for the balance of the given account.
+    pub(crate) async fn fetch_token_balance(
+        &self,
+        token: ContractId,
+        account: &TokenAccount,
+    ) -> Result<u64, Error> {
+        let status = self.status;
+        status("Fetching token balance...");
+
+        let token = hex::encode(token.as_bytes());
+
+        // the target type of the deserialization has to match the return type
+        // of the contract-query
+        let balance: u64 = rkyv::from_bytes(
+            &self
+                .client
+                .contract_query::<_, _, 1024>(
+                    token.as_str(),
+                    "balance",
+                    account,
+                )
+                .await?,
+        )
+        .map_err(|_| Error::Rkyv)?;
+
+        status("Token balance received!");
+
+        Ok(balance)
+    }
+
+    /// Queries the name service for what the given name, without its
+    /// suffix, resolves to.
+    pub(crate) async fn fetch_name_target(
+        &self,
+        name: &str,
+    ) -> Result<Option<Target>, Error> {
+        let status = self.status;
+        status("Resolving name...");
+
+        let name_service = hex::encode(NAME_SERVICE_CONTRACT.as_bytes());
+
+        // the target type of the deserialization has to match the return type
+        // of the contract-query
+        let target: Option<Target> = rkyv::from_bytes(
+            &self
+                .client
+                .contract_query::<_, _, 1024>(
+                    name_service.as_str(),
+                    "resolve",
+                    &String::from(name),
+                )
+                .await?,
+        )
+        .map_err(|_| Error::Rkyv)?;
+
+        status("Name resolved!");
+
+        Ok(target)
+    }
+
     pub fn close(&mut self) {
         self.cache().close();
         let store = &mut self.store;
@@ -4,8 +4,10 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use dusk_core::transfer::MAX_NULLIFIERS_PAGE;
 use futures::StreamExt;
 use rues::CONTRACTS_TARGET;
+use wallet_core::scan_owned;
 
 use super::*;
 use crate::clients::{Cache, TRANSFER_CONTRACT};
@@ -19,17 +21,15 @@ pub(crate) async fn sync_db(
 ) -> Result<(), Error> {
     let seed = store.get_seed();
 
-    let keys: Vec<(PhoenixSecretKey, PhoenixViewKey, PhoenixPublicKey)> = (0
-        ..MAX_PROFILES)
+    let keys: Vec<(PhoenixSecretKey, PhoenixPublicKey)> = (0..MAX_PROFILES)
         .map(|i| {
             let i = i as u8;
-            (
-                derive_phoenix_sk(seed, i),
-                derive_phoenix_vk(seed, i),
-                derive_phoenix_pk(seed, i),
-            )
+            (derive_phoenix_sk(seed, i), derive_phoenix_pk(seed, i))
         })
         .collect();
+    let vks: Vec<PhoenixViewKey> = (0..MAX_PROFILES)
+        .map(|i| derive_phoenix_vk(seed, i as u8))
+        .collect();
 
     status("Getting cached note position...");
 
@@ -69,41 +69,49 @@ pub(crate) async fn sync_db(
         let mut leaf_chunk = buffer.chunks_exact(TREE_LEAF);
 
         for leaf_bytes in leaf_chunk.by_ref() {
-            let NoteLeaf { block_height, note } =
+            let leaf: NoteLeaf =
                 rkyv::from_bytes(leaf_bytes).map_err(|_| Error::Rkyv)?;
 
-            last_pos = std::cmp::max(last_pos, *note.pos());
+            last_pos = std::cmp::max(last_pos, *leaf.note.pos());
 
-            note_data.push((block_height, note));
+            note_data.push(leaf);
         }
 
         buffer = leaf_chunk.remainder().to_vec();
     }
 
-    for (sk, vk, pk) in keys.iter() {
+    // classify the notes of all profiles in a single pass
+    let owned_notes = scan_owned(&vks, &note_data);
+
+    for ((sk, pk), owned) in keys.iter().zip(owned_notes) {
+        if owned.is_empty() {
+            continue;
+        }
+
         let pk_bs58 = bs58::encode(pk.to_bytes()).into_string();
-        for (block_height, note) in note_data.iter() {
-            if vk.owns(note.stealth_address()) {
-                let nullifier = note.gen_nullifier(sk);
-                let spent =
-                    fetch_existing_nullifiers_remote(client, &[nullifier])
-                        .await?
-                        .first()
-                        .is_some();
-
-                let note = (note.clone(), nullifier);
-
-                match spent {
-                    true => cache.insert_spent(&pk_bs58, *block_height, note),
-                    false => cache.insert(&pk_bs58, *block_height, note),
-                }?;
-            }
+        let nullifiers: Vec<BlsScalar> = owned
+            .iter()
+            .map(|leaf| leaf.note.gen_nullifier(sk))
+            .collect();
+        let existing =
+            fetch_existing_nullifiers_remote(client, &nullifiers).await?;
+
+        for (NoteLeaf { block_height, note }, nullifier) in
+            owned.into_iter().zip(nullifiers)
+        {
+            let spent = existing.contains(&nullifier);
+            let note = (note, nullifier);
+
+            match spent {
+                true => cache.insert_spent(&pk_bs58, block_height, note),
+                false => cache.insert(&pk_bs58, block_height, note),
+            }?;
         }
     }
 
     // Remove spent nullifiers from live notes
     // zerorize all the secret keys
-    for (mut sk, _, pk) in keys {
+    for (mut sk, pk) in keys {
         let nullifiers: Vec<BlsScalar> = cache.unspent_notes_id(&pk)?;
 
         if !nullifiers.is_empty() {
@@ -126,23 +134,64 @@ pub(crate) async fn sync_db(
 
 /// Asks the node to return the nullifiers that already exist from the given
 /// nullifiers.
+///
+/// The nullifiers are sent in pages of at most [`MAX_NULLIFIERS_PAGE`], so
+/// that wallets with many notes don't exceed the argument buffer of the
+/// query.
+///
+/// Nodes whose transfer contract predates `existing_nullifiers_paged` are
+/// asked with `existing_nullifiers` instead, page by page as well.
 pub(crate) async fn fetch_existing_nullifiers_remote(
     client: &RuesHttpClient,
     nullifiers: &[BlsScalar],
 ) -> Result<Vec<BlsScalar>, Error> {
-    if nullifiers.is_empty() {
-        return Ok(vec![]);
-    }
-    let nullifiers = nullifiers.to_vec();
-    let data = client
-        .contract_query::<_, _, 1024>(
-            TRANSFER_CONTRACT,
-            "existing_nullifiers",
-            &nullifiers,
-        )
-        .await?;
+    let mut existing = vec![];
+    let mut paged = true;
+
+    for (page, offset) in nullifiers
+        .chunks(MAX_NULLIFIERS_PAGE)
+        .zip((0..).step_by(MAX_NULLIFIERS_PAGE))
+    {
+        if paged {
+            let data = client
+                .contract_query::<_, _, 1024>(
+                    TRANSFER_CONTRACT,
+                    "existing_nullifiers_paged",
+                    &(page.to_vec(), offset as u64),
+                )
+                .await;
+
+            match data {
+                Ok(data) => {
+                    let positions: Vec<u64> =
+                        rkyv::from_bytes(&data).map_err(|_| Error::Rkyv)?;
+
+                    for pos in positions {
+                        let nullifier = nullifiers.get(pos as usize).ok_or(
+                            Error::Rusk("Invalid nullifier position".into()),
+                        )?;
+                        existing.push(*nullifier);
+                    }
+                    continue;
+                }
+                // The query is missing from the node's transfer contract
+                Err(Error::Rusk(_)) if offset == 0 => paged = false,
+                Err(err) => return Err(err),
+            }
+        }
 
-    let nullifiers = rkyv::from_bytes(&data).map_err(|_| Error::Rkyv)?;
+        let data = client
+            .contract_query::<_, _, 1024>(
+                TRANSFER_CONTRACT,
+                "existing_nullifiers",
+                &page.to_vec(),
+            )
+            .await?;
+
+        let page_existing: Vec<BlsScalar> =
+            rkyv::from_bytes(&data).map_err(|_| Error::Rkyv)?;
+        existing.extend(page_existing);
+    }
 
-    Ok(nullifiers)
+    Ok(existing)
 }
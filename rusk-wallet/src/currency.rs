@@ -9,10 +9,11 @@
 use core::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::num::ParseFloatError;
 use std::ops::{Add, Deref, Div, Mul, Sub};
 use std::str::FromStr;
 
+use dusk_core::Amount;
+
 use super::*;
 
 /// The underlying unit of Dusk
@@ -182,19 +183,13 @@ impl From<Lux> for Dusk {
     }
 }
 
-/// Strings are parsed as Dusk values (floats)
+/// Strings are parsed as decimal Dusk values, without rounding
 impl FromStr for Dusk {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parse_result = f64::from_str(s).map_err(|e: ParseFloatError| {
-            Error::Conversion(format!(
-                "Failed to parse Dusk from string: {}",
-                e
-            ))
-        })?;
-
-        Dusk::try_from(parse_result)
+        let amount = Amount::from_str(s)?;
+        Ok(Self(amount.lux()))
     }
 }
 
@@ -280,6 +275,9 @@ mod tests {
         assert_eq!(*dusk, one_dusk);
         let dusk = Dusk::from_str("69.420").unwrap();
         assert_eq!(dusk, 69.420);
+        let dusk = Dusk::from_str("0.000000001").unwrap();
+        assert_eq!(*dusk, 1);
+        assert!(Dusk::from_str("0.0000000001").is_err());
         let float: f64 = dusk.try_into().unwrap();
         assert_eq!(float, 69.420);
         let borrowed = &Dusk(one_dusk);
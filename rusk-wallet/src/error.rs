@@ -118,9 +118,12 @@ pub enum Error {
     /// Transaction error
     #[error("Transaction error: {0}")]
     Transaction(String),
-    /// Rocksdb cache database error
+    /// Rocksdb cache database error, while importing a legacy cache
     #[error("Rocks cache database error: {0}")]
     RocksDB(rocksdb::Error),
+    /// SQLite cache database error
+    #[error("Cache database error: {0}")]
+    Sqlite(rusqlite::Error),
     /// Provided Network not found
     #[error(
         "Network not found, check config.toml, specify network with -n flag"
@@ -129,6 +132,9 @@ pub enum Error {
     /// The cache database couldn't find column family required
     #[error("Cache database corrupted")]
     CacheDatabaseCorrupted,
+    /// The cache database belongs to a different wallet
+    #[error("Cache database belongs to a different wallet")]
+    CacheOwnerMismatch,
     /// Prover errors from dusk-core
     #[error("Prover Error: {0}")]
     ProverError(String),
@@ -150,6 +156,15 @@ pub enum Error {
     /// Contract file location not found
     #[error("Invalid WASM contract path provided")]
     InvalidWasmContractPath,
+    /// Invalid name provided
+    #[error("Invalid name provided: {0}")]
+    InvalidName(String),
+    /// The name is not registered with the name service
+    #[error("The name {0} is not registered")]
+    NameNotRegistered(String),
+    /// The name resolves to a contract instead of an address
+    #[error("The name {0} resolves to a contract, not to an address")]
+    NameResolvesToContract(String),
     /// Invalid environment variable value
     #[error("Invalid environment variable value {0}")]
     InvalidEnvVar(String),
@@ -197,6 +212,12 @@ impl From<dusk_core::Error> for Error {
             }
             Rkyv(_) => Self::Rkyv,
             MemoTooLarge(m) => Self::MemoTooLarge(m),
+            AmountOverflow => {
+                Self::Conversion("Amount exceeds the maximum value".to_string())
+            }
+            InvalidAmount(s) => {
+                Self::Conversion(format!("Invalid amount of Dusk: {s}"))
+            }
         }
     }
 }
@@ -207,6 +228,12 @@ impl From<rocksdb::Error> for Error {
     }
 }
 
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Sqlite(e)
+    }
+}
+
 impl From<GraphQLError> for Error {
     fn from(e: GraphQLError) -> Self {
         Self::GraphQLError(e)
@@ -7,9 +7,11 @@
 //! This module contains the primitive related to the gas used for transaction
 //! in the Dusk Network.
 
+use dusk_core::Amount;
 use serde::Deserialize;
 
 use crate::currency::Lux;
+use crate::Error;
 
 /// The minimum gas limit
 pub const MIN_LIMIT: u64 = 100_000;
@@ -75,6 +77,18 @@ impl Gas {
         self
     }
 
+    /// Returns the maximum fee, in [Lux], paid for the gas
+    pub fn max_fee(&self) -> Result<Lux, Error> {
+        Ok(Amount::from_lux(self.price).checked_mul(self.limit)?.lux())
+    }
+
+    /// Returns the maximum cost, in [Lux], of a transaction carrying the
+    /// given value and paying for the gas
+    pub fn tx_cost(&self, value: Lux) -> Result<Lux, Error> {
+        let max_fee = Amount::from_lux(self.max_fee()?);
+        Ok(max_fee.checked_add(Amount::from_lux(value))?.lux())
+    }
+
     /// Set the limit
     pub fn set_limit<T>(&mut self, limit: T)
     where
@@ -86,6 +86,21 @@ pub struct FullMoonlightHistory {
     pub full_moonlight_history: MoonlightHistoryJson,
 }
 
+#[derive(Deserialize)]
+struct BlockHeader {
+    pub height: u64,
+}
+
+#[derive(Deserialize)]
+struct BlockWithHeader {
+    pub header: BlockHeader,
+}
+
+#[derive(Deserialize)]
+struct BlockHeightResponse {
+    pub block: Option<BlockWithHeader>,
+}
+
 #[derive(Deserialize)]
 struct SpentTxResponse {
     pub tx: Option<SpentTx>,
@@ -130,6 +145,32 @@ impl GraphQL {
         Ok(())
     }
 
+    /// Obtain the height of the last block
+    pub async fn block_height(&self) -> Result<u64, Error> {
+        let query = "query { block(height: -1) { header { height }}}";
+        let response = self.query(query).await?;
+        let response =
+            serde_json::from_slice::<BlockHeightResponse>(&response)?.block;
+        let block = response.ok_or(GraphQLError::BlockInfo)?;
+
+        Ok(block.header.height)
+    }
+
+    /// Wait for the chain to reach the given block height
+    pub async fn wait_for_height(&self, height: u64) -> Result<(), Error> {
+        loop {
+            let current = self.block_height().await?;
+            if current >= height {
+                return Ok(());
+            }
+
+            (self.status)(&format!(
+                "Waiting for block {height}, the chain is at block {current}..."
+            ));
+            sleep(Duration::from_secs(10)).await;
+        }
+    }
+
     /// Obtain transaction status
     async fn tx_status(&self, tx_id: &str) -> Result<TxStatus, Error> {
         let query =
@@ -27,12 +27,14 @@ pub mod currency;
 pub mod dat;
 pub mod gas;
 
+pub use cache::CacheInfo;
 pub use error::Error;
 pub use gql::{BlockTransaction, GraphQL};
 pub use rues::RuesHttpClient;
 pub use wallet::{
     Address, DecodedNote, Profile, SecureWalletFile, Wallet, WalletPath,
 };
+pub use wallet_core::{CoinControl, CoinSelection};
 
 use dusk_core::stake::StakeData;
 use dusk_core::transfer::phoenix::{
@@ -53,6 +55,10 @@ pub const MAX_CONVERTIBLE: Dusk = Dusk::MAX;
 pub const MIN_CONVERTIBLE: Dusk = Dusk::new(1);
 /// The length of an epoch in blocks
 pub const EPOCH: u64 = 2160;
+/// The number of blocks before the end of an epoch at which a staking
+/// transaction scheduled for the epoch boundary is submitted, leaving time for
+/// it to be included in the epoch
+pub const EPOCH_BOUNDARY_MARGIN: u64 = 10;
 /// Max addresses the wallet can store
 pub const MAX_PROFILES: usize = get_max_profiles();
 
@@ -72,3 +78,39 @@ const fn get_max_profiles() -> usize {
         None => DEFAULT_MAX_PROFILES,
     }
 }
+
+/// Returns the block height at which a staking transaction scheduled for the
+/// epoch boundary is submitted, given the current block height.
+///
+/// A stake becomes eligible from the second epoch after the one it is included
+/// in, wherever it lands in it, so submitting it at the end of the epoch keeps
+/// the funds available for longer without delaying its eligibility. Likewise,
+/// an unstake submitted at the end of the epoch keeps the stake in place for
+/// the whole epoch.
+pub const fn epoch_boundary_height(block_height: u64) -> u64 {
+    let next_epoch = (block_height / EPOCH + 1) * EPOCH;
+    let target = next_epoch.saturating_sub(EPOCH_BOUNDARY_MARGIN);
+    if target > block_height {
+        target
+    } else {
+        block_height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_boundary() {
+        assert_eq!(epoch_boundary_height(0), EPOCH - EPOCH_BOUNDARY_MARGIN);
+        assert_eq!(
+            epoch_boundary_height(EPOCH + 1),
+            2 * EPOCH - EPOCH_BOUNDARY_MARGIN
+        );
+
+        // within the margin, the transaction is submitted right away
+        let height = 2 * EPOCH - 1;
+        assert_eq!(epoch_boundary_height(height), height);
+    }
+}
@@ -41,7 +41,7 @@ impl RuesHttpClient {
     }
 
     /// Utility for querying the rusk VM
-    pub async fn contract_query<I, C, const N: usize>(
+    pub async fn contract_query<'c, I, C, const N: usize>(
         &self,
         contract: C,
         method: &str,
@@ -50,7 +50,7 @@ impl RuesHttpClient {
     where
         I: Archive,
         I: rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<N>>,
-        C: Into<Option<&'static str>>,
+        C: Into<Option<&'c str>>,
     {
         let data = rkyv::to_bytes(value).map_err(|_| Error::Rkyv)?.to_vec();
 
@@ -71,7 +71,7 @@ impl RuesHttpClient {
     /// Send a RuskRequest to a specific target.
     ///
     /// The response is interpreted as Binary
-    pub async fn call<E>(
+    pub async fn call<'e, E>(
         &self,
         target: &str,
         entity: E,
@@ -79,7 +79,7 @@ impl RuesHttpClient {
         request: &[u8],
     ) -> Result<Vec<u8>, Error>
     where
-        E: Into<Option<&'static str>>,
+        E: Into<Option<&'e str>>,
     {
         let response =
             self.call_raw(target, entity, topic, request, false).await?;
@@ -88,7 +88,7 @@ impl RuesHttpClient {
     }
 
     /// Send a RuskRequest to a specific target without parsing the response
-    pub async fn call_raw<E>(
+    pub async fn call_raw<'e, E>(
         &self,
         target: &str,
         entity: E,
@@ -97,7 +97,7 @@ impl RuesHttpClient {
         feed: bool,
     ) -> Result<Response, Error>
     where
-        E: Into<Option<&'static str>>,
+        E: Into<Option<&'e str>>,
     {
         let uri = &self.uri;
         let entity = entity.into().map(|e| format!(":{e}")).unwrap_or_default();
@@ -5,7 +5,10 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod address;
+mod escrow;
 mod file;
+mod name_service;
+mod token;
 mod transaction;
 
 pub use address::{Address, Profile};
@@ -32,9 +35,10 @@ use wallet_core::prelude::keys::{
     derive_bls_pk, derive_bls_sk, derive_phoenix_pk, derive_phoenix_sk,
     derive_phoenix_vk,
 };
-use wallet_core::{phoenix_balance, BalanceInfo};
+use wallet_core::{phoenix_balance, BalanceInfo, CoinControl, CoinSelection};
 use zeroize::Zeroize;
 
+use crate::cache::CacheInfo;
 use crate::clients::State;
 use crate::crypto::encrypt;
 use crate::currency::Dusk;
@@ -43,6 +47,7 @@ use crate::dat::{
     RESERVED,
 };
 use crate::gas::MempoolGasPrices;
+use crate::gql::{BlockTransaction, GraphQL};
 use crate::rues::RuesHttpClient;
 use crate::store::LocalStore;
 use crate::Error;
@@ -66,6 +71,7 @@ pub struct Wallet<F: SecureWalletFile + Debug> {
     store: LocalStore,
     file: Option<F>,
     file_version: Option<DatFileVersion>,
+    coin_selection: CoinSelection,
 }
 
 impl<F: SecureWalletFile + Debug> Wallet<F> {
@@ -108,6 +114,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
                 store: LocalStore::from(seed_bytes),
                 file: None,
                 file_version: None,
+                coin_selection: CoinSelection::default(),
             })
         } else {
             Err(Error::InvalidMnemonicPhrase)
@@ -148,6 +155,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
                 state: None,
                 file: Some(file),
                 file_version: Some(DatFileVersion::Legacy),
+                coin_selection: CoinSelection::default(),
             });
         }
 
@@ -165,6 +173,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
             state: None,
             file: Some(file),
             file_version: Some(file_version),
+            coin_selection: CoinSelection::default(),
         })
     }
 
@@ -252,11 +261,45 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
             http_state,
             http_prover,
             self.store.clone(),
+            self.coin_selection,
         )?);
 
         Ok(())
     }
 
+    /// Sets the strategy used to pick the input-notes of phoenix
+    /// transactions.
+    pub fn set_coin_selection(&mut self, coin_selection: CoinSelection) {
+        self.coin_selection = coin_selection;
+        if let Some(state) = self.state.as_mut() {
+            state.set_coin_selection(coin_selection);
+        }
+    }
+
+    /// Returns the strategy used to pick the input-notes of phoenix
+    /// transactions.
+    pub fn coin_selection(&self) -> CoinSelection {
+        self.coin_selection
+    }
+
+    /// Freezes the note with the given nullifier, so that it isn't picked as
+    /// input-note of phoenix transactions. Returns `false` if the note was
+    /// already frozen.
+    pub fn freeze_note(&self, nullifier: BlsScalar) -> Result<bool, Error> {
+        self.state()?.set_frozen(nullifier, true)
+    }
+
+    /// Thaws the note with the given nullifier, allowing it to be spent
+    /// again. Returns `false` if the note wasn't frozen.
+    pub fn thaw_note(&self, nullifier: BlsScalar) -> Result<bool, Error> {
+        self.state()?.set_frozen(nullifier, false)
+    }
+
+    /// Returns the coin-control metadata, holding the frozen notes.
+    pub fn coin_control(&self) -> Result<CoinControl, Error> {
+        self.state()?.cache().coin_control()
+    }
+
     /// Sync wallet state
     pub async fn sync(&self) -> Result<(), Error> {
         self.state()?.sync().await
@@ -592,6 +635,34 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         Ok(network_last_pos == db_pos)
     }
 
+    /// Returns a summary of the content of the cache, with the notes counted
+    /// for each profile.
+    pub fn cache_info(&self) -> Result<CacheInfo, Error> {
+        let profiles: Vec<_> =
+            self.profiles.iter().map(|p| p.shielded_addr).collect();
+
+        self.state()?.cache().info(&profiles)
+    }
+
+    /// Returns the transactions of the block at the given height, querying
+    /// them only if they're not in the cache already.
+    pub async fn block_transactions(
+        &self,
+        gql: &GraphQL,
+        block_height: u64,
+    ) -> Result<Vec<BlockTransaction>, Error> {
+        let cache = self.state()?.cache();
+
+        if let Some(txs) = cache.block_transactions(block_height)? {
+            return Ok(txs);
+        }
+
+        let txs = gql.txs_for_block(block_height).await?;
+        cache.insert_block_transactions(block_height, &txs)?;
+
+        Ok(txs)
+    }
+
     /// Erase the cache directory
     pub fn delete_cache(&mut self) -> Result<(), Error> {
         let path = self.cache_path()?;
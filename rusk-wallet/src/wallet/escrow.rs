@@ -0,0 +1,94 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::fmt::Debug;
+
+use dusk_core::abi::ContractId;
+use dusk_core::escrow::{CreateEscrow, EscrowId};
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::Transaction;
+
+use super::file::SecureWalletFile;
+use super::Wallet;
+use crate::currency::Dusk;
+use crate::gas::Gas;
+use crate::Error;
+
+impl<F: SecureWalletFile + Debug> Wallet<F> {
+    /// Locks Dusk from the public account of the given profile in an escrow
+    /// for the payee.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn escrow_create(
+        &self,
+        sender_idx: u8,
+        escrow: ContractId,
+        payee: &BlsPublicKey,
+        arbiter: Option<&BlsPublicKey>,
+        amt: Dusk,
+        timeout: u64,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        let create = CreateEscrow {
+            payee: *payee,
+            arbiter: arbiter.copied(),
+            value: *amt,
+            timeout,
+        };
+        let call = ContractCall::new(escrow, "create", &create)
+            .map_err(|_| Error::Rkyv)?;
+
+        self.moonlight_execute(sender_idx, Dusk::from(0), amt, gas, Some(call))
+            .await
+    }
+
+    /// Releases the funds of an escrow to its payee, from the public account
+    /// of the given profile.
+    pub async fn escrow_release(
+        &self,
+        sender_idx: u8,
+        escrow: ContractId,
+        escrow_id: EscrowId,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        self.escrow_settle(sender_idx, escrow, "release", escrow_id, gas)
+            .await
+    }
+
+    /// Refunds the funds of an escrow to its payer, from the public account
+    /// of the given profile.
+    pub async fn escrow_refund(
+        &self,
+        sender_idx: u8,
+        escrow: ContractId,
+        escrow_id: EscrowId,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        self.escrow_settle(sender_idx, escrow, "refund", escrow_id, gas)
+            .await
+    }
+
+    async fn escrow_settle(
+        &self,
+        sender_idx: u8,
+        escrow: ContractId,
+        fn_name: &str,
+        escrow_id: EscrowId,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        let call = ContractCall::new(escrow, fn_name, &escrow_id)
+            .map_err(|_| Error::Rkyv)?;
+
+        self.moonlight_execute(
+            sender_idx,
+            Dusk::from(0),
+            Dusk::from(0),
+            gas,
+            Some(call),
+        )
+        .await
+    }
+}
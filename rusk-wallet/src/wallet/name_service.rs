@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::fmt::Debug;
+
+use dusk_core::name_service::{parse_name, Target};
+
+use super::file::SecureWalletFile;
+use super::{Address, Wallet};
+use crate::Error;
+
+impl<F: SecureWalletFile + Debug> Wallet<F> {
+    /// Resolves a name registered with the Dusk Name Service, such as
+    /// `alice.dusk`, to the address it points to.
+    pub async fn resolve_name(&self, name: &str) -> Result<Address, Error> {
+        let bare_name =
+            parse_name(name).ok_or_else(|| Error::InvalidName(name.into()))?;
+
+        match self.state()?.fetch_name_target(bare_name).await? {
+            Some(Target::Moonlight(pk)) => Ok(Address::Public(pk)),
+            Some(Target::Phoenix(pk)) => Ok(Address::Shielded(pk)),
+            Some(Target::Contract(_)) => {
+                Err(Error::NameResolvesToContract(name.into()))
+            }
+            None => Err(Error::NameNotRegistered(name.into())),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::fmt::Debug;
+
+use dusk_core::abi::ContractId;
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::token::{Account, Approve, Transfer};
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::Transaction;
+
+use super::file::SecureWalletFile;
+use super::Wallet;
+use crate::currency::Dusk;
+use crate::gas::Gas;
+use crate::Error;
+
+impl<F: SecureWalletFile + Debug> Wallet<F> {
+    /// Obtains the balance of a token held by the public account of the given
+    /// profile.
+    pub async fn token_balance(
+        &self,
+        profile_idx: u8,
+        token: ContractId,
+    ) -> Result<u64, Error> {
+        let account = Account::External(*self.public_key(profile_idx)?);
+
+        self.state()?.fetch_token_balance(token, &account).await
+    }
+
+    /// Transfers tokens from the public account of the given profile.
+    pub async fn token_transfer(
+        &self,
+        sender_idx: u8,
+        token: ContractId,
+        receiver: &BlsPublicKey,
+        value: u64,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        let transfer = Transfer {
+            receiver: Account::External(*receiver),
+            value,
+        };
+        let call = ContractCall::new(token, "transfer", &transfer)
+            .map_err(|_| Error::Rkyv)?;
+
+        self.moonlight_execute(
+            sender_idx,
+            Dusk::from(0),
+            Dusk::from(0),
+            gas,
+            Some(call),
+        )
+        .await
+    }
+
+    /// Allows a spender to transfer up to `value` tokens from the public
+    /// account of the given profile.
+    pub async fn token_approve(
+        &self,
+        sender_idx: u8,
+        token: ContractId,
+        spender: &BlsPublicKey,
+        value: u64,
+        gas: Gas,
+    ) -> Result<Transaction, Error> {
+        let approve = Approve {
+            spender: Account::External(*spender),
+            value,
+        };
+        let call = ContractCall::new(token, "approve", &approve)
+            .map_err(|_| Error::Rkyv)?;
+
+        self.moonlight_execute(
+            sender_idx,
+            Dusk::from(0),
+            Dusk::from(0),
+            gas,
+            Some(call),
+        )
+        .await
+    }
+}
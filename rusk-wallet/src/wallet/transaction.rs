@@ -15,8 +15,9 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use wallet_core::transaction::{
     moonlight, moonlight_deployment, moonlight_stake, moonlight_stake_reward,
-    moonlight_to_phoenix, moonlight_unstake, phoenix, phoenix_deployment,
-    phoenix_stake, phoenix_stake_reward, phoenix_to_moonlight, phoenix_unstake,
+    moonlight_to_phoenix, moonlight_unstake, phoenix, phoenix_consolidation,
+    phoenix_deployment, phoenix_stake, phoenix_stake_reward,
+    phoenix_to_moonlight, phoenix_unstake,
 };
 use zeroize::Zeroize;
 
@@ -45,6 +46,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         if !gas.is_enough() {
             return Err(Error::NotEnoughGas);
         }
+        let memo = memo.map(TransactionData::new_memo).transpose()?;
 
         let state = self.state()?;
 
@@ -54,13 +56,18 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         let mut sender_sk = self.derive_phoenix_sk(sender_idx);
         let refund_pk = self.shielded_key(sender_idx)?;
 
-        let tx_cost = amt + gas.limit * gas.price;
-        let inputs = state
-            .tx_input_notes(sender_idx, tx_cost)
-            .await?
-            .into_iter()
-            .map(|(note, opening, _nullifier)| (note, opening))
-            .collect();
+        let tx_cost = gas.tx_cost(amt)?;
+        let inputs = match state.tx_input_notes(sender_idx, tx_cost).await {
+            // merge the notes if more than MAX_INPUT_NOTES are needed
+            Err(Error::NotEnoughBalance) => {
+                self.consolidate_notes(sender_idx, tx_cost, &gas).await?;
+                state.tx_input_notes(sender_idx, tx_cost).await?
+            }
+            inputs => inputs?,
+        }
+        .into_iter()
+        .map(|(note, opening, _nullifier)| (note, opening))
+        .collect();
 
         let root = state.fetch_root().await?;
         let chain_id = state.fetch_chain_id().await?;
@@ -88,6 +95,65 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         state.propagate(tx).await
     }
 
+    /// Merges the shielded notes of the given account until `tx_cost` can be
+    /// covered by the input notes of a single transaction.
+    ///
+    /// The consolidations are planned with [`plan`], and executed one at a
+    /// time, each paying the max fee of the given gas. Every consolidation is
+    /// waited for before planning the next.
+    ///
+    /// [`plan`]: wallet_core::transaction::plan
+    async fn consolidate_notes(
+        &self,
+        sender_idx: u8,
+        tx_cost: u64,
+        gas: &Gas,
+    ) -> Result<(), Error> {
+        let state = self.state()?;
+        let fee = gas.max_fee()?;
+
+        let mut rng = StdRng::from_entropy();
+
+        loop {
+            let plan = state.spend_plan(sender_idx, tx_cost, fee)?;
+            let Some(consolidation) = plan.consolidations.first() else {
+                return Ok(());
+            };
+
+            let tx_input =
+                state.consolidation_input_notes(consolidation).await?;
+            let nullifiers: Vec<_> = tx_input
+                .iter()
+                .map(|(_note, _opening, nullifier)| *nullifier)
+                .collect();
+            let inputs = tx_input
+                .into_iter()
+                .map(|(note, opening, _nullifier)| (note, opening))
+                .collect();
+
+            let root = state.fetch_root().await?;
+            let chain_id = state.fetch_chain_id().await?;
+
+            let mut sender_sk = self.derive_phoenix_sk(sender_idx);
+            let tx = phoenix_consolidation(
+                &mut rng,
+                &sender_sk,
+                inputs,
+                root,
+                consolidation.value,
+                gas.limit,
+                gas.price,
+                chain_id,
+                &Prover,
+            )?;
+            sender_sk.zeroize();
+
+            let tx = state.prove(tx).await?;
+            state.propagate(tx).await?;
+            state.wait_for_spent(&nullifiers).await?;
+        }
+    }
+
     /// Transfers funds between public accounts.
     pub async fn moonlight_transfer(
         &self,
@@ -105,6 +171,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         if !gas.is_enough() {
             return Err(Error::NotEnoughGas);
         }
+        let memo = memo.map(TransactionData::new_memo).transpose()?;
 
         let mut sender_sk = self.derive_bls_sk(sender_idx);
         let sender_pk = self.public_key(sender_idx)?;
@@ -153,7 +220,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         // the same
         let receiver_pk = self.shielded_key(sender_idx)?;
 
-        let tx_cost = deposit + gas.limit * gas.price;
+        let tx_cost = gas.tx_cost(deposit)?;
         let inputs = state
             .tx_input_notes(sender_idx, tx_cost)
             .await?
@@ -274,7 +341,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         };
         let mut stake_owner_sk = self.derive_bls_sk(stake_owner_idx);
 
-        let tx_cost = amt + gas.limit * gas.price;
+        let tx_cost = gas.tx_cost(amt)?;
         let inputs = state
             .tx_input_notes(profile_idx, tx_cost)
             .await?
@@ -393,7 +460,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
             return Err(Error::NotStaked);
         }
 
-        let tx_cost = gas.limit * gas.price;
+        let tx_cost = gas.max_fee()?;
         let inputs = state.tx_input_notes(profile_idx, tx_cost).await?;
 
         let root = state.fetch_root().await?;
@@ -480,7 +547,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         let mut sender_sk = self.derive_phoenix_sk(sender_idx);
         let mut stake_sk = self.derive_bls_sk(sender_idx);
 
-        let tx_cost = gas.limit * gas.price;
+        let tx_cost = gas.max_fee()?;
         let inputs = state.tx_input_notes(sender_idx, tx_cost).await?;
 
         let root = state.fetch_root().await?;
@@ -568,7 +635,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
     ) -> Result<Transaction, Error> {
         let mut rng = StdRng::from_entropy();
         let state = self.state()?;
-        let tx_cost = *amt + gas.limit * gas.price;
+        let tx_cost = gas.tx_cost(*amt)?;
         let inputs = state.tx_input_notes(profile_idx, tx_cost).await?;
 
         let root = state.fetch_root().await?;
@@ -647,7 +714,7 @@ impl<F: SecureWalletFile + Debug> Wallet<F> {
         let chain_id = state.fetch_chain_id().await?;
         let root = state.fetch_root().await?;
 
-        let tx_cost = gas.limit * gas.price;
+        let tx_cost = gas.max_fee()?;
         let inputs = state.tx_input_notes(sender_idx, tx_cost).await?;
 
         let mut sender_sk = self.derive_phoenix_sk(sender_idx);
@@ -134,7 +134,9 @@ fn bench_accept(
 
                     rusk.accept_transactions(
                         prev_root,
+                        [0; 48],
                         BLOCK_HEIGHT,
+                        0,
                         BLOCK_GAS_LIMIT,
                         BLOCK_HASH,
                         generator,
@@ -40,6 +40,11 @@ pub struct Args {
     #[clap(long, value_parser)]
     pub profile: Option<PathBuf>,
 
+    /// Sets the network the profile is namespaced by (e.g. mainnet, testnet,
+    /// devnet)
+    #[clap(long)]
+    pub network: Option<String>,
+
     #[cfg(feature = "ephemeral")]
     /// Ephemeral state file (archive)
     #[clap(short, long = "state", value_parser)]
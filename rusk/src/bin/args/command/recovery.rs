@@ -8,6 +8,7 @@ use clap::builder::BoolishValueParser;
 use clap::Subcommand;
 use rusk_recovery_tools::Theme;
 use std::io;
+use std::time::Duration;
 use tracing::info;
 
 #[allow(clippy::large_enum_variant)]
@@ -45,6 +46,45 @@ pub enum RecoveryCommand {
         #[clap(short, long, value_parser, num_args(1))]
         output: Option<std::path::PathBuf>,
     },
+
+    #[cfg(feature = "recovery-state")]
+    /// Regenerate the genesis state and compare it with the stored one,
+    /// reporting the contracts diverging
+    VerifyState {
+        /// Regenerate the state applying the init config specified in this
+        /// file.
+        #[clap(short, long, value_parser, env = "RUSK_RECOVERY_INPUT")]
+        init: Option<std::path::PathBuf>,
+
+        /// The hex-encoded root of the stored commit to compare, instead of
+        /// the persisted one.
+        #[clap(long)]
+        root: Option<String>,
+    },
+
+    /// Inspect and clean up the cached CRS, circuits and keys
+    #[clap(subcommand)]
+    Profile(ProfileCommand),
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Subcommand, Debug)]
+pub enum ProfileCommand {
+    /// List the cached artifacts with their sizes and hashes
+    Inventory,
+
+    /// Check the integrity of the cached artifacts
+    Verify,
+
+    /// Remove the cached artifacts not referenced by the cached circuits
+    Gc {
+        /// Only remove the artifacts older than this
+        #[clap(
+            long,
+            default_value = "1day",
+            value_parser = humantime_serde::re::humantime::parse_duration
+        )]
+        max_age: Duration,
+    },
 }
 
 impl RecoveryCommand {
@@ -54,6 +94,9 @@ impl RecoveryCommand {
         let keys_dir = rusk_profile::get_rusk_keys_dir()?;
         let state_dir = rusk_profile::get_rusk_state_dir()?;
 
+        if let Some(network) = rusk_profile::get_rusk_network()? {
+            info!("{} {network}", theme.info("NETWORK"));
+        }
         info!("{} {}", theme.info("PROFILE"), profile_dir.display());
         info!("{} {}", theme.info("CIRCUITS"), circuits_dir.display());
         info!("{} {}", theme.info("KEYS"), keys_dir.display());
@@ -73,10 +116,15 @@ impl RecoveryCommand {
                 init,
                 output,
             } => crate::args::state::recovery_state(init, force, output),
+            #[cfg(feature = "recovery-state")]
+            Self::VerifyState { init, root } => {
+                crate::args::state::verify_state(init, root)
+            }
             #[cfg(feature = "recovery-keys")]
             Self::Keys { keep, crs_url } => {
                 rusk_recovery_tools::keys::exec(keep, crs_url)
             }
+            Self::Profile(command) => command.run(&theme),
         };
 
         if let Err(e) = &result {
@@ -86,3 +134,45 @@ impl RecoveryCommand {
         result
     }
 }
+
+impl ProfileCommand {
+    fn run(self, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+        match self {
+            Self::Inventory => {
+                for artifact in rusk_profile::inventory()? {
+                    info!(
+                        "{} {} ({} bytes, sha256 {})",
+                        theme.info(format!("{:?}", artifact.kind)),
+                        artifact.path.display(),
+                        artifact.size,
+                        artifact.hash
+                    );
+                }
+            }
+            Self::Verify => {
+                let corrupted = rusk_profile::verify_all()?;
+                for corruption in &corrupted {
+                    info!(
+                        "{} {}: {}",
+                        theme.error("Corrupted"),
+                        corruption.path.display(),
+                        corruption.reason
+                    );
+                }
+                if !corrupted.is_empty() {
+                    Err(format!("{} corrupted artifacts", corrupted.len()))?;
+                }
+                info!("{} cached artifacts", theme.success("Verified"));
+            }
+            Self::Gc { max_age } => {
+                let removed = rusk_profile::gc(max_age)?;
+                info!(
+                    "{} {} artifacts",
+                    theme.success("Removed"),
+                    removed.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
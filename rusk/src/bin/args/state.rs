@@ -8,7 +8,9 @@ use super::*;
 
 use std::{env, fs, io};
 
-use rusk_recovery_tools::state::{deploy, restore_state, tar};
+use rusk_recovery_tools::state::{
+    deploy, restore_state, tar, verify, Snapshot,
+};
 use rusk_recovery_tools::Theme;
 use tracing::info;
 
@@ -17,12 +19,7 @@ pub fn recovery_state(
     force: bool,
     output_file: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let config = match &init {
-        Some(path) => fs::read_to_string(path)
-            .map_err(|_| format!("file {path:?} not found"))?,
-        None => rusk_recovery_tools::state::DEFAULT_SNAPSHOT.into(),
-    };
-    let init = toml::from_str(&config)?;
+    let init = load_snapshot(init.as_ref())?;
 
     let theme = Theme::default();
     info!("{} Network state", theme.action("Checking"));
@@ -92,6 +89,72 @@ pub fn recovery_state(
     Ok(())
 }
 
+pub fn verify_state(
+    init: Option<PathBuf>,
+    root: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let init = load_snapshot(init.as_ref())?;
+    let root = match root {
+        Some(root) => Some(
+            <[u8; 32]>::try_from(hex::decode(root)?)
+                .map_err(|_| "Invalid root")?,
+        ),
+        None => None,
+    };
+
+    let theme = Theme::default();
+    info!("{} Network state", theme.action("Verifying"));
+
+    let state_dir = rusk_profile::get_rusk_state_dir()?;
+    let verification =
+        verify(&state_dir, root, &init, *rusk::DUSK_CONSENSUS_KEY)?;
+
+    info!(
+        "{} {}",
+        theme.action("Expected Root"),
+        hex::encode(verification.expected_root)
+    );
+    info!(
+        "{} {}",
+        theme.action("Stored Root"),
+        hex::encode(verification.found_root)
+    );
+
+    if verification.is_valid() {
+        info!("{} network state", theme.success("Verified"));
+        return Ok(());
+    }
+
+    for divergence in &verification.diverging {
+        info!(
+            "{} contract {} (expected {}, found {})",
+            theme.error("Diverging"),
+            hex::encode(divergence.contract.as_bytes()),
+            hex::encode(divergence.expected),
+            hex::encode(divergence.found),
+        );
+    }
+    if verification.diverging.is_empty() {
+        info!(
+            "{} genesis contracts match, the divergence is elsewhere",
+            theme.warn("All")
+        );
+    }
+
+    Err("The stored state diverges from the regenerated one".into())
+}
+
+fn load_snapshot(
+    init: Option<&PathBuf>,
+) -> Result<Snapshot, Box<dyn std::error::Error>> {
+    let config = match init {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|_| format!("file {path:?} not found"))?,
+        None => rusk_recovery_tools::state::DEFAULT_SNAPSHOT.into(),
+    };
+    Ok(toml::from_str(&config)?)
+}
+
 fn clean_state() -> Result<(), io::Error> {
     let state_path = rusk_profile::get_rusk_state_dir()?;
 
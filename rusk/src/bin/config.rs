@@ -41,6 +41,8 @@ pub(crate) struct Config {
     log_type: Option<String>,
     log_filter: Option<String>,
 
+    network: Option<String>,
+
     #[cfg(feature = "chain")]
     #[serde(default = "DataBrokerConfig::default")]
     pub(crate) databroker: DataBrokerConfig,
@@ -105,6 +107,16 @@ impl From<&Args> for Config {
             env::set_var("RUSK_PROFILE_PATH", profile);
         }
 
+        // Set the network namespacing the profile. The argument overrides
+        // the env, which overrides the config
+        if let Some(network) = &args.network {
+            env::set_var("RUSK_NETWORK", network);
+        } else if let Some(network) = &rusk_config.network {
+            if env::var("RUSK_NETWORK").is_err() {
+                env::set_var("RUSK_NETWORK", network);
+            }
+        }
+
         rusk_config.http.merge(args);
 
         #[cfg(feature = "chain")]
@@ -5,10 +5,13 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::{
+    io,
     path::PathBuf,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use node::chain::block_builder::Params as BlockBuilderParams;
+use node::chain::failover::Params as FailoverParams;
 use node::database::DatabaseOptions;
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +45,10 @@ pub(crate) struct ChainConfig {
     #[serde(with = "humantime_serde")]
     #[serde(default)]
     genesis_timestamp: Option<SystemTime>,
+
+    failover: Option<FailoverParams>,
+
+    block_builder: Option<BlockBuilderParams>,
 }
 
 impl ChainConfig {
@@ -57,13 +64,20 @@ impl ChainConfig {
         }
     }
 
-    pub(crate) fn db_path(&self) -> PathBuf {
-        self.db_path.clone().unwrap_or_else(|| {
-            let mut path = dirs::home_dir().expect("OS not supported");
-            path.push(".dusk");
-            path.push(env!("CARGO_BIN_NAME"));
-            path
-        })
+    /// The directory of the chain database and the archive.
+    ///
+    /// Unless explicitly configured, it is namespaced by the network selected,
+    /// if any.
+    pub(crate) fn db_path(&self) -> io::Result<PathBuf> {
+        match self.db_path.clone() {
+            Some(path) => Ok(path),
+            None => {
+                let mut path = dirs::home_dir().expect("OS not supported");
+                path.push(".dusk");
+                path.push(env!("CARGO_BIN_NAME"));
+                rusk_profile::to_rusk_network_dir(path)
+            }
+        }
     }
 
     pub(crate) fn consensus_keys_path(&self) -> String {
@@ -132,4 +146,12 @@ impl ChainConfig {
             })
             .unwrap_or_default()
     }
+
+    pub(crate) fn failover(&self) -> Option<FailoverParams> {
+        self.failover.clone()
+    }
+
+    pub(crate) fn block_builder(&self) -> BlockBuilderParams {
+        self.block_builder.clone().unwrap_or_default()
+    }
 }
@@ -7,6 +7,7 @@
 use std::path::PathBuf;
 
 use hyper::HeaderMap;
+use rusk::http::{HttpAuth, HttpCache, HttpCors, HttpRoute, HttpWebhook};
 use serde::de::{self, Unexpected};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -31,6 +32,16 @@ pub struct HttpConfig {
     pub ws_event_channel_cap: usize,
     #[serde(with = "vec_header_map", default = "default_http_headers")]
     pub headers: HeaderMap,
+    #[serde(default)]
+    pub auth: HttpAuth,
+    #[serde(default)]
+    pub cors: HttpCors,
+    #[serde(default)]
+    pub routes: Vec<HttpRoute>,
+    #[serde(default)]
+    pub cache: HttpCache,
+    #[serde(default)]
+    pub webhooks: Vec<HttpWebhook>,
 }
 
 // Custom deserialization function for `feeder_call_gas`.
@@ -74,6 +85,11 @@ impl Default for HttpConfig {
             listen_address: None,
             ws_sub_channel_cap: default_ws_sub_channel_cap(),
             ws_event_channel_cap: default_ws_event_channel_cap(),
+            auth: HttpAuth::default(),
+            cors: HttpCors::default(),
+            routes: Vec::new(),
+            cache: HttpCache::default(),
+            webhooks: Vec::new(),
         }
     }
 }
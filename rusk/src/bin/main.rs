@@ -60,25 +60,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("Using state from {state_dir:?}");
 
         #[cfg(feature = "ephemeral")]
-        let db_path = tempdir.as_ref().map_or_else(
-            || config.chain.db_path(),
-            |t| std::path::Path::to_path_buf(t.path()),
-        );
+        let db_path = match tempdir.as_ref() {
+            Some(t) => t.path().to_path_buf(),
+            None => config.chain.db_path()?,
+        };
 
         #[cfg(not(feature = "ephemeral"))]
-        let db_path = config.chain.db_path();
+        let db_path = config.chain.db_path()?;
 
         node_builder = node_builder
             .with_vm_config(config.vm)
             .with_feeder_call_gas(config.http.feeder_call_gas)
             .with_db_path(db_path)
             .with_db_options(config.chain.db_options())
+            .with_protocol_activation_height(
+                config.kadcast.protocol_activation_height(),
+            )
             .with_kadcast(config.kadcast)
             .with_consensus_keys(config.chain.consensus_keys_path())
             .with_databroker(config.databroker)
             .with_telemetry(config.telemetry.listen_addr())
             .with_chain_queue_size(config.chain.max_queue_size())
             .with_genesis_timestamp(config.chain.genesis_timestamp())
+            .with_failover(config.chain.failover())
+            .with_block_builder(config.chain.block_builder())
             .with_mempool(config.mempool.into())
             .with_state_dir(state_dir)
             .with_min_gas_limit(config.chain.min_gas_limit());
@@ -116,6 +121,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             key: config.http.key,
             headers: config.http.headers,
             ws_event_channel_cap: config.http.ws_event_channel_cap,
+            auth: config.http.auth,
+            cors: config.http.cors,
+            routes: config.http.routes,
+            cache: config.http.cache,
+            webhooks: config.http.webhooks,
         };
         node_builder = node_builder.with_http(http_builder)
     }
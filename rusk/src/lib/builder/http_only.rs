@@ -35,7 +35,8 @@ impl RuskHttpBuilder {
 
             let cert_and_key = match (http.cert, http.key) {
                 (Some(cert), Some(key)) => Some((cert, key)),
-                _ => None,
+                (None, None) => None,
+                _ => anyhow::bail!("Both cert and key are needed for TLS"),
             };
 
             _ws_server = Some(
@@ -45,6 +46,11 @@ impl RuskHttpBuilder {
                     http.ws_event_channel_cap,
                     http.address,
                     http.headers,
+                    http.auth,
+                    http.cors,
+                    http.routes,
+                    http.cache,
+                    http.webhooks,
                     cert_and_key,
                 )
                 .await?,
@@ -8,6 +8,8 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use kadcast::config::Config as KadcastConfig;
+use node::chain::block_builder::Params as BlockBuilderParam;
+use node::chain::failover::Params as FailoverParam;
 use node::chain::ChainSrv;
 use node::database::rocksdb;
 use node::database::{DatabaseOptions, DB};
@@ -33,12 +35,15 @@ pub struct RuskNodeBuilder {
     consensus_keys_path: String,
     databroker: BrokerParam,
     kadcast: KadcastConfig,
+    protocol_activation_height: Option<u64>,
     mempool: MempoolParam,
     telemetry_address: Option<String>,
     db_path: PathBuf,
     db_options: DatabaseOptions,
     max_chain_queue_size: usize,
     genesis_timestamp: u64,
+    failover: Option<FailoverParam>,
+    block_builder: BlockBuilderParam,
     vm_config: RuskVmConfig,
     min_gas_limit: Option<u64>,
     feeder_call_gas: u64,
@@ -73,6 +78,16 @@ impl RuskNodeBuilder {
         self
     }
 
+    /// Set the block height from which the messages are encoded with the
+    /// latest protocol version, keeping the previous one if not set.
+    pub fn with_protocol_activation_height(
+        mut self,
+        height: Option<u64>,
+    ) -> Self {
+        self.protocol_activation_height = height;
+        self
+    }
+
     pub fn with_db_path(mut self, db_path: PathBuf) -> Self {
         self.db_path = db_path;
         self
@@ -106,6 +121,16 @@ impl RuskNodeBuilder {
         self
     }
 
+    pub fn with_failover(mut self, failover: Option<FailoverParam>) -> Self {
+        self.failover = failover;
+        self
+    }
+
+    pub fn with_block_builder(mut self, conf: BlockBuilderParam) -> Self {
+        self.block_builder = conf;
+        self
+    }
+
     #[deprecated(since = "1.0.3", note = "please use `with_vm_config` instead")]
     pub fn with_generation_timeout<O: Into<Option<Duration>>>(
         mut self,
@@ -225,12 +250,19 @@ impl RuskNodeBuilder {
                 self.db_path.clone(),
                 self.db_options.clone(),
             );
-            let net = Kadcast::new(self.kadcast)?;
-            RuskNode::new(
+            let mut net = Kadcast::new(self.kadcast)?;
+            if let Some(height) = self.protocol_activation_height {
+                net = net.with_activation_height(height);
+            }
+            let node = RuskNode::new(
                 Node::new(net, db, rusk.clone()),
                 #[cfg(feature = "archive")]
                 archive.clone(),
-            )
+            );
+            match &self.http {
+                Some(http) => node.with_lookup_cache(http.cache.clone()),
+                None => node,
+            }
         };
 
         let mut chain_srv = ChainSrv::new(
@@ -239,7 +271,9 @@ impl RuskNodeBuilder {
             node_sender.clone(),
             self.genesis_timestamp,
             *crate::DUSK_CONSENSUS_KEY,
-        );
+        )
+        .with_failover(self.failover)
+        .with_block_builder(self.block_builder.policy()?);
         if self.command_revert {
             chain_srv
                 .initialize(
@@ -278,7 +312,8 @@ impl RuskNodeBuilder {
 
             let cert_and_key = match (http.cert, http.key) {
                 (Some(cert), Some(key)) => Some((cert, key)),
-                _ => None,
+                (None, None) => None,
+                _ => anyhow::bail!("Both cert and key are needed for TLS"),
             };
 
             _ws_server = Some(
@@ -288,6 +323,11 @@ impl RuskNodeBuilder {
                     http.ws_event_channel_cap,
                     http.address,
                     http.headers,
+                    http.auth,
+                    http.cors,
+                    http.routes,
+                    http.cache,
+                    http.webhooks,
                     cert_and_key,
                 )
                 .await?,
@@ -60,6 +60,8 @@ pub enum Error {
     MemoTooLarge(usize),
     /// Chain tip different from the expected one
     TipChanged,
+    /// State override that cannot be applied
+    InvalidOverride(String),
 }
 
 impl std::error::Error for Error {}
@@ -109,6 +111,12 @@ impl From<dusk_core::Error> for Error {
             }
             ExecErr::Rkyv(e) => Self::Transaction(ExecErr::Rkyv(e)),
             ExecErr::MemoTooLarge(size) => Self::MemoTooLarge(size),
+            ExecErr::AmountOverflow => {
+                Self::Transaction(ExecErr::AmountOverflow)
+            }
+            ExecErr::InvalidAmount(s) => {
+                Self::Transaction(ExecErr::InvalidAmount(s))
+            }
         }
     }
 }
@@ -187,6 +195,9 @@ impl fmt::Display for Error {
             Error::TipChanged => {
                 write!(f, "Chain tip different from the expected one")
             }
+            Error::InvalidOverride(reason) => {
+                write!(f, "Invalid state override: {reason}")
+            }
         }
     }
 }
@@ -6,19 +6,33 @@
 
 #![allow(unused)]
 
+mod auth;
+mod cache;
 #[cfg(feature = "chain")]
 mod chain;
+mod cors;
 mod event;
+mod gateway;
+pub mod health;
 #[cfg(feature = "prover")]
 mod prover;
+pub mod rest;
 #[cfg(feature = "chain")]
 mod rusk;
 mod stream;
+mod webhook;
 
+pub use auth::HttpAuth;
+pub use cache::HttpCache;
+#[cfg(feature = "chain")]
+pub(crate) use chain::graphql::LookupCache;
+pub use cors::HttpCors;
 pub(crate) use event::{
     BinaryWrapper, DataType, ExecutionError, MessageResponse as EventResponse,
     RequestData,
 };
+pub use gateway::HttpRoute;
+pub use webhook::{HttpWebhook, WebhookEvent};
 
 use dusk_core::abi::Event;
 use tokio::task::JoinError;
@@ -72,10 +86,20 @@ use node_data::events::contract::ContractEvent;
 use crate::http::event::FullOrStreamBody;
 use crate::VERSION;
 
-pub use self::event::{RuesDispatchEvent, RuesEvent, RUES_LOCATION_PREFIX};
+pub use self::event::{
+    RuesDispatchEvent, RuesEvent, RUES_BINARY_PROTOCOL, RUES_LOCATION_PREFIX,
+};
 
-use self::event::{ResponseData, RuesEventUri, SessionId};
+use self::cache::ResponseCache;
+use self::event::{EventFraming, ResponseData, RuesEventUri, SessionId};
+use self::gateway::Gateway;
+use self::health::{NodeHealth, HEALTH_PATH, READINESS_PATH};
+use self::rest::{RestRoute, OPENAPI_PATH, REST_PREFIX};
 use self::stream::{Listener, Stream};
+use self::webhook::Webhooks;
+
+/// The path of the replay of the archived events, requested with `GET`.
+const EVENTS_REPLAY_PATH: &str = "/on/archive/events";
 
 const RUSK_VERSION_HEADER: &str = "Rusk-Version";
 const RUSK_VERSION_STRICT_HEADER: &str = "Rusk-Version-Strict";
@@ -92,6 +116,11 @@ pub struct HttpServerConfig {
     pub key: Option<PathBuf>,
     pub headers: HeaderMap,
     pub ws_event_channel_cap: usize,
+    pub auth: HttpAuth,
+    pub cors: HttpCors,
+    pub routes: Vec<HttpRoute>,
+    pub cache: HttpCache,
+    pub webhooks: Vec<HttpWebhook>,
 }
 
 impl HttpServer {
@@ -105,6 +134,11 @@ impl HttpServer {
         ws_event_channel_cap: usize,
         addr: A,
         headers: HeaderMap,
+        auth: HttpAuth,
+        cors: HttpCors,
+        routes: Vec<HttpRoute>,
+        cache: HttpCache,
+        webhooks: Vec<HttpWebhook>,
         cert_and_key: Option<(P1, P2)>,
     ) -> io::Result<Self>
     where
@@ -130,6 +164,11 @@ impl HttpServer {
             event_receiver,
             shutdown_receiver,
             headers,
+            auth,
+            cors,
+            routes,
+            cache,
+            webhooks,
             ws_event_channel_cap,
         ));
 
@@ -165,6 +204,32 @@ impl HandleRequest for DataSources {
         }
         Err(anyhow::anyhow!("unsupported location"))
     }
+
+    fn can_handle_rest(&self, route: &RestRoute) -> bool {
+        self.sources.iter().any(|s| s.can_handle_rest(route))
+    }
+
+    async fn handle_rest(
+        &self,
+        route: &RestRoute,
+    ) -> anyhow::Result<Option<serde_json::Value>> {
+        info!("Received REST request for {route:?}");
+        for h in &self.sources {
+            if h.can_handle_rest(route) {
+                return h.handle_rest(route).await;
+            }
+        }
+        Err(anyhow::anyhow!("unsupported route"))
+    }
+
+    async fn health(&self) -> Option<anyhow::Result<NodeHealth>> {
+        for h in &self.sources {
+            if let Some(health) = h.health().await {
+                return Some(health);
+            }
+        }
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -186,6 +251,11 @@ async fn listening_loop<H>(
     events: broadcast::Receiver<RuesEvent>,
     mut shutdown: broadcast::Receiver<Infallible>,
     headers: HeaderMap,
+    auth: HttpAuth,
+    cors: HttpCors,
+    routes: Vec<HttpRoute>,
+    cache: HttpCache,
+    webhooks: Vec<HttpWebhook>,
     ws_event_channel_cap: usize,
 ) where
     H: HandleRequest,
@@ -193,12 +263,32 @@ async fn listening_loop<H>(
     let sources = Arc::new(handler);
     let sockets_map = Arc::new(RwLock::new(HashMap::new()));
 
+    // Only the responses over finalized blocks are cached
+    let cache = Arc::new(ResponseCache::new(cache));
+    let mut cache_events = events.resubscribe();
+    let events_cache = cache.clone();
+    task::spawn(async move {
+        loop {
+            match cache_events.recv().await {
+                Ok(event) => events_cache.on_event(&event),
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    task::spawn(Webhooks::new(webhooks).run(events.resubscribe()));
+
     let service = ExecutionService {
         sources: sources.clone(),
         sockets_map: sockets_map.clone(),
         events: events.resubscribe(),
         shutdown: shutdown.resubscribe(),
         headers: Arc::new(headers),
+        auth: Arc::new(auth),
+        cors: Arc::new(cors),
+        gateway: Arc::new(Gateway::new(routes)),
+        cache,
         ws_event_channel_cap,
     };
 
@@ -241,6 +331,10 @@ struct ExecutionService<H> {
     events: broadcast::Receiver<RuesEvent>,
     shutdown: broadcast::Receiver<Infallible>,
     headers: Arc<HeaderMap>,
+    auth: Arc<HttpAuth>,
+    cors: Arc<HttpCors>,
+    gateway: Arc<Gateway>,
+    cache: Arc<ResponseCache>,
     ws_event_channel_cap: usize,
 }
 
@@ -252,6 +346,10 @@ impl<H> Clone for ExecutionService<H> {
             events: self.events.resubscribe(),
             shutdown: self.shutdown.resubscribe(),
             headers: self.headers.clone(),
+            auth: self.auth.clone(),
+            cors: self.cors.clone(),
+            gateway: self.gateway.clone(),
+            cache: self.cache.clone(),
             ws_event_channel_cap: self.ws_event_channel_cap,
         }
     }
@@ -283,22 +381,38 @@ where
         let shutdown = self.shutdown.resubscribe();
         let ws_event_channel_cap = self.ws_event_channel_cap;
         let headers = self.headers.clone();
+        let auth = self.auth.clone();
+        let gateway = self.gateway.clone();
+        let cache = self.cache.clone();
+
+        // Preflight requests from the allowed origins are answered directly
+        let preflight = self.cors.is_preflight(&req);
+        let cors_headers = self.cors.headers(req.headers(), preflight);
 
         Box::pin(async move {
-            let mut rsp = handle_request(
-                req,
-                sources,
-                sockets_map,
-                events,
-                shutdown,
-                ws_event_channel_cap,
-            )
-            .await;
+            let mut rsp = match preflight {
+                true => response(StatusCode::NO_CONTENT, ""),
+                false => {
+                    handle_request(
+                        req,
+                        sources,
+                        sockets_map,
+                        events,
+                        shutdown,
+                        auth,
+                        gateway,
+                        cache,
+                        ws_event_channel_cap,
+                    )
+                    .await
+                }
+            };
 
             // We insert all the custom headers set in the configuration here,
             // skipping the ones that are invalid.
             rsp.map(|mut rsp| {
                 rsp.headers_mut().extend(headers.as_ref().clone());
+                rsp.headers_mut().extend(cors_headers);
                 rsp
             })
             .or_else(|error| {
@@ -320,6 +434,7 @@ enum SubscriptionAction {
 async fn handle_stream_rues<H: HandleRequest>(
     sid: SessionId,
     websocket: HyperWebsocket,
+    framing: EventFraming,
     events: broadcast::Receiver<RuesEvent>,
     mut subscriptions: mpsc::Receiver<SubscriptionAction>,
     mut shutdown: broadcast::Receiver<Infallible>,
@@ -411,7 +526,7 @@ async fn handle_stream_rues<H: HandleRequest>(
             }
 
             Some(event) = events.next() => {
-                let mut event = match event {
+                let event = match event {
                     Ok(event) => event,
                     Err(err) => {
                         // If the event channel is closed, it means the
@@ -437,8 +552,7 @@ async fn handle_stream_rues<H: HandleRequest>(
 
                 // If the event is subscribed, we send it to the client.
                 if is_subscribed {
-                    event.add_header("Content-Location", event.uri.to_string());
-                    let event = event.to_bytes();
+                    let event = framing.encode(event);
 
                     // If the event fails sending we close the socket on the client
                     // and stop processing further.
@@ -477,13 +591,26 @@ async fn handle_request_rues<H: HandleRequest>(
     >,
     events: broadcast::Receiver<RuesEvent>,
     shutdown: broadcast::Receiver<Infallible>,
+    auth: Arc<HttpAuth>,
+    gateway: Arc<Gateway>,
     ws_event_channel_cap: usize,
 ) -> Result<Response<FullOrStreamBody>, ExecutionError> {
     if hyper_tungstenite::is_upgrade_request(&req) {
         let (subscription_sender, subscriptions) =
             mpsc::channel(ws_event_channel_cap);
 
-        let (response, websocket) = hyper_tungstenite::upgrade(&mut req, None)?;
+        // The client may request the binary framing of the events as a
+        // subprotocol, which the server has to confirm in the response.
+        let (framing, protocol) = EventFraming::negotiate(req.headers());
+
+        let (mut response, websocket) =
+            hyper_tungstenite::upgrade(&mut req, None)?;
+        if let Some(protocol) = protocol {
+            response.headers_mut().insert(
+                "Sec-WebSocket-Protocol",
+                HeaderValue::from_static(protocol),
+            );
+        }
 
         let mut sockets = sockets_map.write().await;
 
@@ -498,6 +625,7 @@ async fn handle_request_rues<H: HandleRequest>(
         task::spawn(handle_stream_rues(
             sid,
             websocket,
+            framing,
             events,
             subscriptions,
             shutdown,
@@ -506,8 +634,37 @@ async fn handle_request_rues<H: HandleRequest>(
         ));
 
         Ok(response.map(Into::into))
-    } else if req.method() == Method::POST {
-        let (event, binary_resp) = RuesDispatchEvent::from_request(req).await?;
+    } else if req.method() == Method::POST || is_events_replay(&req) {
+        // Requests to targets served by other backends are forwarded as they
+        // are, leaving their authentication to the backend.
+        if let Some(route) = gateway.route(&req) {
+            return match gateway.forward(route, req).await {
+                Ok(rsp) => Ok(rsp),
+                Err(e) => {
+                    warn!("Failed forwarding to {}: {e}", route.backend);
+                    response(
+                        StatusCode::BAD_GATEWAY,
+                        "{\"error\":\"Failed forwarding request\"}",
+                    )
+                }
+            };
+        }
+
+        // Replays are dispatched with the query of the request as data
+        let replay_query = is_events_replay(&req)
+            .then(|| req.uri().query().unwrap_or_default().to_string());
+
+        let (mut event, binary_resp) =
+            RuesDispatchEvent::from_request(req).await?;
+        if let Some(query) = replay_query {
+            event.data = query.into();
+        }
+        if !auth.authorize(&event) {
+            return response(
+                StatusCode::UNAUTHORIZED,
+                "{\"error\":\"Unauthorized\"}",
+            );
+        }
         let is_binary = event.is_binary();
         let mut resp_headers = event.x_headers();
         let (responder, mut receiver) = mpsc::unbounded_channel();
@@ -586,6 +743,10 @@ async fn handle_request_rues<H: HandleRequest>(
     }
 }
 
+fn is_events_replay<B>(req: &Request<B>) -> bool {
+    req.method() == Method::GET && req.uri().path() == EVENTS_REPLAY_PATH
+}
+
 async fn handle_request<H>(
     mut req: Request<Incoming>,
     sources: Arc<H>,
@@ -594,6 +755,9 @@ async fn handle_request<H>(
     >,
     events: broadcast::Receiver<RuesEvent>,
     shutdown: broadcast::Receiver<Infallible>,
+    auth: Arc<HttpAuth>,
+    gateway: Arc<Gateway>,
+    cache: Arc<ResponseCache>,
     ws_event_channel_cap: usize,
 ) -> Result<Response<FullOrStreamBody>, ExecutionError>
 where
@@ -609,11 +773,22 @@ where
             sockets_map,
             events,
             shutdown,
+            auth,
+            gateway,
             ws_event_channel_cap,
         )
         .await;
     }
 
+    if path.starts_with(REST_PREFIX) {
+        return handle_request_rest(req, sources, &cache).await;
+    }
+
+    if path == HEALTH_PATH || path == READINESS_PATH {
+        let readiness = path == READINESS_PATH;
+        return handle_request_health(sources, readiness).await;
+    }
+
     #[cfg(feature = "http-wasm")]
     if path == "/static/drivers/wallet-core.wasm" {
         let wallet_wasm = include_bytes!("../assets/wallet_core-1.0.0.wasm");
@@ -629,6 +804,103 @@ where
     Err(ExecutionError::Generic(anyhow::anyhow!("Unsupported path")))
 }
 
+fn json_response(
+    status: StatusCode,
+    value: &serde_json::Value,
+) -> Result<Response<FullOrStreamBody>, ExecutionError> {
+    let mut response = response(status, value.to_string())?;
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    Ok(response)
+}
+
+async fn handle_request_rest<H: HandleRequest>(
+    req: Request<Incoming>,
+    handler: Arc<H>,
+    cache: &ResponseCache,
+) -> Result<Response<FullOrStreamBody>, ExecutionError> {
+    if req.method() != Method::GET {
+        return response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "{\"error\":\"Method not allowed\"}",
+        );
+    }
+
+    if req.uri().path() == OPENAPI_PATH {
+        return json_response(StatusCode::OK, &rest::openapi());
+    }
+
+    let route = match RestRoute::parse(req.uri()) {
+        Some(route) if handler.can_handle_rest(&route) => route,
+        _ => {
+            return response(
+                StatusCode::NOT_FOUND,
+                "{\"error\":\"Invalid URL path\"}",
+            );
+        }
+    };
+
+    let cache_key = cache::rest_key(&route);
+    if let Some(value) = cache_key.as_deref().and_then(|key| cache.get(key)) {
+        return json_response(StatusCode::OK, &value);
+    }
+
+    match handler.handle_rest(&route).await {
+        Ok(Some(value)) => {
+            let height = cache::rest_height(&route, &value);
+            if let (Some(key), Some(height)) = (cache_key, height) {
+                cache.insert(key, height, value.clone());
+            }
+            json_response(StatusCode::OK, &value)
+        }
+        Ok(None) => json_response(
+            StatusCode::NOT_FOUND,
+            &serde_json::json!({ "error": "Not found" }),
+        ),
+        Err(e) => json_response(
+            StatusCode::BAD_REQUEST,
+            &serde_json::json!({ "error": e.to_string() }),
+        ),
+    }
+}
+
+/// Report the health of the node.
+///
+/// The health check fails if the node cannot be inspected, while the readiness
+/// check also fails if the node is not in sync with the network.
+async fn handle_request_health<H: HandleRequest>(
+    handler: Arc<H>,
+    readiness: bool,
+) -> Result<Response<FullOrStreamBody>, ExecutionError> {
+    let health = match handler.health().await {
+        // Servers without a node are always ready
+        None => {
+            let status = serde_json::json!({ "status": "ok" });
+            return json_response(StatusCode::OK, &status);
+        }
+        Some(Ok(health)) => health,
+        Some(Err(e)) => {
+            let status = serde_json::json!({
+                "status": "unavailable",
+                "database": e.to_string(),
+            });
+            return json_response(StatusCode::SERVICE_UNAVAILABLE, &status);
+        }
+    };
+
+    let (code, status) = match (readiness, health.is_ready()) {
+        (true, false) => (StatusCode::SERVICE_UNAVAILABLE, "syncing"),
+        _ => (StatusCode::OK, "ok"),
+    };
+
+    let mut value = serde_json::to_value(&health)?;
+    value["status"] = status.into();
+    value["database"] = "ok".into();
+    json_response(code, &value)
+}
+
 async fn handle_execution_rues<H>(
     sources: Arc<H>,
     event: RuesDispatchEvent,
@@ -665,6 +937,23 @@ pub trait HandleRequest: Send + Sync + 'static {
         &self,
         request: &RuesDispatchEvent,
     ) -> anyhow::Result<ResponseData>;
+
+    fn can_handle_rest(&self, _route: &RestRoute) -> bool {
+        false
+    }
+    /// Handle a route of the REST API, returning `None` if the requested item
+    /// is not found.
+    async fn handle_rest(
+        &self,
+        _route: &RestRoute,
+    ) -> anyhow::Result<Option<serde_json::Value>> {
+        Err(anyhow::anyhow!("Unsupported"))
+    }
+
+    /// The health of the node, if the handler serves one.
+    async fn health(&self) -> Option<anyhow::Result<NodeHealth>> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -679,6 +968,7 @@ mod tests {
     };
     use std::net::TcpStream;
     use tungstenite::client;
+    use tungstenite::client::IntoClientRequest;
 
     /// A [`HandleRequest`] implementation that returns the same data
     struct TestHandle;
@@ -701,10 +991,11 @@ mod tests {
         ) -> anyhow::Result<ResponseData> {
             let response = match request.uri.inner() {
                 ("test", _, "stream") => {
-                    let (sender, rec) = std::sync::mpsc::channel();
+                    let (sender, rec) =
+                        mpsc::channel(event::CHANNEL_BUFFER_CAP);
                     thread::spawn(move || {
                         for f in STREAMED_DATA.iter() {
-                            sender.send(f.to_vec()).unwrap()
+                            sender.blocking_send(f.to_vec()).unwrap()
                         }
                     });
                     ResponseData::new(rec)
@@ -731,6 +1022,11 @@ mod tests {
             ws_event_channel_cap,
             "localhost:0",
             HeaderMap::new(),
+            HttpAuth::default(),
+            HttpCors::default(),
+            vec![],
+            HttpCache::default(),
+            vec![],
             cert_and_key,
         )
         .await
@@ -778,6 +1074,11 @@ mod tests {
             ws_event_channel_cap,
             "localhost:0",
             HeaderMap::new(),
+            HttpAuth::default(),
+            HttpCors::default(),
+            vec![],
+            HttpCache::default(),
+            vec![],
             Some((cert_path, key_path)),
         )
         .await
@@ -827,6 +1128,11 @@ mod tests {
             ws_event_channel_cap,
             "localhost:0",
             HeaderMap::new(),
+            HttpAuth::default(),
+            HttpCors::default(),
+            vec![],
+            HttpCache::default(),
+            vec![],
             cert_and_key,
         )
         .await
@@ -976,6 +1282,98 @@ mod tests {
         assert_eq!(received_event, event, "Event should be the same");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn websocket_rues_binary() {
+        let cert_and_key: Option<(String, String)> = None;
+
+        let (event_sender, event_receiver) = broadcast::channel(16);
+        let ws_event_channel_cap = 2;
+
+        let server = HttpServer::bind(
+            TestHandle,
+            event_receiver,
+            ws_event_channel_cap,
+            "localhost:0",
+            HeaderMap::new(),
+            HttpAuth::default(),
+            HttpCors::default(),
+            vec![],
+            HttpCache::default(),
+            vec![],
+            cert_and_key,
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
+
+        let stream = TcpStream::connect(server.local_addr)
+            .expect("Connecting to the server should succeed");
+
+        // request the binary framing of the events
+        let mut request = format!("ws://{}/on", server.local_addr)
+            .into_client_request()
+            .expect("Creating the request should succeed");
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static(RUES_BINARY_PROTOCOL),
+        );
+
+        let (mut stream, response) = client(request, stream)
+            .expect("Handshake with the server should succeed");
+        assert_eq!(
+            response.headers().get("Sec-WebSocket-Protocol"),
+            Some(&HeaderValue::from_static(RUES_BINARY_PROTOCOL)),
+            "The binary framing should be accepted"
+        );
+
+        let first_message =
+            stream.read().expect("Session ID should be received");
+        let sid = SessionId::parse(
+            &first_message
+                .into_text()
+                .expect("Session ID should come in a text message"),
+        )
+        .expect("Session ID should be parsed");
+
+        const SUB_CONTRACT_ID: WrappedContractId =
+            WrappedContractId(ContractId::from_bytes([1; 32]));
+        const TOPIC: &str = "topic";
+
+        let sub_contract_id_hex = hex::encode(SUB_CONTRACT_ID.0);
+
+        let response = reqwest::Client::new()
+            .get(format!(
+                "http://{}/on/contracts:{sub_contract_id_hex}/{TOPIC}",
+                server.local_addr
+            ))
+            .header("Rusk-Session-Id", sid.to_string())
+            .send()
+            .await
+            .expect("Requesting should succeed");
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let received_event = RuesEvent::from(ContractTxEvent {
+            event: ContractEvent {
+                target: SUB_CONTRACT_ID,
+                topic: TOPIC.into(),
+                data: b"hello, events".to_vec(),
+            },
+            origin: [0; 32],
+        });
+
+        event_sender
+            .send(received_event.clone())
+            .expect("Sending event should succeed");
+
+        let message = stream.read().expect("Event should be received");
+        let event_bytes = message.into_data();
+
+        let event = RuesEvent::from_binary_frame(&event_bytes)
+            .expect("Event should deserialize");
+
+        assert_eq!(received_event, event, "Event should be the same");
+    }
+
     pub fn from_bytes(data: &[u8]) -> anyhow::Result<RuesEvent> {
         let (mut headers, data) = crate::http::event::parse_header(data)?;
 
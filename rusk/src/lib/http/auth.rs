@@ -0,0 +1,255 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use async_graphql::parser::parse_query;
+use async_graphql::parser::types::OperationType;
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+
+use super::RuesDispatchEvent;
+
+/// The header carrying a static API key.
+pub const RUSK_API_KEY_HEADER: &str = "rusk-api-key";
+
+const AUTHORIZATION_HEADER: &str = "authorization";
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// Authentication of the requests to the protected RUES locations.
+///
+/// Authentication is enabled once at least one API key or a JWT secret is
+/// configured. A protected request is then authorized if it carries one of
+/// the API keys in the `Rusk-Api-Key` header, or a valid HS256 JWT as a bearer
+/// token in the `Authorization` header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpAuth {
+    /// The static API keys accepted.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    /// The secret the JWTs are signed with.
+    pub jwt_secret: Option<String>,
+    /// The protected locations, as `component/topic` pairs.
+    #[serde(default = "default_protected")]
+    pub protected: Vec<String>,
+    /// Whether GraphQL mutations are protected.
+    #[serde(default = "default_protect_mutations")]
+    pub protect_mutations: bool,
+}
+
+impl Default for HttpAuth {
+    fn default() -> Self {
+        Self {
+            api_keys: Vec::new(),
+            jwt_secret: None,
+            protected: default_protected(),
+            protect_mutations: default_protect_mutations(),
+        }
+    }
+}
+
+fn default_protected() -> Vec<String> {
+    vec!["transactions/propagate".into(), "contracts/deploy".into()]
+}
+
+const fn default_protect_mutations() -> bool {
+    true
+}
+
+impl HttpAuth {
+    /// Returns if authentication is enabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || self.jwt_secret.is_some()
+    }
+
+    /// Returns if the given request is authorized.
+    pub fn authorize(&self, event: &RuesDispatchEvent) -> bool {
+        !self.is_enabled()
+            || !self.is_protected(event)
+            || self.has_api_key(event)
+            || self.has_jwt(event)
+    }
+
+    fn is_protected(&self, event: &RuesDispatchEvent) -> bool {
+        let uri = &event.uri;
+        let location = format!("{}/{}", uri.component, uri.topic);
+        if self.protected.contains(&location) {
+            return true;
+        }
+
+        self.protect_mutations
+            && uri.component == "graphql"
+            && is_mutation(&event.data.as_string())
+    }
+
+    fn has_api_key(&self, event: &RuesDispatchEvent) -> bool {
+        let key = match event.headers.get(RUSK_API_KEY_HEADER) {
+            Some(serde_json::Value::String(key)) => key.clone(),
+            Some(key) => key.to_string(),
+            None => return false,
+        };
+
+        // Compare the hashes, since their comparison runs in constant time
+        let key = blake3::hash(key.as_bytes());
+        self.api_keys
+            .iter()
+            .any(|api_key| blake3::hash(api_key.as_bytes()) == key)
+    }
+
+    fn has_jwt(&self, event: &RuesDispatchEvent) -> bool {
+        let Some(secret) = &self.jwt_secret else {
+            return false;
+        };
+        let token = event
+            .headers
+            .get(AUTHORIZATION_HEADER)
+            .and_then(|value| value.as_str())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX));
+        let Some(token) = token else {
+            return false;
+        };
+
+        let key = DecodingKey::from_secret(secret.as_bytes());
+        let validation = Validation::new(Algorithm::HS256);
+        decode::<serde_json::Value>(token, &key, &validation).is_ok()
+    }
+}
+
+/// Returns if the given GraphQL document contains a mutation.
+///
+/// Documents that fail parsing are considered mutations, so they're not let
+/// through unauthenticated. Empty documents are requests for the schema.
+fn is_mutation(query: &str) -> bool {
+    if query.trim().is_empty() {
+        return false;
+    }
+
+    match parse_query(query) {
+        Ok(document) => document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    use crate::http::event::RuesEventUri;
+
+    fn dispatch(
+        path: &str,
+        data: &str,
+        headers: &[(&str, &str)],
+    ) -> RuesDispatchEvent {
+        RuesDispatchEvent {
+            uri: RuesEventUri::parse_from_path(path).expect("valid path"),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), (*v).into()))
+                .collect(),
+            data: data.to_string().into(),
+        }
+    }
+
+    fn auth() -> HttpAuth {
+        HttpAuth {
+            api_keys: vec!["key".into()],
+            jwt_secret: Some("secret".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let event = dispatch("/on/transactions/propagate", "", &[]);
+        assert!(HttpAuth::default().authorize(&event));
+    }
+
+    #[test]
+    fn api_key() {
+        let auth = auth();
+
+        let event = dispatch("/on/transactions/propagate", "", &[]);
+        assert!(!auth.authorize(&event));
+
+        let event = dispatch(
+            "/on/transactions/propagate",
+            "",
+            &[(RUSK_API_KEY_HEADER, "wrong")],
+        );
+        assert!(!auth.authorize(&event));
+
+        let event = dispatch(
+            "/on/transactions/propagate",
+            "",
+            &[(RUSK_API_KEY_HEADER, "key")],
+        );
+        assert!(auth.authorize(&event));
+
+        let event = dispatch("/on/node/info", "", &[]);
+        assert!(auth.authorize(&event), "reads are not protected");
+    }
+
+    #[test]
+    fn deploy_dry_run() {
+        let auth = auth();
+
+        let event = dispatch("/on/contracts/deploy", "{}", &[]);
+        assert!(!auth.authorize(&event));
+
+        let event = dispatch(
+            "/on/contracts/deploy",
+            "{}",
+            &[(RUSK_API_KEY_HEADER, "key")],
+        );
+        assert!(auth.authorize(&event));
+    }
+
+    #[test]
+    fn jwt() {
+        let auth = auth();
+
+        let claims = serde_json::json!({ "exp": u32::MAX });
+        let token = |secret: &str| {
+            let key = EncodingKey::from_secret(secret.as_bytes());
+            let token = encode(&Header::default(), &claims, &key)
+                .expect("encoding should succeed");
+            format!("{BEARER_PREFIX}{token}")
+        };
+
+        let valid = token("secret");
+        let event = dispatch(
+            "/on/transactions/propagate",
+            "",
+            &[(AUTHORIZATION_HEADER, &valid)],
+        );
+        assert!(auth.authorize(&event));
+
+        let invalid = token("other");
+        let event = dispatch(
+            "/on/transactions/propagate",
+            "",
+            &[(AUTHORIZATION_HEADER, &invalid)],
+        );
+        assert!(!auth.authorize(&event));
+    }
+
+    #[test]
+    fn graphql_mutations() {
+        let auth = auth();
+
+        let query = "query { block(height: 1) { header { hash } } }";
+        let event = dispatch("/on/graphql/query", query, &[]);
+        assert!(auth.authorize(&event));
+
+        let mutation = "mutation { submitTransaction(raw: \"00\") { hash } }";
+        let event = dispatch("/on/graphql/query", mutation, &[]);
+        assert!(!auth.authorize(&event));
+    }
+}
@@ -0,0 +1,245 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::event::{DataType, RuesEvent};
+use super::rest::RestRoute;
+
+/// The limits of the cache of responses to expensive queries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpCache {
+    /// The maximum number of cached responses. Caching is disabled if zero.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    /// The time after which a cached response expires.
+    #[serde(default = "default_ttl", with = "humantime_serde")]
+    pub ttl: Duration,
+}
+
+impl Default for HttpCache {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+            ttl: default_ttl(),
+        }
+    }
+}
+
+const fn default_max_entries() -> usize {
+    1024
+}
+
+const fn default_ttl() -> Duration {
+    Duration::from_secs(60)
+}
+
+struct CacheEntry<V> {
+    inserted: Instant,
+    used: Instant,
+    value: V,
+}
+
+/// A cache of responses to queries over finalized data.
+///
+/// Only responses to queries over finalized data may be cached, since they
+/// never change once the chain is finalized. Entries are only dropped once
+/// they expire, or to make room for others, starting from the least recently
+/// used.
+pub(crate) struct ResponseCache<V = serde_json::Value> {
+    limits: HttpCache,
+    finalized_height: AtomicU64,
+    entries: Mutex<HashMap<String, CacheEntry<V>>>,
+}
+
+impl<V: Clone> ResponseCache<V> {
+    pub fn new(limits: HttpCache) -> Self {
+        Self {
+            limits,
+            finalized_height: AtomicU64::new(0),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The height of the last finalized block the cache is aware of.
+    pub fn finalized_height(&self) -> u64 {
+        self.finalized_height.load(Ordering::Relaxed)
+    }
+
+    /// Record that the block at the given height is finalized.
+    pub fn finalize(&self, height: u64) {
+        self.finalized_height.fetch_max(height, Ordering::Relaxed);
+    }
+
+    /// The cached response to the given query, if any.
+    pub fn get(&self, key: &str) -> Option<V> {
+        let mut entries = self.entries.lock().expect("cache lock to be sane");
+        let entry = entries.get_mut(key)?;
+        if entry.inserted.elapsed() >= self.limits.ttl {
+            entries.remove(key);
+            return None;
+        }
+        entry.used = Instant::now();
+        Some(entry.value.clone())
+    }
+
+    /// Cache the response to the given query, computed over data at the given
+    /// height.
+    ///
+    /// The response is not cached if the data is not finalized yet.
+    pub fn insert(&self, key: String, height: u64, value: V) {
+        if self.limits.max_entries == 0 || height > self.finalized_height() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().expect("cache lock to be sane");
+        if entries.len() >= self.limits.max_entries {
+            let ttl = self.limits.ttl;
+            entries.retain(|_, entry| entry.inserted.elapsed() < ttl);
+        }
+        if entries.len() >= self.limits.max_entries {
+            let least_used = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_used) = least_used {
+                entries.remove(&least_used);
+            }
+        }
+
+        let now = Instant::now();
+        entries.insert(
+            key,
+            CacheEntry {
+                inserted: now,
+                used: now,
+                value,
+            },
+        );
+    }
+
+    /// Track the finalized height if the event reports a newly finalized
+    /// block.
+    pub fn on_event(&self, event: &RuesEvent) {
+        let (component, _, topic) = event.uri.inner();
+        if component != "blocks" || topic != "statechange" {
+            return;
+        }
+        let DataType::Json(data) = &event.data else {
+            return;
+        };
+        if data["state"] != "finalized" {
+            return;
+        }
+        if let Some(height) = data["atHeight"].as_u64() {
+            self.finalize(height);
+        }
+    }
+}
+
+/// The key the response to the given REST route is cached by, if it may be
+/// cached.
+pub(crate) fn rest_key(route: &RestRoute) -> Option<String> {
+    match route {
+        RestRoute::Block {
+            height: Some(height),
+        } => Some(format!("blocks/{height}")),
+        RestRoute::Transaction { hash } => Some(format!("transactions/{hash}")),
+        _ => None,
+    }
+}
+
+/// The height of the block the response to the given REST route is computed
+/// over.
+pub(crate) fn rest_height(
+    route: &RestRoute,
+    value: &serde_json::Value,
+) -> Option<u64> {
+    match route {
+        RestRoute::Block { height } => *height,
+        RestRoute::Transaction { .. } => value["block_height"].as_u64(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::http::event::RuesEventUri;
+
+    fn finalized(height: u64) -> RuesEvent {
+        RuesEvent {
+            uri: RuesEventUri {
+                component: "blocks".into(),
+                entity: None,
+                topic: "statechange".into(),
+            },
+            data: DataType::Json(json!({
+                "state": "finalized",
+                "atHeight": height,
+            })),
+            headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn finalization() {
+        let cache = ResponseCache::new(HttpCache::default());
+        cache.on_event(&finalized(10));
+
+        cache.insert("block/11".into(), 11, json!(11));
+        assert_eq!(
+            cache.get("block/11"),
+            None,
+            "non-final data should not be cached"
+        );
+
+        cache.insert("block/10".into(), 10, json!(10));
+        assert_eq!(cache.get("block/10"), Some(json!(10)));
+
+        cache.on_event(&finalized(12));
+        assert_eq!(
+            cache.get("block/10"),
+            Some(json!(10)),
+            "finalized data should outlive further finalization"
+        );
+        cache.insert("block/11".into(), 11, json!(11));
+        assert_eq!(cache.get("block/11"), Some(json!(11)));
+    }
+
+    #[test]
+    fn limits() {
+        let cache = ResponseCache::new(HttpCache {
+            max_entries: 2,
+            ..Default::default()
+        });
+
+        cache.insert("block/0".into(), 0, json!(0));
+        cache.insert("block/1".into(), 0, json!(1));
+        // Using the oldest entry keeps it from being evicted
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get("block/0"), Some(json!(0)));
+        cache.insert("block/2".into(), 0, json!(2));
+
+        assert_eq!(cache.get("block/0"), Some(json!(0)));
+        assert_eq!(cache.get("block/1"), None, "the least used is evicted");
+        assert_eq!(cache.get("block/2"), Some(json!(2)));
+
+        let cache = ResponseCache::new(HttpCache {
+            ttl: Duration::ZERO,
+            ..Default::default()
+        });
+        cache.insert("block/0".into(), 0, json!(0));
+        assert_eq!(cache.get("block/0"), None, "the entry is expired");
+    }
+}
@@ -4,36 +4,58 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod conflicts;
+mod deploy;
 mod geo;
 pub mod graphql;
+mod provisioners;
+#[cfg(feature = "archive")]
+mod replay;
+mod rest;
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use dusk_core::transfer::Transaction as ProtocolTransaction;
-use node::database::rocksdb::{Backend, DBTransaction};
-use node::database::{Mempool, DB};
+use node::database::rocksdb::{Backend, DBTransaction, MD_HASH_KEY};
+use node::database::{Ledger, Mempool, Metadata, DB};
 use node::mempool::MempoolSrv;
 use node::network::Kadcast;
 use node::Network;
-use node_data::ledger::Transaction;
+use node_data::ledger::{Label, Transaction};
 use node_data::message::Message;
 
-use graphql::{DBContext, Query};
+use graphql::{DBContext, Mutation, Query, Subscription};
 
-use async_graphql::{
-    EmptyMutation, EmptySubscription, Name, Schema, Variables,
-};
+use async_graphql::{Name, Schema, Variables};
 use serde_json::{json, Map, Value};
 use tracing::error;
 
 use super::*;
+use crate::http::event::CHANNEL_BUFFER_CAP;
 use crate::node::RuskNode;
 use crate::{VERSION, VERSION_BUILD};
 
 const GQL_VAR_PREFIX: &str = "rusk-gqlvar-";
 
+/// Decode a serialized transaction to JSON, without verifying or submitting
+/// it.
+///
+/// The JSON is the same as the one of the transaction events, with the hash
+/// of the transaction added to it.
+fn decode_tx(data: &[u8]) -> anyhow::Result<ResponseData> {
+    let tx: Transaction = ProtocolTransaction::from_slice(data)
+        .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?
+        .into();
+
+    let mut decoded = serde_json::to_value(&tx)?;
+    decoded["hash"] = hex::encode(tx.id()).into();
+
+    Ok(ResponseData::new(decoded))
+}
+
 fn variables_from_headers(headers: &Map<String, Value>) -> Variables {
     let mut var = Variables::default();
     headers
@@ -57,8 +79,14 @@ impl HandleRequest for RuskNode {
         #[allow(clippy::match_like_matches_macro)]
         match request.uri.inner() {
             ("graphql", _, "query") => true,
+            ("graphql", _, "subscribe") => true,
             ("transactions", _, "preverify") => true,
             ("transactions", _, "propagate") => true,
+            ("transactions", None, "decode") => true,
+            ("transactions", None, "conflicts") => true,
+            ("contracts", None, "deploy") => true,
+            #[cfg(feature = "archive")]
+            ("archive", None, "events") => true,
             ("network", _, "peers") => true,
             ("network", _, "peers_location") => true,
             ("node", _, "info") => true,
@@ -74,12 +102,29 @@ impl HandleRequest for RuskNode {
             ("graphql", _, "query") => {
                 self.handle_gql(&request.data, &request.headers).await
             }
+            ("graphql", _, "subscribe") => {
+                self.handle_gql_subscription(&request.data, &request.headers)
+                    .await
+            }
             ("transactions", _, "preverify") => {
                 self.handle_preverify(request.data.as_bytes()).await
             }
             ("transactions", _, "propagate") => {
                 self.propagate_tx(request.data.as_bytes()).await
             }
+            ("transactions", None, "decode") => {
+                decode_tx(request.data.as_bytes())
+            }
+            ("transactions", None, "conflicts") => {
+                self.transaction_conflicts(request.data.as_bytes()).await
+            }
+            ("contracts", None, "deploy") => {
+                self.verify_deploy(request.data.as_bytes()).await
+            }
+            #[cfg(feature = "archive")]
+            ("archive", None, "events") => {
+                self.replay_events(&request.data.as_string()).await
+            }
             ("network", _, "peers") => {
                 let amount = request.data.as_string().trim().parse()?;
                 self.alive_nodes(amount).await
@@ -99,23 +144,79 @@ impl HandleRequest for RuskNode {
             _ => anyhow::bail!("Unsupported"),
         }
     }
+
+    fn can_handle_rest(&self, route: &RestRoute) -> bool {
+        matches!(
+            route,
+            RestRoute::Blocks { .. }
+                | RestRoute::Block { .. }
+                | RestRoute::Transactions { .. }
+                | RestRoute::Transaction { .. }
+                | RestRoute::Provisioners { .. }
+                | RestRoute::Committee
+        )
+    }
+    async fn handle_rest(
+        &self,
+        route: &RestRoute,
+    ) -> anyhow::Result<Option<Value>> {
+        let value = match route {
+            RestRoute::Blocks { count } => {
+                Some(serde_json::to_value(self.rest_blocks(*count).await?)?)
+            }
+            RestRoute::Block { height } => self
+                .rest_block(*height)
+                .await?
+                .map(serde_json::to_value)
+                .transpose()?,
+            RestRoute::Transactions { count } => Some(serde_json::to_value(
+                self.rest_transactions(*count).await?,
+            )?),
+            RestRoute::Transaction { hash } => self
+                .rest_transaction(hash)
+                .await?
+                .map(serde_json::to_value)
+                .transpose()?,
+            RestRoute::Provisioners { query } => Some(serde_json::to_value(
+                self.rest_provisioners(query).await?,
+            )?),
+            RestRoute::Committee => {
+                Some(serde_json::to_value(self.rest_committee().await?)?)
+            }
+            _ => anyhow::bail!("Unsupported"),
+        };
+        Ok(value)
+    }
+
+    async fn health(&self) -> Option<anyhow::Result<NodeHealth>> {
+        Some(self.node_health().await)
+    }
 }
 impl RuskNode {
+    async fn gql_schema(&self) -> Schema<Query, Mutation, Subscription> {
+        let vm = self.inner().vm_handler();
+        let events = vm.read().await.event_sender.clone();
+
+        #[cfg(feature = "archive")]
+        let db = (self.db(), self.archive());
+        #[cfg(not(feature = "archive"))]
+        let db = (self.db(), ());
+
+        Schema::build(Query, Mutation, Subscription)
+            .data(db)
+            .data((vm, self.network()))
+            .data(events)
+            .data(self.lookups())
+            .finish()
+    }
+
     async fn handle_gql(
         &self,
         data: &RequestData,
         headers: &serde_json::Map<String, Value>,
     ) -> anyhow::Result<ResponseData> {
         let gql_query = data.as_string();
-
-        #[cfg(feature = "archive")]
-        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
-            .data((self.db(), self.archive()))
-            .finish();
-        #[cfg(not(feature = "archive"))]
-        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
-            .data((self.db(), ()))
-            .finish();
+        let schema = self.gql_schema().await;
 
         if gql_query.trim().is_empty() {
             return Ok(ResponseData::new(schema.sdl()));
@@ -135,6 +236,43 @@ impl RuskNode {
         Ok(ResponseData::new(data))
     }
 
+    /// Execute a GraphQL subscription, streaming each of its responses as a
+    /// line of JSON.
+    ///
+    /// The stream ends once the response is dropped by the client.
+    async fn handle_gql_subscription(
+        &self,
+        data: &RequestData,
+        headers: &serde_json::Map<String, Value>,
+    ) -> anyhow::Result<ResponseData> {
+        let variables = variables_from_headers(headers);
+        let gql_query =
+            async_graphql::Request::new(data.as_string()).variables(variables);
+
+        let schema = self.gql_schema().await;
+        let mut responses = schema.execute_stream(gql_query);
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_CAP);
+        task::spawn(async move {
+            while let Some(response) = responses.next().await {
+                let mut line = match serde_json::to_vec(&response) {
+                    Ok(line) => line,
+                    Err(e) => {
+                        error!("Cannot serialize subscription response {e}");
+                        break;
+                    }
+                };
+                line.push(b'\n');
+
+                if sender.send(line).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(ResponseData::new(receiver))
+    }
+
     async fn handle_preverify(
         &self,
         data: &[u8],
@@ -173,6 +311,51 @@ impl RuskNode {
         Ok(ResponseData::new(serde_json::to_value(nodes)?))
     }
 
+    async fn node_health(&self) -> anyhow::Result<NodeHealth> {
+        let peers = self.network().read().await.alive_nodes_count().await;
+
+        let (tip, finalized_height, mempool_txs) =
+            self.db().read().await.view(|t| {
+                let tip_hash = t
+                    .op_read(MD_HASH_KEY)?
+                    .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+                let tip = t
+                    .light_block(&tip_hash)?
+                    .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?
+                    .header;
+
+                // Start from the tip to find the last final block
+                let finalized_height = (0..=tip.height)
+                    .rev()
+                    .find(|height| {
+                        matches!(
+                            t.block_label_by_height(*height),
+                            Ok(Some((_, Label::Final(_))))
+                        )
+                    })
+                    .unwrap_or_default();
+
+                Ok::<_, anyhow::Error>((
+                    tip,
+                    finalized_height,
+                    t.mempool_txs_count(),
+                ))
+            })?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or_default();
+
+        Ok(NodeHealth {
+            tip_height: tip.height,
+            finalized_height,
+            peers,
+            sync_lag: now.saturating_sub(tip.timestamp),
+            mempool_txs,
+        })
+    }
+
     async fn get_info(&self) -> anyhow::Result<ResponseData> {
         let mut info: HashMap<&str, serde_json::Value> = HashMap::new();
         info.insert("version", VERSION.as_str().into());
@@ -0,0 +1,125 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_core::BlsScalar;
+use node_data::ledger::SpendingId;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::http::rusk::parse_account;
+
+/// The nullifiers and account nonce a transaction is about to spend.
+#[derive(Debug, Deserialize)]
+struct ConflictsRequest {
+    /// The hex-encoded nullifiers of a Phoenix transaction.
+    #[serde(default)]
+    nullifiers: Vec<String>,
+    /// The base58-encoded sender of a Moonlight transaction.
+    #[serde(default)]
+    account: Option<String>,
+    /// The nonce of the Moonlight transaction.
+    #[serde(default)]
+    nonce: Option<u64>,
+}
+
+/// The transactions conflicting with the ones requested.
+#[derive(Debug, Default, Serialize)]
+struct ConflictsReport {
+    /// If any conflict was found.
+    conflict: bool,
+    /// The hex-encoded hashes of the mempool transactions spending any of
+    /// the nullifiers or the nonce.
+    mempool: Vec<String>,
+    /// The nullifiers already spent in a block.
+    spent_nullifiers: Vec<String>,
+    /// The nonce of the account in the current state, if an account was
+    /// given. Any nonce up to it was already used in a block.
+    account_nonce: Option<u64>,
+}
+
+impl RuskNode {
+    /// Find the transactions, in the mempool or in the blocks, spending the
+    /// given nullifiers or account nonce.
+    ///
+    /// This lets wallets detect a double spend before broadcasting a
+    /// transaction, instead of it being rejected.
+    pub(super) async fn transaction_conflicts(
+        &self,
+        data: &[u8],
+    ) -> anyhow::Result<ResponseData> {
+        let request: ConflictsRequest = serde_json::from_slice(data)?;
+
+        let nullifiers = request
+            .nullifiers
+            .iter()
+            .map(|n| {
+                let bytes: [u8; 32] = hex::decode(n)?
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("Invalid nullifier {n}"))?;
+                BlsScalar::from_bytes(&bytes)
+                    .map_err(|_| anyhow::anyhow!("Invalid nullifier {n}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let account =
+            request.account.as_deref().map(parse_account).transpose()?;
+
+        let mut spending_ids: Vec<_> = nullifiers
+            .iter()
+            .map(|n| SpendingId::Nullifier(n.to_bytes()))
+            .collect();
+        if let Some(account) = account {
+            let nonce = request
+                .nonce
+                .ok_or_else(|| anyhow::anyhow!("Missing account nonce"))?;
+            spending_ids.push(SpendingId::AccountNonce(account, nonce));
+        }
+        if spending_ids.is_empty() {
+            anyhow::bail!("Missing nullifiers or account");
+        }
+
+        let mut report = ConflictsReport::default();
+
+        let vm = self.inner().vm_handler();
+        let rusk = vm.read().await;
+        if !nullifiers.is_empty() {
+            report.spent_nullifiers = rusk
+                .existing_nullifiers(&nullifiers)
+                .map_err(|e| anyhow::anyhow!("Cannot query the state {e:?}"))?
+                .iter()
+                .map(|n| hex::encode(n.to_bytes()))
+                .collect();
+        }
+        if let Some(account) = &account {
+            let state = rusk
+                .account(account)
+                .map_err(|e| anyhow::anyhow!("Cannot query the state {e:?}"))?;
+            report.account_nonce = Some(state.nonce);
+        }
+        drop(rusk);
+
+        let mut mempool: Vec<_> = self
+            .db()
+            .read()
+            .await
+            .view(|t| t.mempool_txs_by_spendable_ids(&spending_ids))
+            .into_iter()
+            .map(hex::encode)
+            .collect();
+        mempool.sort();
+        report.mempool = mempool;
+
+        let nonce_used = matches!(
+            (request.nonce, report.account_nonce),
+            (Some(nonce), Some(account_nonce)) if nonce <= account_nonce
+        );
+        report.conflict = nonce_used
+            || !report.mempool.is_empty()
+            || !report.spent_nullifiers.is_empty();
+
+        Ok(ResponseData::new(serde_json::to_value(report)?))
+    }
+}
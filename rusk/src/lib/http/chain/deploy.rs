@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::cmp::max;
+
+use dusk_bytes::Serializable;
+use dusk_consensus::config::MAX_BLOCK_SIZE;
+use dusk_core::transfer::data::{ContractBytecode, ContractDeploy};
+use dusk_vm::{gen_contract_id, validate_bytecode};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::http::rusk::parse_account;
+
+/// A contract deployment to verify.
+#[derive(Debug, Deserialize)]
+struct DeployRequest {
+    /// The hex-encoded WASM bytecode of the contract.
+    bytecode: String,
+    /// The hex-encoded arguments of the `init` method, if any.
+    #[serde(default)]
+    init_args: Option<String>,
+    /// The base58-encoded public key of the owner of the contract.
+    owner: String,
+    #[serde(default)]
+    nonce: u64,
+}
+
+/// The report of the verification of a contract deployment.
+#[derive(Debug, Serialize)]
+struct DeployReport {
+    /// The hex-encoded id the contract would be deployed at.
+    contract_id: String,
+    /// If a deployment transaction would succeed.
+    valid: bool,
+    /// The gas needed by the deployment, on top of the gas spent by the
+    /// transaction itself. It is the sum of the deploy charge, the deposit and
+    /// the gas needed by the initialization.
+    gas_estimate: u64,
+    /// The deposit escrowed for the state the contract occupies. It must be
+    /// covered by the gas limit, but is withheld from the refund rather than
+    /// spent.
+    deposit: u64,
+    /// The gas needed by the initialization of the contract. It must be
+    /// available to the transaction, even if it isn't charged.
+    init_gas: u64,
+    /// The minimum gas price of a deployment transaction.
+    min_gas_price: u64,
+    /// The rule violations found by the static validation of the bytecode.
+    /// They cause the deployment to fail once the validation is enforced.
+    warnings: Vec<String>,
+    /// If the static validation of the bytecode is enforced by the network.
+    validation_enforced: bool,
+    /// The error the deployment or initialization of the contract failed with,
+    /// if any.
+    init_error: Option<String>,
+}
+
+impl RuskNode {
+    /// Verify a contract deployment, statically validating the bytecode and
+    /// initializing the contract on top of the current tip, without changing
+    /// the state.
+    ///
+    /// Bytecode that wouldn't fit in a block is rejected, and the
+    /// initialization may spend at most the block gas limit.
+    pub(super) async fn verify_deploy(
+        &self,
+        data: &[u8],
+    ) -> anyhow::Result<ResponseData> {
+        let request: DeployRequest = serde_json::from_slice(data)?;
+
+        // Hex encoding doubles the size of the bytecode
+        if request.bytecode.len() / 2 > MAX_BLOCK_SIZE {
+            anyhow::bail!("Bytecode larger than {MAX_BLOCK_SIZE} bytes");
+        }
+
+        let bytes = hex::decode(&request.bytecode)?;
+        let init_args = request.init_args.map(hex::decode).transpose()?;
+        let owner = parse_account(&request.owner)?;
+        let deploy = ContractDeploy {
+            bytecode: ContractBytecode {
+                hash: blake3::hash(&bytes).into(),
+                bytes,
+            },
+            owner: owner.to_bytes().to_vec(),
+            init_args,
+            nonce: request.nonce,
+        };
+
+        // The deployment is verified as part of the next block
+        let block_height = self.db().read().await.view(|t| {
+            let tip_hash = t
+                .op_read(MD_HASH_KEY)?
+                .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+            let tip = t
+                .light_block(&tip_hash)?
+                .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+            Ok::<_, anyhow::Error>(tip.header.height + 1)
+        })?;
+
+        let vm = self.inner().vm_handler();
+        let rusk = vm.read().await.clone();
+        let config = rusk.vm_config.to_execution_config(block_height);
+
+        let warnings: Vec<_> = validate_bytecode(&deploy.bytecode.bytes)
+            .err()
+            .unwrap_or_default()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let rejected = config.with_bytecode_validation && !warnings.is_empty();
+
+        let bytecode_len = deploy.bytecode.bytes.len() as u64;
+        let contract_id = gen_contract_id(
+            &deploy.bytecode.bytes,
+            deploy.nonce,
+            &deploy.owner,
+        );
+        let deploy_charge = max(
+            bytecode_len * config.gas_per_deploy_byte,
+            config.min_deploy_points,
+        );
+        let deposit = config.deploy_deposit.charge(&deploy.bytecode.bytes);
+
+        // Deploying and initializing the contract may take a while, so it is
+        // done off the async runtime.
+        let (init_gas, init_error) = match rejected {
+            true => (0, None),
+            false => {
+                let dry_run = tokio::task::spawn_blocking(move || {
+                    rusk.dry_run_deploy(&deploy, block_height)
+                })
+                .await?;
+                match dry_run {
+                    Ok((_, init_gas)) => (init_gas, None),
+                    Err(err) => (0, Some(err.to_string())),
+                }
+            }
+        };
+
+        let report = DeployReport {
+            contract_id: hex::encode(contract_id.as_bytes()),
+            valid: !rejected && init_error.is_none(),
+            gas_estimate: deploy_charge + deposit + init_gas,
+            deposit,
+            init_gas,
+            min_gas_price: config.min_deploy_gas_price,
+            warnings,
+            validation_enforced: config.with_bytecode_validation,
+            init_error,
+        };
+
+        Ok(ResponseData::new(serde_json::to_value(report)?))
+    }
+}
@@ -8,17 +8,20 @@
 mod archive;
 mod block;
 mod data;
+mod subscription;
 mod tx;
 
 use block::*;
 use data::*;
+use subscription::*;
 use tx::*;
 
-use async_graphql::{Context, FieldError, FieldResult, Object};
+use async_graphql::{Context, FieldError, FieldResult, Object, Subscription};
 use dusk_core::abi::ContractId;
 use dusk_core::transfer::TRANSFER_CONTRACT;
 use node::database::rocksdb::Backend;
 use node::database::{Ledger, DB};
+use node::network::Kadcast;
 use node_data::ledger::Label;
 #[cfg(feature = "archive")]
 use {
@@ -30,16 +33,46 @@ use {
     node::archive::{Archive, MoonlightGroup},
 };
 
+use futures_util::Stream;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::http::cache::{HttpCache, ResponseCache};
+use crate::http::RuesEvent;
+use crate::node::Rusk;
+
+#[cfg(feature = "archive")]
+pub(super) use archive::data::deserialized_archive_data::DeserializedContractEvent;
 
 #[cfg(feature = "archive")]
 pub type DBContext = (Arc<RwLock<Backend>>, Archive);
 #[cfg(not(feature = "archive"))]
 pub type DBContext = (Arc<RwLock<Backend>>, ());
 
+/// The handles of the node needed to submit and simulate transactions.
+pub type NodeContext = (Arc<RwLock<Rusk>>, Arc<RwLock<Kadcast<255>>>);
+
+/// The sender of the events emitted by the node, subscribed to by the
+/// resolvers of subscriptions.
+pub type EventsContext = broadcast::Sender<RuesEvent>;
+
 pub type OptResult<T> = FieldResult<Option<T>>;
 
+/// The lookups of finalized blocks and transactions, cached between requests.
+pub struct LookupCache {
+    blocks: ResponseCache<Block>,
+    txs: ResponseCache<SpentTransaction>,
+}
+
+impl LookupCache {
+    pub fn new(limits: HttpCache) -> Self {
+        Self {
+            blocks: ResponseCache::new(limits.clone()),
+            txs: ResponseCache::new(limits),
+        }
+    }
+}
+
 pub struct Query;
 
 #[Object]
@@ -180,6 +213,36 @@ impl Query {
         })
     }
 
+    /// Get the capabilities of the node, describing the queries and features
+    /// it supports.
+    async fn node_capabilities(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<NodeCapabilities> {
+        #[cfg(feature = "archive")]
+        let archived_height = {
+            let (_, archive) = ctx.data::<DBContext>()?;
+            Some(archive.fetch_last_finalized_block().await?.0)
+        };
+        #[cfg(not(feature = "archive"))]
+        let archived_height = None;
+
+        let mut data_drivers = vec![];
+        if cfg!(feature = "http-wasm") {
+            data_drivers.push("wallet-core".to_string());
+        }
+
+        Ok(NodeCapabilities {
+            version: crate::VERSION.to_string(),
+            archive: cfg!(feature = "archive"),
+            archived_height,
+            pruning_horizon: None,
+            prover: cfg!(feature = "prover"),
+            finalized_only: false,
+            data_drivers,
+        })
+    }
+
     #[cfg(feature = "archive")]
     async fn full_moonlight_history(
         &self,
@@ -281,3 +344,64 @@ impl Query {
         Ok(next_height)
     }
 }
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Submit a hex-encoded transaction to the network, returning its hash.
+    ///
+    /// The transaction is checked before being propagated, failing with the
+    /// reason it would be rejected by the mempool.
+    async fn submit_transaction(
+        &self,
+        ctx: &Context<'_>,
+        raw: String,
+    ) -> FieldResult<SubmittedTransaction> {
+        submit_tx(ctx, raw).await
+    }
+
+    /// Simulate the execution of a hex-encoded transaction in the next block,
+    /// returning the gas it would spend and the error it would fail with.
+    async fn simulate_transaction(
+        &self,
+        ctx: &Context<'_>,
+        raw: String,
+    ) -> FieldResult<SimulatedTransaction> {
+        simulate_tx(ctx, raw).await
+    }
+}
+
+pub struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Stream the blocks accepted by the node.
+    async fn new_blocks(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = Block>> {
+        new_blocks(ctx)
+    }
+
+    /// Stream the executed Moonlight transactions sent or received by the
+    /// account with the given base58-encoded public key.
+    async fn transactions_by_account(
+        &self,
+        ctx: &Context<'_>,
+        pk: String,
+    ) -> FieldResult<impl Stream<Item = SpentTransaction>> {
+        transactions_by_account(ctx, pk)
+    }
+
+    /// Stream the events emitted by the contract with the given hex-encoded
+    /// ID, optionally filtered by topic.
+    async fn contract_events(
+        &self,
+        ctx: &Context<'_>,
+        contract: String,
+        topic: Option<String>,
+    ) -> FieldResult<impl Stream<Item = ContractEventData>> {
+        contract_events(ctx, contract, topic)
+    }
+}
@@ -52,9 +52,9 @@ pub mod deserialized_archive_data {
     use dusk_core::stake::STAKE_CONTRACT;
     use dusk_core::transfer::withdraw::WithdrawReceiver;
     use dusk_core::transfer::{
-        ConvertEvent, DepositEvent, MoonlightTransactionEvent, WithdrawEvent,
-        CONVERT_TOPIC, DEPOSIT_TOPIC, MINT_TOPIC, MOONLIGHT_TOPIC,
-        TRANSFER_CONTRACT, WITHDRAW_TOPIC,
+        ConvertEvent, DepositEvent, MemoEvent, MoonlightTransactionEvent,
+        WithdrawEvent, CONVERT_TOPIC, DEPOSIT_TOPIC, MEMO_TOPIC, MINT_TOPIC,
+        MOONLIGHT_TOPIC, TRANSFER_CONTRACT, WITHDRAW_TOPIC,
     };
     use node_data::events::contract::{
         ContractEvent, OriginHash, WrappedContractId,
@@ -207,6 +207,25 @@ pub mod deserialized_archive_data {
         }
     }
 
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DeserializedMemoEvent(pub MemoEvent);
+
+    impl Serialize for DeserializedMemoEvent {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let memo_event = &self.0;
+            let mut state = serializer.serialize_struct("MemoEvent", 2)?;
+            state.serialize_field("kind", &memo_event.kind.to_byte())?;
+            state.serialize_field(
+                "content",
+                &hex::encode(&memo_event.content),
+            )?;
+            state.end()
+        }
+    }
+
     #[derive(Debug, Clone, PartialEq, Serialize)]
     pub struct DeserializedContractEvent {
         pub target: WrappedContractId,
@@ -256,6 +275,9 @@ pub mod deserialized_archive_data {
                                 serde_json::to_value(event.data)
                             })
                     }
+                    MEMO_TOPIC => rkyv::from_bytes::<MemoEvent>(&event.data)
+                        .map(|e| serde_json::to_value(DeserializedMemoEvent(e)))
+                        .unwrap_or_else(|_| serde_json::to_value(event.data)),
                     _ => serde_json::to_value(hex::encode(event.data)),
                 }
             } else {
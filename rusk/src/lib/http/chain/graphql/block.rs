@@ -48,9 +48,33 @@ pub async fn block_by_hash(
     hash: String,
 ) -> OptResult<Block> {
     let (db, _) = ctx.data::<DBContext>()?;
+    let cache = &ctx.data::<Arc<LookupCache>>()?.blocks;
     let hash = hex::decode(hash)?;
-    let header = db.read().await.view(|t| t.light_block(&hash))?;
-    Ok(header.map(Block::from))
+    let key = hex::encode(&hash);
+    if let Some(block) = cache.get(&key) {
+        return Ok(Some(block));
+    }
+
+    let lookup = db.read().await.view(|t| {
+        let Some(block) = t.light_block(&hash)? else {
+            return Ok::<_, anyhow::Error>(None);
+        };
+        let height = block.header.height;
+        let is_final = matches!(
+            t.block_label_by_height(height)?,
+            Some((final_hash, Label::Final(_))) if final_hash[..] == hash[..]
+        );
+        Ok(Some((Block::from(block), height, is_final)))
+    })?;
+
+    let Some((block, height, is_final)) = lookup else {
+        return Ok(None);
+    };
+    if is_final {
+        cache.finalize(height);
+        cache.insert(key, height, block.clone());
+    }
+    Ok(Some(block))
 }
 
 pub async fn last_blocks(
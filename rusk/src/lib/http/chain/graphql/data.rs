@@ -25,6 +25,7 @@ impl BlockPair {
     }
 }
 
+#[derive(Clone)]
 pub struct Block {
     header: node_data::ledger::Header,
     txs_id: Vec<[u8; 32]>,
@@ -46,6 +47,7 @@ impl Block {
 }
 
 pub struct Header<'a>(&'a node_data::ledger::Header);
+#[derive(Clone)]
 pub struct SpentTransaction(pub node_data::ledger::SpentTransaction);
 pub struct Transaction<'a>(TransactionData<'a>);
 
@@ -197,6 +199,10 @@ impl SpentTransaction {
         &self.0.err
     }
 
+    pub async fn err_code(&self) -> Option<u32> {
+        self.0.failure().map(|failure| failure.code)
+    }
+
     pub async fn gas_spent(&self) -> u64 {
         self.0.gas_spent
     }
@@ -299,7 +305,7 @@ impl Transaction<'_> {
     }
 
     pub async fn memo(&self) -> Option<String> {
-        self.0.inner.memo().map(hex::encode)
+        self.0.inner.memo().map(|memo| hex::encode(memo.content()))
     }
 }
 
@@ -309,3 +315,61 @@ pub struct CallData {
     fn_name: String,
     data: String,
 }
+
+/// The result of the submission of a transaction.
+#[derive(SimpleObject)]
+pub struct SubmittedTransaction {
+    /// The hash of the transaction.
+    pub hash: String,
+}
+
+/// The result of the simulation of a transaction on top of the current tip.
+#[derive(SimpleObject)]
+pub struct SimulatedTransaction {
+    /// The hash of the transaction.
+    pub hash: String,
+    /// The gas the transaction would spend.
+    pub gas_spent: u64,
+    /// The error the transaction would fail with, if any.
+    pub err: Option<String>,
+    /// The code of the error, if the transaction would be aborted with
+    /// `abi::fail`.
+    pub err_code: Option<u32>,
+}
+
+/// An event emitted by a contract during the execution of a transaction.
+#[derive(SimpleObject)]
+pub struct ContractEventData {
+    /// The hash of the transaction the event was emitted in.
+    pub origin: String,
+    /// The ID of the contract emitting the event.
+    pub contract: String,
+    /// The topic of the event.
+    pub topic: String,
+    /// The hex-encoded data of the event.
+    pub data: String,
+}
+
+/// The capabilities of a node, allowing clients to adapt to the queries and
+/// features it supports.
+#[derive(SimpleObject)]
+pub struct NodeCapabilities {
+    /// The version of the node.
+    pub version: String,
+    /// If the node runs an archive, serving the archive queries such as the
+    /// moonlight history and contract events.
+    pub archive: bool,
+    /// The height up to which the archive holds the history of the chain,
+    /// starting from genesis.
+    pub archived_height: Option<u64>,
+    /// The height below which blocks are pruned. Nodes currently keep every
+    /// block, so none is ever given.
+    pub pruning_horizon: Option<u64>,
+    /// If the node serves proofs for phoenix transactions.
+    pub prover: bool,
+    /// If the node only serves finalized data. Nodes currently always serve
+    /// the blocks and transactions that are not final yet.
+    pub finalized_only: bool,
+    /// The data drivers served by the node.
+    pub data_drivers: Vec<String>,
+}
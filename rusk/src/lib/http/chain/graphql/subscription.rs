@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+use futures_util::{future, Stream, StreamExt};
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::http::event::DataType;
+
+/// Stream the events emitted by the node, skipping the ones missed by a
+/// lagging subscriber.
+fn events(ctx: &Context<'_>) -> FieldResult<impl Stream<Item = RuesEvent>> {
+    let events = ctx.data::<EventsContext>()?.subscribe();
+    Ok(BroadcastStream::new(events)
+        .filter_map(|event| future::ready(event.ok())))
+}
+
+pub fn new_blocks(ctx: &Context<'_>) -> FieldResult<impl Stream<Item = Block>> {
+    let (db, _) = ctx.data::<DBContext>()?;
+    let db = db.clone();
+
+    Ok(events(ctx)?.filter_map(move |event| {
+        let db = db.clone();
+        async move {
+            let uri = &event.uri;
+            if uri.component != "blocks" || uri.topic != "accepted" {
+                return None;
+            }
+            let hash = hex::decode(uri.entity.as_ref()?).ok()?;
+            let block = db.read().await.view(|t| t.light_block(&hash));
+            block.ok().flatten().map(Block::from)
+        }
+    }))
+}
+
+/// Stream the executed Moonlight transactions sent or received by the given
+/// account.
+pub fn transactions_by_account(
+    ctx: &Context<'_>,
+    pk: String,
+) -> FieldResult<impl Stream<Item = SpentTransaction>> {
+    let (db, _) = ctx.data::<DBContext>()?;
+    let db = db.clone();
+
+    Ok(events(ctx)?.filter_map(move |event| {
+        let db = db.clone();
+        let pk = pk.clone();
+        async move {
+            let uri = &event.uri;
+            if uri.component != "transactions" || uri.topic != "executed" {
+                return None;
+            }
+            let DataType::Json(data) = &event.data else {
+                return None;
+            };
+
+            let tx = &data["inner"];
+            let is_involved = [&tx["sender"], &tx["receiver"]]
+                .into_iter()
+                .any(|account| account.as_str() == Some(pk.as_str()));
+            if !is_involved {
+                return None;
+            }
+
+            let hash = hex::decode(uri.entity.as_ref()?).ok()?;
+            let tx = db.read().await.view(|t| t.ledger_tx(&hash));
+            tx.ok().flatten().map(SpentTransaction)
+        }
+    }))
+}
+
+/// Stream the events emitted by the given contract, optionally only the ones
+/// with the given topic.
+pub fn contract_events(
+    ctx: &Context<'_>,
+    contract: String,
+    topic: Option<String>,
+) -> FieldResult<impl Stream<Item = ContractEventData>> {
+    let contract = contract.to_lowercase();
+
+    Ok(events(ctx)?.filter_map(move |event| {
+        let uri = event.uri;
+        let is_subscribed = uri.component == "contracts"
+            && uri.entity.as_ref() == Some(&contract)
+            && topic.as_ref().map_or(true, |topic| topic == &uri.topic);
+
+        let event = is_subscribed.then(|| {
+            let origin = event
+                .headers
+                .get("Rusk-Origin")
+                .and_then(|origin| origin.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let data = match event.data {
+                DataType::Binary(data) => hex::encode(data.inner),
+                _ => String::new(),
+            };
+
+            ContractEventData {
+                origin,
+                contract: contract.clone(),
+                topic: uri.topic,
+                data,
+            }
+        });
+        future::ready(event)
+    }))
+}
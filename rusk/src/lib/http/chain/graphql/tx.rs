@@ -5,17 +5,46 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use super::*;
+use dusk_core::abi::ContractFailure;
+use dusk_core::transfer::Transaction as ProtocolTransaction;
 use node::database::rocksdb::MD_HASH_KEY;
 use node::database::{Mempool, Metadata};
+use node::mempool::MempoolSrv;
+use node::Network;
+use node_data::ledger;
 
 pub async fn tx_by_hash(
     ctx: &Context<'_>,
     hash: String,
 ) -> OptResult<SpentTransaction> {
     let (db, _) = ctx.data::<DBContext>()?;
+    let cache = &ctx.data::<Arc<LookupCache>>()?.txs;
     let hash = hex::decode(hash)?;
-    let tx = db.read().await.view(|t| t.ledger_tx(&hash))?;
-    Ok(tx.map(SpentTransaction))
+    let key = hex::encode(&hash);
+    if let Some(tx) = cache.get(&key) {
+        return Ok(Some(tx));
+    }
+
+    let lookup = db.read().await.view(|t| {
+        let Some(tx) = t.ledger_tx(&hash)? else {
+            return Ok::<_, anyhow::Error>(None);
+        };
+        let height = tx.block_height;
+        let is_final = matches!(
+            t.block_label_by_height(height)?,
+            Some((_, Label::Final(_)))
+        );
+        Ok(Some((SpentTransaction(tx), height, is_final)))
+    })?;
+
+    let Some((tx, height, is_final)) = lookup else {
+        return Ok(None);
+    };
+    if is_final {
+        cache.finalize(height);
+        cache.insert(key, height, tx.clone());
+    }
+    Ok(Some(tx))
 }
 
 pub async fn last_transactions(
@@ -74,3 +103,59 @@ pub async fn mempool_by_hash<'a>(
     let tx = db.read().await.view(|db| db.mempool_tx(hash))?;
     Ok(tx.map(|t| t.into()))
 }
+
+/// Decode a transaction from its hex-encoded bytes.
+fn decode_tx(raw: &str) -> FieldResult<ledger::Transaction> {
+    let bytes = hex::decode(raw)?;
+    let tx = ProtocolTransaction::from_slice(&bytes)
+        .map_err(|e| FieldError::new(format!("Invalid transaction {e:?}")))?;
+    Ok(tx.into())
+}
+
+pub async fn submit_tx(
+    ctx: &Context<'_>,
+    raw: String,
+) -> FieldResult<SubmittedTransaction> {
+    let tx = decode_tx(&raw)?;
+    let (db, _) = ctx.data::<DBContext>()?;
+    let (vm, network) = ctx.data::<NodeContext>()?;
+
+    // Check the transaction before propagating it, to report the reason it
+    // would be rejected by the mempool
+    MempoolSrv::check_tx(db, vm, &tx, true, usize::MAX)
+        .await
+        .map_err(|e| FieldError::new(format!("Tx not accepted: {e}")))?;
+
+    let hash = hex::encode(tx.id());
+    network.read().await.route_internal(tx.into());
+
+    Ok(SubmittedTransaction { hash })
+}
+
+pub async fn simulate_tx(
+    ctx: &Context<'_>,
+    raw: String,
+) -> FieldResult<SimulatedTransaction> {
+    let tx = decode_tx(&raw)?;
+    let (vm, _) = ctx.data::<NodeContext>()?;
+
+    // The transaction is simulated as part of the next block
+    let block_height = last_block(ctx).await?.header().height + 1;
+    let receipt = vm
+        .read()
+        .await
+        .simulate_transaction(&tx.inner, block_height)?;
+
+    let err = receipt.data.err().map(|e| format!("{e}"));
+    let err_code = err
+        .as_deref()
+        .and_then(ContractFailure::from_panic)
+        .map(|failure| failure.code);
+
+    Ok(SimulatedTransaction {
+        hash: hex::encode(tx.id()),
+        gas_spent: receipt.gas_spent,
+        err,
+        err_code,
+    })
+}
@@ -0,0 +1,137 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_consensus::config::exclude_next_generator;
+use dusk_consensus::user::committee::Committee;
+use dusk_consensus::user::provisioners::Provisioners;
+use dusk_consensus::user::sortition;
+use dusk_core::stake::StakeFundOwner;
+use node::vm::VMExecution;
+use node_data::ledger::{Header, Seed};
+use node_data::StepName;
+
+use super::*;
+use crate::http::rest::{
+    CommitteeMember, CommitteeResponse, IterationCommittees,
+    ProvisionerResponse, ProvisionersQuery, ProvisionersResponse,
+};
+
+impl RuskNode {
+    /// The header of the last block.
+    async fn tip_header(&self) -> anyhow::Result<Header> {
+        self.db().read().await.view(|t| {
+            let tip_hash = t
+                .op_read(MD_HASH_KEY)?
+                .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+            let tip = t
+                .light_block(&tip_hash)?
+                .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+            Ok(tip.header)
+        })
+    }
+
+    /// A page of the provisioners at the tip, sorted as requested.
+    pub(super) async fn rest_provisioners(
+        &self,
+        query: &ProvisionersQuery,
+    ) -> anyhow::Result<ProvisionersResponse> {
+        let round = self.tip_header().await?.height + 1;
+
+        let vm = self.inner().vm_handler();
+        let provisioners = vm
+            .read()
+            .await
+            .provisioners(None)
+            .map_err(|e| anyhow::anyhow!("Cannot query the state {e}"))?
+            .map(|(keys, stake)| {
+                let amount = stake.amount.unwrap_or_default();
+                let owner = match keys.owner {
+                    StakeFundOwner::Account(account) => {
+                        bs58::encode(account.to_bytes()).into_string()
+                    }
+                    StakeFundOwner::Contract(contract) => {
+                        hex::encode(contract.as_bytes())
+                    }
+                };
+
+                ProvisionerResponse {
+                    key: bs58::encode(keys.account.to_bytes()).into_string(),
+                    amount: amount.value,
+                    locked_amount: amount.locked,
+                    eligibility: amount.eligibility,
+                    eligible: amount.value > 0 && amount.eligibility <= round,
+                    reward: stake.reward,
+                    faults: stake.faults,
+                    hard_faults: stake.hard_faults,
+                    owner,
+                }
+            })
+            .collect();
+
+        Ok(query.paginate(provisioners))
+    }
+
+    /// The committees of the round following the tip, for its first two
+    /// iterations.
+    pub(super) async fn rest_committee(
+        &self,
+    ) -> anyhow::Result<CommitteeResponse> {
+        let tip = self.tip_header().await?;
+        let round = tip.height + 1;
+
+        let vm = self.inner().vm_handler();
+        let provisioners = vm.read().await.get_provisioners(tip.state_hash)?;
+
+        Ok(CommitteeResponse {
+            round,
+            current: iteration_committees(&provisioners, tip.seed, round, 0),
+            next: iteration_committees(&provisioners, tip.seed, round, 1),
+        })
+    }
+}
+
+/// Extract the committees of an iteration, the same way the consensus does.
+fn iteration_committees(
+    provisioners: &Provisioners,
+    seed: Seed,
+    round: u64,
+    iteration: u8,
+) -> IterationCommittees {
+    let generator = provisioners.get_generator(iteration, seed, round);
+
+    // The generators of the iteration and of the next one are excluded from
+    // voting
+    let mut exclusion = vec![generator];
+    if exclude_next_generator(iteration) {
+        exclusion.push(provisioners.get_generator(iteration + 1, seed, round));
+    }
+
+    let members = |step| {
+        let cfg = sortition::Config::new(
+            seed,
+            round,
+            iteration,
+            step,
+            exclusion.clone(),
+        );
+        Committee::new(provisioners, &cfg)
+            .members()
+            .iter()
+            .map(|(key, credits)| CommitteeMember {
+                key: key.to_bs58(),
+                credits: *credits,
+            })
+            .collect()
+    };
+
+    IterationCommittees {
+        iteration,
+        generator: generator.to_bs58(),
+        validation: members(StepName::Validation),
+        ratification: members(StepName::Ratification),
+    }
+}
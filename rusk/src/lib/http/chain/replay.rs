@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use node_data::events::contract::ContractTxEvent;
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use super::graphql::DeserializedContractEvent;
+use super::*;
+
+/// The number of events fetched from the archive at once.
+const REPLAY_PAGE_SIZE: i64 = 1000;
+
+/// The parameters of an event replay, given in the query of the request.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ReplayQuery {
+    /// The block height to replay the events from.
+    from_height: u64,
+    /// The cursor of the last event received, to resume a replay after it.
+    cursor: Option<i64>,
+    /// If the data of the events of the genesis contracts is decoded to JSON.
+    decode: bool,
+}
+
+impl ReplayQuery {
+    fn parse(query: &str) -> anyhow::Result<Self> {
+        let mut replay = Self::default();
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            match key {
+                "from_height" => replay.from_height = value.parse()?,
+                "cursor" => replay.cursor = Some(decode_cursor(value)?),
+                "decode" => replay.decode = value.is_empty() || value == "true",
+                _ => anyhow::bail!("Unknown parameter {key}"),
+            }
+        }
+        Ok(replay)
+    }
+}
+
+/// Encode the id of an event in the archive into an opaque cursor.
+fn encode_cursor(id: i64) -> String {
+    hex::encode(id.to_be_bytes())
+}
+
+fn decode_cursor(cursor: &str) -> anyhow::Result<i64> {
+    let bytes = hex::decode(cursor)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid cursor"))?;
+    Ok(i64::from_be_bytes(bytes))
+}
+
+/// A replayed event, sent as a line of JSON.
+#[derive(Serialize)]
+struct ReplayedEvent {
+    /// The cursor to resume the replay after this event.
+    cursor: String,
+    block_height: u64,
+    block_hash: String,
+    /// The hex-encoded hash of the transaction emitting the event.
+    origin: String,
+    /// The hex-encoded id of the contract emitting the event.
+    source: String,
+    topic: String,
+    /// The data of the event, hex-encoded or decoded to JSON.
+    data: Value,
+}
+
+impl RuskNode {
+    /// Replay the finalized contract events from the archive, in the order
+    /// they were emitted, streaming them as lines of JSON.
+    ///
+    /// The replay starts from the given height, or resumes after the event of
+    /// the given cursor, and ends at the last finalized block archived.
+    pub(super) async fn replay_events(
+        &self,
+        query: &str,
+    ) -> anyhow::Result<ResponseData> {
+        let query = ReplayQuery::parse(query)?;
+        let archive = self.archive();
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_CAP);
+        task::spawn(async move {
+            let from_height = query.from_height as i64;
+            let mut after_id = query.cursor.unwrap_or_default();
+            loop {
+                let events = match archive
+                    .fetch_finalized_events_after(
+                        from_height,
+                        after_id,
+                        REPLAY_PAGE_SIZE,
+                    )
+                    .await
+                {
+                    Ok(events) => events,
+                    Err(e) => {
+                        error!("Failed replaying events: {e}");
+                        break;
+                    }
+                };
+                let is_last_page = (events.len() as i64) < REPLAY_PAGE_SIZE;
+
+                for event in events {
+                    after_id = event.id;
+                    let cursor = encode_cursor(event.id);
+                    let block_height = event.block_height as u64;
+                    let block_hash = event.block_hash.clone();
+                    let event = match ContractTxEvent::try_from(event) {
+                        Ok(event) => event,
+                        Err(e) => {
+                            error!("Failed replaying events: {e}");
+                            return;
+                        }
+                    };
+
+                    let origin = hex::encode(event.origin);
+                    let event = event.event;
+                    let source = hex::encode(event.target.0.as_bytes());
+                    let topic = event.topic.clone();
+                    let data = match query.decode {
+                        true => DeserializedContractEvent::from(event).data,
+                        false => Value::String(hex::encode(event.data)),
+                    };
+
+                    let replayed = ReplayedEvent {
+                        cursor,
+                        block_height,
+                        block_hash,
+                        origin,
+                        source,
+                        topic,
+                        data,
+                    };
+                    let Ok(mut line) = serde_json::to_vec(&replayed) else {
+                        return;
+                    };
+                    line.push(b'\n');
+                    if sender.send(line).await.is_err() {
+                        return;
+                    }
+                }
+
+                if is_last_page {
+                    break;
+                }
+            }
+        });
+
+        Ok(ResponseData::new(DataType::Channel(receiver)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_query() {
+        assert_eq!(ReplayQuery::parse("").unwrap(), ReplayQuery::default());
+
+        let cursor = encode_cursor(42);
+        let query = format!("from_height=10&cursor={cursor}&decode=true");
+        assert_eq!(
+            ReplayQuery::parse(&query).unwrap(),
+            ReplayQuery {
+                from_height: 10,
+                cursor: Some(42),
+                decode: true,
+            }
+        );
+
+        assert!(ReplayQuery::parse("cursor=invalid").is_err());
+        assert!(ReplayQuery::parse("unknown=1").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use node::database::LightBlock;
+use node_data::ledger::SpentTransaction;
+
+use super::*;
+use crate::http::rest::{BlockResponse, TransactionResponse};
+
+impl From<LightBlock> for BlockResponse {
+    fn from(block: LightBlock) -> Self {
+        let header = block.header;
+        Self {
+            hash: hex::encode(header.hash),
+            height: header.height,
+            timestamp: header.timestamp,
+            prev_block_hash: hex::encode(header.prev_block_hash),
+            state_hash: hex::encode(header.state_hash),
+            generator: header.generator_bls_pubkey.to_bs58(),
+            gas_limit: header.gas_limit,
+            transactions: block
+                .transactions_ids
+                .iter()
+                .map(hex::encode)
+                .collect(),
+        }
+    }
+}
+
+impl From<SpentTransaction> for TransactionResponse {
+    fn from(tx: SpentTransaction) -> Self {
+        let err_code = tx.failure().map(|failure| failure.code);
+        let inner = &tx.inner.inner;
+        let tx_type = match inner {
+            ProtocolTransaction::Phoenix(_) => "phoenix",
+            ProtocolTransaction::Moonlight(_) => "moonlight",
+        };
+
+        Self {
+            hash: hex::encode(tx.inner.id()),
+            tx_type: tx_type.into(),
+            block_height: tx.block_height,
+            gas_spent: tx.gas_spent,
+            gas_limit: inner.gas_limit(),
+            gas_price: inner.gas_price(),
+            err: tx.err,
+            err_code,
+        }
+    }
+}
+
+impl RuskNode {
+    /// The last `count` blocks, from the most recent.
+    pub(super) async fn rest_blocks(
+        &self,
+        count: usize,
+    ) -> anyhow::Result<Vec<BlockResponse>> {
+        self.db().read().await.view(|t| {
+            let mut blocks = vec![];
+            let mut hash = t.op_read(MD_HASH_KEY)?;
+            while let Some(block_hash) = hash {
+                let Some(block) = t.light_block(&block_hash)? else {
+                    break;
+                };
+                hash = Some(block.header.prev_block_hash.to_vec());
+                blocks.push(block.into());
+                if blocks.len() >= count {
+                    break;
+                }
+            }
+            Ok(blocks)
+        })
+    }
+
+    /// The block at the given height, or the last block.
+    pub(super) async fn rest_block(
+        &self,
+        height: Option<u64>,
+    ) -> anyhow::Result<Option<BlockResponse>> {
+        self.db().read().await.view(|t| {
+            let hash = match height {
+                Some(height) => t.block_hash_by_height(height)?.map(Vec::from),
+                None => t.op_read(MD_HASH_KEY)?,
+            };
+            let block = match hash {
+                Some(hash) => t.light_block(&hash)?,
+                None => None,
+            };
+            Ok(block.map(Into::into))
+        })
+    }
+
+    /// The last `count` executed transactions, from the most recent.
+    pub(super) async fn rest_transactions(
+        &self,
+        count: usize,
+    ) -> anyhow::Result<Vec<TransactionResponse>> {
+        self.db().read().await.view(|t| {
+            let mut txs = vec![];
+            let mut hash = t.op_read(MD_HASH_KEY)?;
+            while let Some(block_hash) = hash {
+                let Some(block) = t.light_block(&block_hash)? else {
+                    break;
+                };
+                for tx_id in block.transactions_ids {
+                    let tx = t
+                        .ledger_tx(&tx_id)?
+                        .ok_or_else(|| anyhow::anyhow!("Cannot find tx"))?;
+                    txs.push(tx.into());
+                    if txs.len() >= count {
+                        return Ok(txs);
+                    }
+                }
+                hash = Some(block.header.prev_block_hash.to_vec());
+            }
+            Ok(txs)
+        })
+    }
+
+    /// The executed transaction with the given hex-encoded hash.
+    pub(super) async fn rest_transaction(
+        &self,
+        hash: &str,
+    ) -> anyhow::Result<Option<TransactionResponse>> {
+        let hash = hex::decode(hash)?;
+        let tx = self.db().read().await.view(|t| t.ledger_tx(&hash))?;
+        Ok(tx.map(Into::into))
+    }
+}
@@ -0,0 +1,172 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use hyper::header::{
+    ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+    ACCESS_CONTROL_MAX_AGE, ORIGIN, VARY,
+};
+use hyper::http::HeaderValue;
+use hyper::{HeaderMap, Method, Request};
+use serde::{Deserialize, Serialize};
+
+const ANY_ORIGIN: &str = "*";
+
+/// Cross-origin resource sharing of the HTTP server.
+///
+/// CORS is enabled once at least one origin is allowed. Requests from the
+/// allowed origins are then answered with the corresponding
+/// `Access-Control-*` headers, and their preflight requests are answered
+/// directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpCors {
+    /// The allowed origins, or `*` to allow any origin.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// The methods allowed in cross-origin requests.
+    #[serde(default = "default_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    /// The headers allowed in cross-origin requests, or `*` to allow any.
+    #[serde(default = "default_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+    /// The headers of the responses exposed to cross-origin requests.
+    #[serde(default = "default_exposed_headers")]
+    pub exposed_headers: Vec<String>,
+    /// The number of seconds the result of a preflight request may be cached.
+    #[serde(default = "default_max_age")]
+    pub max_age: u64,
+}
+
+impl Default for HttpCors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: default_allowed_methods(),
+            allowed_headers: default_allowed_headers(),
+            exposed_headers: default_exposed_headers(),
+            max_age: default_max_age(),
+        }
+    }
+}
+
+fn default_allowed_methods() -> Vec<String> {
+    vec!["GET".into(), "POST".into(), "DELETE".into()]
+}
+
+fn default_allowed_headers() -> Vec<String> {
+    vec!["*".into()]
+}
+
+fn default_exposed_headers() -> Vec<String> {
+    vec!["Rusk-Version".into()]
+}
+
+const fn default_max_age() -> u64 {
+    86400
+}
+
+impl HttpCors {
+    /// Returns if the given request is a preflight request from an allowed
+    /// origin.
+    pub fn is_preflight<B>(&self, req: &Request<B>) -> bool {
+        req.method() == Method::OPTIONS
+            && self.allowed_origin(req.headers()).is_some()
+    }
+
+    /// The value of the `Access-Control-Allow-Origin` header for the given
+    /// request headers, if the origin of the request is allowed.
+    fn allowed_origin(&self, headers: &HeaderMap) -> Option<HeaderValue> {
+        let origin = headers.get(ORIGIN)?;
+        let is_allowed = self.allowed_origins.iter().any(|allowed| {
+            allowed == ANY_ORIGIN || allowed.as_bytes() == origin.as_bytes()
+        });
+        is_allowed.then(|| origin.clone())
+    }
+
+    /// The CORS headers to add to the response to a request with the given
+    /// headers.
+    ///
+    /// No headers are returned if the origin of the request is not allowed.
+    pub fn headers(
+        &self,
+        req_headers: &HeaderMap,
+        preflight: bool,
+    ) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let Some(origin) = self.allowed_origin(req_headers) else {
+            return headers;
+        };
+
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        headers.insert(VARY, HeaderValue::from_static("Origin"));
+
+        let mut insert = |name, values: &[String]| {
+            if let Ok(value) = HeaderValue::from_str(&values.join(", ")) {
+                headers.insert(name, value);
+            }
+        };
+        insert(ACCESS_CONTROL_EXPOSE_HEADERS, &self.exposed_headers);
+        if preflight {
+            insert(ACCESS_CONTROL_ALLOW_METHODS, &self.allowed_methods);
+            insert(ACCESS_CONTROL_ALLOW_HEADERS, &self.allowed_headers);
+            headers.insert(ACCESS_CONTROL_MAX_AGE, self.max_age.into());
+        }
+
+        headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, origin: &str) -> Request<()> {
+        Request::builder()
+            .method(method)
+            .header(ORIGIN, origin)
+            .body(())
+            .expect("request to build")
+    }
+
+    #[test]
+    fn allowed_origins() {
+        let cors = HttpCors {
+            allowed_origins: vec!["https://dusk.network".into()],
+            ..Default::default()
+        };
+
+        let req = request(Method::OPTIONS, "https://dusk.network");
+        assert!(cors.is_preflight(&req));
+        let headers = cors.headers(req.headers(), true);
+        assert_eq!(
+            headers[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dusk.network"
+        );
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_METHODS], "GET, POST, DELETE");
+
+        let req = request(Method::GET, "https://dusk.network");
+        assert!(!cors.is_preflight(&req));
+        let headers = cors.headers(req.headers(), false);
+        assert!(headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+
+        let req = request(Method::OPTIONS, "https://example.com");
+        assert!(!cors.is_preflight(&req));
+        assert!(cors.headers(req.headers(), true).is_empty());
+    }
+
+    #[test]
+    fn any_origin() {
+        let cors = HttpCors {
+            allowed_origins: vec![ANY_ORIGIN.into()],
+            ..Default::default()
+        };
+
+        let req = request(Method::GET, "https://example.com");
+        let headers = cors.headers(req.headers(), false);
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_ORIGIN], "https://example.com");
+    }
+}
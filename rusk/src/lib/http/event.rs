@@ -7,8 +7,7 @@
 use base64::engine::{general_purpose::STANDARD as BASE64, Engine};
 use bytecheck::CheckBytes;
 use dusk_core::abi::ContractId;
-use futures_util::stream::Iter as StreamIter;
-use futures_util::{stream, Stream, StreamExt};
+use futures_util::{Stream, StreamExt};
 use http_body_util::{BodyExt, Either, Full, StreamBody};
 use hyper::body::{Body, Buf, Bytes, Frame, Incoming};
 use hyper::header::{InvalidHeaderName, InvalidHeaderValue};
@@ -26,8 +25,9 @@ use std::fmt::{Display, Formatter};
 use std::pin::Pin;
 use std::str::FromStr;
 use std::str::Split;
-use std::sync::mpsc;
 use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tungstenite::http::HeaderValue;
 
 use super::{RUSK_VERSION_HEADER, RUSK_VERSION_STRICT_HEADER};
@@ -82,7 +82,7 @@ impl MessageResponse {
                     either: Either::Right(StreamBody::new(
                         BinaryOrTextStream {
                             is_binary,
-                            stream: stream::iter(receiver),
+                            stream: ReceiverStream::new(receiver),
                         },
                     )),
                 },
@@ -145,7 +145,7 @@ impl Body for FullOrStreamBody {
 pub struct BinaryOrTextStream {
     is_binary: bool,
     #[pin]
-    stream: StreamIter<<mpsc::Receiver<Vec<u8>> as IntoIterator>::IntoIter>,
+    stream: ReceiverStream<Vec<u8>>,
 }
 
 impl Stream for BinaryOrTextStream {
@@ -319,6 +319,9 @@ impl From<mpsc::Receiver<Vec<u8>>> for DataType {
     }
 }
 
+/// The number of items buffered when streaming the items of a channel.
+pub const CHANNEL_BUFFER_CAP: usize = 64;
+
 #[serde_with::serde_as]
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(transparent)]
@@ -330,6 +333,7 @@ pub struct BinaryWrapper {
 const CONTENT_TYPE: &str = "content-type";
 const ACCEPT: &str = "accept";
 const CONTENT_TYPE_BINARY: &str = "application/octet-stream";
+const SEC_WEBSOCKET_PROTOCOL: &str = "sec-websocket-protocol";
 static CONTENT_TYPE_JSON: HeaderValue =
     HeaderValue::from_static("application/json");
 
@@ -358,6 +362,15 @@ pub(crate) fn parse_header(bytes: &[u8]) -> anyhow::Result<Header> {
     Ok((header, bytes))
 }
 
+fn parse_len_prefixed(bytes: &[u8]) -> anyhow::Result<(&[u8], &[u8])> {
+    let (len, bytes) = parse_len(bytes)?;
+    if bytes.len() < len {
+        return Err(anyhow::anyhow!("not enough bytes for parsed len {len}"));
+    }
+
+    Ok(bytes.split_at(len))
+}
+
 fn parse_target_type(bytes: &[u8]) -> anyhow::Result<(u8, &[u8])> {
     if bytes.is_empty() {
         return Err(anyhow::anyhow!("not enough bytes for target type"));
@@ -723,6 +736,114 @@ impl RuesEvent {
 
         bytes
     }
+
+    /// Serialize the event into a binary frame, for the sessions that
+    /// negotiated the [`RUES_BINARY_PROTOCOL`].
+    ///
+    /// Every field of the frame is prefixed by its length, as a little-endian
+    /// `u32`:
+    /// - the location of the event
+    /// - the number of headers, followed by each header as its name and its
+    ///   JSON value
+    /// - the data of the event, as is
+    pub fn to_binary_frame(&self) -> Vec<u8> {
+        let location = self.uri.to_string();
+        let data = self.data.to_bytes();
+
+        let mut bytes = Vec::with_capacity(12 + location.len() + data.len());
+
+        extend_len_prefixed(&mut bytes, location.as_bytes());
+        bytes.extend((self.headers.len() as u32).to_le_bytes());
+        for (name, value) in &self.headers {
+            let value = serde_json::to_vec(value)
+                .expect("Serializing JSON should succeed");
+            extend_len_prefixed(&mut bytes, name.as_bytes());
+            extend_len_prefixed(&mut bytes, &value);
+        }
+        extend_len_prefixed(&mut bytes, &data);
+
+        bytes
+    }
+
+    /// Deserialize an event from a binary frame.
+    ///
+    /// See [`RuesEvent::to_binary_frame`] for the format of the frame.
+    pub fn from_binary_frame(bytes: &[u8]) -> anyhow::Result<Self> {
+        let (location, bytes) = parse_string(bytes)?;
+        let uri = RuesEventUri::parse_from_path(&location)
+            .ok_or(anyhow::anyhow!("Invalid location"))?;
+
+        let (headers_len, mut bytes) = parse_len(bytes)?;
+        let mut headers = serde_json::Map::new();
+        for _ in 0..headers_len {
+            let (name, rest) = parse_string(bytes)?;
+            let (value, rest) = parse_len_prefixed(rest)?;
+            headers.insert(name, serde_json::from_slice(value)?);
+            bytes = rest;
+        }
+
+        let (data, bytes) = parse_len_prefixed(bytes)?;
+        if !bytes.is_empty() {
+            return Err(anyhow::anyhow!("trailing bytes after the data"));
+        }
+
+        Ok(Self {
+            uri,
+            headers,
+            data: data.to_vec().into(),
+        })
+    }
+}
+
+/// The WebSocket subprotocol a client requests to receive events as binary
+/// frames, instead of the default framing with JSON headers.
+pub const RUES_BINARY_PROTOCOL: &str = "rues.binary.v1";
+
+/// The framing of the events sent to a RUES WebSocket session, negotiated
+/// when the session is opened.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventFraming {
+    /// The events are sent with their headers serialized as JSON.
+    #[default]
+    Json,
+    /// The events are sent as binary frames.
+    Binary,
+}
+
+impl EventFraming {
+    /// Negotiate the framing from the WebSocket subprotocols requested by the
+    /// client, returning the framing and the subprotocol accepted, if any.
+    pub fn negotiate(
+        headers: &hyper::HeaderMap,
+    ) -> (Self, Option<&'static str>) {
+        let binary = headers
+            .get_all(SEC_WEBSOCKET_PROTOCOL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|protocol| protocol.trim() == RUES_BINARY_PROTOCOL);
+
+        match binary {
+            true => (Self::Binary, Some(RUES_BINARY_PROTOCOL)),
+            false => (Self::Json, None),
+        }
+    }
+
+    /// Encode the given event with the framing.
+    pub fn encode(self, mut event: RuesEvent) -> Vec<u8> {
+        match self {
+            Self::Json => {
+                event.add_header("Content-Location", event.uri.to_string());
+                event.to_bytes()
+            }
+            Self::Binary => event.to_binary_frame(),
+        }
+    }
+}
+
+fn extend_len_prefixed(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend((field.len() as u32).to_le_bytes());
+    bytes.extend(field);
 }
 
 #[cfg(feature = "chain")]
@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::str::FromStr;
+
+use http_body_util::BodyExt;
+use hyper::body::Incoming;
+use hyper::header::{
+    HeaderName, CONNECTION, CONTENT_LENGTH, HOST, TRANSFER_ENCODING,
+};
+use hyper::http::uri::Authority;
+use hyper::{Request, Response};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task;
+
+use super::event::{
+    DataType, FullOrStreamBody, MessageResponse, RuesEventUri,
+    CHANNEL_BUFFER_CAP,
+};
+
+/// The headers that only apply to a single connection, and are therefore not
+/// forwarded.
+const HOP_HEADERS: [HeaderName; 4] =
+    [HOST, CONNECTION, CONTENT_LENGTH, TRANSFER_ENCODING];
+
+/// A route of RUES targets to a backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRoute {
+    /// The hostname the route applies to. The route applies to any host if
+    /// none is given.
+    pub host: Option<String>,
+    /// The targets routed, either as a component, such as `graphql`, or as a
+    /// `component/topic` pair, such as `prover/prove`.
+    pub target: String,
+    /// The base URL of the backend, such as `http://127.0.0.1:8081`.
+    pub backend: String,
+}
+
+impl HttpRoute {
+    fn matches(&self, host: Option<&str>, uri: &RuesEventUri) -> bool {
+        let host_matches = match (&self.host, host) {
+            (None, _) => true,
+            (Some(route_host), Some(host)) => {
+                route_host.eq_ignore_ascii_case(host)
+            }
+            (Some(_), None) => false,
+        };
+
+        let target_matches = match self.target.split_once('/') {
+            Some((component, topic)) => {
+                component == uri.component && topic == uri.topic
+            }
+            None => self.target == uri.component,
+        };
+
+        host_matches && target_matches
+    }
+}
+
+/// Forwards RUES dispatch requests to the backends given by a routing table,
+/// making the server act as a gateway.
+#[derive(Default)]
+pub(crate) struct Gateway {
+    routes: Vec<HttpRoute>,
+    client: reqwest::Client,
+}
+
+impl Gateway {
+    pub fn new(routes: Vec<HttpRoute>) -> Self {
+        Self {
+            routes,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// The route of the given request, if any. The first matching route in
+    /// the table is taken.
+    pub fn route<B>(&self, req: &Request<B>) -> Option<&HttpRoute> {
+        if self.routes.is_empty() {
+            return None;
+        }
+
+        let uri = RuesEventUri::parse_from_path(req.uri().path())?;
+        let host = request_host(req);
+        self.routes
+            .iter()
+            .find(|route| route.matches(host.as_deref(), &uri))
+    }
+
+    /// Forward the request to the backend of the given route, streaming the
+    /// response of the backend back.
+    pub async fn forward(
+        &self,
+        route: &HttpRoute,
+        req: Request<Incoming>,
+    ) -> anyhow::Result<Response<FullOrStreamBody>> {
+        let (parts, body) = req.into_parts();
+
+        let path = parts
+            .uri
+            .path_and_query()
+            .map(|path| path.as_str())
+            .unwrap_or_default();
+        let url = format!("{}{path}", route.backend.trim_end_matches('/'));
+
+        let mut headers = parts.headers;
+        for header in HOP_HEADERS {
+            headers.remove(header);
+        }
+
+        let body = body.collect().await?.to_bytes();
+        let mut backend_rsp = self
+            .client
+            .request(parts.method, url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+
+        let status = backend_rsp.status();
+        let mut headers = backend_rsp.headers().clone();
+        for header in HOP_HEADERS {
+            headers.remove(header);
+        }
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_CAP);
+        task::spawn(async move {
+            while let Ok(Some(chunk)) = backend_rsp.chunk().await {
+                if sender.send(chunk.to_vec()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut rsp = MessageResponse {
+            headers: serde_json::Map::new(),
+            data: DataType::Channel(receiver),
+            error: None,
+        }
+        .into_http(true)?;
+        *rsp.status_mut() = status;
+        rsp.headers_mut().extend(headers);
+
+        Ok(rsp)
+    }
+}
+
+/// The hostname the request is addressed to, without the port.
+fn request_host<B>(req: &Request<B>) -> Option<String> {
+    let host = req
+        .headers()
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(Authority::from_str)
+        .and_then(Result::ok)
+        .or_else(|| req.uri().authority().cloned())?;
+    Some(host.host().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(host: &str, path: &str) -> Request<()> {
+        Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(HOST, host)
+            .body(())
+            .expect("request to build")
+    }
+
+    #[test]
+    fn routing() {
+        let archive = HttpRoute {
+            host: Some("archive.dusk.network".into()),
+            target: "graphql".into(),
+            backend: "http://127.0.0.1:8081".into(),
+        };
+        let prover = HttpRoute {
+            host: None,
+            target: "prover/prove".into(),
+            backend: "http://127.0.0.1:8082".into(),
+        };
+        let gateway = Gateway::new(vec![archive.clone(), prover.clone()]);
+
+        let req = request("archive.dusk.network:8080", "/on/graphql/query");
+        assert_eq!(gateway.route(&req), Some(&archive));
+
+        let req = request("nodes.dusk.network", "/on/graphql/query");
+        assert_eq!(gateway.route(&req), None, "the host should not match");
+
+        let req = request("nodes.dusk.network", "/on/prover/prove");
+        assert_eq!(gateway.route(&req), Some(&prover));
+
+        let req = request("nodes.dusk.network", "/on/prover/other");
+        assert_eq!(gateway.route(&req), None, "the topic should not match");
+    }
+}
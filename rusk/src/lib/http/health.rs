@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use serde::Serialize;
+
+pub const HEALTH_PATH: &str = "/healthz";
+pub const READINESS_PATH: &str = "/readyz";
+
+/// The maximum number of seconds the tip may be behind the current time for
+/// the node to be considered in sync.
+pub const MAX_SYNC_LAG: u64 = 120;
+
+/// The health of a node, as reported by the health and readiness endpoints.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeHealth {
+    pub tip_height: u64,
+    pub finalized_height: u64,
+    /// The number of alive peers.
+    pub peers: usize,
+    /// The number of seconds the tip is behind the current time.
+    pub sync_lag: u64,
+    /// The number of transactions in the mempool.
+    pub mempool_txs: usize,
+}
+
+impl NodeHealth {
+    /// Returns if the node is ready to serve requests, meaning it's connected
+    /// to the network and in sync with it.
+    pub fn is_ready(&self) -> bool {
+        self.peers > 0 && self.sync_lag <= MAX_SYNC_LAG
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readiness() {
+        let health = NodeHealth {
+            tip_height: 10,
+            finalized_height: 8,
+            peers: 3,
+            sync_lag: 10,
+            mempool_txs: 0,
+        };
+        assert!(health.is_ready());
+
+        let isolated = NodeHealth {
+            peers: 0,
+            ..health.clone()
+        };
+        assert!(!isolated.is_ready(), "isolated nodes are not ready");
+
+        let syncing = NodeHealth {
+            sync_lag: MAX_SYNC_LAG + 1,
+            ..health
+        };
+        assert!(!syncing.is_ready(), "syncing nodes are not ready");
+    }
+}
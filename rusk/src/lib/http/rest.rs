@@ -0,0 +1,559 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The typed REST layer, served under `/v1`.
+//!
+//! The responses are described by the types in this module, from which the
+//! OpenAPI specification served at `/v1/openapi.json` is derived.
+
+use hyper::Uri;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+pub const REST_PREFIX: &str = "/v1/";
+pub const OPENAPI_PATH: &str = "/v1/openapi.json";
+
+/// The number of blocks or transactions listed by default.
+const DEFAULT_COUNT: usize = 10;
+/// The maximum number of blocks or transactions listed.
+const MAX_COUNT: usize = 100;
+
+/// A route of the REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestRoute {
+    /// The last blocks, from the most recent.
+    Blocks { count: usize },
+    /// The block at a height, or the last block if none is given.
+    Block { height: Option<u64> },
+    /// The last transactions, from the most recent.
+    Transactions { count: usize },
+    /// A transaction by its hash.
+    Transaction { hash: String },
+    /// An account by its base58-encoded public key.
+    Account { pk: String },
+    /// A page of the provisioners.
+    Provisioners { query: ProvisionersQuery },
+    /// The committees of the round being voted.
+    Committee,
+}
+
+impl RestRoute {
+    /// Parse the route of the given URI, returning `None` if it doesn't match
+    /// any.
+    pub fn parse(uri: &Uri) -> Option<Self> {
+        let path = uri.path().strip_prefix(REST_PREFIX)?;
+        let segments: Vec<_> = path.trim_end_matches('/').split('/').collect();
+
+        let route = match segments[..] {
+            ["blocks"] => Self::Blocks {
+                count: ListQuery::parse(uri).count(),
+            },
+            ["blocks", "latest"] => Self::Block { height: None },
+            ["blocks", height] => Self::Block {
+                height: Some(height.parse().ok()?),
+            },
+            ["transactions"] => Self::Transactions {
+                count: ListQuery::parse(uri).count(),
+            },
+            ["transactions", hash] => Self::Transaction {
+                hash: hash.to_string(),
+            },
+            ["accounts", pk] => Self::Account { pk: pk.to_string() },
+            ["provisioners"] => Self::Provisioners {
+                query: ProvisionersQuery::parse(uri),
+            },
+            ["committee"] => Self::Committee,
+            _ => return None,
+        };
+
+        Some(route)
+    }
+}
+
+/// The query of the routes listing items.
+#[derive(Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListQuery {
+    /// The number of items to list, at most 100. Defaults to 10.
+    count: Option<usize>,
+}
+
+impl ListQuery {
+    fn parse(uri: &Uri) -> Self {
+        let count = uri
+            .query()
+            .into_iter()
+            .flat_map(|query| query.split('&'))
+            .find_map(|param| param.strip_prefix("count="))
+            .and_then(|count| count.parse().ok());
+        Self { count }
+    }
+
+    fn count(&self) -> usize {
+        self.count.unwrap_or(DEFAULT_COUNT).clamp(1, MAX_COUNT)
+    }
+}
+
+/// The order the provisioners are sorted by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvisionerSort {
+    /// By base58-encoded public key.
+    Key,
+    /// By amount staked.
+    #[default]
+    Stake,
+    /// By the height the stake is eligible from.
+    Eligibility,
+}
+
+/// The direction the provisioners are sorted in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// The query of the provisioners route.
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProvisionersQuery {
+    /// The page to list, starting from 0.
+    page: Option<usize>,
+    /// The number of provisioners in a page, at most 100. Defaults to 10.
+    page_size: Option<usize>,
+    /// The field to sort by. Defaults to `stake`.
+    sort: Option<ProvisionerSort>,
+    /// The direction to sort in. Defaults to `desc`.
+    order: Option<SortOrder>,
+}
+
+impl ProvisionersQuery {
+    fn parse(uri: &Uri) -> Self {
+        let mut query = Self::default();
+        let params = uri.query().into_iter().flat_map(|query| query.split('&'));
+        for param in params {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            match key {
+                "page" => query.page = value.parse().ok(),
+                "page_size" => query.page_size = value.parse().ok(),
+                "sort" => {
+                    query.sort = serde_json::from_value(value.into()).ok()
+                }
+                "order" => {
+                    query.order = serde_json::from_value(value.into()).ok()
+                }
+                _ => {}
+            }
+        }
+        query
+    }
+
+    /// Sort the given provisioners and return the page requested.
+    pub fn paginate(
+        &self,
+        mut provisioners: Vec<ProvisionerResponse>,
+    ) -> ProvisionersResponse {
+        let page = self.page.unwrap_or_default();
+        let page_size =
+            self.page_size.unwrap_or(DEFAULT_COUNT).clamp(1, MAX_COUNT);
+
+        // Ties are broken by key, for the pages to be stable
+        provisioners.sort_by(|a, b| {
+            let ordering = match self.sort.unwrap_or_default() {
+                ProvisionerSort::Key => a.key.cmp(&b.key),
+                ProvisionerSort::Stake => a.amount.cmp(&b.amount),
+                ProvisionerSort::Eligibility => {
+                    a.eligibility.cmp(&b.eligibility)
+                }
+            };
+            let ordering = ordering.then_with(|| a.key.cmp(&b.key));
+            match self.order.unwrap_or_default() {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let total = provisioners.len();
+        let provisioners = provisioners
+            .into_iter()
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+            .collect();
+
+        ProvisionersResponse {
+            total,
+            page,
+            page_size,
+            provisioners,
+        }
+    }
+}
+
+/// A block of the chain.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockResponse {
+    /// The hex-encoded hash of the block.
+    pub hash: String,
+    pub height: u64,
+    /// The timestamp of the block, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The hex-encoded hash of the previous block.
+    pub prev_block_hash: String,
+    /// The hex-encoded root of the state after the block.
+    pub state_hash: String,
+    /// The base58-encoded public key of the generator of the block.
+    pub generator: String,
+    pub gas_limit: u64,
+    /// The hex-encoded hashes of the transactions in the block.
+    pub transactions: Vec<String>,
+}
+
+/// A transaction executed in a block.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransactionResponse {
+    /// The hex-encoded hash of the transaction.
+    pub hash: String,
+    /// The type of the transaction, either `phoenix` or `moonlight`.
+    pub tx_type: String,
+    pub block_height: u64,
+    pub gas_spent: u64,
+    pub gas_limit: u64,
+    pub gas_price: u64,
+    /// The error the transaction failed with, if any.
+    pub err: Option<String>,
+    /// The code of the error, if the transaction was aborted with
+    /// `abi::fail`.
+    pub err_code: Option<u32>,
+}
+
+/// A public account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountResponse {
+    /// The base58-encoded public key of the account.
+    pub pk: String,
+    pub balance: u64,
+    pub nonce: u64,
+}
+
+/// A provisioner, staking to take part in the consensus.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProvisionerResponse {
+    /// The base58-encoded public key of the provisioner.
+    pub key: String,
+    /// The amount staked.
+    pub amount: u64,
+    /// The amount locked by soft slashes.
+    pub locked_amount: u64,
+    /// The height the stake is eligible from.
+    pub eligibility: u64,
+    /// If the stake is eligible for the round being voted.
+    pub eligible: bool,
+    pub reward: u64,
+    pub faults: u8,
+    pub hard_faults: u8,
+    /// The owner of the stake, either the base58-encoded public key of an
+    /// account or the hex-encoded id of a contract.
+    pub owner: String,
+}
+
+/// A page of the provisioners.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProvisionersResponse {
+    /// The total number of provisioners.
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub provisioners: Vec<ProvisionerResponse>,
+}
+
+/// A member of a voting committee.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CommitteeMember {
+    /// The base58-encoded public key of the provisioner.
+    pub key: String,
+    /// The number of votes of the provisioner in the committee.
+    pub credits: usize,
+}
+
+/// The committees of an iteration of the consensus.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IterationCommittees {
+    pub iteration: u8,
+    /// The base58-encoded public key of the generator of the candidate block.
+    pub generator: String,
+    pub validation: Vec<CommitteeMember>,
+    pub ratification: Vec<CommitteeMember>,
+}
+
+/// The committees of the round being voted.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CommitteeResponse {
+    pub round: u64,
+    /// The committees of the first iteration of the round.
+    pub current: IterationCommittees,
+    /// The committees of the iteration following it, voting if the first
+    /// iteration fails.
+    pub next: IterationCommittees,
+}
+
+/// The error returned by a failed request.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// The documentation of the routes, from which the paths of the OpenAPI
+/// specification are derived.
+#[allow(dead_code)]
+mod paths {
+    use super::*;
+
+    /// List the last blocks, from the most recent.
+    #[utoipa::path(
+        get,
+        path = "/v1/blocks",
+        params(ListQuery),
+        responses((status = 200, body = Vec<BlockResponse>))
+    )]
+    pub(super) fn blocks() {}
+
+    /// Get the last block.
+    #[utoipa::path(
+        get,
+        path = "/v1/blocks/latest",
+        responses(
+            (status = 200, body = BlockResponse),
+            (status = 404, body = ErrorResponse),
+        )
+    )]
+    pub(super) fn latest_block() {}
+
+    /// Get the block at a height.
+    #[utoipa::path(
+        get,
+        path = "/v1/blocks/{height}",
+        params(
+            ("height" = u64, Path, description = "The height of the block")
+        ),
+        responses(
+            (status = 200, body = BlockResponse),
+            (status = 404, body = ErrorResponse),
+        )
+    )]
+    pub(super) fn block() {}
+
+    /// List the last executed transactions, from the most recent.
+    #[utoipa::path(
+        get,
+        path = "/v1/transactions",
+        params(ListQuery),
+        responses((status = 200, body = Vec<TransactionResponse>))
+    )]
+    pub(super) fn transactions() {}
+
+    /// Get an executed transaction by its hash.
+    #[utoipa::path(
+        get,
+        path = "/v1/transactions/{hash}",
+        params(
+            (
+                "hash" = String,
+                Path,
+                description = "The hex-encoded hash of the transaction"
+            )
+        ),
+        responses(
+            (status = 200, body = TransactionResponse),
+            (status = 400, body = ErrorResponse),
+            (status = 404, body = ErrorResponse),
+        )
+    )]
+    pub(super) fn transaction() {}
+
+    /// Get a public account by its public key.
+    #[utoipa::path(
+        get,
+        path = "/v1/accounts/{pk}",
+        params(
+            (
+                "pk" = String,
+                Path,
+                description = "The base58-encoded public key of the account"
+            )
+        ),
+        responses(
+            (status = 200, body = AccountResponse),
+            (status = 400, body = ErrorResponse),
+        )
+    )]
+    pub(super) fn account() {}
+
+    /// List a page of the provisioners, with their stakes.
+    #[utoipa::path(
+        get,
+        path = "/v1/provisioners",
+        params(ProvisionersQuery),
+        responses((status = 200, body = ProvisionersResponse))
+    )]
+    pub(super) fn provisioners() {}
+
+    /// Get the committees of the round being voted.
+    #[utoipa::path(
+        get,
+        path = "/v1/committee",
+        responses(
+            (status = 200, body = CommitteeResponse),
+            (status = 400, body = ErrorResponse),
+        )
+    )]
+    pub(super) fn committee() {}
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Rusk REST API"),
+    paths(
+        paths::blocks,
+        paths::latest_block,
+        paths::block,
+        paths::transactions,
+        paths::transaction,
+        paths::account,
+        paths::provisioners,
+        paths::committee,
+    ),
+    components(schemas(
+        BlockResponse,
+        TransactionResponse,
+        AccountResponse,
+        ProvisionerSort,
+        SortOrder,
+        ProvisionerResponse,
+        ProvisionersResponse,
+        CommitteeMember,
+        IterationCommittees,
+        CommitteeResponse,
+        ErrorResponse,
+    ))
+)]
+struct RestApi;
+
+/// The OpenAPI specification of the REST API.
+pub fn openapi() -> serde_json::Value {
+    let mut spec = RestApi::openapi();
+    spec.info.version = crate::VERSION.to_string();
+    serde_json::to_value(spec).expect("The specification should serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_routes() {
+        let parse = |uri: &str| RestRoute::parse(&uri.parse().unwrap());
+
+        assert_eq!(parse("/v1/blocks"), Some(RestRoute::Blocks { count: 10 }));
+        assert_eq!(
+            parse("/v1/blocks?count=1000"),
+            Some(RestRoute::Blocks { count: MAX_COUNT })
+        );
+        assert_eq!(
+            parse("/v1/blocks/latest"),
+            Some(RestRoute::Block { height: None })
+        );
+        assert_eq!(
+            parse("/v1/blocks/42"),
+            Some(RestRoute::Block { height: Some(42) })
+        );
+        assert_eq!(parse("/v1/blocks/invalid"), None);
+        assert_eq!(
+            parse("/v1/transactions?count=5"),
+            Some(RestRoute::Transactions { count: 5 })
+        );
+        assert_eq!(
+            parse("/v1/accounts/pk"),
+            Some(RestRoute::Account { pk: "pk".into() })
+        );
+        assert_eq!(
+            parse("/v1/provisioners?page=2&sort=key&order=asc"),
+            Some(RestRoute::Provisioners {
+                query: ProvisionersQuery {
+                    page: Some(2),
+                    page_size: None,
+                    sort: Some(ProvisionerSort::Key),
+                    order: Some(SortOrder::Asc),
+                }
+            })
+        );
+        assert_eq!(parse("/v1/committee"), Some(RestRoute::Committee));
+        assert_eq!(parse("/v1/unknown"), None);
+    }
+
+    #[test]
+    fn paginate_provisioners() {
+        let provisioner = |key: &str, amount| ProvisionerResponse {
+            key: key.into(),
+            amount,
+            locked_amount: 0,
+            eligibility: 0,
+            eligible: true,
+            reward: 0,
+            faults: 0,
+            hard_faults: 0,
+            owner: key.into(),
+        };
+        let provisioners = vec![
+            provisioner("a", 2),
+            provisioner("b", 3),
+            provisioner("c", 2),
+        ];
+        let keys = |page: ProvisionersResponse| {
+            page.provisioners
+                .into_iter()
+                .map(|provisioner| provisioner.key)
+                .collect::<Vec<_>>()
+        };
+
+        let query = ProvisionersQuery::default();
+        let page = query.paginate(provisioners.clone());
+        assert_eq!(page.total, 3);
+        assert_eq!(keys(page), ["b", "c", "a"]);
+
+        let query = ProvisionersQuery {
+            page: Some(1),
+            page_size: Some(2),
+            sort: Some(ProvisionerSort::Key),
+            order: Some(SortOrder::Asc),
+        };
+        assert_eq!(keys(query.paginate(provisioners)), ["c"]);
+    }
+
+    #[test]
+    fn openapi_spec() {
+        let spec = openapi();
+
+        let paths = spec["paths"].as_object().expect("paths to be present");
+        for path in [
+            "/v1/blocks",
+            "/v1/blocks/latest",
+            "/v1/blocks/{height}",
+            "/v1/transactions",
+            "/v1/transactions/{hash}",
+            "/v1/accounts/{pk}",
+            "/v1/provisioners",
+            "/v1/committee",
+        ] {
+            assert!(paths.contains_key(path), "{path} should be documented");
+        }
+        assert!(spec["components"]["schemas"]["BlockResponse"].is_object());
+    }
+}
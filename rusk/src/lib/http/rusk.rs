@@ -10,18 +10,22 @@ use dusk_bytes::{DeserializableSlice, Serializable};
 use dusk_core::abi::ContractId;
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::stake::StakeFundOwner;
+use dusk_core::transfer::data::ContractCall;
 use node::vm::VMExecution;
 use rusk_profile::CRS_17_HASH;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::sync::{mpsc, Arc};
+use std::sync::Arc;
 use std::thread;
 use tokio::task;
 use tungstenite::http::request;
 
-use crate::node::Rusk;
+use crate::http::event::CHANNEL_BUFFER_CAP;
+use crate::http::rest::AccountResponse;
+use crate::node::{Rusk, StateOverride};
 
 const RUSK_FEEDER_HEADER: &str = "Rusk-Feeder";
+const RUSK_STATE_OVERRIDES_HEADER: &str = "Rusk-State-Overrides";
 
 #[async_trait]
 impl HandleRequest for Rusk {
@@ -43,7 +47,20 @@ impl HandleRequest for Rusk {
             ("contracts", Some(contract_id), method) => {
                 let feeder = request.header(RUSK_FEEDER_HEADER).is_some();
                 let data = request.data.as_bytes();
-                self.handle_contract_query(contract_id, method, data, feeder)
+                match request.header(RUSK_STATE_OVERRIDES_HEADER) {
+                    Some(overrides) if !feeder => self.handle_dry_run_query(
+                        contract_id,
+                        method,
+                        data,
+                        overrides,
+                    ),
+                    _ => self.handle_contract_query(
+                        contract_id,
+                        method,
+                        data,
+                        feeder,
+                    ),
+                }
             }
             ("node", _, "provisioners") => self.get_provisioners(),
 
@@ -52,6 +69,22 @@ impl HandleRequest for Rusk {
             _ => Err(anyhow::anyhow!("Unsupported")),
         }
     }
+
+    fn can_handle_rest(&self, route: &RestRoute) -> bool {
+        matches!(route, RestRoute::Account { .. })
+    }
+    async fn handle_rest(
+        &self,
+        route: &RestRoute,
+    ) -> anyhow::Result<Option<serde_json::Value>> {
+        match route {
+            RestRoute::Account { pk } => {
+                let account = self.rest_account(pk)?;
+                Ok(Some(serde_json::to_value(account)?))
+            }
+            _ => Err(anyhow::anyhow!("Unsupported")),
+        }
+    }
 }
 
 impl Rusk {
@@ -62,21 +95,20 @@ impl Rusk {
         data: &[u8],
         feeder: bool,
     ) -> anyhow::Result<ResponseData> {
-        let contract_bytes = hex::decode(contract)?;
-
-        let contract_bytes = contract_bytes
-            .try_into()
-            .map_err(|_| anyhow::anyhow!("Invalid contract bytes"))?;
-        let contract_id = ContractId::from_bytes(contract_bytes);
+        let contract_id = parse_contract_id(contract)?;
         let fn_name = topic.to_string();
         let data = data.to_vec();
         if feeder {
-            let (sender, receiver) = mpsc::channel();
+            let (sender, receiver) = mpsc::channel(CHANNEL_BUFFER_CAP);
 
             let rusk = self.clone();
 
             thread::spawn(move || {
-                rusk.feeder_query_raw(contract_id, fn_name, data, sender);
+                if let Err(err) =
+                    rusk.feeder_query_raw(contract_id, fn_name, data, sender)
+                {
+                    warn!("Feeder query failed: {err}");
+                }
             });
             Ok(ResponseData::new(receiver))
         } else {
@@ -87,6 +119,22 @@ impl Rusk {
         }
     }
 
+    fn handle_dry_run_query(
+        &self,
+        contract: &str,
+        topic: &str,
+        data: &[u8],
+        overrides: &serde_json::Value,
+    ) -> anyhow::Result<ResponseData> {
+        let contract_id = parse_contract_id(contract)?;
+        let overrides = parse_overrides(overrides)?;
+
+        let data = self
+            .dry_run_query_raw(contract_id, topic, data.to_vec(), &overrides)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(ResponseData::new(data))
+    }
+
     fn get_provisioners(&self) -> anyhow::Result<ResponseData> {
         let prov: Vec<_> = self
             .provisioners(None)
@@ -113,11 +161,7 @@ impl Rusk {
     }
 
     fn get_account(&self, pk: &str) -> anyhow::Result<ResponseData> {
-        let pk = bs58::decode(pk)
-            .into_vec()
-            .map_err(|_| anyhow::anyhow!("Invalid bs58 account"))?;
-        let pk = BlsPublicKey::from_slice(&pk)
-            .map_err(|_| anyhow::anyhow!("Invalid bls account"))?;
+        let pk = parse_account(pk)?;
         let account = self
             .account(&pk)
             .map(|account| {
@@ -130,12 +174,105 @@ impl Rusk {
         Ok(ResponseData::new(account))
     }
 
+    fn rest_account(&self, pk: &str) -> anyhow::Result<AccountResponse> {
+        let account = self
+            .account(&parse_account(pk)?)
+            .map_err(|e| anyhow::anyhow!("Cannot query the state {e:?}"))?;
+        Ok(AccountResponse {
+            pk: pk.to_string(),
+            balance: account.balance,
+            nonce: account.nonce,
+        })
+    }
+
     fn get_crs(&self) -> anyhow::Result<ResponseData> {
         let crs = rusk_profile::get_common_reference_string()?;
         Ok(ResponseData::new(crs).with_header("crs-hash", CRS_17_HASH))
     }
 }
 
+pub(super) fn parse_account(pk: &str) -> anyhow::Result<BlsPublicKey> {
+    let pk = bs58::decode(pk)
+        .into_vec()
+        .map_err(|_| anyhow::anyhow!("Invalid bs58 account"))?;
+    BlsPublicKey::from_slice(&pk)
+        .map_err(|_| anyhow::anyhow!("Invalid bls account"))
+}
+
+fn parse_contract_id(contract: &str) -> anyhow::Result<ContractId> {
+    let contract_bytes = hex::decode(contract)?;
+
+    let contract_bytes = contract_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid contract bytes"))?;
+    Ok(ContractId::from_bytes(contract_bytes))
+}
+
+/// Parses the value of the `Rusk-State-Overrides` header, given either as a
+/// JSON array or as a string encoding one.
+fn parse_overrides(
+    overrides: &serde_json::Value,
+) -> anyhow::Result<Vec<StateOverride>> {
+    let overrides: Vec<OverrideJson> = match overrides {
+        serde_json::Value::String(overrides) => {
+            serde_json::from_str(overrides)?
+        }
+        overrides => serde_json::from_value(overrides.clone())?,
+    };
+    overrides.into_iter().map(StateOverride::try_from).collect()
+}
+
+/// A state override, as received in the `Rusk-State-Overrides` header.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OverrideJson {
+    AccountBalance {
+        account: String,
+        balance: u64,
+    },
+    Call {
+        contract: String,
+        fn_name: String,
+        fn_args: String,
+    },
+    ContractBalance {
+        contract: String,
+        balance: u64,
+    },
+}
+
+impl TryFrom<OverrideJson> for StateOverride {
+    type Error = anyhow::Error;
+
+    fn try_from(value: OverrideJson) -> anyhow::Result<Self> {
+        Ok(match value {
+            OverrideJson::AccountBalance { account, balance } => {
+                let account = bs58::decode(account)
+                    .into_vec()
+                    .map_err(|_| anyhow::anyhow!("Invalid bs58 account"))?;
+                let account = BlsPublicKey::from_slice(&account)
+                    .map_err(|_| anyhow::anyhow!("Invalid bls account"))?;
+                StateOverride::AccountBalance { account, balance }
+            }
+            OverrideJson::Call {
+                contract,
+                fn_name,
+                fn_args,
+            } => StateOverride::Call(ContractCall {
+                contract: parse_contract_id(&contract)?,
+                fn_name,
+                fn_args: hex::decode(fn_args)?,
+            }),
+            OverrideJson::ContractBalance { contract, balance } => {
+                StateOverride::ContractBalance {
+                    contract: parse_contract_id(&contract)?,
+                    balance,
+                }
+            }
+        })
+    }
+}
+
 #[derive(Serialize)]
 struct Provisioner {
     key: String,
@@ -166,3 +303,76 @@ impl From<&StakeFundOwner> for StakeOwner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::SecretKey as BlsSecretKey;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const CONTRACT: &str =
+        "0200000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fn parse_state_overrides() {
+        let sk = BlsSecretKey::random(&mut StdRng::seed_from_u64(0xdead));
+        let account = BlsPublicKey::from(&sk);
+        let encoded = bs58::encode(account.to_bytes()).into_string();
+
+        let header = json!([
+            { "account": encoded, "balance": 42 },
+            { "contract": CONTRACT, "balance": 1 },
+            { "contract": CONTRACT, "fn_name": "set", "fn_args": "0102" },
+        ]);
+        let expected = vec![
+            StateOverride::AccountBalance {
+                account,
+                balance: 42,
+            },
+            StateOverride::ContractBalance {
+                contract: parse_contract_id(CONTRACT).unwrap(),
+                balance: 1,
+            },
+            StateOverride::Call(ContractCall {
+                contract: parse_contract_id(CONTRACT).unwrap(),
+                fn_name: "set".into(),
+                fn_args: vec![1, 2],
+            }),
+        ];
+
+        assert_eq!(parse_overrides(&header).unwrap(), expected);
+        // The header may also carry the JSON as a string
+        let header = serde_json::Value::String(header.to_string());
+        assert_eq!(parse_overrides(&header).unwrap(), expected);
+    }
+
+    #[test]
+    fn reject_malformed_overrides() {
+        let rejected = [
+            // not JSON
+            serde_json::Value::String("[{".into()),
+            // not an array
+            json!({ "contract": CONTRACT, "balance": 1 }),
+            // unknown override
+            json!([{ "balance": 1 }]),
+            // invalid account
+            json!([{ "account": "not-bs58", "balance": 1 }]),
+            json!([{ "account": "2NEpo7TZRRrLZSi2U", "balance": 1 }]),
+            // invalid contract IDs
+            json!([{ "contract": "zz", "balance": 1 }]),
+            json!([{ "contract": "0102", "balance": 1 }]),
+            json!([{ "contract": "02", "fn_name": "set", "fn_args": "" }]),
+            // invalid call arguments
+            json!([{ "contract": CONTRACT, "fn_name": "set", "fn_args": "z" }]),
+        ];
+
+        for header in rejected {
+            assert!(
+                parse_overrides(&header).is_err(),
+                "{header} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,366 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use hyper::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore};
+use tokio::task;
+use tracing::warn;
+
+use super::event::{DataType, RuesEvent};
+
+/// The header carrying the signature of the payload.
+const SIGNATURE_HEADER: &str = "Rusk-Signature";
+
+/// The number of times the delivery of an event is attempted.
+const DELIVERY_ATTEMPTS: u32 = 5;
+/// The time waited before retrying the delivery of an event for the first
+/// time. It doubles on every subsequent attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The time after which a delivery attempt is considered failed.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// The maximum number of deliveries in flight for a single webhook. Events
+/// received while a webhook is at capacity are dropped.
+const MAX_PENDING_DELIVERIES: usize = 64;
+
+/// A webhook, to which the events of a class are posted as they happen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpWebhook {
+    /// The URL the events are posted to.
+    pub url: String,
+    /// The class of the events posted.
+    pub event: WebhookEvent,
+    /// The secret the payloads are signed with, if any.
+    ///
+    /// The signature is the hex-encoded HMAC-SHA256 of the payload, sent in
+    /// the `Rusk-Signature` header as `sha256=<signature>`.
+    pub secret: Option<String>,
+}
+
+/// A class of events a webhook is notified of.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A block is finalized.
+    FinalizedBlock,
+    /// A Moonlight transaction sent or received by an account is executed.
+    AccountTransaction {
+        /// The base58-encoded public key of the account.
+        account: String,
+    },
+    /// A contract emits an event.
+    ContractEvent {
+        /// The hex-encoded id of the contract.
+        contract: String,
+        /// The topic of the events, or any topic if none is given.
+        topic: Option<String>,
+    },
+}
+
+impl WebhookEvent {
+    fn matches(&self, event: &RuesEvent) -> bool {
+        let uri = &event.uri;
+        match self {
+            Self::FinalizedBlock => {
+                uri.component == "blocks"
+                    && uri.topic == "statechange"
+                    && matches!(
+                        &event.data,
+                        DataType::Json(data) if data["state"] == "finalized"
+                    )
+            }
+            Self::AccountTransaction { account } => {
+                if uri.component != "transactions" || uri.topic != "executed" {
+                    return false;
+                }
+                let DataType::Json(data) = &event.data else {
+                    return false;
+                };
+                let tx = &data["inner"];
+                [&tx["sender"], &tx["receiver"]]
+                    .into_iter()
+                    .any(|pk| pk.as_str() == Some(account.as_str()))
+            }
+            Self::ContractEvent { contract, topic } => {
+                uri.component == "contracts"
+                    && uri
+                        .entity
+                        .as_ref()
+                        .is_some_and(|id| id.eq_ignore_ascii_case(contract))
+                    && topic.as_ref().map_or(true, |topic| topic == &uri.topic)
+            }
+        }
+    }
+}
+
+/// The payload posted to a webhook.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    component: &'a str,
+    entity: Option<&'a str>,
+    topic: &'a str,
+    /// The data of the event, hex-encoded if it is binary.
+    data: serde_json::Value,
+}
+
+impl<'a> From<&'a RuesEvent> for WebhookPayload<'a> {
+    fn from(event: &'a RuesEvent) -> Self {
+        let data = match &event.data {
+            DataType::Json(data) => data.clone(),
+            DataType::Binary(data) => hex::encode(&data.inner).into(),
+            DataType::Text(text) => text.clone().into(),
+            DataType::Channel(_) | DataType::None => serde_json::Value::Null,
+        };
+
+        Self {
+            component: &event.uri.component,
+            entity: event.uri.entity.as_deref(),
+            topic: &event.uri.topic,
+            data,
+        }
+    }
+}
+
+/// The hex-encoded HMAC-SHA256 of the payload with the given secret.
+fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC should take keys of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Posts the events emitted by the node to the webhooks notified of them.
+pub(crate) struct Webhooks {
+    hooks: Vec<Webhook>,
+    client: reqwest::Client,
+}
+
+/// A webhook along with the deliveries it has in flight.
+struct Webhook {
+    config: HttpWebhook,
+    pending: Arc<Semaphore>,
+    dropped: AtomicU64,
+}
+
+impl Webhook {
+    fn new(config: HttpWebhook) -> Self {
+        Self {
+            config,
+            pending: Arc::new(Semaphore::new(MAX_PENDING_DELIVERIES)),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Reserves a slot for a delivery, or counts it as dropped if the
+    /// webhook is at capacity.
+    fn reserve(&self) -> Option<OwnedSemaphorePermit> {
+        match self.pending.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                warn!(
+                    "Webhook {} at capacity, dropped {dropped} events so far",
+                    self.config.url
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Webhooks {
+    pub fn new(hooks: Vec<HttpWebhook>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let hooks = hooks.into_iter().map(Webhook::new).collect();
+        Self { hooks, client }
+    }
+
+    /// Deliver the received events to the webhooks until the events channel
+    /// is closed.
+    ///
+    /// Every delivery is retried with an exponential backoff, independently
+    /// from the others, so events may be delivered out of order. At most
+    /// [`MAX_PENDING_DELIVERIES`] deliveries are in flight for each webhook,
+    /// and the events exceeding it are dropped.
+    pub async fn run(self, mut events: broadcast::Receiver<RuesEvent>) {
+        if self.hooks.is_empty() {
+            return;
+        }
+
+        let webhooks = Arc::new(self);
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("Webhooks missed {missed} events");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            for (idx, hook) in webhooks.hooks.iter().enumerate() {
+                if !hook.config.event.matches(&event) {
+                    continue;
+                }
+                let Some(permit) = hook.reserve() else {
+                    continue;
+                };
+                let payload =
+                    match serde_json::to_vec(&WebhookPayload::from(&event)) {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            warn!("Failed serializing webhook payload: {e}");
+                            continue;
+                        }
+                    };
+
+                let webhooks = webhooks.clone();
+                task::spawn(async move {
+                    webhooks
+                        .deliver(&webhooks.hooks[idx].config, payload)
+                        .await;
+                    drop(permit);
+                });
+            }
+        }
+    }
+
+    async fn deliver(&self, hook: &HttpWebhook, payload: Vec<u8>) {
+        let signature = hook
+            .secret
+            .as_ref()
+            .map(|secret| format!("sha256={}", sign(secret, &payload)));
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=DELIVERY_ATTEMPTS {
+            let mut req = self
+                .client
+                .post(&hook.url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(payload.clone());
+            if let Some(signature) = &signature {
+                req = req.header(SIGNATURE_HEADER, signature);
+            }
+
+            let err = match req.send().await {
+                Ok(rsp) if rsp.status().is_success() => return,
+                Ok(rsp) => format!("status {}", rsp.status()),
+                Err(e) => e.to_string(),
+            };
+            warn!("Webhook {} attempt {attempt} failed: {err}", hook.url);
+
+            if attempt < DELIVERY_ATTEMPTS {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::http::event::RuesEventUri;
+
+    fn event(
+        component: &str,
+        entity: Option<&str>,
+        topic: &str,
+        data: DataType,
+    ) -> RuesEvent {
+        RuesEvent {
+            uri: RuesEventUri {
+                component: component.into(),
+                entity: entity.map(Into::into),
+                topic: topic.into(),
+            },
+            data,
+            headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn matching() {
+        let finalized = event(
+            "blocks",
+            Some("aa"),
+            "statechange",
+            DataType::Json(json!({ "state": "finalized", "atHeight": 1 })),
+        );
+        let confirmed = event(
+            "blocks",
+            Some("aa"),
+            "statechange",
+            DataType::Json(json!({ "state": "confirmed", "atHeight": 1 })),
+        );
+        assert!(WebhookEvent::FinalizedBlock.matches(&finalized));
+        assert!(!WebhookEvent::FinalizedBlock.matches(&confirmed));
+
+        let executed = event(
+            "transactions",
+            Some("bb"),
+            "executed",
+            DataType::Json(json!({
+                "inner": { "sender": "alice", "receiver": "bob" },
+            })),
+        );
+        let account = |account: &str| WebhookEvent::AccountTransaction {
+            account: account.into(),
+        };
+        assert!(account("bob").matches(&executed));
+        assert!(!account("carol").matches(&executed));
+
+        let emitted =
+            event("contracts", Some("cc"), "moonlight", DataType::None);
+        let contract = |topic: Option<&str>| WebhookEvent::ContractEvent {
+            contract: "CC".into(),
+            topic: topic.map(Into::into),
+        };
+        assert!(contract(None).matches(&emitted));
+        assert!(contract(Some("moonlight")).matches(&emitted));
+        assert!(!contract(Some("convert")).matches(&emitted));
+    }
+
+    #[test]
+    fn bounded_deliveries() {
+        let hook = Webhook::new(HttpWebhook {
+            url: "http://localhost".into(),
+            event: WebhookEvent::FinalizedBlock,
+            secret: None,
+        });
+
+        let permits: Vec<_> = (0..MAX_PENDING_DELIVERIES)
+            .map(|_| hook.reserve().expect("slot should be free"))
+            .collect();
+        assert!(hook.reserve().is_none());
+        assert!(hook.reserve().is_none());
+        assert_eq!(hook.dropped.load(Ordering::Relaxed), 2);
+
+        drop(permits);
+        assert!(hook.reserve().is_some());
+        assert_eq!(hook.dropped.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn signature() {
+        // Test case 2 of RFC 4231
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -19,9 +19,11 @@ use node::network::Kadcast;
 use node::LongLivedService;
 use parking_lot::RwLock;
 use tokio::sync::broadcast;
-pub use vm::RuskVmConfig;
+pub use vm::{
+    DeployDepositConfig, DeployPolicyConfig, RuskVmConfig, StateOverride,
+};
 
-use crate::http::RuesEvent;
+use crate::http::{HttpCache, LookupCache, RuesEvent};
 pub(crate) use events::ChainEventStreamer;
 #[cfg(feature = "archive")]
 use {
@@ -56,6 +58,7 @@ pub struct RuskNode {
     inner: node::Node<Kadcast<255>, Backend, Rusk>,
     #[cfg(feature = "archive")]
     archive: Archive,
+    lookups: Arc<LookupCache>,
 }
 
 impl RuskNode {
@@ -67,9 +70,16 @@ impl RuskNode {
             inner,
             #[cfg(feature = "archive")]
             archive,
+            lookups: Arc::new(LookupCache::new(HttpCache::default())),
         }
     }
 
+    /// Set the limits of the cache of block and transaction lookups.
+    pub fn with_lookup_cache(mut self, limits: HttpCache) -> Self {
+        self.lookups = Arc::new(LookupCache::new(limits));
+        self
+    }
+
     #[cfg(feature = "archive")]
     pub fn with_archive(mut self, archive: Archive) -> Self {
         self.archive = archive;
@@ -94,6 +104,10 @@ impl RuskNode {
     pub fn inner(&self) -> &node::Node<Kadcast<255>, Backend, Rusk> {
         &self.inner
     }
+
+    pub(crate) fn lookups(&self) -> Arc<LookupCache> {
+        self.lookups.clone()
+    }
 }
 
 /// Calculates the value that the coinbase notes should contain.
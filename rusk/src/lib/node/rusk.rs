@@ -16,24 +16,28 @@ use dusk_consensus::config::{
     RATIFICATION_COMMITTEE_CREDITS, VALIDATION_COMMITTEE_CREDITS,
 };
 use dusk_consensus::operations::{CallParams, VerificationOutput, Voter};
-use dusk_core::abi::Event;
+use dusk_core::abi::{ContractError, ContractId, Event, SEED_BYTES};
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::stake::{
     Reward, RewardReason, StakeData, StakeKeys, STAKE_CONTRACT,
 };
+use dusk_core::transfer::data::ContractDeploy;
 use dusk_core::transfer::{
-    moonlight::AccountData, PANIC_NONCE_NOT_READY, TRANSFER_CONTRACT,
+    moonlight::AccountData, Transaction as ProtocolTransaction,
+    PANIC_NONCE_NOT_READY, TRANSFER_CONTRACT,
 };
 use dusk_core::{BlsScalar, Dusk};
 use dusk_vm::{
-    execute, CallReceipt, Error as VMError, ExecutionConfig, Session, VM,
+    execute, execute_upgrade, gen_contract_id, BlockData, BlockMetrics,
+    CallReceipt, Checkpoint, Error as VMError, ExecutionConfig, Session,
+    EXECUTION_TIME_EXCEEDED, VM,
 };
 use node_data::events::contract::{ContractEvent, ContractTxEvent};
 use node_data::ledger::{Hash, Slash, SpentTransaction, Transaction};
 use parking_lot::RwLock;
 use rusk_profile::to_rusk_state_id_path;
 use tokio::sync::broadcast;
-use tracing::info;
+use tracing::{info, warn};
 
 #[cfg(feature = "archive")]
 use {node_data::archive::ArchivalData, tokio::sync::mpsc::Sender};
@@ -99,8 +103,12 @@ impl Rusk {
         &self,
         params: &CallParams,
         txs: I,
-    ) -> Result<(Vec<SpentTransaction>, Vec<Transaction>, VerificationOutput)>
-    {
+    ) -> Result<(
+        Vec<SpentTransaction>,
+        Vec<Transaction>,
+        VerificationOutput,
+        BlockMetrics,
+    )> {
         let started = Instant::now();
 
         let block_height = params.round;
@@ -108,11 +116,21 @@ impl Rusk {
         let generator = params.generator_pubkey.inner();
         let to_slash = params.to_slash.clone();
         let prev_state_root = params.prev_state_root;
+        let block = BlockData {
+            height: block_height,
+            seed: *params.prev_seed.inner(),
+            timestamp: params.timestamp,
+            generator: Some(*generator),
+        };
 
         let voters = &params.voters_pubkey[..];
 
-        let mut session =
-            self.new_block_session(block_height, prev_state_root)?;
+        // Declared before the session, so that the checkpoints are released
+        // only after the session built on top of them is dropped, even on an
+        // early return.
+        let mut checkpoints = CheckpointGuard::new(&self.vm);
+
+        let mut session = self.new_block_session(block, prev_state_root)?;
 
         let mut block_gas_left = block_gas_limit;
 
@@ -123,7 +141,15 @@ impl Rusk {
 
         let mut event_bloom = Bloom::new();
 
+        let mut metrics = BlockMetrics::default();
+
         let execution_config = self.vm_config.to_execution_config(block_height);
+        // The transactions taking too long are aborted only while generating,
+        // and replayed without any limit.
+        let generation_config = ExecutionConfig {
+            execution_time_limit: self.vm_config.execution_time_limit,
+            ..execution_config.clone()
+        };
 
         // We always write the faults len in a u32
         let mut size_left = params.max_txs_bytes - u32::SIZE;
@@ -155,32 +181,61 @@ impl Rusk {
                 continue;
             }
 
-            match execute(&mut session, &unspent_tx.inner, &execution_config) {
+            // A transaction can only go over the block gas limit if its gas
+            // limit is greater than the gas left in the block. Only in this
+            // case, or when an upgrade may lose the session, we create a
+            // checkpoint to cheaply roll it back.
+            let checkpoint = if unspent_tx.inner.gas_limit() > block_gas_left
+                || unspent_tx.inner.upgrade().is_some()
+            {
+                let (s, checkpoint) =
+                    self.vm.checkpoint(session, self.chain_id, block)?;
+                session = s;
+                checkpoints.push(checkpoint);
+                Some(checkpoint)
+            } else {
+                None
+            };
+
+            let (s, result) =
+                execute_tx(session, &unspent_tx.inner, &generation_config);
+            session = match (s, checkpoint) {
+                (Some(s), _) => s,
+                (None, Some(checkpoint)) => self.vm.rollback_to(&checkpoint)?,
+                (None, None) => self.replay_block_session(
+                    block,
+                    prev_state_root,
+                    &spent_txs,
+                    &execution_config,
+                )?,
+            };
+
+            match result {
                 Ok(receipt) => {
                     let gas_spent = receipt.gas_spent;
 
                     // If the transaction went over the block gas limit we
-                    // re-execute all spent transactions. We don't discard the
-                    // transaction, since it is technically valid.
+                    // roll back to the state before its execution. We don't
+                    // discard the transaction, since it is technically valid.
                     if gas_spent > block_gas_left {
                         info!("Skipping {tx_id_hex} due gas_spent {gas_spent} greater than left: {block_gas_left}");
-                        session = self
-                            .new_block_session(block_height, prev_state_root)?;
-
-                        for spent_tx in &spent_txs {
-                            // We know these transactions were correctly
-                            // executed before, so we don't bother checking.
-                            let _ = execute(
-                                &mut session,
-                                &spent_tx.inner.inner,
+                        session = match checkpoint {
+                            Some(checkpoint) => {
+                                self.vm.rollback_to(&checkpoint)?
+                            }
+                            None => self.replay_block_session(
+                                block,
+                                prev_state_root,
+                                &spent_txs,
                                 &execution_config,
-                            );
-                        }
+                            )?,
+                        };
 
                         continue;
                     }
 
                     size_left -= tx_len;
+                    metrics.record(&receipt);
 
                     // We're currently ignoring the result of successful calls
                     let err = receipt.data.err().map(|e| format!("{e}"));
@@ -198,6 +253,24 @@ impl Rusk {
                         err,
                     });
                 }
+                Err(VMError::Panic(val)) if val == EXECUTION_TIME_EXCEEDED => {
+                    // A transaction taking too long to execute leaves its
+                    // changes in the session, so we roll back to the state
+                    // before its execution. We only skip it for this block,
+                    // since the time taken depends on this node.
+                    info!(
+                        "Skipping {tx_id_hex} due to {EXECUTION_TIME_EXCEEDED}"
+                    );
+                    session = match checkpoint {
+                        Some(checkpoint) => self.vm.rollback_to(&checkpoint)?,
+                        None => self.replay_block_session(
+                            block,
+                            prev_state_root,
+                            &spent_txs,
+                            &execution_config,
+                        )?,
+                    };
+                }
                 Err(VMError::Panic(val)) if val == PANIC_NONCE_NOT_READY => {
                     // If the transaction panic due to a not yet valid nonce,
                     // we should not discard the transactions since it can be
@@ -228,6 +301,11 @@ impl Rusk {
 
         let state_root = session.root();
 
+        // The checkpoints can only be released once the session built on top
+        // of them is dropped.
+        drop(session);
+        drop(checkpoints);
+
         Ok((
             spent_txs,
             discarded_txs,
@@ -235,6 +313,7 @@ impl Rusk {
                 state_root,
                 event_bloom: event_bloom.into(),
             },
+            metrics,
         ))
     }
 
@@ -243,7 +322,9 @@ impl Rusk {
     pub fn verify_transactions(
         &self,
         prev_commit: [u8; 32],
+        prev_seed: [u8; SEED_BYTES],
         block_height: u64,
+        block_timestamp: u64,
         block_hash: Hash,
         block_gas_limit: u64,
         generator: &BlsPublicKey,
@@ -251,7 +332,13 @@ impl Rusk {
         slashing: Vec<Slash>,
         voters: &[Voter],
     ) -> Result<(Vec<SpentTransaction>, VerificationOutput)> {
-        let session = self.new_block_session(block_height, prev_commit)?;
+        let block = BlockData {
+            height: block_height,
+            seed: prev_seed,
+            timestamp: block_timestamp,
+            generator: Some(*generator),
+        };
+        let session = self.new_block_session(block, prev_commit)?;
         let execution_config = self.vm_config.to_execution_config(block_height);
 
         accept(
@@ -265,7 +352,7 @@ impl Rusk {
             voters,
             &execution_config,
         )
-        .map(|(a, b, _, _)| (a, b))
+        .map(|(a, b, _, _, _)| (a, b))
     }
 
     /// Accept the given transactions.
@@ -277,7 +364,9 @@ impl Rusk {
     pub fn accept_transactions(
         &self,
         prev_commit: [u8; 32],
+        prev_seed: [u8; SEED_BYTES],
         block_height: u64,
+        block_timestamp: u64,
         block_gas_limit: u64,
         block_hash: Hash,
         generator: BlsPublicKey,
@@ -289,22 +378,30 @@ impl Rusk {
         Vec<SpentTransaction>,
         VerificationOutput,
         Vec<ContractEvent>,
+        BlockMetrics,
     )> {
-        let session = self.new_block_session(block_height, prev_commit)?;
+        let block = BlockData {
+            height: block_height,
+            seed: prev_seed,
+            timestamp: block_timestamp,
+            generator: Some(generator),
+        };
+        let session = self.new_block_session(block, prev_commit)?;
 
         let execution_config = self.vm_config.to_execution_config(block_height);
 
-        let (spent_txs, verification_output, session, events) = accept(
-            session,
-            block_height,
-            block_hash,
-            block_gas_limit,
-            &generator,
-            &txs[..],
-            slashing,
-            voters,
-            &execution_config,
-        )?;
+        let (spent_txs, verification_output, session, events, metrics) =
+            accept(
+                session,
+                block_height,
+                block_hash,
+                block_gas_limit,
+                &generator,
+                &txs[..],
+                slashing,
+                voters,
+                &execution_config,
+            )?;
 
         if let Some(expected_verification) = consistency_check {
             if expected_verification != verification_output {
@@ -338,7 +435,7 @@ impl Rusk {
             let _ = self.event_sender.send(event);
         }
 
-        Ok((spent_txs, verification_output, stake_events))
+        Ok((spent_txs, verification_output, stake_events, metrics))
     }
 
     pub fn finalize_state(
@@ -402,6 +499,27 @@ impl Rusk {
         }))
     }
 
+    /// Returns the stakes as weighted in the consensus, with the value
+    /// delegated to each provisioner added to its stake.
+    pub fn weighted_provisioners(
+        &self,
+        base_commit: Option<[u8; 32]>,
+    ) -> Result<impl Iterator<Item = (StakeKeys, StakeData)>> {
+        let (sender, receiver) = mpsc::channel();
+        self.feeder_query(
+            STAKE_CONTRACT,
+            "weighted_stakes",
+            &(),
+            sender,
+            base_commit,
+        )?;
+        Ok(receiver.into_iter().map(|bytes| {
+            rkyv::from_bytes::<(StakeKeys, StakeData)>(&bytes).expect(
+                "The contract should only return (StakeKeys, StakeData) tuples",
+            )
+        }))
+    }
+
     /// Returns an account's information.
     pub fn account(&self, pk: &BlsPublicKey) -> Result<AccountData> {
         self.query(TRANSFER_CONTRACT, "account", pk)
@@ -451,16 +569,94 @@ impl Rusk {
         self.query(STAKE_CONTRACT, "get_stake", pk)
     }
 
+    /// Simulates the execution of a transaction in a block at the given
+    /// height, on top of the current tip.
+    ///
+    /// The state changes of the transaction are discarded.
+    pub fn simulate_transaction(
+        &self,
+        tx: &ProtocolTransaction,
+        block_height: u64,
+    ) -> Result<CallReceipt<Result<Vec<u8>, ContractError>>> {
+        let commit = self.tip.read().current;
+        let block = BlockData::at_height(block_height);
+        let session = self.new_block_session(block, commit)?;
+
+        let execution_config = self.vm_config.to_execution_config(block_height);
+        let (_, receipt) = execute_tx(session, tx, &execution_config);
+
+        Ok(receipt?)
+    }
+
+    /// Dry-runs the deployment of a contract in a block at the given height,
+    /// on top of the current tip, returning the id it would be deployed at and
+    /// the gas its initialization needs.
+    ///
+    /// The contract is deployed and initialized in sessions that are then
+    /// discarded. The initialization may spend at most the block gas limit.
+    pub fn dry_run_deploy(
+        &self,
+        deploy: &ContractDeploy,
+        block_height: u64,
+    ) -> Result<(ContractId, u64)> {
+        let commit = self.tip.read().current;
+        let block = BlockData::at_height(block_height);
+        let contract_id = gen_contract_id(
+            &deploy.bytecode.bytes,
+            deploy.nonce,
+            &deploy.owner,
+        );
+
+        let mut hi = self.vm_config.block_gas_limit;
+        self.try_deploy(deploy, contract_id, block, commit, hi)?;
+
+        // The VM doesn't report the gas spent by the initialization, so we
+        // search for the smallest gas limit it succeeds with, to a precision
+        // of about 1.5%.
+        let mut lo = 0;
+        while hi - lo > hi / 64 {
+            let mid = lo + (hi - lo) / 2;
+            match self.try_deploy(deploy, contract_id, block, commit, mid) {
+                Ok(()) => hi = mid,
+                Err(Error::Vm(_)) => lo = mid,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((contract_id, hi))
+    }
+
+    /// Deploys a contract in a new block session on top of the given commit,
+    /// discarding the session afterwards.
+    fn try_deploy(
+        &self,
+        deploy: &ContractDeploy,
+        contract_id: ContractId,
+        block: BlockData,
+        commit: [u8; 32],
+        gas_limit: u64,
+    ) -> Result<()> {
+        let mut session = self.new_block_session(block, commit)?;
+        session.deploy_raw(
+            Some(contract_id),
+            deploy.bytecode.bytes.as_slice(),
+            deploy.init_args.clone(),
+            deploy.owner.clone(),
+            gas_limit,
+        )?;
+        Ok(())
+    }
+
     /// Opens a session for a new block proposal/verification.
     ///
     /// Before returning the session, "before_state_transition" of Stake
     /// Contract is called
     pub(crate) fn new_block_session(
         &self,
-        block_height: u64,
+        block: BlockData,
         commit: [u8; 32],
     ) -> Result<Session> {
-        let mut session = self._session(block_height, None)?;
+        let mut session = self._session(block, None)?;
         if session.root() != commit {
             return Err(Error::TipChanged);
         }
@@ -470,16 +666,40 @@ impl Rusk {
         Ok(session)
     }
 
-    /// Opens a session for query, setting a block height of zero since this
-    /// doesn't affect the result.
+    /// Opens a new block session and re-executes the given spent transactions
+    /// on top of it.
+    fn replay_block_session(
+        &self,
+        block: BlockData,
+        commit: [u8; 32],
+        spent_txs: &[SpentTransaction],
+        execution_config: &ExecutionConfig,
+    ) -> Result<Session> {
+        let mut session = self.new_block_session(block, commit)?;
+
+        for spent_tx in spent_txs {
+            // We know these transactions were correctly executed before, so we
+            // don't bother checking.
+            let (s, _) =
+                execute_tx(session, &spent_tx.inner.inner, execution_config);
+            session = s.ok_or_else(|| {
+                VMError::Panic("replayed upgrade lost the session".into())
+            })?;
+        }
+
+        Ok(session)
+    }
+
+    /// Opens a session for query, setting a block height of zero and no
+    /// block data since these don't affect the result.
     pub(crate) fn query_session(
         &self,
         commit: Option<[u8; 32]>,
     ) -> Result<Session> {
-        self._session(0, commit)
+        self._session(BlockData::at_height(0), commit)
     }
 
-    /// Opens a new session with the specified block height and commit hash.
+    /// Opens a new session with the specified block data and commit hash.
     ///
     /// # Warning
     /// This is a low-level function intended for internal use only.
@@ -491,8 +711,7 @@ impl Rusk {
     /// `query_session` to ensure correct behavior and consistency.
     ///
     /// # Parameters
-    /// - `block_height`: The height of the block for which the session is
-    ///   created.
+    /// - `block`: The data of the block for which the session is created.
     /// - `commit`: The optional commit hash. If not provided, the current tip
     ///   is used.
     ///
@@ -505,7 +724,7 @@ impl Rusk {
     ///   given parameters.
     fn _session(
         &self,
-        block_height: u64,
+        block: BlockData,
         commit: Option<[u8; 32]>,
     ) -> Result<Session> {
         let commit = commit.unwrap_or_else(|| {
@@ -513,7 +732,7 @@ impl Rusk {
             tip.current
         });
 
-        let session = self.vm.session(commit, self.chain_id, block_height)?;
+        let session = self.vm.block_session(commit, self.chain_id, block)?;
 
         Ok(session)
     }
@@ -541,6 +760,29 @@ impl Rusk {
     }
 }
 
+/// Executes a transaction, dispatching contract upgrades to
+/// [`execute_upgrade`].
+///
+/// Returns the session along with the result of the execution, unless it was
+/// lost by a failing upgrade. In that case the state must be rolled back.
+fn execute_tx(
+    mut session: Session,
+    tx: &ProtocolTransaction,
+    config: &ExecutionConfig,
+) -> (
+    Option<Session>,
+    Result<CallReceipt<Result<Vec<u8>, ContractError>>, VMError>,
+) {
+    if tx.upgrade().is_none() {
+        let result = execute(&mut session, tx, config);
+        return (Some(session), result);
+    }
+    match execute_upgrade(session, tx, config) {
+        Ok((session, receipt)) => (Some(session), Ok(receipt)),
+        Err(err) => (None, Err(err)),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn accept(
     session: Session,
@@ -557,7 +799,15 @@ fn accept(
     VerificationOutput,
     Session,
     Vec<ContractTxEvent>,
+    BlockMetrics,
 )> {
+    // The time taken differs between nodes, so it must never decide the
+    // outcome of a block being verified or accepted.
+    debug_assert!(
+        execution_config.execution_time_limit.is_none(),
+        "blocks are never executed with a time limit"
+    );
+
     let mut session = session;
 
     let mut block_gas_left = block_gas_limit;
@@ -568,10 +818,16 @@ fn accept(
     let mut events = Vec::new();
     let mut event_bloom = Bloom::new();
 
+    let mut metrics = BlockMetrics::default();
+
     for unspent_tx in txs {
         let tx = &unspent_tx.inner;
         let tx_id = unspent_tx.id();
-        let receipt = execute(&mut session, tx, execution_config)?;
+        let (s, receipt) = execute_tx(session, tx, execution_config);
+        let receipt = receipt?;
+        session = s.expect("session to be kept by an executed transaction");
+
+        metrics.record(&receipt);
 
         event_bloom.add_events(&receipt.events);
 
@@ -632,6 +888,7 @@ fn accept(
         },
         session,
         events,
+        metrics,
     ))
 }
 
@@ -775,3 +1032,32 @@ fn slash(session: &mut Session, slash: Vec<Slash>) -> Result<Vec<Event>> {
     }
     Ok(events)
 }
+
+/// Releases the checkpoints created while executing a block when dropped.
+struct CheckpointGuard<'a> {
+    vm: &'a VM,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl<'a> CheckpointGuard<'a> {
+    fn new(vm: &'a VM) -> Self {
+        Self {
+            vm,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, checkpoint: Checkpoint) {
+        self.checkpoints.push(checkpoint);
+    }
+}
+
+impl Drop for CheckpointGuard<'_> {
+    fn drop(&mut self) {
+        for checkpoint in self.checkpoints.drain(..) {
+            if let Err(err) = self.vm.release_checkpoint(checkpoint) {
+                warn!("Failed to release checkpoint: {err}");
+            }
+        }
+    }
+}
@@ -19,12 +19,21 @@ use dusk_core::{
     signatures::bls::PublicKey as BlsPublicKey, stake::StakeData,
     transfer::Transaction as ProtocolTransaction,
 };
-use node::vm::{PreverificationResult, VMExecution};
+use dusk_vm::BlockMetrics;
+use node::vm::{
+    ContractMetrics, ExecutionMetrics, PreverificationResult, VMExecution,
+};
 use node_data::bls::PublicKey;
-use node_data::ledger::{Block, Slash, SpentTransaction, Transaction};
+use node_data::ledger::{Block, Seed, Slash, SpentTransaction, Transaction};
 
 use super::Rusk;
-pub use config::Config as RuskVmConfig;
+pub use config::{
+    Config as RuskVmConfig, DeployDepositConfig, DeployPolicyConfig,
+};
+pub use query::StateOverride;
+
+/// Number of top gas consuming contracts logged for each executed block.
+const TOP_CONSUMERS: usize = 5;
 
 impl VMExecution for Rusk {
     fn execute_state_transition<I: Iterator<Item = Transaction>>(
@@ -38,17 +47,20 @@ impl VMExecution for Rusk {
     )> {
         info!("Received execute_state_transition request");
 
-        let (txs, discarded_txs, verification_output) =
+        let (txs, discarded_txs, verification_output, metrics) =
             self.execute_transactions(params, txs).map_err(|inner| {
                 anyhow::anyhow!("Cannot execute txs: {inner}!!")
             })?;
 
+        log_top_consumers(&metrics);
+
         Ok((txs, discarded_txs, verification_output))
     }
 
     fn verify_state_transition(
         &self,
         prev_commit: [u8; 32],
+        prev_seed: Seed,
         blk: &Block,
         voters: &[Voter],
     ) -> Result<VerificationOutput, VstError> {
@@ -63,7 +75,9 @@ impl VMExecution for Rusk {
         let (_, verification_output) = self
             .verify_transactions(
                 prev_commit,
+                *prev_seed.inner(),
                 blk.header().height,
+                blk.header().timestamp,
                 blk.header().hash,
                 blk.header().gas_limit,
                 &generator,
@@ -85,12 +99,14 @@ impl VMExecution for Rusk {
     fn accept(
         &self,
         prev_root: [u8; 32],
+        prev_seed: Seed,
         blk: &Block,
         voters: &[Voter],
     ) -> anyhow::Result<(
         Vec<SpentTransaction>,
         VerificationOutput,
         Vec<ContractEvent>,
+        ExecutionMetrics,
     )> {
         debug!("Received accept request");
         let generator = blk.header().generator_bls_pubkey;
@@ -99,10 +115,12 @@ impl VMExecution for Rusk {
 
         let slashing = Slash::from_block(blk)?;
 
-        let (txs, verification_output, stake_events) = self
+        let (txs, verification_output, stake_events, metrics) = self
             .accept_transactions(
                 prev_root,
+                *prev_seed.inner(),
                 blk.header().height,
+                blk.header().timestamp,
                 blk.header().gas_limit,
                 blk.header().hash,
                 generator,
@@ -116,7 +134,24 @@ impl VMExecution for Rusk {
             )
             .map_err(|inner| anyhow::anyhow!("Cannot accept txs: {inner}!!"))?;
 
-        Ok((txs, verification_output, stake_events))
+        log_top_consumers(&metrics);
+
+        let metrics = ExecutionMetrics {
+            gas_spent: metrics.gas_spent,
+            transactions: metrics.transactions,
+            max_calls: metrics.max_calls,
+            top_consumers: metrics
+                .top_consumers(TOP_CONSUMERS)
+                .into_iter()
+                .map(|(contract, contract_metrics)| ContractMetrics {
+                    contract,
+                    gas_spent: contract_metrics.gas_spent,
+                    calls: contract_metrics.calls,
+                })
+                .collect(),
+        };
+
+        Ok((txs, verification_output, stake_events, metrics))
     }
 
     fn move_to_commit(&self, commit: [u8; 32]) -> anyhow::Result<()> {
@@ -217,6 +252,25 @@ impl VMExecution for Rusk {
         }
     }
 
+    fn is_stale(&self, tx: &Transaction) -> anyhow::Result<bool> {
+        match &tx.inner {
+            ProtocolTransaction::Phoenix(tx) => {
+                let tx_nullifiers = tx.nullifiers().to_vec();
+                let existing_nullifiers =
+                    self.existing_nullifiers(&tx_nullifiers).map_err(|e| {
+                        anyhow::anyhow!("Cannot check nullifiers: {e}")
+                    })?;
+                Ok(!existing_nullifiers.is_empty())
+            }
+            ProtocolTransaction::Moonlight(tx) => {
+                let account_data = self.account(tx.sender()).map_err(|e| {
+                    anyhow::anyhow!("Cannot check account: {e}")
+                })?;
+                Ok(tx.nonce() <= account_data.nonce)
+            }
+        }
+    }
+
     fn get_provisioners(
         &self,
         base_commit: [u8; 32],
@@ -296,6 +350,18 @@ where
     iter.into_iter().all(move |x| uniq.insert(x))
 }
 
+/// Logs the contracts that spent the most gas in an executed block.
+fn log_top_consumers(metrics: &BlockMetrics) {
+    for (contract, contract_metrics) in metrics.top_consumers(TOP_CONSUMERS) {
+        debug!(
+            event = "top gas consumer",
+            contract = hex::encode(contract.as_bytes()),
+            gas_spent = contract_metrics.gas_spent,
+            calls = contract_metrics.calls,
+        );
+    }
+}
+
 impl Rusk {
     fn query_provisioners(
         &self,
@@ -303,7 +369,7 @@ impl Rusk {
     ) -> anyhow::Result<Provisioners> {
         info!("Received get_provisioners request");
         let provisioners = self
-            .provisioners(base_commit)
+            .weighted_provisioners(base_commit)
             .map_err(|e| anyhow::anyhow!("Cannot get provisioners {e}"))?
             .map(|(pk, stake)| {
                 (PublicKey::new(pk.account), Self::to_stake(stake))
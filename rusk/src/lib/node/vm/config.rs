@@ -7,7 +7,7 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
-use dusk_vm::ExecutionConfig;
+use dusk_vm::{DeployDeposit, DeployPolicy, ExecutionConfig};
 use serde::{Deserialize, Serialize};
 
 const fn default_gas_per_deploy_byte() -> u64 {
@@ -48,8 +48,88 @@ pub struct Config {
     #[serde(default)]
     pub generation_timeout: Option<Duration>,
 
+    /// The maximum time a transaction may take to execute in a candidate
+    /// block, regardless of the gas it spends. Slower transactions are left
+    /// out of the block, but kept for the next ones.
+    #[serde(with = "humantime_serde")]
+    #[serde(default)]
+    pub execution_time_limit: Option<Duration>,
+
     /// Set of features to activate
     pub features: HashMap<String, u64>,
+
+    /// Restrictions on contract deployments, for permissioned networks, from
+    /// the activation of the `DEPLOY_POLICY` feature.
+    #[serde(default)]
+    pub deploy_policy: DeployPolicyConfig,
+
+    /// The deposit escrowed for the state a deployed contract occupies, from
+    /// the activation of the `DEPLOY_DEPOSIT` feature.
+    #[serde(default)]
+    pub deploy_deposit: DeployDepositConfig,
+}
+
+/// Restrictions on the contract deployments accepted by the node.
+///
+/// They only apply once the `DEPLOY_POLICY` feature is active, and by default
+/// every deployment is accepted.
+#[serde_with::serde_as]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeployPolicyConfig {
+    /// The hex-encoded owners allowed to deploy contracts. If not set, any
+    /// owner is allowed to deploy.
+    #[serde_as(as = "Option<Vec<serde_with::hex::Hex>>")]
+    #[serde(default)]
+    pub allowed_owners: Option<Vec<Vec<u8>>>,
+
+    /// The minimum deposit a deployment transaction must carry.
+    #[serde(default)]
+    pub min_deposit: u64,
+
+    /// The maximum size, in bytes, of the deployed bytecode.
+    #[serde(default)]
+    pub max_bytecode_size: Option<usize>,
+
+    /// The imports a deployed contract may not use, either as `name` or as
+    /// `module.name`.
+    #[serde(default)]
+    pub banned_imports: Vec<String>,
+}
+
+impl From<&DeployPolicyConfig> for DeployPolicy {
+    fn from(config: &DeployPolicyConfig) -> Self {
+        DeployPolicy {
+            allowed_owners: config.allowed_owners.clone(),
+            min_deposit: config.min_deposit,
+            max_bytecode_size: config.max_bytecode_size,
+            banned_imports: config.banned_imports.clone(),
+        }
+    }
+}
+
+/// The deposit escrowed on deployment for the state a contract occupies, on
+/// top of the deploy charge.
+///
+/// It only applies once the `DEPLOY_DEPOSIT` feature is active, and by default
+/// no deposit is required.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DeployDepositConfig {
+    /// The gas charged for each byte of the bytecode.
+    #[serde(default)]
+    pub gas_per_byte: u64,
+
+    /// The gas charged for each 64KiB page of initial memory.
+    #[serde(default)]
+    pub gas_per_memory_page: u64,
+}
+
+impl From<&DeployDepositConfig> for DeployDeposit {
+    fn from(config: &DeployDepositConfig) -> Self {
+        DeployDeposit {
+            gas_per_byte: config.gas_per_byte,
+            gas_per_memory_page: config.gas_per_memory_page,
+        }
+    }
 }
 
 impl Default for Config {
@@ -66,7 +146,10 @@ impl Config {
             min_deploy_points: default_min_deploy_points(),
             block_gas_limit: default_block_gas_limit(),
             generation_timeout: None,
+            execution_time_limit: None,
             features: HashMap::new(),
+            deploy_policy: DeployPolicyConfig::default(),
+            deploy_deposit: DeployDepositConfig::default(),
         }
     }
 
@@ -113,17 +196,81 @@ impl Config {
         self
     }
 
+    /// Set the maximum time a transaction may take to execute in a candidate
+    /// block.
+    pub const fn with_execution_time_limit(
+        mut self,
+        execution_time_limit: Option<Duration>,
+    ) -> Self {
+        self.execution_time_limit = execution_time_limit;
+        self
+    }
+
+    /// Set the restrictions on contract deployments.
+    pub fn with_deploy_policy(
+        mut self,
+        deploy_policy: DeployPolicyConfig,
+    ) -> Self {
+        self.deploy_policy = deploy_policy;
+        self
+    }
+
+    /// Set the deposit escrowed for the state a deployed contract occupies.
+    pub const fn with_deploy_deposit(
+        mut self,
+        deploy_deposit: DeployDepositConfig,
+    ) -> Self {
+        self.deploy_deposit = deploy_deposit;
+        self
+    }
+
     /// Create a new `Config` with the given parameters.
     pub fn to_execution_config(&self, block_height: u64) -> ExecutionConfig {
         let with_public_sender = self
             .feature("ABI_PUBLIC_SENDER")
             .map(|activation| activation >= block_height)
             .unwrap_or_default();
+        let with_bytecode_validation = self
+            .feature("BYTECODE_VALIDATION")
+            .map(|activation| block_height >= activation)
+            .unwrap_or_default();
+        let with_event_subscriptions = self
+            .feature("EVENT_SUBSCRIPTIONS")
+            .map(|activation| block_height >= activation)
+            .unwrap_or_default();
+        let with_tagged_memos = self
+            .feature("TAGGED_MEMOS")
+            .map(|activation| block_height >= activation)
+            .unwrap_or_default();
+        let deploy_policy = match self
+            .feature("DEPLOY_POLICY")
+            .map(|activation| block_height >= activation)
+            .unwrap_or_default()
+        {
+            true => (&self.deploy_policy).into(),
+            false => DeployPolicy::ALLOW_ALL,
+        };
+        let deploy_deposit = match self
+            .feature("DEPLOY_DEPOSIT")
+            .map(|activation| block_height >= activation)
+            .unwrap_or_default()
+        {
+            true => (&self.deploy_deposit).into(),
+            false => DeployDeposit::NONE,
+        };
         ExecutionConfig {
             gas_per_deploy_byte: self.gas_per_deploy_byte,
             min_deploy_points: self.min_deploy_points,
             min_deploy_gas_price: self.min_deployment_gas_price,
             with_public_sender,
+            with_bytecode_validation,
+            with_event_subscriptions,
+            with_tagged_memos,
+            deploy_policy,
+            deploy_deposit,
+            // Only set when generating a block, since the time taken differs
+            // between nodes
+            execution_time_limit: None,
         }
     }
 
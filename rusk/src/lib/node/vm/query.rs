@@ -5,14 +5,111 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::node::Rusk;
-use crate::Result;
+use crate::{Error, Result};
 
 use std::sync::mpsc;
+use std::thread;
 
 use bytecheck::CheckBytes;
 use dusk_core::abi::{ContractId, StandardBufSerializer};
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use dusk_vm::{Error as VMError, Session};
 use rkyv::validation::validators::DefaultValidator;
 use rkyv::{Archive, Deserialize, Infallible, Serialize};
+use tokio::sync::mpsc as tokio_mpsc;
+
+/// A temporary modification of the state, applied to the session of a dry-run
+/// query before the query is executed.
+///
+/// Overrides are never committed, they only live for the duration of the
+/// query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateOverride {
+    /// Sets the balance of a public account.
+    AccountBalance {
+        account: AccountPublicKey,
+        balance: u64,
+    },
+    /// Sets the balance of a contract.
+    ///
+    /// Since the transfer contract only allows the stake contract to lower a
+    /// contract's balance, the balance can only be raised.
+    ContractBalance { contract: ContractId, balance: u64 },
+    /// Performs an arbitrary call, e.g. to a setter of the contract state.
+    Call(ContractCall),
+}
+
+impl StateOverride {
+    fn apply(&self, session: &mut Session) -> Result<()> {
+        match self {
+            StateOverride::AccountBalance { account, balance } => {
+                let current = session
+                    .call::<_, AccountData>(
+                        TRANSFER_CONTRACT,
+                        "account",
+                        account,
+                        u64::MAX,
+                    )?
+                    .data
+                    .balance;
+
+                if *balance > current {
+                    session.call::<_, ()>(
+                        TRANSFER_CONTRACT,
+                        "add_account_balance",
+                        &(*account, balance - current),
+                        u64::MAX,
+                    )?;
+                } else if *balance < current {
+                    session.call::<_, ()>(
+                        TRANSFER_CONTRACT,
+                        "sub_account_balance",
+                        &(*account, current - balance),
+                        u64::MAX,
+                    )?;
+                }
+            }
+            StateOverride::ContractBalance { contract, balance } => {
+                let current = session
+                    .call::<_, u64>(
+                        TRANSFER_CONTRACT,
+                        "contract_balance",
+                        contract,
+                        u64::MAX,
+                    )?
+                    .data;
+
+                if *balance < current {
+                    return Err(Error::InvalidOverride(format!(
+                        "cannot lower the balance of contract {}",
+                        hex::encode(contract.as_bytes())
+                    )));
+                }
+                if *balance > current {
+                    session.call::<_, ()>(
+                        TRANSFER_CONTRACT,
+                        "add_contract_balance",
+                        &(*contract, balance - current),
+                        u64::MAX,
+                    )?;
+                }
+            }
+            StateOverride::Call(call) => {
+                session.call_raw(
+                    call.contract,
+                    &call.fn_name,
+                    call.fn_args.clone(),
+                    u64::MAX,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
 
 impl Rusk {
     pub fn query_raw<S, V>(
@@ -114,28 +211,85 @@ impl Rusk {
         Ok(())
     }
 
+    /// Performs a feeder query against the current tip, writing the fed
+    /// items into the given bounded channel.
+    ///
+    /// The contract call runs on its own thread, while the items it feeds are
+    /// forwarded as they come. Once the receiver is dropped, forwarding stops
+    /// and the call is aborted on the next item it feeds. The VM only feeds
+    /// into an unbounded channel, so the items not yet forwarded to a slow
+    /// receiver are bounded by the feeder gas limit, which each item fed
+    /// draws on.
+    ///
+    /// This blocks the calling thread until the call is done or aborted, so it
+    /// must not be called from within the async runtime.
     pub fn feeder_query_raw<S, V>(
         &self,
         contract_id: ContractId,
         call_name: S,
         call_arg: V,
-        feeder: mpsc::Sender<Vec<u8>>,
+        feeder: tokio_mpsc::Sender<Vec<u8>>,
     ) -> Result<()>
     where
         S: AsRef<str>,
         V: Into<Vec<u8>>,
     {
         let mut session = self.query_session(None)?;
+        let (sender, receiver) = mpsc::channel();
 
-        // For feeder queries we use the gas limit set in the config
-        session.feeder_call_raw(
-            contract_id,
-            call_name.as_ref(),
-            call_arg,
-            self.feeder_gas_limit,
-            feeder,
-        )?;
+        let call_name = call_name.as_ref().to_owned();
+        let call_arg = call_arg.into();
+        let gas_limit = self.feeder_gas_limit;
+        let call = thread::spawn(move || {
+            session.feeder_call_raw(
+                contract_id,
+                &call_name,
+                call_arg,
+                gas_limit,
+                sender,
+            )
+        });
 
-        Ok(())
+        for item in receiver {
+            // The receiver was dropped, so nobody is interested in the rest.
+            // Dropping our end of the channel aborts the call.
+            if feeder.blocking_send(item).is_err() {
+                break;
+            }
+        }
+
+        match call.join().expect("The query thread should not panic") {
+            Ok(_) | Err(VMError::FeedPulled(_)) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Performs a dry-run query against the current tip, with the given
+    /// overrides applied to the state beforehand.
+    ///
+    /// The overrides are applied in order, and are discarded together with
+    /// the query session.
+    pub fn dry_run_query_raw<S, V>(
+        &self,
+        contract_id: ContractId,
+        fn_name: S,
+        fn_arg: V,
+        overrides: &[StateOverride],
+    ) -> Result<Vec<u8>>
+    where
+        S: AsRef<str>,
+        V: Into<Vec<u8>>,
+    {
+        let mut session = self.query_session(None)?;
+
+        for state_override in overrides {
+            state_override.apply(&mut session)?;
+        }
+
+        // For queries we set a point limit of effectively infinite
+        session
+            .call_raw(contract_id, fn_name.as_ref(), fn_arg, u64::MAX)
+            .map(|receipt| receipt.data)
+            .map_err(Into::into)
     }
 }
@@ -0,0 +1,264 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! A cluster of in-process nodes running consensus together.
+//!
+//! Each node has its own VM state, database, mempool and consensus keys, and
+//! reaches the others through a [`SimHub`]. All the nodes are provisioners
+//! with the same stake, so that consensus needs a quorum of them.
+
+use std::env;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use dusk_bytes::Serializable;
+use dusk_core::signatures::bls::{
+    PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+};
+use node::chain::ChainSrv;
+use node::database::rocksdb::{Backend, MD_HASH_KEY};
+use node::database::{DatabaseOptions, Ledger, Metadata, DB};
+use node::databroker::conf::Params as BrokerParam;
+use node::databroker::DataBrokerSrv;
+use node::mempool::conf::Params as MempoolParam;
+use node::mempool::MempoolSrv;
+use node::{LongLivedService, Node};
+use node_data::get_current_timestamp;
+use node_data::ledger::Header;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rusk::node::RuskVmConfig;
+use rusk::{Rusk, DUSK_CONSENSUS_KEY};
+use tempfile::{tempdir, TempDir};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::common::network::{SimHub, SimNetwork};
+use crate::common::state::new_state;
+
+type ClusterService = dyn LongLivedService<SimNetwork, Backend, Rusk>;
+
+/// The password the consensus keys of the nodes are encrypted with.
+const CONSENSUS_KEYS_PASS: &str = "password";
+
+/// The minimum block time of the cluster, in seconds, to keep tests short.
+const MINIMUM_BLOCK_TIME: &str = "1";
+
+/// The stake of each node, in Lux.
+const STAKE: u64 = 1_000_000_000_000;
+
+const MAX_CHAIN_QUEUE_SIZE: usize = 1000;
+
+/// A node of a [`Cluster`].
+pub struct ClusterNode {
+    addr: SocketAddr,
+    node: Node<SimNetwork, Backend, Rusk>,
+    tasks: Vec<JoinHandle<()>>,
+    _dir: TempDir,
+}
+
+impl ClusterNode {
+    /// Returns the header of the tip of the chain of the node.
+    pub async fn tip(&self) -> anyhow::Result<Header> {
+        let db = self.node.database();
+        let db = db.read().await;
+        db.view(|t| {
+            let hash = t
+                .op_read(MD_HASH_KEY)?
+                .ok_or_else(|| anyhow::anyhow!("tip should be stored"))?;
+            t.block_header(&hash)?
+                .ok_or_else(|| anyhow::anyhow!("tip should be found"))
+        })
+    }
+
+    /// Returns the hash of the block at the given height in the chain of the
+    /// node, if any.
+    pub async fn block_hash(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<Option<[u8; 32]>> {
+        let db = self.node.database();
+        let db = db.read().await;
+        db.view(|t| t.block_hash_by_height(height))
+    }
+}
+
+/// A set of nodes connected by a [`SimHub`].
+pub struct Cluster {
+    hub: SimHub,
+    nodes: Vec<ClusterNode>,
+}
+
+impl Cluster {
+    /// Starts a cluster of the given number of nodes, all sharing the same
+    /// genesis state.
+    pub async fn start(size: usize) -> anyhow::Result<Self> {
+        // The keys password and block time are read from the environment
+        env::set_var("DUSK_CONSENSUS_KEYS_PASS", CONSENSUS_KEYS_PASS);
+        env::set_var("RUSK_MINIMUM_BLOCK_TIME", MINIMUM_BLOCK_TIME);
+
+        let keys: Vec<_> = (0..size as u64)
+            .map(|i| {
+                let sk = BlsSecretKey::random(&mut StdRng::seed_from_u64(i));
+                let pk = BlsPublicKey::from(&sk);
+                (sk, pk)
+            })
+            .collect();
+        let snapshot = toml::from_str(&genesis_stakes(&keys))?;
+        let genesis_timestamp = get_current_timestamp();
+
+        let hub = SimHub::default();
+        let mut nodes = Vec::with_capacity(size);
+
+        for (idx, (sk, pk)) in keys.iter().enumerate() {
+            let dir = tempdir()?;
+            let rusk = new_state(
+                dir.path().join("state"),
+                &snapshot,
+                RuskVmConfig::new(),
+            )?;
+
+            let keys_path = dir.path().join("consensus.keys");
+            let mut rng = StdRng::seed_from_u64(idx as u64);
+            node_data::bls::save_keys(
+                &mut rng,
+                keys_path.clone(),
+                sk,
+                pk,
+                CONSENSUS_KEYS_PASS,
+            )?;
+
+            let db = Backend::create_or_open(
+                dir.path().join("chain.db"),
+                DatabaseOptions::default(),
+            );
+            let addr = SocketAddr::from(([127, 0, 0, 1], 9000 + idx as u16));
+            let net = hub.connect(addr);
+            let node = Node::new(net, db, rusk);
+
+            let (event_sender, mut event_receiver) = mpsc::channel(1000);
+            let mut services: Vec<Box<ClusterService>> = vec![
+                Box::new(MempoolSrv::new(
+                    MempoolParam::default(),
+                    event_sender.clone(),
+                )),
+                Box::new(ChainSrv::new(
+                    keys_path.display().to_string(),
+                    MAX_CHAIN_QUEUE_SIZE,
+                    event_sender,
+                    genesis_timestamp,
+                    *DUSK_CONSENSUS_KEY,
+                )),
+                Box::new(DataBrokerSrv::new(BrokerParam::default())),
+            ];
+            node.initialize(&mut services).await?;
+
+            let runner = node.clone();
+            let tasks = vec![
+                tokio::spawn(async move {
+                    let _ = runner.spawn_all(services).await;
+                }),
+                // Nobody listens to the node events
+                tokio::spawn(async move {
+                    while event_receiver.recv().await.is_some() {}
+                }),
+            ];
+
+            nodes.push(ClusterNode {
+                addr,
+                node,
+                tasks,
+                _dir: dir,
+            });
+        }
+
+        Ok(Self { hub, nodes })
+    }
+
+    pub fn node(&self, idx: usize) -> &ClusterNode {
+        &self.nodes[idx]
+    }
+
+    /// Splits the cluster in groups of nodes, given by their index, which
+    /// only reach the nodes of the same group.
+    pub fn partition(&self, groups: &[&[usize]]) {
+        let groups: Vec<Vec<_>> = groups
+            .iter()
+            .map(|group| {
+                group.iter().map(|idx| self.nodes[*idx].addr).collect()
+            })
+            .collect();
+        self.hub.partition(&groups);
+    }
+
+    /// Reconnects all the nodes of the cluster.
+    pub fn heal(&self) {
+        self.hub.heal();
+    }
+
+    /// Waits until the given nodes reach the given height.
+    pub async fn wait_for_height(
+        &self,
+        nodes: &[usize],
+        height: u64,
+        timeout: Duration,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        loop {
+            let mut heights = vec![];
+            for idx in nodes {
+                heights.push(self.nodes[*idx].tip().await?.height);
+            }
+            if heights.iter().all(|h| *h >= height) {
+                return Ok(());
+            }
+            if start.elapsed() > timeout {
+                anyhow::bail!(
+                    "nodes {nodes:?} at heights {heights:?} after {timeout:?}, \
+                     expected {height}"
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Asserts the given nodes have the same block at the given height.
+    pub async fn assert_same_block(
+        &self,
+        nodes: &[usize],
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let mut hashes = vec![];
+        for idx in nodes {
+            hashes.push(self.nodes[*idx].block_hash(height).await?);
+        }
+        assert!(
+            hashes.windows(2).all(|w| w[0] == w[1]),
+            "nodes {nodes:?} should agree on block {height}: {hashes:?}"
+        );
+        Ok(())
+    }
+}
+
+impl Drop for Cluster {
+    fn drop(&mut self) {
+        for node in &self.nodes {
+            for task in &node.tasks {
+                task.abort();
+            }
+        }
+    }
+}
+
+/// Returns the genesis snapshot staking the given provisioners.
+fn genesis_stakes(keys: &[(BlsSecretKey, BlsPublicKey)]) -> String {
+    keys.iter()
+        .map(|(_, pk)| {
+            let address = bs58::encode(pk.to_bytes()).into_string();
+            format!("[[stake]]\naddress = \"{address}\"\namount = {STAKE}\n\n")
+        })
+        .collect()
+}
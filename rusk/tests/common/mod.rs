@@ -5,6 +5,9 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 pub mod block;
+pub mod cluster;
+pub mod network;
+pub mod rng;
 pub mod state;
 pub mod wallet;
 
@@ -0,0 +1,250 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! An in-process [`Network`] connecting the nodes of a test cluster.
+//!
+//! Every node is directly connected to every other node it is not partitioned
+//! from. Messages go through their wire encoding, so that the nodes exchange
+//! exactly what they would over Kadcast.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use node::{BoxedFilter, Network};
+use node_data::message::payload::{GetResource, Inv};
+use node_data::message::{AsyncQueue, Message, Metadata, PROTOCOL_VERSION};
+use node_data::{get_current_timestamp, Serializable};
+use parking_lot::{Mutex, RwLock};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use tracing::{debug, warn};
+
+/// Number of peers a `flood_request` is sent to, as in Kadcast
+const REDUNDANCY_PEER_COUNT: usize = 8;
+
+#[derive(Default)]
+struct Peer {
+    routes: HashMap<u8, AsyncQueue<Message>>,
+    filters: HashMap<u8, BoxedFilter>,
+    /// The partition the peer belongs to. Peers only reach the ones in the
+    /// same partition.
+    partition: usize,
+}
+
+/// The medium the nodes of a cluster exchange messages through.
+#[derive(Clone, Default)]
+pub struct SimHub {
+    peers: Arc<RwLock<BTreeMap<SocketAddr, Mutex<Peer>>>>,
+}
+
+impl SimHub {
+    /// Connects a new node to the hub.
+    pub fn connect(&self, addr: SocketAddr) -> SimNetwork {
+        self.peers.write().insert(addr, Mutex::default());
+        SimNetwork {
+            addr,
+            hub: self.clone(),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Splits the nodes in the given groups, which can only reach the nodes
+    /// of the same group. The nodes not listed form a group on their own.
+    pub fn partition(&self, groups: &[Vec<SocketAddr>]) {
+        let peers = self.peers.read();
+        for peer in peers.values() {
+            peer.lock().partition = 0;
+        }
+        for (idx, group) in groups.iter().enumerate() {
+            for addr in group {
+                if let Some(peer) = peers.get(addr) {
+                    peer.lock().partition = idx + 1;
+                }
+            }
+        }
+    }
+
+    /// Reconnects all the nodes.
+    pub fn heal(&self) {
+        self.partition(&[]);
+    }
+
+    /// Returns the peers reachable from the given node.
+    fn reachable(&self, from: &SocketAddr) -> Vec<SocketAddr> {
+        let peers = self.peers.read();
+        let Some(partition) = peers.get(from).map(|p| p.lock().partition)
+        else {
+            return vec![];
+        };
+        peers
+            .iter()
+            .filter(|(addr, peer)| {
+                *addr != from && peer.lock().partition == partition
+            })
+            .map(|(addr, _)| *addr)
+            .collect()
+    }
+
+    /// Delivers an encoded message to the given peers, the way the Kadcast
+    /// listener does.
+    fn deliver(&self, src_addr: SocketAddr, blob: &[u8], to: &[SocketAddr]) {
+        let peers = self.peers.read();
+        for addr in to {
+            let Some(peer) = peers.get(addr) else {
+                continue;
+            };
+
+            let mut msg = match Message::read_slice(&mut &blob[..]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    warn!(
+                        "{addr} cannot decode message from {src_addr}: {err}"
+                    );
+                    return;
+                }
+            };
+            // Every peer is reached directly, so messages are never relayed
+            msg.metadata = Some(Metadata {
+                height: 0,
+                src_addr,
+                ray_id: String::new(),
+            });
+
+            let mut peer = peer.lock();
+            let topic: u8 = msg.topic().into();
+            if let Some(filter) = peer.filters.get_mut(&topic) {
+                if let Err(e) = filter.filter(&msg) {
+                    debug!("{addr} discards message due to {e}");
+                    continue;
+                }
+            }
+            if let Some(queue) = peer.routes.get(&topic) {
+                queue.try_send(msg);
+            }
+        }
+    }
+}
+
+/// The network of a node of a cluster.
+pub struct SimNetwork {
+    addr: SocketAddr,
+    hub: SimHub,
+    counter: AtomicU64,
+}
+
+impl SimNetwork {
+    fn encode(&self, msg: &Message) -> anyhow::Result<Vec<u8>> {
+        let mut encoded = vec![];
+        msg.write_versioned(&PROTOCOL_VERSION, &mut encoded)?;
+        Ok(encoded)
+    }
+
+    fn send(&self, mut msg: Message, to: &[SocketAddr]) -> anyhow::Result<()> {
+        // The nonce makes repeated requests distinct, as in Kadcast
+        let nonce = self.counter.fetch_add(1, Ordering::SeqCst);
+        msg.payload.set_nonce(nonce);
+
+        let encoded = self.encode(&msg)?;
+        self.hub.deliver(self.addr, &encoded, to);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Network for SimNetwork {
+    async fn broadcast(&self, msg: &Message) -> anyhow::Result<()> {
+        // Messages received are already delivered to every reachable peer
+        if !msg.is_local() {
+            return Ok(());
+        }
+
+        let encoded = self.encode(msg)?;
+        let to = self.hub.reachable(&self.addr);
+        self.hub.deliver(self.addr, &encoded, &to);
+        Ok(())
+    }
+
+    async fn flood_request(
+        &self,
+        msg_inv: &Inv,
+        ttl_as_sec: Option<u64>,
+        hops_limit: u16,
+    ) -> anyhow::Result<()> {
+        let ttl_as_sec = ttl_as_sec
+            .map_or_else(|| u64::MAX, |v| get_current_timestamp() + v);
+
+        let msg = GetResource::new(
+            msg_inv.clone(),
+            Some(self.addr),
+            ttl_as_sec,
+            hops_limit,
+        );
+        self.send_to_alive_peers(msg.into(), REDUNDANCY_PEER_COUNT)
+            .await
+    }
+
+    async fn send_to_peer(
+        &self,
+        msg: Message,
+        peer_addr: SocketAddr,
+    ) -> anyhow::Result<()> {
+        if self.hub.reachable(&self.addr).contains(&peer_addr) {
+            self.send(msg, &[peer_addr])?;
+        }
+        Ok(())
+    }
+
+    async fn send_to_alive_peers(
+        &self,
+        msg: Message,
+        amount: usize,
+    ) -> anyhow::Result<()> {
+        let mut peers = self.hub.reachable(&self.addr);
+        let seed = self.counter.load(Ordering::SeqCst);
+        peers.shuffle(&mut StdRng::seed_from_u64(seed));
+        peers.truncate(amount);
+
+        self.send(msg, &peers)
+    }
+
+    async fn add_route(
+        &mut self,
+        msg_type: u8,
+        queue: AsyncQueue<Message>,
+    ) -> anyhow::Result<()> {
+        let peers = self.hub.peers.read();
+        let peer = peers.get(&self.addr).expect("peer to be connected");
+        peer.lock().routes.insert(msg_type, queue);
+        Ok(())
+    }
+
+    async fn add_filter(
+        &mut self,
+        msg_type: u8,
+        filter: BoxedFilter,
+    ) -> anyhow::Result<()> {
+        let peers = self.hub.peers.read();
+        let peer = peers.get(&self.addr).expect("peer to be connected");
+        peer.lock().filters.insert(msg_type, filter);
+        Ok(())
+    }
+
+    fn get_info(&self) -> anyhow::Result<String> {
+        Ok(self.addr.to_string())
+    }
+
+    fn public_addr(&self) -> &SocketAddr {
+        &self.addr
+    }
+
+    async fn alive_nodes_count(&self) -> usize {
+        self.hub.reachable(&self.addr).len()
+    }
+}
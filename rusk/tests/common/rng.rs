@@ -0,0 +1,60 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The randomness of the tests.
+//!
+//! All the randomness of a test run derives from a single seed: the value of
+//! `RUSK_TEST_SEED` if set, or [`DEFAULT_SEED`] otherwise. The seed drives
+//! both the rng returned by [`test_rng`] and the prover, so the notes, proofs
+//! and transactions of a failing test are reproduced by running it again
+//! with the seed it printed.
+//!
+//! Setting `RUSK_TEST_SEED=random` draws a fresh seed for the run.
+
+use std::env;
+use std::sync::LazyLock;
+
+use rand::rngs::{OsRng, StdRng};
+use rand::{RngCore, SeedableRng};
+
+/// The environment variable the seed of the tests is read from.
+pub const SEED_ENV: &str = "RUSK_TEST_SEED";
+
+/// The seed of the tests when none is given.
+pub const DEFAULT_SEED: u64 = 0xdead;
+
+static SEED: LazyLock<u64> = LazyLock::new(|| match env::var(SEED_ENV) {
+    Err(_) => DEFAULT_SEED,
+    Ok(seed) if seed == "random" => OsRng.next_u64(),
+    Ok(seed) => parse_seed(&seed).unwrap_or_else(|| {
+        panic!("{SEED_ENV} should be a number or \"random\", got {seed}")
+    }),
+});
+
+/// Parses a decimal, or `0x` prefixed hexadecimal, seed.
+fn parse_seed(seed: &str) -> Option<u64> {
+    match seed.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => seed.parse().ok(),
+    }
+}
+
+/// Returns the seed of the test run.
+pub fn test_seed() -> u64 {
+    *SEED
+}
+
+/// Returns an rng seeded with the seed of the test run, and seeds the prover
+/// with it.
+///
+/// The seed is printed to the output of the test, which is only shown when
+/// the test fails.
+pub fn test_rng() -> StdRng {
+    let seed = test_seed();
+    rusk_prover::set_rng_seed(seed);
+    eprintln!("Reproduce with {SEED_ENV}={seed:#x}");
+    StdRng::seed_from_u64(seed)
+}
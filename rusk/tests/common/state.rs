@@ -22,7 +22,8 @@ use dusk_core::{
 use node_data::{
     bls::PublicKeyBytes,
     ledger::{
-        Attestation, Block, Header, IterationsInfo, Slash, SpentTransaction,
+        Attestation, Block, Header, IterationsInfo, Seed, Slash,
+        SpentTransaction,
     },
     message::payload::Vote,
 };
@@ -145,6 +146,8 @@ pub fn generator_procedure(
         voters_pubkey: voters.clone(),
         max_txs_bytes: usize::MAX,
         prev_state_root: prev_root,
+        prev_seed: Seed::default(),
+        timestamp: 0,
     };
 
     let (transfer_txs, discarded, execute_output) =
@@ -175,14 +178,28 @@ pub fn generator_procedure(
     )
     .expect("valid block");
 
-    let verify_output =
-        rusk.verify_state_transition(prev_root, &block, &voters)?;
+    let verify_output = rusk.verify_state_transition(
+        prev_root,
+        Seed::default(),
+        &block,
+        &voters,
+    )?;
     info!("verify_state_transition new verification: {verify_output}",);
 
-    let (accept_txs, accept_output, _) =
-        rusk.accept(prev_root, &block, &voters)?;
+    let (accept_txs, accept_output, _, accept_metrics) =
+        rusk.accept(prev_root, Seed::default(), &block, &voters)?;
 
     assert_eq!(accept_txs.len(), expected.executed, "all txs accepted");
+    assert_eq!(
+        accept_metrics.transactions,
+        accept_txs.len() as u64,
+        "the metrics account for every accepted tx"
+    );
+    assert_eq!(
+        accept_metrics.gas_spent,
+        accept_txs.iter().map(|tx| tx.gas_spent).sum::<u64>(),
+        "the metrics account for the gas spent by every accepted tx"
+    );
 
     info!(
         "accept block {} with new verification: {accept_output}",
@@ -257,6 +274,8 @@ pub fn generator_procedure2(
         voters_pubkey: voters.clone(),
         max_txs_bytes: usize::MAX,
         prev_state_root: prev_root,
+        prev_seed: Seed::default(),
+        timestamp: 0,
     };
 
     let (transfer_txs, discarded, execute_output) =
@@ -287,14 +306,28 @@ pub fn generator_procedure2(
     )
     .expect("valid block");
 
-    let verify_output =
-        rusk.verify_state_transition(prev_root, &block, &voters)?;
+    let verify_output = rusk.verify_state_transition(
+        prev_root,
+        Seed::default(),
+        &block,
+        &voters,
+    )?;
     info!("verify_state_transition new verification: {verify_output}",);
 
-    let (accept_txs, accept_output, _) =
-        rusk.accept(prev_root, &block, &voters)?;
+    let (accept_txs, accept_output, _, accept_metrics) =
+        rusk.accept(prev_root, Seed::default(), &block, &voters)?;
 
     assert_eq!(accept_txs.len(), expected.executed, "all txs accepted");
+    assert_eq!(
+        accept_metrics.transactions,
+        accept_txs.len() as u64,
+        "the metrics account for every accepted tx"
+    );
+    assert_eq!(
+        accept_metrics.gas_spent,
+        accept_txs.iter().map(|tx| tx.gas_spent).sum::<u64>(),
+        "the metrics account for the gas spent by every accepted tx"
+    );
 
     info!(
         "accept block {} with new verification: {accept_output}",
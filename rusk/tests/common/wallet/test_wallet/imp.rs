@@ -33,8 +33,8 @@ use wallet_core::keys::{derive_bls_sk, derive_phoenix_sk};
 use wallet_core::transaction::{
     moonlight, moonlight_deployment, moonlight_stake, moonlight_stake_reward,
     moonlight_to_phoenix, moonlight_unstake, phoenix as phoenix_transaction,
-    phoenix_deployment, phoenix_stake, phoenix_stake_reward,
-    phoenix_to_moonlight, phoenix_unstake,
+    phoenix_consolidation, phoenix_deployment, phoenix_stake,
+    phoenix_stake_reward, phoenix_to_moonlight, phoenix_unstake, plan,
 };
 use wallet_core::{phoenix_balance, BalanceInfo};
 use zeroize::Zeroize;
@@ -482,6 +482,69 @@ where
         Ok(tx)
     }
 
+    /// Plans the consolidations needed for the sender's notes to cover the
+    /// given cost, returning the transaction of the first one, or `None` if
+    /// the cost can already be covered by a single transaction.
+    pub fn phoenix_consolidate<Rng: RngCore + CryptoRng>(
+        &self,
+        rng: &mut Rng,
+        sender_index: u8,
+        transaction_cost: u64,
+        gas_limit: u64,
+        gas_price: u64,
+    ) -> Result<Option<Transaction>, Error<S, SC>> {
+        let mut sender_sk = self.phoenix_secret_key(sender_index)?;
+        let sender_vk = PhoenixViewKey::from(&sender_sk);
+
+        let notes: Vec<_> = self
+            .unspent_notes_and_nullifiers(&sender_sk)?
+            .into_iter()
+            .map(|(note_leaf, nullifier)| (nullifier, note_leaf))
+            .collect();
+
+        let plan = plan(
+            &sender_vk,
+            &notes.into(),
+            transaction_cost,
+            gas_limit * gas_price,
+        )
+        .ok_or(ExecutionError::InsufficientBalance)?;
+        let Some(consolidation) = plan.consolidations.first() else {
+            return Ok(None);
+        };
+
+        let mut inputs = Vec::with_capacity(consolidation.inputs.len());
+        for input in &consolidation.inputs {
+            let (_, note_leaf) =
+                input.note().ok_or(Error::NoteCombinationProblem)?;
+            let opening = self
+                .state
+                .fetch_opening(&note_leaf.note)
+                .map_err(Error::from_state_err)?;
+            inputs.push((note_leaf.note.clone(), opening));
+        }
+
+        let root = self.state.fetch_root().map_err(Error::from_state_err)?;
+        let chain_id =
+            self.state.fetch_chain_id().map_err(Error::from_state_err)?;
+
+        let tx = phoenix_consolidation(
+            rng,
+            &sender_sk,
+            inputs,
+            root,
+            consolidation.value,
+            gas_limit,
+            gas_price,
+            chain_id,
+            &LocalProver,
+        )?;
+
+        sender_sk.zeroize();
+
+        Ok(Some(tx))
+    }
+
     /// Stakes an amount of Dusk using Phoenix notes.
     #[allow(clippy::too_many_arguments)]
     pub fn phoenix_stake<Rng: RngCore + CryptoRng>(
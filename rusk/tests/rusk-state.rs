@@ -11,6 +11,8 @@ use crate::common::*;
 
 use std::path::Path;
 use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use dusk_core::{
     transfer::{
@@ -25,13 +27,13 @@ use dusk_core::{
 use dusk_vm::VM;
 use ff::Field;
 use parking_lot::RwLockWriteGuard;
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::{Rusk, RuskTip, RuskVmConfig};
 use rusk::Result;
 use tempfile::tempdir;
+use tokio::sync::mpsc as tokio_mpsc;
 use tracing::info;
 
+use crate::common::rng::test_rng;
 use crate::common::state::new_state;
 
 const BLOCK_HEIGHT: u64 = 1;
@@ -62,7 +64,7 @@ where
     F: FnOnce(RwLockWriteGuard<'a, RuskTip>, &'a VM) -> T,
 {
     info!("Generating a note");
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let sender_sk = PhoenixSecretKey::random(&mut rng);
     let sender_pk = PhoenixPublicKey::from(&sender_sk);
@@ -169,6 +171,87 @@ pub fn rusk_state_finalized() -> Result<()> {
     Ok(())
 }
 
+fn leaves_from_height_raw(
+    rusk: &Rusk,
+    height: u64,
+) -> (JoinHandle<Result<()>>, tokio_mpsc::Receiver<Vec<u8>>) {
+    let (sender, receiver) = tokio_mpsc::channel(1);
+    let arg = rkyv::to_bytes::<_, 8>(&height)
+        .expect("Serializing the height should succeed")
+        .to_vec();
+
+    let rusk = rusk.clone();
+    let query = thread::spawn(move || {
+        rusk.feeder_query_raw(
+            TRANSFER_CONTRACT,
+            "leaves_from_height",
+            arg,
+            sender,
+        )
+    });
+
+    (query, receiver)
+}
+
+#[test]
+pub fn rusk_feeder_query_slow_receiver() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    push_note(&rusk, |_tip, _vm| {});
+    let expected = leaves_from_height(&rusk, 0)?;
+
+    // the leaves are forwarded one at a time, as the receiver takes them
+    let (query, mut receiver) = leaves_from_height_raw(&rusk, 0);
+    let mut leaves = Vec::new();
+    while let Some(bytes) = receiver.blocking_recv() {
+        thread::sleep(Duration::from_millis(100));
+        leaves.push(rkyv::from_bytes::<NoteLeaf>(&bytes).unwrap());
+    }
+    query.join().expect("The query should not panic")?;
+
+    let positions = |leaves: &[NoteLeaf]| -> Vec<u64> {
+        leaves.iter().map(|leaf| *leaf.note.pos()).collect()
+    };
+    assert_eq!(positions(&leaves), positions(&expected));
+
+    Ok(())
+}
+
+#[test]
+pub fn rusk_feeder_query_dropped_receiver() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    push_note(&rusk, |_tip, _vm| {});
+
+    // dropping the receiver before any leaf is taken stops the query, which
+    // returns without error
+    let (query, receiver) = leaves_from_height_raw(&rusk, 0);
+    drop(receiver);
+    query.join().expect("The query should not panic")?;
+
+    // the same goes for dropping it midway
+    let (query, mut receiver) = leaves_from_height_raw(&rusk, 0);
+    receiver
+        .blocking_recv()
+        .expect("The first leaf should be fed");
+    drop(receiver);
+    query.join().expect("The query should not panic")?;
+
+    // the VM is left free to serve the following queries
+    let leaves = leaves_from_height(&rusk, 0)?;
+    assert_eq!(leaves.len(), 2);
+
+    Ok(())
+}
+
 // This code is used to generate the transaction bytes for the phoenix
 // benchmarks. To generate:
 //   - uncomment the `#[tokio::test(..)]' line
@@ -206,7 +289,7 @@ async fn generate_phoenix_txs() -> Result<(), Box<dyn std::error::Error>> {
 
     for sender_index in 0..N_ADDRESSES as u8 {
         let wallet = wallet.clone();
-        let mut rng = StdRng::seed_from_u64(0xdead);
+        let mut rng = test_rng();
 
         let receiver_index = (sender_index + 1) % N_ADDRESSES as u8;
         let receiver = wallet.phoenix_public_key(receiver_index).unwrap();
@@ -0,0 +1,53 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::time::Duration;
+
+use crate::common::cluster::Cluster;
+use crate::common::logger;
+
+const TIMEOUT: Duration = Duration::from_secs(120);
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn cluster_agrees_on_blocks() -> anyhow::Result<()> {
+    logger();
+
+    let cluster = Cluster::start(3).await?;
+    cluster.wait_for_height(&[0, 1, 2], 3, TIMEOUT).await?;
+
+    for height in 1..=3 {
+        cluster.assert_same_block(&[0, 1, 2], height).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn cluster_syncs_after_partition() -> anyhow::Result<()> {
+    logger();
+
+    let cluster = Cluster::start(4).await?;
+    cluster.wait_for_height(&[0, 1, 2, 3], 1, TIMEOUT).await?;
+
+    // Three nodes out of four still hold a quorum, the isolated one stalls
+    cluster.partition(&[&[0, 1, 2], &[3]]);
+    let isolated = cluster.node(3).tip().await?.height;
+    cluster
+        .wait_for_height(&[0, 1, 2], isolated + 3, TIMEOUT)
+        .await?;
+    assert!(
+        cluster.node(3).tip().await?.height < isolated + 3,
+        "isolated node should not follow the chain"
+    );
+
+    // Once reconnected, it catches up with the majority chain
+    cluster.heal();
+    let tip = cluster.node(0).tip().await?.height;
+    cluster.wait_for_height(&[3], tip, TIMEOUT).await?;
+    cluster.assert_same_block(&[0, 3], tip).await?;
+
+    Ok(())
+}
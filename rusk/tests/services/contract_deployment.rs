@@ -12,10 +12,10 @@ use dusk_core::abi::ContractId;
 use dusk_core::transfer::data::{
     ContractBytecode, ContractDeploy, TransactionData,
 };
+use dusk_core::transfer::TRANSFER_CONTRACT;
 use dusk_vm::{gen_contract_id, ContractData, Error as VMError, VM};
-use rand::prelude::*;
-use rand::rngs::StdRng;
-use rusk::node::RuskVmConfig;
+use node_data::ledger::SpentTransaction;
+use rusk::node::{DeployDepositConfig, DeployPolicyConfig, RuskVmConfig};
 use rusk::{Result, Rusk, DUSK_CONSENSUS_KEY};
 use rusk_recovery_tools::state;
 use tempfile::tempdir;
@@ -23,6 +23,7 @@ use tokio::sync::broadcast;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{
     generator_procedure, ExecuteResult, DEFAULT_MIN_GAS_LIMIT,
 };
@@ -51,7 +52,13 @@ const CHAIN_ID: u8 = 0xFA;
 const BOB_ECHO_VALUE: u64 = 775;
 const BOB_INIT_VALUE: u8 = 5;
 
-fn initial_state<P: AsRef<Path>>(dir: P, deploy_bob: bool) -> Result<Rusk> {
+const DEPOSIT_PER_BYTE: u64 = 10;
+
+fn initial_state<P: AsRef<Path>>(
+    dir: P,
+    deploy_bob: bool,
+    vm_config: RuskVmConfig,
+) -> Result<Rusk> {
     let dir = dir.as_ref();
 
     let snapshot =
@@ -104,7 +111,7 @@ fn initial_state<P: AsRef<Path>>(dir: P, deploy_bob: bool) -> Result<Rusk> {
     let rusk = Rusk::new(
         dir,
         CHAIN_ID,
-        RuskVmConfig::new(),
+        vm_config,
         DEFAULT_MIN_GAS_LIMIT,
         u64::MAX,
         sender,
@@ -127,8 +134,8 @@ fn make_and_execute_transaction_deploy(
     should_fail: bool,
     should_discard: bool,
     gas_price: u64,
-) {
-    let mut rng = StdRng::seed_from_u64(0xcafe);
+) -> Option<SpentTransaction> {
+    let mut rng = test_rng();
 
     let init_args = Some(vec![init_value]);
 
@@ -167,17 +174,55 @@ fn make_and_execute_transaction_deploy(
     );
     let spent_transactions =
         result.expect("generator procedure should succeed");
-    if !should_discard {
-        let mut spent_transactions = spent_transactions.into_iter();
-        let tx = spent_transactions
-            .next()
-            .expect("There should be one spent transactions");
-        if should_fail {
-            assert!(tx.err.is_some(), "Transaction should fail");
-        } else {
-            assert!(tx.err.is_none(), "Transaction should not fail");
-        }
+    if should_discard {
+        return None;
+    }
+
+    let mut spent_transactions = spent_transactions.into_iter();
+    let tx = spent_transactions
+        .next()
+        .expect("There should be one spent transactions");
+    if should_fail {
+        assert!(tx.err.is_some(), "Transaction should fail");
+    } else {
+        assert!(tx.err.is_none(), "Transaction should not fail");
     }
+    Some(tx)
+}
+
+// A VM config escrowing a deposit for deployments from the given height.
+fn deposit_config(activation_height: u64) -> RuskVmConfig {
+    let mut config =
+        RuskVmConfig::new().with_deploy_deposit(DeployDepositConfig {
+            gas_per_byte: DEPOSIT_PER_BYTE,
+            gas_per_memory_page: 0,
+        });
+    config
+        .features
+        .insert("DEPLOY_DEPOSIT".into(), activation_height);
+    config
+}
+
+// A VM config only allowing another owner to deploy, from the given height.
+fn policy_config(activation_height: u64) -> RuskVmConfig {
+    let mut config =
+        RuskVmConfig::new().with_deploy_policy(DeployPolicyConfig {
+            allowed_owners: Some(vec![vec![2; 32]]),
+            ..DeployPolicyConfig::default()
+        });
+    config
+        .features
+        .insert("DEPLOY_POLICY".into(), activation_height);
+    config
+}
+
+// A VM config validating the bytecode of deployments from the given height.
+fn validation_config(activation_height: u64) -> RuskVmConfig {
+    let mut config = RuskVmConfig::new();
+    config
+        .features
+        .insert("BYTECODE_VALIDATION".into(), activation_height);
+    config
 }
 
 struct Fixture {
@@ -190,9 +235,13 @@ struct Fixture {
 
 impl Fixture {
     fn build(deploy_bob: bool) -> Self {
+        Self::build_with_config(deploy_bob, RuskVmConfig::new())
+    }
+
+    fn build_with_config(deploy_bob: bool, vm_config: RuskVmConfig) -> Self {
         let tmp =
             tempdir().expect("Should be able to create temporary directory");
-        let rusk = initial_state(&tmp, deploy_bob)
+        let rusk = initial_state(&tmp, deploy_bob, vm_config)
             .expect("Initializing should succeed");
 
         let cache = Arc::new(RwLock::new(HashMap::new()));
@@ -269,6 +318,12 @@ impl Fixture {
         );
     }
 
+    pub fn escrowed_deposit(&self) -> u64 {
+        self.rusk
+            .query(TRANSFER_CONTRACT, "deploy_deposit", &self.contract_id)
+            .expect("Querying the deposit should succeed")
+    }
+
     pub fn wallet_balance(&self) -> u64 {
         self.wallet
             .get_balance(0)
@@ -474,3 +529,147 @@ pub async fn contract_deploy_gas_limit_too_low() {
     let funds_spent = before_balance - after_balance;
     assert_eq!(funds_spent, 0);
 }
+
+/// We deploy a contract once the deploy deposit is active.
+/// The deposit is escrowed by the transfer contract, and is not counted as
+/// gas spent by the transaction.
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_deploy_deposit() {
+    logger();
+    let plain = Fixture::build(false);
+    let f = Fixture::build_with_config(false, deposit_config(BLOCK_HEIGHT));
+
+    let plain_tx = make_and_execute_transaction_deploy(
+        &plain.rusk,
+        &plain.wallet,
+        plain.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        false,
+        GAS_PRICE,
+    )
+    .expect("The deployment should be executed");
+    let tx = make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        f.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        false,
+        GAS_PRICE,
+    )
+    .expect("The deployment should be executed");
+    f.assert_bob_contract_is_deployed();
+
+    assert_eq!(tx.gas_spent, plain_tx.gas_spent);
+
+    let deposit = f.bob_bytecode.len() as u64 * DEPOSIT_PER_BYTE;
+    assert_eq!(f.escrowed_deposit(), deposit * GAS_PRICE);
+    assert_eq!(plain.escrowed_deposit(), 0);
+}
+
+/// We deploy a contract before the deploy deposit is active.
+/// No deposit is escrowed, regardless of the configured pricing.
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_deploy_deposit_not_active() {
+    logger();
+    let f = Fixture::build_with_config(false, deposit_config(BLOCK_HEIGHT + 1));
+
+    make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        f.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        false,
+        GAS_PRICE,
+    );
+    f.assert_bob_contract_is_deployed();
+
+    assert_eq!(f.escrowed_deposit(), 0);
+}
+
+/// We deploy a contract from an owner the deploy policy doesn't allow.
+/// Once the policy is active, the transaction is discarded.
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_deploy_policy() {
+    logger();
+    let f = Fixture::build_with_config(false, policy_config(BLOCK_HEIGHT));
+
+    let before_balance = f.wallet_balance();
+    make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        f.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        true,
+        GAS_PRICE,
+    );
+    let after_balance = f.wallet_balance();
+    f.assert_bob_contract_is_not_deployed();
+    assert_eq!(before_balance, after_balance);
+}
+
+/// We deploy a contract from an owner the deploy policy doesn't allow,
+/// before the policy is active. The deployment succeeds.
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_deploy_policy_not_active() {
+    logger();
+    let f = Fixture::build_with_config(false, policy_config(BLOCK_HEIGHT + 1));
+
+    make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        f.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        false,
+        GAS_PRICE,
+    );
+    f.assert_bob_contract_is_deployed();
+}
+
+/// We deploy a well-formed module exporting nothing, once the bytecode
+/// validation is active. The deployment fails validation, while bob still
+/// deploys.
+#[tokio::test(flavor = "multi_thread")]
+pub async fn contract_deploy_validation() {
+    logger();
+    let f = Fixture::build_with_config(false, validation_config(BLOCK_HEIGHT));
+
+    let bytecode = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    let tx = make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        bytecode,
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        true,
+        false,
+        GAS_PRICE,
+    )
+    .expect("The transaction should be executed");
+    let err = tx.err.expect("The deployment should fail");
+    assert!(
+        err.contains("failed bytecode validation"),
+        "unexpected error {err}"
+    );
+
+    make_and_execute_transaction_deploy(
+        &f.rusk,
+        &f.wallet,
+        f.bob_bytecode.clone(),
+        GAS_LIMIT,
+        BOB_INIT_VALUE,
+        false,
+        false,
+        GAS_PRICE,
+    );
+    f.assert_bob_contract_is_deployed();
+}
@@ -15,14 +15,13 @@ use dusk_core::transfer::data::ContractCall;
 use dusk_core::transfer::{self, Transaction};
 use dusk_vm::gen_contract_id;
 use node_data::ledger::SpentTransaction;
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use std::collections::HashMap;
 use tempfile::tempdir;
 use tracing::info;
 
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure2, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -50,7 +49,7 @@ pub async fn stake_from_contract() -> Result<()> {
     // Setup the logger
     logger();
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let tmp = tempdir().expect("Should be able to create temporary directory");
     let rusk = stake_state(&tmp)?;
@@ -9,13 +9,12 @@ use std::path::Path;
 use std::sync::{Arc, RwLock};
 
 use node_data::ledger::SpentTransaction;
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -61,7 +60,7 @@ fn wallet_convert_to_moonlight(
         "The Moonlight account should have its initial balance"
     );
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let tx = wallet
         .phoenix_to_moonlight(&mut rng, 0, 0, CONVERT_VALUE, GAS_LIMIT, 1)
@@ -123,7 +122,7 @@ fn wallet_convert_to_phoenix(
         "The Phoenix notes must be of its initial value"
     );
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let tx = wallet
         .moonlight_to_phoenix(&mut rng, 0, 0, CONVERT_VALUE, GAS_LIMIT, 1)
@@ -0,0 +1,201 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::path::Path;
+
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::stake::STAKE_CONTRACT;
+use dusk_core::transfer::data::ContractCall;
+use dusk_core::transfer::moonlight::AccountData;
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use rusk::node::{RuskVmConfig, StateOverride};
+use rusk::{Error, Result, Rusk};
+use tempfile::tempdir;
+
+use crate::common::logger;
+use crate::common::state::new_state;
+use crate::common::wallet::{
+    test_wallet as wallet, TestStateClient, TestStore,
+};
+
+const INITIAL_MOONLIGHT_BALANCE: u64 = 10_000_000_000;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/convert.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, RuskVmConfig::new())
+}
+
+fn account_public_key(rusk: &Rusk) -> BlsPublicKey {
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache: Default::default(),
+        },
+    );
+    wallet
+        .account_public_key(0)
+        .expect("Getting the account public key should succeed")
+}
+
+fn dry_run_account(
+    rusk: &Rusk,
+    account: &BlsPublicKey,
+    overrides: &[StateOverride],
+) -> Result<AccountData> {
+    let account = rkyv::to_bytes::<_, 1024>(account)
+        .expect("Serializing the account should succeed")
+        .to_vec();
+    let data = rusk.dry_run_query_raw(
+        TRANSFER_CONTRACT,
+        "account",
+        account,
+        overrides,
+    )?;
+    Ok(rkyv::from_bytes(&data).expect("The account should deserialize"))
+}
+
+fn dry_run_contract_balance(
+    rusk: &Rusk,
+    overrides: &[StateOverride],
+) -> Result<u64> {
+    let contract = rkyv::to_bytes::<_, 1024>(&STAKE_CONTRACT)
+        .expect("Serializing the contract should succeed")
+        .to_vec();
+    let data = rusk.dry_run_query_raw(
+        TRANSFER_CONTRACT,
+        "contract_balance",
+        contract,
+        overrides,
+    )?;
+    Ok(rkyv::from_bytes(&data).expect("The balance should deserialize"))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn dry_run_account_balance() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+    let account = account_public_key(&rusk);
+    let root = rusk.state_root();
+
+    for balance in
+        [INITIAL_MOONLIGHT_BALANCE * 2, INITIAL_MOONLIGHT_BALANCE / 2]
+    {
+        let overrides = [StateOverride::AccountBalance { account, balance }];
+        let data = dry_run_account(&rusk, &account, &overrides)?;
+        assert_eq!(data.balance, balance, "The override should be visible");
+    }
+
+    assert_eq!(
+        rusk.account(&account)?.balance,
+        INITIAL_MOONLIGHT_BALANCE,
+        "The override should not be committed"
+    );
+    assert_eq!(rusk.state_root(), root, "The tip should not move");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn dry_run_contract_balance_override() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+    let root = rusk.state_root();
+
+    let initial_balance = dry_run_contract_balance(&rusk, &[])?;
+    let balance = initial_balance + 1_000;
+
+    let overrides = [StateOverride::ContractBalance {
+        contract: STAKE_CONTRACT,
+        balance,
+    }];
+    assert_eq!(
+        dry_run_contract_balance(&rusk, &overrides)?,
+        balance,
+        "The override should be visible"
+    );
+
+    assert_eq!(
+        dry_run_contract_balance(&rusk, &[])?,
+        initial_balance,
+        "The override should not be committed"
+    );
+    assert_eq!(rusk.state_root(), root, "The tip should not move");
+
+    // The balance of a contract can't be lowered
+    let overrides = [
+        StateOverride::ContractBalance {
+            contract: STAKE_CONTRACT,
+            balance,
+        },
+        StateOverride::ContractBalance {
+            contract: STAKE_CONTRACT,
+            balance: balance - 1,
+        },
+    ];
+    let Err(err) = dry_run_contract_balance(&rusk, &overrides) else {
+        panic!("Lowering the balance of a contract should fail");
+    };
+    assert!(
+        matches!(err, Error::InvalidOverride(_)),
+        "unexpected error {err:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn dry_run_call() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+    let account = account_public_key(&rusk);
+    let root = rusk.state_root();
+
+    let call = ContractCall::new(
+        TRANSFER_CONTRACT,
+        "add_account_balance",
+        &(account, 1_000u64),
+    )
+    .expect("Building the call should succeed");
+    let overrides = [StateOverride::Call(call)];
+
+    let data = dry_run_account(&rusk, &account, &overrides)?;
+    assert_eq!(
+        data.balance,
+        INITIAL_MOONLIGHT_BALANCE + 1_000,
+        "The override should be visible"
+    );
+
+    assert_eq!(
+        rusk.account(&account)?.balance,
+        INITIAL_MOONLIGHT_BALANCE,
+        "The override should not be committed"
+    );
+    assert_eq!(rusk.state_root(), root, "The tip should not move");
+
+    // A call to a function that doesn't exist fails the query
+    let call = ContractCall {
+        contract: TRANSFER_CONTRACT,
+        fn_name: "no_such_function".into(),
+        fn_args: vec![],
+    };
+    let overrides = [StateOverride::Call(call)];
+    assert!(
+        dry_run_account(&rusk, &account, &overrides).is_err(),
+        "A failing override should fail the query"
+    );
+
+    Ok(())
+}
@@ -12,14 +12,13 @@ use dusk_core::transfer::{
     data::{ContractCall, TransactionData},
     TRANSFER_CONTRACT,
 };
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -70,7 +69,7 @@ fn make_transactions(
         "The sender should have the given initial balance"
     );
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     // The first transaction will be a `wallet.execute` to the transfer
     // contract, querying for the root of the tree. This will be given too
@@ -4,9 +4,11 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+pub mod cluster;
 pub mod contract_deployment;
 pub mod contract_stake;
 pub mod conversion;
+pub mod dry_run;
 //pub mod deploy;
 pub mod finalization;
 pub mod gas_behavior;
@@ -9,14 +9,13 @@ use std::sync::{Arc, RwLock};
 
 use dusk_core::stake::DEFAULT_MINIMUM_STAKE;
 
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use std::collections::HashMap;
 use tempfile::tempdir;
 use tracing::info;
 
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -45,7 +44,7 @@ fn wallet_stake(
     wallet: &wallet::Wallet<TestStore, TestStateClient>,
     value: u64,
 ) {
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     wallet
         .get_stake(0)
@@ -65,6 +64,18 @@ fn wallet_stake(
     let tx = wallet
         .moonlight_stake(0, 2, value, GAS_LIMIT, GAS_PRICE)
         .expect("Failed to create a stake transaction");
+
+    let root_before_simulation = rusk.state_root();
+    let simulated = rusk
+        .simulate_transaction(&tx, BLOCK_HEIGHT)
+        .expect("simulation to succeed");
+    assert!(simulated.data.is_ok(), "simulated stake to succeed");
+    assert_eq!(
+        rusk.state_root(),
+        root_before_simulation,
+        "simulation must not change the state"
+    );
+
     let executed_txs = generator_procedure(
         rusk,
         &[tx],
@@ -81,6 +92,10 @@ fn wallet_stake(
     {
         panic!("Stake transaction failed due to {e}")
     }
+    assert_eq!(
+        executed_txs[0].gas_spent, simulated.gas_spent,
+        "simulation to spend the same gas as the execution"
+    );
 
     let stake = wallet.get_stake(2).expect("stake to be found");
     let stake_value = stake.amount.expect("stake should have an amount").value;
@@ -177,7 +192,7 @@ fn wallet_reward(
     rusk: &Rusk,
     wallet: &wallet::Wallet<TestStore, TestStateClient>,
 ) {
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let stake = wallet.get_stake(2).expect("stake to be found");
     assert_eq!(stake.reward, 0, "stake reward must be empty");
@@ -8,14 +8,13 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, RwLock};
 
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state, ExecuteResult};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -66,7 +65,7 @@ fn wallet_transfer(
         .phoenix_public_key(3)
         .expect("Failed to get public key");
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let initial_balance_0 = wallet
         .get_balance(0)
@@ -208,7 +207,7 @@ fn wallet_transfer_deploy(
         .phoenix_public_key(3)
         .expect("Failed to get public key");
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let initial_balance_0 = wallet
         .get_balance(0)
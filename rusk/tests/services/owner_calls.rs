@@ -6,8 +6,6 @@
 
 use bytecheck::CheckBytes;
 use dusk_bytes::Serializable;
-use rand::rngs::StdRng;
-use rand::SeedableRng;
 use rkyv::validation::validators::DefaultValidator;
 use rkyv::{Archive, Deserialize, Infallible, Serialize};
 use rusk::node::RuskVmConfig;
@@ -28,6 +26,7 @@ use tokio::sync::broadcast;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::DEFAULT_MIN_GAS_LIMIT;
 use crate::common::wallet::{
     test_wallet as wallet, test_wallet::Wallet, TestStateClient, TestStore,
@@ -242,7 +241,7 @@ where
 pub async fn non_bls_owner_guarded_call() -> Result<(), Error> {
     logger();
     const VALUE: u8 = 244;
-    let rng = &mut StdRng::seed_from_u64(0xcafe);
+    let rng = &mut test_rng();
     let sk = BlsSecretKey::random(rng);
     let mut f = Fixture::build(NON_BLS_OWNER);
     f.assert_bob_contract_is_deployed();
@@ -266,7 +265,7 @@ pub async fn bls_owner_guarded_call() -> Result<(), Error> {
     logger();
     const VALUE1: u8 = 244;
     const VALUE2: u8 = 233;
-    let rng = &mut StdRng::seed_from_u64(0xcafe);
+    let rng = &mut test_rng();
     let sk = BlsSecretKey::random(rng);
     let pk = BlsPublicKey::from(&sk);
     let owner = pk.to_bytes();
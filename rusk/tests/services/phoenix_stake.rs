@@ -15,14 +15,13 @@ use dusk_core::{
     transfer::data::ContractCall,
 };
 
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use std::collections::HashMap;
 use tempfile::tempdir;
 use tracing::info;
 
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -61,7 +60,7 @@ fn wallet_stake(
     wallet: &wallet::Wallet<TestStore, TestStateClient>,
     value: u64,
 ) {
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     wallet
         .get_stake(0)
@@ -193,7 +192,7 @@ fn wallet_reward(
     rusk: &Rusk,
     wallet: &wallet::Wallet<TestStore, TestStateClient>,
 ) {
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     let stake_sk = wallet.account_secret_key(2).unwrap();
     let stake_pk = BlsPublicKey::from(&stake_sk);
@@ -9,14 +9,13 @@ use std::path::Path;
 use std::sync::{Arc, RwLock};
 
 use node_data::ledger::SpentTransaction;
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -49,7 +48,7 @@ fn wallet_transfer(
         .phoenix_public_key(1)
         .expect("Failed to get public key");
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     // Store the sender initial balance
     let sender_initial_balance = wallet
@@ -131,6 +130,95 @@ fn wallet_transfer(
     );
 }
 
+/// Transfers more than the value of 4 notes, merging the notes of the sender
+/// first, one block per consolidation.
+fn wallet_transfer_consolidated(
+    rusk: &Rusk,
+    wallet: &wallet::Wallet<TestStore, TestStateClient>,
+    amount: u64,
+) {
+    const GAS_LIMIT: u64 = 1_000_000_000;
+    const GAS_PRICE: u64 = 2;
+
+    let receiver_pk = wallet
+        .phoenix_public_key(1)
+        .expect("Failed to get public key");
+
+    let mut rng = test_rng();
+
+    let sender_initial_balance = wallet
+        .get_balance(0)
+        .expect("Failed to get the balance")
+        .value;
+
+    let mut block_height = 1;
+    let mut fees = 0;
+    let mut consolidations = 0;
+
+    let cost = amount + GAS_LIMIT * GAS_PRICE;
+    while let Some(tx) = wallet
+        .phoenix_consolidate(&mut rng, 0, cost, GAS_LIMIT, GAS_PRICE)
+        .expect("Failed to plan the transfer")
+    {
+        let txs = generator_procedure(
+            rusk,
+            &[tx],
+            block_height,
+            BLOCK_GAS_LIMIT,
+            vec![],
+            None,
+        )
+        .expect("consolidation to succeed");
+        let tx = txs.first().expect("tx to be processed");
+        assert!(tx.err.is_none(), "The consolidation should not fail");
+
+        fees += tx.gas_spent * GAS_PRICE;
+        consolidations += 1;
+        block_height += 1;
+    }
+    assert!(consolidations > 0, "The notes should be consolidated");
+
+    let tx = wallet
+        .phoenix_transfer(
+            &mut rng,
+            0,
+            &receiver_pk,
+            amount,
+            GAS_LIMIT,
+            GAS_PRICE,
+        )
+        .expect("The transfer should fit in a transaction");
+    let txs = generator_procedure(
+        rusk,
+        &[tx],
+        block_height,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        None,
+    )
+    .expect("transfer to succeed");
+    let tx = txs.first().expect("tx to be processed");
+    assert!(tx.err.is_none(), "The transfer should not fail");
+    fees += tx.gas_spent * GAS_PRICE;
+
+    assert_eq!(
+        wallet
+            .get_balance(1)
+            .expect("Failed to get the balance")
+            .value,
+        amount,
+        "Wrong resulting balance for the receiver"
+    );
+    assert_eq!(
+        wallet
+            .get_balance(0)
+            .expect("Failed to get the balance")
+            .value,
+        sender_initial_balance - amount - fees,
+        "Final sender balance mismatch"
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 pub async fn wallet() -> Result<()> {
     // Setup the logger
@@ -193,3 +281,25 @@ pub async fn wallet() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn wallet_consolidated() -> Result<()> {
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+    );
+
+    // more than 4 notes are needed to cover the amount
+    wallet_transfer_consolidated(&rusk, &wallet, 6 * INITIAL_BALANCE);
+
+    Ok(())
+}
@@ -7,19 +7,19 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use dusk_core::transfer::{
     data::{ContractCall, TransactionData},
     TRANSFER_CONTRACT,
 };
-use rand::prelude::*;
-use rand::rngs::StdRng;
 use rusk::node::RuskVmConfig;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 use tracing::info;
 
 use crate::common::logger;
+use crate::common::rng::test_rng;
 use crate::common::state::{generator_procedure, new_state, ExecuteResult};
 use crate::common::wallet::{
     test_wallet as wallet, TestStateClient, TestStore,
@@ -37,9 +37,16 @@ const DEPOSIT: u64 = 0;
 
 // Creates the Rusk initial state for the tests below
 fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let vm_config = RuskVmConfig::new().with_block_gas_limit(BLOCK_GAS_LIMIT);
+    initial_state_with_config(dir, vm_config)
+}
+
+fn initial_state_with_config<P: AsRef<Path>>(
+    dir: P,
+    vm_config: RuskVmConfig,
+) -> Result<Rusk> {
     let snapshot = toml::from_str(include_str!("../config/unspendable.toml"))
         .expect("Cannot deserialize config");
-    let vm_config = RuskVmConfig::new().with_block_gas_limit(BLOCK_GAS_LIMIT);
 
     new_state(dir, &snapshot, vm_config)
 }
@@ -81,7 +88,7 @@ fn make_transactions(
         "The sender should have the given initial balance"
     );
 
-    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut rng = test_rng();
 
     // The first transaction will be a `wallet.execute` to the transfer
     // contract, querying for the root of the tree. This will be given too
@@ -205,3 +212,65 @@ pub async fn unspendable() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn execution_time_limit() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let vm_config = RuskVmConfig::new()
+        .with_block_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_time_limit(Some(Duration::ZERO));
+    let rusk = initial_state_with_config(&tmp, vm_config)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+    );
+
+    let contract_call = ContractCall {
+        contract: TRANSFER_CONTRACT,
+        fn_name: String::from("root"),
+        fn_args: Vec::new(),
+    };
+    let tx = wallet
+        .phoenix_execute(
+            &mut test_rng(),
+            SENDER_INDEX_2,
+            GAS_LIMIT_2,
+            GAS_PRICE,
+            DEPOSIT,
+            TransactionData::Call(contract_call),
+        )
+        .expect("Making the transaction should succeed");
+
+    // No transaction executes in no time, so it should be skipped, but not
+    // discarded
+    let expected = ExecuteResult {
+        discarded: 0,
+        executed: 0,
+    };
+    generator_procedure(
+        &rusk,
+        &[tx],
+        BLOCK_HEIGHT,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        Some(expected),
+    )
+    .expect("generator procedure should succeed");
+
+    // The changes of the skipped transaction should be rolled back
+    let balance = wallet
+        .get_balance(SENDER_INDEX_2)
+        .expect("Getting the balance should succeed")
+        .value;
+    assert_eq!(balance, INITIAL_BALANCE, "The balance should be untouched");
+
+    Ok(())
+}
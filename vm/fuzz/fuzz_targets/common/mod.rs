@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Shared setup of the fuzz targets.
+//!
+//! Every target executes transactions on top of the same genesis state, which
+//! is created once per fuzzing process. Each input gets a fresh session that
+//! is dropped afterwards, so inputs don't influence each other and crashes
+//! are reproducible from a single input.
+
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+use dusk_core::signatures::bls::{
+    PublicKey as AccountPublicKey, SecretKey as AccountSecretKey,
+};
+use dusk_core::transfer::{Transaction, TRANSFER_CONTRACT};
+use dusk_vm::{execute, ContractData, ExecutionConfig, Session, VM};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub const CHAIN_ID: u8 = 0xFA;
+pub const BLOCK_HEIGHT: u64 = 1;
+
+const GAS_LIMIT: u64 = u64::MAX;
+const OWNER: [u8; 32] = [0; 32];
+const GENESIS_VALUE: u64 = dusk_core::dusk(1_000_000.0);
+
+const CONFIG: ExecutionConfig = ExecutionConfig::DEFAULT;
+
+struct Genesis {
+    vm: VM,
+    base: [u8; 32],
+    sk: AccountSecretKey,
+}
+
+fn genesis() -> &'static Genesis {
+    static GENESIS: OnceLock<Genesis> = OnceLock::new();
+
+    GENESIS.get_or_init(|| {
+        let transfer_bytecode = include_bytes!(
+            "../../../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+        );
+
+        let mut rng = StdRng::seed_from_u64(0xF022);
+        let sk = AccountSecretKey::random(&mut rng);
+        let pk = AccountPublicKey::from(&sk);
+
+        let vm = VM::ephemeral().expect("Creating ephemeral VM should work");
+        let mut session = vm.genesis_session(CHAIN_ID);
+
+        session
+            .deploy(
+                transfer_bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .contract_id(TRANSFER_CONTRACT),
+                GAS_LIMIT,
+            )
+            .expect("Deploying the transfer contract should succeed");
+
+        session
+            .call::<_, ()>(
+                TRANSFER_CONTRACT,
+                "add_account_balance",
+                &(pk, GENESIS_VALUE),
+                GAS_LIMIT,
+            )
+            .expect("Inserting genesis account should succeed");
+
+        let base = session.commit().expect("Committing should succeed");
+
+        Genesis { vm, base, sk }
+    })
+}
+
+/// The secret key of the account funded at genesis.
+pub fn funded_sk() -> &'static AccountSecretKey {
+    &genesis().sk
+}
+
+/// Opens a new session on top of the genesis state.
+fn session() -> Session {
+    let genesis = genesis();
+    genesis
+        .vm
+        .session(genesis.base, CHAIN_ID, BLOCK_HEIGHT)
+        .expect("Instantiating new session should succeed")
+}
+
+/// Executes the transaction in a fresh session, checking the invariants that
+/// must hold for any input.
+///
+/// Errors returned by [`execute`] are expected, since most inputs are not
+/// valid transactions. A panic, however, is a crash of the node.
+pub fn execute_and_check(tx: &Transaction) {
+    let mut session = session();
+
+    if let Ok(receipt) = execute(&mut session, tx, &CONFIG) {
+        assert!(
+            receipt.gas_spent <= tx.gas_limit(),
+            "spent {} gas with a limit of {}",
+            receipt.gas_spent,
+            tx.gas_limit()
+        );
+    }
+}
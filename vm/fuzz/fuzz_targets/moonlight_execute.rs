@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Executes Moonlight transactions signed by a funded account, with arbitrary
+//! values, fees, nonces and payloads.
+//!
+//! Since the signature is valid and the sender is funded, inputs make it past
+//! the transfer contract's checks and exercise contract calls and deployments.
+
+#![no_main]
+
+mod common;
+
+use dusk_core::abi::ContractId;
+use dusk_core::transfer::data::{
+    ContractBytecode, ContractCall, ContractDeploy, TransactionData,
+};
+use dusk_core::transfer::Transaction;
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+
+use common::{execute_and_check, funded_sk, CHAIN_ID};
+
+#[derive(Debug, Arbitrary)]
+enum Payload {
+    None,
+    Call {
+        contract: [u8; 32],
+        fn_name: String,
+        fn_args: Vec<u8>,
+    },
+    Deploy {
+        bytecode: Vec<u8>,
+        owner: Vec<u8>,
+        init_args: Option<Vec<u8>>,
+        nonce: u64,
+    },
+    Memo(Vec<u8>),
+}
+
+impl From<Payload> for Option<TransactionData> {
+    fn from(payload: Payload) -> Self {
+        match payload {
+            Payload::None => None,
+            Payload::Call {
+                contract,
+                fn_name,
+                fn_args,
+            } => Some(
+                ContractCall {
+                    contract: ContractId::from_bytes(contract),
+                    fn_name,
+                    fn_args,
+                }
+                .into(),
+            ),
+            // Deployments with a mismatching hash are rejected early, so the
+            // hash is always computed correctly to reach the deployment itself.
+            Payload::Deploy {
+                bytecode,
+                owner,
+                init_args,
+                nonce,
+            } => Some(
+                ContractDeploy {
+                    bytecode: ContractBytecode {
+                        hash: blake3::hash(&bytecode).into(),
+                        bytes: bytecode,
+                    },
+                    owner,
+                    init_args,
+                    nonce,
+                }
+                .into(),
+            ),
+            Payload::Memo(memo) => Some(memo.into()),
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    value: u64,
+    deposit: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    nonce: u64,
+    payload: Payload,
+}
+
+fuzz_target!(|input: Input| {
+    let data: Option<TransactionData> = input.payload.into();
+
+    let tx = match Transaction::moonlight(
+        funded_sk(),
+        None,
+        input.value,
+        input.deposit,
+        input.gas_limit,
+        input.gas_price,
+        input.nonce,
+        CHAIN_ID,
+        data,
+    ) {
+        Ok(tx) => tx,
+        // e.g. a memo too large
+        Err(_) => return,
+    };
+
+    execute_and_check(&tx);
+});
@@ -0,0 +1,26 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Executes arbitrary bytes deserialized as a transaction of either type.
+//!
+//! This is the path taken by transactions received from the network, and
+//! covers Phoenix transactions, whose proofs cannot be generated while
+//! fuzzing.
+
+#![no_main]
+
+mod common;
+
+use dusk_core::transfer::Transaction;
+use libfuzzer_sys::fuzz_target;
+
+use common::execute_and_check;
+
+fuzz_target!(|bytes: &[u8]| {
+    if let Ok(tx) = Transaction::from_slice(bytes) {
+        execute_and_check(&tx);
+    }
+});
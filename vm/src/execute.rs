@@ -5,14 +5,31 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod config;
+mod metrics;
+mod policy;
+mod stream;
+mod subscriptions;
+mod upgrade;
+
+use std::time::Instant;
 
 use blake2b_simd::Params;
 use dusk_core::abi::{ContractError, ContractId, Metadata, CONTRACT_ID_BYTES};
-use dusk_core::transfer::data::ContractBytecode;
+use dusk_core::transfer::data::{ContractBytecode, MemoKind};
 use dusk_core::transfer::{Transaction, TRANSFER_CONTRACT};
 use piecrust::{CallReceipt, Error, Session};
 
-pub use config::Config;
+use crate::validate_bytecode;
+
+pub use config::{Config, DeployDeposit};
+pub use metrics::{BlockMetrics, ContractMetrics};
+pub use policy::DeployPolicy;
+pub use stream::call_streamed;
+pub use upgrade::{execute_upgrade, upgrade_contract};
+
+/// The error of a transaction aborted for exceeding the
+/// [`Config::execution_time_limit`].
+pub const EXECUTION_TIME_EXCEEDED: &str = "execution time limit exceeded";
 
 /// Executes a transaction in the provided session.
 ///
@@ -24,7 +41,10 @@ pub use config::Config;
 /// 1. Check if the transaction contains contract deployment data, and if so,
 ///    verifies if gas limit is enough for deployment and if the gas price is
 ///    sufficient for deployment. If either gas price or gas limit is not
-///    sufficient for deployment, transaction is discarded.
+///    sufficient for deployment, transaction is discarded. The same happens if
+///    the deployment is rejected by the configured [`DeployPolicy`].
+///    Transactions carrying a tagged memo are discarded as well, unless
+///    [`Config::with_tagged_memos`] is set.
 ///
 /// 2. Call the "spend_and_execute" function on the transfer contract with
 ///    unlimited gas. If this fails, an error is returned. If an error is
@@ -34,9 +54,10 @@ pub use config::Config;
 /// 3. If the transaction contains contract deployment data, additional checks
 ///    are performed and if they pass, deployment is executed. The following
 ///    checks are performed:
-///    - gas limit should be is smaller than deploy charge plus gas used for
-///      spending funds
+///    - gas limit should be is smaller than deploy charge, plus the configured
+///      [`DeployDeposit`], plus gas used for spending funds
 ///    - transaction's bytecode's bytes are consistent with bytecode's hash
+///    - transaction's bytecode passes static validation, if enabled
 ///    Deployment execution may fail for deployment-specific reasons, such as
 ///    for example:
 ///    - contract already deployed
@@ -44,15 +65,39 @@ pub use config::Config;
 ///    If deployment execution fails, the entire gas limit is consumed and error
 ///    is returned.
 ///
-/// 4. Call the "refund" function on the transfer contract with unlimited gas.
+/// 4. If event subscriptions are enabled, deliver the events emitted so far to
+///    the contracts that subscribed to them with `abi::on_event`, through the
+///    transfer contract. The gas of the callbacks is charged to the
+///    transaction, and a failing callback only has its own changes reverted.
+///    Failed deliveries don't fail the transaction, and are reported with a
+///    [`FailedDelivery`] event in its receipt.
+///
+/// 5. If an execution time limit is configured and steps 2 to 4 took longer, an
+///    [`EXECUTION_TIME_EXCEEDED`] error is returned without refunding the
+///    transaction. Unlike the other errors, the session is then left with the
+///    state changes of the transaction, since they can't be reverted from
+///    within it, and the caller must roll the session back to its state before
+///    the transaction.
+///
+/// 6. If a deposit was charged for the deployment, call the "escrow_deposit"
+///    function on the transfer contract with unlimited gas. The deposit is
+///    withheld from the refund and escrowed by the transfer contract, instead
+///    of counting as gas spent, and is recorded in the receipt with a
+///    [`ContractDeployDeposit`] event.
+///
+/// 7. Call the "refund" function on the transfer contract with unlimited gas.
 ///    The amount charged depends on the gas spent by the transaction, and the
-///    optional contract call in steps 2 or 3.
+///    optional contract call in steps 2, 3 or 4.
 ///
 /// Note that deployment transaction will never be re-executed for reasons
 /// related to deployment, as it is either discarded or it charges the
 /// full gas limit. It might be re-executed only if some other transaction
 /// failed to fit the block.
 ///
+/// Contract upgrade transactions are rejected, since replacing the bytecode
+/// of a contract consumes the session. They are executed with
+/// [`execute_upgrade`] instead.
+///
 /// # Arguments
 /// * `session` - A mutable reference to the session executing the transaction.
 /// * `tx` - The transaction to execute.
@@ -60,23 +105,80 @@ pub use config::Config;
 ///
 /// # Returns
 /// A result indicating success or failure.
+///
+/// [`FailedDelivery`]: dusk_core::abi::FailedDelivery
+/// [`ContractDeployDeposit`]: dusk_core::transfer::data::ContractDeployDeposit
 pub fn execute(
     session: &mut Session,
     tx: &Transaction,
     config: &Config,
 ) -> Result<CallReceipt<Result<Vec<u8>, ContractError>>, Error> {
+    if tx.upgrade().is_some() {
+        return Err(Error::Panic(
+            "upgrade transactions are executed with `execute_upgrade`".into(),
+        ));
+    }
+
+    // Transaction will be discarded if it carries a tagged memo before they
+    // are enabled.
+    memo_check(tx, config)?;
+
     // Transaction will be discarded if it is a deployment transaction
     // with gas limit smaller than deploy charge.
     deploy_check(tx, config)?;
 
+    let started = Instant::now();
+
+    // Spend the inputs and execute the call. If this errors the transaction is
+    // unspendable.
+    let mut receipt = spend(session, tx, config)?;
+
+    // Deploy if this is a deployment transaction and spend part is successful.
+    let deposit = contract_deploy(session, tx, config, &mut receipt);
+
+    // Deliver the events to the contracts that subscribed to them.
+    if config.with_event_subscriptions {
+        subscriptions::deliver_events(session, &mut receipt);
+    }
+
+    // Abort the transaction if it took longer than allowed, regardless of the
+    // gas it spent. Its changes are left for the caller to roll back.
+    if let Some(limit) = config.execution_time_limit {
+        if started.elapsed() > limit {
+            clear_session(session, config);
+            return Err(Error::Panic(EXECUTION_TIME_EXCEEDED.into()));
+        }
+    }
+
+    // Ensure all gas is consumed if there's an error in the contract call
+    if receipt.data.is_err() {
+        receipt.gas_spent = receipt.gas_limit;
+    }
+
+    if let Some((contract, deposit)) = deposit {
+        escrow_deposit(session, &mut receipt, contract, deposit);
+    }
+
+    refund(session, &mut receipt);
+
+    clear_session(session, config);
+
+    Ok(receipt)
+}
+
+// Spends the inputs of the transaction and executes its call, if any, setting
+// the public sender first if enabled.
+fn spend(
+    session: &mut Session,
+    tx: &Transaction,
+    config: &Config,
+) -> Result<CallReceipt<Result<Vec<u8>, ContractError>>, Error> {
     if config.with_public_sender {
         let _ = session
             .set_meta(Metadata::PUBLIC_SENDER, tx.moonlight_sender().copied());
     }
 
-    // Spend the inputs and execute the call. If this errors the transaction is
-    // unspendable.
-    let mut receipt = session
+    session
         .call::<_, Result<Vec<u8>, ContractError>>(
             TRANSFER_CONTRACT,
             "spend_and_execute",
@@ -86,19 +188,16 @@ pub fn execute(
         .map_err(|e| {
             clear_session(session, config);
             e
-        })?;
-
-    // Deploy if this is a deployment transaction and spend part is successful.
-    contract_deploy(session, tx, config, &mut receipt);
-
-    // Ensure all gas is consumed if there's an error in the contract call
-    if receipt.data.is_err() {
-        receipt.gas_spent = receipt.gas_limit;
-    }
+        })
+}
 
-    // Refund the appropriate amount to the transaction. This call is guaranteed
-    // to never error. If it does, then a programming error has occurred. As
-    // such, the call to `Result::expect` is warranted.
+// Refunds the appropriate amount to the transaction. This call is guaranteed
+// to never error. If it does, then a programming error has occurred. As such,
+// the call to `Result::expect` is warranted.
+fn refund(
+    session: &mut Session,
+    receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
+) {
     let refund_receipt = session
         .call::<_, ()>(
             TRANSFER_CONTRACT,
@@ -109,10 +208,29 @@ pub fn execute(
         .expect("Refunding must succeed");
 
     receipt.events.extend(refund_receipt.events);
+}
 
-    clear_session(session, config);
+// Moves the deposit charged for a deployment or upgrade out of the gas spent
+// by the transaction, into escrow with the transfer contract. Like refunding,
+// this call is guaranteed to never error.
+fn escrow_deposit(
+    session: &mut Session,
+    receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
+    contract: ContractId,
+    deposit: u64,
+) {
+    receipt.gas_spent -= deposit;
 
-    Ok(receipt)
+    let escrow_receipt = session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "escrow_deposit",
+            &(contract, deposit),
+            u64::MAX,
+        )
+        .expect("Escrowing the deposit must succeed");
+
+    receipt.events.extend(escrow_receipt.events);
 }
 
 fn clear_session(session: &mut Session, config: &Config) {
@@ -121,19 +239,31 @@ fn clear_session(session: &mut Session, config: &Config) {
     }
 }
 
+fn memo_check(tx: &Transaction, config: &Config) -> Result<(), Error> {
+    let tagged = tx.memo().is_some_and(|memo| memo.kind() != MemoKind::Plain);
+    if tagged && !config.with_tagged_memos {
+        return Err(Error::Panic("tagged memos are not enabled".into()));
+    }
+
+    Ok(())
+}
+
 fn deploy_check(tx: &Transaction, config: &Config) -> Result<(), Error> {
-    if tx.deploy().is_some() {
+    if tx.deploy().is_some() || tx.upgrade().is_some() {
         let gas_per_deploy_byte = config.gas_per_deploy_byte;
         let min_deploy_gas_price = config.min_deploy_gas_price;
         let deploy_charge =
             tx.deploy_charge(gas_per_deploy_byte, min_deploy_gas_price);
+        let deposit = deploy_deposit(tx, config);
 
         if tx.gas_price() < min_deploy_gas_price {
             return Err(Error::Panic("gas price too low to deploy".into()));
         }
-        if tx.gas_limit() < deploy_charge {
+        if tx.gas_limit() < deploy_charge.saturating_add(deposit) {
             return Err(Error::Panic("not enough gas to deploy".into()));
         }
+
+        config.deploy_policy.check(tx)?;
     }
 
     Ok(())
@@ -144,16 +274,21 @@ fn deploy_check(tx: &Transaction, config: &Config) -> Result<(), Error> {
 // 1) Transaction gas limit is smaller than deploy charge plus gas used for
 //    spending funds.
 // 2) Transaction's bytecode's bytes are not consistent with bytecode's hash.
-// 3) Deployment fails for deploy-specific reasons like e.g.:
+// 3) Transaction's bytecode fails static validation, if enabled.
+// 4) Deployment fails for deploy-specific reasons like e.g.:
 //      - contract already deployed
 //      - corrupted bytecode
 //      - sufficient gas to spend funds yet insufficient for deployment
+//
+// On success, the deposit charged is counted as gas spent until it is
+// escrowed, and is returned with the ID of the deployed contract.
 fn contract_deploy(
     session: &mut Session,
     tx: &Transaction,
     config: &Config,
     receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
-) {
+) -> Option<(ContractId, u64)> {
+    let mut escrow = None;
     if let Some(deploy) = tx.deploy() {
         let gas_per_deploy_byte = config.gas_per_deploy_byte;
         let min_deploy_points = config.min_deploy_points;
@@ -162,28 +297,44 @@ fn contract_deploy(
         if receipt.data.is_ok() {
             let deploy_charge =
                 tx.deploy_charge(gas_per_deploy_byte, min_deploy_points);
-            let min_gas_limit = receipt.gas_spent + deploy_charge;
+            let deposit = deploy_deposit(tx, config);
+            let min_gas_limit = receipt.gas_spent + deploy_charge + deposit;
             if gas_left < min_gas_limit {
                 receipt.data = Err(ContractError::OutOfGas);
             } else if !verify_bytecode_hash(&deploy.bytecode) {
                 receipt.data = Err(ContractError::Panic(
                     "failed bytecode hash check".into(),
                 ))
+            } else if let Err(err) = check_bytecode(&deploy.bytecode, config) {
+                receipt.data = Err(err)
             } else {
+                let contract = gen_contract_id(
+                    &deploy.bytecode.bytes,
+                    deploy.nonce,
+                    &deploy.owner,
+                );
                 let result = session.deploy_raw(
-                    Some(gen_contract_id(
-                        &deploy.bytecode.bytes,
-                        deploy.nonce,
-                        &deploy.owner,
-                    )),
+                    Some(contract),
                     deploy.bytecode.bytes.as_slice(),
                     deploy.init_args.clone(),
                     deploy.owner.clone(),
                     gas_left,
                 );
+                let result = result.and_then(|_| {
+                    upgrade::set_code_hash(
+                        session,
+                        contract,
+                        deploy.bytecode.hash,
+                    )
+                });
                 match result {
                     // Should the gas spent by the INIT method charged too?
-                    Ok(_) => receipt.gas_spent += deploy_charge,
+                    Ok(_) => {
+                        receipt.gas_spent += deploy_charge + deposit;
+                        if deposit > 0 {
+                            escrow = Some((contract, deposit));
+                        }
+                    }
                     Err(err) => {
                         let msg = format!("failed deployment: {err:?}");
                         receipt.data = Err(ContractError::Panic(msg))
@@ -192,6 +343,18 @@ fn contract_deploy(
             }
         }
     }
+    escrow
+}
+
+// The deposit charged for the state occupied by the contract deployed or
+// upgraded by the transaction, if any.
+fn deploy_deposit(tx: &Transaction, config: &Config) -> u64 {
+    let bytecode = match (tx.deploy(), tx.upgrade()) {
+        (Some(deploy), _) => &deploy.bytecode,
+        (_, Some(upgrade)) => &upgrade.bytecode,
+        _ => return 0,
+    };
+    config.deploy_deposit.charge(&bytecode.bytes)
 }
 
 // Verifies that the stored contract bytecode hash is correct.
@@ -201,6 +364,25 @@ fn verify_bytecode_hash(bytecode: &ContractBytecode) -> bool {
     bytecode.hash == computed
 }
 
+// Statically validates the contract bytecode, if enabled.
+fn check_bytecode(
+    bytecode: &ContractBytecode,
+    config: &Config,
+) -> Result<(), ContractError> {
+    if !config.with_bytecode_validation {
+        return Ok(());
+    }
+
+    validate_bytecode(bytecode.bytes.as_slice()).map_err(|diagnostics| {
+        let diagnostics: Vec<_> =
+            diagnostics.iter().map(ToString::to_string).collect();
+        ContractError::Panic(format!(
+            "failed bytecode validation: {}",
+            diagnostics.join(", ")
+        ))
+    })
+}
+
 /// Generates a unique identifier for a smart contract.
 ///
 /// # Arguments
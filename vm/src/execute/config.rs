@@ -4,6 +4,11 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::time::Duration;
+
+use super::DeployPolicy;
+use crate::initial_memory_pages;
+
 /// Configuration for the execution of a transaction.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -18,6 +23,32 @@ pub struct Config {
     ///
     /// This field may be deprecated after the feature rollout.
     pub with_public_sender: bool,
+    /// Enable the static validation of the bytecode of deployed contracts.
+    ///
+    /// This field may be deprecated after the feature rollout.
+    pub with_bytecode_validation: bool,
+    /// Enable the delivery of events to the contracts subscribed to them.
+    ///
+    /// This field may be deprecated after the feature rollout.
+    pub with_event_subscriptions: bool,
+    /// Accept transactions carrying a tagged memo.
+    ///
+    /// This field may be deprecated after the feature rollout.
+    pub with_tagged_memos: bool,
+    /// The policy restricting which contract deployments are accepted.
+    pub deploy_policy: DeployPolicy,
+    /// The deposit escrowed for the state a deployed contract occupies.
+    ///
+    /// This field may be deprecated after the feature rollout.
+    pub deploy_deposit: DeployDeposit,
+    /// The maximum wall-clock time the contract calls of a transaction may
+    /// take, no matter the gas they spend.
+    ///
+    /// Since the time taken differs between nodes, this should only be set
+    /// when generating a block, never when verifying one. A transaction going
+    /// over it leaves its changes in the session, which must be rolled back
+    /// by the caller.
+    pub execution_time_limit: Option<Duration>,
 }
 
 impl Default for Config {
@@ -33,5 +64,51 @@ impl Config {
         min_deploy_points: 0,
         min_deploy_gas_price: 0,
         with_public_sender: false,
+        with_bytecode_validation: false,
+        with_event_subscriptions: false,
+        with_tagged_memos: false,
+        deploy_policy: DeployPolicy::ALLOW_ALL,
+        deploy_deposit: DeployDeposit::NONE,
+        execution_time_limit: None,
     };
 }
+
+/// The deposit, in gas, required on deployment for the state the contract
+/// occupies, on top of the deploy charge. Upgrades require the deposit of
+/// their new bytecode.
+///
+/// Unlike the deploy charge, the deposit is not spent: its value is withheld
+/// from the refund of the transaction and escrowed by the transfer contract,
+/// which must therefore support it. The escrow is recorded in the receipt of
+/// the deployment or upgrade with a [`ContractDeployDeposit`] event.
+///
+/// [`ContractDeployDeposit`]: dusk_core::transfer::data::ContractDeployDeposit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeployDeposit {
+    /// The gas charged for each byte of the bytecode.
+    pub gas_per_byte: u64,
+    /// The gas charged for each 64KiB page of initial memory the contract
+    /// declares.
+    pub gas_per_memory_page: u64,
+}
+
+impl DeployDeposit {
+    /// No deposit is required.
+    pub const NONE: DeployDeposit = DeployDeposit {
+        gas_per_byte: 0,
+        gas_per_memory_page: 0,
+    };
+
+    /// Returns the deposit required for deploying the given bytecode.
+    pub fn charge(&self, bytecode: &[u8]) -> u64 {
+        if *self == Self::NONE {
+            return 0;
+        }
+
+        let bytes = bytecode.len() as u64;
+        let pages = initial_memory_pages(bytecode);
+        bytes
+            .saturating_mul(self.gas_per_byte)
+            .saturating_add(pages.saturating_mul(self.gas_per_memory_page))
+    }
+}
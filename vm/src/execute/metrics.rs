@@ -0,0 +1,70 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::BTreeMap;
+
+use dusk_core::abi::ContractId;
+use piecrust::CallReceipt;
+
+/// Execution statistics of a single contract over a block.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContractMetrics {
+    /// The gas spent in calls to the contract.
+    ///
+    /// This excludes the gas spent by the inter-contract calls the contract
+    /// made, which is accounted to the contracts called.
+    pub gas_spent: u64,
+    /// The number of times the contract was called.
+    pub calls: u64,
+}
+
+/// Execution statistics aggregated over the transactions of a block.
+///
+/// Metrics are collected by calling [`BlockMetrics::record`] with the receipt
+/// of each transaction included in the block.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BlockMetrics {
+    /// The total gas spent by the recorded transactions.
+    pub gas_spent: u64,
+    /// The number of recorded transactions.
+    pub transactions: u64,
+    /// The largest number of calls made by a single transaction.
+    pub max_calls: u64,
+    /// The statistics of every contract called in the block.
+    pub contracts: BTreeMap<ContractId, ContractMetrics>,
+}
+
+impl BlockMetrics {
+    /// Records the execution of a transaction.
+    pub fn record<T>(&mut self, receipt: &CallReceipt<T>) {
+        self.gas_spent += receipt.gas_spent;
+        self.transactions += 1;
+
+        let mut calls = 0;
+        for elem in receipt.call_tree.iter() {
+            let metrics = self.contracts.entry(elem.contract_id).or_default();
+            metrics.gas_spent += elem.spent;
+            metrics.calls += 1;
+            calls += 1;
+        }
+        self.max_calls = self.max_calls.max(calls);
+    }
+
+    /// Returns the `n` contracts that spent the most gas, in descending order.
+    pub fn top_consumers(
+        &self,
+        n: usize,
+    ) -> Vec<(ContractId, ContractMetrics)> {
+        let mut contracts: Vec<_> = self
+            .contracts
+            .iter()
+            .map(|(contract, metrics)| (*contract, *metrics))
+            .collect();
+        contracts.sort_by(|(_, a), (_, b)| b.gas_spent.cmp(&a.gas_spent));
+        contracts.truncate(n);
+        contracts
+    }
+}
@@ -0,0 +1,201 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::transfer::Transaction;
+use piecrust::Error;
+use wasmparser::{Parser, Payload};
+
+/// Policy restricting which contract deployments are accepted.
+///
+/// The policy is evaluated before a deployment transaction is executed. A
+/// transaction violating the policy is discarded, the same way a deployment
+/// with an insufficient gas price is. The new bytecode of an upgrade is held
+/// to the same policy, as if the owner of the upgraded contract deployed it.
+///
+/// The default policy, [`DeployPolicy::ALLOW_ALL`], accepts every deployment
+/// and is what a permissionless network is expected to use. Node operators
+/// running a permissioned network may restrict deployments further.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployPolicy {
+    /// The owners allowed to deploy contracts.
+    ///
+    /// If `None` any owner is allowed to deploy.
+    pub allowed_owners: Option<Vec<Vec<u8>>>,
+    /// The minimum deposit a deployment transaction must carry.
+    pub min_deposit: u64,
+    /// The maximum size, in bytes, of the deployed bytecode.
+    ///
+    /// If `None` the bytecode size is not restricted.
+    pub max_bytecode_size: Option<usize>,
+    /// The names of the imported functions a contract may not use.
+    ///
+    /// An entry either matches the import name alone (e.g. `"hq"`), or the
+    /// module and name joined by a `.` (e.g. `"env.hq"`).
+    pub banned_imports: Vec<String>,
+}
+
+impl Default for DeployPolicy {
+    fn default() -> Self {
+        Self::ALLOW_ALL
+    }
+}
+
+impl DeployPolicy {
+    /// A policy accepting every deployment.
+    pub const ALLOW_ALL: DeployPolicy = DeployPolicy {
+        allowed_owners: None,
+        min_deposit: 0,
+        max_bytecode_size: None,
+        banned_imports: Vec::new(),
+    };
+
+    /// Checks the deployment contained in the given transaction against the
+    /// policy.
+    ///
+    /// Transactions not carrying a deployment always pass the check.
+    ///
+    /// # Errors
+    /// If the deployment violates the policy, an [`Error::Panic`] describing
+    /// the violation is returned.
+    pub fn check(&self, tx: &Transaction) -> Result<(), Error> {
+        let deploy = match tx.deploy() {
+            Some(deploy) => deploy,
+            None => return Ok(()),
+        };
+
+        self.check_contract(&deploy.owner, tx.deposit(), &deploy.bytecode.bytes)
+    }
+
+    /// Checks the upgrade contained in the given transaction against the
+    /// policy, as if the given owner of the upgraded contract deployed the
+    /// new bytecode.
+    ///
+    /// Transactions not carrying an upgrade always pass the check.
+    ///
+    /// # Errors
+    /// If the upgrade violates the policy, an [`Error::Panic`] describing the
+    /// violation is returned.
+    pub fn check_upgrade(
+        &self,
+        tx: &Transaction,
+        owner: &[u8],
+    ) -> Result<(), Error> {
+        let upgrade = match tx.upgrade() {
+            Some(upgrade) => upgrade,
+            None => return Ok(()),
+        };
+
+        self.check_contract(owner, tx.deposit(), &upgrade.bytecode.bytes)
+    }
+
+    fn check_contract(
+        &self,
+        owner: &[u8],
+        deposit: u64,
+        bytecode: &[u8],
+    ) -> Result<(), Error> {
+        if let Some(allowed_owners) = &self.allowed_owners {
+            if !allowed_owners.iter().any(|allowed| allowed == owner) {
+                return Err(Error::Panic("owner not allowed to deploy".into()));
+            }
+        }
+
+        if deposit < self.min_deposit {
+            return Err(Error::Panic("deposit too low to deploy".into()));
+        }
+
+        if let Some(max_bytecode_size) = self.max_bytecode_size {
+            if bytecode.len() > max_bytecode_size {
+                return Err(Error::Panic(
+                    "bytecode too large to deploy".into(),
+                ));
+            }
+        }
+
+        if !self.banned_imports.is_empty() {
+            self.check_imports(bytecode)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_imports(&self, bytecode: &[u8]) -> Result<(), Error> {
+        for payload in Parser::new(0).parse_all(bytecode) {
+            let payload = payload.map_err(|err| {
+                Error::Panic(format!("invalid bytecode: {err}"))
+            })?;
+
+            if let Payload::ImportSection(reader) = payload {
+                for import in reader {
+                    let import = import.map_err(|err| {
+                        Error::Panic(format!("invalid bytecode: {err}"))
+                    })?;
+
+                    if self.is_banned(import.module, import.name) {
+                        return Err(Error::Panic(format!(
+                            "banned import: {}.{}",
+                            import.module, import.name
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_banned(&self, module: &str, name: &str) -> bool {
+        self.banned_imports
+            .iter()
+            .any(|banned| match banned.split_once('.') {
+                Some((banned_module, banned_name)) => {
+                    banned_module == module && banned_name == name
+                }
+                None => banned == name,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (module (import "env" "hq" (func)) (import "env" "c" (func)))
+    const IMPORTING_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+        0x02, 0x12, 0x02, // import section, two entries
+        0x03, b'e', b'n', b'v', 0x02, b'h', b'q', 0x00, 0x00, // env.hq
+        0x03, b'e', b'n', b'v', 0x01, b'c', 0x00, 0x00, // env.c
+    ];
+
+    fn policy(banned_imports: &[&str]) -> DeployPolicy {
+        DeployPolicy {
+            banned_imports: banned_imports
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ..DeployPolicy::ALLOW_ALL
+        }
+    }
+
+    #[test]
+    fn banned_imports() {
+        assert!(policy(&[]).check_imports(IMPORTING_MODULE).is_ok());
+        assert!(policy(&["foo"]).check_imports(IMPORTING_MODULE).is_ok());
+        assert!(policy(&["other.hq"])
+            .check_imports(IMPORTING_MODULE)
+            .is_ok());
+
+        assert!(policy(&["hq"]).check_imports(IMPORTING_MODULE).is_err());
+        assert!(policy(&["env.c"]).check_imports(IMPORTING_MODULE).is_err());
+    }
+
+    #[test]
+    fn invalid_bytecode() {
+        assert!(policy(&["hq"]).check_imports(&[0xde, 0xad]).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{ArgChunk, ContractId, STREAM_CHUNK_FN};
+use piecrust::{CallReceipt, Error, Session};
+
+/// Calls a function of a contract with an argument that may be larger than
+/// [`ARGBUF_LEN`].
+///
+/// This is the host counterpart of `abi::call_streamed`: the argument is sent
+/// in chunks to the `stream_receiver!` entrypoint of the contract, after which
+/// `fn_name` is called without arguments, retrieving the argument with
+/// `abi::take_streamed_arg`.
+///
+/// Each chunk is a separate call with the gas left by the previous ones, so
+/// the gas spent grows with the number of chunks. The returned receipt
+/// accounts for the gas spent and the events emitted by all the calls.
+///
+/// # Errors
+/// Errors if sending any of the chunks fails, or if the call fails.
+///
+/// [`ARGBUF_LEN`]: dusk_core::abi::ARGBUF_LEN
+pub fn call_streamed(
+    session: &mut Session,
+    contract: ContractId,
+    fn_name: &str,
+    arg: &[u8],
+    gas_limit: u64,
+) -> Result<CallReceipt<Vec<u8>>, Error> {
+    let mut gas_spent = 0;
+    let mut events = Vec::new();
+
+    for chunk in ArgChunk::split(arg) {
+        let receipt = session.call::<_, ()>(
+            contract,
+            STREAM_CHUNK_FN,
+            &chunk,
+            gas_limit - gas_spent,
+        )?;
+        gas_spent += receipt.gas_spent;
+        events.extend(receipt.events);
+    }
+
+    let mut receipt = session.call_raw(
+        contract,
+        fn_name,
+        Vec::new(),
+        gas_limit - gas_spent,
+    )?;
+
+    receipt.gas_spent += gas_spent;
+    receipt.gas_limit = gas_limit;
+    events.append(&mut receipt.events);
+    receipt.events = events;
+
+    Ok(receipt)
+}
@@ -0,0 +1,127 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::abi::{
+    ContractError, ContractId, EventDelivery, EventSubscription,
+    FailedDelivery, DELIVERY_FAILED_TOPIC, SUBSCRIBE_TOPIC,
+};
+use dusk_core::transfer::TRANSFER_CONTRACT;
+use piecrust::{CallReceipt, Event, Session};
+use rkyv::{Deserialize, Infallible};
+
+/// Delivers the events of the receipt to the contracts that subscribed to
+/// them during the transaction.
+///
+/// Events are processed in emission order, and a subscription only applies to
+/// the events emitted after it. Events emitted by the callbacks are appended to
+/// the receipt and processed as well, so the gas limit of the transaction is
+/// what bounds the delivery.
+///
+/// Each event is delivered through the `deliver_event` entrypoint of the
+/// transfer contract, so the callbacks run within the ongoing transaction,
+/// with the transfer contract as their caller. If a callback fails, only its
+/// changes are reverted and the gas it spent is charged like that of any
+/// other. If the transaction runs out of gas, the delivery stops and the gas
+/// left is charged.
+///
+/// Since the changes of the transaction's call can't be reverted at this
+/// point, a failed delivery never fails the receipt. It is reported instead
+/// with a [`FailedDelivery`] event appended to it.
+pub(super) fn deliver_events(
+    session: &mut Session,
+    receipt: &mut CallReceipt<Result<Vec<u8>, ContractError>>,
+) {
+    let mut subscriptions = Vec::<(ContractId, EventSubscription)>::new();
+
+    let mut index = 0;
+    'events: while receipt.data.is_ok() && index < receipt.events.len() {
+        let event = receipt.events[index].clone();
+        index += 1;
+
+        if event.topic == SUBSCRIBE_TOPIC {
+            // Malformed subscriptions are ignored, as any other event.
+            if let Some(subscription) = parse_subscription(&event.data) {
+                subscriptions.push((event.source, subscription));
+            }
+            continue;
+        }
+        if event.topic == DELIVERY_FAILED_TOPIC {
+            continue;
+        }
+
+        let subscribers: Vec<_> = subscriptions
+            .iter()
+            .filter(|(_, subscription)| {
+                subscription.contract == event.source
+                    && subscription.topic == event.topic
+            })
+            .map(|(subscriber, _)| *subscriber)
+            .collect();
+
+        for subscriber in subscribers {
+            let delivery = EventDelivery {
+                source: event.source,
+                topic: event.topic.clone(),
+                data: event.data.clone(),
+            };
+            let gas_left = receipt.gas_limit - receipt.gas_spent;
+
+            match session.call::<_, Result<(), ContractError>>(
+                TRANSFER_CONTRACT,
+                "deliver_event",
+                &(subscriber, delivery),
+                gas_left,
+            ) {
+                Ok(callback_receipt) => {
+                    receipt.gas_spent += callback_receipt.gas_spent;
+                    receipt.events.extend(callback_receipt.events);
+                    if let Err(error) = callback_receipt.data {
+                        let failure =
+                            failed_delivery(subscriber, &event, error);
+                        receipt.events.push(failure);
+                    }
+                }
+                Err(err) => {
+                    // The delivery itself failed, so its gas is unknown and
+                    // the gas left is charged.
+                    receipt.gas_spent = receipt.gas_limit;
+                    let error = ContractError::Panic(format!("{err}"));
+                    let failure = failed_delivery(subscriber, &event, error);
+                    receipt.events.push(failure);
+                    break 'events;
+                }
+            }
+        }
+    }
+}
+
+fn parse_subscription(data: &[u8]) -> Option<EventSubscription> {
+    let archived = rkyv::check_archived_root::<EventSubscription>(data).ok()?;
+    archived.deserialize(&mut Infallible).ok()
+}
+
+// Reports the failure to deliver an event to a subscriber.
+fn failed_delivery(
+    subscriber: ContractId,
+    event: &Event,
+    error: ContractError,
+) -> Event {
+    let failure = FailedDelivery {
+        subscriber,
+        source: event.source,
+        topic: event.topic.clone(),
+        error,
+    };
+    let data = rkyv::to_bytes::<_, 256>(&failure)
+        .expect("Serializing the failed delivery should succeed")
+        .to_vec();
+
+    Event {
+        source: TRANSFER_CONTRACT,
+        topic: DELIVERY_FAILED_TOPIC.into(),
+        data,
+    }
+}
@@ -0,0 +1,260 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::DeserializableSlice;
+use dusk_core::abi::{ContractError, ContractId};
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::transfer::data::{
+    ContractUpgrade, ContractUpgradeEvent, MIGRATE_FN, UPGRADE_TOPIC,
+};
+use dusk_core::transfer::{Transaction, TRANSFER_CONTRACT};
+use piecrust::{CallReceipt, ContractData, Error, Event, Session};
+
+use super::{
+    check_bytecode, clear_session, deploy_check, deploy_deposit,
+    escrow_deposit, refund, spend, verify_bytecode_hash,
+};
+use crate::execute::Config;
+
+/// The receipt of an executed transaction.
+type TxReceipt = CallReceipt<Result<Vec<u8>, ContractError>>;
+
+/// Executes a contract upgrade transaction in the provided session.
+///
+/// The transaction is spent like any other, after which the upgrade is
+/// performed with [`upgrade_contract`], using the gas left by the spending.
+/// Replacing the bytecode consumes the session, so it is taken by value and
+/// returned along with the receipt.
+///
+/// The new bytecode goes through the same checks as the bytecode of a
+/// deployment. An upgrade violating the [`DeployPolicy`] is discarded, as if
+/// the owner of the contract deployed the new bytecode. If the upgrade is
+/// rejected otherwise, e.g. by the static validation, the transaction fails
+/// and consumes its entire gas limit, as a failed deployment does. If it
+/// succeeds, the deploy charge and the gas spent by the migration are charged,
+/// and the [`ContractUpgradeEvent`] is recorded in the receipt. The
+/// [`DeployDeposit`] for the new bytecode is escrowed by the transfer contract,
+/// and recorded with a [`ContractDeployDeposit`] event if it isn't zero.
+///
+/// # Errors
+/// Errors when the transaction is not an upgrade, cannot be spent, or when
+/// the migration fails. In the latter case the session is lost, and the
+/// state should be rolled back to before the transaction, e.g. with a
+/// [`Checkpoint`].
+///
+/// [`Checkpoint`]: crate::Checkpoint
+/// [`DeployPolicy`]: crate::DeployPolicy
+/// [`DeployDeposit`]: crate::DeployDeposit
+/// [`ContractDeployDeposit`]: dusk_core::transfer::data::ContractDeployDeposit
+pub fn execute_upgrade(
+    mut session: Session,
+    tx: &Transaction,
+    config: &Config,
+) -> Result<(Session, TxReceipt), Error> {
+    let upgrade = tx
+        .upgrade()
+        .ok_or_else(|| Error::Panic("not an upgrade transaction".into()))?;
+
+    deploy_check(tx, config)?;
+
+    // Upgrades of missing contracts are rejected by `check_upgrade` instead
+    if let Some(metadata) = session.contract_metadata(&upgrade.contract) {
+        config.deploy_policy.check_upgrade(tx, &metadata.owner)?;
+    }
+
+    let mut receipt = spend(&mut session, tx, config)?;
+
+    let deploy_charge =
+        tx.deploy_charge(config.gas_per_deploy_byte, config.min_deploy_points);
+    let deposit = deploy_deposit(tx, config);
+    let gas_left = tx.gas_limit() - receipt.gas_spent;
+
+    if receipt.data.is_ok() {
+        if gas_left < deploy_charge + deposit {
+            receipt.data = Err(ContractError::OutOfGas);
+        } else if let Err(err) = check_upgrade(&mut session, upgrade) {
+            receipt.data = Err(ContractError::Panic(format!("{err}")));
+        } else if let Err(err) = check_bytecode(&upgrade.bytecode, config) {
+            receipt.data = Err(err);
+        } else {
+            let gas_limit = gas_left - deploy_charge - deposit;
+            let (s, gas_spent, event) =
+                upgrade_contract(session, upgrade, gas_limit)?;
+            session = s;
+            receipt.gas_spent += deploy_charge + deposit + gas_spent;
+            receipt.events.push(event);
+            if deposit > 0 {
+                escrow_deposit(
+                    &mut session,
+                    &mut receipt,
+                    upgrade.contract,
+                    deposit,
+                );
+            }
+        }
+    }
+
+    // Ensure all gas is consumed if the upgrade was rejected
+    if receipt.data.is_err() {
+        receipt.gas_spent = receipt.gas_limit;
+    }
+
+    refund(&mut session, &mut receipt);
+    clear_session(&mut session, config);
+
+    Ok((session, receipt))
+}
+
+/// Upgrades the bytecode of a deployed contract, preserving its ID.
+///
+/// The upgrade must be signed by the owner of the contract, and its
+/// `prev_hash` must be the hash of the bytecode recorded by the transfer
+/// contract when the contract was deployed or last upgraded. Its `chain_id`
+/// must be the one of the session, and its `nonce` must follow the number of
+/// upgrades recorded for the contract, so that an upgrade cannot be replayed
+/// on another network, nor once the contract returns to the bytecode it
+/// replaced. The new bytecode is deployed alongside the old
+/// one and, if the upgrade carries migrate arguments, its [`MIGRATE_FN`]
+/// entrypoint is called with them. Only once this succeeds does the new
+/// bytecode replace the old one, so a failing migration leaves the contract
+/// untouched.
+///
+/// On success, returns the session with the upgrade applied, the gas spent by
+/// the migration, and a [`ContractUpgradeEvent`] recording the old and new
+/// bytecode hashes, emitted under the [`UPGRADE_TOPIC`] topic.
+///
+/// # Errors
+/// Errors if the contract does not exist, if the bytecode hashes, the chain
+/// ID, the nonce or the owner's signature do not verify, or if the migration
+/// fails.
+pub fn upgrade_contract(
+    mut session: Session,
+    upgrade: &ContractUpgrade,
+    gas_limit: u64,
+) -> Result<(Session, u64, Event), Error> {
+    let owner = check_upgrade(&mut session, upgrade)?;
+
+    let mut gas_spent = 0;
+    let mut session = session.migrate(
+        upgrade.contract,
+        &upgrade.bytecode.bytes,
+        ContractData::<()>::builder().owner(owner),
+        gas_limit,
+        |new_contract, session| {
+            if let Some(migrate_args) = &upgrade.migrate_args {
+                let receipt = session.call_raw(
+                    new_contract,
+                    MIGRATE_FN,
+                    migrate_args.clone(),
+                    gas_limit,
+                )?;
+                gas_spent = receipt.gas_spent;
+            }
+            Ok(())
+        },
+    )?;
+
+    session.call::<_, ()>(
+        TRANSFER_CONTRACT,
+        "record_upgrade",
+        &(upgrade.contract, upgrade.bytecode.hash),
+        u64::MAX,
+    )?;
+
+    let event = ContractUpgradeEvent {
+        contract: upgrade.contract,
+        old_hash: upgrade.prev_hash,
+        new_hash: upgrade.bytecode.hash,
+    };
+    let data = rkyv::to_bytes::<_, 128>(&event)
+        .map_err(|err| Error::Panic(format!("{err:?}")))?
+        .to_vec();
+
+    let event = Event {
+        source: upgrade.contract,
+        topic: UPGRADE_TOPIC.into(),
+        data,
+    };
+
+    Ok((session, gas_spent, event))
+}
+
+/// Checks an upgrade can be applied, returning the owner of the contract.
+fn check_upgrade(
+    session: &mut Session,
+    upgrade: &ContractUpgrade,
+) -> Result<Vec<u8>, Error> {
+    let owner = session
+        .contract_metadata(&upgrade.contract)
+        .ok_or(Error::ContractDoesNotExist(upgrade.contract))?
+        .owner
+        .clone();
+
+    if !verify_bytecode_hash(&upgrade.bytecode) {
+        return Err(Error::Panic("failed bytecode hash check".into()));
+    }
+
+    if code_hash(session, upgrade.contract)? != Some(upgrade.prev_hash) {
+        return Err(Error::Panic("failed previous bytecode hash check".into()));
+    }
+
+    let chain_id: u8 = session
+        .call(TRANSFER_CONTRACT, "chain_id", &(), u64::MAX)?
+        .data;
+    if upgrade.chain_id != chain_id {
+        return Err(Error::Panic("failed chain id check".into()));
+    }
+
+    let nonce: u64 = session
+        .call(
+            TRANSFER_CONTRACT,
+            "upgrade_nonce",
+            &upgrade.contract,
+            u64::MAX,
+        )?
+        .data;
+    if upgrade.nonce != nonce + 1 {
+        return Err(Error::Panic("failed upgrade nonce check".into()));
+    }
+
+    let owner_pk = BlsPublicKey::from_slice(&owner)
+        .map_err(|_| Error::Panic("owner is not a BLS public key".into()))?;
+    if owner_pk
+        .verify(&upgrade.signature, &upgrade.signature_message())
+        .is_err()
+    {
+        return Err(Error::Panic("invalid upgrade signature".into()));
+    }
+
+    Ok(owner)
+}
+
+/// Returns the hash of the bytecode of a contract recorded by the transfer
+/// contract, if any.
+fn code_hash(
+    session: &mut Session,
+    contract: ContractId,
+) -> Result<Option<[u8; 32]>, Error> {
+    session
+        .call(TRANSFER_CONTRACT, "code_hash", &contract, u64::MAX)
+        .map(|receipt| receipt.data)
+}
+
+/// Records the hash of the bytecode of a contract in the transfer contract.
+pub(crate) fn set_code_hash(
+    session: &mut Session,
+    contract: ContractId,
+    hash: [u8; 32],
+) -> Result<(), Error> {
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "set_code_hash",
+            &(contract, hash),
+            u64::MAX,
+        )
+        .map(|_| ())
+}
@@ -6,8 +6,11 @@
 
 //! The host-queries registered on the Dusk VM
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::any::Any;
 
+use blake2b_simd::Params;
 use dusk_bytes::DeserializableSlice;
 use dusk_core::groth16::bn254::{Bn254, G1Projective};
 use dusk_core::groth16::serialize::CanonicalDeserialize;
@@ -24,11 +27,23 @@ use dusk_core::signatures::schnorr::{
 };
 use dusk_core::BlsScalar;
 use dusk_poseidon::{Domain, Hash as PoseidonHash};
+use piecrust::HostQuery;
 use rkyv::ser::serializers::AllocSerializer;
 use rkyv::{Archive, Deserialize, Serialize};
+use sha2::{Digest, Sha512};
 
 use crate::cache;
 
+/// The base gas price of the [`blake2b`] host query.
+pub const BLAKE2B_BASE_PRICE: u64 = 2_000;
+/// The gas price of the [`blake2b`] host query per byte of its argument.
+pub const BLAKE2B_BYTE_PRICE: u64 = 10;
+
+/// The base gas price of the [`sha512`] host query.
+pub const SHA512_BASE_PRICE: u64 = 2_000;
+/// The gas price of the [`sha512`] host query per byte of its argument.
+pub const SHA512_BYTE_PRICE: u64 = 15;
+
 /// Computes a cryptographic hash of a byte vector.
 ///
 /// This function uses the BLS12-381 scalar field to generate a deterministic
@@ -70,6 +85,47 @@ pub fn poseidon_hash(scalars: Vec<BlsScalar>) -> BlsScalar {
     PoseidonHash::digest(Domain::Other, &scalars)[0]
 }
 
+/// Computes the BLAKE2b hash of a byte vector.
+///
+/// The length of the output and the personalization are configurable, to
+/// support the BLAKE2b variants used by other chains, such as the 32 bytes
+/// personalized hashes of Zcash and the unpersonalized ones of Substrate.
+///
+/// # Arguments
+/// * `bytes` - A vector of bytes representing the input data to be hashed.
+/// * `hash_len` - The length of the output, between 1 and 64 bytes.
+/// * `personal` - The personalization of the hash. All zeroes is equivalent to
+///   no personalization.
+///
+/// # Returns
+/// A vector of `hash_len` bytes with the BLAKE2b hash of the input bytes, or
+/// an empty vector if `hash_len` is not between 1 and 64.
+pub fn blake2b(bytes: Vec<u8>, hash_len: u8, personal: [u8; 16]) -> Vec<u8> {
+    if !(1..=64).contains(&hash_len) {
+        return Vec::new();
+    }
+
+    Params::new()
+        .hash_length(hash_len as usize)
+        .personal(&personal)
+        .hash(&bytes)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Computes the SHA-512 hash of a byte vector.
+///
+/// # Arguments
+/// * `bytes` - A vector of bytes representing the input data to be hashed.
+///
+/// # Returns
+/// An array of 64 bytes with the SHA-512 hash of the input bytes.
+pub fn sha512(bytes: Vec<u8>) -> [u8; 64] {
+    let mut hash = [0u8; 64];
+    hash.copy_from_slice(&Sha512::digest(bytes));
+    hash
+}
+
 /// Verifies a PLONK zero-knowledge proof.
 ///
 /// This function verifies a proof generated by a PLONK proving system. It takes
@@ -238,6 +294,51 @@ where
     bytes.len() as u32
 }
 
+/// A host query charging a base price plus a price per byte of its argument,
+/// for the queries whose cost grows with the length of their input.
+pub(crate) struct PricedQuery<F> {
+    query: F,
+    base_price: u64,
+    byte_price: u64,
+}
+
+impl<F> PricedQuery<F> {
+    pub(crate) const fn new(
+        query: F,
+        base_price: u64,
+        byte_price: u64,
+    ) -> Self {
+        Self {
+            query,
+            base_price,
+            byte_price,
+        }
+    }
+}
+
+impl<F> HostQuery for PricedQuery<F>
+where
+    F: Send + Sync + Fn(&mut [u8], u32) -> u32,
+{
+    fn deserialize_and_price(
+        &self,
+        arg_buf: &[u8],
+        arg: &mut Box<dyn Any>,
+    ) -> u64 {
+        let arg_len = arg_buf.len();
+        *arg = Box::new(arg_len as u32);
+        self.base_price
+            .saturating_add(self.byte_price.saturating_mul(arg_len as u64))
+    }
+
+    fn execute(&self, arg: &Box<dyn Any>, arg_buf: &mut [u8]) -> u32 {
+        let arg_len = *arg
+            .downcast_ref::<u32>()
+            .expect("The argument length should be stored when pricing");
+        (self.query)(arg_buf, arg_len)
+    }
+}
+
 pub(crate) fn host_hash(arg_buf: &mut [u8], arg_len: u32) -> u32 {
     wrap_host_query(arg_buf, arg_len, hash)
 }
@@ -246,6 +347,16 @@ pub(crate) fn host_poseidon_hash(arg_buf: &mut [u8], arg_len: u32) -> u32 {
     wrap_host_query(arg_buf, arg_len, poseidon_hash)
 }
 
+pub(crate) fn host_blake2b(arg_buf: &mut [u8], arg_len: u32) -> u32 {
+    wrap_host_query(arg_buf, arg_len, |(bytes, hash_len, personal)| {
+        blake2b(bytes, hash_len, personal)
+    })
+}
+
+pub(crate) fn host_sha512(arg_buf: &mut [u8], arg_len: u32) -> u32 {
+    wrap_host_query(arg_buf, arg_len, sha512)
+}
+
 pub(crate) fn host_verify_plonk(arg_buf: &mut [u8], arg_len: u32) -> u32 {
     let hash = *blake2b_simd::blake2b(&arg_buf[..arg_len as usize]).as_array();
     let cached = cache::get_plonk_verification(hash);
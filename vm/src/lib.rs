@@ -13,10 +13,18 @@
 
 extern crate alloc;
 
-pub use self::execute::{execute, gen_contract_id, Config as ExecutionConfig};
+pub use self::execute::{
+    call_streamed, execute, execute_upgrade, gen_contract_id, upgrade_contract,
+    BlockMetrics, Config as ExecutionConfig, ContractMetrics, DeployDeposit,
+    DeployPolicy, EXECUTION_TIME_EXCEEDED,
+};
+pub use self::validate::{
+    initial_memory_pages, validate_bytecode, Diagnostic,
+    MAX_INITIAL_MEMORY_PAGES,
+};
 pub use piecrust::{
-    CallReceipt, CallTree, CallTreeElem, ContractData, Error, PageOpening,
-    Session,
+    CallReceipt, CallTree, CallTreeElem, ContractData, Error, Event,
+    PageOpening, Session,
 };
 
 use alloc::vec::Vec;
@@ -24,17 +32,71 @@ use std::fmt::{self, Debug, Formatter};
 use std::path::{Path, PathBuf};
 use std::thread;
 
-use dusk_core::abi::{Metadata, Query};
+use dusk_core::abi::{Metadata, Query, SEED_BYTES};
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use piecrust::{SessionData, VM as PiecrustVM};
 
 use self::host_queries::{
-    host_hash, host_poseidon_hash, host_verify_bls, host_verify_bls_multisig,
-    host_verify_groth16_bn254, host_verify_plonk, host_verify_schnorr,
+    host_blake2b, host_hash, host_poseidon_hash, host_sha512, host_verify_bls,
+    host_verify_bls_multisig, host_verify_groth16_bn254, host_verify_plonk,
+    host_verify_schnorr, PricedQuery, BLAKE2B_BASE_PRICE, BLAKE2B_BYTE_PRICE,
+    SHA512_BASE_PRICE, SHA512_BYTE_PRICE,
 };
 
 pub(crate) mod cache;
 mod execute;
 pub mod host_queries;
+mod validate;
+
+/// The data of the block executed by a [`Session`], made available to the
+/// contracts through the session metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockData {
+    /// The height of the block.
+    pub height: u64,
+    /// The seed of the previous block, as agreed by consensus.
+    pub seed: [u8; SEED_BYTES],
+    /// The timestamp of the block, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The generator of the block, if the session executes one.
+    pub generator: Option<BlsPublicKey>,
+}
+
+impl BlockData {
+    /// Creates the data of a session not executing an actual block, with a
+    /// zeroed seed and timestamp and no generator.
+    pub const fn at_height(height: u64) -> Self {
+        Self {
+            height,
+            seed: [0; SEED_BYTES],
+            timestamp: 0,
+            generator: None,
+        }
+    }
+}
+
+/// A checkpoint of the state of a [`Session`].
+///
+/// Checkpoints are created with [`VM::checkpoint`] and allow a session to be
+/// rolled back to the state it had at that point with [`VM::rollback_to`].
+/// This is useful for speculative execution, e.g. when a block generator wants
+/// to inspect the receipt of a transaction before deciding to include it.
+///
+/// A checkpoint is backed by an unfinalized commit, which should be released
+/// with [`VM::release_checkpoint`] once the checkpoint is not needed anymore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    root: [u8; 32],
+    chain_id: u8,
+    block: BlockData,
+}
+
+impl Checkpoint {
+    /// Returns the state root of the checkpoint.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+}
 
 /// The Virtual Machine (VM) for executing smart contracts in the Dusk Network.
 ///
@@ -155,12 +217,41 @@ impl VM {
         base: [u8; 32],
         chain_id: u8,
         block_height: u64,
+    ) -> Result<Session, Error> {
+        self.block_session(base, chain_id, BlockData::at_height(block_height))
+    }
+
+    /// Creates a new session for the execution of a block.
+    ///
+    /// This method behaves like [`session`], additionally making the seed of
+    /// the previous block, the timestamp and the generator of the block
+    /// available to the contracts executed in the session. Sessions created
+    /// with [`session`] use [`BlockData::at_height`].
+    ///
+    /// # Arguments
+    /// * `base` - A 32-byte array representing the base state from which the
+    ///   session begins.
+    /// * `chain_id` - The identifier of the network.
+    /// * `block` - The data of the block being executed.
+    ///
+    /// # Errors
+    /// If base commit is provided but does not exist.
+    ///
+    /// [`session`]: VM::session
+    pub fn block_session(
+        &self,
+        base: [u8; 32],
+        chain_id: u8,
+        block: BlockData,
     ) -> Result<Session, Error> {
         self.0.session(
             SessionData::builder()
                 .base(base)
                 .insert(Metadata::CHAIN_ID, chain_id)?
-                .insert(Metadata::BLOCK_HEIGHT, block_height)?,
+                .insert(Metadata::BLOCK_HEIGHT, block.height)?
+                .insert(Metadata::SEED, block.seed)?
+                .insert(Metadata::BLOCK_TIMESTAMP, block.timestamp)?
+                .insert(Metadata::BLOCK_GENERATOR, block.generator)?,
         )
     }
 
@@ -193,13 +284,101 @@ impl VM {
                     .insert(Metadata::CHAIN_ID, chain_id)
                     .expect("Inserting chain ID in metadata should succeed")
                     .insert(Metadata::BLOCK_HEIGHT, 0)
-                    .expect(
-                        "Inserting block height in metadata should succeed",
-                    ),
+                    .expect("Inserting block height in metadata should succeed")
+                    .insert(Metadata::SEED, [0u8; SEED_BYTES])
+                    .expect("Inserting seed in metadata should succeed")
+                    .insert(Metadata::BLOCK_TIMESTAMP, 0u64)
+                    .expect("Inserting timestamp in metadata should succeed")
+                    .insert(Metadata::BLOCK_GENERATOR, None::<BlsPublicKey>)
+                    .expect("Inserting generator in metadata should succeed"),
             )
             .expect("Creating a genesis session should always succeed")
     }
 
+    /// Creates a checkpoint of the given session.
+    ///
+    /// The session state is committed and a new session continuing from the
+    /// committed state is returned together with the checkpoint. The returned
+    /// session is created with the given chain ID and block data, which should
+    /// be the same the original session was created with.
+    ///
+    /// # Arguments
+    /// * `session` - The session to create a checkpoint of.
+    /// * `chain_id` - The identifier of the network.
+    /// * `block` - The data of the block the session executes.
+    ///
+    /// # Returns
+    /// A `Result` containing the continued session and the checkpoint.
+    ///
+    /// # Errors
+    /// If committing the session or spawning the continued session fails.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use dusk_vm::{BlockData, VM};
+    ///
+    /// const CHAIN_ID: u8 = 42;
+    /// const BLOCK: BlockData = BlockData::at_height(21);
+    ///
+    /// let vm = VM::ephemeral().unwrap();
+    /// let base = vm.genesis_session(CHAIN_ID).commit().unwrap();
+    /// let session = vm.block_session(base, CHAIN_ID, BLOCK).unwrap();
+    ///
+    /// let (session, checkpoint) =
+    ///     vm.checkpoint(session, CHAIN_ID, BLOCK).unwrap();
+    ///
+    /// // [...] speculatively execute transactions through the session
+    ///
+    /// // discard the changes made after the checkpoint
+    /// drop(session);
+    /// let session = vm.rollback_to(&checkpoint).unwrap();
+    /// vm.release_checkpoint(checkpoint).unwrap();
+    /// ```
+    pub fn checkpoint(
+        &self,
+        session: Session,
+        chain_id: u8,
+        block: BlockData,
+    ) -> Result<(Session, Checkpoint), Error> {
+        let root = session.commit()?;
+        let checkpoint = Checkpoint {
+            root,
+            chain_id,
+            block,
+        };
+        let session = self.rollback_to(&checkpoint)?;
+        Ok((session, checkpoint))
+    }
+
+    /// Spawns a new session with the state the checkpoint was created at.
+    ///
+    /// Any change made by the sessions spawned from the checkpoint is
+    /// discarded, as long as they are not committed.
+    ///
+    /// # Errors
+    /// If the checkpoint was already released.
+    pub fn rollback_to(
+        &self,
+        checkpoint: &Checkpoint,
+    ) -> Result<Session, Error> {
+        self.block_session(
+            checkpoint.root,
+            checkpoint.chain_id,
+            checkpoint.block,
+        )
+    }
+
+    /// Releases the commit backing the given checkpoint.
+    ///
+    /// This should only be called once the sessions spawned from the
+    /// checkpoint are dropped.
+    pub fn release_checkpoint(
+        &self,
+        checkpoint: Checkpoint,
+    ) -> Result<(), Error> {
+        self.delete_commit(checkpoint.root)
+    }
+
     /// Retrieves all pending commits in the VM.
     ///
     /// This method fetches unfinalized state changes for inspection or
@@ -244,6 +423,18 @@ impl VM {
         self.0.register_host_query(Query::HASH, host_hash);
         self.0
             .register_host_query(Query::POSEIDON_HASH, host_poseidon_hash);
+        self.0.register_host_query(
+            Query::BLAKE2B,
+            PricedQuery::new(
+                host_blake2b,
+                BLAKE2B_BASE_PRICE,
+                BLAKE2B_BYTE_PRICE,
+            ),
+        );
+        self.0.register_host_query(
+            Query::SHA512,
+            PricedQuery::new(host_sha512, SHA512_BASE_PRICE, SHA512_BYTE_PRICE),
+        );
         self.0
             .register_host_query(Query::VERIFY_PLONK, host_verify_plonk);
         self.0.register_host_query(
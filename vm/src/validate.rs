@@ -0,0 +1,578 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Static validation of contract bytecode.
+
+use alloc::vec::Vec;
+use std::fmt;
+
+use wasmparser::{
+    CompositeType, ExternalKind, FuncType, Operator, Parser, Payload, TypeRef,
+    ValType,
+};
+
+/// The maximum number of 64KiB pages a contract may declare as its initial
+/// memory.
+pub const MAX_INITIAL_MEMORY_PAGES: u64 = 1024;
+
+/// The module the host functions are imported from.
+const HOST_MODULE: &str = "env";
+
+/// The functions the host makes available to contracts.
+const HOST_IMPORTS: &[&str] = &[
+    "c",
+    "hq",
+    "hd",
+    "emit",
+    "feed",
+    "caller",
+    "callstack",
+    "limit",
+    "spent",
+    "panic",
+    "owner",
+    "self_id",
+    "hdebug",
+];
+
+/// The name of the exported memory of a contract.
+const MEMORY_EXPORT: &str = "memory";
+
+/// The name of the exported global pointing to the argument buffer of a
+/// contract.
+const ARGBUF_EXPORT: &str = "A";
+
+/// A rule violation found while validating contract bytecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The bytecode is not a well-formed WASM module.
+    Malformed(String),
+    /// A function uses floating point types or instructions.
+    FloatingPoint {
+        /// The index of the offending function.
+        function: u32,
+    },
+    /// A global uses a floating point type.
+    FloatingPointGlobal {
+        /// The index of the offending global.
+        global: u32,
+    },
+    /// The module declares more than one memory.
+    MultipleMemories,
+    /// The initial memory declared is larger than
+    /// [`MAX_INITIAL_MEMORY_PAGES`].
+    ExcessiveMemory {
+        /// The number of pages declared.
+        pages: u64,
+    },
+    /// An export required by the host is missing.
+    MissingExport(&'static str),
+    /// The module doesn't export any function.
+    NoExportedFunctions,
+    /// An exported function doesn't have the signature produced by
+    /// `wrap_call`, i.e. `fn(u32) -> u32`.
+    InvalidExportSignature {
+        /// The name of the offending export.
+        name: String,
+    },
+    /// A function is imported that the host doesn't provide.
+    UnknownImport {
+        /// The module the function is imported from.
+        module: String,
+        /// The name of the imported function.
+        name: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Malformed(err) => write!(f, "malformed module: {err}"),
+            Diagnostic::FloatingPoint { function } => {
+                write!(f, "function {function} uses floating point")
+            }
+            Diagnostic::FloatingPointGlobal { global } => {
+                write!(f, "global {global} uses floating point")
+            }
+            Diagnostic::MultipleMemories => {
+                write!(f, "more than one memory declared")
+            }
+            Diagnostic::ExcessiveMemory { pages } => write!(
+                f,
+                "initial memory of {pages} pages exceeds maximum of \
+                 {MAX_INITIAL_MEMORY_PAGES}"
+            ),
+            Diagnostic::MissingExport(name) => {
+                write!(f, "missing export: {name}")
+            }
+            Diagnostic::NoExportedFunctions => {
+                write!(f, "no exported functions")
+            }
+            Diagnostic::InvalidExportSignature { name } => {
+                write!(f, "exported function {name} has an invalid signature")
+            }
+            Diagnostic::UnknownImport { module, name } => {
+                write!(f, "unknown import: {module}.{name}")
+            }
+        }
+    }
+}
+
+/// Statically validates contract bytecode.
+///
+/// The following rules are checked:
+/// - The bytecode is a well-formed WASM module.
+/// - No function uses floating point types or instructions, since they are a
+///   source of non-determinism.
+/// - At most one memory is declared, with an initial size not exceeding
+///   [`MAX_INITIAL_MEMORY_PAGES`].
+/// - The memory and argument buffer are exported.
+/// - At least one function is exported, and all exported functions have the
+///   signature produced by `wrap_call`.
+/// - Only functions provided by the host are imported.
+///
+/// # Arguments
+/// * `bytecode` - The WASM bytecode of the contract.
+///
+/// # Returns
+/// `Ok(())` if the bytecode passes all checks.
+///
+/// # Errors
+/// A list of [`Diagnostic`]s describing every rule violation found. If the
+/// module is malformed the list only contains the [`Diagnostic::Malformed`]
+/// entry.
+///
+/// # Examples
+/// ```rust
+/// use dusk_vm::{validate_bytecode, Diagnostic};
+///
+/// let diagnostics = validate_bytecode(&[0xde, 0xad]).unwrap_err();
+/// assert!(matches!(diagnostics[..], [Diagnostic::Malformed(_)]));
+/// ```
+pub fn validate_bytecode(bytecode: &[u8]) -> Result<(), Vec<Diagnostic>> {
+    let diagnostics = match Validation::run(bytecode) {
+        Ok(diagnostics) => diagnostics,
+        Err(err) => {
+            return Err(vec![Diagnostic::Malformed(err.to_string())]);
+        }
+    };
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// Returns the number of 64KiB pages the contract declares as its initial
+/// memory.
+///
+/// Malformed bytecode is considered to declare the pages found before the
+/// malformed section, since it is rejected on deployment anyway. The count
+/// saturates, so that huge declarations can't wrap around to a small one.
+pub fn initial_memory_pages(bytecode: &[u8]) -> u64 {
+    let mut pages = 0;
+
+    for payload in Parser::new(0).parse_all(bytecode) {
+        match payload {
+            Ok(Payload::ImportSection(reader)) => {
+                for import in reader.into_iter().flatten() {
+                    if let TypeRef::Memory(ty) = import.ty {
+                        pages = pages.saturating_add(ty.initial);
+                    }
+                }
+            }
+            Ok(Payload::MemorySection(reader)) => {
+                for memory in reader.into_iter().flatten() {
+                    pages = pages.saturating_add(memory.initial);
+                }
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    pages
+}
+
+#[derive(Default)]
+struct Validation {
+    types: Vec<Option<FuncType>>,
+    functions: Vec<u32>,
+    imported_functions: u32,
+    globals: u32,
+    memories: u32,
+    exported_functions: u32,
+    has_memory_export: bool,
+    has_argbuf_export: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validation {
+    fn run(bytecode: &[u8]) -> wasmparser::Result<Vec<Diagnostic>> {
+        let mut validation = Self::default();
+        let mut code_index = 0;
+
+        for payload in Parser::new(0).parse_all(bytecode) {
+            match payload? {
+                Payload::TypeSection(reader) => {
+                    for group in reader {
+                        for ty in group?.into_types() {
+                            validation.types.push(match ty.composite_type {
+                                CompositeType::Func(ty) => Some(ty),
+                                _ => None,
+                            });
+                        }
+                    }
+                }
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        match import.ty {
+                            TypeRef::Func(ty) => {
+                                validation.functions.push(ty);
+                                validation.imported_functions += 1;
+                                validation
+                                    .check_import(import.module, import.name);
+                            }
+                            TypeRef::Memory(ty) => {
+                                validation.check_memory(ty.initial)
+                            }
+                            TypeRef::Global(_) => {
+                                validation.globals += 1;
+                                validation
+                                    .unknown_import(import.module, import.name);
+                            }
+                            _ => validation
+                                .unknown_import(import.module, import.name),
+                        }
+                    }
+                }
+                Payload::FunctionSection(reader) => {
+                    for ty in reader {
+                        validation.functions.push(ty?);
+                    }
+                }
+                Payload::MemorySection(reader) => {
+                    for memory in reader {
+                        validation.check_memory(memory?.initial);
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        if is_float(global?.ty.content_type) {
+                            validation.diagnostics.push(
+                                Diagnostic::FloatingPointGlobal {
+                                    global: validation.globals,
+                                },
+                            );
+                        }
+                        validation.globals += 1;
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        validation.check_export(
+                            export.name,
+                            export.kind,
+                            export.index,
+                        );
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let index = validation.imported_functions + code_index;
+                    code_index += 1;
+
+                    let mut uses_float = validation
+                        .func_type(index)
+                        .map(|ty| {
+                            ty.params().iter().any(|ty| is_float(*ty))
+                                || ty.results().iter().any(|ty| is_float(*ty))
+                        })
+                        .unwrap_or_default();
+
+                    for local in body.get_locals_reader()? {
+                        let (_, ty) = local?;
+                        uses_float |= is_float(ty);
+                    }
+
+                    let mut operators = body.get_operators_reader()?;
+                    while !uses_float && !operators.eof() {
+                        uses_float |= is_float_op(&operators.read()?);
+                    }
+
+                    if uses_float {
+                        validation.diagnostics.push(
+                            Diagnostic::FloatingPoint { function: index },
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !validation.has_memory_export {
+            validation
+                .diagnostics
+                .push(Diagnostic::MissingExport(MEMORY_EXPORT));
+        }
+        if !validation.has_argbuf_export {
+            validation
+                .diagnostics
+                .push(Diagnostic::MissingExport(ARGBUF_EXPORT));
+        }
+        if validation.exported_functions == 0 {
+            validation.diagnostics.push(Diagnostic::NoExportedFunctions);
+        }
+
+        Ok(validation.diagnostics)
+    }
+
+    fn func_type(&self, function: u32) -> Option<&FuncType> {
+        let ty = *self.functions.get(function as usize)?;
+        self.types.get(ty as usize)?.as_ref()
+    }
+
+    fn check_import(&mut self, module: &str, name: &str) {
+        if module != HOST_MODULE || !HOST_IMPORTS.contains(&name) {
+            self.unknown_import(module, name);
+        }
+    }
+
+    fn unknown_import(&mut self, module: &str, name: &str) {
+        self.diagnostics.push(Diagnostic::UnknownImport {
+            module: module.into(),
+            name: name.into(),
+        });
+    }
+
+    fn check_memory(&mut self, pages: u64) {
+        self.memories += 1;
+        if self.memories == 2 {
+            self.diagnostics.push(Diagnostic::MultipleMemories);
+        }
+        if pages > MAX_INITIAL_MEMORY_PAGES {
+            self.diagnostics.push(Diagnostic::ExcessiveMemory { pages });
+        }
+    }
+
+    fn check_export(&mut self, name: &str, kind: ExternalKind, index: u32) {
+        match kind {
+            ExternalKind::Memory if name == MEMORY_EXPORT => {
+                self.has_memory_export = true;
+            }
+            ExternalKind::Global if name == ARGBUF_EXPORT => {
+                self.has_argbuf_export = true;
+            }
+            ExternalKind::Func => {
+                self.exported_functions += 1;
+
+                let is_wrapped = self
+                    .func_type(index)
+                    .map(|ty| {
+                        ty.params() == [ValType::I32]
+                            && ty.results() == [ValType::I32]
+                    })
+                    .unwrap_or_default();
+
+                if !is_wrapped {
+                    self.diagnostics.push(Diagnostic::InvalidExportSignature {
+                        name: name.into(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_float(ty: ValType) -> bool {
+    matches!(ty, ValType::F32 | ValType::F64)
+}
+
+fn is_float_op(op: &Operator) -> bool {
+    use Operator::*;
+
+    matches!(
+        op,
+        F32Load { .. }
+            | F64Load { .. }
+            | F32Store { .. }
+            | F64Store { .. }
+            | F32Const { .. }
+            | F64Const { .. }
+            | F32Eq { .. }
+            | F32Ne { .. }
+            | F32Lt { .. }
+            | F32Gt { .. }
+            | F32Le { .. }
+            | F32Ge { .. }
+            | F64Eq { .. }
+            | F64Ne { .. }
+            | F64Lt { .. }
+            | F64Gt { .. }
+            | F64Le { .. }
+            | F64Ge { .. }
+            | F32Abs { .. }
+            | F32Neg { .. }
+            | F32Ceil { .. }
+            | F32Floor { .. }
+            | F32Trunc { .. }
+            | F32Nearest { .. }
+            | F32Sqrt { .. }
+            | F32Add { .. }
+            | F32Sub { .. }
+            | F32Mul { .. }
+            | F32Div { .. }
+            | F32Min { .. }
+            | F32Max { .. }
+            | F32Copysign { .. }
+            | F64Abs { .. }
+            | F64Neg { .. }
+            | F64Ceil { .. }
+            | F64Floor { .. }
+            | F64Trunc { .. }
+            | F64Nearest { .. }
+            | F64Sqrt { .. }
+            | F64Add { .. }
+            | F64Sub { .. }
+            | F64Mul { .. }
+            | F64Div { .. }
+            | F64Min { .. }
+            | F64Max { .. }
+            | F64Copysign { .. }
+            | I32TruncF32S { .. }
+            | I32TruncF32U { .. }
+            | I32TruncF64S { .. }
+            | I32TruncF64U { .. }
+            | I64TruncF32S { .. }
+            | I64TruncF32U { .. }
+            | I64TruncF64S { .. }
+            | I64TruncF64U { .. }
+            | F32ConvertI32S { .. }
+            | F32ConvertI32U { .. }
+            | F32ConvertI64S { .. }
+            | F32ConvertI64U { .. }
+            | F32DemoteF64 { .. }
+            | F64ConvertI32S { .. }
+            | F64ConvertI32U { .. }
+            | F64ConvertI64S { .. }
+            | F64ConvertI64U { .. }
+            | F64PromoteF32 { .. }
+            | I32ReinterpretF32 { .. }
+            | I64ReinterpretF64 { .. }
+            | F32ReinterpretI32 { .. }
+            | F64ReinterpretI64 { .. }
+            | I32TruncSatF32S { .. }
+            | I32TruncSatF32U { .. }
+            | I32TruncSatF64S { .. }
+            | I32TruncSatF64U { .. }
+            | I64TruncSatF32S { .. }
+            | I64TruncSatF32U { .. }
+            | I64TruncSatF64S { .. }
+            | I64TruncSatF64U { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    fn module(sections: &[&[u8]]) -> Vec<u8> {
+        let mut module = HEADER.to_vec();
+        for section in sections {
+            module.extend_from_slice(section);
+        }
+        module
+    }
+
+    // (type (func (param i32) (result i32)))
+    // (type (func (param f32)))
+    const TYPES: &[u8] = &[
+        0x01, 0x0a, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x01, 0x7d, 0x00,
+    ];
+
+    // (func (type 0)) (func (type 1))
+    const FUNCS: &[u8] = &[0x03, 0x03, 0x02, 0x00, 0x01];
+
+    // (memory 17)
+    const MEMORY: &[u8] = &[0x05, 0x03, 0x01, 0x00, 0x11];
+
+    // (global i32 (i32.const 0))
+    const GLOBAL: &[u8] = &[0x06, 0x06, 0x01, 0x7f, 0x00, 0x41, 0x00, 0x0b];
+
+    // (export "memory" (memory 0)) (export "A" (global 0))
+    // (export "f" (func 0))
+    const EXPORTS: &[u8] = &[
+        0x07, 0x12, 0x03, // export section, three entries
+        0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, // memory
+        0x01, b'A', 0x03, 0x00, // argbuf
+        0x01, b'f', 0x00, 0x00, // function
+    ];
+
+    // (func (local.get 0)) (func)
+    const CODE: &[u8] = &[
+        0x0a, 0x09, 0x02, // code section, two entries
+        0x04, 0x00, 0x20, 0x00, 0x0b, // local.get 0
+        0x02, 0x00, 0x0b, // empty
+    ];
+
+    #[test]
+    fn malformed() {
+        let diagnostics = validate_bytecode(&[0xde, 0xad]).unwrap_err();
+        assert!(matches!(diagnostics[..], [Diagnostic::Malformed(_)]));
+    }
+
+    #[test]
+    fn missing_exports() {
+        let diagnostics = validate_bytecode(HEADER).unwrap_err();
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::MissingExport(MEMORY_EXPORT),
+                Diagnostic::MissingExport(ARGBUF_EXPORT),
+                Diagnostic::NoExportedFunctions,
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_pages() {
+        let bytecode = module(&[TYPES, FUNCS, MEMORY, GLOBAL, EXPORTS, CODE]);
+
+        assert_eq!(initial_memory_pages(&bytecode), 17);
+        assert_eq!(initial_memory_pages(HEADER), 0);
+        assert_eq!(initial_memory_pages(&[0xde, 0xad]), 0);
+    }
+
+    #[test]
+    fn memory_pages_saturate() {
+        // Two 64-bit memories of `u64::MAX` initial pages each
+        let mut memories = vec![0x05, 0x17, 0x02];
+        for _ in 0..2 {
+            memories.push(0x04);
+            memories.extend_from_slice(&[0xff; 9]);
+            memories.push(0x01);
+        }
+        let bytecode = module(&[&memories]);
+
+        assert_eq!(initial_memory_pages(&bytecode), u64::MAX);
+    }
+
+    #[test]
+    fn floating_point() {
+        let bytecode = module(&[TYPES, FUNCS, MEMORY, GLOBAL, EXPORTS, CODE]);
+
+        let diagnostics = validate_bytecode(&bytecode).unwrap_err();
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::FloatingPoint { function: 1 }]
+        );
+    }
+}
@@ -6,10 +6,15 @@
 
 #![deny(clippy::all)]
 
+use std::fmt::Write;
 use std::sync::OnceLock;
 
 use dusk_bytes::{ParseHexStr, Serializable};
-use dusk_core::abi::ContractId;
+use dusk_core::abi::{
+    ArgChunk, ContractError, ContractFailure, ContractId, ContractMetadata,
+    FailedDelivery, ARGBUF_LEN, DELIVERY_FAILED_TOPIC, METADATA_FN,
+    QUERY_TRAMPOLINE_FN, STREAM_CHUNK_FN,
+};
 use dusk_core::groth16::bn254::{Bn254, Fr as Bn254Fr};
 use dusk_core::groth16::relations::lc;
 use dusk_core::groth16::relations::r1cs::{
@@ -29,8 +34,23 @@ use dusk_core::signatures::bls::{
 use dusk_core::signatures::schnorr::{
     PublicKey as SchnorrPublicKey, SecretKey as SchnorrSecretKey,
 };
+use dusk_core::transfer::data::{
+    ContractBytecode, ContractCall, ContractDeploy, ContractUpgrade,
+    ContractUpgradeEvent, Memo, MemoKind, UPGRADE_TOPIC,
+};
+use dusk_core::transfer::{
+    MemoEvent, Transaction, MEMO_TOPIC, TRANSFER_CONTRACT,
+};
 use dusk_core::BlsScalar;
-use dusk_vm::{ContractData, Session, VM};
+use dusk_vm::host_queries::{
+    BLAKE2B_BASE_PRICE, BLAKE2B_BYTE_PRICE, SHA512_BASE_PRICE,
+    SHA512_BYTE_PRICE,
+};
+use dusk_vm::{
+    call_streamed, execute, execute_upgrade, upgrade_contract,
+    validate_bytecode, BlockData, BlockMetrics, ContractData, DeployPolicy,
+    Error, ExecutionConfig, Session, VM,
+};
 use ff::Field;
 use rand::rngs::OsRng;
 
@@ -144,6 +164,112 @@ fn poseidon_hash() {
     );
 }
 
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+#[test]
+fn blake2b() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let hash: Vec<u8> = session
+        .call(
+            contract_id,
+            "blake2b",
+            &(b"abc".to_vec(), 64u8, [0u8; 16]),
+            POINT_LIMIT,
+        )
+        .expect("Querying should succeed")
+        .data;
+    assert_eq!(
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+        to_hex(&hash)
+    );
+
+    let mut personal = [0u8; 16];
+    personal[..8].copy_from_slice(b"ZcashPoW");
+    let hash: Vec<u8> = session
+        .call(
+            contract_id,
+            "blake2b",
+            &(b"abc".to_vec(), 32u8, personal),
+            POINT_LIMIT,
+        )
+        .expect("Querying should succeed")
+        .data;
+    assert_eq!(
+        "6469eaf47a1c918c8bd02cebc0e5063c66a7dbfc6d0308309f08ba90cdc1bdcb",
+        to_hex(&hash)
+    );
+
+    for hash_len in [0u8, 65] {
+        let hash: Vec<u8> = session
+            .call(
+                contract_id,
+                "blake2b",
+                &(b"abc".to_vec(), hash_len, [0u8; 16]),
+                POINT_LIMIT,
+            )
+            .expect("Querying with an invalid length should succeed")
+            .data;
+        assert!(hash.is_empty());
+    }
+}
+
+#[test]
+fn sha512() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let hash: [u8; 64] = session
+        .call(contract_id, "sha512", &b"abc".to_vec(), POINT_LIMIT)
+        .expect("Querying should succeed")
+        .data;
+    assert_eq!(
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        to_hex(&hash)
+    );
+}
+
+#[test]
+fn hash_queries_priced_by_length() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    const EXTRA_BYTES: usize = 10_000;
+    let small = vec![0u8; 100];
+    let large = vec![0u8; small.len() + EXTRA_BYTES];
+
+    let mut blake2b_gas = [0; 2];
+    let mut sha512_gas = [0; 2];
+    for (i, bytes) in [&small, &large].into_iter().enumerate() {
+        blake2b_gas[i] = session
+            .call::<_, Vec<u8>>(
+                contract_id,
+                "blake2b",
+                &(bytes.clone(), 64u8, [0u8; 16]),
+                POINT_LIMIT,
+            )
+            .expect("Querying should succeed")
+            .gas_spent;
+        sha512_gas[i] = session
+            .call::<_, [u8; 64]>(contract_id, "sha512", bytes, POINT_LIMIT)
+            .expect("Querying should succeed")
+            .gas_spent;
+    }
+
+    // the queries charge at least their price for every extra byte hashed
+    let extra = EXTRA_BYTES as u64;
+    assert!(blake2b_gas[1] - blake2b_gas[0] >= extra * BLAKE2B_BYTE_PRICE);
+    assert!(sha512_gas[1] - sha512_gas[0] >= extra * SHA512_BYTE_PRICE);
+    assert!(blake2b_gas[0] >= BLAKE2B_BASE_PRICE);
+    assert!(sha512_gas[0] >= SHA512_BASE_PRICE);
+}
+
 #[test]
 fn schnorr_signature() {
     let vm = VM::ephemeral().expect("Instantiating VM should succeed");
@@ -517,6 +643,100 @@ fn block_height() {
     assert_eq!(height, HEIGHT);
 }
 
+#[test]
+fn block_data() {
+    const HEIGHT: u64 = 123;
+    const SEED: [u8; 48] = [0xab; 48];
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, HEIGHT);
+
+    let generator: Option<BlsPublicKey> = session
+        .call(contract_id, "block_generator", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(generator, None);
+
+    let base = session.commit().expect("Committing should succeed");
+
+    let block = BlockData {
+        height: HEIGHT,
+        seed: SEED,
+        timestamp: TIMESTAMP,
+        generator: Some(*get_owner()),
+    };
+    let mut session = vm
+        .block_session(base, CHAIN_ID, block)
+        .expect("Instantiating new session should succeed");
+
+    let seed: [u8; 48] = session
+        .call(contract_id, "block_seed", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(seed, SEED);
+
+    let timestamp: u64 = session
+        .call(contract_id, "block_timestamp", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(timestamp, TIMESTAMP);
+
+    let generator: Option<BlsPublicKey> = session
+        .call(contract_id, "block_generator", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(generator, Some(*get_owner()));
+}
+
+#[test]
+fn checkpoint_rollback() {
+    const HEIGHT: u64 = 123;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (session, contract_id) = instantiate(&vm, HEIGHT);
+
+    let root = session.root();
+    let (mut session, checkpoint) = vm
+        .checkpoint(session, CHAIN_ID, BlockData::at_height(HEIGHT))
+        .expect("Creating a checkpoint should succeed");
+    assert_eq!(checkpoint.root(), root);
+
+    let bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/host_fn.wasm"
+    );
+    let speculative_id = session
+        .deploy(
+            bytecode,
+            ContractData::builder()
+                .owner([1u8; BlsPublicKey::SIZE])
+                .contract_id(ContractId::from_bytes([0xC0; 32])),
+            POINT_LIMIT,
+        )
+        .expect("Deploying module should succeed");
+    assert_ne!(session.root(), root);
+    drop(session);
+
+    let mut session = vm
+        .rollback_to(&checkpoint)
+        .expect("Rolling back to a checkpoint should succeed");
+    assert_eq!(session.root(), root);
+
+    session
+        .call::<_, u8>(speculative_id, "chain_id", &(), POINT_LIMIT)
+        .expect_err("Contract deployed after the checkpoint should not exist");
+
+    let height: u64 = session
+        .call(contract_id, "block_height", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(height, HEIGHT);
+
+    drop(session);
+    vm.release_checkpoint(checkpoint)
+        .expect("Releasing a checkpoint should succeed");
+}
+
 fn get_owner() -> &'static BlsPublicKey {
     static OWNER: OnceLock<BlsPublicKey> = OnceLock::new();
     OWNER.get_or_init(|| {
@@ -547,6 +767,897 @@ fn owner() {
         .call(contract_id, "contract_owner", get_owner(), POINT_LIMIT)
         .expect("Query should succeed")
         .data;
-
     assert_eq!(owner, get_owner().to_owned());
+
+    let owner: Option<BlsPublicKey> = session
+        .call(contract_id, "contract_try_owner", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(owner, Some(get_owner().to_owned()));
+}
+
+#[test]
+fn owner_not_bls() {
+    let bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/host_fn.wasm"
+    );
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let mut session = vm.genesis_session(CHAIN_ID);
+
+    let contract_id = session
+        .deploy(
+            bytecode,
+            ContractData::builder().owner([0u8; BlsPublicKey::SIZE]),
+            POINT_LIMIT,
+        )
+        .expect("Deploying module should succeed");
+
+    let owner: Option<BlsPublicKey> = session
+        .call(contract_id, "contract_try_owner", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(owner, None);
+
+    session
+        .call::<_, BlsPublicKey>(
+            contract_id,
+            "contract_owner",
+            &(),
+            POINT_LIMIT,
+        )
+        .expect_err("Querying an owner that isn't BLS should panic");
+
+    let metadata: ContractMetadata = session
+        .call(contract_id, "metadata", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(metadata.owner, None);
+}
+
+#[test]
+fn streamed_argument() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let mut prev_gas = 0;
+    for len in [0, 1, ARGBUF_LEN, 3 * ARGBUF_LEN + 5] {
+        let arg: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+        let receipt = call_streamed(
+            &mut session,
+            contract_id,
+            "streamed_sum",
+            &arg,
+            POINT_LIMIT,
+        )
+        .expect("Streamed call should succeed");
+        let (streamed_len, sum): (u64, u64) = rkyv::from_bytes(&receipt.data)
+            .expect("Result should deserialize correctly");
+
+        assert_eq!(streamed_len, len as u64);
+        assert_eq!(sum, arg.iter().map(|byte| *byte as u64).sum::<u64>());
+
+        // The gas spent grows with the number of chunks, which is the same
+        // for empty and single byte arguments
+        if len > 1 {
+            assert!(receipt.gas_spent > prev_gas);
+        }
+        prev_gas = receipt.gas_spent;
+    }
+}
+
+#[test]
+fn streamed_argument_expires() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 1);
+
+    // Stream an argument without calling the function taking it
+    let arg: Vec<u8> = (0..2 * ARGBUF_LEN).map(|i| i as u8).collect();
+    for chunk in ArgChunk::split(&arg) {
+        session
+            .call::<_, ()>(contract_id, STREAM_CHUNK_FN, &chunk, POINT_LIMIT)
+            .expect("Sending a chunk should succeed");
+    }
+    let base = session.commit().expect("Committing should succeed");
+
+    // The stream left over by the previous block can't be taken
+    let mut session = vm
+        .session(base, CHAIN_ID, 2)
+        .expect("Instantiating new session should succeed");
+    session
+        .call::<_, (u64, u64)>(contract_id, "streamed_sum", &(), POINT_LIMIT)
+        .expect_err("Taking a stale argument should fail");
+
+    // A stream sent in the block is taken, and ends with it
+    call_streamed(&mut session, contract_id, "streamed_sum", &arg, POINT_LIMIT)
+        .expect("Streamed call should succeed");
+    session
+        .call::<_, (u64, u64)>(contract_id, "streamed_sum", &(), POINT_LIMIT)
+        .expect_err("Taking an argument twice should fail");
+}
+
+#[test]
+fn gas_introspection() {
+    const GAS_LIMIT: u64 = 1_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let receipt = session
+        .call::<_, (u64, u64)>(contract_id, "gas", &(), GAS_LIMIT)
+        .expect("Query should succeed");
+    let (remaining, spent) = receipt.data;
+
+    assert!(spent > 0, "Some gas should be spent before the query");
+    // The remaining gas is read before the gas spent, so their sum may
+    // slightly exceed the limit
+    assert!(remaining < GAS_LIMIT);
+    assert!(spent <= receipt.gas_spent);
+}
+
+#[test]
+fn contract_failure() {
+    const CODE: u32 = ContractFailure::FIRST_CUSTOM_CODE + 1;
+    const MESSAGE: &str = "custom failure";
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let err = session
+        .call::<_, ()>(
+            contract_id,
+            "fail",
+            &(CODE, String::from(MESSAGE)),
+            POINT_LIMIT,
+        )
+        .expect_err("Call should fail");
+
+    let Error::Panic(msg) = err else {
+        panic!("Call should panic, got {err:?}");
+    };
+    let failure = ContractFailure::from_panic(&msg)
+        .expect("The panic should carry a failure");
+
+    assert_eq!(failure, ContractFailure::new(CODE, MESSAGE));
+}
+
+/// The `CARGO_PKG_VERSION` the `host_fn` contract is built with.
+fn host_fn_version() -> String {
+    let manifest = include_str!("../../contracts/host_fn/Cargo.toml");
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("version = "))
+        .map(|version| version.trim_matches('"').to_string())
+        .expect("The host_fn manifest should declare a version")
+}
+
+#[test]
+fn contract_metadata() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 0);
+
+    let expected = ContractMetadata {
+        name: String::from("host_fn"),
+        version: host_fn_version(),
+        driver_hash: None,
+        owner: Some(*get_owner()),
+    };
+
+    let metadata: ContractMetadata = session
+        .call(contract_id, METADATA_FN, &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(metadata, expected);
+
+    let metadata: ContractMetadata = session
+        .call(contract_id, "metadata", &(), POINT_LIMIT)
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(metadata, expected);
+}
+
+#[test]
+fn block_metrics() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, contract_id) = instantiate(&vm, 1);
+
+    let mut metrics = BlockMetrics::default();
+    for _ in 0..2 {
+        let receipt = session
+            .call::<_, [u8; 48]>(contract_id, "block_seed", &(), POINT_LIMIT)
+            .expect("Query should succeed");
+        metrics.record(&receipt);
+    }
+
+    assert_eq!(metrics.transactions, 2);
+    assert_eq!(metrics.max_calls, 1);
+
+    let top = metrics.top_consumers(1);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].0, contract_id);
+    assert_eq!(top[0].1.calls, 2);
+    assert_eq!(top[0].1.gas_spent, metrics.gas_spent);
+}
+
+const ALICE_ID: ContractId = ContractId::from_bytes([0xA1; 32]);
+
+/// Deploys the transfer contract and alice, owned by the returned key, with
+/// its bytecode hash recorded.
+fn instantiate_upgradable(vm: &VM) -> (Session, BlsSecretKey) {
+    let transfer_bytecode = include_bytes!(
+        "../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+    );
+    let alice_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/alice.wasm"
+    );
+
+    let owner_sk = BlsSecretKey::random(&mut OsRng);
+    let owner = BlsPublicKey::from(&owner_sk);
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+    session
+        .deploy(
+            transfer_bytecode,
+            ContractData::builder()
+                .owner([0u8; 32])
+                .contract_id(TRANSFER_CONTRACT),
+            POINT_LIMIT,
+        )
+        .expect("Deploying the transfer contract should succeed");
+    session
+        .deploy(
+            alice_bytecode,
+            ContractData::builder()
+                .owner(owner.to_bytes())
+                .contract_id(ALICE_ID),
+            POINT_LIMIT,
+        )
+        .expect("Deploying alice should succeed");
+
+    let alice_hash: [u8; 32] = blake3::hash(alice_bytecode).into();
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "set_code_hash",
+            &(ALICE_ID, alice_hash),
+            POINT_LIMIT,
+        )
+        .expect("Recording the code hash should succeed");
+
+    (session, owner_sk)
+}
+
+/// Returns the first upgrade of alice to bob, signed by the given key.
+fn alice_to_bob(
+    owner_sk: &BlsSecretKey,
+    prev_hash: [u8; 32],
+) -> ContractUpgrade {
+    let bob_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/bob.wasm"
+    );
+    signed_upgrade(owner_sk, prev_hash, bob_bytecode, 1)
+}
+
+/// Returns an upgrade of alice to the given bytecode, signed by the given key.
+fn signed_upgrade(
+    owner_sk: &BlsSecretKey,
+    prev_hash: [u8; 32],
+    bytecode: &[u8],
+    nonce: u64,
+) -> ContractUpgrade {
+    let mut upgrade = ContractUpgrade {
+        contract: ALICE_ID,
+        chain_id: CHAIN_ID,
+        nonce,
+        prev_hash,
+        bytecode: ContractBytecode {
+            hash: blake3::hash(bytecode).into(),
+            bytes: bytecode.to_vec(),
+        },
+        migrate_args: None,
+        signature: Default::default(),
+    };
+    upgrade.signature = owner_sk.sign(&upgrade.signature_message());
+    upgrade
+}
+
+/// Returns the hash of alice's bytecode recorded by the transfer contract.
+fn alice_code_hash(session: &mut Session) -> [u8; 32] {
+    session
+        .call::<_, Option<[u8; 32]>>(
+            TRANSFER_CONTRACT,
+            "code_hash",
+            &ALICE_ID,
+            POINT_LIMIT,
+        )
+        .expect("Query should succeed")
+        .data
+        .expect("The code hash should be recorded")
+}
+
+#[test]
+fn upgrade_with_wrong_prev_hash() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (session, owner_sk) = instantiate_upgradable(&vm);
+
+    let upgrade = alice_to_bob(&owner_sk, [0u8; 32]);
+    let Err(err) = upgrade_contract(session, &upgrade, POINT_LIMIT) else {
+        panic!("Upgrading from the wrong bytecode should fail");
+    };
+    assert!(
+        matches!(&err, Error::Panic(msg) if msg.contains("previous bytecode")),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn upgrade_with_wrong_chain_id() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, owner_sk) = instantiate_upgradable(&vm);
+
+    let mut upgrade = alice_to_bob(&owner_sk, alice_code_hash(&mut session));
+    upgrade.chain_id = CHAIN_ID + 1;
+    upgrade.signature = owner_sk.sign(&upgrade.signature_message());
+
+    let Err(err) = upgrade_contract(session, &upgrade, POINT_LIMIT) else {
+        panic!("Upgrading with another chain ID should fail");
+    };
+    assert!(
+        matches!(&err, Error::Panic(msg) if msg.contains("chain id")),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn upgrade_rollback_replay() {
+    let alice_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/alice.wasm"
+    );
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, owner_sk) = instantiate_upgradable(&vm);
+
+    let alice_hash = alice_code_hash(&mut session);
+    let to_bob = alice_to_bob(&owner_sk, alice_hash);
+    let (session, _, _) = upgrade_contract(session, &to_bob, POINT_LIMIT)
+        .expect("Upgrading to bob should succeed");
+
+    let to_alice =
+        signed_upgrade(&owner_sk, to_bob.bytecode.hash, alice_bytecode, 2);
+    let (mut session, _, _) = upgrade_contract(session, &to_alice, POINT_LIMIT)
+        .expect("Upgrading back to alice should succeed");
+    assert_eq!(alice_code_hash(&mut session), alice_hash);
+
+    // Replaying the first upgrade fails, even though the contract runs the
+    // bytecode it replaced again
+    let Err(err) = upgrade_contract(session, &to_bob, POINT_LIMIT) else {
+        panic!("Replaying the upgrade should fail");
+    };
+    assert!(
+        matches!(&err, Error::Panic(msg) if msg.contains("nonce")),
+        "unexpected error {err:?}"
+    );
+}
+
+#[test]
+fn upgrade_replay() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, owner_sk) = instantiate_upgradable(&vm);
+
+    let alice_hash = session
+        .call::<_, Option<[u8; 32]>>(
+            TRANSFER_CONTRACT,
+            "code_hash",
+            &ALICE_ID,
+            POINT_LIMIT,
+        )
+        .expect("Query should succeed")
+        .data
+        .expect("The code hash should be recorded");
+
+    let upgrade = alice_to_bob(&owner_sk, alice_hash);
+    let (mut session, _, event) =
+        upgrade_contract(session, &upgrade, POINT_LIMIT)
+            .expect("Upgrading should succeed");
+
+    assert_eq!(event.source, ALICE_ID);
+    assert_eq!(event.topic, UPGRADE_TOPIC);
+    let event = rkyv::from_bytes::<ContractUpgradeEvent>(&event.data)
+        .expect("The event should deserialize");
+    assert_eq!(event.old_hash, alice_hash);
+    assert_eq!(event.new_hash, upgrade.bytecode.hash);
+
+    // The contract now runs bob's bytecode, and its hash is recorded
+    session
+        .call::<_, u8>(ALICE_ID, "value", &(), POINT_LIMIT)
+        .expect("Calling the new bytecode should succeed");
+    let hash = session
+        .call::<_, Option<[u8; 32]>>(
+            TRANSFER_CONTRACT,
+            "code_hash",
+            &ALICE_ID,
+            POINT_LIMIT,
+        )
+        .expect("Query should succeed")
+        .data;
+    assert_eq!(hash, Some(upgrade.bytecode.hash));
+
+    // Replaying the same upgrade fails, since the bytecode it replaces is
+    // gone
+    assert!(
+        upgrade_contract(session, &upgrade, POINT_LIMIT).is_err(),
+        "Replaying the upgrade should fail"
+    );
+}
+
+#[test]
+fn upgrade_policy() {
+    const GAS_LIMIT: u64 = 100_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, owner_sk) = instantiate_upgradable(&vm);
+    let alice_hash = alice_code_hash(&mut session);
+
+    let sk = BlsSecretKey::random(&mut OsRng);
+    let pk = BlsPublicKey::from(&sk);
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "add_account_balance",
+            &(pk, dusk_core::dusk(1_000.0)),
+            POINT_LIMIT,
+        )
+        .expect("Funding the account should succeed");
+
+    let upgrade = alice_to_bob(&owner_sk, alice_hash);
+    let max_bytecode_size = upgrade.bytecode.bytes.len() - 1;
+    let tx = Transaction::moonlight(
+        &sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        1,
+        1,
+        CHAIN_ID,
+        Some(upgrade),
+    )
+    .expect("Creating the transaction should succeed");
+
+    // The new bytecode is held to the same policy as a deployment
+    let config = ExecutionConfig {
+        deploy_policy: DeployPolicy {
+            max_bytecode_size: Some(max_bytecode_size),
+            ..DeployPolicy::ALLOW_ALL
+        },
+        ..ExecutionConfig::DEFAULT
+    };
+    let Err(err) = execute_upgrade(session, &tx, &config) else {
+        panic!("Upgrading to a bytecode too large should fail");
+    };
+    assert!(
+        matches!(&err, Error::Panic(msg) if msg.contains("too large")),
+        "unexpected error {err:?}"
+    );
+}
+
+/// Executes a transaction calling alice to subscribe to its own events with
+/// the given topic and emit one, returning the deliveries alice recorded and
+/// the failed deliveries reported in the receipt.
+fn deliver_to_alice(
+    topic: &str,
+) -> ((u64, Option<ContractId>), Vec<FailedDelivery>) {
+    const GAS_LIMIT: u64 = 100_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, _) = instantiate_upgradable(&vm);
+
+    let sk = BlsSecretKey::random(&mut OsRng);
+    let pk = BlsPublicKey::from(&sk);
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "add_account_balance",
+            &(pk, dusk_core::dusk(1_000.0)),
+            POINT_LIMIT,
+        )
+        .expect("Funding the account should succeed");
+
+    let call =
+        ContractCall::new(ALICE_ID, "subscribe_and_emit", &topic.to_string())
+            .expect("Building the call should succeed");
+    let tx = Transaction::moonlight(
+        &sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        1,
+        1,
+        CHAIN_ID,
+        Some(call),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let config = ExecutionConfig {
+        with_event_subscriptions: true,
+        ..ExecutionConfig::DEFAULT
+    };
+    let receipt = execute(&mut session, &tx, &config)
+        .expect("Executing the transaction should succeed");
+    assert!(receipt.data.is_ok(), "unexpected error {:?}", receipt.data);
+    assert!(receipt.gas_spent < GAS_LIMIT);
+
+    let failures = receipt
+        .events
+        .iter()
+        .filter(|event| event.topic == DELIVERY_FAILED_TOPIC)
+        .map(|event| {
+            assert_eq!(event.source, TRANSFER_CONTRACT);
+            rkyv::from_bytes(&event.data)
+                .expect("Deserializing the failed delivery should succeed")
+        })
+        .collect();
+
+    let deliveries = session
+        .call(ALICE_ID, "deliveries", &(), POINT_LIMIT)
+        .expect("Querying the deliveries should succeed")
+        .data;
+
+    (deliveries, failures)
+}
+
+#[test]
+fn event_delivery() {
+    let ((deliveries, deliverer), failures) = deliver_to_alice("ping");
+    assert_eq!(deliveries, 1);
+    assert_eq!(deliverer, Some(TRANSFER_CONTRACT));
+    assert!(failures.is_empty());
+}
+
+#[test]
+fn failing_event_delivery() {
+    // The failing callback is reverted, without failing the transaction, and
+    // reported in the receipt
+    let ((deliveries, deliverer), failures) = deliver_to_alice("fail");
+    assert_eq!(deliveries, 0);
+    assert_eq!(deliverer, None);
+
+    assert_eq!(failures.len(), 1);
+    let failure = &failures[0];
+    assert_eq!(failure.subscriber, ALICE_ID);
+    assert_eq!(failure.source, ALICE_ID);
+    assert_eq!(failure.topic, "fail");
+    assert!(
+        matches!(failure.error, ContractError::Panic(_)),
+        "unexpected error {:?}",
+        failure.error
+    );
+}
+
+const BOB_ID: ContractId = ContractId::from_bytes([0xB0; 32]);
+const BOB_INIT_VALUE: u8 = 0xFE;
+
+/// Deploys alice and bob, with bob's value set to `BOB_INIT_VALUE`.
+fn instantiate_alice_and_bob(vm: &VM) -> Session {
+    let alice_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/alice.wasm"
+    );
+    let bob_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/bob.wasm"
+    );
+
+    let mut session = vm.genesis_session(CHAIN_ID);
+    session
+        .deploy(
+            alice_bytecode,
+            ContractData::builder()
+                .owner([0u8; 32])
+                .contract_id(ALICE_ID),
+            POINT_LIMIT,
+        )
+        .expect("Deploying alice should succeed");
+    session
+        .deploy(
+            bob_bytecode,
+            ContractData::builder()
+                .owner([0u8; 32])
+                .contract_id(BOB_ID)
+                .init_arg(&BOB_INIT_VALUE),
+            POINT_LIMIT,
+        )
+        .expect("Deploying bob should succeed");
+
+    session
+}
+
+#[test]
+fn call_query_result() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let mut session = instantiate_alice_and_bob(&vm);
+
+    let echo: u64 = session
+        .call(ALICE_ID, "query_echo", &(BOB_ID, 0xC0FFEE_u64), POINT_LIMIT)
+        .expect("Querying through alice should succeed")
+        .data;
+    assert_eq!(echo, 0xC0FFEE);
+}
+
+#[test]
+fn call_query_reverts() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let mut session = instantiate_alice_and_bob(&vm);
+
+    session
+        .call::<_, ()>(ALICE_ID, "query_reset", &(BOB_ID, 1u8), POINT_LIMIT)
+        .expect("Querying through alice should succeed");
+
+    // The reset made by bob during the query is discarded
+    let value: u8 = session
+        .call(BOB_ID, "value", &(), POINT_LIMIT)
+        .expect("Querying bob should succeed")
+        .data;
+    assert_eq!(value, BOB_INIT_VALUE);
+}
+
+#[test]
+fn contract_metadata_is_read_only() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let mut session = instantiate_alice_and_bob(&vm);
+
+    let metadata: Option<ContractMetadata> = session
+        .call(ALICE_ID, "metadata_of", &BOB_ID, POINT_LIMIT)
+        .expect("Querying the metadata through alice should succeed")
+        .data;
+    let metadata = metadata.expect("Bob should declare its metadata");
+    assert_eq!(metadata.name, "bob");
+    // bob's owner isn't a BLS key
+    assert_eq!(metadata.owner, None);
+
+    // The reset made by bob while returning its metadata is discarded
+    let value: u8 = session
+        .call(BOB_ID, "value", &(), POINT_LIMIT)
+        .expect("Querying bob should succeed")
+        .data;
+    assert_eq!(value, BOB_INIT_VALUE);
+
+    // alice doesn't declare any metadata
+    let metadata: Option<ContractMetadata> = session
+        .call(ALICE_ID, "metadata_of", &ALICE_ID, POINT_LIMIT)
+        .expect("Querying the metadata through alice should succeed")
+        .data;
+    assert_eq!(metadata, None);
+}
+
+#[test]
+fn call_query_trampoline_is_private() {
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let mut session = instantiate_alice_and_bob(&vm);
+
+    let fn_arg = rkyv::to_bytes::<_, 16>(&1u8)
+        .expect("Serializing the argument should succeed")
+        .to_vec();
+    let request = (BOB_ID, String::from("reset"), fn_arg);
+    let err = session
+        .call::<_, ()>(ALICE_ID, QUERY_TRAMPOLINE_FN, &request, POINT_LIMIT)
+        .expect_err("Calling the trampoline directly should fail");
+    let Error::Panic(msg) = err else {
+        panic!("The call should panic, got {err:?}");
+    };
+    let failure = ContractFailure::from_panic(&msg)
+        .expect("The panic should carry a failure code");
+    assert_eq!(failure.code, ContractFailure::UNAUTHORIZED);
+}
+
+#[test]
+fn tagged_memo() {
+    const GAS_LIMIT: u64 = 100_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, _) = instantiate_upgradable(&vm);
+
+    let sk = BlsSecretKey::random(&mut OsRng);
+    let pk = BlsPublicKey::from(&sk);
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "add_account_balance",
+            &(pk, dusk_core::dusk(1_000.0)),
+            POINT_LIMIT,
+        )
+        .expect("Funding the account should succeed");
+
+    let memo = Memo::with_kind(MemoKind::Encrypted, b"ciphertext")
+        .expect("Creating the memo should succeed");
+    let tx = Transaction::moonlight(
+        &sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        1,
+        1,
+        CHAIN_ID,
+        Some(memo.clone()),
+    )
+    .expect("Creating the transaction should succeed");
+
+    // Tagged memos are discarded until they are enabled
+    let Err(err) = execute(&mut session, &tx, &ExecutionConfig::DEFAULT) else {
+        panic!("Executing a tagged memo should fail");
+    };
+    assert!(
+        matches!(&err, Error::Panic(msg) if msg.contains("tagged memos")),
+        "unexpected error {err:?}"
+    );
+
+    let config = ExecutionConfig {
+        with_tagged_memos: true,
+        ..ExecutionConfig::DEFAULT
+    };
+    let receipt = execute(&mut session, &tx, &config)
+        .expect("Executing the transaction should succeed");
+    assert!(receipt.data.is_ok(), "unexpected error {:?}", receipt.data);
+
+    // The kind of the memo is carried by its own event
+    let events: Vec<MemoEvent> = receipt
+        .events
+        .iter()
+        .filter(|event| event.topic == MEMO_TOPIC)
+        .map(|event| {
+            assert_eq!(event.source, TRANSFER_CONTRACT);
+            rkyv::from_bytes(&event.data)
+                .expect("Deserializing the memo event should succeed")
+        })
+        .collect();
+    assert_eq!(
+        events,
+        vec![MemoEvent {
+            kind: MemoKind::Encrypted,
+            content: memo.content().to_vec(),
+        }]
+    );
+}
+
+#[test]
+fn shipped_contracts_validate() {
+    let contracts: [(&str, &[u8]); 6] = [
+        (
+            "transfer",
+            include_bytes!(
+                "../../target/dusk/wasm64-unknown-unknown/release/transfer_contract.wasm"
+            ),
+        ),
+        (
+            "stake",
+            include_bytes!(
+                "../../target/dusk/wasm32-unknown-unknown/release/stake_contract.wasm"
+            ),
+        ),
+        (
+            "alice",
+            include_bytes!(
+                "../../target/dusk/wasm32-unknown-unknown/release/alice.wasm"
+            ),
+        ),
+        (
+            "bob",
+            include_bytes!(
+                "../../target/dusk/wasm32-unknown-unknown/release/bob.wasm"
+            ),
+        ),
+        (
+            "charlie",
+            include_bytes!(
+                "../../target/wasm32-unknown-unknown/release/charlie.wasm"
+            ),
+        ),
+        (
+            "host_fn",
+            include_bytes!(
+                "../../target/dusk/wasm32-unknown-unknown/release/host_fn.wasm"
+            ),
+        ),
+    ];
+
+    for (name, bytecode) in contracts {
+        if let Err(diagnostics) = validate_bytecode(bytecode) {
+            panic!("{name} should pass validation: {diagnostics:?}");
+        }
+    }
+}
+
+/// Executes a transaction deploying the given bytecode, with the bytecode
+/// validation enabled or not.
+fn deploy_with_validation(
+    bytecode: &[u8],
+    with_bytecode_validation: bool,
+) -> Result<Vec<u8>, ContractError> {
+    const GAS_LIMIT: u64 = 100_000_000;
+
+    let vm = VM::ephemeral().expect("Instantiating VM should succeed");
+    let (mut session, _) = instantiate_upgradable(&vm);
+
+    let sk = BlsSecretKey::random(&mut OsRng);
+    let pk = BlsPublicKey::from(&sk);
+    session
+        .call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "add_account_balance",
+            &(pk, dusk_core::dusk(1_000.0)),
+            POINT_LIMIT,
+        )
+        .expect("Funding the account should succeed");
+
+    let deploy = ContractDeploy {
+        bytecode: ContractBytecode {
+            hash: blake3::hash(bytecode).into(),
+            bytes: bytecode.to_vec(),
+        },
+        owner: pk.to_bytes().to_vec(),
+        init_args: None,
+        nonce: 0,
+    };
+    let tx = Transaction::moonlight(
+        &sk,
+        None,
+        0,
+        0,
+        GAS_LIMIT,
+        1,
+        1,
+        CHAIN_ID,
+        Some(deploy),
+    )
+    .expect("Creating the transaction should succeed");
+
+    let config = ExecutionConfig {
+        with_bytecode_validation,
+        ..ExecutionConfig::DEFAULT
+    };
+    let receipt = execute(&mut session, &tx, &config)
+        .expect("Executing the transaction should succeed");
+    if receipt.data.is_err() {
+        assert_eq!(receipt.gas_spent, GAS_LIMIT, "All gas should be spent");
+    }
+    receipt.data
+}
+
+#[test]
+fn deploy_validation() {
+    let alice_bytecode = include_bytes!(
+        "../../target/dusk/wasm32-unknown-unknown/release/alice.wasm"
+    );
+
+    // The module is well-formed, but exports nothing
+    let bytecode = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    let Err(err) = deploy_with_validation(&bytecode, true) else {
+        panic!("Deploying an invalid contract should fail");
+    };
+    assert!(
+        matches!(
+            &err,
+            ContractError::Panic(msg)
+                if msg.contains("failed bytecode validation")
+        ),
+        "unexpected error {err:?}"
+    );
+
+    // Without validation, it only fails if the bytecode can't be deployed
+    if let Err(err) = deploy_with_validation(&bytecode, false) {
+        assert!(
+            matches!(
+                &err,
+                ContractError::Panic(msg)
+                    if !msg.contains("failed bytecode validation")
+            ),
+            "unexpected error {err:?}"
+        );
+    }
+
+    // Valid contracts are deployed with validation enabled
+    if let Err(err) = deploy_with_validation(alice_bytecode, true) {
+        panic!("Deploying alice should succeed: {err:?}");
+    }
 }
@@ -25,8 +25,15 @@ extern crate alloc;
 mod ffi;
 
 pub mod keys;
+pub mod memo;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
 pub mod notes;
 pub mod transaction;
+pub mod vectors;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 /// The seed used to generate the entropy for the keys
 pub type Seed = [u8; 64];
@@ -38,7 +45,10 @@ pub mod prelude {
 }
 
 pub use notes::balance::{
-    calculate as phoenix_balance, TotalAmount as BalanceInfo,
+    calculate as phoenix_balance, BalanceState, TotalAmount as BalanceInfo,
+};
+pub use notes::control::CoinControl;
+pub use notes::owned::{
+    map as map_owned, map_update as map_owned_update, scan as scan_owned,
 };
-pub use notes::owned::map as map_owned;
-pub use notes::pick::notes as pick_notes;
+pub use notes::pick::{notes as pick_notes, CoinSelection, CoinSelector};
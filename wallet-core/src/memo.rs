@@ -0,0 +1,166 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Encryption of transaction memos, readable only by their recipient.
+//!
+//! A memo is encrypted with a key shared between an ephemeral key-pair and the
+//! `A` component of the recipient's phoenix public key, following the ECIES
+//! scheme over `JubJub`. The recipient recovers the shared key with its
+//! [`PhoenixViewKey`], so that memos can be decrypted while scanning for owned
+//! notes.
+//!
+//! The encrypted memo is a [`Memo`] tagged with [`MemoKind::Encrypted`], whose
+//! content consists of the ephemeral public key, the memo xored with a blake3
+//! keystream, and a blake3 MAC of the ciphertext.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_core::transfer::data::{Memo, MemoKind, MAX_MEMO_SIZE, MEMO_TAG_SIZE};
+use dusk_core::transfer::phoenix::{
+    PublicKey as PhoenixPublicKey, ViewKey as PhoenixViewKey,
+};
+use dusk_core::{
+    Error, JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR_EXTENDED,
+};
+use ff::Field;
+use rand::{CryptoRng, RngCore};
+
+const ENCRYPTION_CONTEXT: &str = "dusk wallet-core 2025 memo encryption";
+const MAC_CONTEXT: &str = "dusk wallet-core 2025 memo authentication";
+
+const MAC_SIZE: usize = 32;
+
+/// The number of bytes an encrypted memo is larger than its plaintext,
+/// including its tag.
+pub const ENCRYPTION_OVERHEAD: usize =
+    MEMO_TAG_SIZE + JubJubAffine::SIZE + MAC_SIZE;
+
+/// The maximum size of a memo that can be encrypted and still fit in a
+/// transaction.
+pub const MAX_ENCRYPTED_MEMO_SIZE: usize = MAX_MEMO_SIZE - ENCRYPTION_OVERHEAD;
+
+/// Encrypt a memo so that it can only be read by the owner of the given
+/// [`PhoenixPublicKey`].
+///
+/// # Errors
+/// Errors with [`Error::MemoTooLarge`] if the memo is larger than
+/// [`MAX_ENCRYPTED_MEMO_SIZE`].
+pub fn encrypt<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    receiver_pk: &PhoenixPublicKey,
+    memo: &[u8],
+) -> Result<Memo, Error> {
+    if memo.len() > MAX_ENCRYPTED_MEMO_SIZE {
+        return Err(Error::MemoTooLarge(memo.len() + ENCRYPTION_OVERHEAD));
+    }
+
+    let r = JubJubScalar::random(&mut *rng);
+    let ephemeral_pk = JubJubAffine::from(GENERATOR_EXTENDED * r).to_bytes();
+    let shared = receiver_pk.A() * r;
+
+    let (encryption_key, mac_key) = derive_keys(&shared, &ephemeral_pk);
+
+    let mut encrypted = Vec::with_capacity(memo.len() + ENCRYPTION_OVERHEAD);
+    encrypted.extend(ephemeral_pk);
+    encrypted.extend(memo);
+    apply_keystream(&encryption_key, &mut encrypted[JubJubAffine::SIZE..]);
+
+    let mac = blake3::keyed_hash(&mac_key, &encrypted[JubJubAffine::SIZE..]);
+    encrypted.extend(mac.as_bytes());
+
+    Memo::with_kind(MemoKind::Encrypted, &encrypted)
+}
+
+/// Decrypt a memo encrypted with [`encrypt`] for the owner of the given
+/// [`PhoenixViewKey`].
+///
+/// Returns `None` if the memo isn't tagged as encrypted, wasn't encrypted for
+/// the given key, or was tampered with.
+#[must_use]
+pub fn decrypt(vk: &PhoenixViewKey, memo: &Memo) -> Option<Vec<u8>> {
+    if memo.kind() != MemoKind::Encrypted {
+        return None;
+    }
+
+    let encrypted = memo.content();
+    if encrypted.len() < ENCRYPTION_OVERHEAD - MEMO_TAG_SIZE {
+        return None;
+    }
+    let (ephemeral_pk, rest) = encrypted.split_at(JubJubAffine::SIZE);
+    let (ciphertext, mac) = rest.split_at(rest.len() - MAC_SIZE);
+
+    let point = JubJubAffine::from_slice(ephemeral_pk).ok()?;
+    let shared = JubJubExtended::from(point) * vk.a();
+
+    let (encryption_key, mac_key) = derive_keys(&shared, &point.to_bytes());
+
+    // comparing `blake3::Hash`es is constant-time
+    let mac: [u8; MAC_SIZE] = mac.try_into().ok()?;
+    if blake3::keyed_hash(&mac_key, ciphertext) != blake3::Hash::from(mac) {
+        return None;
+    }
+
+    let mut memo = ciphertext.to_vec();
+    apply_keystream(&encryption_key, &mut memo);
+
+    Some(memo)
+}
+
+/// Decrypt the given memos with each of the given view keys, in a single pass
+/// over the memos, in the same way [`scan_owned`] classifies notes.
+///
+/// The returned lists hold, for the key at the same index, the indices of the
+/// memos encrypted for it together with their plaintext.
+///
+/// [`scan_owned`]: crate::scan_owned
+pub fn scan<T: Borrow<Memo>>(
+    vks: impl AsRef<[PhoenixViewKey]>,
+    memos: impl AsRef<[T]>,
+) -> Vec<Vec<(usize, Vec<u8>)>> {
+    let vks = vks.as_ref();
+
+    memos.as_ref().iter().enumerate().fold(
+        alloc::vec![Vec::new(); vks.len()],
+        |mut decrypted, (index, memo)| {
+            for (i, vk) in vks.iter().enumerate() {
+                if let Some(memo) = decrypt(vk, memo.borrow()) {
+                    decrypted[i].push((index, memo));
+                    break;
+                }
+            }
+            decrypted
+        },
+    )
+}
+
+fn derive_keys(
+    shared: &JubJubExtended,
+    ephemeral_pk: &[u8; JubJubAffine::SIZE],
+) -> ([u8; 32], [u8; 32]) {
+    let mut material = [0u8; 2 * JubJubAffine::SIZE];
+    material[..JubJubAffine::SIZE]
+        .copy_from_slice(&JubJubAffine::from(shared).to_bytes());
+    material[JubJubAffine::SIZE..].copy_from_slice(ephemeral_pk);
+
+    (
+        blake3::derive_key(ENCRYPTION_CONTEXT, &material),
+        blake3::derive_key(MAC_CONTEXT, &material),
+    )
+}
+
+fn apply_keystream(key: &[u8; 32], bytes: &mut [u8]) {
+    let mut keystream = alloc::vec![0u8; bytes.len()];
+    blake3::Hasher::new_keyed(key)
+        .finalize_xof()
+        .fill(&mut keystream);
+
+    bytes
+        .iter_mut()
+        .zip(keystream)
+        .for_each(|(byte, key)| *byte ^= key);
+}
@@ -0,0 +1,456 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! This module provides a [UniFFI] interface to the `wallet-core` library, so
+//! that Swift and Kotlin bindings can be generated for mobile wallets.
+//!
+//! It mirrors the functions exported to the WASM runtime, with the owned-value
+//! API required by UniFFI: keys are derived from the seed on every call and
+//! zeroized afterwards, note-leaves, openings and unproven transactions are
+//! passed as their rkyv archives, and public keys, scalars and proven
+//! transactions use their canonical byte encoding.
+//!
+//! The interface is only compiled with the `uniffi` feature.
+//!
+//! [UniFFI]: https://mozilla.github.io/uniffi-rs/
+
+// UniFFI passes all arguments by value.
+#![allow(clippy::needless_pass_by_value)]
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt;
+
+use dusk_bytes::{DeserializableSlice, Serializable};
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::transfer::data::Memo;
+use dusk_core::transfer::phoenix::{
+    NoteLeaf, NoteOpening, Prove, PublicKey as PhoenixPublicKey,
+    Transaction as PhoenixTransaction,
+};
+use dusk_core::transfer::Transaction;
+use dusk_core::BlsScalar;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use zeroize::Zeroize;
+
+use crate::keys::{
+    derive_bls_pk, derive_bls_sk, derive_phoenix_pk, derive_phoenix_sk,
+    derive_phoenix_vk,
+};
+use crate::notes::{balance, owned, pick};
+use crate::{transaction, Seed};
+
+/// The errors returned by the functions of the mobile interface.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+pub enum MobileError {
+    /// The seed or the rng-seed have an invalid length.
+    InvalidSeed,
+    /// Archiving (rkyv serialization) error.
+    Archiving,
+    /// Unarchiving (rkyv deserialization) error.
+    Unarchiving,
+    /// Deserialization (dusk-bytes deserialization) error.
+    Deserialization,
+    /// The transaction couldn't be created.
+    Transaction,
+    /// The numbers of inputs and of their openings differ.
+    InputsMismatch,
+    /// The memo is larger than a transaction can hold.
+    MemoTooLarge,
+}
+
+impl fmt::Display for MobileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSeed => write!(f, "Invalid seed length"),
+            Self::Archiving => write!(f, "Archiving error"),
+            Self::Unarchiving => write!(f, "Unarchiving error"),
+            Self::Deserialization => write!(f, "Deserialization error"),
+            Self::Transaction => write!(f, "Transaction creation error"),
+            Self::InputsMismatch => {
+                write!(f, "Inputs and openings lengths mismatch")
+            }
+            Self::MemoTooLarge => write!(f, "Memo too large"),
+        }
+    }
+}
+
+/// The public keys of a wallet profile.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct Profile {
+    /// The serialized phoenix public key.
+    pub phoenix_pk: Vec<u8>,
+    /// The serialized BLS public key of the moonlight account.
+    pub bls_pk: Vec<u8>,
+}
+
+/// A note owned by a profile.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct OwnedNote {
+    /// The serialized nullifier of the note.
+    pub nullifier: Vec<u8>,
+    /// The rkyv archive of the [`NoteLeaf`].
+    pub leaf: Vec<u8>,
+}
+
+/// Information about the balance of a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct Balance {
+    /// The total value of the balance.
+    pub value: u64,
+    /// The maximum value spendable in a single transaction.
+    pub spendable: u64,
+}
+
+/// A phoenix transaction waiting for its proof.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct UnprovenTransaction {
+    /// The rkyv archive of the phoenix transaction.
+    pub tx: Vec<u8>,
+    /// The serialized circuits to prove.
+    pub circuits: Vec<u8>,
+}
+
+/// A transaction ready to be propagated to the network.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ReadyTransaction {
+    /// The serialized transaction.
+    pub tx: Vec<u8>,
+    /// The serialized hash of the transaction.
+    pub hash: Vec<u8>,
+}
+
+/// Generate the public keys of the profile at the given index.
+///
+/// # Errors
+/// Errors if the seed is not 64 bytes long.
+#[uniffi::export]
+pub fn generate_profile(
+    seed: Vec<u8>,
+    index: u8,
+) -> Result<Profile, MobileError> {
+    let mut seed = into_seed(seed)?;
+
+    let profile = Profile {
+        phoenix_pk: derive_phoenix_pk(&seed, index).to_bytes().to_vec(),
+        bls_pk: derive_bls_pk(&seed, index).to_bytes().to_vec(),
+    };
+
+    seed.zeroize();
+
+    Ok(profile)
+}
+
+/// Classify the given note-leaves by the profiles at the given indexes that
+/// own them, returning the owned notes of each profile together with their
+/// nullifiers.
+///
+/// # Errors
+/// Errors if the seed is not 64 bytes long or a leaf can't be unarchived.
+#[uniffi::export]
+pub fn scan_notes(
+    seed: Vec<u8>,
+    indexes: Vec<u8>,
+    leaves: Vec<Vec<u8>>,
+) -> Result<Vec<Vec<OwnedNote>>, MobileError> {
+    let leaves = leaves
+        .iter()
+        .map(|leaf| rkyv::from_bytes(leaf).or(Err(MobileError::Unarchiving)))
+        .collect::<Result<Vec<NoteLeaf>, _>>()?;
+
+    let mut seed = into_seed(seed)?;
+    let keys: Vec<_> = indexes
+        .iter()
+        .map(|index| derive_phoenix_sk(&seed, *index))
+        .collect();
+    seed.zeroize();
+
+    let owned = owned::map(&keys, leaves);
+
+    keys.into_iter().for_each(|mut sk| sk.zeroize());
+
+    owned.iter().map(from_note_list).collect()
+}
+
+/// Calculate the balance of the profile at the given index from its owned
+/// note-leaves.
+///
+/// # Errors
+/// Errors if the seed is not 64 bytes long or a leaf can't be unarchived.
+#[uniffi::export]
+pub fn balance(
+    seed: Vec<u8>,
+    index: u8,
+    leaves: Vec<Vec<u8>>,
+) -> Result<Balance, MobileError> {
+    let leaves = leaves
+        .iter()
+        .map(|leaf| rkyv::from_bytes(leaf).or(Err(MobileError::Unarchiving)))
+        .collect::<Result<Vec<NoteLeaf>, _>>()?;
+
+    let mut seed = into_seed(seed)?;
+    let vk = derive_phoenix_vk(&seed, index);
+    seed.zeroize();
+
+    let info = balance::calculate(&vk, leaves.iter());
+
+    Ok(Balance {
+        value: info.value,
+        spendable: info.spendable,
+    })
+}
+
+/// Pick the notes to be used as inputs of a transaction spending `value`
+/// from the owned notes of the profile at the given index.
+///
+/// # Errors
+/// Errors if the seed is not 64 bytes long or a note can't be deserialized.
+#[uniffi::export]
+pub fn pick_notes(
+    seed: Vec<u8>,
+    index: u8,
+    notes: Vec<OwnedNote>,
+    value: u64,
+) -> Result<Vec<OwnedNote>, MobileError> {
+    let notes = into_note_list(notes)?;
+
+    let mut seed = into_seed(seed)?;
+    let vk = derive_phoenix_vk(&seed, index);
+    seed.zeroize();
+
+    from_note_list(&pick::notes(&vk, notes, value))
+}
+
+/// Create an unproven phoenix transaction sending `transfer_value` from the
+/// profile at `sender_index` to the given receiver.
+///
+/// The `inputs` are the note-leaves to spend, and `openings` their rkyv
+/// archived openings in the same order.
+///
+/// # Errors
+/// Errors if the seeds have an invalid length, the numbers of inputs and
+/// openings differ, any of the arguments can't be deserialized, the memo is
+/// too large, or the transaction can't be created.
+#[uniffi::export]
+#[allow(clippy::too_many_arguments)]
+pub fn phoenix(
+    rng_seed: Vec<u8>,
+    seed: Vec<u8>,
+    sender_index: u8,
+    receiver: Vec<u8>,
+    inputs: Vec<Vec<u8>>,
+    openings: Vec<Vec<u8>>,
+    root: Vec<u8>,
+    transfer_value: u64,
+    obfuscated_transaction: bool,
+    deposit: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    chain_id: u8,
+    memo: Option<Vec<u8>>,
+) -> Result<UnprovenTransaction, MobileError> {
+    let rng_seed: [u8; 32] =
+        rng_seed.try_into().or(Err(MobileError::InvalidSeed))?;
+    let mut rng = ChaCha12Rng::from_seed(rng_seed);
+
+    let receiver_pk = PhoenixPublicKey::from_slice(&receiver)
+        .or(Err(MobileError::Deserialization))?;
+    let root =
+        BlsScalar::from_slice(&root).or(Err(MobileError::Deserialization))?;
+
+    if inputs.len() != openings.len() {
+        return Err(MobileError::InputsMismatch);
+    }
+    let memo = into_memo(memo)?;
+    let inputs = inputs
+        .iter()
+        .zip(openings.iter())
+        .map(|(leaf, opening)| {
+            let leaf: NoteLeaf =
+                rkyv::from_bytes(leaf).or(Err(MobileError::Unarchiving))?;
+            let opening: NoteOpening =
+                rkyv::from_bytes(opening).or(Err(MobileError::Unarchiving))?;
+            Ok((leaf.note, opening))
+        })
+        .collect::<Result<Vec<_>, MobileError>>()?;
+
+    let mut seed = into_seed(seed)?;
+    let mut sender_sk = derive_phoenix_sk(&seed, sender_index);
+    seed.zeroize();
+    let change_pk = PhoenixPublicKey::from(&sender_sk);
+
+    let prover = NoOpProver::default();
+
+    let tx = transaction::phoenix(
+        &mut rng,
+        &sender_sk,
+        &change_pk,
+        &receiver_pk,
+        inputs,
+        root,
+        transfer_value,
+        obfuscated_transaction,
+        deposit,
+        gas_limit,
+        gas_price,
+        chain_id,
+        memo,
+        &prover,
+    );
+
+    sender_sk.zeroize();
+
+    let Ok(Transaction::Phoenix(tx)) = tx else {
+        return Err(MobileError::Transaction);
+    };
+
+    let tx = rkyv::to_bytes::<_, 4096>(&tx)
+        .or(Err(MobileError::Archiving))?
+        .to_vec();
+
+    Ok(UnprovenTransaction {
+        tx,
+        circuits: prover.circuits.into_inner(),
+    })
+}
+
+/// Insert the proof of the circuits of an [`UnprovenTransaction`] into its
+/// transaction.
+///
+/// # Errors
+/// Errors if the transaction can't be unarchived.
+#[uniffi::export]
+pub fn into_proven(
+    tx: Vec<u8>,
+    proof: Vec<u8>,
+) -> Result<ReadyTransaction, MobileError> {
+    let mut tx: PhoenixTransaction =
+        rkyv::from_bytes(&tx).or(Err(MobileError::Unarchiving))?;
+
+    tx.set_proof(proof);
+
+    let hash = tx.hash().to_bytes().to_vec();
+    let tx = Transaction::Phoenix(tx).to_var_bytes();
+
+    Ok(ReadyTransaction { tx, hash })
+}
+
+/// Create a moonlight transaction sending `transfer_value` from the account
+/// of the profile at `sender_index` to the given receiver.
+///
+/// # Note
+/// The `nonce` is NOT incremented and should be incremented by the caller of
+/// this function.
+///
+/// # Errors
+/// Errors if the seed is not 64 bytes long, the receiver can't be
+/// deserialized, the memo is too large, or the transaction can't be created.
+#[uniffi::export]
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight(
+    seed: Vec<u8>,
+    sender_index: u8,
+    receiver: Option<Vec<u8>>,
+    transfer_value: u64,
+    deposit: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    nonce: u64,
+    chain_id: u8,
+    memo: Option<Vec<u8>>,
+) -> Result<ReadyTransaction, MobileError> {
+    let receiver_pk = receiver
+        .map(|receiver| BlsPublicKey::from_slice(&receiver))
+        .transpose()
+        .or(Err(MobileError::Deserialization))?;
+    let memo = into_memo(memo)?;
+
+    let mut seed = into_seed(seed)?;
+    let mut sender_sk = derive_bls_sk(&seed, sender_index);
+    seed.zeroize();
+
+    let tx = transaction::moonlight(
+        &sender_sk,
+        receiver_pk,
+        transfer_value,
+        deposit,
+        gas_limit,
+        gas_price,
+        nonce,
+        chain_id,
+        memo,
+    );
+
+    sender_sk.zeroize();
+
+    let tx = tx.or(Err(MobileError::Transaction))?;
+
+    Ok(ReadyTransaction {
+        hash: tx.hash().to_bytes().to_vec(),
+        tx: tx.to_var_bytes(),
+    })
+}
+
+/// Collects the circuits passed to the prover instead of proving them, so that
+/// the proof can be generated by a prover outside of the device.
+#[derive(Default)]
+struct NoOpProver {
+    circuits: RefCell<Vec<u8>>,
+}
+
+impl Prove for NoOpProver {
+    fn prove(&self, circuits: &[u8]) -> Result<Vec<u8>, dusk_core::Error> {
+        *self.circuits.borrow_mut() = circuits.to_vec();
+
+        Ok(circuits.to_vec())
+    }
+}
+
+fn into_seed(mut bytes: Vec<u8>) -> Result<Seed, MobileError> {
+    let seed = Seed::try_from(bytes.as_slice());
+    bytes.zeroize();
+
+    seed.or(Err(MobileError::InvalidSeed))
+}
+
+fn into_memo(memo: Option<Vec<u8>>) -> Result<Option<Memo>, MobileError> {
+    memo.map(Memo::new)
+        .transpose()
+        .or(Err(MobileError::MemoTooLarge))
+}
+
+fn from_note_list(
+    notes: &owned::NoteList,
+) -> Result<Vec<OwnedNote>, MobileError> {
+    notes
+        .iter()
+        .map(|(nullifier, leaf)| {
+            Ok(OwnedNote {
+                nullifier: nullifier.to_bytes().to_vec(),
+                leaf: rkyv::to_bytes::<_, 1024>(leaf)
+                    .or(Err(MobileError::Archiving))?
+                    .to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn into_note_list(
+    notes: Vec<OwnedNote>,
+) -> Result<owned::NoteList, MobileError> {
+    notes
+        .into_iter()
+        .map(|note| {
+            let nullifier = BlsScalar::from_slice(&note.nullifier)
+                .or(Err(MobileError::Deserialization))?;
+            let leaf = rkyv::from_bytes(&note.leaf)
+                .or(Err(MobileError::Unarchiving))?;
+            Ok((nullifier, leaf))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Into::into)
+}
@@ -8,6 +8,8 @@
 
 /// Module for balance information.
 pub mod balance;
+/// Module for coin control.
+pub mod control;
 /// Module for owned notes.
 pub mod owned;
 /// Module for picking notes.
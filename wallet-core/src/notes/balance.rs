@@ -10,7 +10,9 @@ use alloc::vec::Vec;
 
 use dusk_bytes::{DeserializableSlice, Serializable, Write};
 use dusk_core::transfer::phoenix::{Note, ViewKey as PhoenixViewKey};
+use dusk_core::BlsScalar;
 
+use crate::notes::owned::NoteList;
 use crate::notes::MAX_INPUT_NOTES;
 
 /// Calculate the sum for all the given [`Note`]s that belong to the given
@@ -61,6 +63,71 @@ where
     TotalAmount { value, spendable }
 }
 
+/// The decrypted note-values of a particular key, indexed by the notes'
+/// nullifiers, from which the balance can be updated incrementally.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct BalanceState {
+    /// The nullifiers and values of the unspent notes, sorted from largest to
+    /// smallest value.
+    values: Vec<(BlsScalar, u64)>,
+}
+
+impl BalanceState {
+    /// Create the balance-state from all the owned notes of a key.
+    #[must_use]
+    pub fn new(vk: &PhoenixViewKey, notes: &NoteList) -> Self {
+        Self::default().update(vk, notes, &[])
+    }
+
+    /// Update the state with the newly owned notes and the nullifiers of the
+    /// notes spent since the state was computed.
+    ///
+    /// Notes whose value can't be decrypted with the given [`PhoenixViewKey`]
+    /// are ignored.
+    #[must_use]
+    pub fn update(
+        mut self,
+        vk: &PhoenixViewKey,
+        notes: &NoteList,
+        spent: &[BlsScalar],
+    ) -> Self {
+        self.values
+            .extend(notes.iter().filter_map(|(nullifier, leaf)| {
+                leaf.note
+                    .value(Some(vk))
+                    .ok()
+                    .map(|value| (*nullifier, value))
+            }));
+        self.values
+            .retain(|(nullifier, _)| !spent.contains(nullifier));
+
+        self.values.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        self
+    }
+
+    /// Calculate the balance of the notes in the state.
+    #[must_use]
+    pub fn balance(&self) -> TotalAmount {
+        let spendable = self
+            .values
+            .iter()
+            .take(MAX_INPUT_NOTES)
+            .map(|(_, value)| value)
+            .sum();
+        let value = spendable
+            + self
+                .values
+                .iter()
+                .skip(MAX_INPUT_NOTES)
+                .map(|(_, value)| value)
+                .sum::<u64>();
+
+        TotalAmount { value, spendable }
+    }
+}
+
 /// Information about the balance of a particular key.
 #[derive(Debug, Default, Hash, Clone, Copy, PartialEq, Eq)]
 pub struct TotalAmount {
@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Coin control over the notes picked as input-notes of a transaction.
+
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use dusk_core::transfer::phoenix::ViewKey as PhoenixViewKey;
+use dusk_core::BlsScalar;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::notes::owned::NoteList;
+use crate::notes::pick::CoinSelector;
+
+/// The coin-control metadata of a wallet, holding the nullifiers of the notes
+/// that are frozen.
+///
+/// Frozen notes still belong to the wallet and count towards its balance, but
+/// are never picked as input-notes of a transaction.
+#[derive(Default, Archive, Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[archive_attr(derive(CheckBytes))]
+#[allow(clippy::module_name_repetitions)]
+pub struct CoinControl {
+    frozen: Vec<BlsScalar>,
+}
+
+impl CoinControl {
+    /// Freeze the note with the given nullifier.
+    ///
+    /// Returns `false` if the note was already frozen.
+    pub fn freeze(&mut self, nullifier: BlsScalar) -> bool {
+        if self.is_frozen(&nullifier) {
+            return false;
+        }
+        self.frozen.push(nullifier);
+        true
+    }
+
+    /// Thaw the note with the given nullifier, allowing it to be spent again.
+    ///
+    /// Returns `false` if the note wasn't frozen.
+    pub fn thaw(&mut self, nullifier: &BlsScalar) -> bool {
+        let len = self.frozen.len();
+        self.frozen.retain(|frozen| frozen != nullifier);
+        self.frozen.len() != len
+    }
+
+    /// Checks if the note with the given nullifier is frozen.
+    #[must_use]
+    pub fn is_frozen(&self, nullifier: &BlsScalar) -> bool {
+        self.frozen.contains(nullifier)
+    }
+
+    /// The nullifiers of the frozen notes, in the order they were frozen.
+    #[must_use]
+    pub fn frozen(&self) -> &[BlsScalar] {
+        &self.frozen
+    }
+
+    /// Forget the frozen notes that were spent, since they can't be picked
+    /// anymore.
+    pub fn remove_spent(&mut self, spent: &[BlsScalar]) {
+        self.frozen.retain(|frozen| !spent.contains(frozen));
+    }
+
+    /// Filter out the frozen notes from a list of owned notes.
+    #[must_use]
+    pub fn spendable(&self, mut notes: NoteList) -> NoteList {
+        notes.remove_all(&self.frozen);
+        notes
+    }
+
+    /// Pick the input-notes of a transaction with the given strategy, among
+    /// the notes that aren't frozen.
+    #[must_use]
+    pub fn select<S: CoinSelector>(
+        &self,
+        selector: &S,
+        vk: &PhoenixViewKey,
+        notes: NoteList,
+        cost: u64,
+    ) -> NoteList {
+        selector.select(vk, self.spendable(notes), cost)
+    }
+}
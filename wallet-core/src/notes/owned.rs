@@ -12,7 +12,9 @@ use core::ops::Index;
 use core::slice::Iter;
 
 use bytecheck::CheckBytes;
-use dusk_core::transfer::phoenix::{NoteLeaf, SecretKey as PhoenixSecretKey};
+use dusk_core::transfer::phoenix::{
+    NoteLeaf, SecretKey as PhoenixSecretKey, ViewKey as PhoenixViewKey,
+};
 use dusk_core::BlsScalar;
 use rkyv::{Archive, Deserialize, Serialize};
 
@@ -61,6 +63,11 @@ impl NoteList {
     pub fn iter(&self) -> Iter<'_, (BlsScalar, NoteLeaf)> {
         self.entries.iter()
     }
+
+    /// Removes all the entries with the given keys from the collection.
+    pub fn remove_all(&mut self, keys: &[BlsScalar]) {
+        self.entries.retain(|(k, _)| !keys.contains(k));
+    }
 }
 
 impl Index<&BlsScalar> for NoteList {
@@ -109,3 +116,51 @@ pub fn map(
         },
     )
 }
+
+/// Classify the given notes by the view keys that own them, in a single pass
+/// over the notes.
+///
+/// Each note is checked against the keys in order until its owner is found,
+/// so that the notes of all of a wallet's profiles are collected at once
+/// instead of scanning the whole list once per profile. The returned lists
+/// hold the notes owned by the key at the same index.
+pub fn scan(
+    vks: impl AsRef<[PhoenixViewKey]>,
+    notes: impl AsRef<[NoteLeaf]>,
+) -> Vec<Vec<NoteLeaf>> {
+    let vks = vks.as_ref();
+
+    notes.as_ref().iter().fold(
+        vec![Vec::new(); vks.len()],
+        |mut owned, note_leaf| {
+            let stealth_address = note_leaf.note.stealth_address();
+            if let Some(i) = vks.iter().position(|vk| vk.owns(stealth_address))
+            {
+                owned[i].push(note_leaf.clone());
+            }
+            owned
+        },
+    )
+}
+
+/// Update a previous result of [`map`] with newly received notes and the
+/// nullifiers of the notes spent since then.
+///
+/// The new notes owned by the given keys are added to their respective lists,
+/// after which all notes whose nullifier is in `spent` are removed, so that
+/// wallets syncing small deltas don't need to rescan their entire note set.
+pub fn map_update(
+    keys: impl AsRef<[PhoenixSecretKey]>,
+    mut previous: Vec<NoteList>,
+    notes: impl AsRef<[NoteLeaf]>,
+    spent: &[BlsScalar],
+) -> Vec<NoteList> {
+    previous.resize(keys.as_ref().len(), NoteList::default());
+
+    for (notes_map, new_notes) in previous.iter_mut().zip(map(keys, notes)) {
+        notes_map.entries.extend(new_notes.entries);
+        notes_map.remove_all(spent);
+    }
+
+    previous
+}
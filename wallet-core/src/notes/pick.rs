@@ -6,6 +6,7 @@
 
 //! Helper functions for working with notes.
 
+use alloc::vec;
 use alloc::vec::Vec;
 
 use dusk_core::transfer::phoenix::{NoteLeaf, ViewKey as PhoenixViewKey};
@@ -33,15 +34,7 @@ pub fn notes(vk: &PhoenixViewKey, notes: NoteList, cost: u64) -> NoteList {
     }
 
     // decrypt the note-values
-    let mut notes_values_nullifier: Vec<(NoteLeaf, u64, BlsScalar)> = notes
-        .iter()
-        .filter_map(|(nullifier, leaf)| {
-            leaf.as_ref()
-                .value(Some(vk))
-                .ok()
-                .map(|value| (leaf.clone(), value, *nullifier))
-        })
-        .collect();
+    let mut notes_values_nullifier = decrypt_values(vk, &notes);
 
     // sort the input-notes from smallest to largest value
     notes_values_nullifier.sort_by(|(_, aval, _), (_, bval, _)| aval.cmp(bval));
@@ -76,6 +69,233 @@ pub fn notes(vk: &PhoenixViewKey, notes: NoteList, cost: u64) -> NoteList {
         .into()
 }
 
+/// A strategy to pick the input-notes of a phoenix-transaction.
+pub trait CoinSelector {
+    /// Pick up to [`MAX_INPUT_NOTES`] notes from a list of owned notes, whose
+    /// values cover the given cost.
+    ///
+    /// If no such combination can be found, an empty list is returned.
+    fn select(
+        &self,
+        vk: &PhoenixViewKey,
+        notes: NoteList,
+        cost: u64,
+    ) -> NoteList;
+}
+
+/// The coin-selection strategies provided by the wallet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CoinSelection {
+    /// Use as many notes as possible, favoring the ones with the smallest
+    /// values, as done by [`notes`]. This consolidates small notes over time.
+    #[default]
+    Consolidate,
+    /// Use as few notes as possible, favoring the ones with the largest
+    /// values.
+    MinimizeInputs,
+    /// Minimize the value in excess of the cost, which is returned as change.
+    MinimizeChange,
+    /// Use notes of equal value, so that the inputs of a transaction don't
+    /// stand out by their values. Falls back to [`Self::Consolidate`] if no
+    /// notes of equal value cover the cost.
+    PrivacyMax,
+    /// Use the oldest notes first. Falls back to [`Self::MinimizeInputs`] if
+    /// the oldest notes don't cover the cost.
+    OldestFirst,
+}
+
+impl CoinSelector for CoinSelection {
+    fn select(
+        &self,
+        vk: &PhoenixViewKey,
+        notes: NoteList,
+        cost: u64,
+    ) -> NoteList {
+        if *self == Self::Consolidate {
+            return self::notes(vk, notes, cost);
+        }
+
+        let mut candidates = decrypt_values(vk, &notes);
+        let indices = match self {
+            Self::Consolidate => unreachable!("handled above"),
+            Self::MinimizeInputs => minimize_inputs(&mut candidates, cost),
+            Self::MinimizeChange => minimize_change(&mut candidates, cost),
+            Self::PrivacyMax => match equal_values(&mut candidates, cost) {
+                Some(indices) => Some(indices),
+                None => return Self::Consolidate.select(vk, notes, cost),
+            },
+            Self::OldestFirst => oldest_first(&mut candidates, cost)
+                .or_else(|| minimize_inputs(&mut candidates, cost)),
+        };
+
+        indices
+            .map(|indices| {
+                indices
+                    .into_iter()
+                    .map(|index| {
+                        let (leaf, _, nullifier) = &candidates[index];
+                        (*nullifier, leaf.clone())
+                    })
+                    .collect::<Vec<_>>()
+                    .into()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The maximum number of notes considered by [`CoinSelection::MinimizeChange`]
+/// when combining notes, bounding the number of combinations tried.
+const MAX_CHANGE_CANDIDATES: usize = 32;
+
+// Decrypt the values of the notes, skipping the ones that can't be decrypted.
+fn decrypt_values(
+    vk: &PhoenixViewKey,
+    notes: &NoteList,
+) -> Vec<(NoteLeaf, u64, BlsScalar)> {
+    notes
+        .iter()
+        .filter_map(|(nullifier, leaf)| {
+            leaf.as_ref()
+                .value(Some(vk))
+                .ok()
+                .map(|value| (leaf.clone(), value, *nullifier))
+        })
+        .collect()
+}
+
+// Take the notes in the order of the candidates until their values cover the
+// cost.
+fn take_until_covered(
+    candidates: &[(NoteLeaf, u64, BlsScalar)],
+    cost: u64,
+) -> Option<Vec<usize>> {
+    let mut sum = 0u64;
+    for (index, (_, value, _)) in
+        candidates.iter().enumerate().take(MAX_INPUT_NOTES)
+    {
+        sum = sum.saturating_add(*value);
+        if sum >= cost {
+            return Some((0..=index).collect());
+        }
+    }
+    None
+}
+
+fn minimize_inputs(
+    candidates: &mut [(NoteLeaf, u64, BlsScalar)],
+    cost: u64,
+) -> Option<Vec<usize>> {
+    candidates.sort_by(|(_, aval, _), (_, bval, _)| bval.cmp(aval));
+    take_until_covered(candidates, cost)
+}
+
+fn oldest_first(
+    candidates: &mut [(NoteLeaf, u64, BlsScalar)],
+    cost: u64,
+) -> Option<Vec<usize>> {
+    candidates.sort_by_key(|(leaf, _, _)| leaf.block_height);
+    take_until_covered(candidates, cost)
+}
+
+// Pick the notes whose values exceed the cost by the least amount.
+//
+// A single note covering the cost is compared against the combinations of the
+// largest notes that don't, limited to `MAX_CHANGE_CANDIDATES` of them.
+fn minimize_change(
+    candidates: &mut [(NoteLeaf, u64, BlsScalar)],
+    cost: u64,
+) -> Option<Vec<usize>> {
+    candidates.sort_by(|(_, aval, _), (_, bval, _)| aval.cmp(bval));
+    let values: Vec<u64> =
+        candidates.iter().map(|(_, value, _)| *value).collect();
+
+    let split = values.partition_point(|value| *value < cost);
+    let mut best = values.get(split).map(|value| (value - cost, vec![split]));
+
+    let pool: Vec<usize> =
+        (split.saturating_sub(MAX_CHANGE_CANDIDATES)..split).collect();
+    let mut combination = Vec::with_capacity(MAX_INPUT_NOTES);
+    search_change(&values, &pool, cost, 0, &mut combination, &mut best);
+
+    best.map(|(_, indices)| indices)
+}
+
+// Recursively search the combinations of notes in the pool, starting at the
+// given position, for the one with the least change.
+fn search_change(
+    values: &[u64],
+    pool: &[usize],
+    cost: u64,
+    from: usize,
+    combination: &mut Vec<usize>,
+    best: &mut Option<(u64, Vec<usize>)>,
+) {
+    let sum: u64 = combination.iter().map(|index| values[*index]).sum();
+    if sum >= cost {
+        let change = sum - cost;
+        if best
+            .as_ref()
+            .map_or(true, |(best_change, _)| change < *best_change)
+        {
+            *best = Some((change, combination.clone()));
+        }
+        return;
+    }
+    if combination.len() == MAX_INPUT_NOTES {
+        return;
+    }
+
+    for (position, index) in pool.iter().enumerate().skip(from) {
+        combination.push(*index);
+        search_change(values, pool, cost, position + 1, combination, best);
+        combination.pop();
+
+        if matches!(best, Some((0, _))) {
+            return;
+        }
+    }
+}
+
+// Pick notes of the same value covering the cost, choosing the value that
+// results in the least change.
+fn equal_values(
+    candidates: &mut [(NoteLeaf, u64, BlsScalar)],
+    cost: u64,
+) -> Option<Vec<usize>> {
+    candidates.sort_by(|(_, aval, _), (_, bval, _)| aval.cmp(bval));
+
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut start = 0;
+    while start < candidates.len() {
+        let value = candidates[start].1;
+        let end = start
+            + candidates[start..]
+                .iter()
+                .take_while(|(_, v, _)| *v == value)
+                .count();
+
+        if value > 0 {
+            let needed = cost.div_ceil(value).max(1);
+            let available = (end - start).min(MAX_INPUT_NOTES) as u64;
+            if needed <= available {
+                let change = needed * value - cost;
+                if best
+                    .as_ref()
+                    .map_or(true, |(best_change, _)| change < *best_change)
+                {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let indices = (start..start + needed as usize).collect();
+                    best = Some((change, indices));
+                }
+            }
+        }
+
+        start = end;
+    }
+
+    best.map(|(_, indices)| indices)
+}
+
 // Sum up the values of the MAX_INPUT_NOTES notes stored at the given indices
 // and check that this sum is larger or equal the given cost.
 fn is_valid(
@@ -13,14 +13,18 @@ use dusk_core::abi::ContractId;
 use dusk_core::signatures::bls::{
     PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
 };
-use dusk_core::stake::{Stake, Withdraw as StakeWithdraw, STAKE_CONTRACT};
+use dusk_core::stake::{
+    Commission, Delegate, DelegationWithdraw, DoubleSignature, RewardAddress,
+    Stake, StakeKeys, Withdraw as StakeWithdraw, STAKE_CONTRACT,
+};
 use dusk_core::transfer::data::{
     ContractBytecode, ContractCall, ContractDeploy, TransactionData,
 };
 use dusk_core::transfer::moonlight::Transaction as MoonlightTransaction;
 use dusk_core::transfer::phoenix::{
-    Note, NoteOpening, Prove, PublicKey as PhoenixPublicKey,
+    Note, NoteLeaf, NoteOpening, Prove, PublicKey as PhoenixPublicKey,
     SecretKey as PhoenixSecretKey, Transaction as PhoenixTransaction,
+    ViewKey as PhoenixViewKey,
 };
 use dusk_core::transfer::withdraw::{
     Withdraw, WithdrawReceiver, WithdrawReplayToken,
@@ -31,6 +35,9 @@ use ff::Field;
 use rand::{CryptoRng, RngCore};
 use zeroize::Zeroize;
 
+use crate::notes::owned::NoteList;
+use crate::notes::{pick, MAX_INPUT_NOTES};
+
 /// An unproven-transaction is nearly identical to a [`PhoenixTransaction`] with
 /// the only difference being that it carries a serialized [`TxCircuitVec`]
 /// instead of the proof bytes.
@@ -184,7 +191,8 @@ pub fn phoenix_stake<R: RngCore + CryptoRng, P: Prove>(
     )
 }
 
-/// Create a [`Transaction`] to stake from a Moonlight account.
+/// Create a [`Transaction`] to stake from a Moonlight account, either
+/// creating a new stake or topping up an existing one.
 ///
 /// # Note
 /// The `moonlight_nonce` is NOT incremented and should be incremented by the
@@ -224,6 +232,166 @@ pub fn moonlight_stake(
     )
 }
 
+/// A call to the stake contract whose [`DoubleSignature`] was created outside
+/// of the wallet, for instance by a hardware wallet holding the stake keys.
+///
+/// [`DoubleSignature`]: dusk_core::stake::DoubleSignature
+#[derive(Debug, Clone, PartialEq)]
+pub enum StakeCall {
+    /// Create a new stake, or top up an existing one.
+    Stake(Stake),
+    /// Withdraw the given amount of the accumulated rewards.
+    Withdraw(StakeWithdraw),
+    /// Withdraw the given amount of the staked value.
+    Unstake(StakeWithdraw),
+}
+
+impl StakeCall {
+    /// The message that needs to be signed by both the stake account and owner
+    /// keys.
+    #[must_use]
+    pub fn signature_message(&self) -> Vec<u8> {
+        match self {
+            Self::Stake(stake) => stake.signature_message().to_vec(),
+            Self::Withdraw(withdraw) | Self::Unstake(withdraw) => {
+                withdraw.signature_message()
+            }
+        }
+    }
+
+    /// Set the signature created over the [`signature_message`].
+    ///
+    /// [`signature_message`]: Self::signature_message
+    pub fn set_signature(&mut self, signature: DoubleSignature) {
+        match self {
+            Self::Stake(stake) => stake.set_signature(signature),
+            Self::Withdraw(withdraw) | Self::Unstake(withdraw) => {
+                withdraw.set_signature(signature);
+            }
+        }
+    }
+
+    fn into_contract_call(self) -> Result<(u64, ContractCall), Error> {
+        match self {
+            Self::Stake(stake) => Ok((
+                stake.value(),
+                ContractCall::new(STAKE_CONTRACT, "stake", &stake)?,
+            )),
+            Self::Withdraw(withdraw) => Ok((
+                0,
+                ContractCall::new(STAKE_CONTRACT, "withdraw", &withdraw)?,
+            )),
+            Self::Unstake(withdraw) => Ok((
+                0,
+                ContractCall::new(STAKE_CONTRACT, "unstake", &withdraw)?,
+            )),
+        }
+    }
+}
+
+/// Create an unsigned [`StakeCall`] to stake `stake_value`, either creating a
+/// new stake or topping up an existing one.
+///
+/// Topping up an eligible stake locks part of the added value, as computed by
+/// [`StakeAmount::top_up_locked`].
+///
+/// [`StakeAmount::top_up_locked`]: dusk_core::stake::StakeAmount::top_up_locked
+#[must_use]
+pub fn unsigned_stake(
+    stake_pk: &BlsPublicKey,
+    stake_owner_pk: &BlsPublicKey,
+    stake_value: u64,
+    chain_id: u8,
+) -> StakeCall {
+    let keys = StakeKeys::new(*stake_pk, *stake_owner_pk);
+
+    StakeCall::Stake(Stake::new_unsigned(keys, stake_value, chain_id))
+}
+
+/// Create an unsigned [`StakeCall`] to withdraw `reward_amount` of the stake
+/// rewards into a Moonlight account, leaving the rest of the rewards in the
+/// stake.
+///
+/// The `moonlight_nonce` needs to be the one of the transaction paying for the
+/// gas, created with [`moonlight_stake_call`].
+#[must_use]
+pub fn unsigned_moonlight_stake_reward<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    moonlight_receiver_sk: &BlsSecretKey,
+    stake_pk: &BlsPublicKey,
+    reward_amount: u64,
+    moonlight_nonce: u64,
+) -> StakeCall {
+    let withdraw = withdraw_to_moonlight(
+        rng,
+        moonlight_receiver_sk,
+        STAKE_CONTRACT,
+        WithdrawReplayToken::Moonlight(moonlight_nonce),
+        reward_amount,
+    );
+
+    StakeCall::Withdraw(StakeWithdraw::new_unsigned(*stake_pk, withdraw))
+}
+
+/// Create an unsigned [`StakeCall`] to withdraw `unstake_value` of the staked
+/// value into a Moonlight account, leaving the rest of the value staked.
+///
+/// The `moonlight_nonce` needs to be the one of the transaction paying for the
+/// gas, created with [`moonlight_stake_call`].
+#[must_use]
+pub fn unsigned_moonlight_unstake<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    moonlight_receiver_sk: &BlsSecretKey,
+    stake_pk: &BlsPublicKey,
+    unstake_value: u64,
+    moonlight_nonce: u64,
+) -> StakeCall {
+    let withdraw = withdraw_to_moonlight(
+        rng,
+        moonlight_receiver_sk,
+        STAKE_CONTRACT,
+        WithdrawReplayToken::Moonlight(moonlight_nonce),
+        unstake_value,
+    );
+
+    StakeCall::Unstake(StakeWithdraw::new_unsigned(*stake_pk, withdraw))
+}
+
+/// Create a [`Transaction`] carrying a signed [`StakeCall`], paying for the gas
+/// and the staked value from a Moonlight account.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+pub fn moonlight_stake_call(
+    moonlight_sender_sk: &BlsSecretKey,
+    stake_call: StakeCall,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let transfer_value = 0;
+
+    let (deposit, contract_call) = stake_call.into_contract_call()?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        transfer_value,
+        deposit,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
 /// Create an unproven [`Transaction`] to withdraw stake rewards into a
 /// phoenix-note.
 ///
@@ -461,6 +629,251 @@ pub fn moonlight_unstake<R: RngCore + CryptoRng>(
     )
 }
 
+/// Create a [`Transaction`] to delegate Moonlight Dusk to a provisioner.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error.
+/// The same goes for the `stake_nonce`, which must follow the nonce of the
+/// delegator returned by the `get_nonce` query of the stake contract.
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight_delegate(
+    moonlight_sender_sk: &BlsSecretKey,
+    delegator_sk: &BlsSecretKey,
+    provisioner_pk: &BlsPublicKey,
+    delegate_value: u64,
+    stake_nonce: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let transfer_value = 0;
+    let deposit = delegate_value;
+
+    let delegate = Delegate::new(
+        delegator_sk,
+        provisioner_pk,
+        delegate_value,
+        stake_nonce,
+        chain_id,
+    );
+
+    let contract_call =
+        ContractCall::new(STAKE_CONTRACT, "delegate", &delegate)?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        transfer_value,
+        deposit,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
+/// Create a [`Transaction`] to withdraw delegated value from a provisioner
+/// into a Moonlight account.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight_undelegate<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    moonlight_sender_sk: &BlsSecretKey,
+    delegator_sk: &BlsSecretKey,
+    provisioner_pk: &BlsPublicKey,
+    undelegate_value: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let gas_payment_token = WithdrawReplayToken::Moonlight(moonlight_nonce);
+
+    let withdraw = withdraw_to_moonlight(
+        rng,
+        moonlight_sender_sk,
+        STAKE_CONTRACT,
+        gas_payment_token,
+        undelegate_value,
+    );
+    let undelegate =
+        DelegationWithdraw::new(delegator_sk, provisioner_pk, withdraw);
+
+    let contract_call =
+        ContractCall::new(STAKE_CONTRACT, "undelegate", &undelegate)?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        0,
+        0,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
+/// Create a [`Transaction`] to withdraw the rewards of a delegation into a
+/// Moonlight account.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight_delegation_reward<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    moonlight_sender_sk: &BlsSecretKey,
+    delegator_sk: &BlsSecretKey,
+    provisioner_pk: &BlsPublicKey,
+    reward_amount: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let gas_payment_token = WithdrawReplayToken::Moonlight(moonlight_nonce);
+
+    let withdraw = withdraw_to_moonlight(
+        rng,
+        moonlight_sender_sk,
+        STAKE_CONTRACT,
+        gas_payment_token,
+        reward_amount,
+    );
+    let withdraw =
+        DelegationWithdraw::new(delegator_sk, provisioner_pk, withdraw);
+
+    let contract_call =
+        ContractCall::new(STAKE_CONTRACT, "withdraw_delegation", &withdraw)?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        0,
+        0,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
+/// Create a [`Transaction`] to set the commission rate, in basis points, the
+/// provisioner charges on the rewards of its delegators.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error.
+/// The same goes for the `stake_nonce`, which must follow the nonce of the
+/// stake returned by the `get_nonce` query of the stake contract.
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight_set_commission(
+    moonlight_sender_sk: &BlsSecretKey,
+    stake_sk: &BlsSecretKey,
+    stake_owner_sk: &BlsSecretKey,
+    rate: u16,
+    stake_nonce: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let commission =
+        Commission::new(stake_sk, stake_owner_sk, rate, stake_nonce, chain_id);
+
+    let contract_call =
+        ContractCall::new(STAKE_CONTRACT, "set_commission", &commission)?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        0,
+        0,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
+/// Create a [`Transaction`] to set the Moonlight account the rewards of a
+/// stake are minted to when withdrawn, or to unset it when `reward_pk` is
+/// `None`.
+///
+/// # Note
+/// The `moonlight_nonce` is NOT incremented and should be incremented by the
+/// caller of this function, if its not done so, rusk will throw 500 error.
+/// The same goes for the `stake_nonce`, which must follow the nonce of the
+/// stake returned by the `get_nonce` query of the stake contract.
+///
+/// # Errors
+/// The creation of this transaction doesn't error, but still returns a result
+/// for the sake of API consistency.
+#[allow(clippy::too_many_arguments)]
+pub fn moonlight_set_reward_address(
+    moonlight_sender_sk: &BlsSecretKey,
+    stake_sk: &BlsSecretKey,
+    stake_owner_sk: &BlsSecretKey,
+    reward_pk: Option<BlsPublicKey>,
+    stake_nonce: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    moonlight_nonce: u64,
+    chain_id: u8,
+) -> Result<Transaction, Error> {
+    let reward_address = RewardAddress::new(
+        stake_sk,
+        stake_owner_sk,
+        reward_pk,
+        stake_nonce,
+        chain_id,
+    );
+
+    let contract_call = ContractCall::new(
+        STAKE_CONTRACT,
+        "set_reward_address",
+        &reward_address,
+    )?;
+
+    moonlight(
+        moonlight_sender_sk,
+        None,
+        0,
+        0,
+        gas_limit,
+        gas_price,
+        moonlight_nonce,
+        chain_id,
+        Some(contract_call),
+    )
+}
+
 /// Create an unproven [`Transaction`] to convert Phoenix Dusk into Moonlight
 /// Dusk.
 ///
@@ -585,6 +998,31 @@ pub fn moonlight_to_phoenix<R: RngCore + CryptoRng>(
     )
 }
 
+/// Create the [`ContractDeploy`] payload of a transaction deploying the given
+/// bytecode, owned by `owner` and initialized with `init_args`.
+///
+/// The hash of the bytecode is computed here, so that the payload can be used
+/// with any funding path, as done by [`phoenix_deployment`] and
+/// [`moonlight_deployment`].
+#[must_use]
+pub fn deploy(
+    bytecode: impl Into<Vec<u8>>,
+    owner: &BlsPublicKey,
+    init_args: Vec<u8>,
+    nonce: u64,
+) -> ContractDeploy {
+    let bytes = bytecode.into();
+    ContractDeploy {
+        bytecode: ContractBytecode {
+            hash: blake3::hash(&bytes).into(),
+            bytes,
+        },
+        owner: owner.to_bytes().to_vec(),
+        init_args: Some(init_args),
+        nonce,
+    }
+}
+
 /// Create a new unproven [`Transaction`] to deploy a contract to the network.
 ///
 /// # Errors
@@ -628,16 +1066,7 @@ pub fn phoenix_deployment<R: RngCore + CryptoRng, P: Prove>(
         })
         .collect();
 
-    let bytes = bytecode.into();
-    let deploy = ContractDeploy {
-        bytecode: ContractBytecode {
-            hash: blake3::hash(&bytes).into(),
-            bytes,
-        },
-        owner: owner.to_bytes().to_vec(),
-        init_args: Some(init_args),
-        nonce,
-    };
+    let deploy = deploy(bytecode, owner, init_args, nonce);
 
     phoenix(
         rng,
@@ -681,16 +1110,7 @@ pub fn moonlight_deployment(
     let transfer_value = 0;
     let deposit = 0;
 
-    let bytes = bytecode.into();
-    let deploy = ContractDeploy {
-        bytecode: ContractBytecode {
-            hash: blake3::hash(&bytes).into(),
-            bytes,
-        },
-        owner: owner.to_bytes().to_vec(),
-        init_args: Some(init_args),
-        nonce: deploy_nonce,
-    };
+    let deploy = deploy(bytecode, owner, init_args, deploy_nonce);
 
     moonlight(
         moonlight_sender_sk,
@@ -705,6 +1125,198 @@ pub fn moonlight_deployment(
     )
 }
 
+/// An input-note of a transaction in a [`SpendPlan`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum PlannedInput {
+    /// An owned note, together with its nullifier.
+    Note(BlsScalar, NoteLeaf),
+    /// The change-note of the consolidation at the given index of
+    /// [`SpendPlan::consolidations`].
+    Consolidated(usize),
+}
+
+impl PlannedInput {
+    /// Returns the owned note and its nullifier, or `None` if the input is the
+    /// change-note of a consolidation.
+    #[must_use]
+    pub fn note(&self) -> Option<(&BlsScalar, &NoteLeaf)> {
+        match self {
+            Self::Note(nullifier, leaf) => Some((nullifier, leaf)),
+            Self::Consolidated(_) => None,
+        }
+    }
+}
+
+/// A transaction merging up to [`MAX_INPUT_NOTES`] notes into a single
+/// change-note, sent to the owner of the notes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consolidation {
+    /// The input-notes of the consolidation.
+    pub inputs: Vec<PlannedInput>,
+    /// The value of the resulting change-note, i.e. the value of the inputs
+    /// minus the fee of the consolidation.
+    pub value: u64,
+}
+
+/// The chain of transactions needed to spend a given cost from a list of
+/// owned notes.
+///
+/// The consolidations need to be executed in order, waiting for a
+/// consolidation to be included in a block before executing the ones that
+/// spend its change-note. The final transaction spends [`SpendPlan::inputs`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpendPlan {
+    /// The consolidations to execute before the final transaction.
+    pub consolidations: Vec<Consolidation>,
+    /// The input-notes of the final transaction.
+    pub inputs: Vec<PlannedInput>,
+}
+
+impl SpendPlan {
+    /// Returns `true` if the final transaction needs to be preceded by
+    /// consolidations.
+    #[must_use]
+    pub fn is_chained(&self) -> bool {
+        !self.consolidations.is_empty()
+    }
+}
+
+/// Plan the transactions to spend `cost` from the given owned notes.
+///
+/// If up to [`MAX_INPUT_NOTES`] notes cover the cost, the plan consists of a
+/// single transaction whose inputs are picked as by [`pick::notes`].
+/// Otherwise the notes with the largest values are merged, [`MAX_INPUT_NOTES`]
+/// at a time, by consolidations paying `consolidation_fee` each, until the
+/// final transaction can cover the cost.
+///
+/// Returns `None` if the notes can't cover the cost, including the fees of the
+/// consolidations.
+#[must_use]
+pub fn plan(
+    vk: &PhoenixViewKey,
+    notes: &NoteList,
+    cost: u64,
+    consolidation_fee: u64,
+) -> Option<SpendPlan> {
+    let picked = pick::notes(vk, notes.clone(), cost);
+    if !picked.is_empty() {
+        return Some(SpendPlan {
+            consolidations: Vec::new(),
+            inputs: picked
+                .iter()
+                .map(|(nullifier, leaf)| {
+                    PlannedInput::Note(*nullifier, leaf.clone())
+                })
+                .collect(),
+        });
+    }
+
+    // the notes still available as inputs, sorted from largest to smallest
+    let mut pool: Vec<(u64, PlannedInput)> = notes
+        .iter()
+        .filter_map(|(nullifier, leaf)| {
+            leaf.note.value(Some(vk)).ok().map(|value| {
+                (value, PlannedInput::Note(*nullifier, leaf.clone()))
+            })
+        })
+        .collect();
+    pool.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let mut consolidations = Vec::new();
+    loop {
+        let spendable: u64 = pool
+            .iter()
+            .take(MAX_INPUT_NOTES)
+            .map(|(value, _)| value)
+            .sum();
+
+        if spendable >= cost {
+            break;
+        }
+        // merging is only possible if there are notes left to take the place
+        // of the merged ones, and if their value covers the fee
+        if pool.len() <= MAX_INPUT_NOTES || spendable <= consolidation_fee {
+            return None;
+        }
+
+        let inputs: Vec<PlannedInput> = pool
+            .drain(..MAX_INPUT_NOTES)
+            .map(|(_, input)| input)
+            .collect();
+        let value = spendable - consolidation_fee;
+
+        pool.push((value, PlannedInput::Consolidated(consolidations.len())));
+        pool.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        consolidations.push(Consolidation { inputs, value });
+    }
+
+    // use the largest notes until the cost is covered
+    let mut sum = 0;
+    let inputs = pool
+        .into_iter()
+        .take_while(|(value, _)| {
+            let covered = sum >= cost;
+            sum += value;
+            !covered
+        })
+        .map(|(_, input)| input)
+        .collect();
+
+    Some(SpendPlan {
+        consolidations,
+        inputs,
+    })
+}
+
+/// Create a [`Transaction`] executing a [`Consolidation`], merging its
+/// input-notes into a single note of the consolidated value, sent back to the
+/// sender.
+///
+/// The inputs of a consolidation that spends the change-note of another are
+/// only known once the latter is executed. A [`SpendPlan`] is therefore best
+/// executed one consolidation at a time: execute the first, wait for it to be
+/// included in a block, and [`plan`] again until the plan isn't chained.
+///
+/// # Errors
+/// The creation of a transaction is not possible and will error if:
+/// - one of the input-notes doesn't belong to the `sender_sk`
+/// - the transaction input doesn't cover the consolidated value and the max fee
+/// - the `prover` is implemented incorrectly
+#[allow(clippy::too_many_arguments)]
+pub fn phoenix_consolidation<R: RngCore + CryptoRng, P: Prove>(
+    rng: &mut R,
+    sender_sk: &PhoenixSecretKey,
+    inputs: Vec<(Note, NoteOpening)>,
+    root: BlsScalar,
+    consolidated_value: u64,
+    gas_limit: u64,
+    gas_price: u64,
+    chain_id: u8,
+    prover: &P,
+) -> Result<Transaction, Error> {
+    let sender_pk = PhoenixPublicKey::from(sender_sk);
+    let data: Option<TransactionData> = None;
+
+    phoenix(
+        rng,
+        sender_sk,
+        &sender_pk,
+        &sender_pk,
+        inputs,
+        root,
+        consolidated_value,
+        true,
+        0,
+        gas_limit,
+        gas_price,
+        chain_id,
+        data,
+        prover,
+    )
+}
+
 fn stake_reward_to_phoenix<R: RngCore + CryptoRng>(
     rng: &mut R,
     phoenix_sender_sk: &PhoenixSecretKey,
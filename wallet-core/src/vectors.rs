@@ -0,0 +1,210 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Deterministic test vectors for certifying wallet implementations.
+//!
+//! [`generate`] derives from a seed and a profile index the keys of the
+//! profile, a note sent to its phoenix public key together with its nullifier,
+//! and a signature of the moonlight account. Every value is encoded with its
+//! canonical byte representation, so that third-party implementations can
+//! reproduce them.
+//!
+//! The vectors are stored in golden files, one `name = hex-value` pair per
+//! line, created with [`to_golden`] and checked with [`verify`]. The golden
+//! files implementations are certified against are in `tests/vectors`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use dusk_bytes::Serializable;
+use dusk_core::transfer::phoenix::{Note, PublicKey as PhoenixPublicKey};
+use dusk_core::JubJubScalar;
+use ff::Field;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use zeroize::Zeroize;
+
+use crate::keys::{
+    derive_bls_pk, derive_bls_sk, derive_phoenix_pk, derive_phoenix_sk,
+    derive_phoenix_vk,
+};
+use crate::Seed;
+
+/// The message signed with the moonlight secret key in the test vectors.
+pub const SIGNED_MESSAGE: &[u8] = b"dusk wallet-core test vector";
+
+/// The value of the note sent to the profile in the test vectors.
+pub const NOTE_VALUE: u64 = 42;
+
+/// A named value of a test vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The name of the value.
+    pub name: &'static str,
+    /// The canonical byte representation of the value.
+    pub value: Vec<u8>,
+}
+
+/// The errors returned when verifying a golden file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line at the given number isn't a `name = hex-value` pair.
+    Malformed(usize),
+    /// The golden file doesn't contain the value with the given name.
+    Missing(&'static str),
+    /// The value with the given name differs from the golden file.
+    Mismatch {
+        /// The name of the value.
+        name: &'static str,
+        /// The hex-encoded value in the golden file.
+        expected: String,
+        /// The hex-encoded value computed by the implementation.
+        actual: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(line) => write!(f, "Malformed line {line}"),
+            Self::Missing(name) => write!(f, "Missing value `{name}`"),
+            Self::Mismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Mismatch of `{name}`: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+/// Generate the test vector of the profile at the given index.
+#[must_use]
+#[allow(clippy::similar_names)]
+pub fn generate(seed: &Seed, index: u8) -> Vec<Entry> {
+    let mut phoenix_sk = derive_phoenix_sk(seed, index);
+    let phoenix_vk = derive_phoenix_vk(seed, index);
+    let phoenix_pk = derive_phoenix_pk(seed, index);
+    let mut bls_sk = derive_bls_sk(seed, index);
+    let bls_pk = derive_bls_pk(seed, index);
+
+    // the note is created with an rng seeded from the seed and index, so that
+    // its stealth address is deterministic
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(seed);
+    hasher.update(&[index]);
+    let mut rng = ChaCha12Rng::from_seed(hasher.finalize().into());
+
+    let sender_pk = PhoenixPublicKey::from(&derive_phoenix_sk(&[0; 64], 0));
+    let sender_blinder = [
+        JubJubScalar::random(&mut rng),
+        JubJubScalar::random(&mut rng),
+    ];
+    let note = Note::transparent(
+        &mut rng,
+        &sender_pk,
+        &phoenix_pk,
+        NOTE_VALUE,
+        sender_blinder,
+    );
+    let owned = phoenix_vk.owns(note.stealth_address());
+
+    let entries = alloc::vec![
+        entry("phoenix_sk", phoenix_sk.to_bytes()),
+        entry("phoenix_vk", phoenix_vk.to_bytes()),
+        entry("phoenix_pk", phoenix_pk.to_bytes()),
+        entry("bls_sk", bls_sk.to_bytes()),
+        entry("bls_pk", bls_pk.to_bytes()),
+        entry("note_stealth_address", note.stealth_address().to_bytes()),
+        entry("note_owned", [u8::from(owned)]),
+        entry("note_nullifier", note.gen_nullifier(&phoenix_sk).to_bytes()),
+        entry("bls_signature", bls_sk.sign(SIGNED_MESSAGE).to_bytes()),
+    ];
+
+    phoenix_sk.zeroize();
+    bls_sk.zeroize();
+
+    entries
+}
+
+/// Encode a test vector into the content of a golden file.
+#[must_use]
+pub fn to_golden(entries: &[Entry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            let mut line = String::from(entry.name);
+            line.push_str(" = ");
+            line.push_str(&to_hex(&entry.value));
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// Verify the test vector of the profile at the given index against the
+/// content of a golden file.
+///
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// # Errors
+/// Errors if the golden file is malformed, or if a value is missing from it or
+/// differs from the one computed by this implementation.
+pub fn verify(golden: &str, seed: &Seed, index: u8) -> Result<(), Error> {
+    let mut values = Vec::new();
+    for (number, line) in golden.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, value) =
+            line.split_once('=').ok_or(Error::Malformed(number + 1))?;
+        values.push((name.trim(), value.trim().to_ascii_lowercase()));
+    }
+
+    for entry in generate(seed, index) {
+        let expected = values
+            .iter()
+            .find(|(name, _)| *name == entry.name)
+            .map(|(_, value)| value)
+            .ok_or(Error::Missing(entry.name))?;
+        let actual = to_hex(&entry.value);
+
+        if *expected != actual {
+            return Err(Error::Mismatch {
+                name: entry.name,
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn entry(name: &'static str, value: impl AsRef<[u8]>) -> Entry {
+    Entry {
+        name,
+        value: value.as_ref().to_vec(),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    bytes
+        .iter()
+        .flat_map(|byte| {
+            [
+                DIGITS[usize::from(byte >> 4)] as char,
+                DIGITS[usize::from(byte & 0xf)] as char,
+            ]
+        })
+        .collect()
+}
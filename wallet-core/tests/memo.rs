@@ -0,0 +1,87 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_core::transfer::data::{Memo, MemoKind, MAX_MEMO_SIZE};
+use dusk_core::transfer::phoenix::{
+    PublicKey as PhoenixPublicKey, SecretKey as PhoenixSecretKey,
+    ViewKey as PhoenixViewKey,
+};
+use dusk_core::Error;
+use dusk_wallet_core::memo::{
+    decrypt, encrypt, scan, ENCRYPTION_OVERHEAD, MAX_ENCRYPTED_MEMO_SIZE,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_encrypt_decrypt() {
+    let mut rng = StdRng::seed_from_u64(0xdab);
+
+    let sk = PhoenixSecretKey::random(&mut rng);
+    let vk = PhoenixViewKey::from(&sk);
+    let pk = PhoenixPublicKey::from(&sk);
+    let other_vk = PhoenixViewKey::from(&PhoenixSecretKey::random(&mut rng));
+
+    let memo = b"invoice #42";
+    let encrypted = encrypt(&mut rng, &pk, memo).expect("memo fits");
+    assert_eq!(encrypted.kind(), MemoKind::Encrypted);
+    assert_eq!(encrypted.len(), memo.len() + ENCRYPTION_OVERHEAD);
+    let bytes = encrypted.content();
+    assert_ne!(&bytes[bytes.len() - memo.len()..], memo);
+
+    assert_eq!(decrypt(&vk, &encrypted), Some(memo.to_vec()));
+    assert_eq!(decrypt(&other_vk, &encrypted), None);
+
+    // tampering with the ciphertext is detected
+    let mut tampered = bytes.to_vec();
+    tampered[40] ^= 1;
+    let tampered = Memo::with_kind(MemoKind::Encrypted, &tampered).unwrap();
+    assert_eq!(decrypt(&vk, &tampered), None);
+    let truncated = Memo::with_kind(MemoKind::Encrypted, &bytes[..10]).unwrap();
+    assert_eq!(decrypt(&vk, &truncated), None);
+
+    // the same bytes without the tag aren't decrypted
+    let untagged = Memo::new(encrypted.content()).unwrap();
+    assert_eq!(decrypt(&vk, &untagged), None);
+
+    // the largest memo that can be encrypted fits in a transaction
+    let memo = vec![0; MAX_ENCRYPTED_MEMO_SIZE];
+    let encrypted = encrypt(&mut rng, &pk, &memo).expect("memo fits");
+    assert_eq!(encrypted.len(), MAX_MEMO_SIZE);
+
+    let memo = vec![0; MAX_ENCRYPTED_MEMO_SIZE + 1];
+    assert!(matches!(
+        encrypt(&mut rng, &pk, &memo),
+        Err(Error::MemoTooLarge(_))
+    ));
+}
+
+#[test]
+fn test_scan() {
+    let mut rng = StdRng::seed_from_u64(0xdab);
+
+    let sks = [
+        PhoenixSecretKey::random(&mut rng),
+        PhoenixSecretKey::random(&mut rng),
+    ];
+    let vks = [PhoenixViewKey::from(&sks[0]), PhoenixViewKey::from(&sks[1])];
+    let other_pk = PhoenixPublicKey::from(&PhoenixSecretKey::random(&mut rng));
+
+    let memos = [
+        encrypt(&mut rng, &PhoenixPublicKey::from(&sks[1]), b"first").unwrap(),
+        encrypt(&mut rng, &other_pk, b"second").unwrap(),
+        Memo::new(b"plain memo".as_slice()).unwrap(),
+        encrypt(&mut rng, &PhoenixPublicKey::from(&sks[1]), b"fourth").unwrap(),
+    ];
+
+    let decrypted = scan(vks, memos);
+    assert_eq!(decrypted.len(), 2);
+    assert!(decrypted[0].is_empty());
+    assert_eq!(
+        decrypted[1],
+        [(0, b"first".to_vec()), (3, b"fourth".to_vec())]
+    );
+}
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "uniffi")]
+
+use dusk_bytes::Serializable;
+use dusk_core::transfer::data::MAX_MEMO_SIZE;
+use dusk_core::transfer::phoenix::{
+    Note, NoteLeaf, NoteOpening, NoteTreeItem, NotesTree,
+    PublicKey as PhoenixPublicKey,
+};
+use dusk_core::JubJubScalar;
+use dusk_wallet_core::keys::{derive_bls_pk, derive_phoenix_pk};
+use dusk_wallet_core::mobile::{
+    balance, generate_profile, moonlight, phoenix, MobileError,
+};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const SEED: [u8; 64] = [7; 64];
+const RNG_SEED: [u8; 32] = [3; 32];
+const CHAIN_ID: u8 = 0xfa;
+
+fn archived_leaf(
+    rng: &mut StdRng,
+    owner_pk: &PhoenixPublicKey,
+    value: u64,
+) -> (NoteLeaf, Vec<u8>) {
+    let blinders = [
+        JubJubScalar::random(&mut *rng),
+        JubJubScalar::random(&mut *rng),
+    ];
+    let note = Note::transparent(rng, owner_pk, owner_pk, value, blinders);
+    let leaf = NoteLeaf {
+        note,
+        block_height: 1,
+    };
+    let bytes = rkyv::to_bytes::<_, 1024>(&leaf)
+        .expect("the leaf should be archived")
+        .to_vec();
+    (leaf, bytes)
+}
+
+#[test]
+fn profile() {
+    let profile = generate_profile(SEED.to_vec(), 1).expect("valid seed");
+    assert_eq!(
+        profile.phoenix_pk,
+        derive_phoenix_pk(&SEED, 1).to_bytes().to_vec()
+    );
+    assert_eq!(profile.bls_pk, derive_bls_pk(&SEED, 1).to_bytes().to_vec());
+
+    assert_eq!(
+        generate_profile(vec![0; 32], 0),
+        Err(MobileError::InvalidSeed)
+    );
+}
+
+#[test]
+fn phoenix_notes_balance() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let owner_pk = derive_phoenix_pk(&SEED, 0);
+
+    let leaves = [10, 20]
+        .into_iter()
+        .map(|value| archived_leaf(&mut rng, &owner_pk, value).1)
+        .collect();
+    let info = balance(SEED.to_vec(), 0, leaves).expect("valid leaves");
+    assert_eq!(info.value, 30);
+
+    assert_eq!(
+        balance(SEED.to_vec(), 0, vec![vec![0xff; 3]]),
+        Err(MobileError::Unarchiving)
+    );
+}
+
+#[test]
+fn phoenix_inputs_mismatch() {
+    let mut rng = StdRng::seed_from_u64(0xc0ffee);
+    let owner_pk = derive_phoenix_pk(&SEED, 0);
+
+    let mut tree = NotesTree::new();
+    let (leaf, archived) = archived_leaf(&mut rng, &owner_pk, 100);
+    tree.insert(
+        *leaf.note.pos(),
+        NoteTreeItem {
+            hash: leaf.note.hash(),
+            data: (),
+        },
+    );
+    let opening: NoteOpening = tree
+        .opening(*leaf.note.pos())
+        .expect("the note should be in the tree");
+    let opening = rkyv::to_bytes::<_, 4096>(&opening)
+        .expect("the opening should be archived")
+        .to_vec();
+    let root = tree.root().hash.to_bytes().to_vec();
+
+    let call = |inputs: Vec<Vec<u8>>, openings: Vec<Vec<u8>>| {
+        phoenix(
+            RNG_SEED.to_vec(),
+            SEED.to_vec(),
+            0,
+            owner_pk.to_bytes().to_vec(),
+            inputs,
+            openings,
+            root.clone(),
+            1,
+            false,
+            0,
+            100,
+            1,
+            CHAIN_ID,
+            None,
+        )
+    };
+
+    // An opening without its input, or an input without its opening, must be
+    // rejected instead of being silently dropped
+    assert_eq!(
+        call(vec![], vec![opening.clone()]),
+        Err(MobileError::InputsMismatch)
+    );
+    assert_eq!(
+        call(vec![archived.clone(), archived], vec![opening]),
+        Err(MobileError::InputsMismatch)
+    );
+}
+
+#[test]
+fn moonlight_transaction() {
+    let receiver = derive_bls_pk(&SEED, 1).to_bytes().to_vec();
+
+    let tx = moonlight(
+        SEED.to_vec(),
+        0,
+        Some(receiver),
+        10,
+        0,
+        100,
+        1,
+        1,
+        CHAIN_ID,
+        None,
+    )
+    .expect("the transaction should be created");
+    assert_eq!(tx.hash.len(), 32);
+    assert!(!tx.tx.is_empty());
+
+    let invalid = moonlight(
+        SEED.to_vec(),
+        0,
+        Some(vec![0; 3]),
+        10,
+        0,
+        100,
+        1,
+        1,
+        CHAIN_ID,
+        None,
+    );
+    assert_eq!(invalid, Err(MobileError::Deserialization));
+
+    let too_large = moonlight(
+        SEED.to_vec(),
+        0,
+        None,
+        0,
+        0,
+        100,
+        1,
+        1,
+        CHAIN_ID,
+        Some(vec![0; MAX_MEMO_SIZE + 1]),
+    );
+    assert_eq!(too_large, Err(MobileError::MemoTooLarge));
+}
@@ -11,8 +11,10 @@ use dusk_core::transfer::phoenix::{
 use dusk_core::JubJubScalar;
 use dusk_wallet_core::keys::{derive_multiple_phoenix_sk, derive_phoenix_sk};
 use dusk_wallet_core::notes::owned::NoteList;
+use dusk_wallet_core::transaction::{plan, PlannedInput};
 use dusk_wallet_core::{
-    map_owned, phoenix_balance, pick_notes, BalanceInfo, Seed,
+    map_owned, map_owned_update, phoenix_balance, pick_notes, scan_owned,
+    BalanceInfo, BalanceState, CoinControl, CoinSelection, CoinSelector, Seed,
 };
 use ff::Field;
 use rand::rngs::StdRng;
@@ -119,6 +121,37 @@ fn test_map_owned() {
     assert_eq!(&notes_by_2[1][&nullifier].note, note);
 }
 
+#[test]
+fn test_scan_owned() {
+    let mut rng = StdRng::seed_from_u64(0xdab);
+    const SEED: Seed = [1; 64];
+
+    let sks = derive_multiple_phoenix_sk(&SEED, 0..3);
+    let vks: Vec<PhoenixViewKey> =
+        sks.iter().map(PhoenixViewKey::from).collect();
+    let other_pk = PhoenixPublicKey::from(&derive_phoenix_sk(&SEED, 3));
+
+    let note_leaves = vec![
+        gen_note_leaf(&mut rng, true, &PhoenixPublicKey::from(&sks[0]), 12),
+        gen_note_leaf(&mut rng, true, &PhoenixPublicKey::from(&sks[2]), 1),
+        gen_note_leaf(&mut rng, false, &other_pk, 3),
+        gen_note_leaf(&mut rng, true, &PhoenixPublicKey::from(&sks[0]), 76),
+    ];
+
+    let owned = scan_owned(vks.as_slice(), &note_leaves);
+    assert_eq!(owned.len(), 3);
+    assert_eq!(owned[0], [note_leaves[0].clone(), note_leaves[3].clone()]);
+    assert!(owned[1].is_empty());
+    assert_eq!(owned[2], [note_leaves[1].clone()]);
+
+    // the result is consistent with `map_owned`
+    for (owned, mapped) in owned.iter().zip(map_owned(&sks, &note_leaves)) {
+        let mapped: Vec<NoteLeaf> =
+            mapped.iter().map(|(_, leaf)| leaf.clone()).collect();
+        assert_eq!(owned, &mapped);
+    }
+}
+
 #[test]
 fn test_balance() {
     let mut rng = StdRng::seed_from_u64(0xdab);
@@ -170,6 +203,65 @@ fn test_balance() {
     );
 }
 
+#[test]
+fn test_incremental_balance() {
+    let mut rng = StdRng::seed_from_u64(0xdab);
+
+    let sks = [
+        PhoenixSecretKey::random(&mut rng),
+        PhoenixSecretKey::random(&mut rng),
+    ];
+    let vk = PhoenixViewKey::from(&sks[0]);
+    let pk = PhoenixPublicKey::from(&sks[0]);
+    let other_pk = PhoenixPublicKey::from(&sks[1]);
+
+    let first_leaves = [
+        gen_note_leaf(&mut rng, true, &pk, 5),
+        gen_note_leaf(&mut rng, true, &other_pk, 7),
+        gen_note_leaf(&mut rng, false, &pk, 10),
+    ];
+    let second_leaves = [
+        gen_note_leaf(&mut rng, true, &pk, 3),
+        gen_note_leaf(&mut rng, true, &pk, 8),
+        gen_note_leaf(&mut rng, true, &other_pk, 20),
+    ];
+
+    let owned = map_owned(&sks, &first_leaves);
+    let state = BalanceState::new(&vk, &owned[0]);
+    assert_eq!(
+        state.balance(),
+        BalanceInfo {
+            value: 15,
+            spendable: 15,
+        }
+    );
+
+    // spend the note of value 5 and receive the second batch of notes
+    let spent = [first_leaves[0].note.gen_nullifier(&sks[0])];
+    let delta = map_owned(&sks, &second_leaves);
+    let owned = map_owned_update(&sks, owned, &second_leaves, &spent);
+    let state = state.update(&vk, &delta[0], &spent);
+
+    assert_eq!(owned[0].len(), 3);
+    assert_eq!(owned[1].len(), 2);
+    assert!(owned[0].get(&spent[0]).is_none());
+    assert_eq!(
+        state.balance(),
+        BalanceInfo {
+            value: 21,
+            spendable: 21,
+        }
+    );
+
+    // the incremental results match a full rescan
+    let all_leaves: Vec<NoteLeaf> =
+        first_leaves.iter().chain(&second_leaves).cloned().collect();
+    let mut rescanned = map_owned(&sks, all_leaves.as_slice());
+    rescanned[0].remove_all(&spent);
+    assert_eq!(owned, rescanned);
+    assert_eq!(state, BalanceState::new(&vk, &rescanned[0]));
+}
+
 #[test]
 fn test_pick_notes() {
     use rand::SeedableRng;
@@ -256,6 +348,174 @@ fn test_pick_notes() {
     );
 }
 
+#[test]
+fn test_coin_selection() {
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(0xbeef);
+
+    let sk = PhoenixSecretKey::random(&mut rng);
+    let vk = PhoenixViewKey::from(&sk);
+    let pk = PhoenixPublicKey::from(&sk);
+
+    let mut leaves = [
+        gen_note_leaf(&mut rng, true, &pk, 10),
+        gen_note_leaf(&mut rng, true, &pk, 25),
+        gen_note_leaf(&mut rng, true, &pk, 25),
+        gen_note_leaf(&mut rng, true, &pk, 25),
+        gen_note_leaf(&mut rng, true, &pk, 40),
+        gen_note_leaf(&mut rng, true, &pk, 100),
+    ];
+    for (height, leaf) in leaves.iter_mut().rev().enumerate() {
+        leaf.block_height = height as u64;
+    }
+    let all_notes = generate_note_list(leaves, &sk);
+
+    let select = |selection: CoinSelection, cost| {
+        picked_values(&vk, &selection.select(&vk, all_notes.clone(), cost))
+    };
+
+    // the default strategy behaves like `pick_notes`
+    assert_eq!(CoinSelection::default(), CoinSelection::Consolidate);
+    assert_eq!(
+        CoinSelection::Consolidate.select(&vk, all_notes.clone(), 50),
+        pick_notes(&vk, all_notes.clone(), 50)
+    );
+
+    assert_eq!(select(CoinSelection::MinimizeInputs, 50), [100]);
+    assert_eq!(select(CoinSelection::MinimizeInputs, 150), [25, 40, 100]);
+
+    assert_eq!(select(CoinSelection::MinimizeChange, 50), [10, 40]);
+    assert_eq!(select(CoinSelection::MinimizeChange, 65), [25, 40]);
+    assert_eq!(select(CoinSelection::MinimizeChange, 99), [100]);
+
+    assert_eq!(select(CoinSelection::PrivacyMax, 50), [25, 25]);
+    assert_eq!(select(CoinSelection::PrivacyMax, 30), [40]);
+
+    // the oldest notes are the last ones generated
+    assert_eq!(select(CoinSelection::OldestFirst, 50), [100]);
+    assert_eq!(select(CoinSelection::OldestFirst, 120), [40, 100]);
+
+    for selection in [
+        CoinSelection::Consolidate,
+        CoinSelection::MinimizeInputs,
+        CoinSelection::MinimizeChange,
+        CoinSelection::PrivacyMax,
+        CoinSelection::OldestFirst,
+    ] {
+        assert!(select(selection, 191).is_empty());
+    }
+}
+
+// Decrypt and sort the values of a list of notes.
+fn picked_values(vk: &PhoenixViewKey, notes: &NoteList) -> Vec<u64> {
+    let mut values: Vec<u64> = notes
+        .iter()
+        .map(|(_, leaf)| leaf.note.value(Some(vk)).unwrap())
+        .collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn test_coin_control() {
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(0xbeef);
+
+    let sk = PhoenixSecretKey::random(&mut rng);
+    let vk = PhoenixViewKey::from(&sk);
+    let pk = PhoenixPublicKey::from(&sk);
+
+    let leaves = [
+        gen_note_leaf(&mut rng, true, &pk, 10),
+        gen_note_leaf(&mut rng, true, &pk, 40),
+        gen_note_leaf(&mut rng, true, &pk, 100),
+    ];
+    let all_notes = generate_note_list(leaves, &sk);
+    let largest = all_notes.keys()[2];
+
+    let mut control = CoinControl::default();
+    assert!(control.freeze(largest));
+    assert!(!control.freeze(largest));
+    assert!(control.is_frozen(&largest));
+
+    // the frozen note is never picked, even when it's the best candidate
+    let select = |control: &CoinControl, cost| {
+        picked_values(
+            &vk,
+            &control.select(
+                &CoinSelection::MinimizeInputs,
+                &vk,
+                all_notes.clone(),
+                cost,
+            ),
+        )
+    };
+    assert_eq!(select(&control, 50), [10, 40]);
+    assert!(select(&control, 60).is_empty());
+
+    // the metadata survives serialization
+    let bytes = rkyv::to_bytes::<_, 256>(&control).unwrap();
+    let deserialized: CoinControl = rkyv::from_bytes(&bytes).unwrap();
+    assert_eq!(deserialized, control);
+
+    assert!(control.thaw(&largest));
+    assert!(!control.thaw(&largest));
+    assert_eq!(select(&control, 60), [100]);
+
+    control.freeze(largest);
+    control.remove_spent(&[largest]);
+    assert!(control.frozen().is_empty());
+}
+
+#[test]
+fn test_plan() {
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(0xbeef);
+
+    let sk = PhoenixSecretKey::random(&mut rng);
+    let vk = PhoenixViewKey::from(&sk);
+    let pk = PhoenixPublicKey::from(&sk);
+
+    let leaves: Vec<NoteLeaf> = (0..9)
+        .map(|_| gen_note_leaf(&mut rng, true, &pk, 10))
+        .collect();
+    let all_notes = generate_note_list(leaves, &sk);
+    let fee = 2;
+
+    // 4 notes cover the cost, no chaining is needed
+    let spend = plan(&vk, &all_notes, 30, fee).expect("notes cover the cost");
+    assert!(!spend.is_chained());
+    assert_eq!(spend.inputs.len(), 4);
+
+    // one consolidation of 4 notes is needed
+    let spend = plan(&vk, &all_notes, 50, fee).expect("notes cover the cost");
+    assert_eq!(spend.consolidations.len(), 1);
+    assert_eq!(spend.consolidations[0].value, 38);
+    assert_eq!(spend.inputs.len(), 3);
+    assert_eq!(spend.inputs[0], PlannedInput::Consolidated(0));
+
+    // the second consolidation spends the change-note of the first
+    let spend = plan(&vk, &all_notes, 80, fee).expect("notes cover the cost");
+    assert_eq!(spend.consolidations.len(), 2);
+    assert_eq!(spend.consolidations[1].value, 66);
+    assert_eq!(
+        spend.consolidations[1].inputs[0],
+        PlannedInput::Consolidated(0)
+    );
+    assert_eq!(spend.inputs[0], PlannedInput::Consolidated(1));
+    assert!(spend
+        .inputs
+        .iter()
+        .skip(1)
+        .all(|input| matches!(input, PlannedInput::Note(..))));
+
+    // the fees of the consolidations make the cost impossible to cover
+    assert!(plan(&vk, &all_notes, 90, fee).is_none());
+}
+
 fn generate_expected_input_notes(
     ordered_notes: &NoteList,
     expected_indices: &[usize],
@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use dusk_core::stake::DoubleSignature;
+use dusk_wallet_core::keys::derive_bls_sk;
+use dusk_wallet_core::transaction::{
+    deploy, moonlight_deployment, moonlight_stake, moonlight_stake_call,
+    unsigned_moonlight_unstake, unsigned_stake, StakeCall,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const SEED: [u8; 64] = [0; 64];
+
+#[test]
+fn test_deploy() {
+    let sk = derive_bls_sk(&SEED, 0);
+    let owner = BlsPublicKey::from(&sk);
+    let bytecode = vec![0x00, 0x61, 0x73, 0x6d, 0x01];
+    let init_args = vec![1, 2, 3];
+
+    let payload = deploy(bytecode.clone(), &owner, init_args.clone(), 7);
+    assert_eq!(payload.bytecode.bytes, bytecode);
+    assert_eq!(
+        payload.bytecode.hash,
+        <[u8; 32]>::from(blake3::hash(&bytecode))
+    );
+    assert_eq!(payload.owner, owner.to_bytes().to_vec());
+    assert_eq!(payload.init_args, Some(init_args.clone()));
+    assert_eq!(payload.nonce, 7);
+
+    // the moonlight funding path carries the same payload
+    let tx = moonlight_deployment(
+        &sk, bytecode, &owner, init_args, 500_000, 1, 0, 7, 0xfa,
+    )
+    .expect("creating the transaction should succeed");
+    assert_eq!(tx.deploy(), Some(&payload));
+}
+
+#[test]
+fn test_unsigned_stake() {
+    let sender_sk = derive_bls_sk(&SEED, 0);
+    let stake_sk = derive_bls_sk(&SEED, 1);
+    let owner_sk = derive_bls_sk(&SEED, 2);
+    let stake_pk = BlsPublicKey::from(&stake_sk);
+    let owner_pk = BlsPublicKey::from(&owner_sk);
+
+    // signing the message externally gives the same transaction as signing
+    // with the keys in memory
+    let mut stake = unsigned_stake(&stake_pk, &owner_pk, 1_000, 0xfa);
+    let msg = stake.signature_message();
+    stake.set_signature(DoubleSignature {
+        account: stake_sk.sign(&msg),
+        owner: owner_sk.sign(&msg),
+    });
+
+    let tx = moonlight_stake_call(&sender_sk, stake, 500_000, 1, 3, 0xfa)
+        .expect("creating the transaction should succeed");
+    let expected = moonlight_stake(
+        &sender_sk, &stake_sk, &owner_sk, 1_000, 500_000, 1, 3, 0xfa,
+    )
+    .expect("creating the transaction should succeed");
+    assert_eq!(tx, expected);
+
+    // a partial unstake carries the requested value
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let unstake =
+        unsigned_moonlight_unstake(&mut rng, &sender_sk, &stake_pk, 400, 4);
+    match &unstake {
+        StakeCall::Unstake(withdraw) => {
+            assert_eq!(withdraw.transfer_withdraw().value(), 400);
+        }
+        _ => panic!("expected an unstake call"),
+    }
+    assert!(
+        moonlight_stake_call(&sender_sk, unstake, 500_000, 1, 4, 0xfa).is_ok()
+    );
+}
@@ -0,0 +1,74 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bytes::Serializable;
+use dusk_wallet_core::keys::derive_phoenix_pk;
+use dusk_wallet_core::vectors::{generate, to_golden, verify, Error};
+
+const SEED: [u8; 64] = [0; 64];
+const INDEX: u8 = 42;
+
+/// The golden file of the profile at `INDEX` of `SEED`, which must never
+/// change since third-party implementations are certified against it.
+const GOLDEN: &str = include_str!("vectors/zero-seed-42.txt");
+
+#[test]
+fn golden_vectors() {
+    assert_eq!(verify(GOLDEN, &SEED, INDEX), Ok(()));
+
+    let uncommented: String = GOLDEN
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    assert_eq!(to_golden(&generate(&SEED, INDEX)), uncommented);
+}
+
+#[test]
+fn test_vectors() {
+    let entries = generate(&SEED, INDEX);
+
+    // the vectors are deterministic
+    assert_eq!(entries, generate(&SEED, INDEX));
+    assert_ne!(entries, generate(&SEED, INDEX + 1));
+
+    let phoenix_pk = entries
+        .iter()
+        .find(|entry| entry.name == "phoenix_pk")
+        .expect("the vector should contain the phoenix public key");
+    assert_eq!(phoenix_pk.value, derive_phoenix_pk(&SEED, INDEX).to_bytes());
+
+    let owned = entries
+        .iter()
+        .find(|entry| entry.name == "note_owned")
+        .expect("the vector should contain the note ownership");
+    assert_eq!(owned.value, [1]);
+
+    let golden = to_golden(&entries);
+    assert_eq!(verify(&golden, &SEED, INDEX), Ok(()));
+
+    // comments and empty lines are ignored
+    let commented = format!("# profile {INDEX}\n\n{golden}");
+    assert_eq!(verify(&commented, &SEED, INDEX), Ok(()));
+
+    // a golden file of another profile doesn't verify
+    assert!(matches!(
+        verify(&golden, &SEED, INDEX + 1),
+        Err(Error::Mismatch {
+            name: "phoenix_sk",
+            ..
+        })
+    ));
+
+    let truncated: String =
+        golden.lines().take(3).collect::<Vec<_>>().join("\n");
+    assert_eq!(
+        verify(&truncated, &SEED, INDEX),
+        Err(Error::Missing("bls_sk"))
+    );
+
+    assert_eq!(verify("phoenix_sk", &SEED, INDEX), Err(Error::Malformed(1)));
+}